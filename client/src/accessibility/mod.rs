@@ -0,0 +1,3 @@
+mod screen_reader;
+
+pub use screen_reader::*;
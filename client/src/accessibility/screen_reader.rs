@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use std::process::{Child, Command, Stdio};
+
+/// Speaks announcements through the platform's speech command
+pub struct ScreenReader {
+    enabled: bool,
+    speech: RefCell<Option<Child>>,
+}
+
+impl ScreenReader {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            speech: RefCell::new(None),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+
+        if !enabled {
+            self.stop();
+        }
+    }
+
+    /// Interrupts the previous announcement
+    pub fn announce(&self, text: &str) {
+        if !self.enabled || text.is_empty() {
+            return;
+        }
+
+        self.stop();
+
+        let Some(mut command) = speech_command(text) else {
+            log::debug!("Screen reader: {text}");
+            return;
+        };
+
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        match command.spawn() {
+            Ok(child) => *self.speech.borrow_mut() = Some(child),
+            Err(e) => log::error!("Failed to announce {text:?}: {e}"),
+        }
+    }
+
+    fn stop(&self) {
+        if let Some(mut child) = self.speech.borrow_mut().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for ScreenReader {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn speech_command(text: &str) -> Option<Command> {
+    // spd-say returns once speech-dispatcher has the message,
+    // the message priority cancels the previous announcement instead
+    let mut command = Command::new("spd-say");
+    command.args(["--priority", "message", text]);
+    Some(command)
+}
+
+#[cfg(target_os = "macos")]
+fn speech_command(text: &str) -> Option<Command> {
+    let mut command = Command::new("say");
+    command.arg(text);
+    Some(command)
+}
+
+#[cfg(target_os = "windows")]
+fn speech_command(text: &str) -> Option<Command> {
+    use std::os::windows::process::CommandExt;
+
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    // passed through the environment to avoid quoting the text for powershell
+    let mut command = Command::new("powershell");
+    command
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName System.Speech; \
+            (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:HUBOS_ANNOUNCEMENT)",
+        ])
+        .env("HUBOS_ANNOUNCEMENT", text)
+        .creation_flags(CREATE_NO_WINDOW);
+    Some(command)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn speech_command(_text: &str) -> Option<Command> {
+    None
+}
@@ -6,4 +6,36 @@ pub struct Args {
     pub port: u16,
     #[clap(long, value_parser, default_value = "65536")]
     pub resend_budget: usize,
+    /// STUN server used to find more addresses for direct netplay connections, can be repeated
+    #[clap(long, value_parser)]
+    pub stun_server: Vec<String>,
+    /// Milliseconds of delay added to received packets, for testing netplay locally
+    #[clap(long, value_parser, default_value = "0")]
+    pub simulate_latency: u64,
+    /// Milliseconds of random variation added to the simulated latency
+    #[clap(long, value_parser, default_value = "0")]
+    pub simulate_jitter: u64,
+    /// Chance for a received packet to be dropped, from 0.0 to 1.0
+    #[clap(long, value_parser, default_value = "0")]
+    pub simulate_packet_loss: f32,
+    /// Log levels per target, such as "info,netplay=debug", targets are general, netplay, packages, lua, and render.
+    /// Overrides LogFilters in config.ini
+    #[clap(long, value_parser)]
+    pub log: Option<String>,
+    /// Save profile to use, created if it doesn't exist. Defaults to the last selected profile
+    #[clap(long, value_parser)]
+    pub profile: Option<String>,
+    /// Runs battle test fixtures and exits, can be repeated.
+    /// Battles still render to a window and need a GPU, use a virtual display such as xvfb-run on CI
+    #[clap(long, value_parser)]
+    pub test: Vec<String>,
+    /// Plays back a battle test fixture after packages are loaded, see F3 + T in battle
+    #[clap(long, value_parser)]
+    pub replay: Option<String>,
+    /// Creates a package with placeholder assets and exits, CATEGORY can be card, character, or encounter
+    #[clap(long, value_parser, num_args = 2, value_names = ["CATEGORY", "NAME"])]
+    pub new_package: Vec<String>,
+    /// Writes a JSON description and Lua stubs of the battle API to a folder and exits, run from a source checkout
+    #[clap(long, value_parser)]
+    pub lua_api_reference: Option<String>,
 }
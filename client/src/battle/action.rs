@@ -123,7 +123,7 @@ impl Action {
         let lua_api = &game_io.resource::<Globals>().unwrap().battle_api;
         let mut id: Option<GenerationalIndex> = None;
 
-        lua_api.inject_dynamic(lua, &api_ctx, |lua| {
+        lua_api.inject_dynamic(lua, &api_ctx, "card_init", |lua| {
             use rollback_mlua::IntoLua;
 
             // init card action
@@ -70,9 +70,11 @@ where
     P: for<'lua> rollback_mlua::IntoLuaMulti<'lua>,
     R: for<'lua> rollback_mlua::FromLuaMulti<'lua> + Default,
 {
+    /// `label` identifies the callback in the script profiler
     pub fn new_transformed_lua_callback<'lua, F>(
         lua: &'lua rollback_mlua::Lua,
         vm_index: usize,
+        label: &'static str,
         function: rollback_mlua::Function<'lua>,
         param_callback: F,
     ) -> rollback_mlua::Result<Self>
@@ -104,7 +106,7 @@ where
 
                 let mut result = Default::default();
 
-                lua_api.inject_dynamic(lua, &api_ctx, |lua| {
+                lua_api.inject_dynamic(lua, &api_ctx, label, |lua| {
                     let params = param_callback(&api_ctx, lua, params)?;
                     result = lua_callback.call(params)?;
 
@@ -119,9 +121,12 @@ where
     pub fn new_lua_callback<'lua>(
         lua: &'lua rollback_mlua::Lua,
         vm_index: usize,
+        label: &'static str,
         function: rollback_mlua::Function<'lua>,
     ) -> rollback_mlua::Result<Self> {
-        Self::new_transformed_lua_callback(lua, vm_index, function, |_, lua, p| lua.pack_multi(p))
+        Self::new_transformed_lua_callback(lua, vm_index, label, function, |_, lua, p| {
+            lua.pack_multi(p)
+        })
     }
 }
 
@@ -158,6 +163,6 @@ where
     ) -> rollback_mlua::Result<Self> {
         let function = rollback_mlua::Function::from_lua(lua_value, lua)?;
         let vm_index = lua.named_registry_value(VM_INDEX_REGISTRY_KEY)?;
-        Self::new_lua_callback(lua, vm_index, function)
+        Self::new_lua_callback(lua, vm_index, "callback", function)
     }
 }
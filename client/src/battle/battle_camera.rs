@@ -0,0 +1,209 @@
+use crate::bindable::{EaseMode, EntityId};
+use crate::render::{Camera, FrameTime};
+use framework::prelude::*;
+
+const DEFAULT_POSITION: Vec2 = Vec2::new(0.0, 8.0);
+
+// used to spread shake angles without relying on an external rng
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+/// Largest shake offset in pixels while flash reduction is enabled
+const REDUCED_SHAKE_LIMIT: f32 = 1.0;
+
+#[derive(Clone, Copy)]
+struct CameraTween {
+    elapsed: FrameTime,
+    duration: FrameTime,
+    ease: EaseMode,
+}
+
+impl CameraTween {
+    fn new(duration: FrameTime, ease: EaseMode) -> Self {
+        Self {
+            elapsed: 0,
+            duration: duration.max(0),
+            ease,
+        }
+    }
+
+    fn update(&mut self) {
+        if self.elapsed < self.duration {
+            self.elapsed += 1;
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    fn progress(&self) -> f32 {
+        if self.duration == 0 {
+            return 1.0;
+        }
+
+        self.ease.apply(self.elapsed as f32 / self.duration as f32)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum CameraTarget {
+    Offset(Vec2),
+    Entity(EntityId),
+}
+
+/// Camera state stored on the simulation, everything is tracked in frames to survive rollback
+#[derive(Clone)]
+pub struct BattleCamera {
+    target: CameraTarget,
+    start_position: Vec2,
+    last_target_position: Vec2,
+    position_tween: CameraTween,
+    position: Vec2,
+    /// Used by reset, lowered for wider fields
+    default_zoom: f32,
+    start_zoom: f32,
+    target_zoom: f32,
+    zoom_tween: CameraTween,
+    zoom: f32,
+    shake_stress: f32,
+    shake_tween: CameraTween,
+    shake_offset: Vec2,
+}
+
+impl BattleCamera {
+    pub fn new() -> Self {
+        Self {
+            target: CameraTarget::Offset(Vec2::ZERO),
+            start_position: DEFAULT_POSITION,
+            last_target_position: DEFAULT_POSITION,
+            position_tween: CameraTween::new(0, EaseMode::Linear),
+            position: DEFAULT_POSITION,
+            default_zoom: 1.0,
+            start_zoom: 1.0,
+            target_zoom: 1.0,
+            zoom_tween: CameraTween::new(0, EaseMode::Linear),
+            zoom: 1.0,
+            shake_stress: 0.0,
+            shake_tween: CameraTween::new(0, EaseMode::Linear),
+            shake_offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    pub fn focused_entity(&self) -> Option<EntityId> {
+        match self.target {
+            CameraTarget::Entity(id) => Some(id),
+            CameraTarget::Offset(_) => None,
+        }
+    }
+
+    /// Moves the camera to an offset from the default position, the offset is relative to the viewer's perspective
+    pub fn pan(&mut self, offset: Vec2, duration: FrameTime, ease: EaseMode) {
+        self.start_position = self.position;
+        self.target = CameraTarget::Offset(offset);
+        self.position_tween = CameraTween::new(duration, ease);
+    }
+
+    /// Moves the camera to an entity and follows it until another pan, focus, or reset
+    pub fn focus(&mut self, id: EntityId, duration: FrameTime, ease: EaseMode) {
+        self.start_position = self.position;
+        self.target = CameraTarget::Entity(id);
+        self.position_tween = CameraTween::new(duration, ease);
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32, duration: FrameTime, ease: EaseMode) {
+        self.start_zoom = self.zoom;
+        self.target_zoom = zoom.max(0.01);
+        self.zoom_tween = CameraTween::new(duration, ease);
+    }
+
+    /// Snaps to the zoom and returns to it on reset
+    pub fn set_default_zoom(&mut self, zoom: f32) {
+        self.default_zoom = zoom.max(0.01);
+        self.start_zoom = self.default_zoom;
+        self.target_zoom = self.default_zoom;
+        self.zoom_tween = CameraTween::new(0, EaseMode::Linear);
+        self.zoom = self.default_zoom;
+    }
+
+    pub fn shake(&mut self, stress: f32, duration: FrameTime) {
+        let remaining = self.shake_tween.duration - self.shake_tween.elapsed;
+
+        if !self.shake_tween.is_complete() && duration < remaining {
+            return;
+        }
+
+        self.shake_stress = stress;
+        self.shake_tween = CameraTween::new(duration, EaseMode::Linear);
+    }
+
+    pub fn reset(&mut self, duration: FrameTime, ease: EaseMode) {
+        self.pan(Vec2::ZERO, duration, ease);
+        self.set_zoom(self.default_zoom, duration, ease);
+    }
+
+    /// Should be called once per simulation frame
+    /// `resolve_entity` returns the screen position of an entity if it still exists
+    pub fn update(
+        &mut self,
+        perspective_flipped: bool,
+        resolve_entity: impl Fn(EntityId) -> Option<Vec2>,
+    ) {
+        self.position_tween.update();
+        self.zoom_tween.update();
+        self.shake_tween.update();
+
+        let target_position = match self.target {
+            CameraTarget::Offset(mut offset) => {
+                if perspective_flipped {
+                    offset.x = -offset.x;
+                }
+
+                Some(DEFAULT_POSITION + offset)
+            }
+            CameraTarget::Entity(id) => resolve_entity(id),
+        };
+
+        // hold the last known position if the entity was deleted
+        if let Some(target_position) = target_position {
+            self.last_target_position = target_position;
+        }
+
+        let progress = self.position_tween.progress();
+        self.position = self
+            .start_position
+            .lerp(self.last_target_position, progress);
+
+        let progress = self.zoom_tween.progress();
+        self.zoom = self.start_zoom + (self.target_zoom - self.start_zoom) * progress;
+
+        if self.shake_tween.is_complete() {
+            self.shake_offset = Vec2::ZERO;
+        } else {
+            // drop off to zero by the end of the shake
+            let stress = self.shake_stress * (1.0 - self.shake_tween.progress());
+            let angle = self.shake_tween.elapsed as f32 * GOLDEN_ANGLE;
+
+            self.shake_offset = Vec2::new(angle.sin() * stress, angle.cos() * stress);
+        }
+    }
+
+    /// `reduce_shake` limits the shake offset, the simulation is unaffected
+    pub fn apply(&self, camera: &mut Camera, reduce_shake: bool) {
+        let mut shake_offset = self.shake_offset;
+
+        if reduce_shake {
+            shake_offset = shake_offset.clamp_length_max(REDUCED_SHAKE_LIMIT);
+        }
+
+        camera.set_scale(Vec2::splat(self.zoom));
+        camera.snap(self.position + shake_offset);
+    }
+}
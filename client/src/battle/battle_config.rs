@@ -1,37 +1,136 @@
-use super::BattleInitMusic;
+use super::{BattleCallback, BattleInitMusic, EncounterMedalCriteria, Field, PlayerSetup};
+use crate::bindable::EntityId;
+use crate::render::FrameTime;
 use crate::resources::Globals;
+use packets::structures::{BattleRole, BattleTimers};
+use packets::MAX_NETPLAY_PLAYERS;
 
-const DEFAULT_PLAYER_LAYOUTS: [[(i32, i32); 4]; 4] = [
+// layouts are defined for the default 6x3 field, and shifted to fit other fields
+const DEFAULT_PLAYER_LAYOUTS: [[(i32, i32); MAX_NETPLAY_PLAYERS]; MAX_NETPLAY_PLAYERS] = [
     [(2, 2), (0, 0), (0, 0), (0, 0)],
     [(2, 2), (5, 2), (0, 0), (0, 0)],
     [(2, 2), (4, 3), (6, 1), (0, 0)],
     [(1, 3), (3, 1), (4, 3), (6, 1)],
 ];
 
+// every player on the red side
+const CO_OP_PLAYER_LAYOUTS: [[(i32, i32); MAX_NETPLAY_PLAYERS]; MAX_NETPLAY_PLAYERS] = [
+    [(2, 2), (0, 0), (0, 0), (0, 0)],
+    [(2, 1), (2, 3), (0, 0), (0, 0)],
+    [(2, 2), (1, 1), (1, 3), (0, 0)],
+    [(2, 1), (2, 3), (1, 2), (3, 2)],
+];
+
+const DEFAULT_RED_COLS: i32 = 3;
+const DEFAULT_ROWS: i32 = 3;
+
+/// Picks default spawn positions for a field with `cols` x `rows` tiles including the edges
+pub fn default_spawn_positions(
+    player_count: usize,
+    cols: usize,
+    rows: usize,
+    co_op: bool,
+) -> Vec<(i32, i32)> {
+    let spawn_count = player_count.min(MAX_NETPLAY_PLAYERS);
+
+    if spawn_count == 0 {
+        return Vec::new();
+    }
+
+    let layouts = if co_op {
+        &CO_OP_PLAYER_LAYOUTS
+    } else {
+        &DEFAULT_PLAYER_LAYOUTS
+    };
+
+    // keep the same distance from the middle of the field, red owns the left half
+    let red_cols = cols as i32 / 2 - 1;
+    let x_shift = red_cols - DEFAULT_RED_COLS;
+    let y_shift = (rows as i32 - 2 - DEFAULT_ROWS) / 2;
+
+    let max_x = (cols as i32 - 2).max(1);
+    let max_y = (rows as i32 - 2).max(1);
+
+    layouts[spawn_count - 1][..spawn_count]
+        .iter()
+        .map(|&(x, y)| ((x + x_shift).clamp(1, max_x), (y + y_shift).clamp(1, max_y)))
+        .collect()
+}
+
+/// The boss takes the middle of the blue side, every other player spawns together on the red side
+pub fn boss_spawn_positions(
+    player_count: usize,
+    cols: usize,
+    rows: usize,
+    boss_index: usize,
+) -> Vec<(i32, i32)> {
+    let spawn_count = player_count.min(MAX_NETPLAY_PLAYERS);
+
+    if boss_index >= spawn_count {
+        return default_spawn_positions(spawn_count, cols, rows, true);
+    }
+
+    let mut positions = default_spawn_positions(spawn_count - 1, cols, rows, true);
+
+    let red_cols = cols as i32 / 2 - 1;
+    let boss_x = (red_cols + 1 + cols as i32 - 2) / 2;
+    let boss_y = (rows as i32 - 1) / 2;
+    positions.insert(boss_index, (boss_x, boss_y));
+
+    positions
+}
+
 #[derive(Clone)]
 pub struct BattleConfig {
     pub player_spawn_positions: Vec<(i32, i32)>,
+    /// Places every player on the same side by default, used for raids
+    pub co_op: bool,
+    /// The player controlling the encounter's boss
+    pub boss_index: Option<usize>,
+    /// Called with the boss player's id after players load, sets up the boss's controls
+    pub boss_callback: Option<BattleCallback<EntityId>>,
     pub player_flippable: Vec<Option<bool>>,
     pub turn_limit: Option<u32>,
     pub automatic_turn_end: bool,
+    pub timers: BattleTimers,
+    pub card_select_time_limit: Option<FrameTime>,
     // todo:
     // pub status_durations: [FrameTime; 3],
     // pub intangibility_duration: FrameTime,
     // pub super_effective_multiplier: f32,
     pub battle_init_music: Option<BattleInitMusic>,
+    /// Replaces the built in intro, called with (elapsed_time, skipping) until it returns true
+    pub intro_callback: Option<BattleCallback<(FrameTime, bool), bool>>,
+    /// Played after the win / lose message, called with (elapsed_time, skipping, success) until it returns true
+    pub results_callback: Option<BattleCallback<(FrameTime, bool, bool), bool>>,
+    /// Medals declared by the encounter package, evaluated when the local player wins
+    pub medal_criteria: EncounterMedalCriteria,
 }
 
 impl BattleConfig {
-    pub fn new(globals: &Globals, player_count: usize) -> Self {
-        let spawn_count = player_count.min(4);
-        let mut player_spawn_positions = DEFAULT_PLAYER_LAYOUTS[spawn_count - 1].to_vec();
-        player_spawn_positions.resize(spawn_count, (0, 0));
+    pub fn new(globals: &Globals, player_setups: &[PlayerSetup]) -> Self {
+        let spawn_count = player_setups.len().min(MAX_NETPLAY_PLAYERS);
+
+        let boss_index = (player_setups.iter())
+            .find(|setup| setup.role == BattleRole::Boss)
+            .map(|setup| setup.index)
+            .filter(|index| *index < spawn_count);
+
+        let player_spawn_positions = match boss_index {
+            Some(index) => boss_spawn_positions(spawn_count, 8, 5, index),
+            None => default_spawn_positions(spawn_count, 8, 5, false),
+        };
 
         Self {
             player_spawn_positions,
+            co_op: false,
+            boss_index,
+            boss_callback: None,
             player_flippable: vec![None; spawn_count],
             turn_limit: None,
             automatic_turn_end: false,
+            timers: Self::negotiate_timers(player_setups),
+            card_select_time_limit: None,
             // status_durations: [90, 120, 150],
             // intangibility_duration: 120,
             // super_effective_multiplier: 2.0,
@@ -40,6 +139,67 @@ impl BattleConfig {
                 buffer: globals.music.battle.clone(),
                 loops: true,
             }),
+            intro_callback: None,
+            results_callback: None,
+            medal_criteria: EncounterMedalCriteria::default(),
+        }
+    }
+
+    /// Resets spawn positions to the default layout for the field, overwriting positions from spawn_player
+    pub fn reset_player_spawn_positions(&mut self, field: &Field) {
+        let spawn_count = self.player_spawn_positions.len();
+        let (cols, rows) = (field.cols(), field.rows());
+
+        self.player_spawn_positions = match self.boss_index {
+            Some(index) => boss_spawn_positions(spawn_count, cols, rows, index),
+            None => default_spawn_positions(spawn_count, cols, rows, self.co_op),
+        };
+    }
+
+    fn negotiate_timers(player_setups: &[PlayerSetup]) -> BattleTimers {
+        if player_setups.iter().all(|setup| setup.local) {
+            // stall prevention is only for netplay
+            return BattleTimers::default();
         }
+
+        BattleTimers::negotiate(player_setups.iter().map(|setup| &setup.timers))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_field_matches_layouts() {
+        assert_eq!(default_spawn_positions(2, 8, 5, false), [(2, 2), (5, 2)]);
+        assert_eq!(
+            default_spawn_positions(4, 8, 5, true),
+            [(2, 1), (2, 3), (1, 2), (3, 2)]
+        );
+        assert!(default_spawn_positions(0, 8, 5, false).is_empty());
+        assert_eq!(default_spawn_positions(6, 8, 5, false).len(), 4);
+    }
+
+    #[test]
+    fn wider_fields_shift_from_the_middle() {
+        // 8x3 field, red owns the first four columns
+        assert_eq!(
+            default_spawn_positions(3, 10, 5, true),
+            [(3, 2), (2, 1), (2, 3)]
+        );
+        assert_eq!(default_spawn_positions(2, 10, 5, false), [(3, 2), (6, 2)]);
+
+        // narrow fields clamp into bounds
+        assert_eq!(default_spawn_positions(4, 6, 5, false)[0], (1, 3));
+    }
+
+    #[test]
+    fn bosses_face_the_team() {
+        assert_eq!(boss_spawn_positions(3, 8, 5, 1), [(2, 1), (5, 2), (2, 3)]);
+        assert_eq!(boss_spawn_positions(2, 10, 5, 0), [(6, 2), (3, 2)]);
+
+        // out of range bosses are ignored
+        assert_eq!(boss_spawn_positions(2, 8, 5, 2), [(2, 1), (2, 3)]);
     }
 }
@@ -0,0 +1,212 @@
+use crate::render::FrameTime;
+use crate::resources::ResourcePaths;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone)]
+pub enum BattleLogEvent {
+    CardUsed {
+        user: String,
+        card: String,
+        code: String,
+        damage: i32,
+    },
+    Hit {
+        target: String,
+        aggressor: Option<String>,
+        damage: i32,
+    },
+    HealthChanged {
+        target: String,
+        damage: i32,
+        recovered: i32,
+        health: i32,
+    },
+    StatusApplied {
+        target: String,
+        status: String,
+        duration: FrameTime,
+    },
+    Deleted {
+        target: String,
+    },
+    PhaseChanged {
+        target: String,
+        phase: usize,
+    },
+}
+
+impl std::fmt::Display for BattleLogEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BattleLogEvent::CardUsed {
+                user,
+                card,
+                code,
+                damage,
+            } => {
+                write!(f, "{user} used {card}")?;
+
+                if !code.is_empty() {
+                    write!(f, " {code}")?;
+                }
+
+                if *damage != 0 {
+                    write!(f, " ({damage} damage)")?;
+                }
+
+                Ok(())
+            }
+            BattleLogEvent::Hit {
+                target,
+                aggressor,
+                damage,
+            } => {
+                write!(f, "{target} was hit for {damage} damage")?;
+
+                if let Some(aggressor) = aggressor {
+                    write!(f, " by {aggressor}")?;
+                }
+
+                Ok(())
+            }
+            BattleLogEvent::HealthChanged {
+                target,
+                damage,
+                recovered,
+                health,
+            } => {
+                write!(f, "{target} took {damage} damage")?;
+
+                if *recovered != 0 {
+                    write!(f, ", recovered {recovered}")?;
+                }
+
+                write!(f, " ({health} HP)")
+            }
+            BattleLogEvent::StatusApplied {
+                target,
+                status,
+                duration,
+            } => write!(f, "{target} received {status} for {duration} frames"),
+            BattleLogEvent::Deleted { target } => write!(f, "{target} was deleted"),
+            BattleLogEvent::PhaseChanged { target, phase } => {
+                write!(f, "{target} entered phase {phase}")
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BattleLogEntry {
+    pub time: FrameTime,
+    pub event: BattleLogEvent,
+}
+
+/// Entries are shared between simulation backups to avoid copying the log every frame,
+/// each copy tracks its own length so resimulating overwrites entries from an abandoned timeline
+#[derive(Clone, Default)]
+pub struct BattleLog {
+    entries: Arc<Mutex<Vec<BattleLogEntry>>>,
+    len: usize,
+}
+
+impl BattleLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, time: FrameTime, event: BattleLogEvent) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.truncate(self.len);
+        entries.push(BattleLogEntry { time, event });
+
+        self.len += 1;
+    }
+
+    pub fn entries(&self) -> Vec<BattleLogEntry> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().take(self.len).cloned().collect()
+    }
+
+    pub fn export(&self) -> String {
+        let mut text = format!("Hub OS {} Battle Log\n\n", env!("CARGO_PKG_VERSION"));
+
+        for entry in self.entries() {
+            let _ = writeln!(text, "[{:>6}] {}", entry.time, entry.event);
+        }
+
+        text
+    }
+
+    /// Writes the log to the battle log folder on a background thread,
+    /// a file name is generated when one isn't provided
+    pub fn save(&self, file_name: Option<&str>) {
+        let text = self.export();
+
+        let file_name = match file_name {
+            Some(file_name) => file_name.to_string(),
+            None => {
+                let elapsed_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+                format!("{}.txt", elapsed_time.as_secs())
+            }
+        };
+
+        std::thread::spawn(move || {
+            let folder_path =
+                ResourcePaths::game_folder().to_string() + ResourcePaths::BATTLE_LOG_FOLDER;
+
+            // create parent folder
+            let _ = std::fs::create_dir_all(&folder_path);
+
+            let path = folder_path + &file_name;
+
+            if let Err(e) = std::fs::write(&path, text) {
+                log::error!("Failed to save battle log to {:?}: {}", path, e);
+                return;
+            }
+
+            log::info!("Saved battle log to {path}");
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn deleted(target: &str) -> BattleLogEvent {
+        BattleLogEvent::Deleted {
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn rollback_overwrites_abandoned_entries() {
+        let mut log = BattleLog::new();
+        log.push(0, deleted("a"));
+
+        let backup = log.clone();
+        log.push(1, deleted("b"));
+        log.push(2, deleted("c"));
+        assert_eq!(log.len(), 3);
+
+        // roll back and resimulate
+        let mut log = backup;
+        log.push(1, deleted("d"));
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].time, 1);
+        assert_eq!(entries[1].event.to_string(), "d was deleted");
+    }
+}
@@ -1,13 +1,18 @@
+use super::NetplayInputReceiver;
 use crate::packages::*;
 use crate::render::*;
 use crate::resources::*;
 use crate::saves::BattleRecording;
 use crate::saves::BlockGrid;
+use crate::saves::Config;
 use crate::saves::Deck;
+use crate::saves::Loadout;
 use crate::saves::PlayerInputBuffer;
 use framework::prelude::*;
 use packets::structures::InstalledSwitchDrive;
-use packets::structures::{BattleStatistics, Emotion, InstalledBlock};
+use packets::structures::{
+    BattleRole, BattleStatistics, BattleTimers, Emotion, EncounterData, InstalledBlock,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -23,6 +28,16 @@ pub struct PlayerSetup {
     pub index: usize,
     pub local: bool,
     pub buffer: PlayerInputBuffer,
+    #[serde(default)]
+    pub timers: BattleTimers,
+    #[serde(default)]
+    pub role: BattleRole,
+    /// Inputs received during netplay setup that are waiting on earlier inputs
+    #[serde(skip)]
+    pub input_receiver: NetplayInputReceiver,
+    /// Bindings for another player on this machine, their input skips the network
+    #[serde(skip)]
+    pub local_input: Option<Box<Config>>,
 }
 
 impl PlayerSetup {
@@ -39,6 +54,10 @@ impl PlayerSetup {
             index,
             local,
             buffer: PlayerInputBuffer::default(),
+            timers: BattleTimers::default(),
+            role: BattleRole::Player,
+            input_receiver: NetplayInputReceiver::default(),
+            local_input: None,
         }
     }
 
@@ -61,11 +80,20 @@ impl PlayerSetup {
 
     pub fn from_globals(game_io: &GameIO) -> Self {
         let globals = game_io.resource::<Globals>().unwrap();
+
+        Self::from_loadout(game_io, &Loadout::current(&globals.global_save))
+    }
+
+    /// Builds the local player from a loadout without equipping it
+    pub fn from_loadout(game_io: &GameIO, loadout: &Loadout) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
         let global_save = &globals.global_save;
         let restrictions = &globals.restrictions;
         let mut deck_restrictions = restrictions.base_deck_restrictions();
 
-        let player_package = global_save.player_package(game_io).unwrap();
+        let player_package = (globals.player_packages)
+            .package(PackageNamespace::Local, &loadout.character)
+            .unwrap();
         let player_package_info = &player_package.package_info;
 
         let script_enabled = restrictions.owns_player(&player_package_info.id)
@@ -75,11 +103,11 @@ impl PlayerSetup {
 
         // blocks
         let blocks: Vec<_> = restrictions
-            .filter_blocks(game_io, ns, global_save.active_blocks().iter())
+            .filter_blocks(game_io, ns, loadout.blocks.iter())
             .cloned()
             .collect();
 
-        let drives = global_save.active_drive_parts().to_vec();
+        let drives = loadout.drive_parts.clone();
 
         let grid = BlockGrid::new(ns).with_blocks(game_io, blocks.clone());
 
@@ -93,7 +121,7 @@ impl PlayerSetup {
 
         // TODO: Filter Switch Drive restrictions to deck as well.
 
-        let mut deck = global_save.active_deck().cloned().unwrap_or_default();
+        let mut deck = loadout.deck(global_save).cloned().unwrap_or_default();
         deck.conform(game_io, PackageNamespace::Local, &deck_restrictions);
 
         Self {
@@ -108,6 +136,10 @@ impl PlayerSetup {
             drives,
             local: true,
             buffer: PlayerInputBuffer::new_with_delay(INPUT_DELAY),
+            timers: globals.config.battle_timers(),
+            role: BattleRole::Player,
+            input_receiver: NetplayInputReceiver::default(),
+            local_input: None,
         }
     }
 
@@ -124,7 +156,7 @@ pub type BattleStatisticsCallback = Box<dyn FnOnce(Option<BattleStatistics>)>;
 
 pub struct BattleProps {
     pub encounter_package_pair: Option<(PackageNamespace, PackageId)>,
-    pub data: Option<String>,
+    pub data: Option<EncounterData>,
     pub seed: u64,
     pub background: Background,
     pub player_setups: Vec<PlayerSetup>,
@@ -132,9 +164,44 @@ pub struct BattleProps {
     pub receivers: Vec<(Option<usize>, NetplayPacketReceiver)>,
     pub statistics_callback: Option<BattleStatisticsCallback>,
     pub recording_enabled: bool,
+    /// Replays the input in the player setup buffers instead of reading local input
+    pub playback: bool,
+    /// Waits for remote inputs instead of predicting and rolling back
+    pub lockstep: bool,
 }
 
 impl BattleProps {
+    /// Versus on one machine, the second player uses a gamepad other than the active one
+    pub fn new_local_versus(game_io: &GameIO) -> Option<Self> {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let config = &globals.config;
+
+        let controller = (game_io.input().controllers().iter())
+            .find(|controller| controller.id() != config.controller_index)?;
+
+        // the keyboard stays with the first player
+        let mut input_config = config.clone();
+        input_config.key_bindings.clear();
+        input_config.controller_index = controller.id();
+
+        let guid = controller.guid().to_string();
+
+        if let Some(index) = config.input_profile_for_device(&guid) {
+            let profile = &config.input_profiles[index];
+            input_config.controller_bindings = profile.controller_bindings.clone();
+        }
+
+        let mut second_setup = PlayerSetup::from_globals(game_io);
+        second_setup.index = 1;
+        second_setup.local = false;
+        second_setup.local_input = Some(Box::new(input_config));
+
+        let mut props = Self::new_with_defaults(game_io, None);
+        props.player_setups.push(second_setup);
+
+        Some(props)
+    }
+
     pub fn new_with_defaults(
         game_io: &GameIO,
         encounter_package_pair: Option<(PackageNamespace, PackageId)>,
@@ -151,6 +218,17 @@ impl BattleProps {
             receivers: Vec::new(),
             statistics_callback: None,
             recording_enabled: true,
+            playback: false,
+            lockstep: false,
+        }
+    }
+
+    /// Frames between reading local input and simulating it
+    pub fn input_delay(&self) -> usize {
+        if self.lockstep {
+            LOCKSTEP_INPUT_DELAY
+        } else {
+            INPUT_DELAY
         }
     }
 
@@ -172,6 +250,8 @@ impl BattleProps {
             receivers: Vec::new(),
             statistics_callback: None,
             recording_enabled: false,
+            playback: true,
+            lockstep: false,
         }
     }
 
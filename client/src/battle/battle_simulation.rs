@@ -1,26 +1,30 @@
 use super::*;
 use crate::bindable::*;
-use crate::packages::{CardPackage, PackageNamespace};
-use crate::render::ui::{FontName, PlayerHealthUi, Text};
+use crate::packages::{CardPackage, PackageId, PackageNamespace};
+use crate::render::ui::{BossHealthUi, FontName, PlayerHealthUi, Text};
 use crate::render::*;
 use crate::resources::*;
 use crate::scenes::BattleEvent;
 use crate::structures::{DenseSlotMap, SlotMap};
 use framework::prelude::*;
-use packets::structures::{BattleStatistics, BattleSurvivor};
+use packets::structures::{BattleStatistics, BattleSurvivor, FileHash};
 use packets::NetplaySignal;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
 use std::cell::RefCell;
 
 pub struct BattleSimulation {
     pub config: BattleConfig,
     pub statistics: BattleStatistics,
+    pub battle_log: BattleLog,
     pub rng: Xoshiro256PlusPlus,
+    /// Seeds entity rng streams along with their id, see `Entity::create`
+    pub seed: u64,
     pub inputs: Vec<PlayerInput>,
     pub time: FrameTime,
     pub battle_time: FrameTime,
     pub camera: Camera,
+    pub battle_camera: BattleCamera,
     pub background: Background,
     pub fade_sprite: Sprite,
     pub turn_gauge: TurnGauge,
@@ -36,14 +40,21 @@ pub struct BattleSimulation {
     pub actions: DenseSlotMap<Action>,
     pub time_freeze_tracker: TimeFreezeTracker,
     pub components: DenseSlotMap<Component>,
+    pub particle_emitters: DenseSlotMap<ParticleEmitter>,
     pub pending_callbacks: Vec<BattleCallback>,
     pub local_player_id: EntityId,
     pub local_health_ui: PlayerHealthUi,
+    pub boss_bar_entity: Option<EntityId>,
+    pub boss_health_ui: BossHealthUi,
     pub local_team: Team,
     pub music_stack_depth: usize,
     pub battle_started: bool,
     pub intro_complete: bool,
     pub is_resimulation: bool,
+    /// None until the local player wins
+    pub earned_medals: Option<Vec<EncounterMedal>>,
+    /// (library package id, achievement id), recorded when the battle ends
+    pub unlocked_achievements: Vec<(PackageId, String)>,
     pub exit: bool,
 }
 
@@ -59,13 +70,16 @@ impl BattleSimulation {
         fade_sprite.set_color(Color::TRANSPARENT);
 
         Self {
-            config: BattleConfig::new(globals, props.player_setups.len()),
+            config: BattleConfig::new(globals, &props.player_setups),
             statistics: BattleStatistics::new(),
+            battle_log: BattleLog::new(),
             rng: Xoshiro256PlusPlus::seed_from_u64(props.seed),
+            seed: props.seed,
             time: 0,
             battle_time: 0,
             inputs: vec![PlayerInput::new(); props.player_setups.len()],
             camera,
+            battle_camera: BattleCamera::new(),
             background: props.background.clone(),
             fade_sprite,
             turn_gauge: TurnGauge::new(game_io),
@@ -81,23 +95,164 @@ impl BattleSimulation {
             actions: Default::default(),
             time_freeze_tracker: TimeFreezeTracker::new(),
             components: Default::default(),
+            particle_emitters: Default::default(),
             pending_callbacks: Vec::new(),
             local_player_id: EntityId::DANGLING,
             local_health_ui: PlayerHealthUi::new(game_io),
+            boss_bar_entity: None,
+            boss_health_ui: BossHealthUi::new(game_io),
             local_team: Team::Unset,
             music_stack_depth: globals.audio.music_stack_len() + 1,
             battle_started: false,
             intro_complete: false,
             is_resimulation: false,
+            earned_medals: None,
+            unlocked_achievements: Vec::new(),
             exit: false,
         }
     }
 
     pub fn seed_random(&mut self, seed: u64) {
         self.rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        self.seed = seed;
     }
 
     pub fn clone(&mut self, game_io: &GameIO) -> Self {
+        let entities = self.clone_entities();
+
+        Self {
+            config: self.config.clone(),
+            statistics: self.statistics.clone(),
+            battle_log: self.battle_log.clone(),
+            inputs: self.inputs.clone(),
+            rng: self.rng.clone(),
+            seed: self.seed,
+            time: self.time,
+            battle_time: self.battle_time,
+            camera: self.camera.clone(game_io),
+            battle_camera: self.battle_camera.clone(),
+            background: self.background.clone(),
+            fade_sprite: self.fade_sprite.clone(),
+            turn_gauge: self.turn_gauge.clone(),
+            field: self.field.clone(),
+            tile_states: self.tile_states.clone(),
+            entities,
+            generation_tracking: self.generation_tracking.clone(),
+            ownership_tracking: self.ownership_tracking.clone(),
+            queued_attacks: self.queued_attacks.clone(),
+            defense_judge: self.defense_judge,
+            sprite_trees: self.sprite_trees.clone(),
+            animators: self.animators.clone(),
+            actions: self.actions.clone(),
+            time_freeze_tracker: self.time_freeze_tracker.clone(),
+            components: self.components.clone(),
+            particle_emitters: self.particle_emitters.clone(),
+            pending_callbacks: self.pending_callbacks.clone(),
+            local_player_id: self.local_player_id,
+            local_health_ui: self.local_health_ui.clone(),
+            boss_bar_entity: self.boss_bar_entity,
+            boss_health_ui: self.boss_health_ui.clone(),
+            local_team: self.local_team,
+            music_stack_depth: self.music_stack_depth,
+            battle_started: self.battle_started,
+            intro_complete: self.intro_complete,
+            is_resimulation: self.is_resimulation,
+            earned_medals: self.earned_medals.clone(),
+            unlocked_achievements: self.unlocked_achievements.clone(),
+            exit: self.exit,
+        }
+    }
+
+    /// Same as clone, but reuses the allocations held by `target`
+    pub fn clone_into(&mut self, game_io: &GameIO, target: &mut Self) {
+        // a fresh world is still required, see clone_entities
+        target.entities = self.clone_entities();
+
+        let Self {
+            config,
+            statistics,
+            battle_log,
+            rng,
+            seed,
+            inputs,
+            time,
+            battle_time,
+            camera,
+            battle_camera,
+            background,
+            fade_sprite,
+            turn_gauge,
+            field,
+            tile_states,
+            entities: _,
+            generation_tracking,
+            ownership_tracking,
+            queued_attacks,
+            defense_judge,
+            sprite_trees,
+            animators,
+            actions,
+            time_freeze_tracker,
+            components,
+            particle_emitters,
+            pending_callbacks,
+            local_player_id,
+            local_health_ui,
+            boss_bar_entity,
+            boss_health_ui,
+            local_team,
+            music_stack_depth,
+            battle_started,
+            intro_complete,
+            is_resimulation,
+            earned_medals,
+            unlocked_achievements,
+            exit,
+        } = target;
+
+        config.clone_from(&self.config);
+        statistics.clone_from(&self.statistics);
+        battle_log.clone_from(&self.battle_log);
+        rng.clone_from(&self.rng);
+        *seed = self.seed;
+        inputs.clone_from(&self.inputs);
+        *time = self.time;
+        *battle_time = self.battle_time;
+        *camera = self.camera.clone(game_io);
+        battle_camera.clone_from(&self.battle_camera);
+        background.clone_from(&self.background);
+        fade_sprite.clone_from(&self.fade_sprite);
+        turn_gauge.clone_from(&self.turn_gauge);
+        field.clone_from(&self.field);
+        tile_states.clone_from(&self.tile_states);
+        generation_tracking.clone_from(&self.generation_tracking);
+        ownership_tracking.clone_from(&self.ownership_tracking);
+        queued_attacks.clone_from(&self.queued_attacks);
+        *defense_judge = self.defense_judge;
+        sprite_trees.clone_from(&self.sprite_trees);
+        animators.clone_from(&self.animators);
+        actions.clone_from(&self.actions);
+        time_freeze_tracker.clone_from(&self.time_freeze_tracker);
+        components.clone_from(&self.components);
+        particle_emitters.clone_from(&self.particle_emitters);
+        pending_callbacks.clone_from(&self.pending_callbacks);
+        *local_player_id = self.local_player_id;
+        local_health_ui.clone_from(&self.local_health_ui);
+        *boss_bar_entity = self.boss_bar_entity;
+        boss_health_ui.clone_from(&self.boss_health_ui);
+        *local_team = self.local_team;
+        *music_stack_depth = self.music_stack_depth;
+        *battle_started = self.battle_started;
+        *intro_complete = self.intro_complete;
+        *is_resimulation = self.is_resimulation;
+        earned_medals.clone_from(&self.earned_medals);
+        unlocked_achievements.clone_from(&self.unlocked_achievements);
+        *exit = self.exit;
+    }
+
+    /// Always creates a new world, reusing a world would carry over its archetype order,
+    /// which differs between clients and would affect iteration order
+    fn clone_entities(&mut self) -> hecs::World {
         let mut entities = hecs::World::new();
 
         // spawn + remove blank entities to restore generations on dead entities
@@ -133,39 +288,7 @@ impl BattleSimulation {
         clone_component!(EntityShadow);
         clone_component!(EntityShadowVisible);
 
-        Self {
-            config: self.config.clone(),
-            statistics: self.statistics.clone(),
-            inputs: self.inputs.clone(),
-            rng: self.rng.clone(),
-            time: self.time,
-            battle_time: self.battle_time,
-            camera: self.camera.clone(game_io),
-            background: self.background.clone(),
-            fade_sprite: self.fade_sprite.clone(),
-            turn_gauge: self.turn_gauge.clone(),
-            field: self.field.clone(),
-            tile_states: self.tile_states.clone(),
-            entities,
-            generation_tracking: self.generation_tracking.clone(),
-            ownership_tracking: self.ownership_tracking.clone(),
-            queued_attacks: self.queued_attacks.clone(),
-            defense_judge: self.defense_judge,
-            sprite_trees: self.sprite_trees.clone(),
-            animators: self.animators.clone(),
-            actions: self.actions.clone(),
-            time_freeze_tracker: self.time_freeze_tracker.clone(),
-            components: self.components.clone(),
-            pending_callbacks: self.pending_callbacks.clone(),
-            local_player_id: self.local_player_id,
-            local_health_ui: self.local_health_ui.clone(),
-            local_team: self.local_team,
-            music_stack_depth: self.music_stack_depth,
-            battle_started: self.battle_started,
-            intro_complete: self.intro_complete,
-            is_resimulation: self.is_resimulation,
-            exit: self.exit,
-        }
+        entities
     }
 
     pub fn initialize_uninitialized(&mut self) {
@@ -181,14 +304,61 @@ impl BattleSimulation {
         }
     }
 
+    /// Plays the sound panned towards the entity
+    pub fn play_sound_from(&self, game_io: &GameIO, sound_buffer: &SoundBuffer, id: EntityId) {
+        if !self.is_resimulation {
+            let globals = game_io.resource::<Globals>().unwrap();
+            let placement = self.audio_placement(id);
+            globals.audio.play_sound_at(sound_buffer, placement);
+        }
+    }
+
+    /// None if the entity isn't on the field
+    pub fn audio_placement(&self, id: EntityId) -> Option<AudioPlacement> {
+        let entity = self.entities.get::<&Entity>(id.into()).ok()?;
+
+        if !entity.on_field {
+            return None;
+        }
+
+        let perspective_flipped = self.local_team.flips_perspective();
+        let mut position = entity.screen_position(&self.field, perspective_flipped);
+        position.y -= entity.elevation;
+
+        let camera = &self.battle_camera;
+        let offset = (position - camera.position()) * camera.zoom();
+
+        Some(AudioPlacement::from_offset(offset, RESOLUTION_F * 0.5))
+    }
+
     pub fn play_music(&self, game_io: &GameIO, sound_buffer: &SoundBuffer, loops: bool) {
+        self.crossfade_music(game_io, sound_buffer, loops, Duration::ZERO);
+    }
+
+    pub fn crossfade_music(
+        &self,
+        game_io: &GameIO,
+        sound_buffer: &SoundBuffer,
+        loops: bool,
+        duration: Duration,
+    ) {
         let globals = game_io.resource::<Globals>().unwrap();
 
         if globals.audio.music_stack_len() != self.music_stack_depth {
             return;
         }
 
-        globals.audio.play_music(sound_buffer, loops);
+        globals.audio.crossfade_music(sound_buffer, loops, duration);
+    }
+
+    pub fn play_stinger(&self, game_io: &GameIO, sound_buffer: &SoundBuffer) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.is_resimulation || globals.audio.music_stack_len() != self.music_stack_depth {
+            return;
+        }
+
+        globals.audio.play_stinger(sound_buffer);
     }
 
     pub fn wrap_up_statistics(&mut self) {
@@ -227,6 +397,38 @@ impl BattleSimulation {
         self.statistics.calculate_score();
     }
 
+    /// Summarizes the state shared between netplay clients, used to catch desyncs and tampering
+    pub fn state_checksum(&self) -> u64 {
+        let mut entities: Vec<_> = self
+            .entities
+            .query::<(&Entity, Option<&Living>)>()
+            .iter()
+            .map(|(_, (entity, living))| {
+                let id = hecs::Entity::from(entity.id).to_bits().get();
+                let health = living.map(|living| living.health).unwrap_or_default();
+
+                (id, entity.x, entity.y, health)
+            })
+            .collect();
+
+        // query order isn't guaranteed to match
+        entities.sort_unstable();
+
+        let mut bytes = Vec::new();
+        bytes.extend(self.time.to_le_bytes());
+        bytes.extend(self.rng.clone().next_u64().to_le_bytes());
+
+        for (id, x, y, health) in entities {
+            bytes.extend(id.to_le_bytes());
+            bytes.extend(x.to_le_bytes());
+            bytes.extend(y.to_le_bytes());
+            bytes.extend(health.to_le_bytes());
+        }
+
+        let hash = FileHash::hash(&bytes);
+        u64::from_le_bytes(hash.as_bytes()[..8].try_into().unwrap())
+    }
+
     pub fn handle_local_signals(&mut self, local_index: usize, resources: &SharedBattleResources) {
         let input = &self.inputs[local_index];
 
@@ -289,6 +491,10 @@ impl BattleSimulation {
 
         self.field.update_animations();
 
+        self.update_particle_emitters();
+
+        self.update_camera();
+
         self.time += 1;
     }
 
@@ -527,6 +733,37 @@ impl BattleSimulation {
         }
     }
 
+    fn update_particle_emitters(&mut self) {
+        let entities = &self.entities;
+        let field = &self.field;
+
+        for (_, emitter) in &mut self.particle_emitters {
+            let entity = entities.get::<&Entity>(emitter.entity.into()).ok();
+            emitter.update(entity.as_deref(), field);
+        }
+
+        (self.particle_emitters).retain(|_, emitter| !emitter.is_complete());
+    }
+
+    fn update_camera(&mut self) {
+        let perspective_flipped = self.local_team.flips_perspective();
+        let entities = &self.entities;
+        let field = &self.field;
+
+        self.battle_camera.update(perspective_flipped, |id| {
+            let entity = entities.get::<&Entity>(id.into()).ok()?;
+
+            if !entity.on_field {
+                return None;
+            }
+
+            let mut position = entity.screen_position(field, perspective_flipped);
+            position.y -= entity.elevation;
+
+            Some(position)
+        });
+    }
+
     fn update_ui(&mut self) {
         let entities = &mut self.entities;
 
@@ -541,6 +778,26 @@ impl BattleSimulation {
         }
 
         self.local_health_ui.update();
+
+        let Some(id) = self.boss_bar_entity else {
+            return;
+        };
+
+        if let Ok((entity, character, living)) =
+            entities.query_one_mut::<(&Entity, &Character, &Living)>(id.into())
+        {
+            if entity.name != self.boss_health_ui.name() {
+                self.boss_health_ui.set_name(entity.name.clone());
+            }
+
+            (self.boss_health_ui).set_health(living.health, living.max_health);
+            (self.boss_health_ui).set_phase(character.phase_index, character.phase_count());
+        } else {
+            let max_health = self.boss_health_ui.max_health();
+            self.boss_health_ui.set_health(0, max_health);
+        }
+
+        self.boss_health_ui.update();
     }
 
     pub fn is_entity_actionable(
@@ -610,7 +867,7 @@ impl BattleSimulation {
 
         let lua_api = &game_io.resource::<Globals>().unwrap().battle_api;
 
-        lua_api.inject_dynamic(lua, &api_ctx, move |lua| {
+        lua_api.inject_dynamic(lua, &api_ctx, fn_name, move |lua| {
             let params = param_generator(lua)?;
             global_fn.call(params)
         });
@@ -621,6 +878,7 @@ impl BattleSimulation {
     pub fn draw(
         &mut self,
         game_io: &GameIO,
+        resources: &SharedBattleResources,
         render_pass: &mut RenderPass,
         draw_player_indices: bool,
     ) {
@@ -640,20 +898,32 @@ impl BattleSimulation {
         // store whether the player's team flips the perspective into a shorter variable
         let perspective_flipped = self.local_team.flips_perspective();
 
-        let assets = &game_io.resource::<Globals>().unwrap().assets;
+        let globals = game_io.resource::<Globals>().unwrap();
+        let assets = &globals.assets;
+
+        // apply script controlled camera movement
+        let reduce_shake = globals.config.flash_reduction;
+        self.battle_camera.apply(&mut self.camera, reduce_shake);
 
         // draw background
         self.background.draw(game_io, render_pass);
 
         let mut sprite_queue =
             SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::default());
+        sprite_queue.set_texture_atlases(&resources.texture_atlases);
 
         // draw field
+        let team_indicators = globals
+            .config
+            .team_patterns
+            .then_some(&resources.team_indicators);
+
         self.field.draw(
             game_io,
             &mut sprite_queue,
             &mut self.tile_states,
             perspective_flipped,
+            team_indicators,
         );
 
         // draw dramatic fade
@@ -663,7 +933,35 @@ impl BattleSimulation {
             sprite_queue.draw_sprite(&self.fade_sprite);
         }
 
-        // draw entities, sorting by tile, movement offset, and layer
+        // mark the team of characters and obstacles under their feet
+        if let Some(team_indicators) = team_indicators {
+            sprite_queue.set_color_mode(SpriteColorMode::Multiply);
+
+            for (_, entity) in self.entities.query_mut::<hecs::With<&Entity, &Living>>() {
+                if !entity.on_field || blind_filter.is_some_and(|team| team != entity.team) {
+                    continue;
+                }
+
+                let Some(indicator) = team_indicators.get(entity.team) else {
+                    continue;
+                };
+
+                let mut position =
+                    (self.field).calc_tile_center((entity.x, entity.y), perspective_flipped);
+                position += entity.corrected_offset(perspective_flipped);
+
+                let bounds = Rect::new(
+                    position.x - TEAM_MARKER_SIZE.x * 0.5,
+                    position.y - TEAM_MARKER_SIZE.y * 0.5,
+                    TEAM_MARKER_SIZE.x,
+                    TEAM_MARKER_SIZE.y,
+                );
+
+                indicator.draw(game_io, &mut sprite_queue, bounds);
+            }
+        }
+
+        // draw entities, sorting by draw layer, tile, sprite layer, and movement offset
         let mut sorted_entities = Vec::with_capacity(self.entities.len() as usize);
 
         // filter characters + obstacles for blindness
@@ -692,7 +990,8 @@ impl BattleSimulation {
             }
         }
 
-        sorted_entities.sort_by_key(|(_, key)| *key);
+        // ties are broken by id to keep the order from changing as entities move between archetypes
+        sorted_entities.sort_by_key(|(id, key)| (*key, id.id()));
 
         // calculations and shadow rendering
         let mut entity_tree_render_params = Vec::with_capacity(sorted_entities.len());
@@ -761,9 +1060,16 @@ impl BattleSimulation {
 
         sprite_queue.set_shader_effect(SpriteShaderEffect::Default);
 
+        // draw particles above entities
+        sprite_queue.set_color_mode(SpriteColorMode::Multiply);
+
+        for (_, emitter) in &mut self.particle_emitters {
+            emitter.draw(&mut sprite_queue, perspective_flipped);
+        }
+
         // draw hp on living entities
         if self.intro_complete {
-            let mut hp_text = Text::new(game_io, FontName::EntityHP);
+            let mut hp_text = Text::new_unscaled(game_io, FontName::EntityHP);
             hp_text.style.letter_spacing = 0.0;
             let tile_size = self.field.tile_size();
 
@@ -806,7 +1112,7 @@ impl BattleSimulation {
 
         // draw player indices
         if draw_player_indices {
-            let mut index_text = Text::new(game_io, FontName::Code);
+            let mut index_text = Text::new_unscaled(game_io, FontName::Code);
             index_text.style.color = Color::GREEN;
             index_text.style.shadow_color = Color::BLACK;
 
@@ -1,4 +1,5 @@
 use super::Player;
+use crate::bindable::{card_codes_match, WILDCARD_CODE};
 use crate::saves::Card;
 use packets::structures::PackageId;
 
@@ -38,8 +39,8 @@ impl<'a> CardSelectRestriction<'a> {
             }
 
             if let Some(code_restriction) = code_restriction {
-                same_code &= code == code_restriction || code == "*";
-            } else if code != "*" {
+                same_code &= card_codes_match(code, code_restriction);
+            } else if code != WILDCARD_CODE {
                 code_restriction = Some(code);
             }
         }
@@ -66,10 +67,10 @@ impl<'a> CardSelectRestriction<'a> {
 
     pub fn allows_card(self, card: &Card) -> bool {
         match self {
-            Self::Code(code) => card.code == code || card.code == "*",
+            Self::Code(code) => card_codes_match(&card.code, code),
             Self::Package(package_id) => card.package_id == *package_id,
             Self::Mixed { package_id, code } => {
-                card.package_id == *package_id || card.code == code || card.code == "*"
+                card.package_id == *package_id || card_codes_match(&card.code, code)
             }
             Self::Any => true,
         }
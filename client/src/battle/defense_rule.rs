@@ -127,7 +127,7 @@ impl DefenseRule {
 
         let table: LuaTable = lua.registry_value(&self.table).unwrap();
 
-        lua_api.inject_dynamic(lua, &context, |_| {
+        lua_api.inject_dynamic(lua, &context, REPLACE_FN, |_| {
             table.raw_set("#replaced", true)?;
 
             if let Ok(callback) = table.get::<_, LuaFunction>(REPLACE_FN) {
@@ -244,7 +244,7 @@ impl DefenseJudge {
 
             let table: LuaTable = lua.registry_value(&defense_rule.table).unwrap();
 
-            lua_api.inject_dynamic(lua, &context, |lua| {
+            lua_api.inject_dynamic(lua, &context, BLOCK_FN, |lua| {
                 let Ok(callback): LuaResult<LuaFunction> = table.get(BLOCK_FN) else {
                     return Ok(());
                 };
@@ -289,7 +289,7 @@ impl DefenseJudge {
 
             let table: LuaTable = lua.registry_value(&defense_rule.table).unwrap();
 
-            lua_api.inject_dynamic(lua, &context, |_| {
+            lua_api.inject_dynamic(lua, &context, FILTER_STATUSES_FN, |_| {
                 let Ok(callback): LuaResult<LuaFunction> = table.get(FILTER_STATUSES_FN) else {
                     return Ok(());
                 };
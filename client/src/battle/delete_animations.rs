@@ -6,7 +6,7 @@ use framework::prelude::GameIO;
 /// requires Living component
 pub fn delete_player_animation(game_io: &GameIO, simulation: &mut BattleSimulation, id: EntityId) {
     let sfx = &game_io.resource::<Globals>().unwrap().sfx;
-    simulation.play_sound(game_io, &sfx.player_deleted);
+    simulation.play_sound_from(game_io, &sfx.player_deleted, id);
 
     let (entity, player) = simulation
         .entities
@@ -76,8 +76,9 @@ impl Artifact {
             .query_one_mut::<&mut Entity>(id.into())
             .unwrap();
 
-        entity.spawn_callback = BattleCallback::new(|game_io, _, simulation, _| {
-            simulation.play_sound(game_io, &game_io.resource::<Globals>().unwrap().sfx.explode);
+        entity.spawn_callback = BattleCallback::new(move |game_io, _, simulation, _| {
+            let sfx = &game_io.resource::<Globals>().unwrap().sfx;
+            simulation.play_sound_from(game_io, &sfx.explode, id);
         });
 
         id
@@ -105,8 +106,9 @@ impl Artifact {
             .query_one_mut::<&mut Entity>(id.into())
             .unwrap();
 
-        entity.spawn_callback = BattleCallback::new(|game_io, _, simulation, _| {
-            simulation.play_sound(game_io, &game_io.resource::<Globals>().unwrap().sfx.trap);
+        entity.spawn_callback = BattleCallback::new(move |game_io, _, simulation, _| {
+            let sfx = &game_io.resource::<Globals>().unwrap().sfx;
+            simulation.play_sound_from(game_io, &sfx.trap, id);
         });
 
         id
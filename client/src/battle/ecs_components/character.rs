@@ -1,6 +1,7 @@
 use super::{Artifact, Living, Player};
 use crate::battle::{
-    Action, BattleCallback, BattleSimulation, BattleState, Entity, SharedBattleResources, TileState,
+    Action, BattleCallback, BattleLogEvent, BattleSimulation, BattleState, Entity,
+    SharedBattleResources, TileState,
 };
 use crate::bindable::*;
 use crate::lua_api::create_entity_table;
@@ -10,6 +11,18 @@ use crate::render::{FrameTime, SpriteColorQueue};
 use crate::resources::RESOLUTION_F;
 use framework::prelude::{Color, GameIO, Vec2};
 use packets::structures::PackageId;
+use std::collections::VecDeque;
+
+#[derive(Clone)]
+pub struct CharacterPhase {
+    pub health: i32,
+    pub transition_duration: FrameTime,
+    pub callback: BattleCallback,
+}
+
+impl CharacterPhase {
+    pub const DEFAULT_TRANSITION_DURATION: FrameTime = 120;
+}
 
 #[derive(Clone)]
 pub struct Character {
@@ -18,6 +31,8 @@ pub struct Character {
     pub cards: Vec<CardProperties>, // stores cards reversed
     pub card_use_requested: bool,
     pub next_card_mutation: Option<usize>, // stores card index, invert to get a usable index
+    pub phases: VecDeque<CharacterPhase>,  // upcoming phases
+    pub phase_index: usize,
 }
 
 impl Character {
@@ -28,9 +43,59 @@ impl Character {
             cards: Vec::new(),
             card_use_requested: false,
             next_card_mutation: None,
+            phases: VecDeque::new(),
+            phase_index: 0,
         }
     }
 
+    pub fn phase_count(&self) -> usize {
+        self.phase_index + self.phases.len() + 1
+    }
+
+    /// Swaps to the next phase in place, keeping the same entity so targeting is unaffected.
+    /// Returns false if there's no remaining phases
+    pub fn advance_phase(simulation: &mut BattleSimulation, id: EntityId) -> bool {
+        let entities = &mut simulation.entities;
+
+        let Ok((entity, character, living)) =
+            entities.query_one_mut::<(&Entity, &mut Character, &mut Living)>(id.into())
+        else {
+            return false;
+        };
+
+        if entity.deleted {
+            return false;
+        }
+
+        let Some(phase) = character.phases.pop_front() else {
+            return false;
+        };
+
+        character.phase_index += 1;
+
+        living.max_health = phase.health.max(1);
+        living.health = living.max_health;
+
+        // invulnerable while transitioning
+        living.intangibility.enable(IntangibleRule {
+            duration: phase.transition_duration,
+            hit_weaknesses: HitFlag::NONE,
+            ..Default::default()
+        });
+
+        simulation.battle_log.push(
+            simulation.time,
+            BattleLogEvent::PhaseChanged {
+                target: entity.name.clone(),
+                phase: character.phase_index + 1,
+            },
+        );
+
+        simulation.pending_callbacks.push(phase.callback);
+
+        true
+    }
+
     pub fn create(
         game_io: &GameIO,
         simulation: &mut BattleSimulation,
@@ -305,6 +370,16 @@ impl Character {
                 let callbacks = living.modify_used_card(card_properties);
                 simulation.pending_callbacks.extend(callbacks);
 
+                simulation.battle_log.push(
+                    simulation.time,
+                    BattleLogEvent::CardUsed {
+                        user: entity.name.clone(),
+                        card: card_properties.short_name.to_string(),
+                        code: card_properties.code.clone(),
+                        damage: card_properties.damage,
+                    },
+                );
+
                 requesters.push(entity.id);
             }
 
@@ -350,7 +425,7 @@ impl Character {
         // render on the bottom left
         const MARGIN: Vec2 = Vec2::new(1.0, -1.0);
 
-        let line_height = TextStyle::new(game_io, FontName::Thick).line_height();
+        let line_height = TextStyle::new_unscaled(game_io, FontName::Thick).line_height();
         let position = Vec2::new(0.0, RESOLUTION_F.y - line_height) + MARGIN;
 
         // apply aux damage
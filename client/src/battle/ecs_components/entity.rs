@@ -3,6 +3,8 @@ use crate::bindable::*;
 use crate::render::*;
 use crate::structures::SlotMap;
 use framework::prelude::*;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
 use std::collections::VecDeque;
 
 #[derive(Clone, Copy)]
@@ -31,6 +33,10 @@ pub struct Entity {
     pub y: i32,
     pub height: f32,
     pub elevation: f32,
+    /// Drawn above entities with a higher layer regardless of tile, negative is closer
+    pub draw_layer: i32,
+    /// Rows added to y when sorting, lets tall entities draw as if they stood further back or forward
+    pub y_sort_offset: i32,
     pub animator_index: GenerationalIndex,
     pub sprite_tree_index: GenerationalIndex,
     pub offset: Vec2,          // does not flip with teams, only perspective
@@ -43,6 +49,9 @@ pub struct Entity {
     pub action_queue: VecDeque<GenerationalIndex>,
     pub action_index: Option<GenerationalIndex>,
     pub local_components: Vec<GenerationalIndex>,
+    pub summoner: Option<EntityId>,
+    pub summon_lifetime: Option<FrameTime>,
+    pub rng: Xoshiro256PlusPlus, // seeded from the battle seed and entity id, used by entity:rng()
     pub can_move_to_callback: BattleCallback<(i32, i32), bool>,
     pub spawn_callback: BattleCallback,
     pub update_callback: BattleCallback,
@@ -87,6 +96,8 @@ impl Entity {
             y: 0,
             height: 0.0,
             elevation: 0.0,
+            draw_layer: 0,
+            y_sort_offset: 0,
             animator_index,
             sprite_tree_index,
             offset: Vec2::ZERO,
@@ -102,6 +113,9 @@ impl Entity {
             action_queue: VecDeque::new(),
             action_index: None,
             local_components: Vec::new(),
+            summoner: None,
+            summon_lifetime: None,
+            rng: Self::create_rng(simulation.seed, id),
             can_move_to_callback: BattleCallback::stub(false),
             update_callback: BattleCallback::stub(()),
             idle_callback: BattleCallback::stub(()),
@@ -121,6 +135,14 @@ impl Entity {
         id
     }
 
+    /// Independent of creation order relative to other rng use, as long as ids match between peers
+    fn create_rng(battle_seed: u64, id: EntityId) -> Xoshiro256PlusPlus {
+        let id_bits = hecs::Entity::from(id).to_bits().get();
+
+        // seed_from_u64 scrambles the seed, the multiply spreads ids that only differ by generation
+        Xoshiro256PlusPlus::seed_from_u64(battle_seed ^ id_bits.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
     fn reserve(simulation: &mut BattleSimulation) -> EntityId {
         let id = simulation.entities.reserve_entity();
 
@@ -176,14 +198,16 @@ impl Entity {
         self.movement_offset = full_position.movement_offset;
     }
 
-    pub fn sort_key(&self, sprite_trees: &SlotMap<Tree<SpriteNode>>) -> (i32, i32, i32) {
-        let Some(sprite_tree) = sprite_trees.get(self.sprite_tree_index) else {
-            return (self.y, 0, self.movement_offset.y as _);
-        };
+    pub fn sort_key(&self, sprite_trees: &SlotMap<Tree<SpriteNode>>) -> (i32, i32, i32, i32) {
+        let root_layer = sprite_trees
+            .get(self.sprite_tree_index)
+            .map(|sprite_tree| sprite_tree.root().layer())
+            .unwrap_or_default();
 
         (
-            self.y,
-            -sprite_tree.root().layer(),
+            -self.draw_layer,
+            self.y + self.y_sort_offset,
+            -root_layer,
             self.movement_offset.y as _,
         )
     }
@@ -214,7 +238,10 @@ impl Entity {
         simulation: &mut BattleSimulation,
         id: EntityId,
     ) {
-        let Ok(entity) = simulation.entities.query_one_mut::<&mut Entity>(id.into()) else {
+        let entities = &mut simulation.entities;
+        let is_living = entities.satisfies::<&Living>(id.into()).unwrap_or(false);
+
+        let Ok(entity) = entities.query_one_mut::<&mut Entity>(id.into()) else {
             return;
         };
 
@@ -230,6 +257,15 @@ impl Entity {
 
         entity.deleted = true;
 
+        if is_living {
+            simulation.battle_log.push(
+                simulation.time,
+                BattleLogEvent::Deleted {
+                    target: entity.name.clone(),
+                },
+            );
+        }
+
         let listener_callbacks = std::mem::take(&mut entity.delete_callbacks);
         let delete_callback = entity.delete_callback.clone();
 
@@ -251,6 +287,20 @@ impl Entity {
         simulation.pending_callbacks.push(delete_callback);
 
         simulation.call_pending_callbacks(game_io, resources);
+
+        // summons leave with their summoner
+        for summon_id in Entity::summons(simulation, id) {
+            Entity::delete(game_io, resources, simulation, summon_id);
+        }
+    }
+
+    pub fn summons(simulation: &mut BattleSimulation, summoner_id: EntityId) -> Vec<EntityId> {
+        (simulation.entities)
+            .query_mut::<&Entity>()
+            .into_iter()
+            .filter(|(_, entity)| entity.summoner == Some(summoner_id) && !entity.deleted)
+            .map(|(_, entity)| entity.id)
+            .collect()
     }
 
     pub fn mark_erased(
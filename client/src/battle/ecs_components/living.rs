@@ -5,6 +5,13 @@ use crate::structures::DenseSlotMap;
 use framework::prelude::*;
 use std::collections::HashMap;
 
+/// A drag still moving the entity, `drag.distance` is the number of tiles left
+#[derive(Clone, Copy)]
+pub struct ActiveDrag {
+    pub drag: Drag,
+    pub context: HitContext,
+}
+
 #[derive(Clone)]
 pub struct Living {
     pub hit: bool, // used for flashing white
@@ -18,6 +25,12 @@ pub struct Living {
     pub status_director: StatusDirector,
     pub status_callbacks: HashMap<HitFlags, Vec<BattleCallback>>,
     pub countered_callback: BattleCallback,
+    pub hit_resolution: HitResolution,
+    /// Called with the final properties of each hit after its flags are resolved
+    pub hit_resolved_callback: BattleCallback<HitProperties>,
+    pub active_drag: Option<ActiveDrag>,
+    /// Called with the tile that stopped a drag early
+    pub drag_collision_callback: BattleCallback<(i32, i32)>,
     pub aux_props: DenseSlotMap<AuxProp>,
     pub pending_hits: Vec<HitProperties>,
 }
@@ -36,6 +49,10 @@ impl Default for Living {
             status_director: StatusDirector::default(),
             status_callbacks: HashMap::new(),
             countered_callback: BattleCallback::default(),
+            hit_resolution: HitResolution::default(),
+            hit_resolved_callback: BattleCallback::default(),
+            active_drag: None,
+            drag_collision_callback: BattleCallback::default(),
             aux_props: Default::default(),
             pending_hits: Vec::new(),
         }
@@ -143,6 +160,7 @@ impl Living {
         };
 
         let mut hit_prop_list = std::mem::take(&mut living.pending_hits);
+        let target_name = entity.name.clone();
 
         // aux props
         let mut total_damage: i32 = hit_prop_list.iter().map(|hit_props| hit_props.damage).sum();
@@ -155,7 +173,13 @@ impl Living {
             aux_prop.process_body(player, character, entity);
 
             for hit_props in &mut hit_prop_list {
-                aux_prop.process_hit(entity, living.health, living.max_health, hit_props);
+                aux_prop.process_hit(
+                    &resources.element_registry,
+                    entity,
+                    living.health,
+                    living.max_health,
+                    hit_props,
+                );
             }
         }
 
@@ -204,6 +228,11 @@ impl Living {
             );
 
             let entities = &mut simulation.entities;
+            let aggressor_name = entities
+                .query_one_mut::<&Entity>(hit_props.context.aggressor.into())
+                .map(|entity| entity.name.clone())
+                .ok();
+
             let Ok((entity, living)) =
                 entities.query_one_mut::<(&Entity, &mut Living)>(entity_id.into())
             else {
@@ -212,9 +241,13 @@ impl Living {
 
             let mut modified_hit_damage = hit_props.damage;
 
-            // super effective bonus
-            if hit_props.is_super_effective(entity.element) {
+            // super effective bonus, or resistance penalty
+            let element_registry = &resources.element_registry;
+
+            if hit_props.is_super_effective(element_registry, entity.element) {
                 modified_hit_damage += hit_props.damage;
+            } else if hit_props.is_resisted(element_registry, entity.element) {
+                modified_hit_damage -= hit_props.damage / 2;
             }
 
             // apply hit modifying aux props
@@ -226,7 +259,13 @@ impl Living {
                 );
                 aux_prop.mark_tested();
 
-                if !aux_prop.hit_passes_tests(entity, living.health, living.max_health, hit_props) {
+                if !aux_prop.hit_passes_tests(
+                    element_registry,
+                    entity,
+                    living.health,
+                    living.max_health,
+                    hit_props,
+                ) {
                     continue;
                 }
 
@@ -264,6 +303,17 @@ impl Living {
             // update total damage
             total_damage += modified_hit_damage - hit_props.damage;
 
+            if modified_hit_damage != 0 {
+                simulation.battle_log.push(
+                    simulation.time,
+                    BattleLogEvent::Hit {
+                        target: target_name.clone(),
+                        aggressor: aggressor_name,
+                        damage: modified_hit_damage,
+                    },
+                );
+            }
+
             if modified_hit_damage == 0 && hit_props.damage != 0 {
                 // no hit flags can apply if damage is changed to 0 by aux props
                 continue;
@@ -280,91 +330,53 @@ impl Living {
                 living.hit = true
             }
 
-            // handle counter
-            if living.counterable
-                && !living.status_director.is_inactionable(status_registry)
-                && (hit_props.flags & HitFlag::IMPACT) == HitFlag::IMPACT
-                && (hit_props.context.flags & HitFlag::NO_COUNTER) == 0
-            {
-                living.status_director.apply_status(HitFlag::PARALYZE, 150);
-                living.counterable = false;
-
-                // notify self
-                let self_callback = living.countered_callback.clone();
-                simulation.pending_callbacks.push(self_callback);
+            let resolution = living.hit_resolution.clone();
 
-                // notify aggressor
-                let aggressor_id = hit_props.context.aggressor;
+            if resolution.flash_blocks_flinch
+                && living.status_director.remaining_status_time(HitFlag::FLASH) > 0
+            {
+                hit_props.flags &= !HitFlag::FLINCH;
+            }
 
-                let notify_aggressor =
-                    BattleCallback::new(move |game_io, resources, simulation, ()| {
-                        let entities = &mut simulation.entities;
-                        let Ok(aggressor_entity) =
-                            entities.query_one_mut::<&Entity>(aggressor_id.into())
+            for step in resolution.order {
+                match step {
+                    HitStep::Counter => {
+                        Living::resolve_counter(resources, simulation, entity_id, hit_props)
+                    }
+                    HitStep::Statuses => {
+                        let Ok(living) =
+                            (simulation.entities).query_one_mut::<&mut Living>(entity_id.into())
                         else {
                             return;
                         };
 
-                        let callback = aggressor_entity.counter_callback.clone();
-                        callback.call(game_io, resources, simulation, entity_id);
-
-                        // play counter sfx if the attack was caused by the local player
-                        if simulation.local_player_id == aggressor_id {
-                            let globals = game_io.resource::<Globals>().unwrap();
-                            simulation.play_sound(game_io, &globals.sfx.counter_hit);
-                        }
-                    });
-
-                simulation.pending_callbacks.push(notify_aggressor);
-            }
-
-            // apply statuses
-            let status_director = &mut living.status_director;
-            status_director.apply_hit_flags(status_registry, hit_props.flags);
-
-            // handle drag
-            if hit_props.drags() && entity.movement.is_none() {
-                let can_move_to_callback = entity.can_move_to_callback.clone();
-                let delta: IVec2 = hit_props.drag.direction.i32_vector().into();
-
-                // clear + backup lockout to allow can_move_to_funcs that check for immobilize to ignore it
-                let old_lockout = status_director.remaining_drag_lockout();
-                status_director.set_remaining_drag_lockout(0);
-
-                let mut dest = IVec2::new(entity.x, entity.y);
-                let mut duration = 0;
-
-                for _ in 0..hit_props.drag.distance {
-                    dest += delta;
-
-                    let tile_exists = simulation.field.tile_at_mut(dest.into()).is_some();
-
-                    if !tile_exists
-                        || !can_move_to_callback.call(game_io, resources, simulation, dest.into())
-                    {
-                        dest -= delta;
-                        break;
+                        let status_director = &mut living.status_director;
+                        status_director.apply_hit_flags(status_registry, hit_props.flags);
                     }
-
-                    duration += DRAG_PER_TILE_DURATION;
+                    HitStep::Drag => Living::resolve_drag(
+                        game_io,
+                        resources,
+                        simulation,
+                        entity_id,
+                        hit_props,
+                        resolution.drag_cancels_movement,
+                    ),
                 }
+            }
 
-                if duration != 0 {
-                    let entity = (simulation.entities)
-                        .query_one_mut::<&mut Entity>(entity_id.into())
-                        .unwrap();
+            let Ok(living) = (simulation.entities).query_one_mut::<&Living>(entity_id.into())
+            else {
+                return;
+            };
 
-                    entity.movement = Some(Movement::slide(dest.into(), duration));
-                } else {
-                    let living = (simulation.entities)
-                        .query_one_mut::<&mut Living>(entity_id.into())
-                        .unwrap();
+            let callback = living.hit_resolved_callback.clone();
+            let final_hit_props = hit_props.clone();
 
-                    living
-                        .status_director
-                        .set_remaining_drag_lockout(old_lockout);
-                }
-            }
+            simulation.pending_callbacks.push(BattleCallback::new(
+                move |game_io, resources, simulation, ()| {
+                    callback.call(game_io, resources, simulation, final_hit_props.clone());
+                },
+            ));
         }
 
         let living = simulation
@@ -417,6 +429,18 @@ impl Living {
         // apply damage and health modifier
         living.set_health(living.health - total_damage + health_modifier);
 
+        if total_damage != 0 || health_modifier != 0 {
+            simulation.battle_log.push(
+                simulation.time,
+                BattleLogEvent::HealthChanged {
+                    target: target_name,
+                    damage: total_damage,
+                    recovered: health_modifier,
+                    health: living.health,
+                },
+            );
+        }
+
         // handle intangibility
         if living.intangibility.is_retangible() {
             living.intangibility.disable();
@@ -429,6 +453,181 @@ impl Living {
         simulation.call_pending_callbacks(game_io, resources);
     }
 
+    fn resolve_counter(
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+        entity_id: EntityId,
+        hit_props: &HitProperties,
+    ) {
+        let status_registry = &resources.status_registry;
+
+        let Ok(living) = (simulation.entities).query_one_mut::<&mut Living>(entity_id.into())
+        else {
+            return;
+        };
+
+        if !living.counterable
+            || living.status_director.is_inactionable(status_registry)
+            || (hit_props.flags & HitFlag::IMPACT) != HitFlag::IMPACT
+            || (hit_props.context.flags & HitFlag::NO_COUNTER) != 0
+        {
+            return;
+        }
+
+        living.status_director.apply_status(HitFlag::PARALYZE, 150);
+        living.counterable = false;
+
+        // notify self
+        let self_callback = living.countered_callback.clone();
+        simulation.pending_callbacks.push(self_callback);
+
+        // notify aggressor
+        let aggressor_id = hit_props.context.aggressor;
+
+        let notify_aggressor = BattleCallback::new(move |game_io, resources, simulation, ()| {
+            let entities = &mut simulation.entities;
+            let Ok(aggressor_entity) = entities.query_one_mut::<&Entity>(aggressor_id.into())
+            else {
+                return;
+            };
+
+            let callback = aggressor_entity.counter_callback.clone();
+            callback.call(game_io, resources, simulation, entity_id);
+
+            // play counter sfx if the attack was caused by the local player
+            if simulation.local_player_id == aggressor_id {
+                let globals = game_io.resource::<Globals>().unwrap();
+                simulation.play_sound(game_io, &globals.sfx.counter_hit);
+            }
+        });
+
+        simulation.pending_callbacks.push(notify_aggressor);
+    }
+
+    fn resolve_drag(
+        game_io: &GameIO,
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+        entity_id: EntityId,
+        hit_props: &HitProperties,
+        cancels_movement: bool,
+    ) {
+        let Ok((entity, living)) =
+            (simulation.entities).query_one_mut::<(&Entity, &mut Living)>(entity_id.into())
+        else {
+            return;
+        };
+
+        if !hit_props.drags() || (entity.movement.is_some() && !cancels_movement) {
+            return;
+        }
+
+        living.active_drag = Some(ActiveDrag {
+            drag: hit_props.drag,
+            context: hit_props.context,
+        });
+
+        Living::step_drag(game_io, resources, simulation, entity_id);
+    }
+
+    /// Moves a dragged entity one tile, drags are resolved tile by tile
+    /// to respect obstacles and tile effects along the way
+    pub fn step_drag(
+        game_io: &GameIO,
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+        entity_id: EntityId,
+    ) {
+        let Ok((entity, living)) =
+            (simulation.entities).query_one_mut::<(&Entity, &mut Living)>(entity_id.into())
+        else {
+            return;
+        };
+
+        let Some(mut active_drag) = living.active_drag.take() else {
+            return;
+        };
+
+        if active_drag.drag.distance == 0 {
+            return;
+        }
+
+        let can_move_to_callback = entity.can_move_to_callback.clone();
+        let delta: IVec2 = active_drag.drag.direction.i32_vector().into();
+        let dest = IVec2::new(entity.x, entity.y) + delta;
+        let status_director = &mut living.status_director;
+
+        // clear + backup lockout to allow can_move_to_funcs that check for immobilize to ignore it
+        let old_lockout = status_director.remaining_drag_lockout();
+        status_director.set_remaining_drag_lockout(0);
+
+        let tile_exists = simulation.field.tile_at_mut(dest.into()).is_some();
+        let can_move =
+            tile_exists && can_move_to_callback.call(game_io, resources, simulation, dest.into());
+
+        let Ok((entity, living)) =
+            (simulation.entities).query_one_mut::<(&mut Entity, &mut Living)>(entity_id.into())
+        else {
+            return;
+        };
+
+        if can_move {
+            entity.movement = Some(Movement::slide(dest.into(), DRAG_PER_TILE_DURATION));
+            active_drag.drag.distance -= 1;
+            living.active_drag = Some(active_drag);
+            return;
+        }
+
+        living
+            .status_director
+            .set_remaining_drag_lockout(old_lockout);
+
+        // collided with an obstacle
+        let collision_callback = living.drag_collision_callback.clone();
+        let dest = dest.into();
+
+        simulation.pending_callbacks.push(BattleCallback::new(
+            move |game_io, resources, simulation, ()| {
+                collision_callback.call(game_io, resources, simulation, dest);
+            },
+        ));
+
+        let drag = active_drag.drag;
+        let mut context = active_drag.context;
+        context.flags |= HitFlag::NO_COUNTER;
+
+        if drag.wall_damage > 0 {
+            living.queue_hit(HitProperties {
+                damage: drag.wall_damage,
+                flags: HitFlag::IMPACT,
+                context,
+                ..HitProperties::blank()
+            });
+        }
+
+        if !drag.chain || !tile_exists {
+            return;
+        }
+
+        // push entities blocking the way
+        for (id, (entity, living)) in simulation.entities.query_mut::<(&Entity, &mut Living)>() {
+            if EntityId::from(id) == entity_id
+                || !entity.spawned
+                || entity.deleted
+                || (entity.x, entity.y) != dest
+            {
+                continue;
+            }
+
+            living.queue_hit(HitProperties {
+                flags: HitFlag::DRAG,
+                drag,
+                context,
+                ..HitProperties::blank()
+            });
+        }
+    }
+
     pub fn intercept_action(
         game_io: &GameIO,
         resources: &SharedBattleResources,
@@ -187,15 +187,23 @@ impl Player {
         living.register_status_callback(
             HitFlag::FLINCH,
             BattleCallback::new(move |game_io, resources, simulation, _| {
-                // cancel actions
-                Action::cancel_all(game_io, resources, simulation, id);
+                let entities = &mut simulation.entities;
+                let Ok(living) = entities.query_one_mut::<&Living>(id.into()) else {
+                    return;
+                };
+
+                let resolution = living.hit_resolution.clone();
+
+                if resolution.flinch_cancels_actions {
+                    Action::cancel_all(game_io, resources, simulation, id);
+                }
 
                 let (entity, living, player) = simulation
                     .entities
                     .query_one_mut::<(&mut Entity, &Living, &mut Player)>(id.into())
                     .unwrap();
 
-                if !living.status_director.is_dragged() {
+                if resolution.flinch_cancels_movement && !living.status_director.is_dragged() {
                     // cancel movement
                     entity.movement = None;
                 }
@@ -206,7 +214,8 @@ impl Player {
                 let flinch_action_index = Action::create(game_io, simulation, state, id).unwrap();
                 Action::queue_action(simulation, id, flinch_action_index);
 
-                simulation.play_sound(game_io, &game_io.resource::<Globals>().unwrap().sfx.hurt);
+                let sfx = &game_io.resource::<Globals>().unwrap().sfx;
+                simulation.play_sound_from(game_io, &sfx.hurt, id);
             }),
         );
 
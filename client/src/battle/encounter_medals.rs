@@ -0,0 +1,97 @@
+use crate::render::FrameTime;
+use packets::structures::BattleStatistics;
+use serde::{Deserialize, Serialize};
+
+/// Optional challenges declared by encounter packages, awarded on victory
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum EncounterMedal {
+    /// Won within the encounter's par time
+    ParTime,
+    /// Won without taking damage
+    NoDamage,
+}
+
+impl EncounterMedal {
+    pub fn label(self) -> &'static str {
+        match self {
+            EncounterMedal::ParTime => "PAR TIME",
+            EncounterMedal::NoDamage => "NO DAMAGE",
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct EncounterMedalCriteria {
+    pub par_time: Option<FrameTime>,
+    pub no_damage: bool,
+}
+
+impl EncounterMedalCriteria {
+    pub fn available(&self) -> Vec<EncounterMedal> {
+        let mut medals = Vec::new();
+
+        if self.par_time.is_some() {
+            medals.push(EncounterMedal::ParTime);
+        }
+
+        if self.no_damage {
+            medals.push(EncounterMedal::NoDamage);
+        }
+
+        medals
+    }
+
+    /// Expects statistics from a won battle
+    pub fn earned(&self, statistics: &BattleStatistics) -> Vec<EncounterMedal> {
+        let mut medals = Vec::new();
+
+        if self
+            .par_time
+            .is_some_and(|par_time| statistics.time <= par_time)
+        {
+            medals.push(EncounterMedal::ParTime);
+        }
+
+        if self.no_damage && statistics.hits_taken == 0 {
+            medals.push(EncounterMedal::NoDamage);
+        }
+
+        medals
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn earned_medals() {
+        let criteria = EncounterMedalCriteria {
+            par_time: Some(600),
+            no_damage: true,
+        };
+
+        let mut statistics = BattleStatistics {
+            time: 600,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            criteria.earned(&statistics),
+            [EncounterMedal::ParTime, EncounterMedal::NoDamage]
+        );
+
+        statistics.time = 601;
+        statistics.hits_taken = 1;
+        assert!(criteria.earned(&statistics).is_empty());
+    }
+
+    #[test]
+    fn undeclared_medals_are_not_earned() {
+        let criteria = EncounterMedalCriteria::default();
+        let statistics = BattleStatistics::default();
+
+        assert!(criteria.available().is_empty());
+        assert!(criteria.earned(&statistics).is_empty());
+    }
+}
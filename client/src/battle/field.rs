@@ -1,5 +1,6 @@
 use super::{
-    BattleCallback, BattleSimulation, SharedBattleResources, TileState, TileStateAnimationSupport,
+    BattleCallback, BattleSimulation, SharedBattleResources, TeamIndicators, TileState,
+    TileStateAnimationSupport,
 };
 use super::{Entity, Tile};
 use crate::bindable::*;
@@ -261,6 +262,7 @@ impl Field {
         sprite_queue: &mut SpriteColorQueue,
         tile_states: &mut [TileState],
         flipped: bool,
+        team_indicators: Option<&TeamIndicators>,
     ) {
         sprite_queue.set_color_mode(SpriteColorMode::Add);
 
@@ -271,6 +273,7 @@ impl Field {
         let flip_multiplier = if flipped { -1.0 } else { 1.0 };
 
         let mut highlight_positions = Vec::new();
+        let mut indicator_positions = Vec::new();
 
         for row in 0..self.rows {
             let state_row = (row) * 3 / (self.rows - 1) + 1;
@@ -326,6 +329,10 @@ impl Field {
                     highlight_positions.push(position);
                 }
 
+                if !tile_state.hide_body {
+                    indicator_positions.push((position, team));
+                }
+
                 // render state sprite
                 let state_animation_support = tile_state.animation_support;
 
@@ -363,5 +370,25 @@ impl Field {
             highlight_sprite.set_position(position);
             sprite_queue.draw_sprite(&highlight_sprite);
         }
+
+        // draw colorblind patterns
+        let Some(team_indicators) = team_indicators else {
+            return;
+        };
+
+        for (position, team) in indicator_positions {
+            let Some(indicator) = team_indicators.get(team) else {
+                continue;
+            };
+
+            let bounds = Rect::new(
+                position.x - self.tile_size.x * 0.5,
+                position.y,
+                self.tile_size.x,
+                self.tile_size.y,
+            );
+
+            indicator.draw_over_tile(game_io, sprite_queue, bounds);
+        }
     }
 }
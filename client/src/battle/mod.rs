@@ -4,7 +4,9 @@ mod attack_charge;
 mod augment;
 mod battle_animator;
 mod battle_callback;
+mod battle_camera;
 mod battle_config;
+mod battle_log;
 mod battle_props;
 mod battle_script_context;
 mod battle_simulation;
@@ -15,19 +17,24 @@ mod defense_rule;
 mod delete_animations;
 mod ecs_components;
 mod emotion_ui;
+mod encounter_medals;
 mod field;
 mod intangibility;
+mod netplay_input_receiver;
 mod ownership_tracking;
+mod particle_emitter;
 mod player_fallback_resources;
 mod player_form;
 mod player_input;
 mod player_overridables;
 mod rollback_vm;
+mod script_profiler;
 mod shared_battle_resources;
 mod staged_items;
 mod states;
 mod status_director;
 mod status_registry;
+mod team_indicators;
 mod tile;
 mod tile_state;
 mod time_freeze_entity_backup;
@@ -40,7 +47,9 @@ pub use attack_charge::*;
 pub use augment::*;
 pub use battle_animator::*;
 pub use battle_callback::*;
+pub use battle_camera::*;
 pub use battle_config::*;
+pub use battle_log::*;
 pub use battle_props::*;
 pub use battle_script_context::*;
 pub use battle_simulation::*;
@@ -51,19 +60,24 @@ pub use defense_rule::*;
 pub use delete_animations::*;
 pub use ecs_components::*;
 pub use emotion_ui::*;
+pub use encounter_medals::*;
 pub use field::*;
 pub use intangibility::*;
+pub use netplay_input_receiver::*;
 pub use ownership_tracking::*;
+pub use particle_emitter::*;
 pub use player_fallback_resources::*;
 pub use player_form::*;
 pub use player_input::*;
 pub use player_overridables::*;
 pub use rollback_vm::*;
+pub use script_profiler::*;
 pub use shared_battle_resources::*;
 pub use staged_items::*;
 pub use states::*;
 pub use status_director::*;
 pub use status_registry::*;
+pub use team_indicators::*;
 pub use tile::*;
 pub use tile_state::*;
 pub use time_freeze_entity_backup::*;
@@ -0,0 +1,153 @@
+use packets::NetplayBufferItem;
+use std::collections::{BTreeMap, VecDeque};
+
+/// How many recent inputs are used to estimate packet loss
+const LOSS_WINDOW: usize = 120;
+
+/// (loss rate, redundancy), the first entry the loss rate fits under is used
+const REDUNDANCY_STEPS: [(f32, u8); 4] = [(0.02, 1), (0.05, 2), (0.1, 4), (f32::MAX, 8)];
+
+/// Reassembles a remote player's inputs from Buffer packets,
+/// which may arrive out of order and carry copies of previous inputs
+#[derive(Default, Clone)]
+pub struct NetplayInputReceiver {
+    /// Inputs that arrived ahead of a missing input
+    pending: BTreeMap<usize, NetplayBufferItem>,
+    /// Whether each recent input had to be recovered, rather than arriving in its own packet on time
+    recovered: VecDeque<bool>,
+}
+
+impl NetplayInputReceiver {
+    /// `received` is the number of inputs already received from this player.
+    /// `redundant_data` holds the inputs preceding `frame`, oldest first.
+    /// Returns inputs ready to be appended to the player's buffer, in order
+    pub fn receive(
+        &mut self,
+        received: usize,
+        frame: Option<usize>,
+        data: NetplayBufferItem,
+        redundant_data: Vec<NetplayBufferItem>,
+    ) -> Vec<NetplayBufferItem> {
+        let Some(frame) = frame else {
+            // signals injected by the server aren't part of the sender's stream
+            return vec![data];
+        };
+
+        let first_frame = frame.saturating_sub(redundant_data.len());
+        let frames = (first_frame..).zip(redundant_data).chain([(frame, data)]);
+
+        for (item_frame, item) in frames {
+            if item_frame >= received {
+                self.pending.entry(item_frame).or_insert(item);
+            }
+        }
+
+        // drain everything that's now contiguous
+        let mut ready = Vec::new();
+        let mut next_frame = received;
+
+        while let Some(item) = self.pending.remove(&next_frame) {
+            self.track_recovery(next_frame != frame);
+            ready.push(item);
+            next_frame += 1;
+        }
+
+        ready
+    }
+
+    /// How many previous inputs this player should include with each packet
+    pub fn requested_redundancy(&self) -> u8 {
+        let total_recovered = self
+            .recovered
+            .iter()
+            .filter(|recovered| **recovered)
+            .count();
+
+        if total_recovered == 0 {
+            return 0;
+        }
+
+        let loss_rate = total_recovered as f32 / self.recovered.len() as f32;
+
+        REDUNDANCY_STEPS
+            .iter()
+            .find(|(max_loss_rate, _)| loss_rate < *max_loss_rate)
+            .map(|(_, redundancy)| *redundancy)
+            .unwrap_or_default()
+    }
+
+    fn track_recovery(&mut self, recovered: bool) {
+        if self.recovered.len() >= LOSS_WINDOW {
+            self.recovered.pop_front();
+        }
+
+        self.recovered.push_back(recovered);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packets::structures::Input;
+
+    fn item(input: Input) -> NetplayBufferItem {
+        NetplayBufferItem {
+            pressed: vec![input],
+            signals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn in_order_inputs() {
+        let mut receiver = NetplayInputReceiver::default();
+
+        let ready = receiver.receive(0, Some(0), item(Input::Up), Vec::new());
+        assert_eq!(ready, [item(Input::Up)]);
+
+        let ready = receiver.receive(1, Some(1), item(Input::Down), vec![item(Input::Up)]);
+        assert_eq!(ready, [item(Input::Down)]);
+
+        assert_eq!(receiver.requested_redundancy(), 0);
+    }
+
+    #[test]
+    fn lost_input_recovered() {
+        let mut receiver = NetplayInputReceiver::default();
+
+        receiver.receive(0, Some(0), item(Input::Up), Vec::new());
+
+        // frame 1 was lost, frame 2 carries a copy
+        let ready = receiver.receive(
+            1,
+            Some(2),
+            item(Input::Left),
+            vec![item(Input::Up), item(Input::Down)],
+        );
+        assert_eq!(ready, [item(Input::Down), item(Input::Left)]);
+
+        // resent copy of frame 1 is ignored
+        let ready = receiver.receive(3, Some(1), item(Input::Down), Vec::new());
+        assert!(ready.is_empty());
+
+        assert!(receiver.requested_redundancy() > 0);
+    }
+
+    #[test]
+    fn out_of_order_inputs_wait() {
+        let mut receiver = NetplayInputReceiver::default();
+
+        let ready = receiver.receive(0, Some(1), item(Input::Down), Vec::new());
+        assert!(ready.is_empty());
+
+        let ready = receiver.receive(0, Some(0), item(Input::Up), Vec::new());
+        assert_eq!(ready, [item(Input::Up), item(Input::Down)]);
+    }
+
+    #[test]
+    fn server_signals_pass_through() {
+        let mut receiver = NetplayInputReceiver::default();
+
+        let ready = receiver.receive(5, None, item(Input::Up), Vec::new());
+        assert_eq!(ready, [item(Input::Up)]);
+    }
+}
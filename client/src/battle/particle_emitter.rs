@@ -0,0 +1,200 @@
+use super::{BattleSimulation, Entity, Field};
+use crate::bindable::{EntityId, ParticleEmitterProps};
+use crate::render::{Animator, FrameTime, SpriteColorQueue};
+use crate::resources::{AssetManager, Globals};
+use framework::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+#[derive(Clone)]
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    elapsed: FrameTime,
+    flipped: bool,
+}
+
+/// Particles are simulated without entities, positions are tracked in an unflipped perspective
+#[derive(Clone)]
+pub struct ParticleEmitter {
+    pub entity: EntityId,
+    pub spawning: bool,
+    orphaned: bool,
+    props: ParticleEmitterProps,
+    rng: Xoshiro256PlusPlus,
+    sprite: Sprite,
+    animator: Animator,
+    particles: Vec<Particle>,
+    time: FrameTime,
+}
+
+impl ParticleEmitter {
+    pub fn new(
+        game_io: &GameIO,
+        simulation: &mut BattleSimulation,
+        entity: EntityId,
+        props: ParticleEmitterProps,
+    ) -> Self {
+        let assets = &game_io.resource::<Globals>().unwrap().assets;
+        let sprite = assets.new_sprite(game_io, &props.texture_path);
+        let animator = Animator::load_new(assets, &props.animation_path).with_state(&props.state);
+
+        Self {
+            entity,
+            spawning: true,
+            orphaned: false,
+            // seeded from the battle's rng to stay in sync with other clients
+            rng: Xoshiro256PlusPlus::seed_from_u64(simulation.rng.gen()),
+            props,
+            sprite,
+            animator,
+            particles: Vec::new(),
+            time: 0,
+        }
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn update(&mut self, entity: Option<&Entity>, field: &Field) {
+        let Some(entity) = entity.filter(|entity| !entity.erased) else {
+            // the owner is gone, let the remaining particles finish
+            self.spawning = false;
+            self.orphaned = true;
+            self.update_particles();
+            return;
+        };
+
+        if entity.time_frozen {
+            return;
+        }
+
+        self.update_particles();
+
+        if self.spawning && entity.on_field && self.time % self.props.spawn_rate == 0 {
+            let flipped = entity.flipped();
+            let mut offset = self.props.offset;
+
+            if flipped {
+                offset.x = -offset.x;
+            }
+
+            let mut position = field.calc_tile_center((entity.x, entity.y), false);
+            position += entity.corrected_offset(false) + offset;
+            position.y -= entity.elevation;
+
+            for _ in 0..self.props.spawn_count {
+                self.spawn_particle(position, flipped);
+            }
+        }
+
+        self.time += 1;
+    }
+
+    fn update_particles(&mut self) {
+        let lifetime = self.props.lifetime;
+        let acceleration = self.props.acceleration;
+
+        for particle in &mut self.particles {
+            let mut acceleration = acceleration;
+
+            if particle.flipped {
+                acceleration.x = -acceleration.x;
+            }
+
+            particle.velocity += acceleration;
+            particle.position += particle.velocity;
+            particle.elapsed += 1;
+        }
+
+        self.particles
+            .retain(|particle| particle.elapsed < lifetime);
+    }
+
+    fn spawn_particle(&mut self, position: Vec2, flipped: bool) {
+        let min = self.props.min_velocity;
+        let max = self.props.max_velocity;
+
+        let mut velocity = Vec2::new(
+            Self::random_range(&mut self.rng, min.x, max.x),
+            Self::random_range(&mut self.rng, min.y, max.y),
+        );
+
+        // velocity is relative to the direction the owner is facing
+        if flipped {
+            velocity.x = -velocity.x;
+        }
+
+        self.particles.push(Particle {
+            position,
+            velocity,
+            elapsed: 0,
+            flipped,
+        });
+    }
+
+    /// Interpolates with a sampled fraction, gen_range panics on empty or overflowing ranges.
+    /// Props are validated as finite when read from Lua
+    fn random_range(rng: &mut Xoshiro256PlusPlus, a: f32, b: f32) -> f32 {
+        if a == b {
+            return a;
+        }
+
+        let t: f32 = rng.gen();
+        let value = a + (b - a) * t;
+
+        if value.is_finite() {
+            value
+        } else {
+            a
+        }
+    }
+
+    /// Emitters can be removed once the owner is gone and every particle has expired
+    pub fn is_complete(&self) -> bool {
+        self.orphaned && self.particles.is_empty()
+    }
+
+    pub fn draw(&mut self, sprite_queue: &mut SpriteColorQueue, perspective_flipped: bool) {
+        for particle in &self.particles {
+            self.animator.sync_time(particle.elapsed);
+            self.animator.apply(&mut self.sprite);
+
+            let mut position = particle.position;
+            let mut scale = Vec2::ONE;
+
+            if perspective_flipped {
+                position.x = -position.x;
+            }
+
+            if perspective_flipped ^ particle.flipped {
+                scale.x = -1.0;
+            }
+
+            self.sprite.set_position(position);
+            self.sprite.set_scale(scale);
+            sprite_queue.draw_sprite(&self.sprite);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn random_range_stays_in_bounds() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(0);
+
+        assert_eq!(ParticleEmitter::random_range(&mut rng, 2.0, 2.0), 2.0);
+
+        for _ in 0..100 {
+            let value = ParticleEmitter::random_range(&mut rng, 3.0, -1.0);
+            assert!((-1.0..=3.0).contains(&value));
+        }
+
+        let value = ParticleEmitter::random_range(&mut rng, -f32::MAX, f32::MAX);
+        assert!(value.is_finite());
+    }
+}
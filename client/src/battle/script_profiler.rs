@@ -0,0 +1,243 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Frames used for averages and peaks
+const HISTORY_LEN: usize = 60;
+
+#[derive(Default)]
+struct ProfileEntry {
+    vm_index: usize,
+    label: Arc<str>,
+    frame_time: Duration,
+    frame_calls: usize,
+    // (time, calls) per frame
+    history: VecDeque<(Duration, usize)>,
+}
+
+struct ActiveScope {
+    entry_index: usize,
+    start: Instant,
+    child_time: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptProfileSummary {
+    pub vm_index: usize,
+    pub label: Arc<str>,
+    pub average: Duration,
+    pub peak: Duration,
+    pub calls_per_frame: f32,
+}
+
+/// Attributes time spent in Lua to a vm and label.
+/// Nested scopes are excluded from their parent's time.
+#[derive(Default)]
+pub struct ScriptProfiler {
+    enabled: bool,
+    entries: Vec<ProfileEntry>,
+    lookup: HashMap<usize, HashMap<Arc<str>, usize>>,
+    stack: Vec<ActiveScope>,
+}
+
+impl ScriptProfiler {
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Should only be called outside of scopes
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled == enabled {
+            return;
+        }
+
+        self.enabled = enabled;
+        self.stack.clear();
+
+        if !enabled {
+            self.entries.clear();
+            self.lookup.clear();
+        }
+    }
+
+    pub fn begin(&mut self, vm_index: usize, label: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        let labels = self.lookup.entry(vm_index).or_default();
+
+        let entry_index = match labels.get(label) {
+            Some(index) => *index,
+            None => {
+                let label: Arc<str> = label.into();
+                let index = self.entries.len();
+
+                self.entries.push(ProfileEntry {
+                    vm_index,
+                    label: label.clone(),
+                    ..Default::default()
+                });
+
+                labels.insert(label, index);
+                index
+            }
+        };
+
+        self.stack.push(ActiveScope {
+            entry_index,
+            start: Instant::now(),
+            child_time: Duration::ZERO,
+        });
+    }
+
+    pub fn end(&mut self) {
+        let Some(scope) = self.stack.pop() else {
+            return;
+        };
+
+        let elapsed = scope.start.elapsed();
+
+        if let Some(parent) = self.stack.last_mut() {
+            parent.child_time += elapsed;
+        }
+
+        let entry = &mut self.entries[scope.entry_index];
+        entry.frame_time += elapsed.saturating_sub(scope.child_time);
+        entry.frame_calls += 1;
+    }
+
+    pub fn end_frame(&mut self) {
+        for entry in &mut self.entries {
+            if entry.history.len() == HISTORY_LEN {
+                entry.history.pop_front();
+            }
+
+            entry
+                .history
+                .push_back((entry.frame_time, entry.frame_calls));
+            entry.frame_time = Duration::ZERO;
+            entry.frame_calls = 0;
+        }
+    }
+
+    /// Sorted by average time, slowest first
+    pub fn summaries(&self) -> Vec<ScriptProfileSummary> {
+        let mut summaries: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.history.is_empty())
+            .map(|entry| {
+                let frames = entry.history.len() as u32;
+                let total_time: Duration = entry.history.iter().map(|(time, _)| *time).sum();
+                let total_calls: usize = entry.history.iter().map(|(_, calls)| *calls).sum();
+                let peak = entry.history.iter().map(|(time, _)| *time).max();
+
+                ScriptProfileSummary {
+                    vm_index: entry.vm_index,
+                    label: entry.label.clone(),
+                    average: total_time / frames,
+                    peak: peak.unwrap_or_default(),
+                    calls_per_frame: total_calls as f32 / frames as f32,
+                }
+            })
+            .collect();
+
+        summaries.sort_by(|a, b| b.average.cmp(&a.average));
+
+        summaries
+    }
+
+    /// `package_id` resolves a vm index to a package id
+    pub fn export_csv(&self, package_id: impl Fn(usize) -> String) -> String {
+        let mut csv = String::from("package_id,label,average_ms,peak_ms,calls_per_frame\n");
+
+        for summary in self.summaries() {
+            csv += &format!(
+                "{},{},{:.4},{:.4},{:.2}\n",
+                csv_escape(&package_id(summary.vm_index)),
+                csv_escape(&summary.label),
+                summary.average.as_secs_f64() * 1000.0,
+                summary.peak.as_secs_f64() * 1000.0,
+                summary.calls_per_frame
+            );
+        }
+
+        csv
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nested_time_is_exclusive() {
+        let mut profiler = ScriptProfiler::default();
+        profiler.set_enabled(true);
+
+        profiler.begin(0, "outer");
+        std::thread::sleep(Duration::from_millis(2));
+        profiler.begin(1, "inner");
+        std::thread::sleep(Duration::from_millis(20));
+        profiler.end();
+        profiler.end();
+        profiler.end_frame();
+
+        let summaries = profiler.summaries();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!((summaries[0].vm_index, &*summaries[0].label), (1, "inner"));
+        assert!(summaries[1].average < Duration::from_millis(20));
+    }
+
+    #[test]
+    fn disabled_profiler_records_nothing() {
+        let mut profiler = ScriptProfiler::default();
+
+        profiler.begin(0, "update");
+        profiler.end();
+        profiler.end_frame();
+
+        assert!(profiler.summaries().is_empty());
+    }
+
+    #[test]
+    fn calls_are_averaged_across_frames() {
+        let mut profiler = ScriptProfiler::default();
+        profiler.set_enabled(true);
+
+        for _ in 0..3 {
+            profiler.begin(0, "update");
+            profiler.end();
+        }
+
+        profiler.end_frame();
+        profiler.end_frame();
+
+        assert_eq!(profiler.summaries()[0].calls_per_frame, 1.5);
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut profiler = ScriptProfiler::default();
+        profiler.set_enabled(true);
+
+        profiler.begin(0, "a,b");
+        profiler.end();
+        profiler.end_frame();
+
+        let csv = profiler.export_csv(|_| String::from("dev.example"));
+        let mut lines = csv.lines().skip(1);
+
+        assert!(lines.next().unwrap().starts_with("dev.example,\"a,b\","));
+        assert_eq!(lines.next(), None);
+    }
+}
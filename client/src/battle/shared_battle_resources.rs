@@ -1,8 +1,8 @@
-use crate::bindable::{AuxVariable, MathExpr};
+use crate::bindable::{AuxVariable, ElementRegistry, MathExpr};
 use crate::lua_api::BattleVmManager;
 use crate::packages::{PackageInfo, PackageNamespace};
 use crate::render::ui::GlyphAtlas;
-use crate::render::Animator;
+use crate::render::{Animator, TextureAtlasSet};
 use crate::resources::{AssetManager, Globals, ResourcePaths};
 use crate::scenes::BattleEvent;
 use crate::RESOLUTION_F;
@@ -14,12 +14,13 @@ use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use super::{BattleSimulation, StatusRegistry, TileState};
+use super::{BattleSimulation, ScriptProfiler, StatusRegistry, TileState};
 
 /// Resources that are shared between battle snapshots
 pub struct SharedBattleResources {
     pub vm_manager: BattleVmManager,
     pub status_registry: StatusRegistry,
+    pub element_registry: ElementRegistry,
     pub statuses_texture: Arc<Texture>,
     pub statuses_animator: RefCell<Animator>,
     pub alert_animator: RefCell<Animator>,
@@ -30,6 +31,9 @@ pub struct SharedBattleResources {
     pub fade_sprite: Sprite,
     pub event_sender: flume::Sender<BattleEvent>,
     pub event_receiver: flume::Receiver<BattleEvent>,
+    pub script_profiler: RefCell<ScriptProfiler>,
+    pub texture_atlases: TextureAtlasSet,
+    pub team_indicators: TeamIndicators,
 }
 
 impl SharedBattleResources {
@@ -52,6 +56,7 @@ impl SharedBattleResources {
         let mut resources = Self {
             vm_manager: BattleVmManager::new(),
             status_registry: StatusRegistry::new(),
+            element_registry: ElementRegistry::default(),
             statuses_texture: assets.texture(game_io, ResourcePaths::BATTLE_STATUSES),
             statuses_animator: RefCell::new(Animator::load_new(
                 assets,
@@ -66,6 +71,9 @@ impl SharedBattleResources {
             fade_sprite,
             event_sender,
             event_receiver,
+            script_profiler: Default::default(),
+            texture_atlases: Default::default(),
+            team_indicators: TeamIndicators::load(assets),
         };
 
         resources.init(game_io, simulation, dependencies);
@@ -99,6 +107,26 @@ impl SharedBattleResources {
         self.status_registry
             .init(game_io, &self.vm_manager, dependencies);
 
+        // register elements defined by libraries
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        for (package_info, _) in dependencies {
+            if package_info.category != PackageCategory::Library {
+                continue;
+            }
+
+            let library_packages = &globals.library_packages;
+            let package_id = &package_info.id;
+
+            let Some(package) = library_packages.package(package_info.namespace, package_id) else {
+                continue;
+            };
+
+            for definition in &package.elements {
+                self.element_registry.register(definition.clone());
+            }
+        }
+
         // load remaining packages
         BattleVmManager::init(
             game_io,
@@ -1,6 +1,6 @@
 use super::{BattleCallback, SharedBattleResources};
 use crate::bindable::CardProperties;
-use crate::packages::{CardPackage, PackageNamespace};
+use crate::packages::{art_scale, CardPackage, PackageNamespace, CARD_ICON_SIZE};
 use crate::render::SpriteColorQueue;
 use crate::resources::{AssetManager, Globals, ResourcePaths};
 use crate::saves::Card;
@@ -230,7 +230,16 @@ impl StagedItems {
                 StagedItemData::Discard(_) => unreachable!(),
             };
 
+            // card art may be a fallback that needs to be fit to the icon size
+            let scale = match &item.data {
+                StagedItemData::Deck(_) | StagedItemData::Card(_) => {
+                    art_scale(texture.size(), CARD_ICON_SIZE)
+                }
+                _ => Vec2::ONE,
+            };
+
             sprite.set_texture(texture);
+            sprite.set_scale(scale);
             sprite.set_position(position);
             sprite_queue.draw_sprite(&sprite);
 
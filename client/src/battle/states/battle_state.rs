@@ -13,6 +13,9 @@ pub struct BattleState {
     time: FrameTime,
     complete: bool,
     message: Option<(&'static str, FrameTime)>,
+    success: bool,
+    results_time: FrameTime,
+    ready_time: FrameTime, // time since the turn gauge filled
 }
 
 impl State for BattleState {
@@ -76,6 +79,9 @@ impl State for BattleState {
         // process 0 HP
         self.mark_deleted(game_io, resources, simulation);
 
+        // remove expired summons
+        self.update_summons(game_io, resources, simulation);
+
         // new: update living, processes statuses
         self.update_living(game_io, resources, simulation);
 
@@ -101,7 +107,7 @@ impl State for BattleState {
         simulation.battle_time += 1;
         self.time += 1;
 
-        self.detect_success_or_failure(simulation);
+        self.detect_success_or_failure(game_io, resources, simulation);
         self.update_turn_gauge(game_io, simulation);
         self.play_low_hp_sfx(game_io, simulation);
     }
@@ -113,11 +119,11 @@ impl State for BattleState {
         simulation: &mut BattleSimulation,
         sprite_queue: &mut SpriteColorQueue<'a>,
     ) {
-        // win / lose message
-        if let Some((text, start_time)) = self.message {
+        // win / lose message, hidden once a scripted results sequence begins
+        if let Some((text, start_time)) = self.message.filter(|_| self.results_time == 0) {
             const MESSAGE_INTRO_TIME: FrameTime = 10;
 
-            let mut style = TextStyle::new(game_io, FontName::Battle);
+            let mut style = TextStyle::new_unscaled(game_io, FontName::Battle);
             style.letter_spacing = 0.0;
             style.scale.y = inverse_lerp!(0, MESSAGE_INTRO_TIME, simulation.time - start_time);
 
@@ -126,11 +132,36 @@ impl State for BattleState {
             style.bounds.set_position(position);
 
             style.draw(game_io, sprite_queue, text);
+
+            // medals listed under the message
+            if let Some(medals) = &simulation.earned_medals {
+                let mut medal_style = TextStyle::new_unscaled(game_io, FontName::Thick);
+                medal_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+                medal_style.scale.y = style.scale.y;
+
+                let mut y = position.y + size.y * style.scale.y + 4.0;
+
+                for medal in medals {
+                    let label = medal.label();
+                    let medal_size = medal_style.measure(label).size;
+
+                    let x = (RESOLUTION_F.x - medal_size.x) * 0.5;
+                    medal_style.bounds.set_position(Vec2::new(x, y));
+                    medal_style.draw(game_io, sprite_queue, label);
+
+                    y += medal_size.y + 2.0;
+                }
+            }
         } else {
             // turn gauge
             simulation.turn_gauge.draw(sprite_queue);
         }
 
+        // boss health
+        if simulation.boss_bar_entity.is_some() {
+            simulation.boss_health_ui.draw(game_io, sprite_queue);
+        }
+
         // time freeze
         let time_freeze_tracker = &simulation.time_freeze_tracker;
         time_freeze_tracker.draw_ui(game_io, resources, simulation, sprite_queue);
@@ -155,6 +186,9 @@ impl BattleState {
             time: 0,
             complete: false,
             message: None,
+            success: false,
+            results_time: 0,
+            ready_time: 0,
         }
     }
 
@@ -210,6 +244,16 @@ impl BattleState {
             return;
         }
 
+        // force card select open if players are stalling
+        let turn_time = simulation.config.timers.turn_time as FrameTime * 60;
+
+        if turn_time > 0 && self.ready_time >= turn_time {
+            self.complete = true;
+            return;
+        }
+
+        self.ready_time += 1;
+
         let mut player_iter = simulation
             .entities
             .query_mut::<(&Entity, &mut Player)>()
@@ -225,7 +269,12 @@ impl BattleState {
         });
     }
 
-    fn detect_success_or_failure(&mut self, simulation: &mut BattleSimulation) {
+    fn detect_success_or_failure(
+        &mut self,
+        game_io: &GameIO,
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+    ) {
         if simulation.time_freeze_tracker.time_is_frozen() {
             // allow the time freeze action to finish
             return;
@@ -235,11 +284,18 @@ impl BattleState {
 
         if let Some((_, time)) = self.message {
             if simulation.time - time >= TOTAL_MESSAGE_TIME {
-                simulation.exit = true;
+                self.update_results(game_io, resources, simulation);
             }
             return;
         }
 
+        let match_time = simulation.config.timers.match_time as FrameTime * 60;
+
+        if match_time > 0 && simulation.battle_time >= match_time {
+            self.judge(simulation);
+            return;
+        }
+
         // detect failure + find team for success detection
         let local_team;
 
@@ -271,12 +327,111 @@ impl BattleState {
         }
     }
 
+    /// Decides the match by the remaining health of each team, ties are a loss for everyone
+    fn judge(&mut self, simulation: &mut BattleSimulation) {
+        let entities = &mut simulation.entities;
+
+        let Ok(local_team) = entities
+            .query_one_mut::<&Entity>(simulation.local_player_id.into())
+            .map(|entity| entity.team)
+        else {
+            self.fail(simulation);
+            return;
+        };
+
+        // team, health, max health
+        let mut team_health: Vec<(Team, i64, i64)> = Vec::new();
+
+        for (_, (entity, living, _)) in entities.query_mut::<(&Entity, &Living, &Character)>() {
+            if entity.deleted {
+                continue;
+            }
+
+            let health = living.health.max(0) as i64;
+            let max_health = living.max_health.max(1) as i64;
+
+            match team_health
+                .iter_mut()
+                .find(|(team, ..)| *team == entity.team)
+            {
+                Some((_, total, total_max)) => {
+                    *total += health;
+                    *total_max += max_health;
+                }
+                None => team_health.push((entity.team, health, max_health)),
+            }
+        }
+
+        let Some(&(_, local_health, local_max)) =
+            team_health.iter().find(|(team, ..)| *team == local_team)
+        else {
+            self.fail(simulation);
+            return;
+        };
+
+        // compare health ratios without floats to stay deterministic
+        let won = team_health
+            .iter()
+            .filter(|(team, ..)| *team != local_team)
+            .all(|&(_, health, max_health)| local_health * max_health > health * local_max);
+
+        if won {
+            self.succeed(simulation);
+        } else {
+            self.fail(simulation);
+        }
+    }
+
     fn fail(&mut self, simulation: &BattleSimulation) {
         self.message = Some(("<_FAILED_>", simulation.time));
     }
 
-    fn succeed(&mut self, simulation: &BattleSimulation) {
+    fn succeed(&mut self, simulation: &mut BattleSimulation) {
         self.message = Some(("<_SUCCESS_>", simulation.time));
+        self.success = true;
+
+        let medals = simulation
+            .config
+            .medal_criteria
+            .earned(&simulation.statistics);
+        simulation.earned_medals = Some(medals);
+    }
+
+    fn update_results(
+        &mut self,
+        game_io: &GameIO,
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+    ) {
+        if simulation.exit {
+            return;
+        }
+
+        let Some(callback) = simulation.config.results_callback.clone() else {
+            Self::finish(simulation);
+            return;
+        };
+
+        // any player can skip, the script receives one last call to wrap up
+        let skipping = simulation
+            .inputs
+            .iter()
+            .any(|input| input.was_just_pressed(Input::Confirm));
+
+        let params = (self.results_time, skipping, self.success);
+        let finished = callback.call(game_io, resources, simulation, params);
+
+        self.results_time += 1;
+
+        if finished || skipping {
+            Self::finish(simulation);
+        }
+    }
+
+    fn finish(simulation: &mut BattleSimulation) {
+        // taken on the same frame for every client, compared by servers to verify results
+        simulation.statistics.checksum = simulation.state_checksum();
+        simulation.exit = true;
     }
 
     fn detect_battle_start(
@@ -583,6 +738,39 @@ impl BattleState {
         }
 
         for id in pending_deletion {
+            if Character::advance_phase(simulation, id) {
+                continue;
+            }
+
+            Entity::delete(game_io, resources, simulation, id);
+        }
+    }
+
+    fn update_summons(
+        &mut self,
+        game_io: &GameIO,
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+    ) {
+        let mut expired = Vec::new();
+
+        for (_, entity) in simulation.entities.query_mut::<&mut Entity>() {
+            if !entity.spawned || entity.deleted || entity.time_frozen {
+                continue;
+            }
+
+            let Some(lifetime) = &mut entity.summon_lifetime else {
+                continue;
+            };
+
+            *lifetime -= 1;
+
+            if *lifetime <= 0 {
+                expired.push(entity.id);
+            }
+        }
+
+        for id in expired {
             Entity::delete(game_io, resources, simulation, id);
         }
     }
@@ -737,6 +925,22 @@ impl BattleState {
         resources: &SharedBattleResources,
         simulation: &mut BattleSimulation,
     ) {
+        // continue drags that finished moving to the previous tile
+        let dragged_ids: Vec<EntityId> = (simulation.entities)
+            .query_mut::<(&Entity, &Living)>()
+            .into_iter()
+            .filter(|(_, (entity, living))| {
+                !entity.time_frozen && entity.movement.is_none() && living.active_drag.is_some()
+            })
+            .map(|(id, _)| id.into())
+            .collect();
+
+        for id in dragged_ids {
+            Living::step_drag(game_io, resources, simulation, id);
+        }
+
+        simulation.call_pending_callbacks(game_io, resources);
+
         let mut callbacks = Vec::new();
 
         let status_registry = &resources.status_registry;
@@ -782,7 +986,20 @@ impl BattleState {
 
                 // new status callbacks
                 for hit_flag in status_director.take_new_statuses() {
-                    if hit_flag & HitFlag::FLASH != HitFlag::NONE {
+                    let status_name = HitFlag::to_str(status_registry, hit_flag);
+
+                    simulation.battle_log.push(
+                        simulation.time,
+                        BattleLogEvent::StatusApplied {
+                            target: entity.name.clone(),
+                            status: status_name.unwrap_or("Unknown").to_string(),
+                            duration: status_director.remaining_status_time(hit_flag),
+                        },
+                    );
+
+                    if hit_flag & HitFlag::FLASH != HitFlag::NONE
+                        && living.hit_resolution.flash_grants_intangibility
+                    {
                         // apply intangible
 
                         // callback will keep the status director in sync when intangibility is pierced
@@ -176,6 +176,13 @@ impl State for CardSelectState {
 
         self.update_buttons(simulation);
 
+        // auto confirm for players who ran out of time
+        let time_limit = simulation.config.card_select_time_limit;
+
+        if time_limit.is_some_and(|limit| self.time >= limit) {
+            self.confirm_remaining(simulation);
+        }
+
         // completion detection
 
         let all_confirmed = (self.player_selections).iter().all(|selection| {
@@ -331,6 +338,12 @@ impl State for CardSelectState {
             self.ui.draw_names(game_io, simulation, sprite_queue);
         }
 
+        if let Some(limit) = simulation.config.card_select_time_limit {
+            if selection.confirm_time == 0 {
+                self.draw_countdown(game_io, sprite_queue, limit);
+            }
+        }
+
         if !selection.animating_slide && selection.confirm_time != 0 {
             // render text to signal we're waiting on other players
             const MARGIN_TOP: f32 = 38.0;
@@ -378,6 +391,49 @@ impl CardSelectState {
         }
     }
 
+    fn confirm_remaining(&mut self, simulation: &mut BattleSimulation) {
+        for (_, player) in simulation.entities.query_mut::<&mut Player>() {
+            let Some(selection) = self.player_selections.get_mut(player.index) else {
+                continue;
+            };
+
+            if selection.confirm_time != 0 {
+                continue;
+            }
+
+            // close form select, the confirmation is picked up by card input
+            selection.form_open_time = None;
+            selection.form_select_time = None;
+            player.staged_items.set_confirmed(true);
+        }
+    }
+
+    fn draw_countdown(
+        &self,
+        game_io: &GameIO,
+        sprite_queue: &mut SpriteColorQueue,
+        limit: FrameTime,
+    ) {
+        const MARGIN_TOP: f32 = 4.0;
+        const WARNING_TIME: FrameTime = 5 * 60;
+
+        let remaining = (limit - self.time).max(0);
+        let text = format!("{}", (remaining + 59) / 60);
+
+        let mut style = TextStyle::new(game_io, FontName::Thick);
+        style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        if remaining <= WARNING_TIME && (remaining / 15) % 2 == 0 {
+            style.color = Color::ORANGE;
+        }
+
+        let metrics = style.measure(&text);
+        let position = Vec2::new((RESOLUTION_F.x - metrics.size.x) * 0.5, MARGIN_TOP);
+
+        style.bounds.set_position(position);
+        style.draw(game_io, sprite_queue, &text);
+    }
+
     fn handle_input(
         &mut self,
         game_io: &GameIO,
@@ -2,7 +2,7 @@ use super::State;
 use crate::battle::*;
 use crate::bindable::EntityId;
 use crate::render::{FrameTime, SpriteShaderEffect};
-use crate::resources::{Globals, SoundBuffer};
+use crate::resources::{Globals, Input, SoundBuffer};
 use crate::transitions::BATTLE_FADE_DURATION;
 use framework::prelude::*;
 use std::collections::VecDeque;
@@ -39,11 +39,11 @@ impl State for IntroState {
     fn update(
         &mut self,
         game_io: &GameIO,
-        _resources: &SharedBattleResources,
+        resources: &SharedBattleResources,
         simulation: &mut BattleSimulation,
     ) {
-        // first frame setup
-        if simulation.time == 0 {
+        // first frame setup, scripted intros handle visibility themselves
+        if simulation.time == 0 && simulation.config.intro_callback.is_none() {
             use hecs::Without;
 
             let entities = &mut simulation.entities;
@@ -77,6 +77,11 @@ impl State for IntroState {
             simulation.play_music(game_io, &init_music.buffer, init_music.loops);
         }
 
+        if let Some(callback) = simulation.config.intro_callback.clone() {
+            self.update_scripted_intro(game_io, resources, simulation, callback);
+            return;
+        }
+
         let entities = &mut simulation.entities;
 
         for (i, id) in self.tracked_entities.iter().cloned().enumerate() {
@@ -125,8 +130,7 @@ impl State for IntroState {
 
         // mark completion if there's no more entities to introduce
         if self.tracked_entities.is_empty() {
-            self.completed = true;
-            simulation.intro_complete = true;
+            self.complete(simulation);
         }
     }
 }
@@ -139,4 +143,32 @@ impl IntroState {
             tracked_entities: VecDeque::new(),
         }
     }
+
+    fn update_scripted_intro(
+        &mut self,
+        game_io: &GameIO,
+        resources: &SharedBattleResources,
+        simulation: &mut BattleSimulation,
+        callback: BattleCallback<(FrameTime, bool), bool>,
+    ) {
+        // any player can skip, the script receives one last call to wrap up
+        let skipping = simulation
+            .inputs
+            .iter()
+            .any(|input| input.was_just_pressed(Input::Confirm));
+
+        let params = (self.animation_time, skipping);
+        let finished = callback.call(game_io, resources, simulation, params);
+
+        self.animation_time += 1;
+
+        if finished || skipping {
+            self.complete(simulation);
+        }
+    }
+
+    fn complete(&mut self, simulation: &mut BattleSimulation) {
+        self.completed = true;
+        simulation.intro_complete = true;
+    }
 }
@@ -131,7 +131,7 @@ impl State for TurnStartState {
         simulation.turn_gauge.draw(sprite_queue);
 
         // text
-        let mut style = TextStyle::new(game_io, FontName::Battle);
+        let mut style = TextStyle::new_unscaled(game_io, FontName::Battle);
         style.letter_spacing = 0.0;
         style.scale.y = self.resolve_message_scale(simulation);
 
@@ -148,6 +148,13 @@ impl StatusRegistry {
             .map(|item| item.flag)
     }
 
+    pub fn resolve_name(&self, flag: HitFlags) -> Option<&str> {
+        self.list
+            .iter()
+            .find(|item| item.flag == flag)
+            .map(|item| item.name.as_str())
+    }
+
     pub fn registered_list(&self) -> &[RegisteredStatus] {
         &self.list
     }
@@ -0,0 +1,221 @@
+use crate::bindable::Team;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+use serde::Deserialize;
+
+/// Keeps patterns clear of the edges of tiles
+const TILE_INSET: f32 = 3.0;
+const PATTERN_SPACING: f32 = 6.0;
+const PATTERN_THICKNESS: f32 = 2.0;
+
+/// Size of the marker drawn under characters and obstacles
+pub const TEAM_MARKER_SIZE: Vec2 = Vec2::new(18.0, 6.0);
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IndicatorPattern {
+    #[default]
+    None,
+    Stripes,
+    Dots,
+    Checker,
+    Border,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TeamIndicator {
+    pub pattern: IndicatorPattern,
+    pub color: [u8; 4],
+    /// Replaces the pattern with an image, provided by resource packs
+    pub texture: Option<String>,
+}
+
+impl Default for TeamIndicator {
+    fn default() -> Self {
+        Self {
+            pattern: IndicatorPattern::None,
+            color: [255; 4],
+            texture: None,
+        }
+    }
+}
+
+impl TeamIndicator {
+    fn color(&self) -> Color {
+        let [r, g, b, a] = self.color.map(|value| value as f32 / 255.0);
+        Color::new(r, g, b, a)
+    }
+
+    /// Expects the sprite queue to be in multiply mode
+    pub fn draw(&self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, bounds: Rect) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let assets = &globals.assets;
+
+        if let Some(texture_path) = &self.texture {
+            let mut sprite = assets.new_sprite(game_io, texture_path);
+            sprite.set_bounds(bounds);
+            sprite.set_color(self.color());
+            sprite_queue.draw_sprite(&sprite);
+            return;
+        }
+
+        let mut sprite = assets.new_sprite(game_io, ResourcePaths::WHITE_PIXEL);
+        sprite.set_color(self.color());
+
+        let mut draw_rect = |rect: Rect| {
+            sprite.set_bounds(rect);
+            sprite_queue.draw_sprite(&sprite);
+        };
+
+        let end = bounds.position() + bounds.size();
+
+        match self.pattern {
+            IndicatorPattern::None => {}
+            IndicatorPattern::Stripes => {
+                let mut x = bounds.x;
+
+                while x < end.x {
+                    let width = PATTERN_THICKNESS.min(end.x - x);
+                    draw_rect(Rect::new(x, bounds.y, width, bounds.height));
+                    x += PATTERN_SPACING;
+                }
+            }
+            IndicatorPattern::Dots => {
+                let mut y = bounds.y;
+
+                while y + PATTERN_THICKNESS <= end.y {
+                    let mut x = bounds.x;
+
+                    while x + PATTERN_THICKNESS <= end.x {
+                        draw_rect(Rect::new(x, y, PATTERN_THICKNESS, PATTERN_THICKNESS));
+                        x += PATTERN_SPACING;
+                    }
+
+                    y += PATTERN_SPACING;
+                }
+            }
+            IndicatorPattern::Checker => {
+                let cell_size = PATTERN_SPACING * 0.5;
+                let mut y = bounds.y;
+                let mut row = 0;
+
+                while y < end.y {
+                    let height = cell_size.min(end.y - y);
+                    let mut x = bounds.x + (row % 2) as f32 * cell_size;
+
+                    while x < end.x {
+                        let width = cell_size.min(end.x - x);
+                        draw_rect(Rect::new(x, y, width, height));
+                        x += PATTERN_SPACING;
+                    }
+
+                    y += cell_size;
+                    row += 1;
+                }
+            }
+            IndicatorPattern::Border => {
+                let thickness = PATTERN_THICKNESS;
+                let inner_height = (bounds.height - thickness * 2.0).max(0.0);
+
+                draw_rect(Rect::new(bounds.x, bounds.y, bounds.width, thickness));
+                draw_rect(Rect::new(
+                    bounds.x,
+                    end.y - thickness,
+                    bounds.width,
+                    thickness,
+                ));
+                draw_rect(Rect::new(
+                    bounds.x,
+                    bounds.y + thickness,
+                    thickness,
+                    inner_height,
+                ));
+                draw_rect(Rect::new(
+                    end.x - thickness,
+                    bounds.y + thickness,
+                    thickness,
+                    inner_height,
+                ));
+            }
+        }
+    }
+
+    /// Draws over a tile, `bounds` covers the whole tile
+    pub fn draw_over_tile(
+        &self,
+        game_io: &GameIO,
+        sprite_queue: &mut SpriteColorQueue,
+        bounds: Rect,
+    ) {
+        let inset = Vec2::splat(TILE_INSET);
+        let size = (bounds.size() - inset * 2.0).max(Vec2::ZERO);
+        let bounds = Rect::new(bounds.x + inset.x, bounds.y + inset.y, size.x, size.y);
+
+        self.draw(game_io, sprite_queue, bounds);
+    }
+}
+
+/// Colorblind safe indicators for each team, loaded from a table resource packs can override
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TeamIndicators {
+    red: TeamIndicator,
+    blue: TeamIndicator,
+    other: TeamIndicator,
+}
+
+impl TeamIndicators {
+    pub fn load(assets: &impl AssetManager) -> Self {
+        let text = assets.text(ResourcePaths::BATTLE_TEAM_INDICATORS);
+
+        match toml::from_str(&text) {
+            Ok(indicators) => indicators,
+            Err(err) => {
+                log::warn!("Failed to parse team indicators: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn get(&self, team: Team) -> Option<&TeamIndicator> {
+        match team {
+            Team::Red => Some(&self.red),
+            Team::Blue => Some(&self.blue),
+            Team::Other => Some(&self.other),
+            Team::Unset => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_indicator_table() {
+        let indicators: TeamIndicators = toml::from_str(
+            r#"
+            [Red]
+            pattern = "Stripes"
+            color = [255, 255, 255, 150]
+
+            [Blue]
+            texture = "pack/blue.png"
+            "#,
+        )
+        .unwrap();
+
+        let red = indicators.get(Team::Red).unwrap();
+        assert_eq!(red.pattern, IndicatorPattern::Stripes);
+        assert_eq!(red.color, [255, 255, 255, 150]);
+
+        let blue = indicators.get(Team::Blue).unwrap();
+        assert_eq!(blue.texture.as_deref(), Some("pack/blue.png"));
+        assert_eq!(blue.color, [255; 4]);
+
+        let other = indicators.get(Team::Other).unwrap();
+        assert_eq!(other.pattern, IndicatorPattern::None);
+        assert!(indicators.get(Team::Unset).is_none());
+    }
+}
@@ -611,7 +611,7 @@ impl TimeFreezeTracker {
 
         // drawing bar
         if self.can_processing_action_counter() {
-            let text_style = TextStyle::new(game_io, FontName::Thick);
+            let text_style = TextStyle::new_unscaled(game_io, FontName::Thick);
             position.x -= BAR_WIDTH * 0.5;
             position.y += text_style.line_height() + text_style.line_spacing;
 
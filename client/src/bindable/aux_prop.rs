@@ -1,6 +1,6 @@
 use super::{
-    CardClass, CardProperties, Comparison, Element, GenerationalIndex, HitFlags, HitProperties,
-    MathExpr,
+    card_codes_match, CardClass, CardProperties, Comparison, Element, ElementRegistry,
+    GenerationalIndex, HitFlags, HitProperties, MathExpr,
 };
 use crate::battle::{BattleCallback, Character, Entity, Player, SharedBattleResources};
 use crate::lua_api::{create_action_table, VM_INDEX_REGISTRY_KEY};
@@ -318,6 +318,7 @@ impl AuxEffect {
                 let callback = BattleCallback::new_transformed_lua_callback(
                     lua,
                     lua.named_registry_value(VM_INDEX_REGISTRY_KEY)?,
+                    "intercept_action",
                     table.get(2)?,
                     |_, lua, index| lua.pack_multi(create_action_table(lua, index)?),
                 )?;
@@ -327,6 +328,7 @@ impl AuxEffect {
                 let callback = BattleCallback::new_transformed_lua_callback(
                     lua,
                     lua.named_registry_value(VM_INDEX_REGISTRY_KEY)?,
+                    "interrupt_action",
                     table.get(2)?,
                     |_, lua, index| lua.pack_multi(create_action_table(lua, index)?),
                 )?;
@@ -455,13 +457,14 @@ impl AuxProp {
 
     pub fn hit_passes_tests(
         &mut self,
+        element_registry: &ElementRegistry,
         entity: &Entity,
         health: i32,
         max_health: i32,
         hit_props: &HitProperties,
     ) -> bool {
         let previously_passing = self.passed_all_tests();
-        self.process_hit(entity, health, max_health, hit_props);
+        self.process_hit(element_registry, entity, health, max_health, hit_props);
         let now_passing = self.passed_all_tests();
 
         // reset
@@ -506,7 +509,9 @@ impl AuxProp {
                 AuxRequirement::CardHitFlags(flags) => {
                     card.is_some_and(|card| card.hit_flags & *flags == *flags)
                 }
-                AuxRequirement::CardCode(code) => card.is_some_and(|card| card.code == *code),
+                AuxRequirement::CardCode(code) => {
+                    card.is_some_and(|card| card_codes_match(&card.code, code))
+                }
                 AuxRequirement::CardClass(class) => {
                     card.is_some_and(|card| card.card_class == *class)
                 }
@@ -538,7 +543,9 @@ impl AuxProp {
                 AuxRequirement::CardHitFlags(flags) => {
                     card.is_some_and(|card| card.hit_flags & *flags == *flags)
                 }
-                AuxRequirement::CardCode(code) => card.is_some_and(|card| card.code == *code),
+                AuxRequirement::CardCode(code) => {
+                    card.is_some_and(|card| card_codes_match(&card.code, code))
+                }
                 AuxRequirement::CardClass(class) => {
                     card.is_some_and(|card| card.card_class == *class)
                 }
@@ -567,6 +574,7 @@ impl AuxProp {
 
     pub fn process_hit(
         &mut self,
+        element_registry: &ElementRegistry,
         entity: &Entity,
         health: i32,
         max_health: i32,
@@ -578,8 +586,7 @@ impl AuxProp {
                     hit_props.element == *element || hit_props.secondary_element == *element
                 }
                 AuxRequirement::HitElementIsWeakness => {
-                    entity.element.is_weak_to(hit_props.element)
-                        || entity.element.is_weak_to(hit_props.secondary_element)
+                    hit_props.is_super_effective(element_registry, entity.element)
                 }
                 AuxRequirement::HitFlag(hit_flag) => hit_props.flags & *hit_flag == *hit_flag,
                 AuxRequirement::HitDamage(cmp, damage) => cmp.compare(hit_props.damage, *damage),
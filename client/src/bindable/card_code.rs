@@ -0,0 +1,21 @@
+/// Matches every other code in card selection and aux props, ownership still requires the exact code
+pub const WILDCARD_CODE: &str = "*";
+
+pub fn card_codes_match(a: &str, b: &str) -> bool {
+    a == b || a == WILDCARD_CODE || b == WILDCARD_CODE
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wildcard_matches_any_code() {
+        assert!(card_codes_match("A", "A"));
+        assert!(card_codes_match("*", "A"));
+        assert!(card_codes_match("B", "*"));
+        assert!(card_codes_match("*", "*"));
+        assert!(!card_codes_match("A", "B"));
+        assert!(!card_codes_match("A", ""));
+    }
+}
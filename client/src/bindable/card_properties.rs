@@ -64,7 +64,7 @@ impl<H> CardProperties<H> {
         scale: Vec2,
         center: bool,
     ) -> TextStyle {
-        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::Thick);
         text_style.monospace = true;
         text_style.bounds.set_position(position);
 
@@ -148,7 +148,7 @@ impl<H> CardProperties<H> {
         sprite_tree.insert_root_child(element_node);
 
         // code
-        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::Thick);
         text_style.letter_spacing = 2.0;
 
         text_style.color = Color::YELLOW;
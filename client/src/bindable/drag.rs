@@ -4,6 +4,10 @@ use super::Direction;
 pub struct Drag {
     pub direction: Direction,
     pub distance: u32,
+    /// Damage taken when an obstacle stops the drag early
+    pub wall_damage: i32,
+    /// Entities blocking the drag are dragged the remaining distance
+    pub chain: bool,
 }
 
 impl<'lua> rollback_mlua::FromLua<'lua> for Drag {
@@ -25,6 +29,8 @@ impl<'lua> rollback_mlua::FromLua<'lua> for Drag {
         Ok(Drag {
             direction: table.get("direction").unwrap_or_default(),
             distance: table.get("distance").unwrap_or_default(),
+            wall_damage: table.get("wall_damage").unwrap_or_default(),
+            chain: table.get("chain").unwrap_or_default(),
         })
     }
 }
@@ -37,6 +43,8 @@ impl<'lua> rollback_mlua::IntoLua<'lua> for Drag {
         let table = lua.create_table()?;
         table.set("direction", self.direction)?;
         table.set("distance", self.distance)?;
+        table.set("wall_damage", self.wall_damage)?;
+        table.set("chain", self.chain)?;
 
         Ok(rollback_mlua::Value::Table(table))
     }
@@ -0,0 +1,61 @@
+use num_derive::FromPrimitive;
+
+#[repr(u8)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
+pub enum EaseMode {
+    #[default]
+    Linear,
+    In,
+    Out,
+    InOut,
+}
+
+impl EaseMode {
+    /// Maps linear progress \[0.0, 1.0\] to eased progress
+    pub fn apply(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            EaseMode::Linear => progress,
+            EaseMode::In => crate::ease::ease_in(progress),
+            EaseMode::Out => crate::ease::ease_out(progress),
+            EaseMode::InOut => crate::ease::ease_in_out(progress),
+        }
+    }
+}
+
+impl<'lua> rollback_mlua::FromLua<'lua> for EaseMode {
+    fn from_lua(
+        lua_value: rollback_mlua::Value<'lua>,
+        _lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<Self> {
+        use num_traits::FromPrimitive;
+
+        let number = match lua_value {
+            rollback_mlua::Value::Number(number) => number as u8,
+            rollback_mlua::Value::Integer(number) => number as u8,
+            _ => {
+                return Err(rollback_mlua::Error::FromLuaConversionError {
+                    from: lua_value.type_name(),
+                    to: "EaseMode",
+                    message: None,
+                })
+            }
+        };
+
+        EaseMode::from_u8(number).ok_or(rollback_mlua::Error::FromLuaConversionError {
+            from: lua_value.type_name(),
+            to: "EaseMode",
+            message: None,
+        })
+    }
+}
+
+impl<'lua> rollback_mlua::IntoLua<'lua> for EaseMode {
+    fn into_lua(
+        self,
+        _lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<rollback_mlua::Value<'lua>> {
+        Ok(rollback_mlua::Value::Integer(self as _))
+    }
+}
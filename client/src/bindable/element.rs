@@ -1,8 +1,7 @@
-use num_derive::FromPrimitive;
+use std::collections::HashMap;
 use strum::Display;
 
-#[repr(u8)]
-#[derive(PartialEq, Eq, Default, Clone, Copy, FromPrimitive, Display, Debug)]
+#[derive(PartialEq, Eq, Hash, Default, Clone, Copy, Display, Debug)]
 pub enum Element {
     #[default]
     None,
@@ -16,9 +15,54 @@ pub enum Element {
     Summon,
     Plus,
     Break,
+    /// Elements defined by packages, identified by a hash of the lowercase name
+    /// to stay consistent between clients with different packages installed
+    Custom(u16),
 }
 
 impl Element {
+    const BUILT_IN: [Element; 11] = [
+        Element::None,
+        Element::Fire,
+        Element::Aqua,
+        Element::Elec,
+        Element::Wood,
+        Element::Sword,
+        Element::Wind,
+        Element::Cursor,
+        Element::Summon,
+        Element::Plus,
+        Element::Break,
+    ];
+
+    /// Ids at or above this value represent custom elements
+    const CUSTOM_ID_OFFSET: u32 = 256;
+
+    pub fn custom(name: &str) -> Self {
+        // FNV-1a, folded to 16 bits
+        let mut hash: u32 = 0x811c9dc5;
+
+        for byte in name.to_lowercase().bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+
+        Element::Custom(((hash >> 16) ^ (hash & 0xFFFF)) as u16)
+    }
+
+    /// Index into the built in element sprite sheet, custom elements use their own icons
+    pub fn built_in_index(self) -> Option<usize> {
+        Self::BUILT_IN.iter().position(|element| *element == self)
+    }
+
+    /// Stable numeric id, matches the value seen by Lua
+    pub fn id(self) -> u32 {
+        match self {
+            Element::Custom(id) => Self::CUSTOM_ID_OFFSET + id as u32,
+            _ => self.built_in_index().unwrap_or_default() as u32,
+        }
+    }
+
     pub fn is_weak_to(self, other: Element) -> bool {
         matches!(
             (self, other),
@@ -34,6 +78,62 @@ impl Element {
     }
 }
 
+/// An element defined by a package through `[[elements]]` in its package.toml
+#[derive(Clone)]
+pub struct ElementDefinition {
+    pub element: Element,
+    pub name: String,
+    pub icon_texture_path: String,
+    /// Elements dealing double damage to this element
+    pub weaknesses: Vec<Element>,
+    /// Elements dealing half damage to this element
+    pub resistances: Vec<Element>,
+    /// Elements taking double damage from this element
+    pub strengths: Vec<Element>,
+}
+
+#[derive(Default, Clone)]
+pub struct ElementRegistry {
+    definitions: HashMap<Element, ElementDefinition>,
+}
+
+impl ElementRegistry {
+    /// Later definitions replace earlier definitions for the same element
+    pub fn register(&mut self, definition: ElementDefinition) {
+        self.definitions.insert(definition.element, definition);
+    }
+
+    pub fn definition(&self, element: Element) -> Option<&ElementDefinition> {
+        self.definitions.get(&element)
+    }
+
+    pub fn name(&self, element: Element) -> String {
+        match self.definition(element) {
+            Some(definition) => definition.name.clone(),
+            None => element.to_string(),
+        }
+    }
+
+    pub fn is_weak_to(&self, defender: Element, attacker: Element) -> bool {
+        if defender.is_weak_to(attacker) {
+            return true;
+        }
+
+        let defined_weakness = (self.definition(defender))
+            .is_some_and(|definition| definition.weaknesses.contains(&attacker));
+
+        let defined_strength = (self.definition(attacker))
+            .is_some_and(|definition| definition.strengths.contains(&defender));
+
+        defined_weakness || defined_strength
+    }
+
+    pub fn resists(&self, defender: Element, attacker: Element) -> bool {
+        (self.definition(defender))
+            .is_some_and(|definition| definition.resistances.contains(&attacker))
+    }
+}
+
 impl From<String> for Element {
     fn from(s: String) -> Self {
         Self::from(s.as_str())
@@ -53,7 +153,8 @@ impl From<&str> for Element {
             "summon" => Element::Summon,
             "plus" => Element::Plus,
             "break" => Element::Break,
-            _ => Element::None,
+            "" | "none" => Element::None,
+            name => Element::custom(name),
         }
     }
 }
@@ -63,10 +164,9 @@ impl<'lua> rollback_mlua::FromLua<'lua> for Element {
         lua_value: rollback_mlua::Value<'lua>,
         _lua: &'lua rollback_mlua::Lua,
     ) -> rollback_mlua::Result<Self> {
-        use num_traits::FromPrimitive;
-
         let number = match lua_value {
             rollback_mlua::Value::Number(number) => number,
+            rollback_mlua::Value::Integer(number) => number as f64,
             _ => {
                 return Err(rollback_mlua::Error::FromLuaConversionError {
                     from: lua_value.type_name(),
@@ -76,7 +176,17 @@ impl<'lua> rollback_mlua::FromLua<'lua> for Element {
             }
         };
 
-        Element::from_u8(number as u8).ok_or(rollback_mlua::Error::FromLuaConversionError {
+        let number = number as u32;
+
+        let element = if number >= Self::CUSTOM_ID_OFFSET {
+            u16::try_from(number - Self::CUSTOM_ID_OFFSET)
+                .ok()
+                .map(Element::Custom)
+        } else {
+            Self::BUILT_IN.get(number as usize).cloned()
+        };
+
+        element.ok_or(rollback_mlua::Error::FromLuaConversionError {
             from: lua_value.type_name(),
             to: "Element",
             message: None,
@@ -89,6 +199,43 @@ impl<'lua> rollback_mlua::IntoLua<'lua> for Element {
         self,
         _lua: &'lua rollback_mlua::Lua,
     ) -> rollback_mlua::Result<rollback_mlua::Value<'lua>> {
-        Ok(rollback_mlua::Value::Number(self as u8 as f64))
+        Ok(rollback_mlua::Value::Number(self.id() as f64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn custom_elements_from_names() {
+        assert_eq!(Element::from("Fire"), Element::Fire);
+        assert_eq!(Element::from(""), Element::None);
+        assert_eq!(Element::from("Ice"), Element::from("ICE"));
+        assert_ne!(Element::from("Ice"), Element::from("Light"));
+        assert!(Element::from("Ice").id() >= Element::CUSTOM_ID_OFFSET);
+    }
+
+    #[test]
+    fn registry_relationships() {
+        let ice = Element::custom("ice");
+        let mut registry = ElementRegistry::default();
+
+        registry.register(ElementDefinition {
+            element: ice,
+            name: String::from("Ice"),
+            icon_texture_path: String::new(),
+            weaknesses: vec![Element::Fire],
+            resistances: vec![Element::Aqua],
+            strengths: vec![Element::Wood],
+        });
+
+        assert!(registry.is_weak_to(ice, Element::Fire));
+        assert!(registry.is_weak_to(Element::Wood, ice));
+        assert!(registry.is_weak_to(Element::Aqua, Element::Elec));
+        assert!(!registry.is_weak_to(ice, Element::Aqua));
+        assert!(registry.resists(ice, Element::Aqua));
+        assert_eq!(registry.name(ice), "Ice");
+        assert_eq!(registry.name(Element::Fire), "Fire");
     }
 }
@@ -62,6 +62,28 @@ pub mod HitFlag {
         }
     }
 
+    pub fn to_str(status_registry: &StatusRegistry, flag: HitFlags) -> Option<&str> {
+        let name = match flag {
+            RETAIN_INTANGIBLE => "RetainIntangible",
+            NO_COUNTER => "NoCounter",
+            DRAG => "Drag",
+            IMPACT => "Impact",
+            FLINCH => "Flinch",
+            FLASH => "Flash",
+            SHAKE => "Shake",
+            PIERCE_INVIS => "PierceInvis",
+            PIERCE_GUARD => "PierceGuard",
+            PIERCE_GROUND => "PierceGround",
+            PARALYZE => "Paralyze",
+            ROOT => "Root",
+            BLIND => "Blind",
+            CONFUSE => "Confuse",
+            _ => return status_registry.resolve_name(flag),
+        };
+
+        Some(name)
+    }
+
     pub const BUILT_IN_STATUSES: [HitFlags; 4] = [PARALYZE, ROOT, BLIND, CONFUSE];
 
     pub fn status_animation_state(flag: HitFlags) -> &'static str {
@@ -41,8 +41,13 @@ impl HitProperties {
             && self.flags & HitFlag::DRAG != HitFlag::NONE
     }
 
-    pub fn is_super_effective(&self, element: Element) -> bool {
-        element.is_weak_to(self.element) || element.is_weak_to(self.secondary_element)
+    pub fn is_super_effective(&self, registry: &ElementRegistry, element: Element) -> bool {
+        registry.is_weak_to(element, self.element)
+            || registry.is_weak_to(element, self.secondary_element)
+    }
+
+    pub fn is_resisted(&self, registry: &ElementRegistry, element: Element) -> bool {
+        registry.resists(element, self.element) || registry.resists(element, self.secondary_element)
     }
 }
 
@@ -0,0 +1,135 @@
+use num_derive::FromPrimitive;
+
+/// Steps taken to resolve a hit's flags after damage is calculated
+#[repr(u8)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, FromPrimitive)]
+pub enum HitStep {
+    /// Paralyzes a counterable entity hit by an Impact without NoCounter
+    Counter,
+    /// Applies statuses, including Flinch and Flash
+    Statuses,
+    /// Slides the entity in the direction of the hit's Drag
+    Drag,
+}
+
+impl<'lua> rollback_mlua::FromLua<'lua> for HitStep {
+    fn from_lua(
+        lua_value: rollback_mlua::Value<'lua>,
+        _lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<Self> {
+        use num_traits::FromPrimitive;
+
+        let number = match lua_value {
+            rollback_mlua::Value::Integer(number) => number as u8,
+            rollback_mlua::Value::Number(number) => number as u8,
+            _ => {
+                return Err(rollback_mlua::Error::FromLuaConversionError {
+                    from: lua_value.type_name(),
+                    to: "HitStep",
+                    message: None,
+                })
+            }
+        };
+
+        HitStep::from_u8(number).ok_or(rollback_mlua::Error::FromLuaConversionError {
+            from: lua_value.type_name(),
+            to: "HitStep",
+            message: None,
+        })
+    }
+}
+
+impl<'lua> rollback_mlua::IntoLua<'lua> for HitStep {
+    fn into_lua(
+        self,
+        _lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<rollback_mlua::Value<'lua>> {
+        Ok(rollback_mlua::Value::Integer(
+            self as u8 as rollback_mlua::Integer,
+        ))
+    }
+}
+
+/// How hit flags interact with each other and the entity's current state
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HitResolution {
+    /// Steps run in order for every hit, steps left out are skipped
+    pub order: Vec<HitStep>,
+    /// Drag replaces movement in progress, otherwise Drag is ignored while moving
+    pub drag_cancels_movement: bool,
+    /// Flinch cancels movement, unless the entity is being dragged
+    pub flinch_cancels_movement: bool,
+    /// Flinch cancels the entity's actions
+    pub flinch_cancels_actions: bool,
+    /// Flinch is ignored while the entity has Flash
+    pub flash_blocks_flinch: bool,
+    /// Flash makes the entity intangible until the status ends
+    pub flash_grants_intangibility: bool,
+}
+
+impl Default for HitResolution {
+    fn default() -> Self {
+        Self {
+            order: vec![HitStep::Counter, HitStep::Statuses, HitStep::Drag],
+            drag_cancels_movement: false,
+            flinch_cancels_movement: true,
+            flinch_cancels_actions: true,
+            flash_blocks_flinch: false,
+            flash_grants_intangibility: true,
+        }
+    }
+}
+
+impl HitResolution {
+    /// Applies fields set in the table, other fields are left unchanged
+    pub fn update_from_lua_table(
+        &mut self,
+        table: rollback_mlua::Table,
+    ) -> rollback_mlua::Result<()> {
+        if let Some(order) = table.get("order")? {
+            self.order = order;
+        }
+
+        if let Some(value) = table.get("drag_cancels_movement")? {
+            self.drag_cancels_movement = value;
+        }
+
+        if let Some(value) = table.get("flinch_cancels_movement")? {
+            self.flinch_cancels_movement = value;
+        }
+
+        if let Some(value) = table.get("flinch_cancels_actions")? {
+            self.flinch_cancels_actions = value;
+        }
+
+        if let Some(value) = table.get("flash_blocks_flinch")? {
+            self.flash_blocks_flinch = value;
+        }
+
+        if let Some(value) = table.get("flash_grants_intangibility")? {
+            self.flash_grants_intangibility = value;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'lua> rollback_mlua::IntoLua<'lua> for &HitResolution {
+    fn into_lua(
+        self,
+        lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<rollback_mlua::Value<'lua>> {
+        let table = lua.create_table()?;
+        table.set("order", self.order.clone())?;
+        table.set("drag_cancels_movement", self.drag_cancels_movement)?;
+        table.set("flinch_cancels_movement", self.flinch_cancels_movement)?;
+        table.set("flinch_cancels_actions", self.flinch_cancels_actions)?;
+        table.set("flash_blocks_flinch", self.flash_blocks_flinch)?;
+        table.set(
+            "flash_grants_intangibility",
+            self.flash_grants_intangibility,
+        )?;
+
+        Ok(rollback_mlua::Value::Table(table))
+    }
+}
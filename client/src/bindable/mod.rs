@@ -1,14 +1,17 @@
 mod action_lockout;
 mod animator_playback_mode;
 mod audio_behavior;
+mod audio_bus;
 mod aux_prop;
 mod card_class;
+mod card_code;
 mod card_properties;
 mod character_rank;
 mod comparison;
 mod component_lifetime;
 mod defense_priority;
 mod drag;
+mod ease_mode;
 mod element;
 mod emotion;
 mod entity_id;
@@ -17,12 +20,14 @@ mod generational_index;
 mod hit_context;
 mod hit_flags;
 mod hit_properties;
+mod hit_resolution;
 mod input_query;
 mod intangible_rule;
 mod lua_color;
 mod lua_vector;
 mod math_expression;
 mod movement;
+mod particle_emitter_props;
 mod sprite_color_mode;
 mod team;
 mod tile_highlight;
@@ -30,14 +35,17 @@ mod tile_highlight;
 pub use action_lockout::*;
 pub use animator_playback_mode::*;
 pub use audio_behavior::*;
+pub use audio_bus::*;
 pub use aux_prop::*;
 pub use card_class::*;
+pub use card_code::*;
 pub use card_properties::*;
 pub use character_rank::*;
 pub use comparison::*;
 pub use component_lifetime::*;
 pub use defense_priority::*;
 pub use drag::*;
+pub use ease_mode::*;
 pub use element::*;
 pub use emotion::*;
 pub use entity_id::*;
@@ -46,12 +54,14 @@ pub use generational_index::*;
 pub use hit_context::*;
 pub use hit_flags::*;
 pub use hit_properties::*;
+pub use hit_resolution::*;
 pub use input_query::*;
 pub use intangible_rule::*;
 pub use lua_color::*;
 pub use lua_vector::*;
 pub use math_expression::*;
 pub use movement::*;
+pub use particle_emitter_props::*;
 pub use sprite_color_mode::*;
 pub use team::*;
 pub use tile_highlight::*;
@@ -131,7 +131,7 @@ impl<'lua> rollback_mlua::FromLua<'lua> for Movement {
         let on_begin: Option<rollback_mlua::Function> = table.raw_get("on_begin_func")?;
         let vm_index = lua.named_registry_value(VM_INDEX_REGISTRY_KEY)?;
         let on_begin = on_begin
-            .map(|func| BattleCallback::new_lua_callback(lua, vm_index, func))
+            .map(|func| BattleCallback::new_lua_callback(lua, vm_index, "on_begin_func", func))
             .transpose()?;
 
         Ok(Self {
@@ -0,0 +1,93 @@
+use super::LuaVector;
+use crate::render::FrameTime;
+use framework::prelude::Vec2;
+
+#[derive(Clone)]
+pub struct ParticleEmitterProps {
+    pub texture_path: String,
+    pub animation_path: String,
+    pub state: String,
+    pub spawn_rate: FrameTime,
+    pub spawn_count: usize,
+    pub lifetime: FrameTime,
+    pub offset: Vec2,
+    pub min_velocity: Vec2,
+    pub max_velocity: Vec2,
+    pub acceleration: Vec2,
+}
+
+impl Default for ParticleEmitterProps {
+    fn default() -> Self {
+        Self {
+            texture_path: String::new(),
+            animation_path: String::new(),
+            state: String::from("DEFAULT"),
+            spawn_rate: 1,
+            spawn_count: 1,
+            lifetime: 30,
+            offset: Vec2::ZERO,
+            min_velocity: Vec2::ZERO,
+            max_velocity: Vec2::ZERO,
+            acceleration: Vec2::ZERO,
+        }
+    }
+}
+
+impl<'lua> rollback_mlua::FromLua<'lua> for ParticleEmitterProps {
+    fn from_lua(
+        lua_value: rollback_mlua::Value<'lua>,
+        _lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<Self> {
+        let table = match lua_value {
+            rollback_mlua::Value::Table(table) => table,
+            _ => {
+                return Err(rollback_mlua::Error::FromLuaConversionError {
+                    from: lua_value.type_name(),
+                    to: "ParticleEmitterProps",
+                    message: None,
+                })
+            }
+        };
+
+        let default_props = ParticleEmitterProps::default();
+
+        let read_vector =
+            |key: &str| -> Option<Vec2> { table.get::<_, LuaVector>(key).ok().map(Vec2::from) };
+
+        let min_velocity = read_vector("min_velocity").unwrap_or_default();
+
+        let props = ParticleEmitterProps {
+            texture_path: table.get("texture_path")?,
+            animation_path: table.get("animation_path")?,
+            state: table.get("state").unwrap_or(default_props.state),
+            spawn_rate: (table.get("spawn_rate").unwrap_or(default_props.spawn_rate)).max(1),
+            spawn_count: table
+                .get("spawn_count")
+                .unwrap_or(default_props.spawn_count),
+            lifetime: table.get("lifetime").unwrap_or(default_props.lifetime),
+            offset: read_vector("offset").unwrap_or_default(),
+            min_velocity,
+            // a fixed velocity can be set by only providing min_velocity
+            max_velocity: read_vector("max_velocity").unwrap_or(min_velocity),
+            acceleration: read_vector("acceleration").unwrap_or_default(),
+        };
+
+        // velocities are sampled between min and max, every vector must be finite to sample
+        let vectors = [
+            props.offset,
+            props.min_velocity,
+            props.max_velocity,
+            props.acceleration,
+        ];
+
+        if !vectors.iter().all(|vector| vector.is_finite()) {
+            return Err(rollback_mlua::Error::FromLuaConversionError {
+                from: "table",
+                to: "ParticleEmitterProps",
+                message: Some(String::from("vectors must be finite")),
+            });
+        }
+
+        Ok(props)
+    }
+}
@@ -14,6 +14,26 @@ pub fn quadratic(progress: f32) -> f32 {
     symmetric(2.0, progress)
 }
 
+pub fn ease_in(progress: f32) -> f32 {
+    progress * progress
+}
+
+pub fn ease_out(progress: f32) -> f32 {
+    let x = 1.0 - progress;
+
+    1.0 - x * x
+}
+
+pub fn ease_in_out(progress: f32) -> f32 {
+    if progress < 0.5 {
+        2.0 * progress * progress
+    } else {
+        let x = -2.0 * progress + 2.0;
+
+        1.0 - x * x * 0.5
+    }
+}
+
 /// returns progress from value \[0.0, 1.0\]
 macro_rules! inverse_lerp {
     ($start:expr, $end:expr, $value:expr) => {{
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Local, TimeZone};
+
+/// Seconds since the unix epoch in local time, empty if out of range
+pub fn format_timestamp(secs: u64) -> String {
+    format_local(
+        Local.timestamp_opt(secs as i64, 0).single(),
+        "%Y-%m-%d %H:%M",
+    )
+}
+
+/// Milliseconds since the unix epoch in local time, includes seconds
+pub fn format_timestamp_millis(millis: u64) -> String {
+    format_local(
+        Local.timestamp_millis_opt(millis as i64).single(),
+        "%Y-%m-%d %H:%M:%S",
+    )
+}
+
+fn format_local(time: Option<DateTime<Local>>, format: &str) -> String {
+    time.map(|time| time.format(format).to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn out_of_range_is_empty() {
+        assert_eq!(format_timestamp(u64::MAX / 2), "");
+        assert_eq!(format_timestamp(0).len(), "1970-01-01 00:00".len());
+        assert_eq!(
+            format_timestamp_millis(0).len(),
+            "1970-01-01 00:00:00".len()
+        );
+    }
+}
@@ -8,6 +8,14 @@ pub async fn request(uri: &str) -> Option<Vec<u8>> {
     response.body_bytes().await.ok()
 }
 
+pub async fn upload(uri: &str, body: Vec<u8>) -> bool {
+    let Ok(response) = surf::post(uri).body_bytes(body).await else {
+        return false;
+    };
+
+    response.status().is_success()
+}
+
 pub async fn request_json(uri: &str) -> Option<serde_json::Value> {
     let response_vec = request(uri).await?;
     serde_json::from_slice::<serde_json::Value>(&response_vec).ok()
@@ -14,10 +14,12 @@
 // unit structs may eventually gain properties
 #![allow(clippy::default_constructed_unit_structs)]
 
+mod accessibility;
 mod args;
 mod battle;
 mod bindable;
 mod ease;
+mod format_util;
 mod http;
 mod lua_api;
 mod memoize;
@@ -36,13 +38,16 @@ mod transitions;
 use crate::args::Args;
 use crate::overlays::*;
 use crate::render::PostProcessAdjust;
+use crate::render::PostProcessClipRecorder;
 use crate::render::PostProcessColorBlindness;
+use crate::render::PostProcessFlashReduction;
 use crate::render::PostProcessGhosting;
+use crate::render::PostProcessScreenshot;
 use crate::resources::*;
 use crate::scenes::BootScene;
 use clap::Parser;
-use framework::logging::*;
 use framework::prelude::*;
+use packets::structures::PackageCategory;
 use rand::seq::SliceRandom;
 use supporting_service::*;
 
@@ -57,13 +62,44 @@ pub fn main(app: WinitPlatformApp) -> anyhow::Result<()> {
     ResourcePaths::init_game_folder(&app);
 
     let args = Args::parse();
+    let battle_test_paths = args.test.clone();
+    let offer_profile_select = args.profile.is_none();
+    let battle_replay_path = args.replay.clone();
+
+    if let [category, name] = args.new_package.as_slice() {
+        let category = PackageCategory::from(category.as_str());
+        let path = packages::create_package_template(category, name).map_err(anyhow::Error::msg)?;
+        println!("Created {path}");
+        return Ok(());
+    }
+
+    if let Some(folder) = &args.lua_api_reference {
+        lua_api::write_lua_api_reference(folder).map_err(anyhow::Error::msg)?;
+        println!("Wrote the Lua API reference to {folder}");
+        return Ok(());
+    }
+
+    let log_filters = match args.log.as_deref().map(LogFilters::parse) {
+        Some(Ok(filters)) => filters,
+        Some(Err(e)) => {
+            eprintln!("Invalid --log value: {e}");
+            LogFilters::default()
+        }
+        None => LogFilters::default(),
+    };
 
     let (log_sender, log_receiver) = flume::unbounded();
-    default_logger::init_with_listener!(move |log| {
+    ClientLogger::init(log_filters, move |log| {
         let _ = log_sender.send(log);
     });
 
+    CrashReporter::install_panic_hook();
+
+    let profile = SaveProfiles::resolve(args.profile.as_deref());
+    ResourcePaths::init_profile(&profile);
+
     log::info!("Version {}", env!("CARGO_PKG_VERSION"));
+    log::info!("Profile {}", SaveProfiles::display_name(&profile));
 
     let random_title = TITLE_LIST.choose(&mut rand::thread_rng()).unwrap();
     let game = Game::<WinitGameLoop>::new(random_title, TRUE_RESOLUTION.into())
@@ -74,11 +110,26 @@ pub fn main(app: WinitPlatformApp) -> anyhow::Result<()> {
             game_io.set_resource(globals);
         })
         .with_service(SupportingService::new)
+        .with_post_process(|game_io| PostProcessScreenshot::new(game_io))
+        .with_post_process(|game_io| PostProcessClipRecorder::new(game_io))
+        .with_post_process(|game_io| PostProcessFlashReduction::new(game_io))
         .with_post_process(|game_io| PostProcessGhosting::new(game_io))
         .with_post_process(|game_io| PostProcessAdjust::new(game_io))
         .with_post_process(|game_io| PostProcessColorBlindness::new(game_io))
+        .with_overlay(GameOverlayTarget::Render, |game_io| {
+            InputDisplayOverlay::new(game_io)
+        })
+        .with_overlay(GameOverlayTarget::Render, |game_io| {
+            NotificationOverlay::new(game_io)
+        })
+        .with_overlay(GameOverlayTarget::Render, |game_io| {
+            ControllerPromptOverlay::new(game_io)
+        })
         .with_overlay(GameOverlayTarget::Render, |game_io| {
             DebugOverlay::new(game_io)
+        })
+        .with_overlay(GameOverlayTarget::Render, |game_io| {
+            ProfilerOverlay::new(game_io)
         });
 
     #[cfg(target_os = "android")]
@@ -86,7 +137,12 @@ pub fn main(app: WinitPlatformApp) -> anyhow::Result<()> {
         MobileOverlay::new(game_io)
     });
 
-    game.run(|game_io| BootScene::new(game_io, log_receiver))?;
+    game.run(|game_io| {
+        BootScene::new(game_io, log_receiver)
+            .with_battle_tests(battle_test_paths)
+            .with_battle_replay(battle_replay_path)
+            .with_profile_select(offer_profile_select)
+    })?;
 
     Ok(())
 }
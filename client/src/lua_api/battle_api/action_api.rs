@@ -8,7 +8,9 @@ use super::sprite_api::create_sprite_table;
 use super::tile_api::create_tile_table;
 use super::*;
 use crate::battle::*;
-use crate::bindable::{CardProperties, EntityId, GenerationalIndex, SpriteColorMode};
+use crate::bindable::{
+    card_codes_match, CardProperties, EntityId, GenerationalIndex, SpriteColorMode,
+};
 use crate::lua_api::helpers::inherit_metatable;
 use crate::render::{DerivedFrame, FrameTime, SpriteNode};
 use crate::resources::Globals;
@@ -60,6 +62,12 @@ pub fn inject_action_api(lua_api: &mut BattleLuaApi) {
         },
     );
 
+    lua_api.add_dynamic_function(CARD_PROPERTIES_TABLE, "codes_match", |_, lua, params| {
+        let (a, b): (rollback_mlua::String, rollback_mlua::String) = lua.unpack_multi(params)?;
+
+        lua.pack_multi(card_codes_match(a.to_str()?, b.to_str()?))
+    });
+
     lua_api.add_dynamic_function(ACTION_TABLE, "from_card", move |api_ctx, lua, params| {
         let (entity_table, card_props): (rollback_mlua::Table, CardProperties) =
             lua.unpack_multi(params)?;
@@ -154,7 +162,12 @@ pub fn inject_action_api(lua_api: &mut BattleLuaApi) {
             let actions = &mut api_ctx.simulation.actions;
             let action = actions.get_mut(id).ok_or_else(action_not_found)?;
 
-            let callback = BattleCallback::new_lua_callback(lua, api_ctx.vm_index, callback)?;
+            let callback = BattleCallback::new_lua_callback(
+                lua,
+                api_ctx.vm_index,
+                "add_anim_action",
+                callback,
+            )?;
 
             action.frame_callbacks.push((frame.max(1) - 1, callback));
 
@@ -299,6 +312,7 @@ fn inject_step_api(lua_api: &mut BattleLuaApi) {
             BattleCallback::new_transformed_lua_callback(
                 lua,
                 api_ctx.vm_index,
+                UPDATE_FN,
                 callback,
                 move |_, lua, _| {
                     let table: rollback_mlua::Table = lua.registry_value(&key)?;
@@ -485,6 +499,7 @@ fn shared_attachment_constructor<'lua>(
     Ok(table)
 }
 
+#[track_caller]
 fn getter<F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
@@ -508,6 +523,7 @@ where
     });
 }
 
+#[track_caller]
 fn setter<F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
@@ -527,9 +543,10 @@ where
     });
 }
 
+#[track_caller]
 fn callback_setter<G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -564,6 +581,7 @@ fn callback_setter<G, P, F, R>(
                 BattleCallback::new_transformed_lua_callback(
                     lua,
                     api_ctx.vm_index,
+                    name,
                     callback,
                     move |api_ctx, lua, p| {
                         let api_ctx = &mut *api_ctx.borrow_mut();
@@ -225,6 +225,7 @@ pub fn inject_animation_api(lua_api: &mut BattleLuaApi) {
     );
 }
 
+#[track_caller]
 fn getter<F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
@@ -248,6 +249,7 @@ where
     });
 }
 
+#[track_caller]
 fn setter<F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
@@ -271,6 +273,7 @@ where
     });
 }
 
+#[track_caller]
 fn updater<F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
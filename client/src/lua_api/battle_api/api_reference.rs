@@ -0,0 +1,586 @@
+// Generates a reference of the Lua API for editor autocomplete, used by `--lua-api-reference`
+//
+// Names come from the BattleLuaApi registry, parameters are read from the source at each registration,
+// so this must be run from a source checkout
+
+use super::{BattleLuaApi, LuaApiEntryKind, GLOBAL_TABLE};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// How many lines past a registration to search for its parameters
+const MAX_REGISTRATION_LINES: usize = 40;
+
+#[derive(Default, Serialize)]
+struct TableReference {
+    name: String,
+    functions: Vec<FunctionReference>,
+    fields: Vec<FieldReference>,
+}
+
+#[derive(Serialize)]
+struct FunctionReference {
+    name: String,
+    /// Called with `:`, passing the table as `self`
+    method: bool,
+    /// None if the parameters couldn't be determined, treated as variadic
+    params: Option<Vec<ParamReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forwards_to: Option<String>,
+    source: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct ParamReference {
+    name: String,
+    #[serde(rename = "type")]
+    lua_type: String,
+}
+
+#[derive(Serialize)]
+struct FieldReference {
+    name: String,
+    #[serde(rename = "type")]
+    lua_type: String,
+    readable: bool,
+    writable: bool,
+    source: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Signature {
+    method: bool,
+    params: Option<Vec<ParamReference>>,
+    forwards_to: Option<String>,
+}
+
+/// Writes `lua_api.json` and `lua_api.lua` into the folder
+pub fn write_lua_api_reference(folder: &str) -> Result<(), String> {
+    let lua_api = BattleLuaApi::new();
+    let mut sources = SourceCache::default();
+    let mut tables: BTreeMap<String, TableReference> = BTreeMap::new();
+
+    // registration lines per file, to know where each registration ends
+    let mut registration_lines: HashMap<&str, Vec<u32>> = HashMap::new();
+
+    for entry in lua_api.entries() {
+        let lines = registration_lines.entry(entry.location.file()).or_default();
+        lines.push(entry.location.line());
+    }
+
+    for lines in registration_lines.values_mut() {
+        lines.sort();
+        lines.dedup();
+    }
+
+    for entry in lua_api.entries() {
+        let file = entry.location.file();
+        let line = entry.location.line();
+
+        let end_line = registration_lines[file]
+            .iter()
+            .find(|other_line| **other_line > line)
+            .map(|other_line| *other_line as usize - 1)
+            .unwrap_or(usize::MAX)
+            .min(line as usize + MAX_REGISTRATION_LINES);
+
+        let source = sources.read(file, line as usize, end_line);
+        let location = format!("{file}:{line}");
+
+        let table = tables.entry(entry.table_path.to_string()).or_default();
+        table.name = entry.table_path.to_string();
+
+        match entry.kind {
+            LuaApiEntryKind::Function => {
+                let signature = source.as_deref().map(parse_signature).unwrap_or_default();
+
+                table.functions.push(FunctionReference {
+                    name: entry.name.to_string(),
+                    method: signature.method,
+                    params: signature.params,
+                    forwards_to: signature.forwards_to,
+                    source: location,
+                });
+            }
+            LuaApiEntryKind::Getter | LuaApiEntryKind::Setter => {
+                let field = match table.fields.iter_mut().find(|f| f.name == entry.name) {
+                    Some(field) => field,
+                    None => {
+                        table.fields.push(FieldReference {
+                            name: entry.name.to_string(),
+                            lua_type: field_type(entry.name),
+                            readable: false,
+                            writable: false,
+                            source: location,
+                        });
+                        table.fields.last_mut().unwrap()
+                    }
+                };
+
+                if entry.kind == LuaApiEntryKind::Getter {
+                    field.readable = true;
+                } else {
+                    field.writable = true;
+                }
+            }
+        }
+    }
+
+    let mut tables: Vec<_> = tables.into_values().collect();
+
+    for table in &mut tables {
+        table.functions.sort_by(|a, b| a.name.cmp(&b.name));
+        table.fields.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    std::fs::create_dir_all(folder).map_err(|e| format!("Failed to create {folder:?}: {e}"))?;
+
+    let json_path = Path::new(folder).join("lua_api.json");
+    let json = serde_json::to_string_pretty(&tables).map_err(|e| e.to_string())?;
+    std::fs::write(&json_path, json).map_err(|e| format!("Failed to write {json_path:?}: {e}"))?;
+
+    let stubs_path = Path::new(folder).join("lua_api.lua");
+    std::fs::write(&stubs_path, generate_stubs(&tables))
+        .map_err(|e| format!("Failed to write {stubs_path:?}: {e}"))?;
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct SourceCache {
+    files: HashMap<String, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    /// Joins lines from `start_line` to `end_line`, line numbers start at 1
+    fn read(&mut self, file: &str, start_line: usize, end_line: usize) -> Option<String> {
+        let lines = self
+            .files
+            .entry(file.to_string())
+            .or_insert_with(|| {
+                // paths are relative to the workspace when the workspace is built
+                let workspace_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .parent()
+                    .map(|workspace| workspace.join(file));
+
+                [Some(PathBuf::from(file)), workspace_path]
+                    .into_iter()
+                    .flatten()
+                    .find_map(|path| std::fs::read_to_string(path).ok())
+                    .map(|text| text.lines().map(String::from).collect())
+            })
+            .as_ref()?;
+
+        let lines = lines.get(start_line.checked_sub(1)?..end_line.min(lines.len()))?;
+
+        Some(lines.join("\n"))
+    }
+}
+
+fn field_type(name: &str) -> String {
+    if name.ends_with("_func") {
+        String::from("function")
+    } else {
+        String::from("any")
+    }
+}
+
+fn generate_stubs(tables: &[TableReference]) -> String {
+    let mut stubs = String::from("---@meta\n");
+
+    for table in tables {
+        let is_global = table.name == GLOBAL_TABLE;
+
+        let _ = writeln!(stubs);
+
+        if !is_global {
+            let _ = writeln!(stubs, "---@class {}", table.name);
+
+            for field in &table.fields {
+                let _ = writeln!(stubs, "---@field {} {}", field.name, field.lua_type);
+            }
+
+            let _ = writeln!(stubs, "{} = {{}}", table.name);
+        }
+
+        for function in &table.functions {
+            let _ = writeln!(stubs);
+
+            if let Some(forwards_to) = &function.forwards_to {
+                let _ = writeln!(stubs, "--- Shorthand for `{forwards_to}`");
+            }
+
+            let param_names = match &function.params {
+                Some(params) => {
+                    for param in params {
+                        let _ = writeln!(stubs, "---@param {} {}", param.name, param.lua_type);
+                    }
+
+                    params
+                        .iter()
+                        .map(|param| param.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+                None => String::from("..."),
+            };
+
+            let path = if is_global {
+                function.name.clone()
+            } else if function.method {
+                format!("{}:{}", table.name, function.name)
+            } else {
+                format!("{}.{}", table.name, function.name)
+            };
+
+            let _ = writeln!(stubs, "function {path}({param_names}) end");
+        }
+    }
+
+    stubs
+}
+
+/// Reads the parameters from the source of a registration,
+/// starting at the line the function is registered
+fn parse_signature(source: &str) -> Signature {
+    if source.contains("add_convenience_method(") {
+        // the base table is a constant, leaving the getter and method
+        let literals: Vec<_> = source.split('"').skip(1).step_by(2).collect();
+
+        return Signature {
+            method: true,
+            params: None,
+            forwards_to: match literals.as_slice() {
+                [getter, method, ..] => Some(format!("self:{getter}():{method}(...)")),
+                _ => None,
+            },
+        };
+    }
+
+    let Some(closure_params) = source.split('|').nth(1) else {
+        return Signature::default();
+    };
+
+    let closure_params = split_top_level(closure_params, ',');
+
+    let [_, _, param] = closure_params.as_slice() else {
+        return Signature::default();
+    };
+
+    if param.starts_with('_') || *param == "()" {
+        // unused params
+        return Signature {
+            method: false,
+            params: Some(Vec::new()),
+            forwards_to: None,
+        };
+    }
+
+    if *param != "params" {
+        // registered through a helper, the helper unpacks self
+        return Signature {
+            method: true,
+            params: parse_binding(param),
+            forwards_to: None,
+        };
+    }
+
+    let Some(binding) = source
+        .split_once("lua.unpack_multi(params)")
+        .and_then(|(before, _)| before.trim_end().strip_suffix('='))
+        .and_then(|before| before.rsplit_once("let "))
+        .map(|(_, binding)| binding.trim())
+    else {
+        return Signature::default();
+    };
+
+    let Some(mut params) = parse_binding(binding) else {
+        return Signature::default();
+    };
+
+    let method = params
+        .first()
+        .is_some_and(|param| matches!(param.name.as_str(), "table" | "self_table" | "_"));
+
+    if method {
+        params.remove(0);
+    }
+
+    Signature {
+        method,
+        params: Some(params),
+        forwards_to: None,
+    }
+}
+
+/// Parses `(a, b): (A, B)` or `a: A`, untyped bindings are given the `any` type
+fn parse_binding(binding: &str) -> Option<Vec<ParamReference>> {
+    let (pattern, rust_type) = match split_type_annotation(binding) {
+        Some((pattern, rust_type)) => (pattern, Some(rust_type)),
+        None => (binding, None),
+    };
+
+    let names = match pattern.strip_prefix('(') {
+        Some(pattern) => split_top_level(pattern.strip_suffix(')')?, ','),
+        None => vec![pattern],
+    };
+
+    let types = match rust_type {
+        Some(rust_type) if rust_type.starts_with('(') => {
+            let rust_type = rust_type.strip_prefix('(')?.strip_suffix(')')?;
+            split_top_level(rust_type, ',')
+                .into_iter()
+                .map(Some)
+                .collect()
+        }
+        rust_type => vec![rust_type],
+    };
+
+    let names: Vec<String> = match names.as_slice() {
+        // a single binding for multiple values, `offset: (f32, f32)`
+        [name] if types.len() > 1 => (1..=types.len())
+            .map(|i| format!("{}_{i}", param_name(name)))
+            .collect(),
+        names if names.len() == types.len() => names.iter().map(|name| param_name(name)).collect(),
+        _ => return None,
+    };
+
+    let params = names
+        .into_iter()
+        .zip(types)
+        .map(|(name, rust_type)| {
+            let lua_type = rust_type
+                .map(lua_type)
+                .unwrap_or_else(|| String::from("any"));
+
+            if lua_type == "..." {
+                return ParamReference {
+                    name: lua_type,
+                    lua_type: String::from("any"),
+                };
+            }
+
+            ParamReference { name, lua_type }
+        })
+        .collect();
+
+    Some(params)
+}
+
+fn param_name(binding: &str) -> String {
+    let name = binding.trim_start_matches("mut ");
+
+    match name.strip_prefix('_') {
+        Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Finds the `:` separating a binding from its type, skipping paths such as `a::b`
+fn split_type_annotation(binding: &str) -> Option<(&str, &str)> {
+    let bytes = binding.as_bytes();
+    let mut depth = 0;
+
+    for (i, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'(' | b'<' => depth += 1,
+            b')' | b'>' => depth -= 1,
+            b':' if depth == 0 => {
+                let path_separator =
+                    bytes.get(i + 1) == Some(&b':') || (i > 0 && bytes[i - 1] == b':');
+
+                if !path_separator {
+                    return Some((binding[..i].trim(), binding[i + 1..].trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = text[start..].trim();
+
+    if !last.is_empty() {
+        parts.push(last);
+    }
+
+    parts
+}
+
+/// Converts a Rust type to a LuaLS annotation type, returns `...` for variadic types
+fn lua_type(rust_type: &str) -> String {
+    let rust_type = rust_type.trim().trim_start_matches('&').trim();
+    let rust_type = rust_type.strip_prefix("mut ").unwrap_or(rust_type);
+
+    let (outer, inner) = match rust_type.split_once('<') {
+        Some((outer, inner)) => (outer, inner.strip_suffix('>')),
+        None => (rust_type, None),
+    };
+
+    // skip lifetimes, only using the first type parameter
+    let inner = inner.and_then(|inner| {
+        split_top_level(inner, ',')
+            .into_iter()
+            .find(|param| !param.starts_with('\''))
+    });
+
+    let name = outer.rsplit("::").next().unwrap_or(outer);
+
+    match (name, inner) {
+        ("Option", Some(inner)) => format!("{}?", lua_type(inner)),
+        ("Vec", Some(inner)) => format!("{}[]", lua_type(inner)),
+        ("MultiValue" | "Variadic", _) => String::from("..."),
+        ("Table", _) => String::from("table"),
+        ("String" | "str" | "StdString", _) => String::from("string"),
+        ("Function", _) => String::from("function"),
+        ("bool", _) => String::from("boolean"),
+        ("Value" | "()", _) => String::from("any"),
+        ("f32" | "f64" | "Number", _) => String::from("number"),
+        (
+            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize"
+            | "Integer" | "FrameTime",
+            _,
+        ) => String::from("integer"),
+        (name, _) if name.starts_with('(') => String::from("any"),
+        (name, _) => {
+            // LuaVector -> Vector
+            match name.strip_prefix("Lua") {
+                Some(stripped) if stripped.starts_with(char::is_uppercase) => stripped.to_string(),
+                _ => name.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn param(name: &str, lua_type: &str) -> ParamReference {
+        ParamReference {
+            name: name.to_string(),
+            lua_type: lua_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn rust_to_lua_types() {
+        assert_eq!(lua_type("rollback_mlua::Table<'lua>"), "table");
+        assert_eq!(lua_type("Option<rollback_mlua::Function>"), "function?");
+        assert_eq!(lua_type("Vec<LuaVector>"), "Vector[]");
+        assert_eq!(lua_type("&str"), "string");
+        assert_eq!(lua_type("FrameTime"), "integer");
+        assert_eq!(lua_type("rollback_mlua::MultiValue"), "...");
+        assert_eq!(lua_type("Direction"), "Direction");
+    }
+
+    #[test]
+    fn unpacked_method_params() {
+        let source = r#"lua_api.add_dynamic_function(SPRITE_TABLE, "create_text_node", |api_ctx, lua, params| {
+        let (table, text_style_table, text): (
+            rollback_mlua::Table,
+            rollback_mlua::Table,
+            Option<rollback_mlua::String>,
+        ) = lua.unpack_multi(params)?;"#;
+
+        assert_eq!(
+            parse_signature(source),
+            Signature {
+                method: true,
+                params: Some(vec![
+                    param("text_style_table", "table"),
+                    param("text", "string?")
+                ]),
+                forwards_to: None
+            }
+        );
+    }
+
+    #[test]
+    fn unpacked_static_params() {
+        let source = r#"lua_api.add_dynamic_function(SHARED_HITBOX_TABLE, "new", |api_ctx, lua, params| {
+        let (entity_table, lifetime): (rollback_mlua::Table, Option<FrameTime>) =
+            lua.unpack_multi(params)?;"#;
+
+        assert_eq!(
+            parse_signature(source),
+            Signature {
+                method: false,
+                params: Some(vec![
+                    param("entity_table", "table"),
+                    param("lifetime", "integer?")
+                ]),
+                forwards_to: None
+            }
+        );
+    }
+
+    #[test]
+    fn helper_params() {
+        let source = r#"setter(lua_api, "set_name", |entity: &mut Entity, _, name| {"#;
+
+        assert_eq!(
+            parse_signature(source).params,
+            Some(vec![param("name", "any")])
+        );
+
+        let source = r#"getter(lua_api, "name", |entity: &Entity, lua, _: ()| {"#;
+
+        assert_eq!(parse_signature(source).params, Some(Vec::new()));
+
+        let source = r#"setter(lua_api, "set_offset", |node, _, offset: (f32, f32)| {"#;
+
+        assert_eq!(
+            parse_signature(source).params,
+            Some(vec![
+                param("offset_1", "number"),
+                param("offset_2", "number")
+            ])
+        );
+
+        let source = r#"setter(
+        lua_api,
+        "set_offset",
+        |entity: &mut Entity, _, (x, y): (f32, f32)| {"#;
+
+        assert_eq!(
+            parse_signature(source),
+            Signature {
+                method: true,
+                params: Some(vec![param("x", "number"), param("y", "number")]),
+                forwards_to: None
+            }
+        );
+    }
+
+    #[test]
+    fn convenience_method() {
+        let source =
+            r#"lua_api.add_convenience_method(ENTITY_TABLE, "animation", "set_state", None);"#;
+
+        assert_eq!(
+            parse_signature(source).forwards_to.as_deref(),
+            Some("self:animation():set_state(...)")
+        );
+    }
+}
@@ -162,6 +162,7 @@ pub fn create_augment_table(
     Ok(table)
 }
 
+#[track_caller]
 fn getter<F, P, R>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     R: for<'lua> rollback_mlua::IntoLua<'lua>,
@@ -187,6 +188,7 @@ where
     });
 }
 
+#[track_caller]
 fn setter<F, P, R>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     R: for<'lua> rollback_mlua::IntoLuaMulti<'lua>,
@@ -216,9 +218,10 @@ where
     });
 }
 
+#[track_caller]
 fn callback_setter<G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -260,6 +263,7 @@ fn callback_setter<G, P, F, R>(
                 BattleCallback::new_transformed_lua_callback(
                     lua,
                     api_ctx.vm_index,
+                    name,
                     callback,
                     move |_, lua, p| {
                         let table: rollback_mlua::Table = lua.registry_value(&key)?;
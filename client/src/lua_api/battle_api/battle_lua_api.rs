@@ -3,9 +3,11 @@
 // and rust does not allow the type parameter to store dynamic/anonymous lifetimes
 
 use crate::battle::BattleScriptContext;
+use crate::resources::{Globals, ProfileSegment};
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::panic::Location;
 use std::rc::Rc;
 
 type LuaApiFn = dyn for<'lua> Fn(
@@ -17,10 +19,12 @@ type LuaApiFn = dyn for<'lua> Fn(
 struct LuaApiFunction {
     function: Box<LuaApiFn>,
     is_getter: bool,
+    /// Where the function was registered, used to generate the API reference
+    location: &'static Location<'static>,
 }
 
 impl LuaApiFunction {
-    fn new<F>(func: F) -> Self
+    fn new<F>(func: F, location: &'static Location<'static>) -> Self
     where
         F: 'static
             + for<'lua> Fn(
@@ -32,10 +36,11 @@ impl LuaApiFunction {
         Self {
             function: Box::new(func),
             is_getter: false,
+            location,
         }
     }
 
-    fn new_getter<F>(func: F) -> Self
+    fn new_getter<F>(func: F, location: &'static Location<'static>) -> Self
     where
         F: 'static
             + for<'lua> Fn(
@@ -47,6 +52,7 @@ impl LuaApiFunction {
         Self {
             function: Box::new(func),
             is_getter: true,
+            location,
         }
     }
 }
@@ -56,6 +62,22 @@ const NEWINDEX_CALLBACK: u8 = 2;
 
 type DynamicFunctionMap = HashMap<(u8, Cow<'static, str>), LuaApiFunction>;
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LuaApiEntryKind {
+    Function,
+    /// Read through indexing, `table.name`
+    Getter,
+    /// Written through assignment, `table.name = value`
+    Setter,
+}
+
+pub struct LuaApiEntry<'a> {
+    pub table_path: &'a str,
+    pub name: &'a str,
+    pub kind: LuaApiEntryKind,
+    pub location: &'static Location<'static>,
+}
+
 pub struct BattleLuaApi {
     static_function_injectors: Vec<Box<dyn Fn(&rollback_mlua::Lua) -> rollback_mlua::Result<()>>>,
     dynamic_functions: Vec<DynamicFunctionMap>,
@@ -76,11 +98,15 @@ impl BattleLuaApi {
         super::require_api::inject_require_api(&mut lua_api);
         super::resources_api::inject_engine_api(&mut lua_api);
         super::turn_gauge_api::inject_turn_gauge_api(&mut lua_api);
+        super::element_api::inject_element_api(&mut lua_api);
+        super::camera_api::inject_camera_api(&mut lua_api);
         super::entity_api::inject_entity_api(&mut lua_api);
         super::player_form_api::inject_player_form_api(&mut lua_api);
         super::card_select_button_api::inject_card_select_button_api(&mut lua_api);
         super::card_select_api::inject_card_select_api(&mut lua_api);
         super::component_api::inject_component_api(&mut lua_api);
+        super::particle_emitter_api::inject_particle_emitter_api(&mut lua_api);
+        super::rng_api::inject_rng_api(&mut lua_api);
         super::action_api::inject_action_api(&mut lua_api);
         super::movement_api::inject_movement_api(&mut lua_api);
         super::augment_api::inject_augment_api(&mut lua_api);
@@ -105,6 +131,7 @@ impl BattleLuaApi {
         self.static_function_injectors.push(Box::new(injector));
     }
 
+    #[track_caller]
     pub fn add_dynamic_function<F>(&mut self, table_path: &str, function_name: &str, func: F)
     where
         F: 'static
@@ -125,7 +152,7 @@ impl BattleLuaApi {
 
         let prev = self.dynamic_functions[index].insert(
             (INDEX_CALLBACK, Cow::Owned(function_name.to_string())),
-            LuaApiFunction::new(func),
+            LuaApiFunction::new(func, Location::caller()),
         );
 
         if prev.is_some() {
@@ -133,6 +160,7 @@ impl BattleLuaApi {
         }
     }
 
+    #[track_caller]
     pub fn add_dynamic_getter<F>(&mut self, table_path: &str, function_name: &str, func: F)
     where
         F: 'static
@@ -153,7 +181,7 @@ impl BattleLuaApi {
 
         let prev = self.dynamic_functions[index].insert(
             (INDEX_CALLBACK, Cow::Owned(function_name.to_string())),
-            LuaApiFunction::new_getter(func),
+            LuaApiFunction::new_getter(func, Location::caller()),
         );
 
         if prev.is_some() {
@@ -165,6 +193,7 @@ impl BattleLuaApi {
         }
     }
 
+    #[track_caller]
     pub fn add_dynamic_setter<F>(&mut self, table_path: &str, function_name: &str, func: F)
     where
         F: 'static
@@ -185,7 +214,7 @@ impl BattleLuaApi {
 
         let prev = self.dynamic_functions[index].insert(
             (NEWINDEX_CALLBACK, Cow::Owned(function_name.to_string())),
-            LuaApiFunction::new(func),
+            LuaApiFunction::new(func, Location::caller()),
         );
 
         if prev.is_some() {
@@ -197,6 +226,7 @@ impl BattleLuaApi {
         }
     }
 
+    #[track_caller]
     pub fn add_convenience_method(
         &mut self,
         base_table_name: &'static str,
@@ -220,6 +250,31 @@ impl BattleLuaApi {
         });
     }
 
+    /// Every dynamic function, getter, and setter, static injectors are not included
+    pub fn entries(&self) -> impl Iterator<Item = LuaApiEntry<'_>> {
+        self.table_paths
+            .iter()
+            .zip(&self.dynamic_functions)
+            .flat_map(|(table_path, functions)| {
+                functions.iter().map(|((callback_type, name), function)| {
+                    let kind = if *callback_type == NEWINDEX_CALLBACK {
+                        LuaApiEntryKind::Setter
+                    } else if function.is_getter {
+                        LuaApiEntryKind::Getter
+                    } else {
+                        LuaApiEntryKind::Function
+                    };
+
+                    LuaApiEntry {
+                        table_path,
+                        name,
+                        kind,
+                        location: function.location,
+                    }
+                })
+            })
+    }
+
     /// Should be called on lua vm creation after static functions are created on the api struct
     pub fn inject_static(&self, lua: &rollback_mlua::Lua) -> rollback_mlua::Result<()> {
         for table_path in &self.table_paths {
@@ -376,11 +431,12 @@ impl BattleLuaApi {
     }
 
     /// Should be called anytime a lua function must be called, wrap the call in the wrapped_fn
-    /// Automatically logs errors
+    /// Automatically logs errors, time spent is attributed to the label in the script profiler
     pub fn inject_dynamic<'lua, F>(
         &self,
         lua: &'lua rollback_mlua::Lua,
         api_ctx: &RefCell<BattleScriptContext>,
+        label: &str,
         wrapped_fn: F,
     ) where
         F: FnOnce(&'lua rollback_mlua::Lua) -> rollback_mlua::Result<()>,
@@ -393,11 +449,30 @@ impl BattleLuaApi {
             std::mem::transmute::<DynamicApiCtx, DynamicApiCtx<'static, 'static>>(dynamic_api)
         }));
 
+        let (vm_index, resources, game_io) = {
+            let api_ctx = api_ctx.borrow();
+            (api_ctx.vm_index, api_ctx.resources, api_ctx.game_io)
+        };
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals
+            .frame_profiler
+            .borrow_mut()
+            .begin(ProfileSegment::Lua);
+
+        resources
+            .script_profiler
+            .borrow_mut()
+            .begin(vm_index, label);
+
         // call the function
         if let Err(err) = wrapped_fn(lua) {
             log::error!("{err}");
         }
 
+        resources.script_profiler.borrow_mut().end();
+        globals.frame_profiler.borrow_mut().end();
+
         // cleanup
         lua.remove_app_data::<DynamicApiCtx>();
 
@@ -139,8 +139,8 @@ function Buster.new(user, charged, damage)
                 hit_y = hit_y / 2
                 state = "CHARGED_HIT"
             else
-                hit_x = entity:sprite():width() * (math.random(0, 1) - 0.5)
-                hit_y = math.random() * hit_y
+                hit_x = entity:sprite():width() * (spell:rng():random(0, 1) - 0.5)
+                hit_y = spell:rng():random() * hit_y
             end
 
             local hit_artifact = Artifact.new()
@@ -0,0 +1,102 @@
+use super::{BattleLuaApi, BATTLE_TABLE, CAMERA_TABLE};
+use crate::bindable::{EaseMode, EntityId, LuaVector};
+use crate::lua_api::helpers::inherit_metatable;
+use crate::render::FrameTime;
+use framework::prelude::Vec2;
+
+pub fn inject_camera_api(lua_api: &mut BattleLuaApi) {
+    lua_api.add_dynamic_function(BATTLE_TABLE, "camera", |_, lua, _| {
+        let table = lua.create_table()?;
+        inherit_metatable(lua, CAMERA_TABLE, &table)?;
+
+        lua.pack_multi(table)
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "position", |api_ctx, lua, _| {
+        let api_ctx = api_ctx.borrow();
+        let battle_camera = &api_ctx.simulation.battle_camera;
+
+        lua.pack_multi(LuaVector::from(battle_camera.position()))
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "zoom_level", |api_ctx, lua, _| {
+        let api_ctx = api_ctx.borrow();
+        let battle_camera = &api_ctx.simulation.battle_camera;
+
+        lua.pack_multi(battle_camera.zoom())
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "shake", |api_ctx, lua, params| {
+        let (_, power, duration): (rollback_mlua::Table, f32, FrameTime) =
+            lua.unpack_multi(params)?;
+
+        let mut api_ctx = api_ctx.borrow_mut();
+        let battle_camera = &mut api_ctx.simulation.battle_camera;
+        battle_camera.shake(power, duration);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "zoom", |api_ctx, lua, params| {
+        let (_, zoom, duration, ease): (
+            rollback_mlua::Table,
+            f32,
+            Option<FrameTime>,
+            Option<EaseMode>,
+        ) = lua.unpack_multi(params)?;
+
+        let mut api_ctx = api_ctx.borrow_mut();
+        let battle_camera = &mut api_ctx.simulation.battle_camera;
+        battle_camera.set_zoom(zoom, duration.unwrap_or_default(), ease.unwrap_or_default());
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "pan", |api_ctx, lua, params| {
+        let (_, x, y, duration, ease): (
+            rollback_mlua::Table,
+            f32,
+            f32,
+            Option<FrameTime>,
+            Option<EaseMode>,
+        ) = lua.unpack_multi(params)?;
+
+        let mut api_ctx = api_ctx.borrow_mut();
+        let battle_camera = &mut api_ctx.simulation.battle_camera;
+        battle_camera.pan(
+            Vec2::new(x, y),
+            duration.unwrap_or_default(),
+            ease.unwrap_or_default(),
+        );
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "focus", |api_ctx, lua, params| {
+        let (_, entity_table, duration, ease): (
+            rollback_mlua::Table,
+            rollback_mlua::Table,
+            Option<FrameTime>,
+            Option<EaseMode>,
+        ) = lua.unpack_multi(params)?;
+
+        let id: EntityId = entity_table.raw_get("#id")?;
+
+        let mut api_ctx = api_ctx.borrow_mut();
+        let battle_camera = &mut api_ctx.simulation.battle_camera;
+        battle_camera.focus(id, duration.unwrap_or_default(), ease.unwrap_or_default());
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(CAMERA_TABLE, "reset", |api_ctx, lua, params| {
+        let (_, duration, ease): (rollback_mlua::Table, Option<FrameTime>, Option<EaseMode>) =
+            lua.unpack_multi(params)?;
+
+        let mut api_ctx = api_ctx.borrow_mut();
+        let battle_camera = &mut api_ctx.simulation.battle_camera;
+        battle_camera.reset(duration.unwrap_or_default(), ease.unwrap_or_default());
+
+        lua.pack_multi(())
+    });
+}
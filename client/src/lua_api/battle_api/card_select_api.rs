@@ -191,6 +191,7 @@ pub fn inject_card_select_api(lua_api: &mut BattleLuaApi) {
     );
 }
 
+#[track_caller]
 fn generate_stage_item_fn<F>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     F: for<'lua> Fn(
@@ -227,7 +228,7 @@ where
 
         let data = callback(player, lua, game_io, params)?;
         let undo_callback = undo_callback
-            .map(|function| BattleCallback::new_lua_callback(lua, vm_index, function))
+            .map(|function| BattleCallback::new_lua_callback(lua, vm_index, "undo", function))
             .transpose()?;
 
         let item = StagedItem {
@@ -241,6 +242,7 @@ where
     });
 }
 
+#[track_caller]
 fn generate_player_mut_fn<F>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     F: for<'lua> Fn(
@@ -129,7 +129,8 @@ pub fn inject_card_select_button_api(lua_api: &mut BattleLuaApi) {
             }
 
             // display card name
-            let mut name_style = TextStyle::new_monospace(game_io, FontName::Thick);
+            let mut name_style =
+                TextStyle::new_unscaled(game_io, FontName::Thick).with_monospace(true);
             name_style.letter_spacing = 2.0;
             name_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
 
@@ -320,9 +321,10 @@ fn deleted_test(table: &rollback_mlua::Table) -> rollback_mlua::Result<()> {
     Ok(())
 }
 
+#[track_caller]
 fn callback_setter<G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -363,6 +365,7 @@ fn callback_setter<G, P, F, R>(
                     BattleCallback::new_transformed_lua_callback(
                         lua,
                         api_ctx.vm_index,
+                        name,
                         callback,
                         move |_, lua, p| {
                             let table: rollback_mlua::Table = lua.registry_value(&key)?;
@@ -54,6 +54,7 @@ pub fn inject_component_api(lua_api: &mut BattleLuaApi) {
         component.init_callback = BattleCallback::new_transformed_lua_callback(
             lua,
             api_ctx.vm_index,
+            INIT_FN,
             callback,
             move |_, lua, _| {
                 let table: rollback_mlua::Table = lua.registry_value(&key)?;
@@ -74,9 +75,10 @@ pub fn inject_component_api(lua_api: &mut BattleLuaApi) {
     });
 }
 
+#[track_caller]
 fn callback_setter<G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -111,6 +113,7 @@ fn callback_setter<G, P, F, R>(
             *callback_getter(component) = BattleCallback::new_transformed_lua_callback(
                 lua,
                 api_ctx.vm_index,
+                name,
                 callback,
                 move |_, lua, p| {
                     let table: rollback_mlua::Table = lua.registry_value(&key)?;
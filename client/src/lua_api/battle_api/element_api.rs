@@ -0,0 +1,48 @@
+use super::{BattleLuaApi, ELEMENT_TABLE};
+use crate::bindable::Element;
+
+pub fn inject_element_api(lua_api: &mut BattleLuaApi) {
+    lua_api.add_dynamic_function(ELEMENT_TABLE, "from_name", |_, lua, params| {
+        let name: rollback_mlua::String = lua.unpack_multi(params)?;
+
+        lua.pack_multi(Element::from(name.to_str()?))
+    });
+
+    lua_api.add_dynamic_function(ELEMENT_TABLE, "name", |api_ctx, lua, params| {
+        let element: Element = lua.unpack_multi(params)?;
+
+        let api_ctx = api_ctx.borrow();
+        let element_registry = &api_ctx.resources.element_registry;
+
+        lua.pack_multi(element_registry.name(element))
+    });
+
+    lua_api.add_dynamic_function(ELEMENT_TABLE, "is_defined", |api_ctx, lua, params| {
+        let element: Element = lua.unpack_multi(params)?;
+
+        let api_ctx = api_ctx.borrow();
+        let element_registry = &api_ctx.resources.element_registry;
+        let defined = !matches!(element, Element::Custom(_))
+            || element_registry.definition(element).is_some();
+
+        lua.pack_multi(defined)
+    });
+
+    lua_api.add_dynamic_function(ELEMENT_TABLE, "is_weak_to", |api_ctx, lua, params| {
+        let (defender, attacker): (Element, Element) = lua.unpack_multi(params)?;
+
+        let api_ctx = api_ctx.borrow();
+        let element_registry = &api_ctx.resources.element_registry;
+
+        lua.pack_multi(element_registry.is_weak_to(defender, attacker))
+    });
+
+    lua_api.add_dynamic_function(ELEMENT_TABLE, "resists", |api_ctx, lua, params| {
+        let (defender, attacker): (Element, Element) = lua.unpack_multi(params)?;
+
+        let api_ctx = api_ctx.borrow();
+        let element_registry = &api_ctx.resources.element_registry;
+
+        lua.pack_multi(element_registry.resists(defender, attacker))
+    });
+}
@@ -1,15 +1,34 @@
 use super::field_api::get_field_table;
 use super::{create_entity_table, BattleLuaApi, ENCOUNTER_TABLE, MUTATOR_TABLE, SPAWNER_TABLE};
-use crate::battle::{BattleInitMusic, BattleScriptContext, Character, Entity};
+use crate::battle::{
+    BattleCallback, BattleInitMusic, BattleScriptContext, BattleSimulation, Character, Entity,
+    Field,
+};
 use crate::bindable::{CharacterRank, EntityId};
 use crate::lua_api::helpers::{absolute_path, inherit_metatable};
 use crate::packages::PackageId;
-use crate::render::{Animator, Background};
+use crate::render::{Animator, Background, FrameTime};
 use crate::resources::{AssetManager, Globals};
 use framework::prelude::Vec2;
+use packets::structures::EncounterData;
 use std::cell::RefCell;
 
-pub fn encounter_init(api_ctx: BattleScriptContext, data: Option<&str>) {
+// playable columns, the default field is 6 wide
+const DEFAULT_FIELD_WIDTH: usize = 6;
+const MAX_FIELD_WIDTH: usize = 12;
+
+/// Key in the data sent by servers for the card select time limit in frames, see Encounter:set_card_select_time_limit()
+const CARD_SELECT_TIME_LIMIT_KEY: &str = "card_select_time_limit";
+
+pub fn encounter_init(api_ctx: BattleScriptContext, data: Option<&EncounterData>) {
+    // applied before the script, allowing the encounter to override the server
+    let time_limit = (data.and_then(|data| data.get(CARD_SELECT_TIME_LIMIT_KEY)))
+        .and_then(EncounterData::as_integer);
+
+    if let Some(time_limit) = time_limit {
+        set_card_select_time_limit(api_ctx.simulation, Some(time_limit as FrameTime));
+    }
+
     let globals = api_ctx.game_io.resource::<Globals>().unwrap();
     let battle_api = &globals.battle_api;
 
@@ -25,24 +44,14 @@ pub fn encounter_init(api_ctx: BattleScriptContext, data: Option<&str>) {
         }
     };
 
-    let chunk = data.and_then(|data| {
-        let chunk: Option<rollback_mlua::Value> = lua.load(data).eval().ok();
-
-        if chunk.is_none() {
-            log::error!("Failed to read data from server:\n{data}");
-        }
-
-        chunk
-    });
-
     let context = RefCell::new(api_ctx);
 
-    battle_api.inject_dynamic(lua, &context, |lua| {
+    battle_api.inject_dynamic(lua, &context, "encounter_init", |lua| {
         lua.scope(|_| {
             let init_table = lua.create_table()?;
             inherit_metatable(lua, ENCOUNTER_TABLE, &init_table)?;
 
-            encounter_init.call((init_table, chunk))
+            encounter_init.call((init_table, data))
         })
     });
 }
@@ -86,6 +95,71 @@ pub fn inject_encounter_init_api(lua_api: &mut BattleLuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function(ENCOUNTER_TABLE, "enable_co_op", |api_ctx, lua, params| {
+        let (_, enabled): (rollback_mlua::Table, Option<bool>) = lua.unpack_multi(params)?;
+
+        let mut api_ctx = api_ctx.borrow_mut();
+        let simulation = &mut api_ctx.simulation;
+
+        simulation.config.co_op = enabled.unwrap_or(true);
+        simulation
+            .config
+            .reset_player_spawn_positions(&simulation.field);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(ENCOUNTER_TABLE, "boss_index", |api_ctx, lua, params| {
+        let _: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let api_ctx = api_ctx.borrow();
+        lua.pack_multi(api_ctx.simulation.config.boss_index)
+    });
+
+    lua_api.add_dynamic_function(ENCOUNTER_TABLE, "set_boss_func", |api_ctx, lua, params| {
+        let (_, callback): (rollback_mlua::Table, rollback_mlua::Function) =
+            lua.unpack_multi(params)?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let callback = BattleCallback::new_transformed_lua_callback(
+            lua,
+            api_ctx.vm_index,
+            "boss_func",
+            callback,
+            |_, lua, id: EntityId| lua.pack_multi(create_entity_table(lua, id)?),
+        )?;
+
+        api_ctx.simulation.config.boss_callback = Some(callback);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(
+        ENCOUNTER_TABLE,
+        "set_field_width",
+        |api_ctx, lua, params| {
+            let (_, width): (rollback_mlua::Table, usize) = lua.unpack_multi(params)?;
+
+            let width = width.clamp(2, MAX_FIELD_WIDTH);
+
+            let api_ctx = &mut *api_ctx.borrow_mut();
+            let simulation = &mut api_ctx.simulation;
+
+            // replaces the field, edges are included but never visible
+            let rows = simulation.field.rows();
+            simulation.field = Field::new(api_ctx.game_io, width + 2, rows);
+            simulation
+                .config
+                .reset_player_spawn_positions(&simulation.field);
+
+            // zoom out to fit the field on screen
+            let zoom = (DEFAULT_FIELD_WIDTH as f32 / width as f32).min(1.0);
+            simulation.battle_camera.set_default_zoom(zoom);
+
+            lua.pack_multi(())
+        },
+    );
+
     lua_api.add_dynamic_function(ENCOUNTER_TABLE, "set_background", |api_ctx, lua, params| {
         let (_, texture_path, animation_path, vel_x, vel_y): (
             rollback_mlua::Table,
@@ -166,6 +240,20 @@ pub fn inject_encounter_init_api(lua_api: &mut BattleLuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function(
+        ENCOUNTER_TABLE,
+        "set_card_select_time_limit",
+        |api_ctx, lua, params| {
+            let (_, time_limit): (rollback_mlua::Table, Option<FrameTime>) =
+                lua.unpack_multi(params)?;
+
+            let mut api_ctx = api_ctx.borrow_mut();
+            set_card_select_time_limit(api_ctx.simulation, time_limit);
+
+            lua.pack_multi(())
+        },
+    );
+
     lua_api.add_dynamic_function(
         ENCOUNTER_TABLE,
         "enable_flipping",
@@ -190,6 +278,36 @@ pub fn inject_encounter_init_api(lua_api: &mut BattleLuaApi) {
         },
     );
 
+    lua_api.add_dynamic_function(ENCOUNTER_TABLE, "set_intro_func", |api_ctx, lua, params| {
+        let (_, callback): (rollback_mlua::Table, rollback_mlua::Function) =
+            lua.unpack_multi(params)?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let callback =
+            BattleCallback::new_lua_callback(lua, api_ctx.vm_index, "intro_func", callback)?;
+
+        api_ctx.simulation.config.intro_callback = Some(callback);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(
+        ENCOUNTER_TABLE,
+        "set_results_func",
+        |api_ctx, lua, params| {
+            let (_, callback): (rollback_mlua::Table, rollback_mlua::Function) =
+                lua.unpack_multi(params)?;
+
+            let api_ctx = &mut *api_ctx.borrow_mut();
+            let callback =
+                BattleCallback::new_lua_callback(lua, api_ctx.vm_index, "results_func", callback)?;
+
+            api_ctx.simulation.config.results_callback = Some(callback);
+
+            lua.pack_multi(())
+        },
+    );
+
     lua_api.add_dynamic_function(ENCOUNTER_TABLE, "enable_boss_battle", |api_ctx, lua, _| {
         let mut api_ctx = api_ctx.borrow_mut();
         let simulation = &mut api_ctx.simulation;
@@ -248,7 +366,27 @@ fn create_spawner(
     Ok(table)
 }
 
+/// Limits of 0 or less disable the time limit
+fn set_card_select_time_limit(simulation: &mut BattleSimulation, time_limit: Option<FrameTime>) {
+    simulation.config.card_select_time_limit = time_limit.filter(|time| *time > 0);
+}
+
 pub fn inject_mutator_api(lua_api: &mut BattleLuaApi) {
+    // lets spawned enemies, such as bosses, shorten card select
+    lua_api.add_dynamic_function(
+        MUTATOR_TABLE,
+        "set_card_select_time_limit",
+        |api_ctx, lua, params| {
+            let (_, time_limit): (rollback_mlua::Table, Option<FrameTime>) =
+                lua.unpack_multi(params)?;
+
+            let mut api_ctx = api_ctx.borrow_mut();
+            set_card_select_time_limit(api_ctx.simulation, time_limit);
+
+            lua.pack_multi(())
+        },
+    );
+
     lua_api.add_dynamic_function(MUTATOR_TABLE, "mutate", |_, lua, params| {
         let (table, callback): (rollback_mlua::Table, rollback_mlua::Function) =
             lua.unpack_multi(params)?;
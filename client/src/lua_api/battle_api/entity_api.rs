@@ -142,6 +142,102 @@ pub fn inject_entity_api(lua_api: &mut BattleLuaApi) {
         lua.pack_multi(owner)
     });
 
+    lua_api.add_dynamic_function(ENTITY_TABLE, "set_summoner", |api_ctx, lua, params| {
+        let (table, summoner_table, duration): (
+            rollback_mlua::Table,
+            Option<rollback_mlua::Table>,
+            Option<FrameTime>,
+        ) = lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let entities = &mut api_ctx.simulation.entities;
+
+        let Some(summoner_table) = summoner_table else {
+            let entity = entities
+                .query_one_mut::<&mut Entity>(id.into())
+                .map_err(|_| entity_not_found())?;
+
+            entity.summoner = None;
+            entity.summon_lifetime = None;
+            entity.hit_context.aggressor = id;
+
+            return lua.pack_multi(());
+        };
+
+        let summoner_id: EntityId = summoner_table.raw_get("#id")?;
+
+        // summons inherit the summoner's side and aggro,
+        // attacks from the summon's context count as the summoner's for counters
+        let (team, facing, aggressor, hit_resolution) = entities
+            .query_one_mut::<(&Entity, Option<&Living>)>(summoner_id.into())
+            .map(|(summoner, living)| {
+                (
+                    summoner.team,
+                    summoner.facing,
+                    summoner.hit_context.aggressor,
+                    living.map(|living| living.hit_resolution.clone()),
+                )
+            })
+            .map_err(|_| entity_not_found())?;
+
+        let (entity, living) = entities
+            .query_one_mut::<(&mut Entity, Option<&mut Living>)>(id.into())
+            .map_err(|_| entity_not_found())?;
+
+        entity.team = team;
+        entity.facing = facing;
+        entity.hit_context.aggressor = aggressor;
+        entity.summoner = Some(summoner_id);
+        entity.summon_lifetime = duration.filter(|duration| *duration > 0);
+
+        // and are hit by the same rules
+        if let (Some(living), Some(hit_resolution)) = (living, hit_resolution) {
+            living.hit_resolution = hit_resolution;
+        }
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(ENTITY_TABLE, "summoner", |api_ctx, lua, params| {
+        let table: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let entities = &mut api_ctx.simulation.entities;
+
+        let entity = entities
+            .query_one_mut::<&Entity>(id.into())
+            .map_err(|_| entity_not_found())?;
+
+        match entity.summoner {
+            Some(summoner_id) => lua.pack_multi(create_entity_table(lua, summoner_id)?),
+            None => lua.pack_multi(()),
+        }
+    });
+
+    lua_api.add_dynamic_function(ENTITY_TABLE, "summons", |api_ctx, lua, params| {
+        let table: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let summons = Entity::summons(api_ctx.simulation, id);
+
+        let tables = summons
+            .into_iter()
+            .map(|id| create_entity_table(lua, id))
+            .collect::<rollback_mlua::Result<Vec<_>>>()?;
+
+        lua.pack_multi(tables)
+    });
+
+    getter(lua_api, "summon_lifetime", |entity: &Entity, lua, _: ()| {
+        lua.pack_multi(entity.summon_lifetime)
+    });
+
     lua_api.add_dynamic_function(ENTITY_TABLE, "get_tile", |api_ctx, lua, params| {
         let (table, direction, distance): (rollback_mlua::Table, Option<Direction>, Option<i32>) =
             lua.unpack_multi(params)?;
@@ -289,6 +385,30 @@ pub fn inject_entity_api(lua_api: &mut BattleLuaApi) {
         Ok(())
     });
 
+    getter(lua_api, "draw_layer", |entity: &Entity, lua, _: ()| {
+        lua.pack_multi(entity.draw_layer)
+    });
+    setter(
+        lua_api,
+        "set_draw_layer",
+        |entity: &mut Entity, _, draw_layer| {
+            entity.draw_layer = draw_layer;
+            Ok(())
+        },
+    );
+
+    getter(lua_api, "y_sort_offset", |entity: &Entity, lua, _: ()| {
+        lua.pack_multi(entity.y_sort_offset)
+    });
+    setter(
+        lua_api,
+        "set_y_sort_offset",
+        |entity: &mut Entity, _, y_sort_offset| {
+            entity.y_sort_offset = y_sort_offset;
+            Ok(())
+        },
+    );
+
     lua_api.add_convenience_method(ENTITY_TABLE, "sprite", "never_flip", None);
     lua_api.add_convenience_method(ENTITY_TABLE, "sprite", "set_never_flip", None);
 
@@ -522,6 +642,55 @@ pub fn inject_entity_api(lua_api: &mut BattleLuaApi) {
         lua.pack_multi(table)
     });
 
+    lua_api.add_dynamic_function(
+        ENTITY_TABLE,
+        "create_particle_emitter",
+        |api_ctx, lua, params| {
+            let (entity_table, mut props): (rollback_mlua::Table, ParticleEmitterProps) =
+                lua.unpack_multi(params)?;
+
+            props.texture_path = absolute_path(lua, props.texture_path)?;
+            props.animation_path = absolute_path(lua, props.animation_path)?;
+
+            let entity_id: EntityId = entity_table.get("#id")?;
+
+            let api_ctx = &mut *api_ctx.borrow_mut();
+            let simulation = &mut api_ctx.simulation;
+
+            if !simulation.entities.contains(entity_id.into()) {
+                return Err(entity_not_found());
+            }
+
+            let emitter = ParticleEmitter::new(api_ctx.game_io, simulation, entity_id, props);
+            let id = simulation.particle_emitters.insert(emitter);
+
+            let table = lua.create_table()?;
+            table.raw_set("#id", id)?;
+            table.raw_set("#entity", entity_table)?;
+            inherit_metatable(lua, PARTICLE_EMITTER_TABLE, &table)?;
+
+            lua.pack_multi(table)
+        },
+    );
+
+    lua_api.add_dynamic_function(ENTITY_TABLE, "rng", |api_ctx, lua, params| {
+        let entity_table: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let entity_id: EntityId = entity_table.get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+
+        if !api_ctx.simulation.entities.contains(entity_id.into()) {
+            return Err(entity_not_found());
+        }
+
+        let table = lua.create_table()?;
+        table.raw_set("#entity_id", entity_id)?;
+        inherit_metatable(lua, RNG_TABLE, &table)?;
+
+        lua.pack_multi(table)
+    });
+
     getter(lua_api, "context", |entity: &Entity, lua, _: ()| {
         lua.pack_multi(&entity.hit_context)
     });
@@ -782,6 +951,7 @@ pub fn inject_entity_api(lua_api: &mut BattleLuaApi) {
         let callback = BattleCallback::new_transformed_lua_callback(
             lua,
             vm_index,
+            "on_delete",
             callback,
             move |_, lua, _| {
                 let entity_table = create_entity_table(lua, id)?;
@@ -1024,6 +1194,101 @@ fn inject_character_api(lua_api: &mut BattleLuaApi) {
     getter(lua_api, "rank", |character: &Character, lua, _: ()| {
         lua.pack_multi(character.rank)
     });
+
+    lua_api.add_dynamic_function(ENTITY_TABLE, "add_phase", |api_ctx, lua, params| {
+        let (table, health, callback, transition_duration): (
+            rollback_mlua::Table,
+            i32,
+            Option<rollback_mlua::Function>,
+            Option<FrameTime>,
+        ) = lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+
+        let callback = match callback {
+            Some(callback) => {
+                let key = lua.create_registry_value(table)?;
+
+                BattleCallback::new_transformed_lua_callback(
+                    lua,
+                    api_ctx.vm_index,
+                    "add_phase",
+                    callback,
+                    move |_, lua, _| {
+                        let table: rollback_mlua::Table = lua.registry_value(&key)?;
+                        lua.pack_multi(table)
+                    },
+                )?
+            }
+            None => BattleCallback::default(),
+        };
+
+        let entities = &mut api_ctx.simulation.entities;
+        let character = entities
+            .query_one_mut::<&mut Character>(id.into())
+            .map_err(|_| entity_not_found())?;
+
+        character.phases.push_back(CharacterPhase {
+            health,
+            transition_duration: transition_duration
+                .unwrap_or(CharacterPhase::DEFAULT_TRANSITION_DURATION),
+            callback,
+        });
+
+        lua.pack_multi(())
+    });
+
+    getter(lua_api, "phase", |character: &Character, lua, _: ()| {
+        lua.pack_multi(character.phase_index + 1)
+    });
+
+    getter(
+        lua_api,
+        "phase_count",
+        |character: &Character, lua, _: ()| lua.pack_multi(character.phase_count()),
+    );
+
+    lua_api.add_dynamic_function(ENTITY_TABLE, "advance_phase", |api_ctx, lua, params| {
+        let table: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let simulation = &mut api_ctx.simulation;
+
+        if !simulation.entities.contains(id.into()) {
+            return Err(entity_not_found());
+        }
+
+        lua.pack_multi(Character::advance_phase(simulation, id))
+    });
+
+    lua_api.add_dynamic_function(ENTITY_TABLE, "enable_boss_bar", |api_ctx, lua, params| {
+        let (table, enabled): (rollback_mlua::Table, Option<bool>) = lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let simulation = &mut api_ctx.simulation;
+
+        if !simulation
+            .entities
+            .satisfies::<&Character>(id.into())
+            .unwrap_or(false)
+        {
+            return Err(entity_not_found());
+        }
+
+        if enabled.unwrap_or(true) {
+            simulation.boss_bar_entity = Some(id);
+        } else if simulation.boss_bar_entity == Some(id) {
+            simulation.boss_bar_entity = None;
+        }
+
+        lua.pack_multi(())
+    });
 }
 
 fn inject_spell_api(lua_api: &mut BattleLuaApi) {
@@ -1322,6 +1587,34 @@ fn inject_living_api(lua_api: &mut BattleLuaApi) {
         },
     );
 
+    getter(lua_api, "hit_resolution", |living: &Living, lua, _: ()| {
+        lua.pack_multi(&living.hit_resolution)
+    });
+
+    lua_api.add_dynamic_function(
+        ENTITY_TABLE,
+        "set_hit_resolution",
+        |api_ctx, lua, params| {
+            let (table, resolution_table): (rollback_mlua::Table, rollback_mlua::Table) =
+                lua.unpack_multi(params)?;
+
+            let id: EntityId = table.raw_get("#id")?;
+
+            let mut api_ctx = api_ctx.borrow_mut();
+            let entities = &mut api_ctx.simulation.entities;
+
+            let living = entities
+                .query_one_mut::<&mut Living>(id.into())
+                .map_err(|_| entity_not_found())?;
+
+            living
+                .hit_resolution
+                .update_from_lua_table(resolution_table)?;
+
+            lua.pack_multi(())
+        },
+    );
+
     lua_api.add_dynamic_function(ENTITY_TABLE, "is_inactionable", |api_ctx, lua, params| {
         let table: rollback_mlua::Table = lua.unpack_multi(params)?;
 
@@ -1412,6 +1705,23 @@ fn inject_living_api(lua_api: &mut BattleLuaApi) {
         |living: &mut Living| &mut living.countered_callback,
         |lua, table, _| lua.pack_multi(table),
     );
+
+    callback_setter(
+        lua_api,
+        HIT_RESOLVED_FN,
+        |living: &mut Living| &mut living.hit_resolved_callback,
+        |lua, table, hit_props| lua.pack_multi((table, hit_props)),
+    );
+
+    callback_setter(
+        lua_api,
+        DRAG_COLLISION_FN,
+        |living: &mut Living| &mut living.drag_collision_callback,
+        |lua, table, dest| {
+            let tile_table = create_tile_table(lua, dest)?;
+            lua.pack_multi((table, tile_table))
+        },
+    );
 }
 
 fn inject_player_api(lua_api: &mut BattleLuaApi) {
@@ -2019,6 +2329,7 @@ fn inject_player_api(lua_api: &mut BattleLuaApi) {
     );
 }
 
+#[track_caller]
 fn delete_getter<F>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     F: Fn(&Entity) -> bool + 'static,
@@ -2038,6 +2349,7 @@ where
     });
 }
 
+#[track_caller]
 fn getter<C, F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     C: hecs::Component,
@@ -2065,6 +2377,7 @@ where
     });
 }
 
+#[track_caller]
 fn setter<C, F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     C: hecs::Component,
@@ -2087,9 +2400,10 @@ where
     });
 }
 
+#[track_caller]
 fn callback_setter<C, G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -2125,6 +2439,7 @@ fn callback_setter<C, G, P, F, R>(
             *callback_getter(entity) = BattleCallback::new_transformed_lua_callback(
                 lua,
                 api_ctx.vm_index,
+                name,
                 callback,
                 move |_, lua, p| {
                     let table: rollback_mlua::Table = lua.registry_value(&key)?;
@@ -2139,9 +2454,10 @@ fn callback_setter<C, G, P, F, R>(
     });
 }
 
+#[track_caller]
 fn optional_callback_setter<C, G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -2178,6 +2494,7 @@ fn optional_callback_setter<C, G, P, F, R>(
                 BattleCallback::new_transformed_lua_callback(
                     lua,
                     api_ctx.vm_index,
+                    name,
                     callback,
                     move |_, lua, p| {
                         let table: rollback_mlua::Table = lua.registry_value(&key)?;
@@ -2191,6 +2508,7 @@ fn optional_callback_setter<C, G, P, F, R>(
     });
 }
 
+#[track_caller]
 fn movement_function<F, P>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
@@ -2227,6 +2545,7 @@ where
             movement.on_begin = Some(BattleCallback::new_lua_callback(
                 lua,
                 api_ctx.vm_index,
+                "on_begin_func",
                 on_begin,
             )?);
         }
@@ -2279,6 +2598,7 @@ fn attempt_movement<'lua>(
     lua.pack_multi(true)
 }
 
+#[track_caller]
 fn generate_constructor_fn<F>(lua_api: &mut BattleLuaApi, table_name: &str, constructor: F)
 where
     F: Fn(&mut BattleScriptContext) -> rollback_mlua::Result<EntityId> + 'static,
@@ -2300,6 +2620,7 @@ where
     });
 }
 
+#[track_caller]
 fn generate_cast_fn<Q: hecs::Query>(lua_api: &mut BattleLuaApi, table_name: &str) {
     lua_api.add_dynamic_function(table_name, "from", |api_ctx, lua, params| {
         let table: rollback_mlua::Table = lua.unpack_multi(params)?;
@@ -44,6 +44,10 @@ pub fn component_not_found() -> rollback_mlua::Error {
     rollback_mlua::Error::RuntimeError(String::from("component deleted"))
 }
 
+pub fn particle_emitter_not_found() -> rollback_mlua::Error {
+    rollback_mlua::Error::RuntimeError(String::from("particle emitter deleted"))
+}
+
 pub fn action_not_found() -> rollback_mlua::Error {
     rollback_mlua::Error::RuntimeError(String::from("action deleted"))
 }
@@ -141,8 +141,8 @@ pub fn inject_field_api(lua_api: &mut BattleLuaApi) {
 
         let mut api_ctx = api_ctx.borrow_mut();
 
-        let camera = &mut api_ctx.simulation.camera;
-        camera.shake(power, duration as f32 / 60.0);
+        let battle_camera = &mut api_ctx.simulation.battle_camera;
+        battle_camera.shake(power, duration);
 
         lua.pack_multi(())
     });
@@ -180,7 +180,7 @@ pub fn inject_field_api(lua_api: &mut BattleLuaApi) {
                 simulation,
             });
 
-            lua_api.inject_dynamic(lua, &api_ctx, |lua| {
+            lua_api.inject_dynamic(lua, &api_ctx, "notify_on_delete", |lua| {
                 let callback: rollback_mlua::Function = lua.registry_value(&callback_key)?;
 
                 let observer_exists = {
@@ -223,6 +223,7 @@ pub fn inject_field_api(lua_api: &mut BattleLuaApi) {
         let callback = BattleCallback::new_transformed_lua_callback(
             lua,
             vm_index,
+            "callback_on_delete",
             callback,
             move |_, lua, _| {
                 let entity_table = create_entity_table(lua, id)?;
@@ -252,6 +253,7 @@ pub fn inject_field_api(lua_api: &mut BattleLuaApi) {
     });
 }
 
+#[track_caller]
 fn generate_find_entity_fn<Q: hecs::Query>(lua_api: &mut BattleLuaApi, name: &str) {
     lua_api.add_dynamic_function(FIELD_TABLE, name, |api_ctx, lua, params| {
         let (_, callback): (rollback_mlua::Table, rollback_mlua::Function) =
@@ -287,6 +289,7 @@ fn generate_find_entity_fn<Q: hecs::Query>(lua_api: &mut BattleLuaApi, name: &st
     });
 }
 
+#[track_caller]
 fn generate_find_nearest_fn<Q: hecs::Query>(lua_api: &mut BattleLuaApi, name: &str) {
     lua_api.add_dynamic_function(FIELD_TABLE, name, |api_ctx, lua, params| {
         let (_, ref_table, callback): (
@@ -30,10 +30,18 @@ pub(super) fn inject_global_api(lua: &rollback_mlua::Lua) -> rollback_mlua::Resu
     drag_table.set(
         "new",
         lua.create_function(
-            |_, (direction, distance): (Option<Direction>, Option<u32>)| {
+            |_,
+             (direction, distance, wall_damage, chain): (
+                Option<Direction>,
+                Option<u32>,
+                Option<i32>,
+                Option<bool>,
+            )| {
                 Ok(Drag {
                     direction: direction.unwrap_or_default(),
                     distance: distance.unwrap_or_default(),
+                    wall_damage: wall_damage.unwrap_or_default(),
+                    chain: chain.unwrap_or_default(),
                 })
             },
         )?,
@@ -132,6 +140,12 @@ pub(super) fn inject_global_api(lua: &rollback_mlua::Lua) -> rollback_mlua::Resu
     sprite_shader_table.set("None", SpriteShaderEffect::Default)?;
     sprite_shader_table.set("Grayscale", SpriteShaderEffect::Grayscale)?;
     sprite_shader_table.set("Pixelate", SpriteShaderEffect::Pixelate)?;
+    sprite_shader_table.set("Dissolve", SpriteShaderEffect::Dissolve)?;
+    sprite_shader_table.set(
+        "ChromaticAberration",
+        SpriteShaderEffect::ChromaticAberration,
+    )?;
+    sprite_shader_table.set("Flash", SpriteShaderEffect::Flash)?;
     globals.set("SpriteShaderEffect", sprite_shader_table)?;
 
     use crate::bindable::SpriteColorMode;
@@ -150,6 +164,15 @@ pub(super) fn inject_global_api(lua: &rollback_mlua::Lua) -> rollback_mlua::Resu
     color_mode_table.set("Reverse", AnimatorPlaybackMode::Reverse)?;
     globals.set("Playback", color_mode_table)?;
 
+    use crate::bindable::EaseMode;
+
+    let ease_table = lua.create_table()?;
+    ease_table.set("Linear", EaseMode::Linear)?;
+    ease_table.set("In", EaseMode::In)?;
+    ease_table.set("Out", EaseMode::Out)?;
+    ease_table.set("InOut", EaseMode::InOut)?;
+    globals.set("Ease", ease_table)?;
+
     use crate::bindable::TileHighlight;
 
     let tile_state_table = lua.create_table()?;
@@ -216,6 +239,14 @@ pub(super) fn inject_global_api(lua: &rollback_mlua::Lua) -> rollback_mlua::Resu
     defense_order_table.set("CollisionOnly", true)?;
     globals.set("DefenseOrder", defense_order_table)?;
 
+    use crate::bindable::HitStep;
+
+    let hit_step_table = lua.create_table()?;
+    hit_step_table.set("Counter", HitStep::Counter)?;
+    hit_step_table.set("Statuses", HitStep::Statuses)?;
+    hit_step_table.set("Drag", HitStep::Drag)?;
+    globals.set("HitStep", hit_step_table)?;
+
     // todo: ActionOrder, currently stubbed
     globals.set("ActionOrder", lua.create_table()?)?;
 
@@ -248,6 +279,15 @@ pub(super) fn inject_global_api(lua: &rollback_mlua::Lua) -> rollback_mlua::Resu
     audio_behavior_table.set("EndLoop", AudioBehavior::EndLoop)?;
     globals.set("AudioBehavior", audio_behavior_table)?;
 
+    use crate::bindable::AudioBus;
+
+    let audio_bus_table = lua.create_table()?;
+    audio_bus_table.set("Music", AudioBus::Music)?;
+    audio_bus_table.set("Sfx", AudioBus::Sfx)?;
+    audio_bus_table.set("Ui", AudioBus::Ui)?;
+    audio_bus_table.set("Voice", AudioBus::Voice)?;
+    globals.set("AudioBus", audio_bus_table)?;
+
     let shadow_table = lua.create_table()?;
     shadow_table.set("None", ResourcePaths::BLANK)?;
     shadow_table.set(
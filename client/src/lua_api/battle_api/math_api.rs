@@ -1,6 +1,6 @@
+use super::rng_api::random;
 use super::BattleLuaApi;
-use rand::Rng;
-use rollback_mlua::prelude::{LuaError, LuaNil};
+use rollback_mlua::prelude::LuaNil;
 
 pub fn inject_math_api(lua_api: &mut BattleLuaApi) {
     lua_api.add_static_injector(|lua| {
@@ -18,27 +18,28 @@ pub fn inject_math_api(lua_api: &mut BattleLuaApi) {
     lua_api.add_dynamic_function("math", "random", |api_ctx, lua, params| {
         let (n, m): (Option<i32>, Option<i32>) = lua.unpack_multi(params)?;
 
+        warn_math_random(lua)?;
+
         let mut api_ctx = api_ctx.borrow_mut();
-        let rng = &mut api_ctx.simulation.rng;
+        random(lua, &mut api_ctx.simulation.rng, n, m)
+    });
+}
 
-        let Some(mut n) = n else {
-            return lua.pack_multi(rng.gen::<f32>());
-        };
+fn warn_math_random(lua: &rollback_mlua::Lua) -> rollback_mlua::Result<()> {
+    const WARNED_KEY: &str = "math_random_warned";
 
-        let Some(mut m) = m else {
-            if n <= 0 {
-                return Err(LuaError::RuntimeError(String::from(
-                    "n must be larger than 0",
-                )));
-            }
+    if lua
+        .named_registry_value::<bool>(WARNED_KEY)
+        .unwrap_or_default()
+    {
+        return Ok(());
+    }
 
-            return lua.pack_multi(rng.gen_range(1..=n));
-        };
+    lua.set_named_registry_value(WARNED_KEY, true)?;
 
-        if m < n {
-            std::mem::swap(&mut n, &mut m);
-        }
+    log::warn!(
+        "math.random is shared by every script and may desync netplay, use entity:rng() instead"
+    );
 
-        lua.pack_multi(rng.gen_range(n..=m))
-    });
+    Ok(())
 }
@@ -1,12 +1,15 @@
 mod action_api;
 mod animation_api;
+mod api_reference;
 mod augment_api;
 mod battle_lua_api;
 mod built_in_api;
+mod camera_api;
 mod card_select_api;
 mod card_select_button_api;
 mod component_api;
 mod defense_rule_api;
+mod element_api;
 mod encounter_init;
 mod entity_api;
 mod errors;
@@ -14,9 +17,11 @@ mod field_api;
 mod global_api;
 mod math_api;
 mod movement_api;
+mod particle_emitter_api;
 mod player_form_api;
 mod require_api;
 mod resources_api;
+mod rng_api;
 mod sprite_api;
 mod status_api;
 mod sync_node_api;
@@ -25,6 +30,7 @@ mod tile_state_api;
 mod turn_gauge_api;
 
 pub use action_api::create_action_table;
+pub use api_reference::write_lua_api_reference;
 pub use augment_api::create_augment_table;
 pub use battle_lua_api::*;
 pub use card_select_button_api::*;
@@ -38,7 +44,10 @@ pub use tile_state_api::create_custom_tile_state_table;
 // naming conflicts with simple registry values are avoided by using PascalCase
 pub const GLOBAL_TABLE: &str = "_G";
 pub const RESOURCES_TABLE: &str = "Resources";
+pub const AUDIO_TABLE: &str = "Resources.audio";
+pub const ELEMENT_TABLE: &str = "Element";
 pub const TURN_GAUGE_TABLE: &str = "TurnGauge";
+pub const CAMERA_TABLE: &str = "Camera";
 pub const SPRITE_TABLE: &str = "SpriteNode";
 pub const TEXT_STYLE_TABLE: &str = "TextStyle";
 pub const SYNC_NODE_TABLE: &str = "SyncNode";
@@ -59,6 +68,8 @@ pub const ACTION_STEP_TABLE: &str = "ActionStep";
 pub const MOVEMENT_TABLE: &str = "Movement";
 pub const ATTACHMENT_TABLE: &str = "Attachment";
 pub const COMPONENT_TABLE: &str = "Component";
+pub const PARTICLE_EMITTER_TABLE: &str = "ParticleEmitter";
+pub const RNG_TABLE: &str = "Rng";
 pub const FIELD_TABLE: &str = "Field";
 pub const TILE_TABLE: &str = "Tile";
 pub const TILE_STATE_TABLE: &str = "TileState";
@@ -106,6 +117,8 @@ const COUNTER_FN: &str = "on_counter_func";
 
 // living
 const COUNTERED_FN: &str = "on_countered_func";
+const HIT_RESOLVED_FN: &str = "on_hit_resolved_func";
+const DRAG_COLLISION_FN: &str = "on_drag_collision_func";
 
 // spells
 const COLLISION_FN: &str = "on_collision_func";
@@ -0,0 +1,88 @@
+use super::errors::particle_emitter_not_found;
+use super::{BattleLuaApi, PARTICLE_EMITTER_TABLE};
+use crate::battle::*;
+use crate::bindable::*;
+
+pub fn inject_particle_emitter_api(lua_api: &mut BattleLuaApi) {
+    // constructor in entity_api.rs Entity:create_particle_emitter
+
+    lua_api.add_dynamic_function(PARTICLE_EMITTER_TABLE, "owner", |_, lua, params| {
+        let table: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let entity_table: rollback_mlua::Table = table.get("#entity")?;
+
+        lua.pack_multi(entity_table)
+    });
+
+    getter(
+        lua_api,
+        "spawning",
+        |emitter, _, _: ()| Ok(emitter.spawning),
+    );
+
+    setter(lua_api, "set_spawning", |emitter, _, spawning: bool| {
+        emitter.spawning = spawning;
+        Ok(())
+    });
+
+    getter(lua_api, "particle_count", |emitter, _, _: ()| {
+        Ok(emitter.particle_count())
+    });
+
+    lua_api.add_dynamic_function(PARTICLE_EMITTER_TABLE, "eject", |api_ctx, lua, params| {
+        let table: rollback_mlua::Table = lua.unpack_multi(params)?;
+
+        let id: GenerationalIndex = table.get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        api_ctx.simulation.particle_emitters.remove(id);
+
+        lua.pack_multi(())
+    });
+}
+
+#[track_caller]
+fn getter<F, P, R>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
+where
+    R: for<'lua> rollback_mlua::IntoLua<'lua>,
+    P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
+    F: for<'lua> Fn(&ParticleEmitter, &'lua rollback_mlua::Lua, P) -> rollback_mlua::Result<R>
+        + 'static,
+{
+    lua_api.add_dynamic_function(PARTICLE_EMITTER_TABLE, name, move |api_ctx, lua, params| {
+        let (table, param): (rollback_mlua::Table, P) = lua.unpack_multi(params)?;
+
+        let id: GenerationalIndex = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+
+        let emitter = (api_ctx.simulation.particle_emitters)
+            .get(id)
+            .ok_or_else(particle_emitter_not_found)?;
+
+        lua.pack_multi(callback(emitter, lua, param)?)
+    });
+}
+
+#[track_caller]
+fn setter<F, P, R>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
+where
+    R: for<'lua> rollback_mlua::IntoLuaMulti<'lua>,
+    P: for<'lua> rollback_mlua::FromLuaMulti<'lua>,
+    F: for<'lua> Fn(&mut ParticleEmitter, &'lua rollback_mlua::Lua, P) -> rollback_mlua::Result<R>
+        + 'static,
+{
+    lua_api.add_dynamic_function(PARTICLE_EMITTER_TABLE, name, move |api_ctx, lua, params| {
+        let (table, param): (rollback_mlua::Table, P) = lua.unpack_multi(params)?;
+
+        let id: GenerationalIndex = table.raw_get("#id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+
+        let emitter = (api_ctx.simulation.particle_emitters)
+            .get_mut(id)
+            .ok_or_else(particle_emitter_not_found)?;
+
+        lua.pack_multi(callback(emitter, lua, param)?)
+    });
+}
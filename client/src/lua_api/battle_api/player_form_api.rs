@@ -218,9 +218,10 @@ pub fn inject_player_form_api(lua_api: &mut BattleLuaApi) {
     );
 }
 
+#[track_caller]
 fn callback_setter<G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -259,6 +260,7 @@ fn callback_setter<G, P, F, R>(
                 BattleCallback::new_transformed_lua_callback(
                     lua,
                     api_ctx.vm_index,
+                    name,
                     callback,
                     move |_, lua, p| {
                         let table: rollback_mlua::Table = lua.registry_value(&key)?;
@@ -1,8 +1,12 @@
-use super::{BattleLuaApi, ENTITY_TABLE, GAME_FOLDER_KEY, RESOURCES_TABLE};
+use super::{BattleLuaApi, AUDIO_TABLE, ENTITY_TABLE, GAME_FOLDER_KEY, RESOURCES_TABLE};
 use crate::battle::Player;
-use crate::bindable::{AudioBehavior, EntityId};
+use crate::bindable::{AudioBehavior, AudioBus, EntityId};
 use crate::lua_api::helpers::absolute_path;
+use crate::packages::PackageId;
+use crate::render::FrameTime;
 use crate::resources::{AssetManager, Globals};
+use framework::prelude::Duration;
+use std::collections::HashMap;
 
 pub fn inject_engine_api(lua_api: &mut BattleLuaApi) {
     lua_api.add_dynamic_function(RESOURCES_TABLE, "load_texture", |api_ctx, lua, params| {
@@ -51,10 +55,15 @@ pub fn inject_engine_api(lua_api: &mut BattleLuaApi) {
     });
 
     lua_api.add_dynamic_function(ENTITY_TABLE, "play_audio", |api_ctx, lua, params| {
-        let (player_table, path, behavior): (rollback_mlua::Table, String, Option<AudioBehavior>) =
-            lua.unpack_multi(params)?;
+        let (player_table, path, behavior, positional): (
+            rollback_mlua::Table,
+            String,
+            Option<AudioBehavior>,
+            Option<bool>,
+        ) = lua.unpack_multi(params)?;
         let path = absolute_path(lua, path)?;
         let behavior = behavior.unwrap_or_default();
+        let positional = positional.unwrap_or(true);
 
         let api_ctx = &mut *api_ctx.borrow_mut();
         let game_io = api_ctx.game_io;
@@ -70,25 +79,70 @@ pub fn inject_engine_api(lua_api: &mut BattleLuaApi) {
             let sound_buffer = globals.assets.audio(game_io, &path);
             let audio = &globals.audio;
 
-            audio.play_sound_with_behavior(&sound_buffer, behavior);
+            // scripts can opt out for menu sounds
+            let placement = positional
+                .then(|| simulation.audio_placement(entity_id))
+                .flatten();
+
+            audio.play_sound_with_behavior_at(&sound_buffer, behavior, placement);
         }
 
         lua.pack_multi(())
     });
 
     lua_api.add_dynamic_function(RESOURCES_TABLE, "play_music", |api_ctx, lua, params| {
-        let (path, loops): (String, Option<bool>) = lua.unpack_multi(params)?;
+        let (path, loops, fade_duration): (String, Option<bool>, Option<FrameTime>) =
+            lua.unpack_multi(params)?;
 
         let path = absolute_path(lua, path)?;
         let loops = loops.unwrap_or(true);
 
+        // frames to seconds
+        let fade_duration = fade_duration.unwrap_or_default().max(0) as f32 / 60.0;
+
         let api_ctx = api_ctx.borrow();
         let simulation = &api_ctx.simulation;
         let game_io = api_ctx.game_io;
         let globals = game_io.resource::<Globals>().unwrap();
 
         let sound_buffer = globals.assets.audio(game_io, &path);
-        simulation.play_music(game_io, &sound_buffer, loops);
+        let duration = Duration::from_secs_f32(fade_duration);
+        simulation.crossfade_music(game_io, &sound_buffer, loops, duration);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(RESOURCES_TABLE, "play_stinger", |api_ctx, lua, params| {
+        let path: String = lua.unpack_multi(params)?;
+        let path = absolute_path(lua, path)?;
+
+        let api_ctx = api_ctx.borrow();
+        let simulation = &api_ctx.simulation;
+        let game_io = api_ctx.game_io;
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        let sound_buffer = globals.assets.audio(game_io, &path);
+        simulation.play_stinger(game_io, &sound_buffer);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(AUDIO_TABLE, "bus_volume", |api_ctx, lua, params| {
+        let (_, bus): (rollback_mlua::Table, AudioBus) = lua.unpack_multi(params)?;
+
+        let api_ctx = api_ctx.borrow();
+        let globals = api_ctx.game_io.resource::<Globals>().unwrap();
+
+        lua.pack_multi(globals.audio.script_bus_volume(bus))
+    });
+
+    lua_api.add_dynamic_function(AUDIO_TABLE, "set_bus_volume", |api_ctx, lua, params| {
+        let (_, bus, volume): (rollback_mlua::Table, AudioBus, f32) = lua.unpack_multi(params)?;
+
+        // scales the player's volume, reset when the battle ends
+        let api_ctx = api_ctx.borrow();
+        let globals = api_ctx.game_io.resource::<Globals>().unwrap();
+        globals.audio.set_script_bus_volume(bus, volume);
 
         lua.pack_multi(())
     });
@@ -97,4 +151,36 @@ pub fn inject_engine_api(lua_api: &mut BattleLuaApi) {
         let path_str: rollback_mlua::String = lua.named_registry_value(GAME_FOLDER_KEY)?;
         lua.pack_multi(path_str)
     });
+
+    // only recorded for the local client, the battle is unaffected
+    lua_api.add_dynamic_function(
+        RESOURCES_TABLE,
+        "unlock_achievement",
+        |api_ctx, lua, params| {
+            let (package_id, id): (PackageId, String) = lua.unpack_multi(params)?;
+
+            let mut api_ctx = api_ctx.borrow_mut();
+            let unlocked_achievements = &mut api_ctx.simulation.unlocked_achievements;
+
+            if !unlocked_achievements.contains(&(package_id.clone(), id.clone())) {
+                unlocked_achievements.push((package_id, id));
+            }
+
+            lua.pack_multi(())
+        },
+    );
+
+    // text differs between players, it should only be displayed and never affect the battle
+    lua_api.add_dynamic_function(RESOURCES_TABLE, "translate", |api_ctx, lua, params| {
+        let (key, args): (String, Option<HashMap<String, String>>) = lua.unpack_multi(params)?;
+        let args: Vec<_> = args.into_iter().flatten().collect();
+        let folder_path: String = lua.environment()?.get("_folder_path")?;
+
+        let api_ctx = api_ctx.borrow();
+        let globals = api_ctx.game_io.resource::<Globals>().unwrap();
+        let localization = &globals.localization;
+        let text = localization.translate_for_package(&globals.assets, &folder_path, &key, &args);
+
+        lua.pack_multi(text)
+    });
 }
@@ -0,0 +1,54 @@
+use super::errors::entity_not_found;
+use super::{BattleLuaApi, RNG_TABLE};
+use crate::battle::Entity;
+use crate::bindable::EntityId;
+use rand::Rng;
+use rollback_mlua::prelude::LuaError;
+
+pub fn inject_rng_api(lua_api: &mut BattleLuaApi) {
+    // constructor in entity_api.rs Entity:rng
+
+    lua_api.add_dynamic_function(RNG_TABLE, "random", |api_ctx, lua, params| {
+        let (table, n, m): (rollback_mlua::Table, Option<i32>, Option<i32>) =
+            lua.unpack_multi(params)?;
+
+        let id: EntityId = table.raw_get("#entity_id")?;
+
+        let api_ctx = &mut *api_ctx.borrow_mut();
+        let simulation = &mut api_ctx.simulation;
+
+        let entity = (simulation.entities)
+            .query_one_mut::<&mut Entity>(id.into())
+            .map_err(|_| entity_not_found())?;
+
+        random(lua, &mut entity.rng, n, m)
+    });
+}
+
+/// Matches the behavior of Lua's math.random
+pub fn random<'lua>(
+    lua: &'lua rollback_mlua::Lua,
+    rng: &mut impl Rng,
+    n: Option<i32>,
+    m: Option<i32>,
+) -> rollback_mlua::Result<rollback_mlua::MultiValue<'lua>> {
+    let Some(mut n) = n else {
+        return lua.pack_multi(rng.gen::<f32>());
+    };
+
+    let Some(mut m) = m else {
+        if n <= 0 {
+            return Err(LuaError::RuntimeError(String::from(
+                "n must be larger than 0",
+            )));
+        }
+
+        return lua.pack_multi(rng.gen_range(1..=n));
+    };
+
+    if m < n {
+        std::mem::swap(&mut n, &mut m);
+    }
+
+    lua.pack_multi(rng.gen_range(n..=m))
+}
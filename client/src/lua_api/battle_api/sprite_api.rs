@@ -216,6 +216,14 @@ pub fn inject_sprite_api(lua_api: &mut BattleLuaApi) {
         Ok(())
     });
 
+    getter(lua_api, "sort_offset", |node, _, _: ()| {
+        Ok(node.sort_offset())
+    });
+    setter(lua_api, "set_sort_offset", |node, _, sort_offset| {
+        node.set_sort_offset(sort_offset);
+        Ok(())
+    });
+
     //   "add_tags", [](WeakWrapper<SpriteProxyNode>& node, std::initializer_list<std::string> tags) {
     //     node.Unwrap()->AddTags(tags);
     //   },
@@ -448,7 +456,7 @@ fn parse_text_style(
 
             TextStyle::new_with_atlas(glyph_atlas, font)
         } else {
-            TextStyle::new(game_io, font)
+            TextStyle::new_unscaled(game_io, font)
         };
 
     // make sure the font name exists in the glyph map to reduce confusion
@@ -478,6 +486,7 @@ pub fn create_sprite_table(
     Ok(table)
 }
 
+#[track_caller]
 fn getter<F, P, R>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     R: for<'lua> rollback_mlua::IntoLua<'lua>,
@@ -504,6 +513,7 @@ where
     });
 }
 
+#[track_caller]
 fn setter<F, P, R>(lua_api: &mut BattleLuaApi, name: &str, callback: F)
 where
     R: for<'lua> rollback_mlua::IntoLuaMulti<'lua>,
@@ -75,6 +75,7 @@ pub fn inject_status_api(lua_api: &mut BattleLuaApi) {
                 BattleCallback::new_transformed_lua_callback(
                     lua,
                     api_ctx.vm_index,
+                    DELETE_FN,
                     callback,
                     move |_, lua, _| {
                         let table = lua.registry_value::<rollback_mlua::Table>(&key)?;
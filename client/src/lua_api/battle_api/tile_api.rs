@@ -482,6 +482,7 @@ fn remove_entity(
     Ok(())
 }
 
+#[track_caller]
 fn generate_find_entity_fn<Q: hecs::Query>(lua_api: &mut BattleLuaApi, name: &str) {
     lua_api.add_dynamic_function(TILE_TABLE, name, |api_ctx, lua, params| {
         let (tile_table, callback): (rollback_mlua::Table, rollback_mlua::Function) =
@@ -122,9 +122,10 @@ fn inject_custom_tile_state_api(lua_api: &mut BattleLuaApi) {
     );
 }
 
+#[track_caller]
 fn callback_setter<G, P, F, R>(
     lua_api: &mut BattleLuaApi,
-    name: &str,
+    name: &'static str,
     callback_getter: G,
     param_transformer: F,
 ) where
@@ -164,6 +165,7 @@ fn callback_setter<G, P, F, R>(
                     BattleCallback::new_transformed_lua_callback(
                         lua,
                         api_ctx.vm_index,
+                        name,
                         callback,
                         move |_, lua, p| {
                             let table: rollback_mlua::Table = lua.registry_value(&key)?;
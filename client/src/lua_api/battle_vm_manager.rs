@@ -94,9 +94,11 @@ impl BattleVmManager {
             simulation,
         });
 
-        globals.battle_api.inject_dynamic(lua, &api_ctx, |lua| {
-            load_root_script(lua, &globals.assets, package_info)
-        });
+        globals
+            .battle_api
+            .inject_dynamic(lua, &api_ctx, "load", |lua| {
+                load_root_script(lua, &globals.assets, package_info)
+            });
     }
 
     pub fn vms(&self) -> &[RollbackVM] {
@@ -1,19 +1,8 @@
 // #![windows_subsystem = "windows"]
 
 use framework::prelude::WinitPlatformApp;
-use std::panic;
 
 fn main() {
-    panic::set_hook(Box::new(|p| {
-        use std::backtrace::Backtrace;
-
-        let backtrace = Backtrace::force_capture();
-        let output = format!("{p}\n{backtrace}");
-
-        let _ = std::fs::write("crash.txt", &output);
-        log::error!("{output}");
-    }));
-
     // check lib.rs
     hub_os::main(WinitPlatformApp::default()).unwrap()
 }
@@ -0,0 +1,73 @@
+// asks for a gamepad after one is disconnected or when several are connected
+
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::{FontName, TextStyle};
+use crate::render::{Camera, SpriteColorQueue};
+use crate::resources::{
+    AssetManager, Globals, ResourcePaths, RESOLUTION_F, TEXT_DARK_SHADOW_COLOR,
+};
+use framework::prelude::*;
+
+const BACKDROP_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.7);
+const SPACING: f32 = 8.0;
+
+pub struct ControllerPromptOverlay {
+    camera: Camera,
+    backdrop: Sprite,
+}
+
+impl ControllerPromptOverlay {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        let mut backdrop = (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL);
+        backdrop.set_size(RESOLUTION_F);
+        backdrop.set_color(BACKDROP_COLOR);
+
+        Self {
+            camera: Camera::new_ui(game_io),
+            backdrop,
+        }
+    }
+}
+
+impl GameOverlay for ControllerPromptOverlay {
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        let Some(prompt) = globals.controller_prompt else {
+            return;
+        };
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        sprite_queue.draw_sprite(&self.backdrop);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        let title = globals.translate(prompt.title_key());
+        let message = globals.translate(prompt.message_key());
+        let title_size = text_style.measure(&title).size;
+        let message_size = text_style.measure(&message).size;
+
+        let mut y = (RESOLUTION_F.y - title_size.y - SPACING - message_size.y) * 0.5;
+
+        text_style.color = Color::YELLOW;
+        text_style
+            .bounds
+            .set_position(Vec2::new((RESOLUTION_F.x - title_size.x) * 0.5, y));
+        text_style.draw(game_io, &mut sprite_queue, &title);
+
+        y += title_size.y + SPACING;
+
+        text_style.color = Color::WHITE;
+        text_style
+            .bounds
+            .set_position(Vec2::new((RESOLUTION_F.x - message_size.x) * 0.5, y));
+        text_style.draw(game_io, &mut sprite_queue, &message);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -39,6 +39,13 @@ impl DebugOverlay {
             return;
         }
 
+        if input.was_key_just_pressed(Key::G) {
+            let globals = game_io.resource::<Globals>().unwrap();
+            let mut frame_profiler = globals.frame_profiler.borrow_mut();
+            let enabled = frame_profiler.enabled();
+            frame_profiler.set_enabled(!enabled);
+        }
+
         if input.was_key_just_pressed(Key::N) {
             let globals = game_io.resource::<Globals>().unwrap();
             let mut namespaces = globals.namespaces().collect::<Vec<_>>();
@@ -92,7 +99,7 @@ impl GameOverlay for DebugOverlay {
     fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
         let globals = game_io.resource::<Globals>().unwrap();
 
-        if !globals.debug_visible {
+        if !globals.debug_visible || globals.config.clean_feed {
             return;
         }
 
@@ -0,0 +1,151 @@
+// shows pressed inputs for recordings and streams
+
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::{FontName, TextStyle};
+use crate::render::{Camera, SpriteColorQueue};
+use crate::resources::{AssetManager, Globals, Input, InputUtil, ResourcePaths, RESOLUTION_F};
+use crate::saves::InputDisplayPosition;
+use framework::prelude::*;
+
+const MARGIN: f32 = 4.0;
+const DPAD_CELL_SIZE: f32 = 5.0;
+const BUTTON_SIZE: f32 = 9.0;
+const SPACING: f32 = 2.0;
+const PRESSED_COLOR: Color = Color::new(1.0, 1.0, 1.0, 0.9);
+const RELEASED_COLOR: Color = Color::new(0.2, 0.2, 0.2, 0.6);
+
+const DPAD: [(Input, Vec2); 4] = [
+    (Input::Up, Vec2::new(1.0, 0.0)),
+    (Input::Left, Vec2::new(0.0, 1.0)),
+    (Input::Right, Vec2::new(2.0, 1.0)),
+    (Input::Down, Vec2::new(1.0, 2.0)),
+];
+
+const BUTTONS: [(&str, Input); 5] = [
+    ("L", Input::ShoulderL),
+    ("B", Input::Shoot),
+    ("A", Input::UseCard),
+    ("S", Input::Special),
+    ("R", Input::ShoulderR),
+];
+
+pub struct InputDisplayOverlay {
+    camera: Camera,
+    sprite: Sprite,
+    pressed: Vec<Input>,
+}
+
+impl InputDisplayOverlay {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        Self {
+            camera: Camera::new_ui(game_io),
+            sprite: (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL),
+            pressed: Vec::new(),
+        }
+    }
+
+    fn size() -> Vec2 {
+        let dpad_size = DPAD_CELL_SIZE * 3.0;
+        let buttons_width = (BUTTON_SIZE + SPACING) * BUTTONS.len() as f32 - SPACING;
+
+        Vec2::new(
+            dpad_size + SPACING * 2.0 + buttons_width,
+            dpad_size.max(BUTTON_SIZE),
+        )
+    }
+
+    fn color(&self, input: Input) -> Color {
+        if self.pressed.contains(&input) {
+            PRESSED_COLOR
+        } else {
+            RELEASED_COLOR
+        }
+    }
+
+    fn draw_rect(&mut self, sprite_queue: &mut SpriteColorQueue, rect: Rect, color: Color) {
+        self.sprite.set_bounds(rect);
+        self.sprite.set_color(color);
+        sprite_queue.draw_sprite(&self.sprite);
+    }
+}
+
+impl GameOverlay for InputDisplayOverlay {
+    fn post_update(&mut self, game_io: &mut GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        // prefer the snapshot handed to the battle simulation
+        let snapshot = globals.displayed_input.take();
+
+        if globals.config.input_display == InputDisplayPosition::Off {
+            self.pressed.clear();
+            return;
+        }
+
+        if let Some(pressed) = snapshot {
+            self.pressed = pressed;
+            return;
+        }
+
+        let input_util = InputUtil::new(game_io);
+
+        self.pressed.clear();
+        self.pressed
+            .extend((Input::BATTLE.into_iter()).filter(|input| input_util.is_down(*input)));
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let size = Self::size();
+
+        let position = match globals.config.input_display {
+            InputDisplayPosition::Off => return,
+            InputDisplayPosition::TopLeft => Vec2::new(MARGIN, MARGIN),
+            InputDisplayPosition::TopRight => Vec2::new(RESOLUTION_F.x - size.x - MARGIN, MARGIN),
+            InputDisplayPosition::BottomLeft => Vec2::new(MARGIN, RESOLUTION_F.y - size.y - MARGIN),
+            InputDisplayPosition::BottomRight => RESOLUTION_F - size - MARGIN,
+        };
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        // dpad
+        let dpad_top = position.y + (size.y - DPAD_CELL_SIZE * 3.0) * 0.5;
+
+        for (input, cell) in DPAD {
+            let cell_position = Vec2::new(position.x, dpad_top) + cell * DPAD_CELL_SIZE;
+            let rect = Rect::new(
+                cell_position.x,
+                cell_position.y,
+                DPAD_CELL_SIZE,
+                DPAD_CELL_SIZE,
+            );
+
+            self.draw_rect(&mut sprite_queue, rect, self.color(input));
+        }
+
+        // buttons
+        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        let mut x = position.x + DPAD_CELL_SIZE * 3.0 + SPACING * 2.0;
+        let y = position.y + (size.y - BUTTON_SIZE) * 0.5;
+
+        for (label, input) in BUTTONS {
+            let pressed = self.pressed.contains(&input);
+            let rect = Rect::new(x, y, BUTTON_SIZE, BUTTON_SIZE);
+            self.draw_rect(&mut sprite_queue, rect, self.color(input));
+
+            text_style.color = if pressed { Color::BLACK } else { Color::WHITE };
+
+            let label_size = text_style.measure(label).size;
+            text_style
+                .bounds
+                .set_position(rect.center() - label_size * 0.5);
+            text_style.draw(game_io, &mut sprite_queue, label);
+
+            x += BUTTON_SIZE + SPACING;
+        }
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
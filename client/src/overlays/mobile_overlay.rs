@@ -1,6 +1,8 @@
 use crate::bindable::SpriteColorMode;
+use crate::render::ui::{FontName, TextStyle};
 use crate::render::{Animator, Camera, SpriteColorQueue};
 use crate::resources::{AssetManager, Globals, ResourcePaths, RESOLUTION_F};
+use crate::saves::{TouchBinding, TOUCH_BUTTONS, TOUCH_PAGE_COUNT};
 use framework::prelude::*;
 use std::ops::Range;
 
@@ -12,16 +14,8 @@ const BASE: f32 = 64.0 * 3.0;
 const LEFT_INPUT_RANGE: Range<usize> = 0..2;
 const RIGHT_INPUT_RANGE: Range<usize> = 2..8;
 
-const BUTTON_ORDER: [Button; 8] = [
-    Button::LeftTrigger,
-    Button::Select,
-    Button::RightTrigger,
-    Button::Start,
-    Button::A,
-    Button::B,
-    Button::X,
-    Button::Y,
-];
+/// Tapping this corner of the screen switches button pages, measured at the base resolution
+const PAGE_TOGGLE_SIZE: Vec2 = Vec2::new(32.0, 16.0);
 
 pub struct MobileOverlay {
     camera: Camera,
@@ -29,6 +23,9 @@ pub struct MobileOverlay {
     button_sprites: Vec<(Button, Rect, Sprite)>,
     dpad_sprite: (Rect, Sprite),
     dpad_dead_zone: Rect,
+    page: usize,
+    page_toggle_bounds: Rect,
+    button_scale: f32,
 }
 
 impl MobileOverlay {
@@ -39,7 +36,7 @@ impl MobileOverlay {
         let sprite = assets.new_sprite(game_io, ResourcePaths::INPUT_OVERLAY);
         let mut animator = Animator::load_new(assets, ResourcePaths::INPUT_OVERLAY_ANIMATION);
 
-        let button_sprites = BUTTON_ORDER
+        let button_sprites = TOUCH_BUTTONS
             .into_iter()
             .map(|button| {
                 let button_name: &'static str = button.into();
@@ -67,6 +64,9 @@ impl MobileOverlay {
             button_sprites,
             dpad_sprite: (dpad_sprite.bounds(), dpad_sprite),
             dpad_dead_zone,
+            page: 0,
+            page_toggle_bounds: Rect::default(),
+            button_scale: 1.0,
         }
     }
 
@@ -97,6 +97,29 @@ impl GameOverlay for MobileOverlay {
 
         // update buttons
         let button_scale = window_size.y / RESOLUTION_F.y;
+        self.button_scale = button_scale;
+
+        // update page toggle
+        let toggle_size = PAGE_TOGGLE_SIZE * button_scale;
+        self.page_toggle_bounds = Rect::new(
+            window_size.x - toggle_size.x,
+            0.0,
+            toggle_size.x,
+            toggle_size.y,
+        );
+
+        let window = game_io.window();
+        let view_size = window.resolution().as_vec2() * window.render_scale();
+        let render_offset = window.render_offset();
+
+        let toggle_tapped = (game_io.input().touches().iter())
+            .filter(|touch| matches!(touch.phase, TouchPhase::Start))
+            .map(|touch| Self::unnormalize(view_size, touch.position) + render_offset)
+            .any(|position| self.page_toggle_bounds.contains(position));
+
+        if toggle_tapped {
+            self.page = (self.page + 1) % TOUCH_PAGE_COUNT;
+        }
 
         // update dpad
         {
@@ -132,12 +155,20 @@ impl GameOverlay for MobileOverlay {
 
         globals.emulated_input.flush();
 
-        for (button, _, sprite) in &self.button_sprites {
+        let bindings = globals.config.touch_layout.pages[self.page];
+
+        for ((_, _, sprite), binding) in self.button_sprites.iter().zip(bindings) {
             let bounds = sprite.bounds();
             let pressed = touches.iter().any(|&position| bounds.contains(position));
 
-            if pressed {
-                globals.emulated_input.emulate_button(*button);
+            if !pressed {
+                continue;
+            }
+
+            match binding {
+                TouchBinding::None => {}
+                TouchBinding::Button(button) => globals.emulated_input.emulate_button(button),
+                TouchBinding::Input(input) => globals.emulated_input.emulate_input(input),
             }
         }
 
@@ -175,11 +206,22 @@ impl GameOverlay for MobileOverlay {
     }
 
     fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        // touch input still works without the buttons
+        if game_io.resource::<Globals>().unwrap().config.clean_feed {
+            return;
+        }
+
         let mut queue = SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
 
         let touches = Self::touch_positions(game_io);
+        let globals = game_io.resource::<Globals>().unwrap();
+        let bindings = globals.config.touch_layout.pages[self.page];
+
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::ThinSmall);
+        text_style.shadow_color = Color::BLACK;
+        text_style.scale *= self.button_scale;
 
-        for (_, _, sprite) in &mut self.button_sprites {
+        for ((button, _, sprite), binding) in self.button_sprites.iter_mut().zip(bindings) {
             let bounds = sprite.bounds();
             let pressed = touches.iter().any(|&position| bounds.contains(position));
 
@@ -191,15 +233,28 @@ impl GameOverlay for MobileOverlay {
 
             sprite.set_color(color);
             queue.draw_sprite(sprite);
+
+            // label buttons that don't press what's drawn on them
+            if binding != TouchBinding::Button(*button) {
+                let label = binding.label();
+                let position = bounds.center() - text_style.measure(&label).size * 0.5;
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, &mut queue, &label);
+            }
         }
 
         self.dpad_sprite.1.set_color(RELEASED_COLOR);
         queue.draw_sprite(&self.dpad_sprite.1);
 
+        // page toggle
+        let page_label = format!("{}/{TOUCH_PAGE_COUNT}", self.page + 1);
+        let page_label_size = text_style.measure(&page_label).size;
+        let page_label_position = self.page_toggle_bounds.center() - page_label_size * 0.5;
+        text_style.bounds.set_position(page_label_position);
+        text_style.draw(game_io, &mut queue, &page_label);
+
         #[cfg(debug_assertions)]
         {
-            use crate::render::ui::{FontName, TextStyle};
-
             let window = game_io.window();
             let scale = window.render_scale();
             let render_offset = window.render_offset();
@@ -1,5 +1,14 @@
+mod controller_prompt_overlay;
 mod debug_overlay;
+mod input_display_overlay;
+mod notification_overlay;
+mod profiler_overlay;
+
+pub use controller_prompt_overlay::*;
 pub use debug_overlay::*;
+pub use input_display_overlay::*;
+pub use notification_overlay::*;
+pub use profiler_overlay::*;
 
 #[cfg(target_os = "android")]
 mod mobile_overlay;
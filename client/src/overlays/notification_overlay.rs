@@ -0,0 +1,97 @@
+// toasts for notifications from background work, the full list is in the notifications scene
+
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::{FontName, TextStyle};
+use crate::render::{Camera, FrameTime, SpriteColorQueue};
+use crate::resources::{
+    Globals, Notification, ResourcePaths, RESOLUTION_F, TEXT_DARK_SHADOW_COLOR,
+};
+use framework::prelude::*;
+
+const MARGIN: f32 = 4.0;
+const PADDING: f32 = 3.0;
+const WIDTH: f32 = 128.0;
+const TOAST_DURATION: FrameTime = 180;
+const FADE_DURATION: FrameTime = 20;
+const BACKDROP_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.75);
+
+pub struct NotificationOverlay {
+    camera: Camera,
+    backdrop: Sprite,
+    toast: Option<(Notification, FrameTime)>,
+}
+
+impl NotificationOverlay {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        Self {
+            camera: Camera::new_ui(game_io),
+            backdrop: (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL),
+            toast: None,
+        }
+    }
+}
+
+impl GameOverlay for NotificationOverlay {
+    fn post_update(&mut self, game_io: &mut GameIO) {
+        if let Some((_, elapsed)) = &mut self.toast {
+            *elapsed += 1;
+
+            if *elapsed < TOAST_DURATION {
+                return;
+            }
+        }
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        self.toast = globals
+            .notification_center
+            .take_toast()
+            .map(|notification| (notification, 0));
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        let Some((notification, elapsed)) = &self.toast else {
+            return;
+        };
+
+        let alpha = ((TOAST_DURATION - elapsed) as f32 / FADE_DURATION as f32).min(1.0);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style =
+            TextStyle::new(game_io, FontName::ThinSmall).with_shadow_color(TEXT_DARK_SHADOW_COLOR);
+        text_style.bounds.width = WIDTH - PADDING * 2.0;
+
+        let label = notification.kind.label();
+        let label_height = text_style.measure(label).size.y;
+        let text_height = text_style.measure(&notification.text).size.y;
+
+        // draw backdrop in the top right
+        let bounds = Rect::new(
+            RESOLUTION_F.x - WIDTH - MARGIN,
+            MARGIN,
+            WIDTH,
+            label_height + text_height + PADDING * 3.0,
+        );
+
+        self.backdrop.set_bounds(bounds);
+        self.backdrop
+            .set_color(BACKDROP_COLOR.multiply_alpha(alpha));
+        sprite_queue.draw_sprite(&self.backdrop);
+
+        // draw label and text
+        text_style.bounds.x = bounds.x + PADDING;
+        text_style.bounds.y = bounds.y + PADDING;
+        text_style.color = Color::YELLOW.multiply_alpha(alpha);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR.multiply_alpha(alpha);
+        text_style.draw(game_io, &mut sprite_queue, label);
+
+        text_style.bounds.y += label_height + PADDING;
+        text_style.color = Color::WHITE.multiply_alpha(alpha);
+        text_style.draw(game_io, &mut sprite_queue, &notification.text);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -0,0 +1,122 @@
+// breaks frame time into segments recorded by the frame profiler
+
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::{FontName, TextStyle};
+use crate::render::{Camera, SpriteColorQueue};
+use crate::resources::{Globals, ProfileSegment, RESOLUTION_F, TEXT_DARK_SHADOW_COLOR};
+use framework::prelude::*;
+use strum::IntoEnumIterator;
+
+const BAR_WIDTH: f32 = 1.0;
+/// Height of a bar using the entire target frame duration
+const TARGET_HEIGHT: f32 = 32.0;
+const GRAPH_BOTTOM: f32 = 48.0;
+const ALPHA: f32 = 0.95;
+const LEGEND_SPACING: f32 = 60.0;
+
+fn segment_color(segment: ProfileSegment) -> Color {
+    match segment {
+        ProfileSegment::Update => Color::GREEN,
+        ProfileSegment::Lua => Color::YELLOW,
+        ProfileSegment::Render => Color::new(0.3, 0.6, 1.0, 1.0),
+        ProfileSegment::Netplay => Color::ORANGE,
+    }
+}
+
+pub struct ProfilerOverlay {
+    camera: Camera,
+    rectangle: FlatModel,
+}
+
+impl ProfilerOverlay {
+    pub fn new(game_io: &GameIO) -> Self {
+        let mut camera = Camera::new(game_io);
+        camera.snap(RESOLUTION_F * 0.5);
+
+        let mut rectangle = FlatModel::new_square_model(game_io);
+        rectangle.set_origin(Vec2::new(-0.5, 0.5));
+
+        Self { camera, rectangle }
+    }
+}
+
+impl GameOverlay for ProfilerOverlay {
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        // drawn after the scene and other overlays, closing the render segment
+        let mut frame_profiler = globals.frame_profiler.borrow_mut();
+        frame_profiler.end();
+
+        if !frame_profiler.enabled() || globals.config.clean_feed {
+            return;
+        }
+
+        let target_duration = game_io.target_duration().as_secs_f32();
+        let render_pipeline = game_io.resource::<FlatPipeline>().unwrap();
+        let mut queue = RenderQueue::new(game_io, render_pipeline, [self.camera.as_binding()]);
+
+        // draw stacked bars, newest on the right
+        let bar_count = (RESOLUTION_F.x / BAR_WIDTH) as usize;
+        let history: Vec<_> = frame_profiler.history().collect();
+        let skipped = history.len().saturating_sub(bar_count);
+
+        for (i, times) in history[skipped..].iter().enumerate() {
+            let x = i as f32 * BAR_WIDTH;
+            let mut bottom = GRAPH_BOTTOM;
+
+            for segment in ProfileSegment::iter() {
+                let time = times[segment as usize].as_secs_f32();
+                let height = time / target_duration * TARGET_HEIGHT;
+
+                if height <= 0.0 {
+                    continue;
+                }
+
+                self.rectangle.set_position(Vec2::new(x, bottom));
+                self.rectangle.set_scale(Vec2::new(BAR_WIDTH, height));
+                self.rectangle
+                    .set_color(segment_color(segment).multiply_alpha(ALPHA));
+                queue.draw_model(&self.rectangle);
+
+                bottom -= height;
+            }
+        }
+
+        // draw target frame duration line
+        self.rectangle
+            .set_position(Vec2::new(0.0, GRAPH_BOTTOM - TARGET_HEIGHT));
+        self.rectangle.set_scale(Vec2::new(RESOLUTION_F.x, 1.0));
+        self.rectangle.set_color(Color::WHITE.multiply_alpha(ALPHA));
+        queue.draw_model(&self.rectangle);
+
+        render_pass.consume_queue(queue);
+
+        // draw averages
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        let averages = frame_profiler.averages();
+
+        for (i, segment) in ProfileSegment::iter().enumerate() {
+            let name: &'static str = segment.into();
+            let milliseconds = averages[segment as usize].as_secs_f32() * 1000.0;
+
+            text_style.color = segment_color(segment);
+            text_style.bounds.set_position(Vec2::new(
+                (i % 2) as f32 * LEGEND_SPACING + 2.0,
+                GRAPH_BOTTOM + 2.0 + (i / 2) as f32 * text_style.line_height(),
+            ));
+            text_style.draw(
+                game_io,
+                &mut sprite_queue,
+                &format!("{name} {milliseconds:.2}ms"),
+            );
+        }
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
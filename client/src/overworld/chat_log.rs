@@ -0,0 +1,270 @@
+use crate::render::FrameTime;
+use packets::structures::{ActorId, ChatChannel, ChatCommandInfo, MAX_CHAT_MESSAGE_LENGTH};
+use std::collections::VecDeque;
+
+const MAX_LINES: usize = 50;
+
+/// Used for tab completion, aliases are left out
+pub const BUILTIN_CHAT_COMMANDS: [&str; 6] =
+    ["invite", "leave", "party", "reply", "say", "whisper"];
+
+pub struct ChatLine {
+    pub channel: ChatChannel,
+    pub text: String,
+    /// The area's world time when the line was received
+    pub received_time: FrameTime,
+}
+
+#[derive(Default)]
+pub struct ChatLog {
+    lines: VecDeque<ChatLine>,
+    /// The last player to whisper to us, used by /r
+    pub reply_target: Option<ActorId>,
+    /// Commands registered by server scripts
+    pub server_commands: Vec<ChatCommandInfo>,
+}
+
+impl ChatLog {
+    pub fn push(&mut self, line: ChatLine) {
+        if self.lines.len() >= MAX_LINES {
+            self.lines.pop_front();
+        }
+
+        self.lines.push_back(line);
+    }
+
+    /// Oldest first
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &ChatLine> {
+        self.lines.iter()
+    }
+
+    pub fn server_command(&self, name: &str) -> Option<&ChatCommandInfo> {
+        (self.server_commands.iter()).find(|command| command.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Builtin commands followed by server commands
+    pub fn command_names(&self) -> impl Iterator<Item = &str> {
+        let server_names = self
+            .server_commands
+            .iter()
+            .map(|command| command.name.as_str());
+        BUILTIN_CHAT_COMMANDS.into_iter().chain(server_names)
+    }
+}
+
+/// Builds the displayed text for a message, whispers are labelled with the other player
+pub fn format_chat_line(
+    channel: ChatChannel,
+    sender_name: &str,
+    recipient_name: Option<&str>,
+    sent_by_us: bool,
+    message: &str,
+) -> String {
+    match channel {
+        ChatChannel::Whisper(_) if sent_by_us => {
+            format!("[To {}] {message}", recipient_name.unwrap_or("?"))
+        }
+        ChatChannel::Whisper(_) => format!("[From {sender_name}] {message}"),
+        ChatChannel::Party => format!("[Party] {sender_name}: {message}"),
+        ChatChannel::System if sender_name.is_empty() => message.to_string(),
+        ChatChannel::Area | ChatChannel::System => format!("{sender_name}: {message}"),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChatCommand<'a> {
+    Say(&'a str),
+    Party(&'a str),
+    /// The recipient's name followed by the message
+    Whisper(&'a str),
+    Reply(&'a str),
+    /// The name of the player to invite to our party
+    Invite(&'a str),
+    Leave,
+    /// Anything else, possibly a command registered by the server
+    Custom {
+        name: &'a str,
+        args: &'a str,
+    },
+}
+
+impl<'a> ChatCommand<'a> {
+    pub fn parse(text: &'a str) -> Self {
+        let text = text.trim();
+
+        let Some(command_text) = text.strip_prefix('/') else {
+            return Self::Say(text);
+        };
+
+        let (command, rest) = command_text
+            .split_once(char::is_whitespace)
+            .unwrap_or((command_text, ""));
+
+        let rest = rest.trim_start();
+
+        match command.to_lowercase().as_str() {
+            "s" | "say" => Self::Say(rest),
+            "p" | "party" => Self::Party(rest),
+            "w" | "whisper" | "tell" => Self::Whisper(rest),
+            "r" | "reply" => Self::Reply(rest),
+            "invite" => Self::Invite(rest),
+            "leave" => Self::Leave,
+            _ => Self::Custom {
+                name: command,
+                args: rest,
+            },
+        }
+    }
+}
+
+/// Splits a whisper into the recipient and message, picks the longest matching name to allow spaces in names
+pub fn split_whisper<'a, 'b, T>(
+    text: &'a str,
+    names: impl IntoIterator<Item = (T, &'b str)>,
+) -> Option<(T, &'a str)> {
+    let lowercase_text = text.to_lowercase();
+
+    let (value, name_len) = names
+        .into_iter()
+        .filter(|(_, name)| !name.is_empty())
+        .filter(|(_, name)| {
+            let name = name.to_lowercase();
+
+            lowercase_text.starts_with(&name)
+                && lowercase_text[name.len()..].starts_with(char::is_whitespace)
+        })
+        .map(|(value, name)| (value, name.to_lowercase().len()))
+        .max_by_key(|(_, name_len)| *name_len)?;
+
+    let message = text.get(name_len..)?.trim();
+
+    if message.is_empty() {
+        return None;
+    }
+
+    Some((value, message))
+}
+
+/// Case insensitive name lookup, used by /invite
+pub fn find_name<'a, T>(text: &str, names: impl IntoIterator<Item = (T, &'a str)>) -> Option<T> {
+    let text = text.trim();
+
+    (names.into_iter())
+        .find(|(_, name)| !name.is_empty() && name.eq_ignore_ascii_case(text))
+        .map(|(value, _)| value)
+}
+
+/// Completes the command name while typing the command, otherwise completes the last word as a player name.
+/// Prefers the shortest match, None if nothing matches
+pub fn complete_chat_text<'a>(
+    text: &str,
+    command_names: impl IntoIterator<Item = &'a str>,
+    player_names: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let completed = match text.strip_prefix('/') {
+        Some(command) if !command.contains(char::is_whitespace) => {
+            format!("/{} ", shortest_match(command, command_names)?)
+        }
+        _ => {
+            let word_start = text
+                .char_indices()
+                .rfind(|(_, c)| c.is_whitespace())
+                .map(|(i, c)| i + c.len_utf8())
+                .unwrap_or_default();
+
+            let word = &text[word_start..];
+
+            if word.is_empty() {
+                return None;
+            }
+
+            let name = shortest_match(word, player_names)?;
+            format!("{}{name} ", &text[..word_start])
+        }
+    };
+
+    if completed.chars().count() > MAX_CHAT_MESSAGE_LENGTH {
+        return None;
+    }
+
+    Some(completed)
+}
+
+fn shortest_match<'a>(prefix: &str, names: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let prefix = prefix.to_lowercase();
+
+    (names.into_iter())
+        .filter(|name| !name.is_empty() && name.to_lowercase().starts_with(&prefix))
+        .min_by_key(|name| name.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn commands() {
+        assert_eq!(ChatCommand::parse(" hello "), ChatCommand::Say("hello"));
+        assert_eq!(ChatCommand::parse("/p hi"), ChatCommand::Party("hi"));
+        assert_eq!(
+            ChatCommand::parse("/W Lan Hikari hi"),
+            ChatCommand::Whisper("Lan Hikari hi")
+        );
+        assert_eq!(ChatCommand::parse("/r"), ChatCommand::Reply(""));
+        assert_eq!(
+            ChatCommand::parse("/invite Lan Hikari"),
+            ChatCommand::Invite("Lan Hikari")
+        );
+        assert_eq!(ChatCommand::parse("/leave"), ChatCommand::Leave);
+        assert_eq!(
+            ChatCommand::parse("/Dance  slowly "),
+            ChatCommand::Custom {
+                name: "Dance",
+                args: "slowly"
+            }
+        );
+    }
+
+    #[test]
+    fn whispers_match_the_longest_name() {
+        let names = [(0, "Lan"), (1, "Lan Hikari"), (2, "")];
+
+        assert_eq!(split_whisper("lan hikari hi", names), Some((1, "hi")));
+        assert_eq!(split_whisper("Lan hi there", names), Some((0, "hi there")));
+        assert_eq!(split_whisper("Lanhi", names), None);
+        assert_eq!(split_whisper("Lan", names), None);
+    }
+
+    #[test]
+    fn invites_match_whole_names() {
+        let names = [(0, "Lan"), (1, "Lan Hikari"), (2, "")];
+
+        assert_eq!(find_name(" lan hikari ", names), Some(1));
+        assert_eq!(find_name("Lan", names), Some(0));
+        assert_eq!(find_name("Lan Hi", names), None);
+        assert_eq!(find_name("", names), None);
+    }
+
+    #[test]
+    fn completes_commands_and_names() {
+        let commands = ["party", "pet", "whisper"];
+        let names = ["Lan Hikari", "Lan", "Mayl"];
+
+        assert_eq!(
+            complete_chat_text("/pa", commands, names).unwrap(),
+            "/party "
+        );
+        assert_eq!(complete_chat_text("/P", commands, names).unwrap(), "/pet ");
+        assert_eq!(complete_chat_text("/x", commands, names), None);
+
+        assert_eq!(
+            complete_chat_text("/whisper ma", commands, names).unwrap(),
+            "/whisper Mayl "
+        );
+        assert_eq!(
+            complete_chat_text("hi la", commands, names).unwrap(),
+            "hi Lan "
+        );
+        assert_eq!(complete_chat_text("hi ", commands, names), None);
+    }
+}
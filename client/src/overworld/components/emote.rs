@@ -34,49 +34,46 @@ impl Emote {
     }
 
     pub fn spawn_or_recycle(area: &mut OverworldArea, parent_entity: hecs::Entity, emote_id: &str) {
+        let sprite = area.emote_sprite.clone();
+        let animator = area.emote_animator.clone();
+
+        Self::spawn_or_recycle_from(area, parent_entity, sprite, animator, emote_id);
+    }
+
+    /// Uses a sheet other than the server's, such as one from an emote package
+    pub fn spawn_or_recycle_from(
+        area: &mut OverworldArea,
+        parent_entity: hecs::Entity,
+        sprite: Sprite,
+        mut animator: Animator,
+        emote_id: &str,
+    ) {
         let entities = &mut area.entities;
 
-        if !area.emote_animator.has_state(emote_id) {
-            // despawn any existing emote
-            for (id, (_, attachment, _)) in
-                entities.query_mut::<(&mut Emote, &ActorAttachment, &mut Animator)>()
-            {
-                if attachment.actor_entity != parent_entity {
-                    continue;
-                }
+        let existing_entity = entities
+            .query_mut::<(&Emote, &ActorAttachment)>()
+            .into_iter()
+            .find(|(_, (_, attachment))| attachment.actor_entity == parent_entity)
+            .map(|(id, _)| id);
 
+        if !animator.has_state(emote_id) {
+            // despawn any existing emote
+            if let Some(id) = existing_entity {
                 let _ = entities.despawn(id);
-                break;
             }
 
             return;
         }
 
-        for (_, (emote, attachment, animator)) in
-            entities.query_mut::<(&mut Emote, &ActorAttachment, &mut Animator)>()
-        {
-            if attachment.actor_entity != parent_entity {
-                continue;
-            }
+        animator.set_state(emote_id);
+        animator.set_loop_mode(AnimatorLoopMode::Loop);
 
-            // update existing emote
-            emote.lifetime = 0;
-            animator.set_state(emote_id);
-            animator.set_loop_mode(AnimatorLoopMode::Loop);
+        if let Some(id) = existing_entity {
+            // update existing emote, the sheet may have changed
+            let _ = entities.insert(id, (Emote { lifetime: 0 }, animator, sprite));
             return;
         }
 
-        Self::spawn(area, parent_entity, emote_id);
-    }
-
-    pub fn spawn(area: &mut OverworldArea, parent_entity: hecs::Entity, emote_id: &str) {
-        let entities = &mut area.entities;
-
-        let mut animator = area.emote_animator.clone();
-        animator.set_state(emote_id);
-        animator.set_loop_mode(AnimatorLoopMode::Loop);
-
-        let sprite = area.emote_sprite.clone();
         let offset = Self::resolve_offset(entities, parent_entity).unwrap_or_else(|| {
             let Ok(parent_animator) = entities.query_one_mut::<&Animator>(parent_entity) else {
                 return Vec2::ZERO;
@@ -90,7 +87,7 @@ impl Emote {
             point: None,
         };
 
-        area.entities.spawn((
+        entities.spawn((
             Emote { lifetime: 0 },
             animator,
             sprite,
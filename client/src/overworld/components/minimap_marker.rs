@@ -0,0 +1,2 @@
+/// Marks an actor on the minimap, toggled by the server
+pub struct MinimapMarker;
@@ -4,6 +4,7 @@ mod attachments;
 mod emote;
 mod excluded;
 mod interactable_actor;
+mod minimap_marker;
 mod movement_animator;
 mod movement_interpolator;
 mod name_label;
@@ -16,6 +17,7 @@ pub use attachments::*;
 pub use emote::*;
 pub use excluded::*;
 pub use interactable_actor::*;
+pub use minimap_marker::*;
 pub use movement_animator::*;
 pub use movement_interpolator::*;
 pub use name_label::*;
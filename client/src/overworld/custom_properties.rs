@@ -33,6 +33,10 @@ impl CustomProperties {
 
         str_value.parse::<f32>().unwrap_or_default()
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        (self.properties.iter()).map(|(name, value)| (name.as_str(), value.as_str()))
+    }
 }
 
 impl From<roxmltree::Node<'_, '_>> for CustomProperties {
@@ -6,12 +6,29 @@ use hecs::Entity;
 use std::collections::HashMap;
 use std::rc::Rc;
 
+/// Read only access to a map object, for UI that annotates the world
+pub struct MapObjectView<'a> {
+    pub data: &'a ObjectData,
+    pub tile: Option<&'a Tile>,
+    pub position: Vec3,
+}
+
+/// A one shot animation triggered by the server, drawn in place of the tile at its position
+struct TileAnimation {
+    position: IVec3,
+    gid: u32,
+    start_time: FrameTime,
+    animator: Animator,
+}
+
 pub struct Map {
     cols: u32,
     rows: u32,
     tile_size: IVec2,
     name: String,
+    private: bool,
     music_path: String,
+    music_fade: Duration,
     background_properties: BackgroundProperties,
     foreground_properties: BackgroundProperties,
     shadow_map: ShadowMap,
@@ -20,6 +37,7 @@ pub struct Map {
     tilesets: Vec<Rc<Tileset>>,
     tile_metas: Vec<Option<TileMeta>>,
     tiles_modified: bool,
+    tile_animations: Vec<TileAnimation>,
     object_entity_map: HashMap<u32, hecs::Entity>,
     object_entities: hecs::World,
     projection: Projection,
@@ -34,7 +52,9 @@ impl Map {
             rows,
             tile_size: IVec2::new(tile_width, tile_height),
             name: String::new(),
+            private: false,
             music_path: String::new(),
+            music_fade: Duration::ZERO,
             background_properties: BackgroundProperties::default(),
             foreground_properties: BackgroundProperties::default(),
             shadow_map: ShadowMap::new(cols as usize, rows as usize),
@@ -43,6 +63,7 @@ impl Map {
             tilesets: Vec::new(),
             tile_metas: Vec::new(),
             tiles_modified: false,
+            tile_animations: Vec::new(),
             object_entity_map: HashMap::new(),
             object_entities: hecs::World::new(),
             projection: Projection::Isometric,
@@ -57,6 +78,15 @@ impl Map {
         self.name = name;
     }
 
+    /// Private maps are kept out of the journal
+    pub fn private(&self) -> bool {
+        self.private
+    }
+
+    pub fn set_private(&mut self, private: bool) {
+        self.private = private;
+    }
+
     pub fn background_properties(&self) -> &BackgroundProperties {
         &self.background_properties
     }
@@ -89,6 +119,15 @@ impl Map {
         self.music_path = path;
     }
 
+    /// Crossfade duration when entering the area or switching tracks
+    pub fn music_fade(&self) -> Duration {
+        self.music_fade
+    }
+
+    pub fn set_music_fade(&mut self, duration: Duration) {
+        self.music_fade = duration;
+    }
+
     pub fn cols(&self) -> u32 {
         self.cols
     }
@@ -208,6 +247,38 @@ impl Map {
         self.object_entity_map.get(&id).cloned()
     }
 
+    /// Visits every object that hasn't been excluded by the server
+    pub fn inspect_objects(&self, mut callback: impl FnMut(MapObjectView)) {
+        type Query<'a> = hecs::Without<(&'a Vec3, Option<&'a Tile>, &'a ObjectData), &'a Excluded>;
+        let mut query = self.object_entities.query::<Query>();
+
+        for (_, (&position, tile, data)) in query.iter() {
+            callback(MapObjectView {
+                data,
+                tile,
+                position,
+            });
+        }
+    }
+
+    /// Visits objects within `radius` tiles of a world position, ignoring elevation
+    pub fn inspect_objects_near(
+        &self,
+        world_position: Vec3,
+        radius: f32,
+        mut callback: impl FnMut(MapObjectView),
+    ) {
+        let tile_position = self.world_to_tile_space(world_position.xy());
+
+        self.inspect_objects(|object| {
+            let object_tile_position = self.world_to_tile_space(object.position.xy());
+
+            if object_tile_position.distance(tile_position) <= radius {
+                callback(object);
+            }
+        });
+    }
+
     pub fn screen_direction_to_world(&self, direction: Direction) -> Direction {
         if self.projection == Projection::Orthographic {
             direction
@@ -553,12 +624,71 @@ impl Map {
         None
     }
 
+    /// Plays the animation defined in the tileset for `gid` once, covering the tile at `position`
+    pub fn play_tile_animation(&mut self, position: IVec3, gid: u32, world_time: FrameTime) {
+        let Some(tile_meta) = self.tile_meta_for_tile(gid) else {
+            log::warn!("No tile found for animation gid {gid}");
+            return;
+        };
+
+        let mut animator = Animator::new();
+        animator.copy_from(&tile_meta.animator);
+        animator.set_loop_mode(AnimatorLoopMode::Once);
+        animator.sync_time(0);
+
+        let state = tile_meta.id.to_string();
+        let duration = (animator.frame_list(&state))
+            .map(|frame_list| frame_list.duration())
+            .unwrap_or_default();
+
+        if duration == 0 {
+            log::warn!("Tile {gid} has no animation defined in its tileset");
+            return;
+        }
+
+        self.tile_animations
+            .retain(|animation| animation.position != position);
+
+        self.tile_animations.push(TileAnimation {
+            position,
+            gid,
+            start_time: world_time,
+            animator,
+        });
+    }
+
+    /// Keeps animations playing when the map is replaced by an update from the server
+    pub fn take_tile_animations(&mut self, other: &mut Map) {
+        self.tile_animations = std::mem::take(&mut other.tile_animations);
+        let tile_metas = &self.tile_metas;
+
+        self.tile_animations
+            .retain(|animation| matches!(tile_metas.get(animation.gid as usize), Some(Some(_))));
+    }
+
+    fn tile_animation_at(&self, col: i32, row: i32, layer_index: usize) -> Option<&TileAnimation> {
+        let position = IVec3::new(col, row, layer_index as i32);
+
+        self.tile_animations
+            .iter()
+            .find(|animation| animation.position == position)
+    }
+
     pub fn update(&mut self, world_time: FrameTime) {
         // update tile animations
         for meta in self.tile_metas.iter_mut().filter_map(|meta| meta.as_mut()) {
             meta.animator.sync_time(world_time);
         }
 
+        // update one shot tile animations
+        self.tile_animations.retain_mut(|animation| {
+            animation
+                .animator
+                .sync_time(world_time - animation.start_time);
+
+            !animation.animator.is_complete()
+        });
+
         // apply tile animations to tile objects
         for (_, (tile, sprite)) in self.object_entities.query_mut::<(&Tile, &mut Sprite)>() {
             let tile_meta = self
@@ -632,7 +762,12 @@ impl Map {
                 let col = tile_space_start_i.x + vertical_start + j;
                 let row = tile_space_start_i.y + vertical_start - j + row_offset;
 
-                let tile = layer.tile_at((col, row).into());
+                let animation = self.tile_animation_at(col, row, layer_index);
+
+                let tile = match animation {
+                    Some(animation) => Tile::new(animation.gid),
+                    None => *layer.tile_at((col, row).into()),
+                };
 
                 // skip tiles with missing meta information
                 let Some(tile_meta) = self.tile_meta_for_tile(tile.gid) else {
@@ -656,7 +791,11 @@ impl Map {
                     }
                 };
 
-                tile_meta.animator.apply(tile_sprite);
+                match animation {
+                    Some(animation) => animation.animator.apply(tile_sprite),
+                    None => tile_meta.animator.apply(tile_sprite),
+                }
+
                 let sprite_bounds = tile_sprite.size();
 
                 let pos_i = IVec2::new((col * tile_size.x) / 2, row * tile_size.y);
@@ -687,7 +826,7 @@ impl Map {
                     if tile.flipped_vertical { -1.0 } else { 1.0 },
                 ));
 
-                callback(tile_sprite, tile_meta, *tile, (col, row))
+                callback(tile_sprite, tile_meta, tile, (col, row))
             }
         }
     }
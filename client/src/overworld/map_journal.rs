@@ -0,0 +1,175 @@
+use crate::resources::ResourcePaths;
+use framework::prelude::IVec2;
+use packets::address_parsing::uri_encode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Width and height of an explored chunk in tiles
+pub const JOURNAL_CHUNK_SIZE: i32 = 8;
+
+/// How many chunks around the player are revealed
+const JOURNAL_VIEW_RADIUS: i32 = 1;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalArea {
+    /// The path the server sent the map from, used to identify the area
+    pub map_path: String,
+    pub name: String,
+    /// The latest map data received for this area, kept to draw the area after leaving it
+    pub map_data: String,
+    explored_chunks: HashSet<(i32, i32)>,
+}
+
+impl JournalArea {
+    fn chunk_for_tile(tile: IVec2) -> (i32, i32) {
+        (
+            tile.x.div_euclid(JOURNAL_CHUNK_SIZE),
+            tile.y.div_euclid(JOURNAL_CHUNK_SIZE),
+        )
+    }
+
+    pub fn is_explored(&self, tile: IVec2) -> bool {
+        self.explored_chunks.contains(&Self::chunk_for_tile(tile))
+    }
+
+    /// Returns true if a new chunk was revealed
+    fn explore(&mut self, tile: IVec2) -> bool {
+        let (chunk_x, chunk_y) = Self::chunk_for_tile(tile);
+        let mut revealed = false;
+
+        for y in -JOURNAL_VIEW_RADIUS..=JOURNAL_VIEW_RADIUS {
+            for x in -JOURNAL_VIEW_RADIUS..=JOURNAL_VIEW_RADIUS {
+                revealed |= self.explored_chunks.insert((chunk_x + x, chunk_y + y));
+            }
+        }
+
+        revealed
+    }
+}
+
+/// Areas explored on a server, maps marked as private are never recorded
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapJournal {
+    #[serde(skip)]
+    file_path: String,
+    #[serde(skip)]
+    modified: bool,
+    /// Sorted by first visit
+    areas: Vec<JournalArea>,
+}
+
+impl MapJournal {
+    pub fn for_address(address: &str) -> Self {
+        let address = packets::address_parsing::strip_data(address).replace(':', "_p");
+        let file_path =
+            ResourcePaths::profile_path(ResourcePaths::JOURNAL_FOLDER) + &uri_encode(&address);
+
+        let mut journal = match std::fs::read(&file_path) {
+            Ok(bytes) => rmp_serde::from_slice(&bytes).unwrap_or_else(|e| {
+                log::error!("Failed to load journal from {file_path:?}: {e}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+
+        journal.file_path = file_path;
+        journal
+    }
+
+    pub fn areas(&self) -> &[JournalArea] {
+        &self.areas
+    }
+
+    pub fn area(&self, map_path: &str) -> Option<&JournalArea> {
+        self.areas.iter().find(|area| area.map_path == map_path)
+    }
+
+    /// Stores the latest map data for an area
+    pub fn record_map(&mut self, map_path: &str, name: &str, map_data: &str) {
+        let area = match self.areas.iter_mut().find(|a| a.map_path == map_path) {
+            Some(area) => area,
+            None => {
+                self.areas.push(JournalArea {
+                    map_path: map_path.to_string(),
+                    ..Default::default()
+                });
+
+                self.areas.last_mut().unwrap()
+            }
+        };
+
+        if area.name != name || area.map_data != map_data {
+            area.name = name.to_string();
+            area.map_data = map_data.to_string();
+            self.modified = true;
+        }
+    }
+
+    /// Forgets an area, used when a server marks a previously recorded map as private
+    pub fn remove_area(&mut self, map_path: &str) {
+        let original_len = self.areas.len();
+        self.areas.retain(|area| area.map_path != map_path);
+        self.modified |= self.areas.len() != original_len;
+    }
+
+    pub fn explore(&mut self, map_path: &str, tile: IVec2) {
+        let Some(area) = self.areas.iter_mut().find(|a| a.map_path == map_path) else {
+            return;
+        };
+
+        self.modified |= area.explore(tile);
+    }
+
+    /// Writes to disk if anything changed since the last save
+    pub fn save(&mut self) {
+        if !self.modified || self.file_path.is_empty() {
+            return;
+        }
+
+        let _ = std::fs::create_dir_all(ResourcePaths::profile_path(ResourcePaths::JOURNAL_FOLDER));
+
+        let bytes = match rmp_serde::to_vec_named(self) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::error!("Failed to serialize journal: {e}");
+                return;
+            }
+        };
+
+        if let Err(e) = std::fs::write(&self.file_path, bytes) {
+            log::error!("Failed to save journal to {:?}: {e}", self.file_path);
+            return;
+        }
+
+        self.modified = false;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn explores_chunks_around_tiles() {
+        let mut journal = MapJournal::default();
+
+        // unrecorded areas can't be explored
+        journal.explore("/server/maps/a.tmx", IVec2::ZERO);
+        assert!(journal.areas().is_empty());
+
+        journal.record_map("/server/maps/a.tmx", "A", "<map/>");
+        journal.explore("/server/maps/a.tmx", IVec2::new(3, -1));
+
+        let area = journal.area("/server/maps/a.tmx").unwrap();
+        let chunk_size = JOURNAL_CHUNK_SIZE;
+        assert!(area.is_explored(IVec2::new(0, -1)));
+        assert!(area.is_explored(IVec2::new(chunk_size * 2 - 1, chunk_size - 1)));
+        assert!(!area.is_explored(IVec2::new(chunk_size * 2, 0)));
+        assert!(!area.is_explored(IVec2::new(0, -chunk_size * 2 - 1)));
+
+        journal.remove_area("/server/maps/a.tmx");
+        assert!(journal.areas().is_empty());
+    }
+}
@@ -72,7 +72,7 @@ impl Bbs {
         let list_point = animator.point("POSTS_START").unwrap_or_default() + posts_bg_point;
 
         let cursor_start = list_point + Vec2::new(-5.0, 3.0);
-        scroll_tracker.define_cursor(cursor_start, 16.0);
+        scroll_tracker.define_text_cursor(game_io, cursor_start, 16.0);
 
         Self {
             topic,
@@ -0,0 +1,271 @@
+use super::Menu;
+use crate::overworld::components::NameLabel;
+use crate::overworld::{complete_chat_text, ChatLine, OverworldArea, OverworldEvent};
+use crate::render::ui::{
+    FontName, TextInput, TextStyle, Textbox, UiInputTracker, UiLayout, UiLayoutNode, UiStyle,
+};
+use crate::render::SpriteColorQueue;
+use crate::resources::{Globals, InputUtil, ResourcePaths, RESOLUTION_F, TEXT_DARK_SHADOW_COLOR};
+use framework::prelude::*;
+use packets::structures::{ChatChannel, Input, MAX_CHAT_MESSAGE_LENGTH};
+
+const MARGIN: f32 = 4.0;
+const PANEL_HEIGHT: f32 = 96.0;
+const INPUT_HEIGHT: f32 = 12.0;
+const PANEL_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.6);
+const INPUT_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.5);
+const MAX_COMMAND_HINTS: usize = 3;
+
+fn chat_line_color(line: &ChatLine) -> Color {
+    match line.channel {
+        ChatChannel::Area => Color::WHITE,
+        ChatChannel::Whisper(_) => Color::new(0.97, 0.6, 0.85, 1.0),
+        ChatChannel::Party => Color::new(0.55, 0.95, 0.55, 1.0),
+        ChatChannel::System => Color::new(0.97, 0.86, 0.0, 1.0),
+    }
+}
+
+/// Draws lines from newest to oldest above the bottom of the bounds, stops at the top of the bounds
+pub fn draw_chat_lines<'a>(
+    game_io: &GameIO,
+    sprite_queue: &mut SpriteColorQueue,
+    bounds: Rect,
+    lines: impl Iterator<Item = &'a ChatLine>,
+) {
+    let mut text_style =
+        TextStyle::new(game_io, FontName::ThinSmall).with_shadow_color(TEXT_DARK_SHADOW_COLOR);
+    text_style.bounds.width = bounds.width;
+
+    let mut bottom = bounds.y + bounds.height;
+
+    for line in lines {
+        let height = text_style.measure(&line.text).size.y;
+
+        if bottom - height < bounds.y {
+            break;
+        }
+
+        bottom -= height;
+
+        text_style.color = chat_line_color(line);
+        text_style.bounds.x = bounds.x;
+        text_style.bounds.y = bottom;
+        text_style.draw(game_io, sprite_queue, &line.text);
+    }
+}
+
+/// Text entry for chat with the recent history, supports /w, /p, /r, and commands registered by the server.
+/// Tab completes command and player names
+pub struct ChatBox {
+    pixel_sprite: Sprite,
+    input_layout: Option<UiLayout>,
+    input_text: String,
+    text_sender: flume::Sender<String>,
+    text_receiver: flume::Receiver<String>,
+    edit_sender: flume::Sender<String>,
+    edit_receiver: flume::Receiver<String>,
+    ui_input_tracker: UiInputTracker,
+    open: bool,
+}
+
+impl ChatBox {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let (text_sender, text_receiver) = flume::unbounded();
+        let (edit_sender, edit_receiver) = flume::unbounded();
+
+        Self {
+            pixel_sprite: (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL),
+            input_layout: None,
+            input_text: String::new(),
+            text_sender,
+            text_receiver,
+            edit_sender,
+            edit_receiver,
+            ui_input_tracker: UiInputTracker::new(),
+            open: false,
+        }
+    }
+
+    fn panel_bounds() -> Rect {
+        Rect::new(
+            0.0,
+            RESOLUTION_F.y - PANEL_HEIGHT,
+            RESOLUTION_F.x,
+            PANEL_HEIGHT,
+        )
+    }
+
+    fn input_bounds() -> Rect {
+        Rect::new(
+            MARGIN,
+            RESOLUTION_F.y - INPUT_HEIGHT - MARGIN,
+            RESOLUTION_F.x - MARGIN * 2.0,
+            INPUT_HEIGHT,
+        )
+    }
+
+    fn create_input_layout(&self, game_io: &GameIO, text: &str) -> UiLayout {
+        let text_sender = self.text_sender.clone();
+        let edit_sender = self.edit_sender.clone();
+
+        UiLayout::new_horizontal(
+            Self::input_bounds(),
+            vec![UiLayoutNode::new(
+                TextInput::new(game_io, FontName::ThinSmall)
+                    .with_str(text)
+                    .with_color(Color::WHITE)
+                    .with_character_limit(MAX_CHAT_MESSAGE_LENGTH)
+                    .with_silent(true)
+                    .with_active(true)
+                    .on_edit(move |value| edit_sender.send(value.to_string()).unwrap())
+                    .on_change(move |value| text_sender.send(value.to_string()).unwrap()),
+            )
+            .with_style(UiStyle {
+                flex_grow: 1.0,
+                flex_shrink: 1.0,
+                ..Default::default()
+            })],
+        )
+    }
+
+    fn draw_rect(&mut self, sprite_queue: &mut SpriteColorQueue, bounds: Rect, color: Color) {
+        self.pixel_sprite.set_bounds(bounds);
+        self.pixel_sprite.set_color(color);
+        sprite_queue.draw_sprite(&self.pixel_sprite);
+    }
+}
+
+impl Menu for ChatBox {
+    fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn open(&mut self, game_io: &mut GameIO, _area: &mut OverworldArea) {
+        self.open = true;
+        self.input_text.clear();
+        self.input_layout = Some(self.create_input_layout(game_io, ""));
+    }
+
+    fn update(&mut self, game_io: &mut GameIO, area: &mut OverworldArea) {
+        if let Some(text) = self.edit_receiver.try_iter().last() {
+            self.input_text = text;
+        }
+
+        let Ok(text) = self.text_receiver.try_recv() else {
+            return;
+        };
+
+        // tab also ends text input, reopen it with the completed text
+        if game_io.input().was_key_just_pressed(Key::Tab) {
+            let local_entity = area.player_data.entity;
+            let mut name_query = area.entities.query::<&NameLabel>();
+            let names = (name_query.iter())
+                .filter(|(entity, _)| *entity != local_entity)
+                .map(|(_, name_label)| name_label.0.as_str());
+
+            let command_names = area.chat_log.command_names();
+            let text = complete_chat_text(&text, command_names, names).unwrap_or(text);
+
+            self.input_layout = Some(self.create_input_layout(game_io, &text));
+            self.input_text = text;
+            return;
+        }
+
+        // escape and cancel also end text input, only return sends
+        let input_util = InputUtil::new(game_io);
+        let cancelled = game_io.input().was_key_just_pressed(Key::Escape)
+            || input_util.controller_just_pressed(Input::Cancel);
+
+        if !cancelled && !text.trim().is_empty() {
+            let _ = area.event_sender.send(OverworldEvent::ChatSubmitted(text));
+        }
+
+        self.input_layout = None;
+        self.open = false;
+    }
+
+    fn handle_input(
+        &mut self,
+        game_io: &mut GameIO,
+        _area: &mut OverworldArea,
+        _textbox: &mut Textbox,
+    ) {
+        self.ui_input_tracker.update(game_io);
+
+        if let Some(layout) = &mut self.input_layout {
+            layout.update(game_io, &self.ui_input_tracker);
+        }
+    }
+
+    fn draw(
+        &mut self,
+        game_io: &GameIO,
+        _render_pass: &mut RenderPass,
+        sprite_queue: &mut SpriteColorQueue,
+        area: &OverworldArea,
+    ) {
+        // draw panel
+        let panel_bounds = Self::panel_bounds();
+        self.draw_rect(sprite_queue, panel_bounds, PANEL_COLOR);
+
+        // draw history above the input
+        let input_bounds = Self::input_bounds();
+        let history_bounds = Rect::new(
+            MARGIN,
+            panel_bounds.y + MARGIN,
+            panel_bounds.width - MARGIN * 2.0,
+            input_bounds.y - panel_bounds.y - MARGIN * 2.0,
+        );
+
+        draw_chat_lines(
+            game_io,
+            sprite_queue,
+            history_bounds,
+            area.chat_log.lines().rev(),
+        );
+
+        // draw descriptions for matching server commands above the input
+        if let Some(prefix) = self.input_text.strip_prefix('/') {
+            if !prefix.contains(char::is_whitespace) {
+                let prefix = prefix.to_lowercase();
+
+                let hints: Vec<_> = (area.chat_log.server_commands.iter())
+                    .filter(|command| command.name.to_lowercase().starts_with(&prefix))
+                    .take(MAX_COMMAND_HINTS)
+                    .map(|command| format!("/{} {}", command.name, command.description))
+                    .collect();
+
+                let mut text_style = TextStyle::new(game_io, FontName::ThinSmall)
+                    .with_shadow_color(TEXT_DARK_SHADOW_COLOR);
+                text_style.bounds.x = input_bounds.x;
+                text_style.bounds.y = input_bounds.y;
+
+                for hint in hints.iter().rev() {
+                    text_style.bounds.y -= INPUT_HEIGHT;
+
+                    let hint_bounds = Rect::new(
+                        input_bounds.x,
+                        text_style.bounds.y,
+                        input_bounds.width,
+                        INPUT_HEIGHT,
+                    );
+                    self.draw_rect(sprite_queue, hint_bounds, INPUT_COLOR);
+
+                    text_style.draw(game_io, sprite_queue, hint);
+                }
+            }
+        }
+
+        // draw input
+        self.draw_rect(sprite_queue, input_bounds, INPUT_COLOR);
+
+        if let Some(layout) = &mut self.input_layout {
+            layout.draw(game_io, sprite_queue);
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use super::Menu;
+use crate::overworld::{OverworldArea, OverworldEvent};
+use crate::packages::{PackageId, PackageNamespace};
+use crate::render::ui::{FontName, TextStyle, Textbox, UiInputTracker};
+use crate::render::{Animator, SpriteColorQueue};
+use crate::resources::{Globals, InputUtil, ResourcePaths, RESOLUTION_F, TEXT_DARK_SHADOW_COLOR};
+use framework::prelude::*;
+use packets::structures::{Direction, Input};
+
+const RADIUS: f32 = 44.0;
+const SLOT_SIZE: Vec2 = Vec2::new(24.0, 24.0);
+const SLOT_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.5);
+const SELECTED_SLOT_COLOR: Color = Color::new(0.97, 0.86, 0.0, 0.75);
+
+/// Slots in clockwise order starting from the top
+const SLOT_DIRECTIONS: [Direction; 8] = [
+    Direction::Up,
+    Direction::UpRight,
+    Direction::Right,
+    Direction::DownRight,
+    Direction::Down,
+    Direction::DownLeft,
+    Direction::Left,
+    Direction::UpLeft,
+];
+
+#[derive(Clone)]
+enum WheelEmote {
+    Server(String),
+    Package {
+        package_id: PackageId,
+        emote_id: String,
+    },
+}
+
+impl WheelEmote {
+    fn emote_id(&self) -> &str {
+        match self {
+            WheelEmote::Server(emote_id) => emote_id,
+            WheelEmote::Package { emote_id, .. } => emote_id,
+        }
+    }
+}
+
+struct WheelEntry {
+    emote: WheelEmote,
+    /// Index into the wheel's sheets
+    sheet_index: usize,
+}
+
+/// Picks emotes with a direction, includes the server's emotes and emotes from installed packages
+pub struct EmoteWheel {
+    pixel_sprite: Sprite,
+    /// Sprites and animators for the server's emotes followed by each emote package
+    sheets: Vec<(Sprite, Animator)>,
+    entries: Vec<WheelEntry>,
+    page: usize,
+    selected_slot: Option<usize>,
+    ui_input_tracker: UiInputTracker,
+    open: bool,
+}
+
+impl EmoteWheel {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        Self {
+            pixel_sprite: (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL),
+            sheets: Vec::new(),
+            entries: Vec::new(),
+            page: 0,
+            selected_slot: None,
+            ui_input_tracker: UiInputTracker::new(),
+            open: false,
+        }
+    }
+
+    fn page_count(&self) -> usize {
+        self.entries.len().div_ceil(SLOT_DIRECTIONS.len()).max(1)
+    }
+
+    fn page_entries(&self) -> &[WheelEntry] {
+        let start = self.page * SLOT_DIRECTIONS.len();
+        let end = (start + SLOT_DIRECTIONS.len()).min(self.entries.len());
+
+        self.entries.get(start..end).unwrap_or_default()
+    }
+
+    fn selected_entry(&self) -> Option<&WheelEntry> {
+        self.page_entries().get(self.selected_slot?)
+    }
+
+    fn slot_center(slot: usize) -> Vec2 {
+        let angle = slot as f32 * std::f32::consts::TAU / SLOT_DIRECTIONS.len() as f32;
+        let offset = Vec2::new(angle.sin(), -angle.cos()) * RADIUS;
+
+        (RESOLUTION_F * 0.5 + offset).floor()
+    }
+}
+
+impl Menu for EmoteWheel {
+    fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn open(&mut self, game_io: &mut GameIO, area: &mut OverworldArea) {
+        self.open = true;
+        self.page = 0;
+        self.selected_slot = None;
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        // server emotes
+        self.sheets = vec![(area.emote_sprite.clone(), area.emote_animator.clone())];
+
+        let server_emotes = area
+            .emote_animator
+            .iter_states()
+            .map(|(name, _)| WheelEntry {
+                emote: WheelEmote::Server(name.to_string()),
+                sheet_index: 0,
+            });
+
+        self.entries = server_emotes.collect();
+
+        // package emotes
+        let mut packages: Vec<_> = (globals.emote_packages)
+            .packages(PackageNamespace::Local)
+            .collect();
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for package in packages {
+            let sprite = globals.assets.new_sprite(game_io, &package.texture_path);
+            let animator = Animator::load_new(&globals.assets, &package.animation_path);
+            let sheet_index = self.sheets.len();
+
+            for (name, _) in animator.iter_states() {
+                self.entries.push(WheelEntry {
+                    emote: WheelEmote::Package {
+                        package_id: package.package_info.id.clone(),
+                        emote_id: name.to_string(),
+                    },
+                    sheet_index,
+                });
+            }
+
+            self.sheets.push((sprite, animator));
+        }
+    }
+
+    fn update(&mut self, _game_io: &mut GameIO, _area: &mut OverworldArea) {}
+
+    fn handle_input(
+        &mut self,
+        game_io: &mut GameIO,
+        area: &mut OverworldArea,
+        _textbox: &mut Textbox,
+    ) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel)
+            || self.ui_input_tracker.is_active(Input::Info)
+        {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+            self.open = false;
+            return;
+        }
+
+        // pages
+        let page_count = self.page_count();
+        let prev_page = self.page;
+
+        if self.ui_input_tracker.is_active(Input::ShoulderL) {
+            self.page = (self.page + page_count - 1) % page_count;
+        }
+
+        if self.ui_input_tracker.is_active(Input::ShoulderR) {
+            self.page = (self.page + 1) % page_count;
+        }
+
+        if prev_page != self.page {
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+
+        // the held direction picks the slot
+        let direction = InputUtil::new(game_io).direction();
+        let prev_slot = self.selected_slot;
+
+        if direction != Direction::None {
+            self.selected_slot = SLOT_DIRECTIONS.iter().position(|d| *d == direction);
+        }
+
+        if prev_slot != self.selected_slot {
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        let Some(entry) = self.selected_entry() else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            return;
+        };
+
+        let event = match entry.emote.clone() {
+            WheelEmote::Server(emote_id) => OverworldEvent::EmoteSelected(emote_id),
+            WheelEmote::Package {
+                package_id,
+                emote_id,
+            } => OverworldEvent::PackageEmoteSelected {
+                package_id,
+                emote_id,
+            },
+        };
+
+        let _ = area.event_sender.send(event);
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+        self.open = false;
+    }
+
+    fn draw(
+        &mut self,
+        game_io: &GameIO,
+        _render_pass: &mut RenderPass,
+        sprite_queue: &mut SpriteColorQueue,
+        area: &OverworldArea,
+    ) {
+        let start = self.page * SLOT_DIRECTIONS.len();
+
+        for slot in 0..SLOT_DIRECTIONS.len() {
+            let center = Self::slot_center(slot);
+
+            // draw slot
+            let color = if self.selected_slot == Some(slot) {
+                SELECTED_SLOT_COLOR
+            } else {
+                SLOT_COLOR
+            };
+
+            let top_left = center - SLOT_SIZE * 0.5;
+            self.pixel_sprite.set_bounds(Rect::new(
+                top_left.x,
+                top_left.y,
+                SLOT_SIZE.x,
+                SLOT_SIZE.y,
+            ));
+            self.pixel_sprite.set_color(color);
+            sprite_queue.draw_sprite(&self.pixel_sprite);
+
+            // draw emote
+            let Some(entry) = self.entries.get(start + slot) else {
+                continue;
+            };
+
+            let (sprite, animator) = &mut self.sheets[entry.sheet_index];
+            animator.set_state(entry.emote.emote_id());
+
+            let scale = animator.point("SCALE").unwrap_or(Vec2::ONE);
+            sprite.set_scale(scale);
+
+            animator.sync_time(area.world_time);
+            animator.apply(sprite);
+
+            sprite.set_position(center);
+            sprite_queue.draw_sprite(sprite);
+        }
+
+        // draw selection name and page
+        let mut text_style =
+            TextStyle::new(game_io, FontName::ThinSmall).with_shadow_color(TEXT_DARK_SHADOW_COLOR);
+
+        let mut lines = Vec::new();
+
+        if let Some(entry) = self.selected_entry() {
+            lines.push(entry.emote.emote_id().to_uppercase());
+        }
+
+        if self.page_count() > 1 {
+            lines.push(format!("{}/{}", self.page + 1, self.page_count()));
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            let size = text_style.measure(line).size;
+            let center = RESOLUTION_F * 0.5;
+
+            text_style.bounds.x = (center.x - size.x * 0.5).floor();
+            text_style.bounds.y = (center.y - size.y + i as f32 * (size.y + 2.0)).floor();
+            text_style.draw(game_io, sprite_queue, line);
+        }
+    }
+}
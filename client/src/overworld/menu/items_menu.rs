@@ -83,10 +83,14 @@ impl ItemsMenu {
         player_animator.apply(&mut player_sprite);
 
         // scroll tracker
-        let mut scroll_tracker = ScrollTracker::new(game_io, 8);
-        let button_height = TextStyle::new(game_io, FontName::Thick).line_height()
-            + button_nine_patch.top_height()
-            + button_nine_patch.bottom_height();
+        let text_style = TextStyle::new(game_io, FontName::Thick);
+        let button_padding = button_nine_patch.top_height() + button_nine_patch.bottom_height();
+        let button_height = text_style.line_height() + button_padding;
+
+        // room for 8 unscaled buttons, fewer fit as the text scale grows
+        let list_height = (text_style.line_height() / text_style.scale.y + button_padding) * 8.0;
+        let mut scroll_tracker =
+            ScrollTracker::new(game_io, (list_height / button_height) as usize);
         scroll_tracker.define_cursor(cursor_start, button_height);
 
         // description bg sprite
@@ -143,7 +147,7 @@ impl ItemsMenu {
         // if there were previously 0 items, initialize the description text
         if self.registered_count == 0 {
             if let Some((_, item_definition, _)) = Self::consumables_iter(area).next() {
-                self.description_text.text = item_definition.description.clone();
+                self.description_text.text = item_definition.description_with_metadata();
             }
         }
 
@@ -224,7 +228,7 @@ impl Menu for ItemsMenu {
 
             // update description
             let (_, item_definition, _) = Self::consumables_iter(area).nth(index).unwrap();
-            self.description_text.text = item_definition.description.clone();
+            self.description_text.text = item_definition.description_with_metadata();
         }
 
         // handle selection
@@ -1,12 +1,16 @@
 use super::Menu;
 use crate::overworld::components::{Excluded, PlayerMapMarker};
-use crate::overworld::{Map, ObjectData, ObjectType, OverworldArea, Tile, TileClass};
-use crate::render::ui::Textbox;
+use crate::overworld::{
+    JournalArea, Map, MapObjectView, ObjectType, OverworldArea, OverworldEvent, TileClass,
+};
+use crate::render::ui::{FontName, TextStyle, Textbox};
 use crate::render::{
     Animator, Background, Camera, FrameTime, MapSpriteQueue, MapTileSpriteQueue, MapTileUniforms,
     SpriteColorQueue,
 };
-use crate::resources::{AssetManager, Globals, Input, InputUtil, ResourcePaths, RESOLUTION_F};
+use crate::resources::{
+    AssetManager, Globals, Input, InputUtil, ResourcePaths, RESOLUTION_F, TEXT_DARK_SHADOW_COLOR,
+};
 use framework::{prelude::*, wgpu};
 use packets::structures::Direction;
 
@@ -32,7 +36,11 @@ pub struct MapMenu {
     scale: f32,
     scrollable: bool,
     scroll_offset: Vec2,
+    default_position: Vec2,
     last_map_update: FrameTime,
+    /// Index of the journal area being viewed, the current area is displayed when unset
+    journal_page: Option<usize>,
+    rendered_journal_page: Option<usize>,
     open: bool,
 }
 
@@ -61,12 +69,21 @@ impl MapMenu {
             scale: 1.0,
             scrollable: false,
             scroll_offset: Vec2::ZERO,
+            default_position: Vec2::ZERO,
             last_map_update: -1,
+            journal_page: None,
+            rendered_journal_page: None,
             open: false,
         }
     }
 
-    fn update_map_sprites(&mut self, game_io: &GameIO, map: &Map) {
+    /// Only explored tiles are drawn when `journal_area` is set
+    fn update_map_sprites(
+        &mut self,
+        game_io: &GameIO,
+        map: &Map,
+        journal_area: Option<&JournalArea>,
+    ) {
         let tile_size = map.tile_size().as_vec2();
 
         self.scale = TARGET_TILE_HEIGHT / tile_size.y;
@@ -112,6 +129,10 @@ impl MapMenu {
                     return;
                 }
 
+                if journal_area.is_some_and(|area| !area.is_explored(tile_pos.into())) {
+                    return;
+                }
+
                 let frame = sprite.frame();
 
                 let mut center = frame.top_left();
@@ -179,7 +200,8 @@ impl MapMenu {
 
         // default position
         let default_position = (RESOLUTION_F - texture_size.as_vec2()) * 0.5 + origin;
-        self.map_sprite.set_position(default_position.floor());
+        self.default_position = default_position.floor();
+        self.map_sprite.set_position(self.default_position);
 
         // update tile markers
         self.update_tile_markers(map);
@@ -275,6 +297,41 @@ impl MapMenu {
         }
     }
 
+    fn draw_journal_page(
+        &mut self,
+        game_io: &GameIO,
+        sprite_queue: &mut SpriteColorQueue,
+        area: &OverworldArea,
+        index: usize,
+    ) {
+        const TITLE_POSITION: Vec2 = Vec2::new(8.0, 8.0);
+
+        // wait for the scene to load the page
+        if self.rendered_journal_page == Some(index) {
+            sprite_queue.draw_sprite(&self.map_sprite);
+        }
+
+        if let Some(journal_area) = area.journal.areas().get(index) {
+            let title = if journal_area.name.is_empty() {
+                &journal_area.map_path
+            } else {
+                &journal_area.name
+            };
+
+            let mut text_style = TextStyle::new(game_io, FontName::Thick);
+            text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+            text_style.bounds.set_position(TITLE_POSITION);
+            text_style.draw(game_io, sprite_queue, title);
+        }
+
+        // draw overlay
+        sprite_queue.draw_sprite(&self.overlay_sprite);
+
+        if self.scrollable {
+            sprite_queue.draw_sprite(&self.overlay_arrows_sprite);
+        }
+    }
+
     fn center_on_player(&mut self, area: &mut OverworldArea) {
         let entities = &mut area.entities;
         let Ok(&position) = entities.query_one_mut::<&Vec3>(area.player_data.entity) else {
@@ -299,30 +356,87 @@ impl Menu for MapMenu {
     fn open(&mut self, _game_io: &mut GameIO, _area: &mut OverworldArea) {
         self.open = true;
         self.scroll_offset = Vec2::ZERO;
+        self.journal_page = None;
     }
 
     fn update(&mut self, game_io: &mut GameIO, area: &mut OverworldArea) {
         self.background.update();
 
-        if self.last_map_update != area.last_map_update {
-            self.update_map_sprites(game_io, &area.map);
+        if let Some(index) = self.journal_page {
+            // the scene loads the map for the requested page
+            let journal_area = area.journal.areas().get(index);
+
+            if let (Some(journal_area), Some((view_index, map))) =
+                (journal_area, &area.journal_view)
+            {
+                if *view_index == index && self.rendered_journal_page != Some(index) {
+                    self.update_map_sprites(game_io, map, Some(journal_area));
+                    self.rendered_journal_page = Some(index);
+
+                    // redraw the current area when returning to it
+                    self.last_map_update = -1;
+                }
+            }
+        } else if self.last_map_update != area.last_map_update {
+            self.update_map_sprites(game_io, &area.map, None);
             self.last_map_update = area.last_map_update;
+            self.rendered_journal_page = None;
         }
 
         if self.scrollable {
-            self.center_on_player(area);
+            if self.journal_page.is_none() {
+                self.center_on_player(area);
+            } else {
+                self.map_sprite.set_position(self.default_position);
+            }
+
             self.map_sprite
                 .set_position(self.map_sprite.position() - self.scroll_offset);
         }
     }
 
-    fn handle_input(&mut self, game_io: &mut GameIO, _: &mut OverworldArea, _: &mut Textbox) {
+    fn handle_input(&mut self, game_io: &mut GameIO, area: &mut OverworldArea, _: &mut Textbox) {
         let input_util = InputUtil::new(game_io);
 
         if input_util.was_just_pressed(Input::Cancel) || input_util.was_just_pressed(Input::Map) {
             self.open = false;
         }
 
+        if input_util.was_just_pressed(Input::Confirm) {
+            self.open = false;
+            area.event_sender
+                .send(OverworldEvent::OpenFastTravel)
+                .unwrap();
+        }
+
+        // flip through journal pages, the first page is the current area
+        let page_change = if input_util.was_just_pressed(Input::ShoulderL) {
+            -1
+        } else if input_util.was_just_pressed(Input::ShoulderR) {
+            1
+        } else {
+            0
+        };
+
+        let journal_len = area.journal.areas().len();
+
+        if page_change != 0 && journal_len > 0 {
+            let total_pages = journal_len as isize + 1;
+            let current_page = self.journal_page.map_or(0, |index| index + 1) as isize;
+            let next_page = (current_page + page_change).rem_euclid(total_pages) as usize;
+
+            self.journal_page = next_page.checked_sub(1);
+            self.scroll_offset = Vec2::ZERO;
+
+            if let Some(index) = self.journal_page {
+                let event = OverworldEvent::ViewJournalArea(index);
+                area.event_sender.send(event).unwrap();
+            }
+
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+
         if !self.scrollable {
             return;
         }
@@ -373,6 +487,11 @@ impl Menu for MapMenu {
         // draw bg
         self.background.draw(game_io, render_pass);
 
+        if let Some(index) = self.journal_page {
+            self.draw_journal_page(game_io, sprite_queue, area, index);
+            return;
+        }
+
         // draw map
         sprite_queue.draw_sprite(&self.map_sprite);
 
@@ -392,11 +511,12 @@ impl Menu for MapMenu {
         }
 
         // draw objects
-        type ObjectQuery<'a> =
-            hecs::Without<(&'a Vec3, Option<&'a Tile>, &'a ObjectData), &'a Excluded>;
-        let object_entities = area.map.object_entities();
-
-        for (_, (&position, tile, object)) in object_entities.query::<ObjectQuery>().into_iter() {
+        area.map.inspect_objects(|object_view| {
+            let MapObjectView {
+                data: object,
+                tile,
+                position,
+            } = object_view;
             // resolve state
             let state = match object.object_type {
                 ObjectType::CustomWarp
@@ -408,7 +528,7 @@ impl Menu for MapMenu {
                 ObjectType::Shop => "SHOP",
                 ObjectType::Bookmark => "BOOKMARK",
                 _ => {
-                    continue;
+                    return;
                 }
             };
 
@@ -423,7 +543,7 @@ impl Menu for MapMenu {
 
             if let Some(tile) = tile {
                 let Some(tile_meta) = area.map.tile_meta_for_tile(tile.gid) else {
-                    continue;
+                    return;
                 };
 
                 // resolve scale
@@ -463,7 +583,7 @@ impl Menu for MapMenu {
 
             // draw
             sprite_queue.draw_sprite(&self.marker_sprite);
-        }
+        });
 
         // draw actors
         type ActorQuery<'a> = hecs::Without<(&'a Vec3, &'a PlayerMapMarker), &'a Excluded>;
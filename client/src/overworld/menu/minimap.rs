@@ -0,0 +1,247 @@
+use crate::overworld::components::{Excluded, MinimapMarker, PlayerMapMarker};
+use crate::overworld::{JournalArea, Map, OverworldArea, TileClass};
+use crate::render::SpriteColorQueue;
+use crate::resources::{Globals, ResourcePaths, RESOLUTION_F};
+use framework::prelude::*;
+use std::collections::HashMap;
+
+const MARGIN: f32 = 3.0;
+const SIZE: Vec2 = Vec2::new(72.0, 48.0);
+
+/// Height of a tile on the minimap in pixels
+const TILE_HEIGHT: f32 = 2.0;
+const MARKER_SIZE: Vec2 = Vec2::new(2.0, 2.0);
+
+const BACKGROUND_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.5);
+const LAYER_START_COLOR: Color = Color::new(0.0, 0.72, 0.97, 1.0);
+const LAYER_MAX_COLOR: Color = Color::new(0.03, 0.82, 0.97, 1.0);
+const STAIR_COLOR: Color = Color::new(0.54, 0.99, 1.0, 1.0);
+const WARP_COLOR: Color = Color::new(0.97, 0.86, 0.0, 1.0);
+const OBJECT_COLOR: Color = Color::WHITE;
+const ACTOR_COLOR: Color = Color::new(0.97, 0.38, 0.38, 1.0);
+
+/// A small view of the explored tiles around the player,
+/// warps are marked by default and servers can toggle markers for other actors and objects
+pub struct Minimap {
+    visible: bool,
+    pixel_sprite: Sprite,
+    /// Server overrides for object markers, cleared when leaving the area
+    object_markers: HashMap<u32, bool>,
+}
+
+impl Minimap {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        Self {
+            visible: true,
+            pixel_sprite: (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL),
+            object_markers: HashMap::new(),
+        }
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn set_object_marker(&mut self, object_id: u32, visible: bool) {
+        self.object_markers.insert(object_id, visible);
+    }
+
+    pub fn clear_object_markers(&mut self) {
+        self.object_markers.clear();
+    }
+
+    /// Skipped without a journal area, as private maps are never recorded
+    pub fn draw(
+        &mut self,
+        sprite_queue: &mut SpriteColorQueue,
+        area: &OverworldArea,
+        journal_area: Option<&JournalArea>,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        let Some(journal_area) = journal_area else {
+            return;
+        };
+
+        let map = &area.map;
+        let entities = &area.entities;
+
+        let Ok(player_position) = entities.get::<&Vec3>(area.player_data.entity) else {
+            return;
+        };
+
+        let player_position = *player_position;
+
+        let scale = TILE_HEIGHT / map.tile_size().y as f32;
+        let center = map.world_3d_to_screen(player_position);
+        let to_minimap =
+            |world_position: Vec3| (map.world_3d_to_screen(world_position) - center) * scale;
+
+        let sprite = &mut self.pixel_sprite;
+
+        // background
+        draw_rect(sprite, sprite_queue, minimap_bounds(), BACKGROUND_COLOR);
+
+        // tiles
+        draw_tiles(sprite, sprite_queue, map, journal_area, player_position);
+
+        // objects
+        map.inspect_objects(|object| {
+            let data = object.data;
+            let visible =
+                (self.object_markers.get(&data.id).cloned()).unwrap_or(data.object_type.is_warp());
+
+            if !visible {
+                return;
+            }
+
+            let color = if data.object_type.is_warp() {
+                WARP_COLOR
+            } else {
+                OBJECT_COLOR
+            };
+
+            let offset = to_minimap(object.position);
+            draw_point(sprite, sprite_queue, offset, MARKER_SIZE, color);
+        });
+
+        // actors
+        type ActorQuery<'a> =
+            hecs::Without<(&'a Vec3, &'a PlayerMapMarker, &'a MinimapMarker), &'a Excluded>;
+
+        for (entity, (&position, map_marker, _)) in entities.query::<ActorQuery>().into_iter() {
+            if entity == area.player_data.entity {
+                continue;
+            }
+
+            // bots default to a transparent map color
+            let color = if map_marker.color.a == 0.0 {
+                ACTOR_COLOR
+            } else {
+                map_marker.color
+            };
+
+            draw_point(
+                sprite,
+                sprite_queue,
+                to_minimap(position),
+                MARKER_SIZE,
+                color,
+            );
+        }
+
+        // the player is always marked at the center
+        let player_color = (entities.get::<&PlayerMapMarker>(area.player_data.entity))
+            .map(|marker| marker.color)
+            .unwrap_or(Color::WHITE);
+
+        draw_point(sprite, sprite_queue, Vec2::ZERO, MARKER_SIZE, player_color);
+    }
+}
+
+fn minimap_bounds() -> Rect {
+    let position = Vec2::new(MARGIN, RESOLUTION_F.y - SIZE.y - MARGIN);
+    Rect::new(position.x, position.y, SIZE.x, SIZE.y)
+}
+
+fn draw_rect(sprite: &mut Sprite, sprite_queue: &mut SpriteColorQueue, rect: Rect, color: Color) {
+    sprite.set_bounds(rect);
+    sprite.set_color(color);
+    sprite_queue.draw_sprite(sprite);
+}
+
+/// Draws a rect centered on an offset from the minimap's center, rects leaving the minimap are skipped
+fn draw_point(
+    sprite: &mut Sprite,
+    sprite_queue: &mut SpriteColorQueue,
+    offset: Vec2,
+    size: Vec2,
+    color: Color,
+) {
+    let bounds = minimap_bounds();
+    let top_left = (bounds.center() + offset - size * 0.5).floor();
+    let bottom_right = top_left + size;
+
+    if top_left.x < bounds.x
+        || top_left.y < bounds.y
+        || bottom_right.x > bounds.x + bounds.width
+        || bottom_right.y > bounds.y + bounds.height
+    {
+        return;
+    }
+
+    draw_rect(
+        sprite,
+        sprite_queue,
+        Rect::from_corners(top_left, bottom_right),
+        color,
+    );
+}
+
+fn draw_tiles(
+    sprite: &mut Sprite,
+    sprite_queue: &mut SpriteColorQueue,
+    map: &Map,
+    journal_area: &JournalArea,
+    player_position: Vec3,
+) {
+    let tile_size = map.tile_size().as_vec2();
+    let scale = TILE_HEIGHT / tile_size.y;
+    let center = map.world_3d_to_screen(player_position);
+
+    // slightly taller than the tile's step to avoid holes between rows
+    let tile_rect_size = Vec2::new(tile_size.x * 0.5, tile_size.y) * scale;
+
+    let layer_count = map.tile_layers().len();
+
+    // enough tiles to cover the minimap, padded for elevation
+    let step = tile_size * 0.5 * scale;
+    let radius = ((SIZE.x / step.x + SIZE.y / step.y) * 0.25).ceil() as i32 + layer_count as i32;
+
+    let player_tile = map.world_3d_to_tile_space(player_position);
+    let player_tile = player_tile.truncate().floor().as_ivec2();
+
+    for (i, layer) in map.tile_layers().iter().enumerate() {
+        if !layer.visible() {
+            continue;
+        }
+
+        let progress = i as f32 / layer_count as f32;
+        let layer_color = Color::lerp(LAYER_START_COLOR, LAYER_MAX_COLOR, progress);
+
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                let tile_position = player_tile + IVec2::new(x, y);
+                let tile = layer.tile_at(tile_position);
+
+                if tile.gid == 0 || !journal_area.is_explored(tile_position) {
+                    continue;
+                }
+
+                if i > 0 && map.ignore_tile_above(tile_position, (i - 1) as _) {
+                    continue;
+                }
+
+                let Some(tile_meta) = map.tile_meta_for_tile(tile.gid) else {
+                    continue;
+                };
+
+                let color = if tile_meta.tile_class == TileClass::Stairs {
+                    STAIR_COLOR
+                } else {
+                    layer_color
+                };
+
+                let tile_center = tile_position.as_vec2() + 0.5;
+                let world_position = map.tile_3d_to_world(tile_center.extend(i as f32));
+                let offset = (map.world_3d_to_screen(world_position) - center) * scale;
+
+                draw_point(sprite, sprite_queue, offset, tile_rect_size, color);
+            }
+        }
+    }
+}
@@ -1,15 +1,21 @@
 mod bbs;
+mod chat_box;
 mod emote_menu;
+mod emote_wheel;
 mod items_menu;
 mod map_menu;
+mod minimap;
 mod overworld_hud;
 mod overworld_menu_manager;
 mod shop;
 
 pub use bbs::*;
+pub use chat_box::*;
 pub use emote_menu::*;
+pub use emote_wheel::*;
 pub use items_menu::*;
 pub use map_menu::*;
+pub use minimap::*;
 pub use overworld_hud::*;
 pub use overworld_menu_manager::*;
 pub use shop::*;
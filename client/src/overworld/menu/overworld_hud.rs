@@ -1,13 +1,20 @@
-use crate::overworld::{Map, OverworldArea};
-use crate::render::ui::{draw_clock, FontName, PlayerHealthUi, Text};
-use crate::render::SpriteColorQueue;
+use super::{draw_chat_lines, Minimap};
+use crate::overworld::{JournalArea, Map, OverworldArea};
+use crate::render::ui::{draw_clock, FontName, PlayerHealthUi, Text, TextStyle};
+use crate::render::{FrameTime, SpriteColorQueue};
 use crate::resources::{RESOLUTION_F, TEXT_DARK_SHADOW_COLOR};
 use framework::prelude::*;
+use packets::structures::{PartyMember, Quest};
 
 pub struct OverworldHud {
     visible: bool,
     map_name_visible: bool,
+    chat_feed_visible: bool,
     health_ui: PlayerHealthUi,
+    tracked_quest: Option<Quest>,
+    /// Other members of our party
+    party_members: Vec<PartyMember>,
+    minimap: Minimap,
 }
 
 impl OverworldHud {
@@ -15,9 +22,13 @@ impl OverworldHud {
         Self {
             visible: true,
             map_name_visible: true,
+            chat_feed_visible: true,
             health_ui: PlayerHealthUi::new(game_io)
                 .with_max_health(health)
                 .with_health(health),
+            tracked_quest: None,
+            party_members: Vec::new(),
+            minimap: Minimap::new(game_io),
         }
     }
 
@@ -29,13 +40,35 @@ impl OverworldHud {
         self.map_name_visible = visible;
     }
 
+    pub fn set_chat_feed_visible(&mut self, visible: bool) {
+        self.chat_feed_visible = visible;
+    }
+
+    pub fn set_tracked_quest(&mut self, quest: Option<Quest>) {
+        self.tracked_quest = quest;
+    }
+
+    pub fn set_party_members(&mut self, members: Vec<PartyMember>) {
+        self.party_members = members;
+    }
+
+    pub fn minimap_mut(&mut self) -> &mut Minimap {
+        &mut self.minimap
+    }
+
     pub fn update(&mut self, area: &OverworldArea) {
         self.health_ui.set_health(area.player_data.health);
         self.health_ui.set_max_health(area.player_data.max_health());
         self.health_ui.update();
     }
 
-    pub fn draw(&self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, map: &Map) {
+    pub fn draw(
+        &mut self,
+        game_io: &GameIO,
+        sprite_queue: &mut SpriteColorQueue,
+        area: &OverworldArea,
+        journal_area: Option<&JournalArea>,
+    ) {
         if !self.visible {
             return;
         }
@@ -43,14 +76,109 @@ impl OverworldHud {
         self.health_ui.draw(game_io, sprite_queue);
         draw_clock(game_io, sprite_queue);
 
+        if let Some(quest) = &self.tracked_quest {
+            draw_quest_tracker(game_io, sprite_queue, quest);
+        }
+
+        if !self.party_members.is_empty() {
+            draw_party(game_io, sprite_queue, &self.party_members);
+        }
+
         if self.map_name_visible {
-            draw_map_name(game_io, sprite_queue, map);
+            draw_map_name(game_io, sprite_queue, &area.map);
+        }
+
+        self.minimap.draw(sprite_queue, area, journal_area);
+
+        if self.chat_feed_visible {
+            draw_chat_feed(game_io, sprite_queue, area);
         }
     }
 }
 
+/// Recent chat lines, drawn above the minimap
+fn draw_chat_feed(game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, area: &OverworldArea) {
+    const DURATION: FrameTime = 60 * 8;
+    let bounds = Rect::new(3.0, RESOLUTION_F.y - 102.0, 160.0, 48.0);
+
+    let lines = (area.chat_log.lines().rev())
+        .take_while(|line| area.world_time - line.received_time < DURATION);
+
+    draw_chat_lines(game_io, sprite_queue, bounds, lines);
+}
+
 const TEXT_SHADOW_COLOR: Color = Color::new(0.41, 0.41, 0.41, 1.0);
 
+fn draw_quest_tracker(game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, quest: &Quest) {
+    const MARGIN: f32 = 3.0;
+    const TOP: f32 = 18.0;
+
+    let mut label = Text::new(game_io, FontName::Thin);
+    label.style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+    let mut lines = vec![quest.name.clone()];
+
+    match quest.current_objective() {
+        Some(objective) => match objective.progress_text() {
+            Some(progress) => lines.push(format!("{} {progress}", objective.description)),
+            None => lines.push(objective.description.clone()),
+        },
+        None if quest.is_complete() => lines.push(String::from("Complete!")),
+        None => {}
+    }
+
+    // right align each line below the clock
+    let mut y = TOP;
+
+    for line in lines {
+        label.text = line;
+
+        let text_size = label.measure().size;
+        let position = Vec2::new(RESOLUTION_F.x - text_size.x - MARGIN, y);
+        label.style.bounds.set_position(position);
+        label.draw(game_io, sprite_queue);
+
+        y += label.line_height();
+    }
+}
+
+/// Lists each member's health and area below our health
+fn draw_party(game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, members: &[PartyMember]) {
+    const MARGIN: f32 = 3.0;
+    const TOP: f32 = 20.0;
+    const GAP: f32 = 2.0;
+    const LOW_HEALTH_COLOR: Color = Color::new(0.97, 0.35, 0.35, 1.0);
+
+    let mut text_style =
+        TextStyle::new(game_io, FontName::ThinSmall).with_shadow_color(TEXT_DARK_SHADOW_COLOR);
+    text_style.bounds.x = MARGIN;
+
+    let mut y = TOP;
+
+    for member in members {
+        let health_line = format!("{} {}/{}", member.name, member.health, member.max_health);
+
+        text_style.color = if member.health * 4 <= member.max_health {
+            LOW_HEALTH_COLOR
+        } else {
+            Color::WHITE
+        };
+
+        text_style.bounds.y = y;
+        text_style.draw(game_io, sprite_queue, &health_line);
+        y += text_style.measure(&health_line).size.y;
+
+        if !member.area_name.is_empty() {
+            text_style.color = Color::WHITE;
+            text_style.bounds.y = y;
+            text_style.draw(game_io, sprite_queue, &member.area_name);
+            y += text_style.measure(&member.area_name).size.y;
+        }
+
+        y += GAP;
+    }
+}
+
 fn draw_map_name(game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, map: &Map) {
     const MARGIN: Vec2 = Vec2::new(1.0, 3.0);
 
@@ -570,7 +570,7 @@ impl OverworldMenuManager {
         area: &OverworldArea,
     ) {
         // draw names
-        if !self.is_blocking_hud() {
+        if !self.is_blocking_hud() && area.name_tags_visible {
             area.draw_player_names(game_io, sprite_queue);
         }
 
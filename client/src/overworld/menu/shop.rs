@@ -74,7 +74,7 @@ impl Shop {
         );
 
         let cursor_start = animator.point("CURSOR").unwrap_or_default() - animator.origin();
-        scroll_tracker.define_cursor(cursor_start, 16.0);
+        scroll_tracker.define_text_cursor(game_io, cursor_start, 16.0);
 
         // money
         let money_sprite = list_sprite.clone();
@@ -328,7 +328,7 @@ impl Menu for Shop {
             text_style.bounds.set_position(price_position);
             text_style.draw(game_io, sprite_queue, &item.price_text);
 
-            offset.y += 16.0;
+            offset.y += self.scroll_tracker.cursor_multiplier();
         }
 
         // draw cursor
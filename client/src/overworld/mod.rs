@@ -1,9 +1,11 @@
 mod background_properties;
 mod camera_controller;
+mod chat_log;
 pub mod components;
 mod custom_properties;
 mod identity;
 mod map;
+mod map_journal;
 mod menu;
 mod objects;
 mod online;
@@ -21,9 +23,11 @@ mod tiles;
 
 pub use background_properties::*;
 pub use camera_controller::*;
+pub use chat_log::*;
 pub use custom_properties::*;
 pub use identity::*;
 pub use map::*;
+pub use map_journal::*;
 pub use menu::*;
 pub use objects::*;
 pub use online::*;
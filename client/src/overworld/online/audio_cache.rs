@@ -0,0 +1,90 @@
+use crate::resources::ResourcePaths;
+use packets::structures::FileHash;
+use std::fs;
+use std::time::SystemTime;
+
+/// Music and ambience shared between servers, stored by hash to avoid downloading the same file for every server
+pub struct AudioCache;
+
+impl AudioCache {
+    fn path(hash: FileHash) -> String {
+        format!("{}{hash}", ResourcePaths::AUDIO_CACHE_FOLDER)
+    }
+
+    fn stored_files() -> Vec<(FileHash, SystemTime, u64)> {
+        let path = ResourcePaths::AUDIO_CACHE_FOLDER;
+
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    log::error!("Failed to read audio cache folder \"{path}\": {err}");
+                }
+
+                return Vec::new();
+            }
+        };
+
+        entries
+            .flatten()
+            .flat_map(|entry| {
+                let metadata = entry
+                    .metadata()
+                    .ok()
+                    .filter(|metadata| metadata.is_file())?;
+                let hash = FileHash::from_hex(entry.file_name().to_str()?)?;
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+                Some((hash, modified, metadata.len()))
+            })
+            .collect()
+    }
+
+    /// Deletes the oldest files until the cache fits within the limit
+    pub fn enforce_limit(limit: u64) {
+        let mut files = Self::stored_files();
+        let mut total_size: u64 = files.iter().map(|(_, _, size)| size).sum();
+
+        if total_size <= limit {
+            return;
+        }
+
+        files.sort_by_key(|(_, modified, _)| *modified);
+
+        for (hash, _, size) in files {
+            if total_size <= limit {
+                break;
+            }
+
+            if let Err(err) = fs::remove_file(Self::path(hash)) {
+                log::error!("Failed to remove {hash} from the audio cache: {err}");
+                continue;
+            }
+
+            total_size -= size;
+        }
+    }
+
+    pub fn load(hash: FileHash) -> Option<Vec<u8>> {
+        let path = Self::path(hash);
+
+        match fs::read(&path) {
+            Ok(data) => Some(data),
+            Err(err) => {
+                log::error!("Failed to load {hash} from the audio cache: {err}");
+                None
+            }
+        }
+    }
+
+    pub fn store(hash: FileHash, data: &[u8]) {
+        if let Err(err) = fs::create_dir_all(ResourcePaths::AUDIO_CACHE_FOLDER) {
+            log::error!("Failed to create audio cache folder: {err}");
+            return;
+        }
+
+        if let Err(err) = fs::write(Self::path(hash), data) {
+            log::error!("Failed to store {hash} in the audio cache: {err}");
+        }
+    }
+}
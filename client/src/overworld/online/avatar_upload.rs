@@ -0,0 +1,151 @@
+use packets::{ClientAssetType, ClientPacket};
+
+/// Bytes sent per AssetStream packet
+const CHUNK_SIZE: usize = 4096;
+/// Chunks sent per frame, spreads large avatars across frames
+const CHUNKS_PER_FRAME: usize = 4;
+
+/// Streams avatar assets to the server, followed by the avatar change
+pub struct AvatarUpload {
+    assets: Vec<(ClientAssetType, Vec<u8>)>,
+    avatar_change: ClientPacket,
+    asset_index: usize,
+    offset: usize,
+    sent_bytes: usize,
+    total_bytes: usize,
+    complete: bool,
+}
+
+impl AvatarUpload {
+    pub fn new(assets: Vec<(ClientAssetType, Vec<u8>)>, avatar_change: ClientPacket) -> Self {
+        let total_bytes = assets.iter().map(|(_, data)| data.len()).sum();
+
+        Self {
+            assets,
+            avatar_change,
+            asset_index: 0,
+            offset: 0,
+            sent_bytes: 0,
+            total_bytes,
+            complete: false,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.total_bytes == 0 {
+            return 1.0;
+        }
+
+        self.sent_bytes as f32 / self.total_bytes as f32
+    }
+
+    /// Starts over, used when the server lost the partial upload
+    pub fn restart(&mut self) {
+        self.asset_index = 0;
+        self.offset = 0;
+        self.sent_bytes = 0;
+        self.complete = false;
+    }
+
+    pub fn next_packets(&mut self) -> Vec<ClientPacket> {
+        self.take_packets(CHUNKS_PER_FRAME)
+    }
+
+    pub fn remaining_packets(&mut self) -> Vec<ClientPacket> {
+        self.take_packets(usize::MAX)
+    }
+
+    fn take_packets(&mut self, chunk_limit: usize) -> Vec<ClientPacket> {
+        let mut packets = Vec::new();
+        let mut chunks = 0;
+
+        while !self.complete && chunks < chunk_limit {
+            let Some((asset_type, data)) = self.assets.get(self.asset_index) else {
+                packets.push(self.avatar_change.clone());
+                self.complete = true;
+                break;
+            };
+
+            let asset_type = *asset_type;
+
+            if self.offset == 0 {
+                packets.push(ClientPacket::AssetStreamStart {
+                    asset_type,
+                    size: data.len() as u64,
+                });
+            }
+
+            let end = (self.offset + CHUNK_SIZE).min(data.len());
+
+            if end > self.offset {
+                packets.push(ClientPacket::AssetStream {
+                    asset_type,
+                    data: data[self.offset..end].to_vec(),
+                });
+
+                self.sent_bytes += end - self.offset;
+                chunks += 1;
+            }
+
+            if end == data.len() {
+                self.asset_index += 1;
+                self.offset = 0;
+            } else {
+                self.offset = end;
+            }
+        }
+
+        packets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_upload() -> AvatarUpload {
+        AvatarUpload::new(
+            vec![
+                (ClientAssetType::Texture, vec![0; CHUNK_SIZE * 5 + 1]),
+                (ClientAssetType::Animation, Vec::new()),
+            ],
+            ClientPacket::Logout,
+        )
+    }
+
+    #[test]
+    fn spreads_chunks_across_frames() {
+        let mut upload = create_upload();
+
+        let packets = upload.next_packets();
+        assert_eq!(packets.len(), CHUNKS_PER_FRAME + 1);
+        assert!(matches!(
+            packets[0],
+            ClientPacket::AssetStreamStart { size, .. } if size == CHUNK_SIZE as u64 * 5 + 1
+        ));
+        assert!(upload.progress() < 1.0);
+
+        // remaining texture chunks, the empty animation, and the avatar change
+        let packets = upload.next_packets();
+        assert_eq!(packets.len(), 4);
+        assert_eq!(packets.last(), Some(&ClientPacket::Logout));
+        assert!(upload.is_complete());
+        assert_eq!(upload.progress(), 1.0);
+
+        assert!(upload.next_packets().is_empty());
+    }
+
+    #[test]
+    fn restart_resends_everything() {
+        let mut upload = create_upload();
+        let first_attempt = upload.remaining_packets();
+
+        upload.restart();
+        assert!(!upload.is_complete());
+        assert_eq!(upload.remaining_packets(), first_attempt);
+    }
+}
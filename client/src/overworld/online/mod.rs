@@ -1,3 +1,7 @@
+mod audio_cache;
+mod avatar_upload;
 mod server_asset_manager;
 
+pub use audio_cache::*;
+pub use avatar_upload::*;
 pub use server_asset_manager::*;
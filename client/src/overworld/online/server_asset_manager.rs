@@ -1,8 +1,9 @@
+use super::AudioCache;
 use crate::render::ui::GlyphAtlas;
 use crate::resources::*;
 use framework::prelude::*;
 use packets::address_parsing::{uri_decode, uri_encode};
-use packets::structures::{AssetDataType, TextureAnimPathPair};
+use packets::structures::{AssetDataType, FileHash, TextureAnimPathPair};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Write;
@@ -75,10 +76,14 @@ impl ServerAssetManager {
         // find stored assets
         let assets = Self::find_stored_assets(&path_prefix);
 
+        // trim the shared audio cache before the server offers tracks from it
+        let globals = game_io.resource::<Globals>().unwrap();
+        AudioCache::enforce_limit(globals.config.audio_cache_limit_bytes());
+
         // setup texture map
         let mut textures = HashMap::new();
 
-        let local_assets = &game_io.resource::<Globals>().unwrap().assets;
+        let local_assets = &globals.assets;
         textures.insert(
             ResourcePaths::BLANK.to_string(),
             local_assets.texture(game_io, ResourcePaths::BLANK),
@@ -232,12 +237,19 @@ impl ServerAssetManager {
         }
 
         let remote_path = download.remote_path;
+        let mut save_to_disk = download.save_to_disk;
+
+        if download.data_type == AssetDataType::Audio && save_to_disk {
+            // audio is stored in the shared cache instead of the server's folder
+            AudioCache::store(FileHash::hash(&data), &data);
+            save_to_disk = false;
+        }
 
         self.store_asset(
             remote_path.clone(),
             download.last_modified,
             data,
-            download.save_to_disk,
+            save_to_disk,
         );
 
         match download.data_type {
@@ -255,6 +267,27 @@ impl ServerAssetManager {
         }
     }
 
+    /// Used when the server offers audio by hash, returns false if the audio needs to be requested
+    pub fn load_cached_audio(
+        &self,
+        game_io: &GameIO,
+        remote_path: String,
+        last_modified: u64,
+        hash: FileHash,
+    ) -> bool {
+        let Some(data) = AudioCache::load(hash) else {
+            return false;
+        };
+
+        self.store_asset(remote_path.clone(), last_modified, data, false);
+
+        // cache as audio
+        self.sounds.borrow_mut().remove(&remote_path);
+        self.audio(game_io, &remote_path);
+
+        true
+    }
+
     pub fn stored_assets(&self) -> Vec<StoredServerAsset> {
         self.stored_assets
             .borrow()
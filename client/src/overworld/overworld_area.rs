@@ -3,6 +3,7 @@ use crate::overworld::*;
 use crate::render::ui::{FontName, TextStyle};
 use crate::render::*;
 use crate::resources::*;
+use crate::saves::ChatFilterLevel;
 use framework::prelude::*;
 use packets::structures::ItemDefinition;
 use std::collections::HashMap;
@@ -16,12 +17,18 @@ pub struct OverworldArea {
     pub entities: hecs::World,
     pub map: Map,
     pub last_map_update: FrameTime,
+    pub journal: MapJournal,
+    /// A journal area loaded for the map menu, paired with its index in the journal
+    pub journal_view: Option<(usize, Map)>,
     pub emote_sprite: Sprite,
     pub emote_animator: Animator,
+    pub chat_log: ChatLog,
     pub event_sender: flume::Sender<OverworldEvent>,
     pub event_receiver: flume::Receiver<OverworldEvent>,
     pub world_time: FrameTime,
     pub visible: bool,
+    pub name_tags_visible: bool,
+    pub chat_filter: ChatFilterLevel,
     input_locks: usize,
     background: Background,
     foreground: Background,
@@ -70,13 +77,18 @@ impl OverworldArea {
             entities,
             map: Map::new(0, 0, 0, 0),
             last_map_update: 0,
+            journal: MapJournal::default(),
+            journal_view: None,
             emote_sprite,
             emote_animator,
+            chat_log: ChatLog::default(),
             event_sender,
             event_receiver,
             world_time: 0,
             input_locks: 0,
             visible: false,
+            name_tags_visible: true,
+            chat_filter: ChatFilterLevel::default(),
             background: Background::new_blank(game_io),
             foreground: Background::new_blank(game_io),
             camera_controller: CameraController::new(player_entity),
@@ -140,7 +152,9 @@ impl OverworldArea {
         }
     }
 
-    pub fn set_map(&mut self, game_io: &GameIO, assets: &impl AssetManager, map: Map) {
+    pub fn set_map(&mut self, game_io: &GameIO, assets: &impl AssetManager, mut map: Map) {
+        map.take_tile_animations(&mut self.map);
+
         if self.map.background_properties() != map.background_properties() {
             self.background = map
                 .background_properties()
@@ -1,4 +1,5 @@
 use super::OverworldArea;
+use crate::packages::PackageId;
 use crate::render::ui::PackageListing;
 use framework::common::GameIO;
 use framework::prelude::{NextScene, Vec3};
@@ -10,6 +11,13 @@ pub enum OverworldEvent {
     },
     Callback(Box<dyn FnOnce(&mut GameIO, &mut OverworldArea) + Send + Sync>),
     EmoteSelected(String),
+    /// Text entered in the chat box, may start with a command
+    ChatSubmitted(String),
+    /// An emote from an installed emote package
+    PackageEmoteSelected {
+        package_id: PackageId,
+        emote_id: String,
+    },
     ItemUse(String),
     TextboxResponse(u8),
     PromptResponse(String),
@@ -31,6 +39,14 @@ pub enum OverworldEvent {
         message: String,
     },
     PackageReferred(PackageListing),
+    /// Requests a journal area to be loaded into `OverworldArea::journal_view`
+    ViewJournalArea(usize),
+    OpenFastTravel,
+    /// Asks the server to move the player to a visited warp
+    FastTravel {
+        map_path: String,
+        object_id: u32,
+    },
     NextScene(NextScene),
     Leave,
 }
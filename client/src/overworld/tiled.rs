@@ -3,7 +3,7 @@ use super::*;
 use crate::parse_util::parse_or_default;
 use crate::render::*;
 use crate::resources::*;
-use framework::prelude::{GameIO, Rect, Vec2};
+use framework::prelude::{Duration, GameIO, Rect, Vec2};
 use std::rc::Rc;
 
 pub fn load_map<A: AssetManager>(game_io: &GameIO, assets: &A, data: &str) -> Option<Map> {
@@ -91,9 +91,16 @@ pub fn load_map<A: AssetManager>(game_io: &GameIO, assets: &A, data: &str) -> Op
                 "name" => {
                     map.set_name(property_value.to_string());
                 }
+                "private" => {
+                    map.set_private(property_value == "true");
+                }
                 "music" | "song" => {
                     map.set_music_path(property_value.to_string());
                 }
+                "music fade" | "song fade" => {
+                    let secs: f32 = property_value.parse().unwrap_or_default();
+                    map.set_music_fade(Duration::from_secs_f32(secs.max(0.0)));
+                }
                 _ => {
                     // println!("{}", property_name.to_lowercase());
                 }
@@ -5,8 +5,12 @@ use crate::render::SpriteColorQueue;
 use crate::resources::{AssetManager, Globals, ResourcePaths};
 use framework::prelude::{GameIO, Sprite, Texture, UVec2, Vec2};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+pub const CARD_ICON_SIZE: UVec2 = UVec2::new(14, 14);
+pub const CARD_PREVIEW_SIZE: UVec2 = UVec2::new(56, 48);
+
 #[derive(Deserialize, Default)]
 #[serde(default)]
 struct CardMeta {
@@ -35,6 +39,7 @@ struct CardMeta {
     prevent_time_freeze_counter: bool,
     conceal: bool,
     tags: Vec<String>,
+    translations: HashMap<String, PackageTranslation>,
 }
 
 #[derive(Default, Clone)]
@@ -49,6 +54,7 @@ pub struct CardPackage {
     pub regular_allowed: bool,
     pub hidden: bool,
     pub limit: usize,
+    pub translations: PackageTranslations,
 }
 
 impl Package for CardPackage {
@@ -102,15 +108,15 @@ impl Package for CardPackage {
 
         package.icon_texture_path = base_path.clone() + &meta.icon_texture_path;
         package.preview_texture_path = base_path.clone() + &meta.preview_texture_path;
-        package.description = meta.description;
-        package.long_description = meta.long_description;
+        package.description = meta.description.clone();
+        package.long_description = meta.long_description.clone();
         package.default_codes = meta.codes;
         package.hidden = meta.hidden;
         package.limit = meta.limit.unwrap_or(5);
 
         // card properties
         package.card_properties.package_id = package.package_info.id.clone();
-        package.card_properties.short_name = meta.name.into();
+        package.card_properties.short_name = meta.name.clone().into();
         package.card_properties.damage = meta.damage;
         package.card_properties.recover = meta.recover;
         package.card_properties.element = meta.element.into();
@@ -135,6 +141,13 @@ impl Package for CardPackage {
         package.card_properties.conceal = meta.conceal;
         package.card_properties.tags = meta.tags;
 
+        let default_translation = PackageTranslation {
+            name: Some(meta.name),
+            description: Some(meta.description),
+            long_description: Some(meta.long_description),
+        };
+        package.translations = PackageTranslations::new(default_translation, meta.translations);
+
         // default for regular_allowed is based on card class
         let card_class = package.card_properties.card_class;
         package.regular_allowed = meta
@@ -143,6 +156,14 @@ impl Package for CardPackage {
 
         package
     }
+
+    fn set_language(&mut self, language: &str) {
+        let translations = &self.translations;
+
+        self.card_properties.short_name = translations.name(language).to_string().into();
+        self.description = translations.description(language).to_string();
+        self.long_description = translations.long_description(language).to_string();
+    }
 }
 
 impl CardPackage {
@@ -155,12 +176,15 @@ impl CardPackage {
         position: Vec2,
     ) {
         let (icon_texture, _) = Self::icon_texture(game_io, namespace, package_id);
+        let scale = art_scale(icon_texture.size(), CARD_ICON_SIZE);
 
         let mut sprite = Sprite::new(game_io, icon_texture);
         sprite.set_position(position);
+        sprite.set_scale(scale);
         sprite_queue.draw_sprite(&sprite);
     }
 
+    /// Falls back to the preview art when the icon is missing, see `art_scale()` for fitting it
     pub fn icon_texture<'a>(
         game_io: &'a GameIO,
         namespace: PackageNamespace,
@@ -171,10 +195,14 @@ impl CardPackage {
         let package_manager = &globals.card_packages;
 
         if let Some(package) = package_manager.package_or_fallback(namespace, package_id) {
-            let icon_texture = assets.texture(game_io, &package.icon_texture_path);
+            let paths = [&package.icon_texture_path, &package.preview_texture_path];
 
-            if icon_texture.size() == UVec2::new(14, 14) {
-                return (icon_texture, &package.icon_texture_path);
+            for path in paths {
+                let texture = assets.texture(game_io, path);
+
+                if texture.size() != UVec2::ONE {
+                    return (texture, path);
+                }
             }
         };
 
@@ -182,6 +210,7 @@ impl CardPackage {
         (assets.texture(game_io, path), path)
     }
 
+    /// Falls back to the icon when the preview art is missing, see `art_scale()` for fitting it
     pub fn preview_texture<'a>(
         game_io: &'a GameIO,
         package_id: &PackageId,
@@ -192,15 +221,58 @@ impl CardPackage {
         let ns = PackageNamespace::Local;
 
         if let Some(package) = package_manager.package_or_fallback(ns, package_id) {
-            let path = package.preview_texture_path.as_str();
-            let texture = assets.texture(game_io, path);
+            let paths = [&package.preview_texture_path, &package.icon_texture_path];
+
+            for path in paths {
+                let texture = assets.texture(game_io, path);
 
-            if texture.size() != UVec2::ONE {
-                return (texture, path);
+                if texture.size() != UVec2::ONE {
+                    return (texture, path);
+                }
             }
         }
 
-        let path = ResourcePaths::CARD_ICON_MISSING;
+        let path = ResourcePaths::CARD_PREVIEW_MISSING;
         (assets.texture(game_io, path), path)
     }
 }
+
+/// Scale for fitting card art into `target_size`, large art shrinks to fit and small art grows by whole steps
+pub fn art_scale(texture_size: UVec2, target_size: UVec2) -> Vec2 {
+    if texture_size == target_size || texture_size.cmpeq(UVec2::ZERO).any() {
+        return Vec2::ONE;
+    }
+
+    let ratios = target_size.as_vec2() / texture_size.as_vec2();
+    let scale = ratios.min_element();
+
+    if scale < 1.0 {
+        Vec2::splat(scale)
+    } else {
+        Vec2::splat(scale.floor())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fits_art_to_target() {
+        assert_eq!(art_scale(CARD_ICON_SIZE, CARD_ICON_SIZE), Vec2::ONE);
+
+        // preview art used as an icon
+        assert_eq!(
+            art_scale(CARD_PREVIEW_SIZE, CARD_ICON_SIZE),
+            Vec2::splat(0.25)
+        );
+
+        // icon used as preview art
+        assert_eq!(
+            art_scale(CARD_ICON_SIZE, CARD_PREVIEW_SIZE),
+            Vec2::splat(3.0)
+        );
+
+        assert_eq!(art_scale(UVec2::ZERO, CARD_ICON_SIZE), Vec2::ONE);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::render::ui::{PackageListing, PackagePreviewData};
+
+use super::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct EmoteMeta {
+    category: String,
+    name: String,
+    description: String,
+    texture_path: String,
+    animation_path: String,
+}
+
+/// Emotes usable on any server, each animation state is an emote
+#[derive(Default, Clone)]
+pub struct EmotePackage {
+    pub package_info: PackageInfo,
+    pub name: String,
+    description: String,
+    pub texture_path: String,
+    pub animation_path: String,
+}
+
+impl Package for EmotePackage {
+    fn package_info(&self) -> &PackageInfo {
+        &self.package_info
+    }
+
+    fn create_package_listing(&self) -> PackageListing {
+        PackageListing {
+            id: self.package_info.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            creator: String::new(),
+            hash: self.package_info.hash,
+            preview_data: PackagePreviewData::Emote,
+            dependencies: self.package_info.requirements.clone(),
+        }
+    }
+
+    fn load_new(package_info: PackageInfo, package_table: toml::Table) -> Self {
+        let mut package = Self {
+            package_info,
+            ..Default::default()
+        };
+
+        let meta: EmoteMeta = match package_table.try_into() {
+            Ok(toml) => toml,
+            Err(e) => {
+                log::error!("Failed to parse {:?}:\n{e}", package.package_info.toml_path);
+                return package;
+            }
+        };
+
+        if meta.category != "emote" {
+            log::error!(
+                "Missing `category = \"emote\"` in {:?}",
+                package.package_info.toml_path
+            );
+        }
+
+        let base_path = &package.package_info.base_path;
+
+        package.name = meta.name;
+        package.description = meta.description;
+        package.texture_path = base_path.clone() + &meta.texture_path;
+        package.animation_path = base_path.clone() + &meta.animation_path;
+
+        package
+    }
+}
@@ -1,5 +1,7 @@
 use super::*;
+use crate::battle::EncounterMedalCriteria;
 use crate::render::ui::{PackageListing, PackagePreviewData};
+use crate::render::FrameTime;
 use serde::Deserialize;
 
 #[derive(Deserialize, Default)]
@@ -11,6 +13,14 @@ struct EncounterMeta {
     preview_texture_path: String,
     recording_path: Option<String>,
     recording_overrides: Vec<PackageId>,
+    medals: MedalMeta,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct MedalMeta {
+    par_time: Option<f32>, // seconds
+    no_damage: bool,
 }
 
 #[derive(Default, Clone)]
@@ -21,6 +31,7 @@ pub struct EncounterPackage {
     pub preview_texture_path: String,
     pub recording_path: Option<String>,
     pub recording_overrides: Vec<PackageId>,
+    pub medals: EncounterMedalCriteria,
 }
 
 impl Package for EncounterPackage {
@@ -68,6 +79,10 @@ impl Package for EncounterPackage {
         package.preview_texture_path = base_path.clone() + &meta.preview_texture_path;
         package.recording_path = meta.recording_path.map(|path| base_path.clone() + &path);
         package.recording_overrides = meta.recording_overrides;
+        package.medals = EncounterMedalCriteria {
+            par_time: (meta.medals.par_time).map(|secs| (secs * 60.0).round() as FrameTime),
+            no_damage: meta.medals.no_damage,
+        };
 
         package
     }
@@ -1,6 +1,8 @@
+use crate::bindable::{Element, ElementDefinition};
 use crate::render::ui::{PackageListing, PackagePreviewData};
 
 use super::*;
+use packets::structures::AchievementDefinition;
 use serde::Deserialize;
 
 #[derive(Deserialize, Default)]
@@ -9,13 +11,37 @@ struct LibraryMeta {
     category: String,
     name: String,
     description: String,
+    elements: Vec<ElementMeta>,
+    achievements: Vec<AchievementMeta>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ElementMeta {
+    name: String,
+    icon_texture_path: String,
+    weaknesses: Vec<String>,
+    resistances: Vec<String>,
+    strengths: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AchievementMeta {
+    id: String,
+    name: String,
+    description: String,
+    icon_texture_path: String,
 }
 
 #[derive(Default, Clone)]
 pub struct LibraryPackage {
     pub package_info: PackageInfo,
-    name: String,
+    pub name: String,
     description: String,
+    pub elements: Vec<ElementDefinition>,
+    /// Unlocked by scripts through `Resources.unlock_achievement()`
+    pub achievements: Vec<AchievementDefinition>,
 }
 
 impl Package for LibraryPackage {
@@ -40,6 +66,8 @@ impl Package for LibraryPackage {
             package_info,
             name: String::new(),
             description: String::new(),
+            elements: Vec::new(),
+            achievements: Vec::new(),
         };
 
         let meta: LibraryMeta = match package_table.try_into() {
@@ -60,6 +88,57 @@ impl Package for LibraryPackage {
         package.name = meta.name;
         package.description = meta.description;
 
+        let base_path = &package.package_info.base_path;
+
+        for element_meta in meta.elements {
+            let element = Element::from(element_meta.name.as_str());
+
+            if !matches!(element, Element::Custom(_)) {
+                log::error!(
+                    "Element name {:?} is reserved by a built in element, in {:?}",
+                    element_meta.name,
+                    package.package_info.toml_path
+                );
+                continue;
+            }
+
+            let parse_elements =
+                |names: Vec<String>| names.into_iter().map(Element::from).collect();
+
+            package.elements.push(ElementDefinition {
+                element,
+                name: element_meta.name,
+                icon_texture_path: base_path.clone() + &element_meta.icon_texture_path,
+                weaknesses: parse_elements(element_meta.weaknesses),
+                resistances: parse_elements(element_meta.resistances),
+                strengths: parse_elements(element_meta.strengths),
+            });
+        }
+
+        for achievement_meta in meta.achievements {
+            if achievement_meta.id.is_empty() {
+                log::error!(
+                    "Achievement {:?} is missing an id, in {:?}",
+                    achievement_meta.name,
+                    package.package_info.toml_path
+                );
+                continue;
+            }
+
+            let icon_texture_path = if achievement_meta.icon_texture_path.is_empty() {
+                String::new()
+            } else {
+                base_path.clone() + &achievement_meta.icon_texture_path
+            };
+
+            package.achievements.push(AchievementDefinition {
+                id: achievement_meta.id,
+                name: achievement_meta.name,
+                description: achievement_meta.description,
+                icon_texture_path,
+            });
+        }
+
         package
     }
 }
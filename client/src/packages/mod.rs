@@ -1,12 +1,15 @@
 mod augment_package;
 mod card_package;
 mod character_package;
+mod emote_package;
 mod encounter_package;
 mod library_package;
 mod package;
 mod package_info;
 mod package_manager;
 mod package_namespace;
+mod package_template;
+mod package_translations;
 mod player_package;
 mod repo_package_updater;
 mod resource_package;
@@ -17,12 +20,15 @@ mod util;
 pub use augment_package::*;
 pub use card_package::*;
 pub use character_package::*;
+pub use emote_package::*;
 pub use encounter_package::*;
 pub use library_package::*;
 pub use package::*;
 pub use package_info::*;
 pub use package_manager::*;
 pub use package_namespace::*;
+pub use package_template::*;
+pub use package_translations::*;
 pub use player_package::*;
 pub use repo_package_updater::*;
 pub use resource_package::*;
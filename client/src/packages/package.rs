@@ -7,4 +7,7 @@ pub trait Package {
     fn create_package_listing(&self) -> PackageListing;
 
     fn load_new(package_info: PackageInfo, package_table: toml::Table) -> Self;
+
+    /// Swaps displayed text for the language's translation, an empty language selects the default
+    fn set_language(&mut self, _language: &str) {}
 }
@@ -47,7 +47,10 @@ impl PackageInfo {
     // returns the [package] table and processes properties shared among every package type
     pub(crate) fn parse_toml(&mut self, assets: &LocalAssetManager) -> Option<toml::Table> {
         let toml_text = assets.text(&self.toml_path);
+        self.parse_toml_text(&toml_text)
+    }
 
+    pub(crate) fn parse_toml_text(&mut self, toml_text: &str) -> Option<toml::Table> {
         if toml_text.is_empty() {
             // assume no file / not a mod
             // attempting to access the file will already provide a warning
@@ -1,5 +1,5 @@
 use super::{ChildPackageInfo, Package, PackageId, PackageInfo, PackageNamespace};
-use crate::resources::{LocalAssetManager, ResourcePaths};
+use crate::resources::{parallel_map, LocalAssetManager, ResourcePaths};
 use packets::structures::FileHash;
 use std::collections::HashMap;
 
@@ -9,6 +9,7 @@ pub struct PackageManager<T> {
     package_category: PackageCategory,
     package_maps: HashMap<PackageNamespace, HashMap<PackageId, T>>,
     package_ids: Vec<PackageId>,
+    language: String,
 }
 
 impl<T: Package> PackageManager<T> {
@@ -17,6 +18,20 @@ impl<T: Package> PackageManager<T> {
             package_category,
             package_maps: HashMap::new(),
             package_ids: Vec::new(),
+            language: String::new(),
+        }
+    }
+
+    /// Applied to loaded packages and any packages loaded later
+    pub fn set_language(&mut self, language: &str) {
+        self.language = language.to_string();
+
+        for package in self
+            .package_maps
+            .values_mut()
+            .flat_map(|map| map.values_mut())
+        {
+            package.set_language(language);
         }
     }
 
@@ -73,7 +88,6 @@ impl<T: Package> PackageManager<T> {
 
     pub fn load_packages_in_folder<F>(
         &mut self,
-        assets: &LocalAssetManager,
         namespace: PackageNamespace,
         path: &str,
         mut callback: F,
@@ -90,11 +104,17 @@ impl<T: Package> PackageManager<T> {
             .filter_map(|entry| entry.path().to_str().map(|path| path.to_string()))
             .collect();
 
-        let total = paths.len();
+        // zipping and hashing is the slow part, handled across threads
+        let category = self.package_category;
+
+        let read_packages = parallel_map(
+            paths,
+            |base_path| Self::read_package(category, namespace, &base_path),
+            &mut callback,
+        );
 
-        for (i, base_path) in paths.into_iter().enumerate() {
-            self.load_package(assets, namespace, &base_path);
-            callback(i, total);
+        for (package_info, package_table) in read_packages.into_iter().flatten() {
+            self.internal_load_package(package_info, package_table);
         }
     }
 
@@ -113,7 +133,7 @@ impl<T: Package> PackageManager<T> {
         child_package_info: &ChildPackageInfo,
     ) -> bool {
         let Some(mut package_info) =
-            self.generate_package_info(namespace, &child_package_info.path)
+            Self::generate_package_info(self.package_category, namespace, &child_package_info.path)
         else {
             return false;
         };
@@ -139,13 +159,31 @@ impl<T: Package> PackageManager<T> {
     ) -> Option<&PackageInfo> {
         let path_string = ResourcePaths::clean(path_str);
 
-        let mut package_info = self.generate_package_info(namespace, &path_string)?;
+        let mut package_info =
+            Self::generate_package_info(self.package_category, namespace, &path_string)?;
         package_info.hash = Self::zip_and_hash(&package_info)?;
 
         let package_table = package_info.parse_toml(assets)?;
         self.internal_load_package(package_info, package_table)
     }
 
+    /// Thread safe alternative to load_package, reads the package.toml without the asset cache
+    fn read_package(
+        category: PackageCategory,
+        namespace: PackageNamespace,
+        path_str: &str,
+    ) -> Option<(PackageInfo, toml::Table)> {
+        let path_string = ResourcePaths::clean(path_str);
+
+        let mut package_info = Self::generate_package_info(category, namespace, &path_string)?;
+        package_info.hash = Self::zip_and_hash(&package_info)?;
+
+        let toml_text = LocalAssetManager::read_text(&package_info.toml_path);
+        let package_table = package_info.parse_toml_text(&toml_text)?;
+
+        Some((package_info, package_table))
+    }
+
     pub fn load_virtual_package(
         &mut self,
         assets: &LocalAssetManager,
@@ -157,7 +195,11 @@ impl<T: Package> PackageManager<T> {
             return None;
         };
 
-        let mut package_info = self.generate_package_info(namespace, &zip_meta.virtual_prefix)?;
+        let mut package_info = Self::generate_package_info(
+            self.package_category,
+            namespace,
+            &zip_meta.virtual_prefix,
+        )?;
         package_info.hash = hash;
 
         let package_table = package_info.parse_toml(assets)?;
@@ -170,7 +212,7 @@ impl<T: Package> PackageManager<T> {
     }
 
     fn generate_package_info(
-        &self,
+        category: PackageCategory,
         namespace: PackageNamespace,
         path: &str,
     ) -> Option<PackageInfo> {
@@ -185,7 +227,7 @@ impl<T: Package> PackageManager<T> {
         Some(PackageInfo {
             id: PackageId::new_blank(),
             hash: FileHash::ZERO,
-            category: self.package_category,
+            category,
             namespace,
             base_path: base_path.clone(),
             script_path,
@@ -273,7 +315,8 @@ impl<T: Package> PackageManager<T> {
         package_info: PackageInfo,
         package_table: toml::Table,
     ) -> Option<&PackageInfo> {
-        let package = T::load_new(package_info, package_table);
+        let mut package = T::load_new(package_info, package_table);
+        package.set_language(&self.language);
 
         let package_info = package.package_info();
         let package_id = package_info.id.clone();
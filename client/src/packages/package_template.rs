@@ -0,0 +1,213 @@
+// Scaffolding for new packages, used by `--new-package`
+
+use crate::resources::encode_png;
+use packets::structures::PackageCategory;
+use std::path::Path;
+
+const PLACEHOLDER_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+const CHARACTER_WIDTH: u32 = 32;
+const CHARACTER_HEIGHT: u32 = 48;
+
+/// Creates a package with placeholder assets in the category's mod folder,
+/// returns the path to the new package
+pub fn create_package_template(category: PackageCategory, name: &str) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\', '.']) {
+        return Err(format!("{name:?} isn't a valid package name"));
+    }
+
+    let folder = format!("{}{name}/", category.mod_path());
+
+    if Path::new(&folder).exists() {
+        return Err(format!("{folder:?} already exists"));
+    }
+
+    let id = format!("dev.local.{name}");
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+    match category {
+        PackageCategory::Card => {
+            files.push(("package.toml".into(), card_toml(&id, name).into()));
+            files.push(("entry.lua".into(), CARD_SCRIPT.into()));
+            files.push(("icon.png".into(), placeholder_png(14, 14)));
+            files.push(("preview.png".into(), placeholder_png(56, 48)));
+        }
+        PackageCategory::Character => {
+            files.extend(character_files(&id, name));
+        }
+        PackageCategory::Encounter => {
+            let character_id = format!("{id}.Enemy");
+
+            files.push(("package.toml".into(), encounter_toml(&id, name).into()));
+            files.push(("entry.lua".into(), encounter_script(&character_id).into()));
+            files.push(("preview.png".into(), placeholder_png(50, 50)));
+
+            // bundling a character, giving the encounter something to spawn
+            for (path, bytes) in character_files(&character_id, name) {
+                files.push((format!("enemy/{path}"), bytes));
+            }
+        }
+        _ => {
+            return Err(String::from(
+                "Templates are only available for cards, characters, and encounters",
+            ));
+        }
+    }
+
+    for (path, bytes) in files {
+        let path = folder.clone() + &path;
+
+        if let Some(parent) = Path::new(&path).parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+        }
+
+        std::fs::write(&path, bytes).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+    }
+
+    Ok(folder)
+}
+
+fn card_toml(id: &str, name: &str) -> String {
+    format!(
+        r#"[package]
+category = "card"
+id = "{id}"
+name = "{name}"
+description = "Hits the tile in front"
+long_description = "A starting point for a new card, hits the tile in front of the user"
+codes = ["*"]
+damage = 10
+element = "None"
+card_class = "Standard"
+hit_flags = ["Impact", "Flinch"]
+icon_texture_path = "icon.png"
+preview_texture_path = "preview.png"
+"#
+    )
+}
+
+const CARD_SCRIPT: &str = r#"function card_init(user, props)
+  local action = Action.new(user, "CHARACTER_SHOOT")
+
+  action.on_execute_func = function(self, user)
+    local tile = user:get_tile(user:facing(), 1)
+
+    if not tile then
+      return
+    end
+
+    local spell = Spell.new(user:team())
+    spell:set_facing(user:facing())
+    spell:set_hit_props(HitProps.from_card(props, user:context()))
+
+    spell.on_update_func = function(self)
+      self:current_tile():attack_entities(self)
+      self:erase()
+    end
+
+    user:field():spawn(spell, tile)
+  end
+
+  return action
+end
+"#;
+
+fn character_files(id: &str, name: &str) -> Vec<(String, Vec<u8>)> {
+    let toml = format!(
+        r#"[package]
+category = "character"
+id = "{id}"
+name = "{name}"
+"#
+    );
+
+    let animation = format!(
+        r#"imagePath="character.png"
+
+animation state="IDLE"
+frame duration="1" x="0" y="0" w="{CHARACTER_WIDTH}" h="{CHARACTER_HEIGHT}" originx="{}" originy="{CHARACTER_HEIGHT}"
+"#,
+        CHARACTER_WIDTH / 2
+    );
+
+    let script = format!(
+        r#"function character_init(character)
+  character:set_name("{name}")
+  character:set_health(100)
+  character:set_height({CHARACTER_HEIGHT})
+
+  character:set_texture(Resources.load_texture("character.png"))
+
+  local animation = character:animation()
+  animation:load("character.animation")
+  animation:set_state("IDLE")
+  animation:set_playback(Playback.Loop)
+end
+"#
+    );
+
+    vec![
+        ("package.toml".into(), toml.into()),
+        ("entry.lua".into(), script.into()),
+        ("character.animation".into(), animation.into()),
+        (
+            "character.png".into(),
+            placeholder_png(CHARACTER_WIDTH, CHARACTER_HEIGHT),
+        ),
+    ]
+}
+
+fn encounter_toml(id: &str, name: &str) -> String {
+    format!(
+        r#"[package]
+category = "encounter"
+id = "{id}"
+name = "{name}"
+description = "A starting point for a new encounter"
+preview_texture_path = "preview.png"
+
+[[defines.characters]]
+id = "{id}.Enemy"
+path = "enemy"
+"#
+    )
+}
+
+fn encounter_script(character_id: &str) -> String {
+    format!(
+        r#"local character_id = "{character_id}"
+
+function encounter_init(encounter)
+  encounter
+      :create_spawner(character_id, Rank.V1)
+      :spawn_at(5, 2)
+end
+"#
+    )
+}
+
+/// Solid color RGBA image
+fn placeholder_png(width: u32, height: u32) -> Vec<u8> {
+    let pixel_data = PLACEHOLDER_COLOR.repeat((width * height) as usize);
+
+    encode_png(width, height, &pixel_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn png_chunks() {
+        let bytes = placeholder_png(2, 3);
+
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        // IEND has a well known crc
+        assert!(bytes.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+
+        // IHDR dimensions
+        assert_eq!(bytes[16..24], [0, 0, 0, 2, 0, 0, 0, 3]);
+    }
+}
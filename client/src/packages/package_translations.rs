@@ -0,0 +1,122 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Text overrides for a single language, unset fields fall back
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct PackageTranslation {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub long_description: Option<String>,
+}
+
+/// Read from `[translations.<language>]` tables in package.toml
+#[derive(Default, Clone)]
+pub struct PackageTranslations {
+    default: PackageTranslation,
+    languages: HashMap<String, PackageTranslation>,
+}
+
+impl PackageTranslations {
+    pub fn new(
+        default: PackageTranslation,
+        languages: HashMap<String, PackageTranslation>,
+    ) -> Self {
+        let languages = languages
+            .into_iter()
+            .map(|(language, translation)| (normalize_language(&language), translation))
+            .collect();
+
+        Self { default, languages }
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.languages.keys().map(String::as_str)
+    }
+
+    pub fn name(&self, language: &str) -> &str {
+        self.resolve(language, |translation| translation.name.as_ref())
+    }
+
+    pub fn description(&self, language: &str) -> &str {
+        self.resolve(language, |translation| translation.description.as_ref())
+    }
+
+    pub fn long_description(&self, language: &str) -> &str {
+        self.resolve(language, |translation| {
+            translation.long_description.as_ref()
+        })
+    }
+
+    /// Tries the exact language, then the base language ("pt" for "pt-br"), then the default
+    fn resolve(
+        &self,
+        language: &str,
+        field: impl Fn(&PackageTranslation) -> Option<&String>,
+    ) -> &str {
+        let language = normalize_language(language);
+        let base_language = language.split('-').next().unwrap_or_default();
+
+        let text = [language.as_str(), base_language]
+            .into_iter()
+            .filter(|language| !language.is_empty())
+            .flat_map(|language| self.languages.get(language))
+            .chain(std::iter::once(&self.default))
+            .find_map(field);
+
+        text.map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// Lowercase with `-` separators, "pt_BR" becomes "pt-br"
+pub fn normalize_language(language: &str) -> String {
+    language.trim().to_lowercase().replace('_', "-")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn translation(name: Option<&str>, description: Option<&str>) -> PackageTranslation {
+        PackageTranslation {
+            name: name.map(String::from),
+            description: description.map(String::from),
+            long_description: None,
+        }
+    }
+
+    fn create_translations() -> PackageTranslations {
+        PackageTranslations::new(
+            translation(Some("Cannon"), Some("Shoots forward")),
+            HashMap::from([
+                (
+                    String::from("pt"),
+                    translation(Some("Canhão"), Some("Atira")),
+                ),
+                (String::from("pt_BR"), translation(None, Some("Dispara"))),
+            ]),
+        )
+    }
+
+    #[test]
+    fn falls_back_to_base_language_then_default() {
+        let translations = create_translations();
+
+        assert_eq!(translations.name("pt-BR"), "Canhão");
+        assert_eq!(translations.description("pt-BR"), "Dispara");
+        assert_eq!(translations.description("pt"), "Atira");
+        assert_eq!(translations.name("ja"), "Cannon");
+        assert_eq!(translations.name(""), "Cannon");
+        assert_eq!(translations.long_description("pt"), "");
+    }
+
+    #[test]
+    fn normalizes_language_keys() {
+        let translations = create_translations();
+        let mut languages: Vec<_> = translations.languages().collect();
+        languages.sort();
+
+        assert_eq!(languages, ["pt", "pt-br"]);
+        assert_eq!(normalize_language(" PT_br "), "pt-br");
+    }
+}
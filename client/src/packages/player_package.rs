@@ -3,6 +3,7 @@ use crate::bindable::Element;
 use crate::render::ui::{PackageListing, PackagePreviewData};
 use packets::structures::TextureAnimPathPair;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize, Default)]
 #[serde(default)]
@@ -19,6 +20,7 @@ struct PlayerMeta {
     mugshot_animation_path: String,
     emotions_texture_path: String,
     emotions_animation_path: String,
+    translations: HashMap<String, PackageTranslation>,
 }
 
 #[derive(Default, Clone)]
@@ -33,6 +35,7 @@ pub struct PlayerPackage {
     pub overworld_paths: TextureAnimPathPair<'static>,
     pub mugshot_paths: TextureAnimPathPair<'static>,
     pub emotions_paths: Option<TextureAnimPathPair<'static>>,
+    pub translations: PackageTranslations,
 }
 
 impl Package for PlayerPackage {
@@ -78,9 +81,9 @@ impl Package for PlayerPackage {
 
         let base_path = &package.package_info.base_path;
 
-        package.name = meta.name;
+        package.name = meta.name.clone();
         package.health = meta.health;
-        package.description = meta.description;
+        package.description = meta.description.clone();
         package.preview_texture_path = base_path.clone() + &meta.preview_texture_path;
         package.overworld_paths.texture = (base_path.clone() + &meta.overworld_texture_path).into();
         package.overworld_paths.animation =
@@ -95,6 +98,18 @@ impl Package for PlayerPackage {
             });
         }
 
+        let default_translation = PackageTranslation {
+            name: Some(meta.name),
+            description: Some(meta.description),
+            long_description: None,
+        };
+        package.translations = PackageTranslations::new(default_translation, meta.translations);
+
         package
     }
+
+    fn set_language(&mut self, language: &str) {
+        self.name = self.translations.name(language).to_string();
+        self.description = self.translations.description(language).to_string();
+    }
 }
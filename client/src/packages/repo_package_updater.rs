@@ -1,6 +1,6 @@
 use super::PackageNamespace;
 use crate::render::ui::PackageListing;
-use crate::resources::{Globals, ResourcePaths};
+use crate::resources::{Globals, IntegrityCache, NotificationKind, ResourcePaths};
 use framework::prelude::{AsyncTask, GameIO};
 use packets::address_parsing::uri_encode;
 use packets::structures::{PackageCategory, PackageId};
@@ -206,10 +206,24 @@ impl RepoPackageUpdater {
         // reload package
         let globals = game_io.resource_mut::<Globals>().unwrap();
         let path = globals.resolve_package_download_path(category, old_id);
+        let was_installed = globals
+            .package_info(category, PackageNamespace::Local, old_id)
+            .is_some();
 
         globals.unload_package(category, PackageNamespace::Local, old_id);
         globals.load_package(category, PackageNamespace::Local, &path);
 
+        // remember the installed hash to detect changes on boot
+        let mut integrity_cache = IntegrityCache::load();
+        integrity_cache.forget_package(old_id);
+
+        if let Some(package_info) = globals.package_info(category, PackageNamespace::Local, new_id)
+        {
+            integrity_cache.record_package(new_id.clone(), package_info.hash);
+        }
+
+        integrity_cache.save();
+
         // update save
         if old_id != new_id {
             let global_save = &mut globals.global_save;
@@ -217,6 +231,14 @@ impl RepoPackageUpdater {
             global_save.save();
         }
 
+        let (kind, text) = if was_installed {
+            (NotificationKind::PackageUpdate, format!("Updated {new_id}"))
+        } else {
+            (NotificationKind::Download, format!("Downloaded {new_id}"))
+        };
+
+        globals.notification_center.push(kind, text);
+
         // continue working on queue
         self.request_latest_listing(game_io);
     }
@@ -46,6 +46,11 @@ impl ResourcePackage {
         }
     }
 
+    /// The translations folder within this package, used by language packs
+    pub fn translations_folder(&self) -> String {
+        self.package_info.base_path.clone() + ResourcePaths::TRANSLATIONS_FOLDER
+    }
+
     pub fn default_package_listing() -> PackageListing {
         PackageListing {
             id: PackageId::default(),
@@ -127,18 +132,18 @@ impl PackageManager<ResourcePackage> {
         }
 
         // apply the final order
-        let saved_order = &mut global_save.resource_package_order;
-
-        for (id, enabled) in saved_order.iter() {
-            if !enabled {
-                continue;
-            }
-
-            let Some(package) = self.package(PackageNamespace::Local, id) else {
-                continue;
-            };
-
+        for package in self.enabled_packages(global_save) {
             package.apply(game_io, assets);
         }
     }
+
+    /// Enabled packages in the saved order, later packages override earlier packages
+    pub fn enabled_packages<'a>(
+        &'a self,
+        global_save: &'a GlobalSave,
+    ) -> impl Iterator<Item = &'a ResourcePackage> {
+        (global_save.resource_package_order.iter())
+            .filter(|(_, enabled)| *enabled)
+            .flat_map(|(id, _)| self.package(PackageNamespace::Local, id))
+    }
 }
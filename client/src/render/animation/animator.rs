@@ -4,6 +4,7 @@ use crate::resources::AssetManager;
 use framework::graphics::Sprite;
 use framework::prelude::{Rect, Vec2};
 use indexmap::IndexMap;
+use std::sync::Arc;
 use uncased::{Uncased, UncasedStr};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -45,8 +46,9 @@ pub struct Animator {
     loop_count: usize,
     bounced: bool,
     reversed: bool,
-    states: IndexMap<Uncased<'static>, FrameList>,
-    derived_states: Vec<DerivedState>,
+    // shared between clones until modified, animators are cloned for every rollback snapshot
+    states: Arc<IndexMap<Uncased<'static>, FrameList>>,
+    derived_states: Arc<Vec<DerivedState>>,
 }
 
 impl Animator {
@@ -60,8 +62,8 @@ impl Animator {
             loop_mode: AnimatorLoopMode::Once,
             bounced: false,
             reversed: false,
-            states: IndexMap::new(),
-            derived_states: Vec::new(),
+            states: Default::default(),
+            derived_states: Default::default(),
         }
     }
 
@@ -101,7 +103,8 @@ impl Animator {
     }
 
     pub fn load_from_str(&mut self, data: &str) {
-        self.states.clear();
+        // replacing rather than clearing, avoids copying states shared with clones
+        self.states = Default::default();
 
         let mut work_state: Option<(String, FrameList)> = None;
         let mut frame: Option<AnimationFrame> = None;
@@ -126,7 +129,8 @@ impl Animator {
                             frame_list.add_frame(frame);
                         }
 
-                        self.states.insert(Uncased::from(state_name), frame_list);
+                        Arc::make_mut(&mut self.states)
+                            .insert(Uncased::from(state_name), frame_list);
                     }
 
                     let attributes = Animator::read_attributes(word, line, i);
@@ -244,7 +248,7 @@ impl Animator {
                 frame_list.add_frame(frame);
             }
 
-            self.states.insert(Uncased::from(state_name), frame_list);
+            Arc::make_mut(&mut self.states).insert(Uncased::from(state_name), frame_list);
         }
 
         self.rederive_states();
@@ -361,13 +365,13 @@ impl Animator {
     }
 
     fn rederive_states(&mut self) {
-        for data in &self.derived_states {
+        for data in self.derived_states.iter() {
             let frame_list = self
                 .derive_frames(&data.original_state, &data.frame_derivation)
                 .unwrap_or_default();
 
             let state = data.state.clone().into();
-            self.states.insert(state, frame_list);
+            Arc::make_mut(&mut self.states).insert(state, frame_list);
         }
     }
 
@@ -401,8 +405,8 @@ impl Animator {
         };
 
         let state = derivation.state.clone().into();
-        self.states.insert(state, frames);
-        self.derived_states.push(derivation);
+        Arc::make_mut(&mut self.states).insert(state, frames);
+        Arc::make_mut(&mut self.derived_states).push(derivation);
     }
 
     /// Assumes state is uppercase
@@ -426,13 +430,13 @@ impl Animator {
 
     pub fn add_state<S: AsRef<str>>(&mut self, state: S, frame_list: FrameList) {
         let state = state.as_ref().to_ascii_uppercase().into();
-        self.states.insert(state, frame_list);
+        Arc::make_mut(&mut self.states).insert(state, frame_list);
     }
 
     pub fn remove_state(&mut self, state: &str) {
         // todo: avoid clone
         let q = Uncased::from_owned(state.to_string());
-        self.states.shift_remove(&q);
+        Arc::make_mut(&mut self.states).shift_remove(&q);
     }
 
     pub fn loop_mode(&self) -> AnimatorLoopMode {
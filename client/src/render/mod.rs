@@ -3,6 +3,8 @@ mod background;
 mod camera;
 mod shaders;
 mod sprite_node;
+mod texture_atlas;
+mod texture_readback;
 pub mod ui;
 
 pub use animation::*;
@@ -10,5 +12,7 @@ pub use background::*;
 pub use camera::*;
 pub use shaders::*;
 pub use sprite_node::*;
+pub use texture_atlas::*;
+pub use texture_readback::*;
 
 pub use crate::bindable::Direction;
@@ -2,8 +2,11 @@ mod background_pipeline;
 mod map_pipeline;
 mod post_process_adjust;
 mod post_process_adjust_config;
+mod post_process_clip_recorder;
 mod post_process_color_blindness;
+mod post_process_flash_reduction;
 mod post_process_ghosting;
+mod post_process_screenshot;
 mod sprite_color_pipeline;
 mod sprite_shader_effect;
 
@@ -11,7 +14,10 @@ pub use background_pipeline::*;
 pub use map_pipeline::*;
 pub use post_process_adjust::*;
 pub use post_process_adjust_config::*;
+pub use post_process_clip_recorder::*;
 pub use post_process_color_blindness::*;
+pub use post_process_flash_reduction::*;
 pub use post_process_ghosting::*;
+pub use post_process_screenshot::*;
 pub use sprite_color_pipeline::*;
 pub use sprite_shader_effect::*;
@@ -0,0 +1,127 @@
+use crate::render::TextureReadback;
+use crate::resources::{save_clip, Globals, RESOLUTION_F};
+use framework::{prelude::*, wgpu};
+use std::collections::VecDeque;
+
+const CLIP_KEY: Key = Key::F9;
+const CLIP_SECONDS: usize = 30;
+/// Frames skipped between recorded frames, 60 / 4 = 15 frames per second
+const CLIP_FRAME_INTERVAL: usize = 4;
+const CLIP_FRAME_RATE: usize = 60 / CLIP_FRAME_INTERVAL;
+const CLIP_FRAME_LIMIT: usize = CLIP_SECONDS * CLIP_FRAME_RATE;
+const CLIP_SCALE: f32 = 0.5;
+
+/// Passes the frame through unchanged, keeping a rolling buffer of downscaled frames that can be saved as a clip
+pub struct PostProcessClipRecorder {
+    capture_target: RenderTarget,
+    post_pipeline: PostPipeline,
+    frame_counter: usize,
+    /// Set when the capture target is rendered, read back once the frame is submitted
+    captured: bool,
+    readbacks: VecDeque<TextureReadback>,
+    frames: VecDeque<Vec<u8>>,
+}
+
+impl PostProcessClipRecorder {
+    pub fn new(game_io: &GameIO) -> Self {
+        let device = game_io.graphics().device();
+        let shader = device.create_shader_module(include_wgsl!("post_process_screenshot.wgsl"));
+
+        Self {
+            capture_target: RenderTarget::new(game_io, (RESOLUTION_F * CLIP_SCALE).as_uvec2()),
+            post_pipeline: PostPipeline::new(game_io, &shader, "fs_main", &[]),
+            frame_counter: 0,
+            captured: false,
+            readbacks: VecDeque::new(),
+            frames: VecDeque::new(),
+        }
+    }
+
+    fn collect_frames(&mut self, game_io: &GameIO) {
+        game_io.graphics().device().poll(wgpu::Maintain::Poll);
+
+        while let Some(readback) = self.readbacks.front_mut() {
+            if !readback.is_finished() {
+                break;
+            }
+
+            let readback = self.readbacks.pop_front().unwrap();
+
+            let Some(rgba) = readback.read() else {
+                continue;
+            };
+
+            if self.frames.len() >= CLIP_FRAME_LIMIT {
+                self.frames.pop_front();
+            }
+
+            self.frames.push_back(rgba);
+        }
+    }
+}
+
+impl PostProcess for PostProcessClipRecorder {
+    fn render_pipeline(&self) -> &PostPipeline {
+        &self.post_pipeline
+    }
+
+    fn uniform_resources(&self) -> Vec<BindingResource> {
+        Vec::new()
+    }
+
+    fn update(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if !globals.config.clip_recording {
+            self.captured = false;
+            self.readbacks.clear();
+            self.frames.clear();
+            return;
+        }
+
+        // the previous frame has been submitted, the capture is ready to copy
+        if std::mem::take(&mut self.captured) {
+            let readback = TextureReadback::new(game_io, self.capture_target.texture());
+            self.readbacks.push_back(readback);
+        }
+
+        self.collect_frames(game_io);
+
+        if game_io.input().was_key_just_pressed(CLIP_KEY) && !self.frames.is_empty() {
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+
+            let size = self.capture_target.texture().size();
+            let frames = self.frames.iter().cloned().collect();
+            save_clip(size, frames, CLIP_FRAME_RATE);
+        }
+    }
+
+    fn draw(
+        &mut self,
+        game_io: &GameIO,
+        mut render_pass: RenderPass,
+        texture_source: &TextureSourceModel,
+    ) {
+        let copy_pipeline = game_io.resource::<CopyPipeline>().unwrap();
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        self.frame_counter += 1;
+
+        if globals.config.clip_recording && self.frame_counter % CLIP_FRAME_INTERVAL == 0 {
+            let mut sub_pass = render_pass.create_subpass(&self.capture_target);
+
+            let mut queue = RenderQueue::new(game_io, copy_pipeline, []);
+            queue.draw_model(texture_source);
+            sub_pass.consume_queue(queue);
+
+            sub_pass.flush();
+
+            self.captured = true;
+        }
+
+        let mut queue = RenderQueue::new(game_io, copy_pipeline, []);
+        queue.draw_model(texture_source);
+        render_pass.consume_queue(queue);
+        render_pass.flush();
+    }
+}
@@ -0,0 +1,90 @@
+use framework::{prelude::*, wgpu};
+
+/// Limits how quickly the average brightness of the screen can change,
+/// softening full screen flashes and rapid flickering
+pub struct PostProcessFlashReduction {
+    active_target: RenderTarget,
+    saved_target: RenderTarget,
+    post_pipeline: PostPipeline,
+    model: TextureSourceModel,
+}
+
+impl PostProcessFlashReduction {
+    pub fn new(game_io: &GameIO) -> Self {
+        let device = game_io.graphics().device();
+        let shader =
+            device.create_shader_module(include_wgsl!("post_process_flash_reduction.wgsl"));
+
+        let active_target = RenderTarget::new(game_io, UVec2::new(1, 1));
+        let saved_target = RenderTarget::new(game_io, UVec2::new(1, 1));
+        let texture = saved_target.texture().clone();
+
+        Self {
+            active_target,
+            saved_target,
+            post_pipeline: PostPipeline::new(
+                game_io,
+                &shader,
+                "fs_main",
+                &[BindGroupLayoutEntry {
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    binding_type: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                }],
+            ),
+            model: TextureSourceModel::new(game_io, texture),
+        }
+    }
+}
+
+impl PostProcess for PostProcessFlashReduction {
+    fn render_pipeline(&self) -> &PostPipeline {
+        &self.post_pipeline
+    }
+
+    fn uniform_resources(&self) -> Vec<BindingResource> {
+        Vec::new()
+    }
+
+    fn update(&mut self, _game_io: &GameIO) {}
+
+    fn draw(
+        &mut self,
+        game_io: &GameIO,
+        mut render_pass: RenderPass,
+        texture_source: &TextureSourceModel,
+    ) {
+        let copy_pipeline = game_io.resource::<CopyPipeline>().unwrap();
+
+        // resize target if necessary
+        self.active_target
+            .resize(game_io, texture_source.texture().size());
+
+        // blend the source texture with the previous output
+        let mut sub_pass = render_pass.create_subpass(&self.active_target);
+
+        let previous_texture = self.saved_target.texture().clone();
+        let mut queue = RenderQueue::new(
+            game_io,
+            self.render_pipeline(),
+            [previous_texture.as_binding()],
+        );
+        queue.draw_model(texture_source);
+        sub_pass.consume_queue(queue);
+
+        sub_pass.flush();
+
+        // copy final render
+        let mut queue = RenderQueue::new(game_io, copy_pipeline, []);
+
+        self.model.set_texture(self.active_target.texture().clone());
+        queue.draw_model(&self.model);
+        render_pass.consume_queue(queue);
+        render_pass.flush();
+
+        std::mem::swap(&mut self.active_target, &mut self.saved_target);
+    }
+}
@@ -0,0 +1,48 @@
+@group(1) @binding(0)
+var txture: texture_2d<f32>;
+@group(1) @binding(1)
+var smplr: sampler;
+
+// the previous output
+@group(0) @binding(0)
+var previous: texture_2d<f32>;
+
+// largest change in average brightness allowed per frame
+const MAX_CHANGE: f32 = 0.04;
+// samples per axis used to estimate average brightness
+const SAMPLES: u32 = 4u;
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+fn load(texture: texture_2d<f32>, uv: vec2<f32>) -> vec3<f32> {
+    let size = vec2<f32>(textureDimensions(texture));
+    let coords = min(vec2<u32>(uv * size), textureDimensions(texture) - 1u);
+    return textureLoad(texture, coords, 0).rgb;
+}
+
+// the same for every pixel, so the whole screen is slowed together
+fn average_change() -> f32 {
+    var total = 0.0;
+
+    for (var x = 0u; x < SAMPLES; x++) {
+        for (var y = 0u; y < SAMPLES; y++) {
+            let uv = (vec2<f32>(f32(x), f32(y)) + 0.5) / f32(SAMPLES);
+            total += luminance(load(txture, uv)) - luminance(load(previous, uv));
+        }
+    }
+
+    return total / f32(SAMPLES * SAMPLES);
+}
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let color = textureSample(txture, smplr, uv).rgb;
+    let change = abs(average_change());
+
+    // small changes such as moving sprites pass through untouched
+    let progress = min(MAX_CHANGE / max(change, 0.0001), 1.0);
+
+    return vec4<f32>(mix(load(previous, uv), color, progress), 1.0);
+}
@@ -0,0 +1,88 @@
+use crate::render::TextureReadback;
+use crate::resources::{Globals, Screenshot, ScreenshotMetadata, RESOLUTION_F};
+use framework::prelude::*;
+
+const SCREENSHOT_KEY: Key = Key::F12;
+
+/// Passes the frame through unchanged, copying it at the native resolution when the screenshot key is pressed
+pub struct PostProcessScreenshot {
+    capture_target: RenderTarget,
+    post_pipeline: PostPipeline,
+    requested: Option<ScreenshotMetadata>,
+    /// Copied into the capture target, read back once the frame is submitted
+    captured: Option<ScreenshotMetadata>,
+}
+
+impl PostProcessScreenshot {
+    pub fn new(game_io: &GameIO) -> Self {
+        let device = game_io.graphics().device();
+        let shader = device.create_shader_module(include_wgsl!("post_process_screenshot.wgsl"));
+
+        Self {
+            capture_target: RenderTarget::new(game_io, UVec2::new(1, 1)),
+            post_pipeline: PostPipeline::new(game_io, &shader, "fs_main", &[]),
+            requested: None,
+            captured: None,
+        }
+    }
+}
+
+impl PostProcess for PostProcessScreenshot {
+    fn render_pipeline(&self) -> &PostPipeline {
+        &self.post_pipeline
+    }
+
+    fn uniform_resources(&self) -> Vec<BindingResource> {
+        Vec::new()
+    }
+
+    fn update(&mut self, game_io: &GameIO) {
+        // the previous frame has been submitted, the capture is ready to read
+        if let Some(metadata) = self.captured.take() {
+            let readback = TextureReadback::new(game_io, self.capture_target.texture());
+            let size = readback.size();
+
+            if let Some(rgba) = readback.wait(game_io) {
+                Screenshot::save(size.x, size.y, rgba, metadata);
+            }
+        }
+
+        if game_io.input().was_key_just_pressed(SCREENSHOT_KEY) {
+            let globals = game_io.resource::<Globals>().unwrap();
+            let mut metadata = globals.screenshot_metadata.clone();
+            metadata.server_address = globals.connected_server_address.clone();
+
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+            self.requested = Some(metadata);
+        }
+    }
+
+    fn draw(
+        &mut self,
+        game_io: &GameIO,
+        mut render_pass: RenderPass,
+        texture_source: &TextureSourceModel,
+    ) {
+        let copy_pipeline = game_io.resource::<CopyPipeline>().unwrap();
+
+        if let Some(metadata) = self.requested.take() {
+            // captured at the game's resolution, before any window scaling
+            self.capture_target.resize(game_io, RESOLUTION_F.as_uvec2());
+
+            let mut sub_pass = render_pass.create_subpass(&self.capture_target);
+
+            let mut queue = RenderQueue::new(game_io, copy_pipeline, []);
+            queue.draw_model(texture_source);
+            sub_pass.consume_queue(queue);
+
+            sub_pass.flush();
+
+            self.captured = Some(metadata);
+        }
+
+        let mut queue = RenderQueue::new(game_io, copy_pipeline, []);
+        queue.draw_model(texture_source);
+        render_pass.consume_queue(queue);
+        render_pass.flush();
+    }
+}
@@ -0,0 +1,9 @@
+@group(1) @binding(0)
+var txture: texture_2d<f32>;
+@group(1) @binding(1)
+var smplr: sampler;
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    return textureSample(txture, smplr, uv);
+}
@@ -5,19 +5,63 @@ use framework::prelude::*;
 use framework::wgpu;
 use std::sync::Arc;
 
+struct SpriteEffectPipelines {
+    add: SpritePipeline<SpriteInstanceData>,
+    multiply: SpritePipeline<SpriteInstanceData>,
+    palette_add: SpritePipeline<SpriteInstanceData>,
+    palette_multiply: SpritePipeline<SpriteInstanceData>,
+}
+
+impl SpriteEffectPipelines {
+    fn new(
+        game_io: &GameIO,
+        shared_shader: &wgpu::ShaderModule,
+        palette_shader: &wgpu::ShaderModule,
+        vertex_main: &str,
+        add_main: &str,
+        multiply_main: &str,
+    ) -> Self {
+        Self {
+            add: create_pipeline(game_io, shared_shader, vertex_main, add_main),
+            multiply: create_pipeline(game_io, shared_shader, vertex_main, multiply_main),
+            palette_add: create_palette_pipeline(
+                game_io,
+                shared_shader,
+                vertex_main,
+                palette_shader,
+                add_main,
+            ),
+            palette_multiply: create_palette_pipeline(
+                game_io,
+                shared_shader,
+                vertex_main,
+                palette_shader,
+                multiply_main,
+            ),
+        }
+    }
+
+    fn pipeline_for_config(
+        &self,
+        with_palette: bool,
+        color_mode: SpriteColorMode,
+    ) -> &SpritePipeline<SpriteInstanceData> {
+        match (with_palette, color_mode) {
+            (false, SpriteColorMode::Add) => &self.add,
+            (false, SpriteColorMode::Multiply) => &self.multiply,
+            (true, SpriteColorMode::Add) => &self.palette_add,
+            (true, SpriteColorMode::Multiply) => &self.palette_multiply,
+        }
+    }
+}
+
 pub struct SpritePipelineCollection {
-    add_pipeline: SpritePipeline<SpriteInstanceData>,
-    multiply_pipeline: SpritePipeline<SpriteInstanceData>,
-    palette_add_pipeline: SpritePipeline<SpriteInstanceData>,
-    palette_multiply_pipeline: SpritePipeline<SpriteInstanceData>,
-    grayscale_add_pipeline: SpritePipeline<SpriteInstanceData>,
-    grayscale_multiply_pipeline: SpritePipeline<SpriteInstanceData>,
-    grayscale_palette_add_pipeline: SpritePipeline<SpriteInstanceData>,
-    grayscale_palette_multiply_pipeline: SpritePipeline<SpriteInstanceData>,
-    pixelate_add_pipeline: SpritePipeline<SpriteInstanceData>,
-    pixelate_multiply_pipeline: SpritePipeline<SpriteInstanceData>,
-    pixelate_palette_add_pipeline: SpritePipeline<SpriteInstanceData>,
-    pixelate_palette_multiply_pipeline: SpritePipeline<SpriteInstanceData>,
+    default_pipelines: SpriteEffectPipelines,
+    grayscale_pipelines: SpriteEffectPipelines,
+    pixelate_pipelines: SpriteEffectPipelines,
+    dissolve_pipelines: SpriteEffectPipelines,
+    chromatic_aberration_pipelines: SpriteEffectPipelines,
+    flash_pipelines: SpriteEffectPipelines,
 }
 
 impl SpritePipelineCollection {
@@ -27,75 +71,41 @@ impl SpritePipelineCollection {
         let palette_shader =
             device.create_shader_module(include_wgsl!("sprite_palette_shader.wgsl"));
 
-        Self {
-            add_pipeline: create_pipeline(game_io, &shared_shader, "vs_main", "add_main"),
-            multiply_pipeline: create_pipeline(game_io, &shared_shader, "vs_main", "multiply_main"),
-            palette_add_pipeline: create_palette_pipeline(
-                game_io,
-                &shared_shader,
-                "vs_main",
-                &palette_shader,
-                "add_main",
-            ),
-            palette_multiply_pipeline: create_palette_pipeline(
+        let create_pipelines = |vertex_main, add_main, multiply_main| {
+            SpriteEffectPipelines::new(
                 game_io,
                 &shared_shader,
-                "vs_main",
                 &palette_shader,
-                "multiply_main",
-            ),
-            grayscale_add_pipeline: create_pipeline(
-                game_io,
-                &shared_shader,
-                "vs_main",
-                "grayscale_add_main",
-            ),
-            grayscale_multiply_pipeline: create_pipeline(
-                game_io,
-                &shared_shader,
-                "vs_main",
-                "grayscale_multiply_main",
-            ),
-            grayscale_palette_add_pipeline: create_palette_pipeline(
-                game_io,
-                &shared_shader,
+                vertex_main,
+                add_main,
+                multiply_main,
+            )
+        };
+
+        Self {
+            default_pipelines: create_pipelines("vs_main", "add_main", "multiply_main"),
+            grayscale_pipelines: create_pipelines(
                 "vs_main",
-                &palette_shader,
                 "grayscale_add_main",
-            ),
-            grayscale_palette_multiply_pipeline: create_palette_pipeline(
-                game_io,
-                &shared_shader,
-                "vs_main",
-                &palette_shader,
                 "grayscale_multiply_main",
             ),
-            pixelate_add_pipeline: create_pipeline(
-                game_io,
-                &shared_shader,
+            pixelate_pipelines: create_pipelines(
                 "pixelate_vs_main",
                 "pixelate_add_main",
-            ),
-            pixelate_multiply_pipeline: create_pipeline(
-                game_io,
-                &shared_shader,
-                "pixelate_vs_main",
                 "pixelate_multiply_main",
             ),
-            pixelate_palette_add_pipeline: create_palette_pipeline(
-                game_io,
-                &shared_shader,
-                "pixelate_vs_main",
-                &palette_shader,
-                "pixelate_add_main",
+            dissolve_pipelines: create_pipelines(
+                "vs_main",
+                "dissolve_add_main",
+                "dissolve_multiply_main",
             ),
-            pixelate_palette_multiply_pipeline: create_palette_pipeline(
-                game_io,
-                &shared_shader,
-                "pixelate_vs_main",
-                &palette_shader,
-                "pixelate_multiply_main",
+            chromatic_aberration_pipelines: create_pipelines(
+                "frame_bounds_vs_main",
+                "chromatic_aberration_add_main",
+                "chromatic_aberration_multiply_main",
             ),
+            // flashing replaces the color, color mode has no effect
+            flash_pipelines: create_pipelines("vs_main", "flash_main", "flash_main"),
         }
     }
 
@@ -105,47 +115,16 @@ impl SpritePipelineCollection {
         with_palette: bool,
         color_mode: SpriteColorMode,
     ) -> &SpritePipeline<SpriteInstanceData> {
-        match shader_effect {
-            SpriteShaderEffect::Default => {
-                if with_palette {
-                    match color_mode {
-                        SpriteColorMode::Add => &self.palette_add_pipeline,
-                        SpriteColorMode::Multiply => &self.palette_multiply_pipeline,
-                    }
-                } else {
-                    match color_mode {
-                        SpriteColorMode::Add => &self.add_pipeline,
-                        SpriteColorMode::Multiply => &self.multiply_pipeline,
-                    }
-                }
-            }
-            SpriteShaderEffect::Grayscale => {
-                if with_palette {
-                    match color_mode {
-                        SpriteColorMode::Add => &self.grayscale_palette_add_pipeline,
-                        SpriteColorMode::Multiply => &self.grayscale_palette_multiply_pipeline,
-                    }
-                } else {
-                    match color_mode {
-                        SpriteColorMode::Add => &self.grayscale_add_pipeline,
-                        SpriteColorMode::Multiply => &self.grayscale_multiply_pipeline,
-                    }
-                }
-            }
-            SpriteShaderEffect::Pixelate => {
-                if with_palette {
-                    match color_mode {
-                        SpriteColorMode::Add => &self.pixelate_palette_add_pipeline,
-                        SpriteColorMode::Multiply => &self.pixelate_palette_multiply_pipeline,
-                    }
-                } else {
-                    match color_mode {
-                        SpriteColorMode::Add => &self.pixelate_add_pipeline,
-                        SpriteColorMode::Multiply => &self.pixelate_multiply_pipeline,
-                    }
-                }
-            }
-        }
+        let pipelines = match shader_effect {
+            SpriteShaderEffect::Default => &self.default_pipelines,
+            SpriteShaderEffect::Grayscale => &self.grayscale_pipelines,
+            SpriteShaderEffect::Pixelate => &self.pixelate_pipelines,
+            SpriteShaderEffect::Dissolve => &self.dissolve_pipelines,
+            SpriteShaderEffect::ChromaticAberration => &self.chromatic_aberration_pipelines,
+            SpriteShaderEffect::Flash => &self.flash_pipelines,
+        };
+
+        pipelines.pipeline_for_config(with_palette, color_mode)
     }
 }
 
@@ -213,6 +192,7 @@ pub struct SpriteColorQueue<'a> {
     updated_camera: bool,
     palette: Option<Arc<Texture>>,
     previous_palette_ptr: Option<*const Texture>,
+    texture_atlases: Option<&'a TextureAtlasSet>,
     game_io: &'a GameIO,
 }
 
@@ -237,6 +217,7 @@ impl<'a> SpriteColorQueue<'a> {
             updated_camera: false,
             palette: None,
             previous_palette_ptr: None,
+            texture_atlases: None,
             game_io,
         }
     }
@@ -270,6 +251,11 @@ impl<'a> SpriteColorQueue<'a> {
         self.sprite_queue.set_scissor(rect);
     }
 
+    /// Sprites will draw from atlases when possible, allowing larger batches
+    pub fn set_texture_atlases(&mut self, texture_atlases: &'a TextureAtlasSet) {
+        self.texture_atlases = Some(texture_atlases);
+    }
+
     pub fn draw_sprite(&mut self, sprite: &Sprite) {
         let updated_shader_effect = self.shader_effect != self.previous_shader_effect;
         let updated_color_mode = self.color_mode != self.previous_color_mode;
@@ -321,7 +307,12 @@ impl<'a> SpriteColorQueue<'a> {
             self.previous_palette_ptr = palette_ptr;
         }
 
-        self.sprite_queue.draw_sprite(sprite);
+        let atlas_sprite = self
+            .texture_atlases
+            .and_then(|texture_atlases| texture_atlases.remap(sprite));
+
+        self.sprite_queue
+            .draw_sprite(atlas_sprite.as_ref().unwrap_or(sprite));
         self.queue_draw_count += 1;
     }
 }
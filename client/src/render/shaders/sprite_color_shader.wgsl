@@ -29,6 +29,13 @@ struct PixelateVertexOutput {
     @location(2) frame_size: vec2<f32>,
 };
 
+struct FrameBoundsVertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) frame_bounds: vec4<f32>,
+};
+
 fn plain_vs_main(v_in: VertexInput) -> VertexOutput {
     var v_out: VertexOutput;
 
@@ -66,6 +73,19 @@ fn pixelate_vs_main(v_in: VertexInput) -> PixelateVertexOutput {
     return v_out;
 }
 
+@vertex
+fn frame_bounds_vs_main(v_in: VertexInput) -> FrameBoundsVertexOutput {
+    let plain_v_out = plain_vs_main(v_in);
+
+    var v_out: FrameBoundsVertexOutput;
+    v_out.position = plain_v_out.position;
+    v_out.uv = plain_v_out.uv;
+    v_out.color = plain_v_out.color;
+    v_out.frame_bounds = v_in.bounds;
+
+    return v_out;
+}
+
 @group(1) @binding(0)
 var txture: texture_2d<f32>;
 @group(1) @binding(1)
@@ -130,4 +150,79 @@ fn pixelate_add_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>,
     out.w = sample.w * color.w;
 
     return out;
-}
\ No newline at end of file
+}
+
+fn dissolve_noise(uv: vec2<f32>) -> f32 {
+    // stable per texel, so the pattern doesn't crawl as the sprite moves
+    let texel = floor(uv * vec2<f32>(textureDimensions(txture)));
+    return fract(sin(dot(texel, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+fn dissolve_sample(uv: vec2<f32>, color: vec4<f32>) -> vec4<f32> {
+    var sample = textureSample(txture, smplr, uv);
+
+    if dissolve_noise(uv) >= color.a {
+        sample.a = 0.0;
+    }
+
+    return sample;
+}
+
+@fragment
+fn dissolve_multiply_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = dissolve_sample(uv, color);
+
+    return vec4<f32>(color.rgb * sample.rgb, sample.a);
+}
+
+@fragment
+fn dissolve_add_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = dissolve_sample(uv, color);
+    var out: vec4<f32> = clamp(vec4<f32>(color.rgb, 0.0) + sample, vec4<f32>(), vec4<f32>(1.0));
+    out.w = sample.w;
+
+    return out;
+}
+
+fn chromatic_aberration_sample(uv: vec2<f32>, color: vec4<f32>, frame_bounds: vec4<f32>) -> vec4<f32> {
+    // split up to 3 texels at 0 alpha
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(txture));
+    let offset = vec2<f32>((1.0 - color.a) * 3.0 * texel_size.x, 0.0);
+
+    // avoid bleeding into neighboring frames
+    let frame_start = frame_bounds.xy;
+    let frame_end = frame_bounds.xy + frame_bounds.zw;
+    let min_uv = min(frame_start, frame_end);
+    let max_uv = max(frame_start, frame_end);
+
+    let r_sample = textureSample(txture, smplr, clamp(uv - offset, min_uv, max_uv));
+    let sample = textureSample(txture, smplr, uv);
+    let b_sample = textureSample(txture, smplr, clamp(uv + offset, min_uv, max_uv));
+
+    let alpha = max(sample.a, max(r_sample.a, b_sample.a));
+
+    return vec4<f32>(r_sample.r * r_sample.a, sample.g * sample.a, b_sample.b * b_sample.a, alpha);
+}
+
+@fragment
+fn chromatic_aberration_multiply_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>, @location(2) frame_bounds: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = chromatic_aberration_sample(uv, color, frame_bounds);
+
+    return vec4<f32>(color.rgb * sample.rgb, sample.a);
+}
+
+@fragment
+fn chromatic_aberration_add_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>, @location(2) frame_bounds: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = chromatic_aberration_sample(uv, color, frame_bounds);
+    var out: vec4<f32> = clamp(vec4<f32>(color.rgb, 0.0) + sample, vec4<f32>(), vec4<f32>(1.0));
+    out.w = sample.w;
+
+    return out;
+}
+
+@fragment
+fn flash_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = textureSample(txture, smplr, uv);
+
+    return vec4<f32>(mix(sample.rgb, color.rgb, color.a), sample.a);
+}
@@ -80,3 +80,78 @@ fn pixelate_add_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>,
 
     return out;
 }
+
+fn dissolve_noise(uv: vec2<f32>) -> f32 {
+    // stable per texel, so the pattern doesn't crawl as the sprite moves
+    let texel = floor(uv * vec2<f32>(textureDimensions(txture)));
+    return fract(sin(dot(texel, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+fn dissolve_sample(uv: vec2<f32>, color: vec4<f32>) -> vec4<f32> {
+    var sample = sample_palette(uv);
+
+    if dissolve_noise(uv) >= color.a {
+        sample.a = 0.0;
+    }
+
+    return sample;
+}
+
+@fragment
+fn dissolve_multiply_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = dissolve_sample(uv, color);
+
+    return vec4<f32>(color.rgb * sample.rgb, sample.a);
+}
+
+@fragment
+fn dissolve_add_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = dissolve_sample(uv, color);
+    var out: vec4<f32> = clamp(vec4<f32>(color.rgb, 0.0) + sample, vec4<f32>(), vec4<f32>(1.0));
+    out.w = sample.w;
+
+    return out;
+}
+
+fn chromatic_aberration_sample(uv: vec2<f32>, color: vec4<f32>, frame_bounds: vec4<f32>) -> vec4<f32> {
+    // split up to 3 texels at 0 alpha
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(txture));
+    let offset = vec2<f32>((1.0 - color.a) * 3.0 * texel_size.x, 0.0);
+
+    // avoid bleeding into neighboring frames
+    let frame_start = frame_bounds.xy;
+    let frame_end = frame_bounds.xy + frame_bounds.zw;
+    let min_uv = min(frame_start, frame_end);
+    let max_uv = max(frame_start, frame_end);
+
+    let r_sample = sample_palette(clamp(uv - offset, min_uv, max_uv));
+    let sample = sample_palette(uv);
+    let b_sample = sample_palette(clamp(uv + offset, min_uv, max_uv));
+
+    let alpha = max(sample.a, max(r_sample.a, b_sample.a));
+
+    return vec4<f32>(r_sample.r * r_sample.a, sample.g * sample.a, b_sample.b * b_sample.a, alpha);
+}
+
+@fragment
+fn chromatic_aberration_multiply_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>, @location(2) frame_bounds: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = chromatic_aberration_sample(uv, color, frame_bounds);
+
+    return vec4<f32>(color.rgb * sample.rgb, sample.a);
+}
+
+@fragment
+fn chromatic_aberration_add_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>, @location(2) frame_bounds: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = chromatic_aberration_sample(uv, color, frame_bounds);
+    var out: vec4<f32> = clamp(vec4<f32>(color.rgb, 0.0) + sample, vec4<f32>(), vec4<f32>(1.0));
+    out.w = sample.w;
+
+    return out;
+}
+
+@fragment
+fn flash_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = sample_palette(uv);
+
+    return vec4<f32>(mix(sample.rgb, color.rgb, color.a), sample.a);
+}
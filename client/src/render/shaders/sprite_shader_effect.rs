@@ -6,7 +6,14 @@ pub enum SpriteShaderEffect {
     #[default]
     Default,
     Grayscale,
+    /// Color alpha controls pixelation, lower alpha is more pixelated
     Pixelate,
+    /// Color alpha controls the amount of pixels kept, lower alpha removes more pixels
+    Dissolve,
+    /// Color alpha controls the color channel split, lower alpha splits further
+    ChromaticAberration,
+    /// Color alpha controls how much of the color replaces the texture's color
+    Flash,
 }
 
 impl<'lua> rollback_mlua::FromLua<'lua> for SpriteShaderEffect {
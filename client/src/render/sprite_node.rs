@@ -11,6 +11,7 @@ pub use crate::structures::{Tree, TreeIndex, TreeNode};
 #[derive(Clone)]
 pub struct SpriteNode {
     layer: i32,
+    sort_offset: i32,
     scale: Vec2,
     offset: Vec2,
     palette_path: String,
@@ -35,6 +36,7 @@ impl SpriteNode {
 
         Self {
             layer: 0,
+            sort_offset: 0,
             scale: Vec2::ONE,
             offset: Vec2::ZERO,
             palette_path: String::new(),
@@ -60,6 +62,15 @@ impl SpriteNode {
         self.layer = layer;
     }
 
+    pub fn sort_offset(&self) -> i32 {
+        self.sort_offset
+    }
+
+    /// Orders nodes sharing a layer, higher is closer
+    pub fn set_sort_offset(&mut self, sort_offset: i32) {
+        self.sort_offset = sort_offset;
+    }
+
     pub fn offset(&self) -> Vec2 {
         self.offset
     }
@@ -403,7 +414,7 @@ impl Tree<SpriteNode> {
 
         // sort nodes
         sprite_nodes.extend(self.values_mut());
-        sprite_nodes.sort_by_key(|node| -node.layer());
+        sprite_nodes.sort_by_key(|node| (-node.layer(), node.sort_offset()));
 
         // draw nodes
         for node in sprite_nodes.iter_mut() {
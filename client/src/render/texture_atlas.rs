@@ -0,0 +1,273 @@
+use super::{Camera, SpriteColorQueue};
+use crate::bindable::SpriteColorMode;
+use crate::RESOLUTION_F;
+use framework::prelude::*;
+use framework::wgpu;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const ATLAS_SIZE: u32 = 2048;
+/// Larger textures are left alone, they would crowd out everything else
+const MAX_REGION_SIZE: u32 = 512;
+/// Space between regions, avoids sampling neighboring textures
+const REGION_PADDING: u32 = 1;
+/// Packing a single texture wouldn't save any texture switches
+const MIN_BATCH_SIZE: usize = 2;
+
+/// Shelf packer, places rects left to right in rows
+pub struct AtlasPacker {
+    width: u32,
+    max_height: u32,
+    padding: u32,
+    cursor: UVec2,
+    shelf_height: u32,
+    used_width: u32,
+}
+
+impl AtlasPacker {
+    pub fn new(width: u32, max_height: u32, padding: u32) -> Self {
+        Self {
+            width,
+            max_height,
+            padding,
+            cursor: UVec2::ZERO,
+            shelf_height: 0,
+            used_width: 0,
+        }
+    }
+
+    /// Returns the top left of the new region, or None if the atlas is full
+    pub fn insert(&mut self, size: UVec2) -> Option<UVec2> {
+        let padded_size = size + UVec2::splat(self.padding);
+
+        if padded_size.x > self.width {
+            return None;
+        }
+
+        if self.cursor.x + padded_size.x > self.width {
+            // start a new shelf
+            self.cursor = UVec2::new(0, self.cursor.y + self.shelf_height);
+            self.shelf_height = 0;
+        }
+
+        if self.cursor.y + padded_size.y > self.max_height {
+            return None;
+        }
+
+        let position = self.cursor;
+        self.cursor.x += padded_size.x;
+        self.shelf_height = self.shelf_height.max(padded_size.y);
+        self.used_width = self.used_width.max(self.cursor.x);
+
+        Some(position)
+    }
+
+    pub fn used_size(&self) -> UVec2 {
+        UVec2::new(self.used_width, self.cursor.y + self.shelf_height)
+    }
+}
+
+enum AtlasEntry {
+    Pending(Arc<Texture>),
+    Packed {
+        // kept to prevent the pointer from being reused
+        _texture: Arc<Texture>,
+        atlas_index: usize,
+        offset: Vec2,
+    },
+    Ignored,
+}
+
+#[derive(Default)]
+struct AtlasState {
+    atlases: Vec<Arc<Texture>>,
+    entries: HashMap<*const Texture, AtlasEntry>,
+    pending_count: usize,
+}
+
+/// Combines textures seen by `remap` into shared textures, allowing more sprites to be drawn in a batch.
+/// Packing is deferred to `pack_pending`, atlases are never modified after creation.
+#[derive(Default)]
+pub struct TextureAtlasSet {
+    state: RefCell<AtlasState>,
+}
+
+impl TextureAtlasSet {
+    /// Returns a copy of the sprite drawing from an atlas, or None if the texture isn't packed yet
+    pub fn remap(&self, sprite: &Sprite) -> Option<Sprite> {
+        let texture = sprite.texture();
+        let state = &mut *self.state.borrow_mut();
+
+        let entry = match state.entries.entry(Arc::as_ptr(texture)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let size = texture.size();
+
+                if size.x > MAX_REGION_SIZE || size.y > MAX_REGION_SIZE {
+                    entry.insert(AtlasEntry::Ignored)
+                } else {
+                    state.pending_count += 1;
+                    entry.insert(AtlasEntry::Pending(texture.clone()))
+                }
+            }
+        };
+
+        let AtlasEntry::Packed {
+            atlas_index,
+            offset,
+            ..
+        } = *entry
+        else {
+            return None;
+        };
+
+        // frames outside of the texture would sample neighbors
+        let mut frame = sprite.frame();
+        let texture_size = texture.size().as_vec2();
+
+        if frame.x < 0.0
+            || frame.y < 0.0
+            || frame.x + frame.width > texture_size.x
+            || frame.y + frame.height > texture_size.y
+        {
+            return None;
+        }
+
+        frame.x += offset.x;
+        frame.y += offset.y;
+
+        let size = sprite.size();
+        let mut sprite = sprite.clone();
+        sprite.set_texture(state.atlases[atlas_index].clone());
+        sprite.set_frame(frame);
+        sprite.set_size(size);
+
+        Some(sprite)
+    }
+
+    /// Packs textures seen since the last call into new atlases
+    pub fn pack_pending(&self, game_io: &GameIO) {
+        let mut state = self.state.borrow_mut();
+
+        if state.pending_count < MIN_BATCH_SIZE {
+            return;
+        }
+
+        state.pending_count = 0;
+
+        let mut pending: Vec<Arc<Texture>> = state
+            .entries
+            .values()
+            .filter_map(|entry| match entry {
+                AtlasEntry::Pending(texture) => Some(texture.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // tallest first keeps shelves tight
+        pending.sort_by_key(|texture| std::cmp::Reverse(texture.size().y));
+
+        let mut pending = pending.into_iter().peekable();
+
+        while pending.peek().is_some() {
+            let mut packer = AtlasPacker::new(ATLAS_SIZE, ATLAS_SIZE, REGION_PADDING);
+            let mut placements = Vec::new();
+
+            while let Some(texture) = pending.peek() {
+                let Some(position) = packer.insert(texture.size()) else {
+                    break;
+                };
+
+                placements.push((pending.next().unwrap(), position.as_vec2()));
+            }
+
+            if placements.is_empty() {
+                break;
+            }
+
+            let atlas_index = state.atlases.len();
+            let atlas = Self::render_atlas(game_io, packer.used_size(), &placements);
+            state.atlases.push(atlas);
+
+            for (texture, offset) in placements {
+                state.entries.insert(
+                    Arc::as_ptr(&texture),
+                    AtlasEntry::Packed {
+                        _texture: texture,
+                        atlas_index,
+                        offset,
+                    },
+                );
+            }
+        }
+
+        log::debug!("Packed textures into {} atlases", state.atlases.len());
+    }
+
+    fn render_atlas(
+        game_io: &GameIO,
+        size: UVec2,
+        placements: &[(Arc<Texture>, Vec2)],
+    ) -> Arc<Texture> {
+        let target = RenderTarget::new(game_io, size);
+
+        let mut camera = Camera::new_ui(game_io);
+        camera.snap(size.as_vec2() * 0.5);
+        camera.set_scale(RESOLUTION_F / size.as_vec2());
+
+        let device = game_io.graphics().device();
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("texture_atlas_command_encoder"),
+        });
+
+        let mut render_pass = RenderPass::new(&mut encoder, &target);
+        let mut sprite_queue = SpriteColorQueue::new(game_io, &camera, SpriteColorMode::Multiply);
+
+        for (texture, offset) in placements {
+            let mut sprite = Sprite::new(game_io, texture.clone());
+            sprite.set_position(*offset);
+            sprite_queue.draw_sprite(&sprite);
+        }
+
+        render_pass.consume_queue(sprite_queue);
+        render_pass.flush();
+
+        let queue = game_io.graphics().queue();
+        queue.submit([encoder.finish()]);
+
+        target.texture().clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn packer_fills_shelves() {
+        let mut packer = AtlasPacker::new(10, 10, 0);
+
+        assert_eq!(packer.insert(UVec2::new(6, 4)), Some(UVec2::new(0, 0)));
+        assert_eq!(packer.insert(UVec2::new(4, 2)), Some(UVec2::new(6, 0)));
+        // wraps to the next shelf, below the tallest region
+        assert_eq!(packer.insert(UVec2::new(5, 5)), Some(UVec2::new(0, 4)));
+        assert_eq!(packer.used_size(), UVec2::new(10, 9));
+
+        // no room left
+        assert_eq!(packer.insert(UVec2::new(6, 2)), None);
+    }
+
+    #[test]
+    fn packer_padding() {
+        let mut packer = AtlasPacker::new(8, 8, 1);
+
+        assert_eq!(packer.insert(UVec2::new(3, 3)), Some(UVec2::new(0, 0)));
+        assert_eq!(packer.insert(UVec2::new(3, 3)), Some(UVec2::new(4, 0)));
+        assert_eq!(packer.insert(UVec2::new(3, 3)), Some(UVec2::new(0, 4)));
+
+        // too wide once padded
+        assert_eq!(packer.insert(UVec2::new(8, 1)), None);
+    }
+}
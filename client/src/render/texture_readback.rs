@@ -0,0 +1,128 @@
+use framework::{prelude::*, wgpu};
+
+/// Copies a texture into a mappable buffer, the texture should be rendered by an earlier submission
+pub struct TextureReadback {
+    buffer: wgpu::Buffer,
+    size: UVec2,
+    padded_row_len: u32,
+    swap_red_blue: bool,
+    receiver: flume::Receiver<bool>,
+    mapped: Option<bool>,
+}
+
+impl TextureReadback {
+    pub fn new(game_io: &GameIO, texture: &Texture) -> Self {
+        let size = texture.size();
+        let padded_row_len = (size.x * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let device = game_io.graphics().device();
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("texture_readback_buffer"),
+            size: (padded_row_len * size.y) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("texture_readback_command_encoder"),
+        });
+
+        let raw_texture = texture.texture();
+
+        encoder.copy_texture_to_buffer(
+            raw_texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_len),
+                    rows_per_image: Some(size.y),
+                },
+            },
+            wgpu::Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        game_io.graphics().queue().submit([encoder.finish()]);
+
+        let (sender, receiver) = flume::bounded(1);
+
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                if let Err(e) = &result {
+                    log::error!("Failed to read texture: {e}");
+                }
+
+                let _ = sender.send(result.is_ok());
+            });
+
+        let swap_red_blue = matches!(
+            raw_texture.format(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+
+        Self {
+            buffer,
+            size,
+            padded_row_len,
+            swap_red_blue,
+            receiver,
+            mapped: None,
+        }
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Checks for completion without blocking, completion requires the device to be polled
+    pub fn is_finished(&mut self) -> bool {
+        if self.mapped.is_none() {
+            self.mapped = self.receiver.try_recv().ok();
+        }
+
+        self.mapped.is_some()
+    }
+
+    /// Blocks until the copy completes
+    pub fn wait(mut self, game_io: &GameIO) -> Option<Vec<u8>> {
+        if self.mapped.is_none() {
+            game_io.graphics().device().poll(wgpu::Maintain::Wait);
+            self.mapped = self.receiver.recv().ok();
+        }
+
+        self.read()
+    }
+
+    /// RGBA pixels with rows from top to bottom, None if the copy is unfinished or failed
+    pub fn read(self) -> Option<Vec<u8>> {
+        if self.mapped != Some(true) {
+            return None;
+        }
+
+        let row_len = (self.size.x * 4) as usize;
+        let slice = self.buffer.slice(..);
+        let mapped_range = slice.get_mapped_range();
+
+        let mut rgba = Vec::with_capacity(row_len * self.size.y as usize);
+
+        for row in mapped_range.chunks_exact(self.padded_row_len as usize) {
+            rgba.extend(&row[..row_len]);
+        }
+
+        drop(mapped_range);
+        self.buffer.unmap();
+
+        if self.swap_red_blue {
+            for pixel in rgba.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Some(rgba)
+    }
+}
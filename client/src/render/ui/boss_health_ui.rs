@@ -0,0 +1,142 @@
+use super::{FontName, TextStyle};
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::{Color, GameIO, Rect, Sprite, Vec2};
+
+const BAR_SIZE: Vec2 = Vec2::new(96.0, 4.0);
+const PHASE_PIP_SIZE: f32 = 3.0;
+const PHASE_PIP_SPACING: f32 = 2.0;
+const BACKGROUND_COLOR: Color = Color::new(0.1, 0.1, 0.15, 1.0);
+const HEALTH_COLOR: Color = Color::new(0.85, 0.2, 0.3, 1.0);
+const DRAIN_COLOR: Color = Color::new(1.0, 0.85, 0.6, 1.0);
+
+#[derive(Clone)]
+pub struct BossHealthUi {
+    name: String,
+    health: i32,
+    max_health: i32,
+    displayed_progress: f32,
+    phase: usize,
+    phase_count: usize,
+    sprite: Sprite,
+}
+
+impl BossHealthUi {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        Self {
+            name: String::new(),
+            health: 0,
+            max_health: 0,
+            displayed_progress: 0.0,
+            phase: 0,
+            phase_count: 1,
+            sprite: (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn max_health(&self) -> i32 {
+        self.max_health
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_health(&mut self, health: i32, max_health: i32) {
+        self.health = health;
+        self.max_health = max_health;
+    }
+
+    pub fn set_phase(&mut self, phase: usize, phase_count: usize) {
+        if phase != self.phase {
+            // refill for the next segment
+            self.displayed_progress = 0.0;
+        }
+
+        self.phase = phase;
+        self.phase_count = phase_count.max(1);
+    }
+
+    fn progress(&self) -> f32 {
+        if self.max_health <= 0 {
+            0.0
+        } else {
+            (self.health as f32 / self.max_health as f32).clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn update(&mut self) {
+        const RATE: f32 = 0.02;
+
+        let progress = self.progress();
+
+        if self.displayed_progress < progress {
+            self.displayed_progress = (self.displayed_progress + RATE).min(progress);
+        } else {
+            self.displayed_progress = (self.displayed_progress - RATE).max(progress);
+        }
+    }
+
+    pub fn draw(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        let bar_position = Vec2::new(
+            (RESOLUTION_F.x - BAR_SIZE.x) * 0.5,
+            RESOLUTION_F.y - BAR_SIZE.y - BATTLE_UI_MARGIN * 2.0,
+        );
+
+        // name
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        let text_size = text_style.measure(&self.name).size;
+        text_style.bounds.set_position(Vec2::new(
+            bar_position.x,
+            bar_position.y - text_size.y - BATTLE_UI_MARGIN,
+        ));
+        text_style.draw(game_io, sprite_queue, &self.name);
+
+        // remaining phases, drawn right aligned above the bar
+        let remaining_phases = self.phase_count.saturating_sub(self.phase + 1);
+        let mut pip_position = Vec2::new(
+            bar_position.x + BAR_SIZE.x - PHASE_PIP_SIZE,
+            bar_position.y - PHASE_PIP_SIZE - BATTLE_UI_MARGIN,
+        );
+
+        for _ in 0..remaining_phases {
+            self.draw_rect(
+                sprite_queue,
+                Rect::new(
+                    pip_position.x,
+                    pip_position.y,
+                    PHASE_PIP_SIZE,
+                    PHASE_PIP_SIZE,
+                ),
+                HEALTH_COLOR,
+            );
+            pip_position.x -= PHASE_PIP_SIZE + PHASE_PIP_SPACING;
+        }
+
+        // bar
+        let bar_rect = Rect::new(bar_position.x, bar_position.y, BAR_SIZE.x, BAR_SIZE.y);
+        self.draw_rect(sprite_queue, bar_rect, BACKGROUND_COLOR);
+
+        let mut drain_rect = bar_rect;
+        drain_rect.width *= self.displayed_progress.max(self.progress());
+        self.draw_rect(sprite_queue, drain_rect, DRAIN_COLOR);
+
+        let mut health_rect = bar_rect;
+        health_rect.width *= self.displayed_progress.min(self.progress());
+        self.draw_rect(sprite_queue, health_rect, HEALTH_COLOR);
+    }
+
+    fn draw_rect(&mut self, sprite_queue: &mut SpriteColorQueue, rect: Rect, color: Color) {
+        self.sprite.set_bounds(rect);
+        self.sprite.set_color(color);
+        sprite_queue.draw_sprite(&self.sprite);
+    }
+}
@@ -2,7 +2,6 @@ use crate::render::ui::*;
 use crate::render::*;
 use crate::{bindable::GenerationalIndex, resources::*};
 use framework::prelude::*;
-use std::collections::VecDeque;
 
 pub struct ContextMenu<T: Copy + 'static> {
     ui_layout: UiLayout,
@@ -13,7 +12,7 @@ pub struct ContextMenu<T: Copy + 'static> {
     body_index: GenerationalIndex,
     arrow_sprite: Sprite,
     arrow_visible: bool,
-    first_option_index: Option<GenerationalIndex>,
+    option_indices: Vec<GenerationalIndex>,
     open: bool,
     phantom_data: std::marker::PhantomData<T>,
 }
@@ -92,7 +91,7 @@ impl<T: Copy + 'static> ContextMenu<T> {
             arrow_sprite,
             arrow_visible: false,
             body_index: body_index.unwrap(),
-            first_option_index: None,
+            option_indices: Vec::new(),
             open: false,
             phantom_data: Default::default(),
         }
@@ -115,8 +114,8 @@ impl<T: Copy + 'static> ContextMenu<T> {
     pub fn open(&mut self) {
         self.open = true;
 
-        if let Some(index) = self.first_option_index {
-            self.ui_layout.set_focused_index(Some(index));
+        if let Some(index) = self.option_indices.first() {
+            self.ui_layout.set_focused_index(Some(*index));
         }
     }
 
@@ -134,9 +133,12 @@ impl<T: Copy + 'static> ContextMenu<T> {
             ..Default::default()
         };
 
-        let mut nodes: VecDeque<_> = options
+        let mut option_indices = vec![None; options.len()];
+
+        let nodes: Vec<_> = options
             .iter()
-            .map(|(label, option)| {
+            .zip(option_indices.iter_mut())
+            .map(|((label, option), handle)| {
                 let sender = self.ui_sender.clone();
                 let option = *option;
 
@@ -151,18 +153,30 @@ impl<T: Copy + 'static> ContextMenu<T> {
                     }),
                 )
                 .with_style(option_style.clone())
+                .with_handle(handle)
             })
             .collect();
 
-        if let Some(first_node) = nodes.pop_front() {
-            let node = first_node.with_handle(&mut self.first_option_index);
-            nodes.push_front(node);
+        self.ui_layout.set_children(self.body_index, nodes);
+        self.option_indices = option_indices.into_iter().flatten().collect();
+
+        if let Some(index) = self.option_indices.first() {
+            self.ui_layout.set_focused_index(Some(*index));
         }
+    }
 
-        self.ui_layout.set_children(self.body_index, nodes.into());
+    /// The position of the focused option in the options list
+    pub fn focused_option(&self) -> Option<usize> {
+        let focused_index = self.ui_layout.focused_index()?;
+
+        self.option_indices
+            .iter()
+            .position(|index| *index == focused_index)
+    }
 
-        if let Some(index) = self.first_option_index {
-            self.ui_layout.set_focused_index(Some(index));
+    pub fn focus_option(&mut self, option: usize) {
+        if let Some(index) = self.option_indices.get(option) {
+            self.ui_layout.set_focused_index(Some(*index));
         }
     }
 
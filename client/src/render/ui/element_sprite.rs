@@ -11,9 +11,13 @@ impl ElementSprite {
         let globals = game_io.resource::<Globals>().unwrap();
         let assets = &globals.assets;
 
+        if let Some(definition) = globals.element_definition(element) {
+            return assets.new_sprite(game_io, &definition.icon_texture_path);
+        }
+
         let mut sprite = assets.new_sprite(game_io, ResourcePaths::ELEMENTS);
 
-        sprite.set_frame(Rect::new((element as u8 as f32) * 14.0, 0.0, 14.0, 14.0));
+        sprite.set_frame(Self::built_in_frame(element));
 
         sprite
     }
@@ -24,11 +28,25 @@ impl ElementSprite {
 
         let mut sprite_node = SpriteNode::new(game_io, SpriteColorMode::Multiply);
 
+        if let Some(definition) = globals.element_definition(element) {
+            let texture = assets.texture(game_io, &definition.icon_texture_path);
+            sprite_node.set_texture_direct(texture);
+
+            return sprite_node;
+        }
+
         let texture = assets.texture(game_io, ResourcePaths::ELEMENTS);
         sprite_node.set_texture_direct(texture);
 
-        sprite_node.set_frame(Rect::new((element as u8 as f32) * 14.0, 0.0, 14.0, 14.0));
+        sprite_node.set_frame(Self::built_in_frame(element));
 
         sprite_node
     }
+
+    // undefined custom elements display as Element::None
+    fn built_in_frame(element: Element) -> Rect {
+        let index = element.built_in_index().unwrap_or_default();
+
+        Rect::new((index as f32) * 14.0, 0.0, 14.0, 14.0)
+    }
 }
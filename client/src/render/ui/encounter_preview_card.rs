@@ -0,0 +1,153 @@
+use super::{build_9patch, FontName, NinePatch, TextStyle};
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+use packets::structures::EncounterPreview;
+
+const MARGIN: f32 = 24.0;
+const PADDING: f32 = 6.0;
+const ENEMY_SPACING: f32 = 8.0;
+
+struct PreviewEnemy {
+    name: String,
+    sprite: Option<Sprite>,
+}
+
+pub struct EncounterPreviewCard {
+    frame: NinePatch,
+    enemies: Vec<PreviewEnemy>,
+    stakes: String,
+    declinable: bool,
+}
+
+impl EncounterPreviewCard {
+    /// `assets` is used to load enemy sprites
+    pub fn new(game_io: &GameIO, assets: &impl AssetManager, preview: &EncounterPreview) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let ui_texture = globals
+            .assets
+            .texture(game_io, ResourcePaths::UI_NINE_PATCHES);
+        let ui_animator =
+            Animator::load_new(&globals.assets, ResourcePaths::UI_NINE_PATCHES_ANIMATION);
+
+        let enemies = preview
+            .enemies
+            .iter()
+            .map(|enemy| {
+                let sprite = enemy.sprite.as_ref().map(|pair| {
+                    let mut sprite = assets.new_sprite(game_io, &pair.texture);
+                    let mut animator = Animator::load_new(assets, &pair.animation);
+                    animator.set_state("IDLE");
+                    animator.apply(&mut sprite);
+                    sprite
+                });
+
+                PreviewEnemy {
+                    name: enemy.name.clone(),
+                    sprite,
+                }
+            })
+            .collect();
+
+        Self {
+            frame: build_9patch!(game_io, ui_texture, &ui_animator, "FRAME"),
+            enemies,
+            stakes: preview.stakes.clone(),
+            declinable: preview.declinable,
+        }
+    }
+
+    pub fn declinable(&self) -> bool {
+        self.declinable
+    }
+
+    pub fn draw(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+        text_style.color = Color::WHITE;
+
+        let line_height = text_style.line_height();
+
+        let sprite_height = self
+            .enemies
+            .iter()
+            .flat_map(|enemy| enemy.sprite.as_ref())
+            .map(|sprite| sprite.size().y)
+            .fold(0.0, f32::max);
+
+        // enemies, names, stakes, and the hint
+        let mut content_height = sprite_height + line_height;
+
+        if !self.stakes.is_empty() {
+            content_height += line_height + PADDING;
+        }
+
+        if self.declinable {
+            content_height += line_height + PADDING;
+        }
+
+        let width = RESOLUTION_F.x - MARGIN * 2.0;
+        let height = content_height + PADDING * 2.0;
+        let frame_bounds = Rect::new(MARGIN, (RESOLUTION_F.y - height) * 0.5, width, height);
+        self.frame.draw(sprite_queue, frame_bounds);
+
+        let content_bounds = self.frame.body_bounds(frame_bounds);
+        let center_x = content_bounds.center().x;
+        let mut y = content_bounds.y + PADDING;
+
+        // enemies are laid out side by side, sharing a baseline
+        let column_width = self
+            .enemies
+            .iter()
+            .map(|enemy| {
+                let name_width = text_style.measure(&enemy.name).size.x;
+                let sprite_width = enemy.sprite.as_ref().map(|s| s.size().x).unwrap_or(0.0);
+                name_width.max(sprite_width)
+            })
+            .fold(0.0, f32::max);
+
+        let row_width = (column_width + ENEMY_SPACING) * self.enemies.len() as f32 - ENEMY_SPACING;
+        let mut x = center_x - row_width.max(0.0) * 0.5;
+
+        for enemy in &mut self.enemies {
+            let column_center = x + column_width * 0.5;
+
+            if let Some(sprite) = &mut enemy.sprite {
+                let size = sprite.size();
+                let origin = sprite.origin();
+                sprite.set_position(Vec2::new(
+                    column_center - size.x * 0.5 + origin.x,
+                    y + sprite_height - size.y + origin.y,
+                ));
+                sprite_queue.draw_sprite(sprite);
+            }
+
+            let name_width = text_style.measure(&enemy.name).size.x;
+            text_style.bounds.x = column_center - name_width * 0.5;
+            text_style.bounds.y = y + sprite_height;
+            text_style.draw(game_io, sprite_queue, &enemy.name);
+
+            x += column_width + ENEMY_SPACING;
+        }
+
+        y += sprite_height + line_height + PADDING;
+
+        if !self.stakes.is_empty() {
+            text_style.color = Color::YELLOW;
+            text_style.bounds.x = center_x - text_style.measure(&self.stakes).size.x * 0.5;
+            text_style.bounds.y = y;
+            text_style.draw(game_io, sprite_queue, &self.stakes);
+
+            y += line_height + PADDING;
+        }
+
+        if self.declinable {
+            const HINT: &str = "CONFIRM: FIGHT  CANCEL: BACK OUT";
+
+            text_style.color = Color::WHITE;
+            text_style.bounds.x = center_x - text_style.measure(HINT).size.x * 0.5;
+            text_style.bounds.y = y;
+            text_style.draw(game_io, sprite_queue, HINT);
+        }
+    }
+}
@@ -9,6 +9,10 @@ use std::sync::Arc;
 
 const SPLIT_PATTERN: &str = "_U+";
 
+static THIN_FALLBACKS: [FontName; 2] = [FontName::Thin, FontName::Fallback];
+static THICK_FALLBACKS: [FontName; 2] = [FontName::Thick, FontName::Fallback];
+static DEFAULT_FALLBACKS: [FontName; 1] = [FontName::Fallback];
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FontName {
     Thick,
@@ -25,6 +29,8 @@ pub enum FontName {
     Result,
     Battle,
     EntityHP,
+    /// Used for characters missing from other fonts, language packs can provide CJK glyphs here
+    Fallback,
     External(Arc<str>),
 }
 
@@ -47,10 +53,23 @@ impl FontName {
             "RESULT" => FontName::Result,
             "BATTLE" => FontName::Battle,
             "ENTITY_HP" => FontName::EntityHP,
+            "FALLBACK" => FontName::Fallback,
             _ => FontName::External(uppercase_name.into()),
         }
     }
 
+    /// Fonts searched in order for characters missing from this font
+    pub fn fallbacks(&self) -> &'static [FontName] {
+        match self {
+            FontName::ThinSmall | FontName::Micro | FontName::Context | FontName::Code => {
+                &THIN_FALLBACKS
+            }
+            FontName::MenuTitle | FontName::Battle | FontName::Result => &THICK_FALLBACKS,
+            FontName::Fallback => &[],
+            _ => &DEFAULT_FALLBACKS,
+        }
+    }
+
     pub fn from_state_prefix(state_prefix: &str) -> Option<Self> {
         let font_name = match state_prefix {
             "THICK_U+" => FontName::Thick,
@@ -67,6 +86,7 @@ impl FontName {
             "RESULT_U+" => FontName::Result,
             "BATTLE_U+" => FontName::Battle,
             "ENTITY_HP_U+" => FontName::EntityHP,
+            "FALLBACK_U+" => FontName::Fallback,
             _ => {
                 let name_end = state_prefix.rfind(SPLIT_PATTERN)?;
                 let name = &state_prefix[0..name_end];
@@ -180,11 +200,24 @@ impl GlyphAtlas {
             .collect()
     }
 
+    /// Searches the font's fallbacks for characters missing from the font
     pub fn character_frame<'a>(
         &'a self,
         font: &'a FontName,
         character: &'a str,
-    ) -> Option<&AnimationFrame> {
+    ) -> Option<&'a AnimationFrame> {
+        self.font_character_frame(font, character).or_else(|| {
+            font.fallbacks()
+                .iter()
+                .find_map(|font| self.font_character_frame(font, character))
+        })
+    }
+
+    fn font_character_frame<'a>(
+        &'a self,
+        font: &'a FontName,
+        character: &'a str,
+    ) -> Option<&'a AnimationFrame> {
         self.map
             .get(&(Cow::Borrowed(font), Cow::Borrowed(character)))
     }
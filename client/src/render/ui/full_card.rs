@@ -9,6 +9,8 @@ use std::collections::HashMap;
 use uncased::{Uncased, UncasedStr};
 
 const MAX_ART_TIME: FrameTime = 5;
+/// Preview art is loaded once a card stays selected for this long, icons are displayed until then
+const ART_LOAD_DELAY: FrameTime = 10;
 
 pub struct FullCard {
     card_sprite: Sprite,
@@ -22,6 +24,8 @@ pub struct FullCard {
     preview_position: Vec2,
     previous_card: Option<Card>,
     current_card: Option<Card>,
+    previous_art_loaded: bool,
+    art_loaded: bool,
     description_style: TextStyle,
     art_time: FrameTime,
     flipped: bool,
@@ -66,7 +70,7 @@ impl FullCard {
         let preview_point = card_animator.point("PREVIEW").unwrap_or_default();
 
         // description_style
-        let mut description_style = TextStyle::new(game_io, FontName::Thin);
+        let mut description_style = TextStyle::new_unscaled(game_io, FontName::Thin);
         description_style.line_spacing = 4.0;
         description_style.color = Color::BLACK;
         description_style.shadow_color = TEXT_TRANSPARENT_SHADOW_COLOR;
@@ -92,6 +96,8 @@ impl FullCard {
             preview_position: position + preview_point,
             previous_card: None,
             current_card: None,
+            previous_art_loaded: false,
+            art_loaded: false,
             description_style,
             art_time: 0,
             flipped: false,
@@ -119,6 +125,8 @@ impl FullCard {
     pub fn set_card(&mut self, card: Option<Card>) {
         self.previous_card = self.current_card.take();
         self.current_card = card;
+        self.previous_art_loaded = self.art_loaded;
+        self.art_loaded = false;
         self.art_time = 0;
     }
 
@@ -135,9 +143,14 @@ impl FullCard {
 
         let art_progress = (self.art_time as f32 / MAX_ART_TIME as f32).clamp(0.0, 1.0);
 
-        let card = match art_progress > 0.5 {
-            true => self.current_card.as_ref(),
-            false => self.previous_card.as_ref(),
+        // avoid loading art for every card passed while scrolling or while the art isn't visible
+        if !self.flipped && self.art_time >= ART_LOAD_DELAY {
+            self.art_loaded = true;
+        }
+
+        let (card, art_loaded) = match art_progress > 0.5 {
+            true => (self.current_card.as_ref(), self.art_loaded),
+            false => (self.previous_card.as_ref(), self.previous_art_loaded),
         };
 
         let package = card.as_ref().and_then(|card| package(game_io, card));
@@ -234,7 +247,14 @@ impl FullCard {
             if let Some(card) = card {
                 let scale = (0.5 - art_progress).abs() * 2.0;
 
-                card.draw_preview(game_io, sprite_queue, self.preview_position, scale);
+                let (art_texture, _) = if art_loaded {
+                    CardPackage::preview_texture(game_io, &card.package_id)
+                } else {
+                    CardPackage::icon_texture(game_io, PackageNamespace::Local, &card.package_id)
+                };
+
+                let position = self.preview_position;
+                card.draw_preview_with_art(game_io, sprite_queue, position, scale, art_texture);
             }
 
             // draw description
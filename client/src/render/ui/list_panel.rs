@@ -0,0 +1,143 @@
+use super::{ScrollTracker, ScrollableFrame, UiInputTracker};
+use crate::render::SpriteColorQueue;
+use crate::resources::{Globals, RESOLUTION_F};
+use framework::prelude::{GameIO, Rect, Vec2};
+
+const DETAILS_MARGIN: f32 = 8.0;
+const LINE_HEIGHT: f32 = 16.0;
+
+/// A labeled list of single line rows, details for the selected row are drawn to the right
+pub struct ListPanel {
+    frame: ScrollableFrame,
+    scroll_tracker: ScrollTracker,
+    header_lines: usize,
+    line_height: f32,
+    cursor_enabled: bool,
+}
+
+impl ListPanel {
+    /// Sits at the left of the screen, use a width of `RESOLUTION_F.x - 16.0` for lists without details
+    pub fn new(game_io: &GameIO, label: &str, width: f32) -> Self {
+        // rows grow with the text scale, fewer rows fit in view
+        let globals = game_io.resource::<Globals>().unwrap();
+        let line_height = LINE_HEIGHT * globals.config.text_scale();
+
+        let bounds = Rect::new(8.0, 20.0, width, RESOLUTION_F.y - 28.0);
+        let frame = ScrollableFrame::new(game_io, bounds).with_label_str(label);
+        let body_bounds = frame.body_bounds();
+
+        let view_size = (body_bounds.height / line_height) as usize;
+        let mut scroll_tracker = ScrollTracker::new(game_io, view_size);
+        let cursor_start = body_bounds.top_left() + Vec2::new(-7.0, 2.0);
+        scroll_tracker.define_cursor(cursor_start, line_height);
+        scroll_tracker.define_scrollbar(frame.scroll_start(), frame.scroll_end());
+
+        Self {
+            frame,
+            scroll_tracker,
+            header_lines: 0,
+            line_height,
+            cursor_enabled: true,
+        }
+    }
+
+    /// Reserves lines above the rows, drawn by the scene starting at `header_start()`
+    pub fn with_header_lines(mut self, lines: usize) -> Self {
+        let view_size = self.scroll_tracker.view_size();
+        self.scroll_tracker
+            .set_view_size(view_size.saturating_sub(lines));
+        self.header_lines = lines;
+        self
+    }
+
+    /// For lists that are only scrolled through, rows can't be selected
+    pub fn with_cursor(mut self, enabled: bool) -> Self {
+        self.cursor_enabled = enabled;
+        self
+    }
+
+    pub fn set_label(&mut self, label: String) {
+        self.frame.set_label(label);
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    pub fn body_bounds(&self) -> Rect {
+        self.frame.body_bounds()
+    }
+
+    /// The area to the right of the list, below the frame label
+    pub fn details_bounds(&self) -> Rect {
+        let bounds = self.frame.bounds();
+        let left = bounds.right() + DETAILS_MARGIN;
+
+        Rect::new(
+            left,
+            bounds.y + DETAILS_MARGIN,
+            RESOLUTION_F.x - left - DETAILS_MARGIN,
+            bounds.height - DETAILS_MARGIN * 2.0,
+        )
+    }
+
+    pub fn header_start(&self) -> Vec2 {
+        self.frame.body_bounds().top_left() + Vec2::new(3.0, 3.0)
+    }
+
+    pub fn scroll_tracker_mut(&mut self) -> &mut ScrollTracker {
+        &mut self.scroll_tracker
+    }
+
+    pub fn set_total_items(&mut self, count: usize) {
+        self.scroll_tracker.set_total_items(count);
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.scroll_tracker.selected_index()
+    }
+
+    pub fn set_selected_index(&mut self, index: usize) {
+        self.scroll_tracker.set_selected_index(index);
+    }
+
+    /// Returns true if the selection moved
+    pub fn handle_input(&mut self, game_io: &GameIO, ui_input_tracker: &UiInputTracker) -> bool {
+        let prev_index = self.scroll_tracker.selected_index();
+
+        self.scroll_tracker.handle_vertical_input(ui_input_tracker);
+
+        if prev_index == self.scroll_tracker.selected_index() {
+            return false;
+        }
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.play_sound(&globals.sfx.cursor_move);
+
+        true
+    }
+
+    /// Calls `draw_row` with the index and position of every row in view
+    pub fn draw(
+        &mut self,
+        game_io: &GameIO,
+        sprite_queue: &mut SpriteColorQueue,
+        mut draw_row: impl FnMut(&mut SpriteColorQueue, usize, Vec2),
+    ) {
+        self.frame.draw(game_io, sprite_queue);
+
+        let mut position = self.header_start();
+        position.y += self.header_lines as f32 * self.line_height;
+
+        for index in self.scroll_tracker.view_range() {
+            draw_row(sprite_queue, index, position);
+            position.y += self.line_height;
+        }
+
+        if self.cursor_enabled && self.scroll_tracker.total_items() > 0 {
+            self.scroll_tracker.draw_cursor(sprite_queue);
+        }
+
+        self.scroll_tracker.draw_scrollbar(sprite_queue);
+    }
+}
@@ -1,13 +1,16 @@
 mod block_preview;
+mod boss_health_ui;
 mod card_select_ui;
 mod clock;
 mod context_menu;
 mod element_sprite;
+mod encounter_preview_card;
 mod font;
 mod full_card;
 mod grid_arrow;
 mod grid_cursor;
 mod grid_scroll_tracker;
+mod list_panel;
 mod log_box;
 mod navigation_menu;
 mod nine_patch;
@@ -25,8 +28,10 @@ mod scrollable_list;
 mod sub_scene_frame;
 mod text;
 mod text_input;
+mod text_shaping;
 mod text_style;
 mod textbox;
+mod textbox_backlog;
 mod textbox_character_navigation;
 mod textbox_cursor;
 mod textbox_doorstop;
@@ -44,15 +49,18 @@ mod ui_input_tracker;
 mod ui_layout;
 
 pub use block_preview::*;
+pub use boss_health_ui::*;
 pub use card_select_ui::*;
 pub use clock::*;
 pub use context_menu::*;
 pub use element_sprite::*;
+pub use encounter_preview_card::*;
 pub use font::*;
 pub use full_card::*;
 pub use grid_arrow::*;
 pub use grid_cursor::*;
 pub use grid_scroll_tracker::*;
+pub use list_panel::*;
 pub use log_box::*;
 pub use navigation_menu::*;
 pub use nine_patch::*;
@@ -72,6 +80,7 @@ pub use text::*;
 pub use text_input::*;
 pub use text_style::*;
 pub use textbox::*;
+pub use textbox_backlog::*;
 pub use textbox_character_navigation::*;
 pub use textbox_cursor::*;
 pub use textbox_doorstop::*;
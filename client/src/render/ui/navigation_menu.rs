@@ -39,6 +39,20 @@ impl OpenState {
 }
 
 impl SceneOption {
+    /// Key for the name read by screen readers
+    fn name_key(&self) -> &'static str {
+        match self {
+            SceneOption::Servers => "navigation.servers",
+            SceneOption::Decks => "navigation.decks",
+            SceneOption::Items => "navigation.items",
+            SceneOption::Library => "navigation.library",
+            SceneOption::Character => "navigation.character",
+            SceneOption::KeyItems => "navigation.key_items",
+            SceneOption::BattleSelect => "navigation.battle_select",
+            SceneOption::Config => "navigation.config",
+        }
+    }
+
     fn state(&self) -> &'static str {
         match self {
             SceneOption::Servers => "SERVERS_LABEL",
@@ -261,6 +275,10 @@ impl NavigationMenu {
         if prev_index != self.scroll_tracker.selected_index() {
             let globals = game_io.resource::<Globals>().unwrap();
             globals.audio.play_sound(&globals.sfx.cursor_move);
+
+            let selected_option = self.items[self.scroll_tracker.selected_index()].target_scene;
+            let name = globals.translate(selected_option.name_key());
+            globals.screen_reader.announce(&name);
         }
 
         if self.ui_input_tracker.is_active(Input::Confirm) {
@@ -88,6 +88,7 @@ impl From<&json::Value> for PackageListing {
             "resource" => PackagePreviewData::Resource,
             "status" => PackagePreviewData::Status,
             "tile_state" => PackagePreviewData::TileState,
+            "emote" => PackagePreviewData::Emote,
             _ => PackagePreviewData::Unknown,
         };
 
@@ -32,6 +32,7 @@ pub enum PackagePreviewData {
     Status,
     Resource,
     TileState,
+    Emote,
     Unknown,
 }
 
@@ -52,6 +53,7 @@ impl PackagePreviewData {
             PackagePreviewData::Resource => Some(PackageCategory::Resource),
             PackagePreviewData::Status => Some(PackageCategory::Status),
             PackagePreviewData::TileState => Some(PackageCategory::TileState),
+            PackagePreviewData::Emote => Some(PackageCategory::Emote),
             _ => None,
         }
     }
@@ -200,7 +202,7 @@ impl PackagePreview {
                 }
 
                 // damage
-                let mut text = Text::new(game_io, FontName::Thick);
+                let mut text = Text::new_unscaled(game_io, FontName::Thick);
                 text.text = format!("{damage}");
 
                 let text_anchor = animator.point("DAMAGE_END").unwrap_or_default();
@@ -232,7 +234,7 @@ impl PackagePreview {
                 self.sprites.push(health_sprite);
 
                 // health
-                let mut text = Text::new(game_io, FontName::Thick);
+                let mut text = Text::new_unscaled(game_io, FontName::Thick);
                 text.text = format!("{health}");
 
                 let text_anchor =
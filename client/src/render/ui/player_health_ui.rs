@@ -24,12 +24,16 @@ impl PlayerHealthUi {
         animator.set_state("DEFAULT");
         let text_offset = animator.point("TEXT_START").unwrap_or_default();
 
+        // sized to fit the health frame
+        let mut text = Text::new_unscaled(game_io, FontName::PlayerHp);
+        text.style.monospace = true;
+
         let mut health_ui = Self {
             max_health: 0,
             current_health: 0,
             target_health: 0,
             style_change_cooldown: 0,
-            text: Text::new_monospace(game_io, FontName::PlayerHp),
+            text,
             text_offset,
             frame_sprite: assets.new_sprite(game_io, ResourcePaths::HEALTH_FRAME),
         };
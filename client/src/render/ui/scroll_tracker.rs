@@ -156,6 +156,16 @@ impl ScrollTracker {
         self.cursor_multiplier = multiplier;
     }
 
+    /// For rows of text, `line_height` is scaled by the text scale setting and fewer rows fit in view
+    pub fn define_text_cursor(&mut self, game_io: &GameIO, start: Vec2, line_height: f32) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let text_scale = globals.config.text_scale();
+
+        let view_size = (self.view_size as f32 / text_scale) as usize;
+        self.set_view_size(view_size.max(1));
+        self.define_cursor(start, line_height * text_scale);
+    }
+
     pub fn set_vertical(&mut self, vertical: bool) {
         self.vertical = vertical;
     }
@@ -100,7 +100,7 @@ impl ScrollableFrame {
 
             let label_position = self.label_sprite.position() + self.label_text_offset;
 
-            let mut text_style = TextStyle::new(game_io, FontName::Micro);
+            let mut text_style = TextStyle::new_unscaled(game_io, FontName::Micro);
             text_style.bounds.set_position(label_position);
             text_style.draw(game_io, sprite_queue, text);
         }
@@ -8,10 +8,15 @@ pub struct ScrollableList {
     scroll_tracker: ScrollTracker,
     children: Vec<Box<dyn UiNode>>,
     focused: bool,
+    announced_label: Option<String>,
 }
 
 impl ScrollableList {
+    /// `item_height` is scaled to fit text using the configured text scale
     pub fn new(game_io: &GameIO, bounds: Rect, item_height: f32) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let item_height = item_height * globals.config.text_scale();
+
         let frame = ScrollableFrame::new(game_io, bounds);
         let inner_bounds = frame.body_bounds();
 
@@ -25,6 +30,7 @@ impl ScrollableList {
             scroll_tracker,
             children: Vec::new(),
             focused: true,
+            announced_label: None,
         }
     }
 
@@ -171,6 +177,28 @@ impl ScrollableList {
 
             child_bounds.y += child_bounds.height;
         }
+
+        self.announce_selection(game_io);
+    }
+
+    /// Announces the selected child if its label changed since the last announcement
+    fn announce_selection(&mut self, game_io: &GameIO) {
+        let label = self
+            .children
+            .get(self.scroll_tracker.selected_index())
+            .filter(|_| self.focused)
+            .and_then(|child| child.accessibility_label());
+
+        if label == self.announced_label {
+            return;
+        }
+
+        if let Some(label) = &label {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.screen_reader.announce(label);
+        }
+
+        self.announced_label = label;
     }
 
     pub fn draw(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
@@ -16,6 +16,14 @@ impl Text {
         }
     }
 
+    /// See `TextStyle::new_unscaled()`
+    pub fn new_unscaled(game_io: &GameIO, font: FontName) -> Self {
+        Self {
+            text: String::new(),
+            style: TextStyle::new_unscaled(game_io, font),
+        }
+    }
+
     pub fn new_monospace(game_io: &GameIO, font: FontName) -> Self {
         let mut text = Self::new(game_io, font);
         text.style.monospace = true;
@@ -71,6 +79,10 @@ impl UiNode for Text {
         self.measure().size
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        Some(self.text.clone())
+    }
+
     fn draw_bounded(
         &mut self,
         game_io: &GameIO,
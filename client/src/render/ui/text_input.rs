@@ -21,6 +21,7 @@ pub struct TextInput {
     sizing_dirty: bool,
     filter_callback: Box<dyn Fn(&str) -> bool>,
     change_callback: Box<dyn Fn(&str)>,
+    edit_callback: Box<dyn Fn(&str)>,
 }
 
 impl TextInput {
@@ -47,6 +48,7 @@ impl TextInput {
             sizing_dirty: false,
             filter_callback: Box::new(|_| true),
             change_callback: Box::new(|_| {}),
+            edit_callback: Box::new(|_| {}),
         }
     }
 
@@ -92,6 +94,12 @@ impl TextInput {
         self
     }
 
+    /// Called after every edit while typing, `on_change` is only called when input ends
+    pub fn on_edit(mut self, callback: impl Fn(&str) + 'static) -> Self {
+        self.edit_callback = Box::new(callback);
+        self
+    }
+
     pub fn with_paged(mut self, page_size: Vec2) -> Self {
         self.paged = true;
         self.lines_per_page = (page_size.y / self.text_style.line_height()) as usize;
@@ -197,6 +205,7 @@ impl UiNode for TextInput {
         if !incoming_text.is_empty() {
             // update text
             self.insert_text(game_io, &incoming_text, holding_ctrl);
+            (self.edit_callback)(&self.text);
         }
 
         if self.caret_index != old_caret_index {
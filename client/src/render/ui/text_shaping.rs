@@ -0,0 +1,159 @@
+use std::ops::Range;
+use unicode_bidi::{BidiClass, BidiInfo};
+
+/// Characters that can't start a line, such as closing brackets and small kana
+const NO_LINE_START: &str = ")]}.,!?:;%、。，．・：；？！ー～…‥）」』】〕〉》｝］〗〙〛ゝゞヽヾ々ぁぃぅぇぉっゃゅょゎゕゖァィゥェォッャュョヮヵヶㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ";
+
+/// Characters that can't end a line, such as opening brackets
+const NO_LINE_END: &str = "([{（「『【〔〈《｛［〖〘〚";
+
+/// Arabic letters with the first of their presentation forms, in the order isolated, final, initial, medial.
+/// Letters that only join to the right only have isolated and final forms
+const ARABIC_FORMS: [(char, u32, bool); 35] = [
+    ('\u{0622}', 0xFE81, false),
+    ('\u{0623}', 0xFE83, false),
+    ('\u{0624}', 0xFE85, false),
+    ('\u{0625}', 0xFE87, false),
+    ('\u{0626}', 0xFE89, true),
+    ('\u{0627}', 0xFE8D, false),
+    ('\u{0628}', 0xFE8F, true),
+    ('\u{0629}', 0xFE93, false),
+    ('\u{062A}', 0xFE95, true),
+    ('\u{062B}', 0xFE99, true),
+    ('\u{062C}', 0xFE9D, true),
+    ('\u{062D}', 0xFEA1, true),
+    ('\u{062E}', 0xFEA5, true),
+    ('\u{062F}', 0xFEA9, false),
+    ('\u{0630}', 0xFEAB, false),
+    ('\u{0631}', 0xFEAD, false),
+    ('\u{0632}', 0xFEAF, false),
+    ('\u{0633}', 0xFEB1, true),
+    ('\u{0634}', 0xFEB5, true),
+    ('\u{0635}', 0xFEB9, true),
+    ('\u{0636}', 0xFEBD, true),
+    ('\u{0637}', 0xFEC1, true),
+    ('\u{0638}', 0xFEC5, true),
+    ('\u{0639}', 0xFEC9, true),
+    ('\u{063A}', 0xFECD, true),
+    ('\u{0641}', 0xFED1, true),
+    ('\u{0642}', 0xFED5, true),
+    ('\u{0643}', 0xFED9, true),
+    ('\u{0644}', 0xFEDD, true),
+    ('\u{0645}', 0xFEE1, true),
+    ('\u{0646}', 0xFEE5, true),
+    ('\u{0647}', 0xFEE9, true),
+    ('\u{0648}', 0xFEED, false),
+    ('\u{0649}', 0xFEEF, false),
+    ('\u{064A}', 0xFEF1, true),
+];
+
+/// Ideographs, kana, and full width forms, which can wrap between any two characters
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2E80}'..='\u{2FDF}'
+            | '\u{3000}'..='\u{31FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FF00}'..='\u{FFEF}'
+            | '\u{20000}'..='\u{3134F}'
+    )
+}
+
+pub fn is_rtl(c: char) -> bool {
+    matches!(unicode_bidi::bidi_class(c), BidiClass::R | BidiClass::AL)
+}
+
+/// Allows wrapping between CJK characters, while keeping punctuation attached to the text it belongs to
+pub fn allows_line_break(previous: &str, next: &str) -> bool {
+    let (Some(previous), Some(next)) = (previous.chars().next_back(), next.chars().next()) else {
+        return false;
+    };
+
+    (is_cjk(previous) || is_cjk(next))
+        && !NO_LINE_START.contains(next)
+        && !NO_LINE_END.contains(previous)
+}
+
+fn arabic_joining(c: char) -> Option<(u32, bool)> {
+    ARABIC_FORMS
+        .iter()
+        .find(|(letter, ..)| *letter == c)
+        .map(|&(_, first_form, dual_joining)| (first_form, dual_joining))
+}
+
+/// The presentation form for an Arabic letter based on the letters around it in logical order
+pub fn arabic_form(previous: Option<char>, c: char, next: Option<char>) -> Option<char> {
+    let (first_form, dual_joining) = arabic_joining(c)?;
+
+    let joins_previous = previous
+        .and_then(arabic_joining)
+        .is_some_and(|(_, dual_joining)| dual_joining);
+    let joins_next = dual_joining && next.and_then(arabic_joining).is_some();
+
+    let offset = match (joins_previous, joins_next) {
+        (false, false) => 0,
+        (true, false) => 1,
+        (false, true) => 2,
+        (true, true) => 3,
+    };
+
+    char::from_u32(first_form + offset)
+}
+
+/// Byte ranges of a single line in left to right display order, paired with whether the range is right to left
+pub fn visual_runs(line: &str) -> Vec<(Range<usize>, bool)> {
+    let bidi_info = BidiInfo::new(line, None);
+
+    let Some(paragraph) = bidi_info.paragraphs.first() else {
+        return Vec::new();
+    };
+
+    let (levels, runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+
+    runs.into_iter()
+        .map(|run| {
+            let rtl = levels[run.start].is_rtl();
+            (run, rtl)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_punctuation_with_cjk_text() {
+        assert!(allows_line_break("漢", "字"));
+        assert!(allows_line_break("A", "字"));
+        assert!(!allows_line_break("字", "。"));
+        assert!(!allows_line_break("「", "仮"));
+        assert!(!allows_line_break("A", "B"));
+    }
+
+    #[test]
+    fn selects_arabic_forms() {
+        // beh, beh, alef, beh
+        assert_eq!(arabic_form(None, 'ب', Some('ب')), Some('\u{FE91}'));
+        assert_eq!(arabic_form(Some('ب'), 'ب', Some('ا')), Some('\u{FE92}'));
+        assert_eq!(arabic_form(Some('ب'), 'ا', Some('ب')), Some('\u{FE8E}'));
+        // alef doesn't join the next letter
+        assert_eq!(arabic_form(Some('ا'), 'ب', None), Some('\u{FE8F}'));
+        assert_eq!(arabic_form(None, 'A', None), None);
+    }
+
+    #[test]
+    fn orders_runs_for_display() {
+        let line = "hi שלום";
+        let runs = visual_runs(line);
+
+        assert_eq!(runs, [(0..3, false), (3..line.len(), true)]);
+
+        let line = "שלום hi";
+        let runs = visual_runs(line);
+
+        assert_eq!(runs, [(9..line.len(), false), (0..9, true)]);
+    }
+}
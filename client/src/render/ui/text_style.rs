@@ -1,3 +1,4 @@
+use super::text_shaping::*;
 use super::FontName;
 use super::GlyphAtlas;
 use crate::bindable::SpriteColorMode;
@@ -37,9 +38,19 @@ pub struct TextStyle {
 }
 
 impl TextStyle {
+    /// Applies the text scale from the config, use `new_unscaled()` for text over fixed size art
     pub fn new(game_io: &GameIO, font: FontName) -> Self {
         let globals = game_io.resource::<Globals>().unwrap();
 
+        let mut style = Self::new_with_atlas(globals.glyph_atlas.clone(), font);
+        style.scale = Vec2::splat(globals.config.text_scale());
+        style
+    }
+
+    /// Ignores the text scale setting, for text placed over art with a fixed size
+    pub fn new_unscaled(game_io: &GameIO, font: FontName) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
         Self::new_with_atlas(globals.glyph_atlas.clone(), font)
     }
 
@@ -111,6 +122,11 @@ impl TextStyle {
         self
     }
 
+    pub fn with_monospace(mut self, monospace: bool) -> Self {
+        self.monospace = monospace;
+        self
+    }
+
     pub fn with_line_spacing(mut self, line_spacing: f32) -> Self {
         self.line_spacing = line_spacing;
         self
@@ -125,6 +141,17 @@ impl TextStyle {
         self.iterate(text, |_, _| {})
     }
 
+    /// Lowers the scale until `name` and `value` fit on one line within `width`,
+    /// text is never shrunk below its unscaled size
+    pub fn fit_row(&mut self, width: f32, name: &str, value: &str) {
+        let used_width = self.measure(name).size.x + self.measure(value).size.x + 1.0;
+
+        if used_width > width && self.scale.x > 1.0 {
+            let scale = (self.scale.x * width / used_width).max(1.0);
+            self.scale = Vec2::splat(scale);
+        }
+    }
+
     pub fn draw(&self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue, text: &str) {
         self.draw_slice(game_io, sprite_queue, text, 0..text.len());
     }
@@ -186,6 +213,10 @@ impl TextStyle {
     where
         F: FnMut(AnimationFrame, Vec2),
     {
+        // glyphs are held until the line is complete, to allow reordering right to left text
+        let mut line_glyphs = Vec::new();
+        let mut line_y = 0.0;
+
         let mut insert_tracker = TextInsertTracker::new(self);
         insert_tracker.line_start_index = range.start;
 
@@ -224,6 +255,8 @@ impl TextStyle {
                     break;
                 }
 
+                let mut frame = None;
+
                 match character {
                     " " => {
                         insert_tracker.insert_space(index);
@@ -235,14 +268,14 @@ impl TextStyle {
                         insert_tracker.new_line(index, 1);
                     }
                     _ => {
-                        let frame = self.character_frame(character);
+                        let character_frame = self.shaped_character_frame(text, index, character);
 
-                        if !frame.valid {
+                        if !character_frame.valid {
                             continue;
                         }
 
-                        let character_size = frame.size();
-                        let (x, y) = insert_tracker.next_position(index, character_size).into();
+                        let character_size = character_frame.size();
+                        let position = insert_tracker.next_position(index, character_size);
 
                         // break early if we can't place this character
                         let line_bottom = insert_tracker.y + insert_tracker.whitespace.y;
@@ -251,12 +284,22 @@ impl TextStyle {
                             break 'primary;
                         }
 
-                        let position = self.bounds.position() + Vec2::new(x, y) * self.scale;
-
-                        callback(frame, position);
+                        max_y = max_y.max(position.y + character_size.y);
+                        frame = Some((character_frame, position));
+                    }
+                }
 
-                        max_y = max_y.max(y + character_size.y);
+                if character != "\n" && character != "\r\n" {
+                    if insert_tracker.y != line_y {
+                        self.place_line(text, &mut line_glyphs, &mut callback);
+                        line_y = insert_tracker.y;
                     }
+
+                    line_glyphs.push(PlacedGlyph {
+                        frame,
+                        range: index..index + character.len(),
+                        slot: insert_tracker.slot.clone(),
+                    });
                 }
 
                 // break early if whitespace caused us to go out of bounds
@@ -272,6 +315,8 @@ impl TextStyle {
             }
         }
 
+        self.place_line(text, &mut line_glyphs, &mut callback);
+
         if max_x > 0.0 {
             // trim off extra spacing
             max_x -= self.letter_spacing;
@@ -290,6 +335,58 @@ impl TextStyle {
         }
     }
 
+    /// Reorders right to left runs in the line before passing the glyphs to the callback
+    fn place_line<F>(&self, text: &str, glyphs: &mut Vec<PlacedGlyph>, callback: &mut F)
+    where
+        F: FnMut(AnimationFrame, Vec2),
+    {
+        let (Some(first), Some(last)) = (glyphs.first(), glyphs.last()) else {
+            return;
+        };
+
+        let line_range = first.range.start..last.range.end;
+        let line_text = &text[line_range.clone()];
+
+        if line_text.chars().any(is_rtl) {
+            let mut ordered_glyphs = Vec::with_capacity(glyphs.len());
+
+            for (run, rtl) in visual_runs(line_text) {
+                let run = run.start + line_range.start..run.end + line_range.start;
+                let run_glyphs = glyphs
+                    .iter()
+                    .filter(|glyph| run.contains(&glyph.range.start));
+
+                if rtl {
+                    ordered_glyphs.extend(run_glyphs.rev().cloned());
+                } else {
+                    ordered_glyphs.extend(run_glyphs.cloned());
+                }
+            }
+
+            // lay the slots out again in display order
+            let mut x = first.slot.start;
+
+            for glyph in &mut ordered_glyphs {
+                let slot_width = glyph.slot.end - glyph.slot.start;
+
+                if let Some((_, position)) = &mut glyph.frame {
+                    position.x += x - glyph.slot.start;
+                }
+
+                glyph.slot = x..x + slot_width;
+                x += slot_width;
+            }
+
+            *glyphs = ordered_glyphs;
+        }
+
+        for glyph in glyphs.drain(..) {
+            if let Some((frame, position)) = glyph.frame {
+                callback(frame, self.bounds.position() + position * self.scale);
+            }
+        }
+    }
+
     fn measure_word(&self, word: &str) -> f32 {
         let whitespace_size = self.glyph_atlas.resolve_whitespace_size(&self.font);
 
@@ -304,8 +401,8 @@ impl TextStyle {
                     let total_chars = word.graphemes(true).count() as f32;
                     width += total_chars * (whitespace_size.x + self.letter_spacing);
                 } else {
-                    for character in word.graphemes(true) {
-                        let frame = self.character_frame(character);
+                    for (index, character) in word.grapheme_indices(true) {
+                        let frame = self.shaped_character_frame(word, index, character);
                         let glyph_width = frame.size().x;
                         width += glyph_width.max(self.min_glyph_width) + self.letter_spacing;
                     }
@@ -331,6 +428,36 @@ impl TextStyle {
             .cloned()
             .unwrap_or_default()
     }
+
+    /// Uses the contextual form for Arabic letters when the atlas provides it
+    fn shaped_character_frame(&self, text: &str, index: usize, character: &str) -> AnimationFrame {
+        let mut chars = character.chars();
+
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            let previous = text[..index].chars().next_back();
+            let next = text[index + character.len()..].chars().next();
+
+            if let Some(form) = arabic_form(previous, c, next) {
+                let mut buffer = [0; 4];
+                let form = form.encode_utf8(&mut buffer);
+
+                if let Some(frame) = self.glyph_atlas.character_frame(&self.font, form) {
+                    return frame.clone();
+                }
+            }
+        }
+
+        self.character_frame(character)
+    }
+}
+
+#[derive(Clone)]
+struct PlacedGlyph {
+    /// None for whitespace
+    frame: Option<(AnimationFrame, Vec2)>,
+    range: Range<usize>,
+    /// Horizontal space taken on the line, including letter spacing
+    slot: Range<f32>,
 }
 
 struct TextInsertTracker<'a> {
@@ -340,6 +467,8 @@ struct TextInsertTracker<'a> {
     style: &'a TextStyle,
     line_start_index: usize,
     line_ranges: Vec<Range<usize>>,
+    /// Horizontal space taken by the last placed character
+    slot: Range<f32>,
 }
 
 impl<'a> TextInsertTracker<'a> {
@@ -351,6 +480,7 @@ impl<'a> TextInsertTracker<'a> {
             style,
             line_start_index: 0,
             line_ranges: Vec::new(),
+            slot: 0.0..0.0,
         }
     }
 
@@ -371,7 +501,9 @@ impl<'a> TextInsertTracker<'a> {
         // center the character for monospace
         let placement_x = self.x + ((width_used - character_size.x) * 0.5).floor();
 
+        let slot_start = self.x;
         self.x += width_used + self.style.letter_spacing;
+        self.slot = slot_start..self.x;
 
         let offset_y = (self.whitespace.y - character_size.y).max(0.0);
 
@@ -395,30 +527,32 @@ impl<'a> TextInsertTracker<'a> {
     }
 }
 
+/// Splits on whitespace, CJK text is split between characters where line breaks are allowed
 fn word_indices(text: &str) -> impl Iterator<Item = (usize, &str)> {
-    use itertools::Itertools;
+    let is_whitespace = |grapheme: &str| matches!(grapheme, "\n" | "\t" | " ");
+    let mut graphemes = text.grapheme_indices(true).peekable();
 
-    text.grapheme_indices(true).peekable().batching(|it| {
-        let mut count = 0;
-        let mut space_found = false;
+    std::iter::from_fn(move || {
+        let (first_index, first_grapheme) = graphemes.next()?;
+        let mut end_index = first_index + first_grapheme.len();
 
-        let mut word_it = it.peeking_take_while(move |(_, grapheme)| {
-            count += 1;
+        if is_whitespace(first_grapheme) {
+            return Some((first_index, first_grapheme));
+        }
 
-            match *grapheme {
-                "\n" | "\t" | " " => {
-                    space_found = true;
+        let mut previous_grapheme = first_grapheme;
 
-                    count == 1
-                }
-                _ => !space_found,
+        while let Some(&(index, grapheme)) = graphemes.peek() {
+            if is_whitespace(grapheme) || allows_line_break(previous_grapheme, grapheme) {
+                break;
             }
-        });
 
-        let (first_index, grapheme) = word_it.next()?;
-        let (last_index, grapheme) = word_it.last().unwrap_or((first_index, grapheme));
+            graphemes.next();
+            end_index = index + grapheme.len();
+            previous_grapheme = grapheme;
+        }
 
-        Some((first_index, &text[first_index..last_index + grapheme.len()]))
+        Some((first_index, &text[first_index..end_index]))
     })
 }
 
@@ -442,5 +576,9 @@ mod test {
         for (output, expected) in word_indices("A BC  DEF G").zip(expected) {
             assert_eq!(output, expected);
         }
+
+        let expected = [(0, "漢"), (3, "字。"), (9, "「仮"), (15, "名」")];
+
+        assert!(word_indices("漢字。「仮名」").eq(expected));
     }
 }
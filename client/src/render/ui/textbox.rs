@@ -1,4 +1,4 @@
-use super::{FontName, TextStyle};
+use super::{FontName, TextStyle, TextboxBacklog};
 use crate::bindable::SpriteColorMode;
 use crate::render::*;
 use crate::resources::*;
@@ -14,6 +14,11 @@ const FAST_CHAR_DELAY: FrameTime = 1;
 const DRAMATIC_CHAR_DELAY: FrameTime = 40;
 
 const AUTO_PAGE_DELAY: FrameTime = 10;
+/// Used for completed pages when auto advance is enabled in the config
+const AUTO_ADVANCE_TEXT_DELAY: FrameTime = 90;
+
+/// How long Info is held before the backlog is displayed
+const BACKLOG_HOLD_DELAY: FrameTime = 15;
 
 pub trait TextboxInterface {
     /// This should not change after the first call
@@ -67,6 +72,11 @@ pub struct Textbox {
     text_style: TextStyle,
     transition_animation_enabled: bool,
     text_animation_enabled: bool,
+    ducks_music: bool,
+    /// Dialogue is recorded in the backlog and can auto advance
+    is_dialogue: bool,
+    backlog_hold_time: FrameTime,
+    backlog: Option<TextboxBacklog>,
     effect_processor: TextboxEffectProcessor,
 }
 
@@ -118,6 +128,10 @@ impl Textbox {
                 .with_line_spacing(3.0),
             transition_animation_enabled: true,
             text_animation_enabled: true,
+            ducks_music: false,
+            is_dialogue: false,
+            backlog_hold_time: 0,
+            backlog: None,
             effect_processor: TextboxEffectProcessor::new(),
         };
 
@@ -135,12 +149,17 @@ impl Textbox {
         textbox
     }
 
+    /// Dialogue textbox, ducks music while open and records messages to the backlog
     pub fn new_overworld(game_io: &GameIO) -> Self {
-        Self::new(
+        let mut textbox = Self::new(
             game_io,
             ResourcePaths::OVERWORLD_TEXTBOX,
             ResourcePaths::OVERWORLD_TEXTBOX_ANIMATION,
-        )
+        );
+
+        textbox.ducks_music = true;
+        textbox.is_dialogue = true;
+        textbox
     }
 
     pub fn with_position(mut self, position: Vec2) -> Self {
@@ -315,6 +334,11 @@ impl Textbox {
             return;
         }
 
+        if self.ducks_music {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.request_music_ducking();
+        }
+
         // update container animation
 
         self.animator.update();
@@ -341,6 +365,31 @@ impl Textbox {
             return;
         }
 
+        // hold info to review recent dialogue
+        if self.is_dialogue && self.accept_input {
+            let input_util = InputUtil::new(game_io);
+
+            if input_util.is_down(Input::Info) {
+                self.backlog_hold_time += 1;
+            } else {
+                self.backlog_hold_time = 0;
+                self.backlog = None;
+            }
+
+            if self.backlog_hold_time >= BACKLOG_HOLD_DELAY && self.backlog.is_none() {
+                self.backlog = Some(TextboxBacklog::new(game_io));
+            }
+
+            if let Some(backlog) = &mut self.backlog {
+                backlog.update(game_io);
+                return;
+            }
+        }
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let idle_char_delay = globals.config.text_char_delay as FrameTime;
+        let auto_advance_text = self.is_dialogue && globals.config.auto_advance_text;
+
         // update text
         let input_util = InputUtil::new(game_io);
         let pressed_advance = self.accept_input && input_util.was_just_pressed(Input::Confirm);
@@ -360,13 +409,13 @@ impl Textbox {
         let is_last_page = self.page_queue.len() == 1;
         let page_completed = self.text_index == page.range.end;
 
-        if !self.text_animation_enabled && !page_completed {
+        if (!self.text_animation_enabled || idle_char_delay == 0) && !page_completed {
             self.skip_animation(game_io);
         } else if !page_completed {
             // try advancing character
             self.char_time += 1;
 
-            if self.char_time >= self.effect_processor.char_delay {
+            if self.char_time >= self.effect_processor.resolve_char_delay(idle_char_delay) {
                 let text = match self.interface_queue.front() {
                     Some((interface, _)) => interface.text(),
                     None => "",
@@ -386,7 +435,8 @@ impl Textbox {
             // try advancing page
             self.char_time += 1;
 
-            let can_auto_advance = auto_advance && self.char_time > AUTO_PAGE_DELAY;
+            let can_auto_advance = (auto_advance && self.char_time > AUTO_PAGE_DELAY)
+                || (auto_advance_text && self.char_time > AUTO_ADVANCE_TEXT_DELAY);
 
             if (!is_last_page || completes_with_input) && (pressed_advance || can_auto_advance) {
                 self.advance_page(game_io);
@@ -498,6 +548,11 @@ impl Textbox {
         if let Some((mut interface, _)) = self.interface_queue.pop_front() {
             interface.handle_completed();
 
+            if self.is_dialogue {
+                let globals = game_io.resource::<Globals>().unwrap();
+                globals.dialogue_backlog.record(interface.text());
+            }
+
             if let Some((_, _, count)) = self.avatar_queue.front_mut() {
                 *count = count.saturating_sub(1);
 
@@ -561,6 +616,11 @@ impl Textbox {
 
         sprite_queue.set_color_mode(SpriteColorMode::Multiply);
 
+        if let Some(backlog) = &mut self.backlog {
+            backlog.draw(game_io, sprite_queue);
+            return;
+        }
+
         // render container
         self.animator.apply(&mut self.sprite);
         sprite_queue.draw_sprite(&self.sprite);
@@ -631,6 +691,15 @@ impl TextboxEffectProcessor {
         *self = Self::new()
     }
 
+    /// The idle speed is replaced by the config's speed, fast text is never slower than idle text
+    fn resolve_char_delay(&self, idle_char_delay: FrameTime) -> FrameTime {
+        match self.char_delay {
+            IDLE_CHAR_DELAY => idle_char_delay,
+            FAST_CHAR_DELAY => FAST_CHAR_DELAY.min(idle_char_delay),
+            char_delay => char_delay,
+        }
+    }
+
     fn process_char(&mut self, char: char) -> bool {
         let mut new_speed = 0;
 
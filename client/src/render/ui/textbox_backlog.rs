@@ -0,0 +1,81 @@
+use super::{FontName, TextStyle};
+use crate::render::SpriteColorQueue;
+use crate::resources::*;
+use framework::prelude::*;
+
+const BACKDROP_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.8);
+const MARGIN: f32 = 8.0;
+const MESSAGE_SPACING: f32 = 6.0;
+
+/// Recent dialogue listed over the screen, the newest message is at the bottom
+pub struct TextboxBacklog {
+    backdrop: Sprite,
+    text_style: TextStyle,
+    messages: Vec<String>,
+    /// Messages hidden below the bottom of the screen
+    scroll: usize,
+}
+
+impl TextboxBacklog {
+    pub fn new(game_io: &GameIO) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        let mut backdrop = (globals.assets).new_sprite(game_io, ResourcePaths::WHITE_PIXEL);
+        backdrop.set_size(RESOLUTION_F);
+        backdrop.set_color(BACKDROP_COLOR);
+
+        let text_style = TextStyle::new(game_io, FontName::Thin)
+            .with_shadow_color(TEXT_DARK_SHADOW_COLOR)
+            .with_bounds(Rect::new(
+                MARGIN,
+                MARGIN,
+                RESOLUTION_F.x - MARGIN * 2.0,
+                f32::INFINITY,
+            ));
+
+        Self {
+            backdrop,
+            text_style,
+            messages: globals.dialogue_backlog.messages(),
+            scroll: 0,
+        }
+    }
+
+    pub fn update(&mut self, game_io: &GameIO) {
+        let input_util = InputUtil::new(game_io);
+        let previous_scroll = self.scroll;
+
+        if input_util.was_just_pressed(Input::Up) && self.scroll + 1 < self.messages.len() {
+            self.scroll += 1;
+        }
+
+        if input_util.was_just_pressed(Input::Down) {
+            self.scroll = self.scroll.saturating_sub(1);
+        }
+
+        if self.scroll != previous_scroll {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+    }
+
+    pub fn draw(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        sprite_queue.draw_sprite(&self.backdrop);
+
+        let mut bottom = RESOLUTION_F.y - MARGIN;
+
+        for (i, message) in self.messages.iter().rev().skip(self.scroll).enumerate() {
+            let top = bottom - self.text_style.measure(message).size.y;
+
+            // always display the bottom message, even if it's too tall to fit
+            if top < MARGIN && i > 0 {
+                break;
+            }
+
+            self.text_style.bounds.y = top;
+            self.text_style.draw(game_io, sprite_queue, message);
+
+            bottom = top - MESSAGE_SPACING;
+        }
+    }
+}
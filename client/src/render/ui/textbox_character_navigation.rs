@@ -4,7 +4,12 @@ use crate::resources::*;
 use framework::prelude::GameIO;
 use framework::prelude::*;
 
-const TOTAL_OPTIONS: usize = 3;
+const TOTAL_OPTIONS: usize = 4;
+/// Options past the first column are listed in a second column,
+/// left and right are left for page turning, so up and down move between columns
+const COLUMN_LEN: usize = 3;
+/// Text before the second column, used to place the cursor
+const FIRST_COLUMN_TEXT: &str = "  Blocks  ";
 
 pub struct TextboxCharacterNavigation {
     selection: usize,
@@ -59,7 +64,7 @@ impl TextboxCharacterNavigation {
 
 impl TextboxInterface for TextboxCharacterNavigation {
     fn text(&self) -> &str {
-        "\x02  Blocks\n  Drives\n  Switch"
+        "\x02  Blocks    Loadouts\n  Drives\n  Switch"
     }
 
     fn is_complete(&self) -> bool {
@@ -77,13 +82,15 @@ impl TextboxInterface for TextboxCharacterNavigation {
 
         let cursor = self.cursor.as_mut().unwrap();
 
-        let cursor_line = self.selection as f32;
+        let cursor_line = (self.selection % COLUMN_LEN) as f32;
+        let mut cursor_x = text_style.measure("  ").size.x;
+
+        if self.selection >= COLUMN_LEN {
+            cursor_x += text_style.measure(FIRST_COLUMN_TEXT).size.x;
+        }
 
         let line_height = text_style.line_height();
-        let relative_position = Vec2::new(
-            text_style.measure("  ").size.x,
-            line_height * 0.5 + line_height * cursor_line,
-        );
+        let relative_position = Vec2::new(cursor_x, line_height * 0.5 + line_height * cursor_line);
 
         cursor.set_position(text_style.bounds.position() + relative_position);
         cursor.update();
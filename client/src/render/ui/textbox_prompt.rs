@@ -10,6 +10,7 @@ pub struct TextboxPrompt {
     text_sender: flume::Sender<String>,
     text_receiver: flume::Receiver<String>,
     filter: Option<Box<dyn Fn(&str) -> bool>>,
+    edit_callback: Option<Box<dyn Fn(&str)>>,
     callback: Option<Box<dyn FnOnce(String)>>,
 }
 
@@ -25,6 +26,7 @@ impl TextboxPrompt {
             text_sender,
             text_receiver,
             filter: None,
+            edit_callback: None,
             callback: Some(Box::new(callback)),
         }
     }
@@ -43,6 +45,12 @@ impl TextboxPrompt {
         self.filter = Some(Box::new(callback));
         self
     }
+
+    /// Called after every edit, before the prompt is complete
+    pub fn on_edit(mut self, callback: impl Fn(&str) + 'static) -> Self {
+        self.edit_callback = Some(Box::new(callback));
+        self
+    }
 }
 
 impl TextboxInterface for TextboxPrompt {
@@ -77,6 +85,10 @@ impl TextboxInterface for TextboxPrompt {
                 text_input = text_input.with_filter(filter);
             }
 
+            if let Some(edit_callback) = self.edit_callback.take() {
+                text_input = text_input.on_edit(edit_callback);
+            }
+
             if let Some(character_limit) = self.character_limit {
                 text_input = text_input.with_character_limit(character_limit);
             }
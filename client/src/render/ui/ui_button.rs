@@ -77,6 +77,10 @@ where
         self.content.measure_ui_size(game_io)
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        self.content.accessibility_label()
+    }
+
     fn draw_bounded(
         &mut self,
         game_io: &GameIO,
@@ -190,18 +190,19 @@ impl UiNode for UiConfigBinding {
             text_style.color = Color::ORANGE;
         }
 
-        // draw input name
-        let text: &'static str = match self.input {
+        let name: &'static str = match self.input {
             Input::AdvanceFrame => "AdvFrame",
             Input::RewindFrame => "RewFrame",
             input => input.into(),
         };
 
-        text_style.draw(game_io, sprite_queue, text);
-
-        // draw binding
         let text = self.displayed_text();
+        text_style.fit_row(bounds.width, name, text);
+
+        // draw input name
+        text_style.draw(game_io, sprite_queue, name);
 
+        // draw binding
         let metrics = text_style.measure(text);
         text_style.bounds.x += bounds.width - metrics.size.x - 1.0;
 
@@ -212,6 +213,17 @@ impl UiNode for UiConfigBinding {
         Vec2::ZERO
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        let name: &'static str = self.input.into();
+        let bound_text = if self.binding {
+            "waiting for input"
+        } else {
+            self.bound_text.as_str()
+        };
+
+        Some(format!("{name}, {bound_text}"))
+    }
+
     fn focusable(&self) -> bool {
         true
     }
@@ -49,11 +49,13 @@ impl<T: Copy> UiNode for UiConfigCycle<T> {
         text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
         text_style.bounds.set_position(bounds.position());
 
+        let text = &self.options[self.selection].0;
+        text_style.fit_row(bounds.width, self.name, text);
+
         // draw name
         text_style.draw(game_io, sprite_queue, self.name);
 
         // draw value
-        let text = &self.options[self.selection].0;
         let metrics = text_style.measure(text);
         text_style.bounds.x += bounds.width - metrics.size.x - 1.0;
 
@@ -64,6 +66,11 @@ impl<T: Copy> UiNode for UiConfigCycle<T> {
         Vec2::ZERO
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        let text = &self.options[self.selection].0;
+        Some(format!("{}, {text}", self.name))
+    }
+
     fn update(&mut self, game_io: &mut GameIO, _bounds: Rect, focused: bool) {
         if !focused {
             return;
@@ -69,13 +69,14 @@ impl<T: PartialEq> UiNode for UiConfigDynamicCycle<T> {
         text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
         text_style.bounds.set_position(bounds.position());
 
+        let range = self.text_scroller.text_range(&self.value_text);
+        let text = &self.value_text[range];
+        text_style.fit_row(bounds.width, self.name, text);
+
         // draw name
         text_style.draw(game_io, sprite_queue, self.name);
 
         // draw value
-        let range = self.text_scroller.text_range(&self.value_text);
-        let text = &self.value_text[range];
-
         let metrics = text_style.measure(text);
         text_style.bounds.x += bounds.width - metrics.size.x - 1.0;
 
@@ -86,6 +87,10 @@ impl<T: PartialEq> UiNode for UiConfigDynamicCycle<T> {
         Vec2::ZERO
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        Some(format!("{}, {}", self.name, self.value_text))
+    }
+
     fn update(&mut self, game_io: &mut GameIO, _bounds: Rect, focused: bool) {
         if !focused {
             self.text_scroller.reset();
@@ -71,6 +71,8 @@ impl UiNode for UiConfigPercentage {
         text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
         text_style.bounds.set_position(bounds.position());
 
+        text_style.fit_row(bounds.width, self.name, &self.value_text);
+
         // draw name
         text_style.draw(game_io, sprite_queue, self.name);
 
@@ -85,6 +87,10 @@ impl UiNode for UiConfigPercentage {
         Vec2::ZERO
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        Some(format!("{}, {}", self.name, self.value_text))
+    }
+
     fn is_locking_focus(&self) -> bool {
         self.locking_focus
     }
@@ -40,11 +40,13 @@ impl UiNode for UiConfigToggle {
         text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
         text_style.bounds.set_position(bounds.position());
 
+        let text = if self.value { "true" } else { "false" };
+        text_style.fit_row(bounds.width, self.name, text);
+
         // draw name
         text_style.draw(game_io, sprite_queue, self.name);
 
         // draw value
-        let text = if self.value { "true" } else { "false" };
         let metrics = text_style.measure(text);
         text_style.bounds.x += bounds.width - metrics.size.x - 1.0;
 
@@ -55,6 +57,11 @@ impl UiNode for UiConfigToggle {
         Vec2::ZERO
     }
 
+    fn accessibility_label(&self) -> Option<String> {
+        let state = if self.value { "on" } else { "off" };
+        Some(format!("{}, {state}", self.name))
+    }
+
     fn update(&mut self, game_io: &mut GameIO, _bounds: Rect, focused: bool) {
         if !focused {
             return;
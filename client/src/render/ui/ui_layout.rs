@@ -31,6 +31,11 @@ pub trait UiNode {
     fn ui_size_dirty(&self) -> bool {
         false
     }
+
+    /// Text read by screen readers when this node is focused
+    fn accessibility_label(&self) -> Option<String> {
+        None
+    }
 }
 
 impl UiNode for () {
@@ -163,6 +168,7 @@ pub struct UiLayout {
     focused: bool,
     focused_index: Option<GenerationalIndex>,
     wrap_selection: bool,
+    announced_label: Option<String>,
 }
 
 impl UiLayout {
@@ -191,6 +197,7 @@ impl UiLayout {
             focused: true,
             focused_index: None,
             wrap_selection: false,
+            announced_label: None,
         };
 
         ui_layout.set_children(ui_layout.tree.root_index(), root_nodes);
@@ -399,6 +406,28 @@ impl UiLayout {
                 globals.audio.play_sound(&globals.sfx.cursor_move);
             }
         }
+
+        self.announce_focus(game_io);
+    }
+
+    /// Announces the focused node if its label changed since the last announcement
+    fn announce_focus(&mut self, game_io: &GameIO) {
+        let label = self
+            .focused_index
+            .filter(|_| self.focused)
+            .and_then(|index| self.tree.get_node(index))
+            .and_then(|node| node.value().content.accessibility_label());
+
+        if label == self.announced_label {
+            return;
+        }
+
+        if let Some(label) = &label {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.screen_reader.announce(label);
+        }
+
+        self.announced_label = label;
     }
 
     pub fn is_focus_locked(&self) -> bool {
@@ -0,0 +1,214 @@
+use super::SoundBuffer;
+use framework::prelude::Texture;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+// shared between caches to allow comparing access times across caches
+static ACCESS_CLOCK: AtomicU64 = AtomicU64::new(0);
+
+fn next_tick() -> u64 {
+    ACCESS_CLOCK.fetch_add(1, Ordering::Relaxed)
+}
+
+pub trait CacheableAsset: Clone {
+    /// Approximate bytes held by the asset
+    fn memory_usage(&self) -> usize;
+
+    /// True if anything outside of the cache holds the asset
+    fn in_use(&self) -> bool;
+}
+
+impl CacheableAsset for Arc<Texture> {
+    fn memory_usage(&self) -> usize {
+        let size = self.size();
+        size.x as usize * size.y as usize * 4
+    }
+
+    fn in_use(&self) -> bool {
+        Arc::strong_count(self) > 1
+    }
+}
+
+impl CacheableAsset for SoundBuffer {
+    fn memory_usage(&self) -> usize {
+        SoundBuffer::memory_usage(self)
+    }
+
+    fn in_use(&self) -> bool {
+        SoundBuffer::in_use(self)
+    }
+}
+
+#[derive(Clone)]
+struct CacheEntry<V> {
+    value: V,
+    bytes: usize,
+    last_used: u64,
+    // pinned entries can't be reloaded from disk
+    pinned: bool,
+}
+
+#[derive(Clone)]
+pub struct AssetCache<V> {
+    entries: HashMap<Arc<str>, CacheEntry<V>>,
+    total_bytes: usize,
+}
+
+impl<V> Default for AssetCache<V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            total_bytes: 0,
+        }
+    }
+}
+
+impl<V: CacheableAsset> AssetCache<V> {
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    pub fn contains_key(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Marks the asset as recently used
+    pub fn get(&mut self, path: &str) -> Option<V> {
+        let entry = self.entries.get_mut(path)?;
+        entry.last_used = next_tick();
+
+        Some(entry.value.clone())
+    }
+
+    pub fn insert(&mut self, path: Arc<str>, value: V) {
+        self.insert_entry(path, value, false);
+    }
+
+    /// Pinned assets are never evicted
+    pub fn insert_pinned(&mut self, path: Arc<str>, value: V) {
+        self.insert_entry(path, value, true);
+    }
+
+    fn insert_entry(&mut self, path: Arc<str>, value: V, pinned: bool) {
+        let bytes = value.memory_usage();
+        self.total_bytes += bytes;
+
+        let entry = CacheEntry {
+            value,
+            bytes,
+            last_used: next_tick(),
+            pinned,
+        };
+
+        if let Some(old_entry) = self.entries.insert(path, entry) {
+            self.total_bytes -= old_entry.bytes;
+        }
+    }
+
+    pub fn remove(&mut self, path: &str) -> Option<V> {
+        let entry = self.entries.remove(path)?;
+        self.total_bytes -= entry.bytes;
+
+        Some(entry.value)
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&str) -> bool) {
+        let total_bytes = &mut self.total_bytes;
+
+        self.entries.retain(|path, entry| {
+            let keep = f(path);
+
+            if !keep {
+                *total_bytes -= entry.bytes;
+            }
+
+            keep
+        });
+    }
+
+    /// Assets that can be evicted, paired with their last use
+    pub fn eviction_candidates(&self) -> impl Iterator<Item = (u64, Arc<str>)> + '_ {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| !entry.pinned && !entry.value.in_use())
+            .map(|(path, entry)| (entry.last_used, path.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone)]
+    struct TestAsset(Arc<usize>);
+
+    impl CacheableAsset for TestAsset {
+        fn memory_usage(&self) -> usize {
+            *self.0
+        }
+
+        fn in_use(&self) -> bool {
+            Arc::strong_count(&self.0) > 1
+        }
+    }
+
+    fn asset(bytes: usize) -> TestAsset {
+        TestAsset(Arc::new(bytes))
+    }
+
+    #[test]
+    fn tracks_total_bytes() {
+        let mut cache = AssetCache::default();
+        cache.insert("a".into(), asset(10));
+        cache.insert("b".into(), asset(20));
+        assert_eq!(cache.total_bytes(), 30);
+
+        // replacing
+        cache.insert("a".into(), asset(5));
+        assert_eq!(cache.total_bytes(), 25);
+
+        cache.remove("b");
+        assert_eq!(cache.total_bytes(), 5);
+
+        cache.retain(|_| false);
+        assert_eq!(cache.total_bytes(), 0);
+    }
+
+    fn sorted_candidates<V: CacheableAsset>(cache: &AssetCache<V>) -> Vec<Arc<str>> {
+        let mut candidates: Vec<_> = cache.eviction_candidates().collect();
+        candidates.sort_by_key(|(last_used, _)| *last_used);
+        candidates.into_iter().map(|(_, path)| path).collect()
+    }
+
+    #[test]
+    fn candidates_are_ordered_by_use() {
+        let mut cache = AssetCache::default();
+        cache.insert("a".into(), asset(10));
+        cache.insert("b".into(), asset(10));
+        cache.insert("c".into(), asset(10));
+
+        // a becomes the most recently used
+        let _ = cache.get("a");
+
+        assert_eq!(
+            sorted_candidates(&cache),
+            [Arc::<str>::from("b"), "c".into(), "a".into()]
+        );
+    }
+
+    #[test]
+    fn pinned_and_used_assets_are_not_candidates() {
+        let mut cache = AssetCache::default();
+        cache.insert_pinned("pinned".into(), asset(10));
+
+        let used = asset(10);
+        cache.insert("used".into(), used.clone());
+        cache.insert("unused".into(), asset(10));
+
+        assert_eq!(sorted_candidates(&cache), [Arc::<str>::from("unused")]);
+
+        drop(used);
+        assert_eq!(sorted_candidates(&cache).len(), 2);
+    }
+}
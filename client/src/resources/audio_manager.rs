@@ -1,46 +1,86 @@
-use super::SoundBuffer;
+use super::{AudioMixer, AudioPlacement, SoundBuffer};
 use indexmap::IndexMap;
-use rodio::cpal::{traits::HostTrait, Device};
+use rodio::cpal::{traits::HostTrait, Device, Host};
+use rodio::source::ChannelVolume;
 use rodio::{DeviceTrait, OutputStream, Source};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
-pub use crate::bindable::AudioBehavior;
+pub use crate::bindable::{AudioBehavior, AudioBus};
+
+struct MusicFade {
+    // the outgoing track
+    sink: rodio::Sink,
+    start: Instant,
+    duration: Duration,
+}
+
+impl MusicFade {
+    fn progress(&self) -> f32 {
+        (self.start.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+}
 
 pub struct AudioManager {
     stream: Option<rodio::OutputStream>,
     stream_handle: Option<rodio::OutputStreamHandle>,
     sfx_sinks: RefCell<IndexMap<usize, (Instant, rodio::Sink, Option<Box<dyn Fn()>>)>>,
     music_sink: RefCell<Option<rodio::Sink>>,
+    music_fade: RefCell<Option<MusicFade>>,
+    stinger_sink: RefCell<Option<rodio::Sink>>,
     music_stack: RefCell<Vec<(SoundBuffer, bool)>>,
-    music_volume: f32,
-    sfx_volume: f32,
+    ambience_sink: RefCell<Option<(SoundBuffer, rodio::Sink)>>,
+    mixer: RefCell<AudioMixer>,
+    // sound id -> bus, unlisted sounds play on the sfx bus
+    sound_buses: RefCell<HashMap<usize, AudioBus>>,
+    ducking_requested: Cell<bool>,
 }
 
 impl AudioManager {
-    pub fn new(name: &str) -> Self {
+    /// Empty names select the default backend and device
+    pub fn new(backend: &str, device: &str) -> Self {
         let mut audio_manager = Self {
             stream: None,
             stream_handle: None,
             sfx_sinks: RefCell::new(Default::default()),
             music_sink: RefCell::new(None),
+            music_fade: RefCell::new(None),
+            stinger_sink: RefCell::new(None),
             music_stack: RefCell::new(vec![(SoundBuffer::new_empty(), false)]),
-            music_volume: 1.0,
-            sfx_volume: 1.0,
+            ambience_sink: RefCell::new(None),
+            mixer: RefCell::new(AudioMixer::default()),
+            sound_buses: RefCell::new(HashMap::new()),
+            ducking_requested: Cell::new(false),
         };
 
-        audio_manager.use_device(name);
+        audio_manager.use_output(backend, device);
 
         audio_manager
     }
 
-    pub fn use_device(&mut self, name: &str) {
-        let Some(device) = Self::get_device(name) else {
-            log::error!("No audio output device named {name:?}");
-            return;
+    /// Falls back to the default device, then the default backend, if the output can't be opened
+    pub fn use_output(&mut self, backend: &str, device: &str) {
+        let host = Self::get_host(backend);
+
+        let device = Self::get_device(&host, device).or_else(|| {
+            log::warn!("No audio output device named {device:?}, using the default device");
+            host.default_output_device()
+        });
+
+        let mut result = match device {
+            Some(device) => OutputStream::try_from_device(&device).map_err(|e| e.to_string()),
+            None => Err(String::from("No audio output device available")),
         };
 
-        let (stream, stream_handle) = match OutputStream::try_from_device(&device) {
+        if let Err(e) = &result {
+            if host.id() != rodio::cpal::default_host().id() {
+                log::warn!("{e}, falling back to the default audio backend");
+                result = OutputStream::try_default().map_err(|e| e.to_string());
+            }
+        }
+
+        let (stream, stream_handle) = match result {
             Ok((stream, stream_handle)) => (Some(stream), Some(stream_handle)),
             Err(e) => {
                 log::error!("{e}");
@@ -51,47 +91,136 @@ impl AudioManager {
         self.stream = stream;
         self.stream_handle = stream_handle;
         self.restart_music();
+
+        if let Some(buffer) = self.current_ambience() {
+            self.play_ambience(&buffer);
+        }
     }
 
-    fn get_device(name: &str) -> Option<Device> {
+    fn get_host(name: &str) -> Host {
+        if name.is_empty() {
+            return rodio::cpal::default_host();
+        }
+
+        let host = rodio::cpal::available_hosts()
+            .into_iter()
+            .find(|id| id.name() == name)
+            .and_then(|id| rodio::cpal::host_from_id(id).ok());
+
+        host.unwrap_or_else(|| {
+            log::warn!("Audio backend {name:?} is unavailable, using the default backend");
+            rodio::cpal::default_host()
+        })
+    }
+
+    fn get_device(host: &Host, name: &str) -> Option<Device> {
         if name.is_empty() {
-            let host = rodio::cpal::default_host();
             return host.default_output_device();
         }
 
-        Self::devices()
+        Self::devices(host)
             .find(|device| matches!(device.name(), Ok(device_name) if device_name == name))
     }
 
-    fn devices() -> impl Iterator<Item = Device> {
-        let host = rodio::cpal::default_host();
+    fn devices(host: &Host) -> impl Iterator<Item = Device> {
         std::iter::once(host.output_devices()).flatten().flatten()
     }
 
-    pub fn device_names() -> impl Iterator<Item = String> {
-        Self::devices().flat_map(|device| device.name())
+    /// Backends compiled in and usable on this system, such as WASAPI or ALSA
+    pub fn backend_names() -> impl Iterator<Item = &'static str> {
+        rodio::cpal::available_hosts()
+            .into_iter()
+            .map(|id| id.name())
     }
 
-    pub fn with_music_volume(mut self, volume: f32) -> Self {
-        self.set_music_volume(volume);
-        self
+    pub fn device_names(backend: &str) -> Vec<String> {
+        let host = Self::get_host(backend);
+        Self::devices(&host)
+            .flat_map(|device| device.name())
+            .collect()
     }
 
-    pub fn with_sfx_volume(mut self, volume: f32) -> Self {
-        self.set_sfx_volume(volume);
+    pub fn with_bus_volume(mut self, bus: AudioBus, volume: f32) -> Self {
+        self.set_bus_volume(bus, volume);
         self
     }
 
     pub fn set_music_volume(&mut self, volume: f32) {
-        self.music_volume = volume;
+        self.set_bus_volume(AudioBus::Music, volume);
+    }
 
-        if let Some(music_sink) = self.music_sink.get_mut() {
-            music_sink.set_volume(volume);
+    pub fn set_sfx_volume(&mut self, volume: f32) {
+        self.set_bus_volume(AudioBus::Sfx, volume);
+    }
+
+    /// Volume set by the player
+    pub fn set_bus_volume(&mut self, bus: AudioBus, volume: f32) {
+        self.mixer.get_mut().set_bus_volume(bus, volume);
+
+        if bus == AudioBus::Music {
+            self.apply_music_volume();
         }
     }
 
-    pub fn set_sfx_volume(&mut self, volume: f32) {
-        self.sfx_volume = volume;
+    pub fn script_bus_volume(&self, bus: AudioBus) -> f32 {
+        self.mixer.borrow().script_volume(bus)
+    }
+
+    /// Volume set by scripts, scales the player's volume for the bus
+    pub fn set_script_bus_volume(&self, bus: AudioBus, volume: f32) {
+        self.mixer.borrow_mut().set_script_volume(bus, volume);
+
+        if bus == AudioBus::Music {
+            self.apply_music_volume();
+        }
+    }
+
+    pub fn reset_script_bus_volumes(&self) {
+        self.mixer.borrow_mut().reset_script_volumes();
+        self.apply_music_volume();
+    }
+
+    /// Plays the sound on the bus when played through play_sound
+    pub fn set_sound_bus(&self, buffer: &SoundBuffer, bus: AudioBus) {
+        self.sound_buses.borrow_mut().insert(buffer.id(), bus);
+    }
+
+    fn sound_volume(&self, buffer: &SoundBuffer) -> f32 {
+        let bus = self
+            .sound_buses
+            .borrow()
+            .get(&buffer.id())
+            .copied()
+            .unwrap_or_default();
+
+        self.mixer.borrow().output_volume(bus)
+    }
+
+    /// Lowers music for the current frame, used while dialogue is open
+    pub fn request_music_ducking(&self) {
+        self.ducking_requested.set(true);
+    }
+
+    fn apply_music_volume(&self) {
+        let volume = self.mixer.borrow().output_volume(AudioBus::Music);
+        let music_fade = self.music_fade.borrow();
+        let fade_progress = music_fade.as_ref().map(MusicFade::progress).unwrap_or(1.0);
+
+        if let Some(music_sink) = self.music_sink.borrow().as_ref() {
+            music_sink.set_volume(volume * fade_progress);
+        }
+
+        if let Some(music_fade) = music_fade.as_ref() {
+            music_fade.sink.set_volume(volume * (1.0 - fade_progress));
+        }
+
+        if let Some(stinger_sink) = self.stinger_sink.borrow().as_ref() {
+            stinger_sink.set_volume(volume);
+        }
+
+        if let Some((_, ambience_sink)) = self.ambience_sink.borrow().as_ref() {
+            ambience_sink.set_volume(volume);
+        }
     }
 
     pub fn is_music_playing(&self) -> bool {
@@ -142,12 +271,29 @@ impl AudioManager {
     }
 
     pub fn play_music(&self, buffer: &SoundBuffer, loops: bool) {
+        self.crossfade_music(buffer, loops, Duration::ZERO);
+    }
+
+    /// Fades out the current track while fading in the new track
+    pub fn crossfade_music(&self, buffer: &SoundBuffer, loops: bool, duration: Duration) {
         let Some(stream_handle) = self.stream_handle.as_ref() else {
             return;
         };
 
-        if let Some(music_sink) = self.music_sink.borrow().as_ref() {
-            music_sink.stop();
+        // a new track interrupts stingers and previous fades
+        self.stop_stinger();
+        self.stop_music_fade();
+
+        if let Some(music_sink) = self.music_sink.borrow_mut().take() {
+            if duration.is_zero() || music_sink.empty() {
+                music_sink.stop();
+            } else {
+                *self.music_fade.borrow_mut() = Some(MusicFade {
+                    sink: music_sink,
+                    start: Instant::now(),
+                    duration,
+                });
+            }
         }
 
         if buffer.is_empty() {
@@ -164,33 +310,147 @@ impl AudioManager {
             }
         };
 
-        music_sink.set_volume(self.music_volume);
+        // set the volume before appending, new tracks may start silent for a fade
+        *self.music_sink.borrow_mut() = Some(music_sink);
+        self.apply_music_volume();
 
-        if loops {
-            music_sink.append(buffer.create_looped_sampler(None));
-        } else {
-            music_sink.append(buffer.create_sampler());
+        if let Some(music_sink) = self.music_sink.borrow().as_ref() {
+            if loops {
+                music_sink.append(buffer.create_looped_sampler(None));
+            } else {
+                music_sink.append(buffer.create_sampler());
+            }
         }
 
-        *self.music_sink.borrow_mut() = Some(music_sink);
         *(self.music_stack.borrow_mut().last_mut().unwrap()) = (buffer.clone(), loops);
     }
 
     pub fn stop_music(&self) {
+        self.stop_stinger();
+        self.stop_music_fade();
+
         if let Some(music_sink) = self.music_sink.borrow().as_ref() {
             music_sink.stop();
         }
     }
 
+    fn stop_music_fade(&self) {
+        if let Some(music_fade) = self.music_fade.borrow_mut().take() {
+            music_fade.sink.stop();
+        }
+    }
+
+    /// Pauses music to play the stinger once, music resumes when the stinger ends
+    pub fn play_stinger(&self, buffer: &SoundBuffer) {
+        let Some(stream_handle) = self.stream_handle.as_ref() else {
+            return;
+        };
+
+        self.stop_stinger();
+
+        if buffer.is_empty() {
+            return;
+        }
+
+        let stinger_sink = match rodio::Sink::try_new(stream_handle) {
+            Ok(stinger_sink) => stinger_sink,
+            Err(e) => {
+                log::error!("Failed to create stinger sink: {e}");
+                return;
+            }
+        };
+
+        stinger_sink.set_volume(self.mixer.borrow().output_volume(AudioBus::Music));
+        stinger_sink.append(buffer.create_sampler());
+
+        self.stop_music_fade();
+        self.apply_music_volume();
+
+        if let Some(music_sink) = self.music_sink.borrow().as_ref() {
+            music_sink.pause();
+        }
+
+        *self.stinger_sink.borrow_mut() = Some(stinger_sink);
+    }
+
+    fn stop_stinger(&self) {
+        if let Some(stinger_sink) = self.stinger_sink.borrow_mut().take() {
+            stinger_sink.stop();
+        }
+
+        if let Some(music_sink) = self.music_sink.borrow().as_ref() {
+            music_sink.play();
+        }
+    }
+
+    pub fn current_ambience(&self) -> Option<SoundBuffer> {
+        let ambience_sink = self.ambience_sink.borrow();
+        ambience_sink.as_ref().map(|(buffer, _)| buffer.clone())
+    }
+
+    /// Ambience loops alongside music and shares its volume
+    pub fn play_ambience(&self, buffer: &SoundBuffer) {
+        let Some(stream_handle) = self.stream_handle.as_ref() else {
+            return;
+        };
+
+        self.stop_ambience();
+
+        if buffer.is_empty() {
+            return;
+        }
+
+        let ambience_sink = match rodio::Sink::try_new(stream_handle) {
+            Ok(ambience_sink) => ambience_sink,
+            Err(e) => {
+                log::error!("Failed to create ambience sink: {e}");
+                return;
+            }
+        };
+
+        ambience_sink.set_volume(self.mixer.borrow().output_volume(AudioBus::Music));
+        ambience_sink.append(buffer.create_looped_sampler(None));
+
+        *self.ambience_sink.borrow_mut() = Some((buffer.clone(), ambience_sink));
+    }
+
+    pub fn stop_ambience(&self) {
+        if let Some((_, ambience_sink)) = self.ambience_sink.borrow_mut().take() {
+            ambience_sink.stop();
+        }
+    }
+
+    fn create_sound_source(
+        &self,
+        buffer: &SoundBuffer,
+        sampler: impl Source<Item = i16> + Send + 'static,
+        placement: Option<AudioPlacement>,
+    ) -> Box<dyn Source<Item = f32> + Send> {
+        let source = sampler
+            .convert_samples::<f32>()
+            .amplify(self.sound_volume(buffer));
+
+        match placement {
+            // mixes down to mono before panning
+            Some(placement) => Box::new(ChannelVolume::new(
+                source,
+                placement.channel_volumes().to_vec(),
+            )),
+            None => Box::new(source),
+        }
+    }
+
     pub fn play_sound(&self, buffer: &SoundBuffer) {
+        self.play_sound_at(buffer, None);
+    }
+
+    /// Sounds without a placement play centered
+    pub fn play_sound_at(&self, buffer: &SoundBuffer, placement: Option<AudioPlacement>) {
         let Some(stream_handle) = self.stream_handle.as_ref() else {
             return;
         };
 
-        let source = buffer
-            .create_sampler()
-            .convert_samples()
-            .amplify(self.sfx_volume);
+        let source = self.create_sound_source(buffer, buffer.create_sampler(), placement);
 
         let res = stream_handle.play_raw(source);
 
@@ -200,15 +460,26 @@ impl AudioManager {
     }
 
     pub fn play_sound_with_behavior(&self, buffer: &SoundBuffer, behavior: AudioBehavior) {
+        self.play_sound_with_behavior_at(buffer, behavior, None);
+    }
+
+    pub fn play_sound_with_behavior_at(
+        &self,
+        buffer: &SoundBuffer,
+        behavior: AudioBehavior,
+        placement: Option<AudioPlacement>,
+    ) {
         match behavior {
-            AudioBehavior::Default => self.play_sound(buffer),
-            AudioBehavior::NoOverlap => self.play_no_overlap(buffer),
-            AudioBehavior::LoopSection(start, end) => self.play_loop_section(buffer, start, end),
+            AudioBehavior::Default => self.play_sound_at(buffer, placement),
+            AudioBehavior::NoOverlap => self.play_no_overlap(buffer, placement),
+            AudioBehavior::LoopSection(start, end) => {
+                self.play_loop_section(buffer, start, end, placement)
+            }
             AudioBehavior::EndLoop => self.end_sound_loop(buffer),
         }
     }
 
-    fn play_no_overlap(&self, buffer: &SoundBuffer) {
+    fn play_no_overlap(&self, buffer: &SoundBuffer, placement: Option<AudioPlacement>) {
         let Some(stream_handle) = self.stream_handle.as_ref() else {
             return;
         };
@@ -250,17 +521,20 @@ impl AudioManager {
                     < Duration::from_millis(17));
 
         if can_queue {
-            let source = buffer
-                .create_sampler()
-                .convert_samples::<f32>()
-                .amplify(self.sfx_volume);
+            let source = self.create_sound_source(buffer, buffer.create_sampler(), placement);
 
             sfx_sink.append(source);
             *start_instant = Instant::now();
         }
     }
 
-    fn play_loop_section(&self, buffer: &SoundBuffer, start: usize, end: usize) {
+    fn play_loop_section(
+        &self,
+        buffer: &SoundBuffer,
+        start: usize,
+        end: usize,
+        placement: Option<AudioPlacement>,
+    ) {
         let Some(stream_handle) = self.stream_handle.as_ref() else {
             return;
         };
@@ -283,7 +557,7 @@ impl AudioManager {
         let sampler = buffer.create_looped_sampler(Some(start..end));
         let callback = Box::new(sampler.end_loop_callback());
 
-        let source = sampler.convert_samples::<f32>().amplify(self.sfx_volume);
+        let source = self.create_sound_source(buffer, sampler, placement);
 
         sink.append(source);
         sink.play();
@@ -309,4 +583,36 @@ impl AudioManager {
 
         sfx_sinks.retain(|_, (_, sink, _)| !sink.empty());
     }
+
+    /// Called once per frame, resumes music after stingers and steps crossfades
+    pub fn update_music_transitions(&self) {
+        let stinger_complete = matches!(&*self.stinger_sink.borrow(), Some(sink) if sink.empty());
+
+        if stinger_complete {
+            self.stop_stinger();
+        }
+
+        let music_fade = self.music_fade.borrow();
+
+        let Some(fade_complete) = music_fade.as_ref().map(|fade| fade.progress() >= 1.0) else {
+            return;
+        };
+
+        std::mem::drop(music_fade);
+
+        if fade_complete {
+            self.stop_music_fade();
+        }
+
+        self.apply_music_volume();
+    }
+
+    /// Called once per frame
+    pub fn update_ducking(&self) {
+        let ducked = self.ducking_requested.replace(false);
+
+        if self.mixer.borrow_mut().update_ducking(ducked) {
+            self.apply_music_volume();
+        }
+    }
 }
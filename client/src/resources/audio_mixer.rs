@@ -0,0 +1,109 @@
+use crate::bindable::AudioBus;
+
+/// Music volume multiplier while fully ducked
+const DUCKED_MUSIC_VOLUME: f32 = 0.35;
+/// Ducking progress per frame, fades in and out over a few frames
+const DUCKING_RATE: f32 = 0.1;
+
+#[derive(Clone)]
+pub struct AudioMixer {
+    // set by the player
+    bus_volumes: [f32; AudioBus::ALL.len()],
+    // set by scripts, scales the player's volume
+    script_volumes: [f32; AudioBus::ALL.len()],
+    ducking: f32,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        Self {
+            bus_volumes: [1.0; AudioBus::ALL.len()],
+            script_volumes: [1.0; AudioBus::ALL.len()],
+            ducking: 0.0,
+        }
+    }
+}
+
+impl AudioMixer {
+    pub fn bus_volume(&self, bus: AudioBus) -> f32 {
+        self.bus_volumes[bus as usize]
+    }
+
+    pub fn set_bus_volume(&mut self, bus: AudioBus, volume: f32) {
+        self.bus_volumes[bus as usize] = volume.max(0.0);
+    }
+
+    pub fn script_volume(&self, bus: AudioBus) -> f32 {
+        self.script_volumes[bus as usize]
+    }
+
+    pub fn set_script_volume(&mut self, bus: AudioBus, volume: f32) {
+        self.script_volumes[bus as usize] = volume.clamp(0.0, 1.0);
+    }
+
+    pub fn reset_script_volumes(&mut self) {
+        self.script_volumes = [1.0; AudioBus::ALL.len()];
+    }
+
+    /// Steps ducking towards the target, returns true if the music volume changed
+    pub fn update_ducking(&mut self, ducked: bool) -> bool {
+        let previous = self.ducking;
+
+        if ducked {
+            self.ducking = (self.ducking + DUCKING_RATE).min(1.0);
+        } else {
+            self.ducking = (self.ducking - DUCKING_RATE).max(0.0);
+        }
+
+        self.ducking != previous
+    }
+
+    pub fn output_volume(&self, bus: AudioBus) -> f32 {
+        let volume = self.bus_volume(bus) * self.script_volume(bus);
+
+        if bus == AudioBus::Music {
+            let ducking_multiplier = 1.0 + (DUCKED_MUSIC_VOLUME - 1.0) * self.ducking;
+            volume * ducking_multiplier
+        } else {
+            volume
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn script_volume_scales_bus_volume() {
+        let mut mixer = AudioMixer::default();
+        mixer.set_bus_volume(AudioBus::Sfx, 0.5);
+        mixer.set_script_volume(AudioBus::Sfx, 0.5);
+
+        assert_eq!(mixer.output_volume(AudioBus::Sfx), 0.25);
+        assert_eq!(mixer.output_volume(AudioBus::Ui), 1.0);
+
+        // scripts can't raise volume past the player's setting
+        mixer.set_script_volume(AudioBus::Sfx, 2.0);
+        assert_eq!(mixer.output_volume(AudioBus::Sfx), 0.5);
+
+        mixer.reset_script_volumes();
+        assert_eq!(mixer.script_volume(AudioBus::Sfx), 1.0);
+    }
+
+    #[test]
+    fn ducking_only_affects_music() {
+        let mut mixer = AudioMixer::default();
+
+        while mixer.update_ducking(true) {}
+
+        assert!((mixer.output_volume(AudioBus::Music) - DUCKED_MUSIC_VOLUME).abs() < 0.001);
+        assert_eq!(mixer.output_volume(AudioBus::Voice), 1.0);
+
+        assert!(mixer.update_ducking(false));
+        while mixer.update_ducking(false) {}
+
+        assert_eq!(mixer.output_volume(AudioBus::Music), 1.0);
+        assert!(!mixer.update_ducking(false));
+    }
+}
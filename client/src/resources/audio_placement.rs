@@ -0,0 +1,75 @@
+use framework::prelude::Vec2;
+
+/// How far sounds lean towards one speaker, 1.0 would silence the other speaker
+const PAN_STRENGTH: f32 = 0.6;
+/// Sounds within this portion of the view play at full volume
+const FULL_VOLUME_DISTANCE: f32 = 0.5;
+/// Sounds this many view half-widths away play at the lowest volume
+const MIN_VOLUME_DISTANCE: f32 = 2.0;
+const MIN_VOLUME: f32 = 0.25;
+
+/// Panning and attenuation for a sound emitted somewhere in the world
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioPlacement {
+    /// -1.0 is fully left, 1.0 is fully right
+    pub pan: f32,
+    pub volume: f32,
+}
+
+impl AudioPlacement {
+    /// `offset` is relative to the center of the view, `range` is the distance from the center to the edge
+    pub fn from_offset(offset: Vec2, range: Vec2) -> Self {
+        let normalized = offset / range.max(Vec2::ONE);
+        let pan = normalized.x.clamp(-1.0, 1.0);
+
+        let distance = normalized.length();
+        let falloff = ((distance - FULL_VOLUME_DISTANCE)
+            / (MIN_VOLUME_DISTANCE - FULL_VOLUME_DISTANCE))
+            .clamp(0.0, 1.0);
+
+        Self {
+            pan,
+            volume: 1.0 + (MIN_VOLUME - 1.0) * falloff,
+        }
+    }
+
+    /// Volumes for the left and right channels
+    pub fn channel_volumes(&self) -> [f32; 2] {
+        let left = 1.0 - self.pan.max(0.0) * PAN_STRENGTH;
+        let right = 1.0 + self.pan.min(0.0) * PAN_STRENGTH;
+
+        [left * self.volume, right * self.volume]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RANGE: Vec2 = Vec2::new(120.0, 80.0);
+
+    #[test]
+    fn centered_sounds_are_unchanged() {
+        let placement = AudioPlacement::from_offset(Vec2::new(10.0, -10.0), RANGE);
+
+        assert_eq!(placement.volume, 1.0);
+
+        let [left, right] = placement.channel_volumes();
+        assert!(left < 1.0 && left > 0.9);
+        assert_eq!(right, 1.0);
+    }
+
+    #[test]
+    fn distant_sounds_pan_and_attenuate() {
+        let placement = AudioPlacement::from_offset(Vec2::new(-RANGE.x, 0.0), RANGE);
+        let [left, right] = placement.channel_volumes();
+
+        assert_eq!(placement.pan, -1.0);
+        assert!(placement.volume < 1.0);
+        assert!(right < left);
+
+        let far_placement = AudioPlacement::from_offset(Vec2::new(-RANGE.x * 10.0, 0.0), RANGE);
+        assert_eq!(far_placement.pan, -1.0);
+        assert_eq!(far_placement.volume, MIN_VOLUME);
+    }
+}
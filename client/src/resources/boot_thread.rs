@@ -1,6 +1,9 @@
 use super::{AssetManager, Globals};
 use crate::packages::*;
-use crate::resources::{GlobalMusic, GlobalSfx, LocalAssetManager, ResourcePaths};
+use crate::resources::{
+    parse_integrity_manifest, GlobalMusic, GlobalSfx, IntegrityCache, IntegrityReport,
+    LocalAssetManager, ResourcePaths,
+};
 use framework::prelude::GameIO;
 use packets::structures::FileHash;
 use std::collections::HashSet;
@@ -23,15 +26,59 @@ pub enum BootEvent {
     StatusManager(PackageManager<StatusPackage>),
     TileStateManager(PackageManager<TileStatePackage>),
     LibraryManager(PackageManager<LibraryPackage>),
+    EmoteManager(PackageManager<EmotePackage>),
     CharacterManager(PackageManager<CharacterPackage>),
+    IntegrityReport(IntegrityReport),
     Done,
 }
 
+/// Categories loaded from package folders, child packages are loaded separately
+const FOLDER_CATEGORIES: [PackageCategory; 8] = [
+    PackageCategory::Player,
+    PackageCategory::Card,
+    PackageCategory::Encounter,
+    PackageCategory::Augment,
+    PackageCategory::Status,
+    PackageCategory::TileState,
+    PackageCategory::Library,
+    PackageCategory::Emote,
+];
+
+/// Tracks progress across every loading step, for a single progress bar
+struct BootProgress {
+    sender: flume::Sender<BootEvent>,
+    progress: usize,
+    total: usize,
+}
+
+impl BootProgress {
+    fn add_work(&mut self, amount: usize) {
+        self.total += amount;
+    }
+
+    fn advance(&mut self, label: &Arc<str>) {
+        self.progress += 1;
+
+        let status_update = ProgressUpdate {
+            label: label.clone(),
+            progress: self.progress,
+            total: self.total,
+        };
+
+        self.sender
+            .send(BootEvent::ProgressUpdate(status_update))
+            .unwrap();
+    }
+}
+
 pub struct BootThread {
     sender: flume::Sender<BootEvent>,
     assets: LocalAssetManager,
+    progress: BootProgress,
     child_packages: Vec<(ChildPackageInfo, PackageNamespace)>,
     hashes: HashSet<FileHash>,
+    integrity_cache: IntegrityCache,
+    integrity_report: IntegrityReport,
 }
 
 impl BootThread {
@@ -41,13 +88,24 @@ impl BootThread {
         let assets = globals.assets.clone();
 
         std::thread::spawn(move || {
+            // child packages and cache files are added to the total as they're found
+            let total = GlobalMusic::total() + GlobalSfx::total() + Self::count_package_folders();
+
             let mut context = Self {
-                sender,
+                sender: sender.clone(),
                 assets,
+                progress: BootProgress {
+                    sender,
+                    progress: 0,
+                    total,
+                },
                 child_packages: Vec::new(),
                 hashes: HashSet::new(),
+                integrity_cache: IntegrityCache::load(),
+                integrity_report: IntegrityReport::default(),
             };
 
+            context.verify_files();
             context.load_audio();
             context.load_packages();
         });
@@ -55,58 +113,94 @@ impl BootThread {
         receiver
     }
 
+    fn count_package_folders() -> usize {
+        FOLDER_CATEGORIES
+            .iter()
+            .flat_map(|category| [category.built_in_path(), category.mod_path()])
+            .flat_map(std::fs::read_dir)
+            .map(|entry_iter| entry_iter.count())
+            .sum()
+    }
+
     fn send(&mut self, event: BootEvent) {
         self.sender.send(event).unwrap();
     }
 
-    fn load_audio(&mut self) {
-        // total for music, add 1 for sound font
-        let total = GlobalMusic::total() + 1;
+    /// Checks built-in resources against the manifest and removes corrupted zips from the cache,
+    /// cached zips are recreated from installed packages or downloaded again from servers
+    fn verify_files(&mut self) {
+        let label: Arc<str> = Arc::from("Verifying Files");
 
-        // load sound font for music
-        self.send(BootEvent::ProgressUpdate(ProgressUpdate {
-            label: Arc::from("Loading Music"),
-            progress: 0,
-            total,
-        }));
+        // the manifest only exists for releases
+        if let Ok(manifest) = std::fs::read_to_string(ResourcePaths::INTEGRITY_MANIFEST) {
+            let entries = parse_integrity_manifest(&manifest);
+            self.progress.add_work(entries.len());
 
-        let sound_font_bytes = self.assets.binary(ResourcePaths::SOUND_FONT);
+            for (expected_hash, path) in entries {
+                if self.integrity_cache.file_hash(path) != Some(expected_hash) {
+                    log::error!("{path:?} is missing or corrupted");
+                    self.integrity_report.resources.push(path.to_string());
+                }
 
-        // load music
-        let mut progress = 1;
+                self.progress.advance(&label);
+            }
+        }
 
-        let load = |path: &str| {
-            self.send(BootEvent::ProgressUpdate(ProgressUpdate {
-                label: Arc::from("Loading Music"),
-                progress,
-                total,
-            }));
+        let Ok(entry_iter) = std::fs::read_dir(ResourcePaths::MOD_CACHE_FOLDER) else {
+            return;
+        };
 
-            progress += 1;
+        let entries: Vec<_> = entry_iter.flatten().collect();
+        self.progress.add_work(entries.len());
 
-            self.assets.non_midi_audio(path)
-        };
+        for entry in entries {
+            let name = entry.file_name();
+            let expected_hash = name
+                .to_str()
+                .and_then(|name| FileHash::from_hex(name.trim_end_matches(".zip")));
 
-        let music = GlobalMusic::load_with(sound_font_bytes, load);
-        self.send(BootEvent::Music(music));
+            // invalid names are deleted while cleaning the cache
+            if let Some(expected_hash) = expected_hash {
+                let path = entry.path().to_string_lossy().into_owned();
 
-        // load sfx
-        let mut progress = 0;
-        let total = GlobalSfx::total();
+                if self.integrity_cache.file_hash(&path) != Some(expected_hash) {
+                    log::warn!("Removing corrupted {:?}", ResourcePaths::shorten(&path));
 
-        let load = |path: &str| {
-            self.send(BootEvent::ProgressUpdate(ProgressUpdate {
-                label: Arc::from("Loading SFX"),
-                progress,
-                total,
-            }));
+                    if let Err(e) = std::fs::remove_file(&path) {
+                        log::error!("Failed to delete {path:?}: {e}");
+                    }
 
-            progress += 1;
+                    self.integrity_cache.forget_file(&path);
+                }
+            }
 
-            self.assets.non_midi_audio(path)
-        };
+            self.progress.advance(&label);
+        }
+    }
 
-        let sfx = Box::new(GlobalSfx::load_with(load));
+    fn load_audio(&mut self) {
+        let label: Arc<str> = Arc::from("Loading Audio");
+
+        // load sound font for music
+        let sound_font_bytes = self.assets.binary(ResourcePaths::SOUND_FONT);
+        self.progress.advance(&label);
+
+        // decode everything at once, the loaders below will read from the cache
+        let paths: Vec<_> = GlobalMusic::track_paths()
+            .into_iter()
+            .chain(GlobalSfx::paths())
+            .collect();
+
+        self.assets
+            .preload_non_midi_audio(&paths, |_, _| self.progress.advance(&label));
+
+        let music =
+            GlobalMusic::load_with(sound_font_bytes, |path| self.assets.non_midi_audio(path));
+        self.send(BootEvent::Music(music));
+
+        let sfx = Box::new(GlobalSfx::load_with(|path| {
+            self.assets.non_midi_audio(path)
+        }));
         self.send(BootEvent::Sfx(sfx));
     }
 
@@ -146,13 +240,21 @@ impl BootThread {
 
         self.send(BootEvent::LibraryManager(library_packages));
 
+        // load emotes
+        let emote_packages = self.load_category(PackageCategory::Emote, "Emotes");
+
+        self.send(BootEvent::EmoteManager(emote_packages));
+
         // load child packages
         self.load_child_packages();
 
         // clean cache
         self.clean_cache_folder();
+        self.integrity_cache.save();
 
         // complete
+        let integrity_report = std::mem::take(&mut self.integrity_report);
+        self.send(BootEvent::IntegrityReport(integrity_report));
         self.send(BootEvent::Done);
     }
 
@@ -189,25 +291,31 @@ impl BootThread {
         path: &'static str,
         label: Arc<str>,
     ) {
-        package_manager.load_packages_in_folder(
-            &self.assets,
-            namespace,
-            path,
-            |progress, total| {
-                let status_update = ProgressUpdate {
-                    label: label.clone(),
-                    progress,
-                    total,
-                };
-
-                let event = BootEvent::ProgressUpdate(status_update);
-                self.sender.send(event).unwrap();
-            },
-        );
+        package_manager.load_packages_in_folder(namespace, path, |_, _| {
+            self.progress.advance(&label);
+        });
 
         // gather hashes
         for package in package_manager.packages(namespace) {
-            self.hashes.insert(package.package_info().hash);
+            let package_info = package.package_info();
+            self.hashes.insert(package_info.hash);
+
+            // test packages installed from the repo for changes
+            let installed_hash = self.integrity_cache.installed_hash(&package_info.id);
+
+            let Some(installed_hash) = installed_hash else {
+                continue;
+            };
+
+            if namespace == PackageNamespace::Local && installed_hash != package_info.hash {
+                log::warn!("{} changed since it was installed", package_info.id);
+
+                self.integrity_report.packages.push((
+                    package_info.category,
+                    package_info.id.clone(),
+                    installed_hash,
+                ));
+            }
         }
 
         // track child packages
@@ -224,19 +332,12 @@ impl BootThread {
             PackageManager::<CharacterPackage>::new(PackageCategory::Character);
 
         // characters and child packages are currently the same
-        let total_child_packages = self.child_packages.len();
-
-        for (i, (child_package, namespace)) in self.child_packages.iter().enumerate() {
-            let status_update = ProgressUpdate {
-                label: Arc::from("Loading Enemies"),
-                progress: i,
-                total: total_child_packages,
-            };
-
-            let event = BootEvent::ProgressUpdate(status_update);
-            self.sender.send(event).unwrap();
+        let label: Arc<str> = Arc::from("Loading Enemies");
+        self.progress.add_work(self.child_packages.len());
 
+        for (child_package, namespace) in &self.child_packages {
             character_packages.load_child_package(*namespace, child_package);
+            self.progress.advance(&label);
         }
 
         self.send(BootEvent::CharacterManager(character_packages));
@@ -265,20 +366,15 @@ impl BootThread {
             })
             .collect();
 
-        let total = entries.len();
-
-        for (i, entry) in entries.into_iter().enumerate() {
-            let status_update = ProgressUpdate {
-                label: Arc::from("Cleaning Cache"),
-                progress: i,
-                total,
-            };
-
-            self.send(BootEvent::ProgressUpdate(status_update));
+        let label: Arc<str> = Arc::from("Cleaning Cache");
+        self.progress.add_work(entries.len());
 
+        for entry in entries {
             if let Err(e) = std::fs::remove_file(entry.path()) {
                 log::error!("Failed to delete {:?}: {e}", entry.path());
             }
+
+            self.progress.advance(&label);
         }
     }
 }
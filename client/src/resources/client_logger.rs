@@ -0,0 +1,333 @@
+use super::ResourcePaths;
+use framework::logging::{LogLevel, LogRecord};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock, RwLock};
+use strum::{EnumIter, EnumString, IntoEnumIterator, IntoStaticStr};
+
+const LOG_FILE_NAME: &str = "latest.log";
+/// Includes the latest log
+const LOG_FILE_COUNT: usize = 5;
+const LOG_FILE_SIZE_LIMIT: u64 = 8 * 1024 * 1024;
+/// Lines kept in memory for crash reports
+const RECENT_LINE_LIMIT: usize = 200;
+
+static LOGGER: OnceLock<ClientLogger> = OnceLock::new();
+
+/// Groups of modules that can be filtered separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum LogTarget {
+    General,
+    Netplay,
+    Packages,
+    Lua,
+    Render,
+}
+
+impl LogTarget {
+    /// Module path prefixes, records can also set the target name directly with `target: "netplay"`
+    const MODULE_PREFIXES: [(&'static str, LogTarget); 14] = [
+        ("hub_os::battle::netplay", LogTarget::Netplay),
+        ("hub_os::scenes::netplay_init_scene", LogTarget::Netplay),
+        ("hub_os::resources::network", LogTarget::Netplay),
+        ("hub_os::resources::stun", LogTarget::Netplay),
+        ("network_channels", LogTarget::Netplay),
+        ("hub_os::packages", LogTarget::Packages),
+        ("hub_os::resources::boot_thread", LogTarget::Packages),
+        ("hub_os::lua_api", LogTarget::Lua),
+        ("hub_os::battle::rollback_vm", LogTarget::Lua),
+        ("hub_os::battle::script_profiler", LogTarget::Lua),
+        ("hub_os::render", LogTarget::Render),
+        ("framework::graphics", LogTarget::Render),
+        ("wgpu", LogTarget::Render),
+        ("naga", LogTarget::Render),
+    ];
+
+    pub fn from_record_target(target: &str) -> Self {
+        if let Ok(log_target) = LogTarget::from_str(target) {
+            return log_target;
+        }
+
+        Self::MODULE_PREFIXES
+            .iter()
+            .find(|(prefix, _)| target.starts_with(prefix))
+            .map(|(_, log_target)| *log_target)
+            .unwrap_or(LogTarget::General)
+    }
+}
+
+/// Levels per target, written as `info,netplay=debug,render=warn`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilters {
+    default_level: LevelFilter,
+    levels: Vec<(LogTarget, LevelFilter)>,
+}
+
+impl Default for LogFilters {
+    fn default() -> Self {
+        Self {
+            default_level: LevelFilter::Info,
+            // graphics libraries are noisy at the info level
+            levels: vec![(LogTarget::Render, LevelFilter::Warn)],
+        }
+    }
+}
+
+impl LogFilters {
+    /// A bare level sets the default for targets without a level, an empty string uses the defaults
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut filters = Self::default();
+
+        for part in text
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            let Some((target, level)) = part.split_once('=') else {
+                filters.default_level = LevelFilter::from_str(part)
+                    .map_err(|_| format!("{part:?} isn't a log level"))?;
+                continue;
+            };
+
+            let target = LogTarget::from_str(target.trim()).map_err(|_| {
+                let targets: Vec<&str> = LogTarget::iter().map(|target| target.into()).collect();
+                format!(
+                    "{target:?} isn't a log target, expected {}",
+                    targets.join(", ")
+                )
+            })?;
+
+            let level = LevelFilter::from_str(level.trim())
+                .map_err(|_| format!("{level:?} isn't a log level"))?;
+
+            filters.levels.retain(|(t, _)| *t != target);
+            filters.levels.push((target, level));
+        }
+
+        Ok(filters)
+    }
+
+    pub fn level(&self, target: LogTarget) -> LevelFilter {
+        self.levels
+            .iter()
+            .find(|(t, _)| *t == target)
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default_level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        (self.levels.iter().map(|(_, level)| *level)).fold(self.default_level, LevelFilter::max)
+    }
+}
+
+struct LogFile {
+    writer: BufWriter<File>,
+    len: u64,
+}
+
+impl LogFile {
+    fn folder() -> String {
+        ResourcePaths::game_folder().to_string() + ResourcePaths::LOG_FOLDER
+    }
+
+    /// Shifts older logs back, dropping the oldest, and starts a new latest log
+    fn open() -> Option<Self> {
+        let folder = Self::folder();
+        let _ = std::fs::create_dir_all(&folder);
+
+        let numbered_path = |i: usize| format!("{folder}{i}.log");
+
+        let _ = std::fs::remove_file(numbered_path(LOG_FILE_COUNT - 1));
+
+        for i in (1..LOG_FILE_COUNT - 1).rev() {
+            let _ = std::fs::rename(numbered_path(i), numbered_path(i + 1));
+        }
+
+        let latest_path = folder.clone() + LOG_FILE_NAME;
+        let _ = std::fs::rename(&latest_path, numbered_path(1));
+
+        let file = File::create(&latest_path).ok()?;
+
+        Some(Self {
+            writer: BufWriter::new(file),
+            len: 0,
+        })
+    }
+}
+
+/// Writes to stdout and rotating files in the log folder, forwarding records to a listener
+pub struct ClientLogger {
+    filters: RwLock<LogFilters>,
+    file: Mutex<Option<LogFile>>,
+    recent_lines: Mutex<VecDeque<String>>,
+    listener: Box<dyn Fn(LogRecord) + Send + Sync>,
+}
+
+impl ClientLogger {
+    /// Should be called once, after the game folder is initialized
+    pub fn init(filters: LogFilters, listener: impl Fn(LogRecord) + Send + Sync + 'static) {
+        log::set_max_level(filters.max_level());
+
+        let logger = LOGGER.get_or_init(|| ClientLogger {
+            filters: RwLock::new(filters),
+            file: Mutex::new(LogFile::open()),
+            recent_lines: Mutex::new(VecDeque::with_capacity(RECENT_LINE_LIMIT)),
+            listener: Box::new(listener),
+        });
+
+        if let Err(e) = log::set_logger(logger) {
+            eprintln!("Failed to initialize logging: {e}");
+        }
+    }
+
+    pub fn set_filters(filters: LogFilters) {
+        let Some(logger) = LOGGER.get() else {
+            return;
+        };
+
+        log::set_max_level(filters.max_level());
+        *logger.filters.write().unwrap() = filters;
+    }
+
+    /// The most recent log lines, oldest first. Avoids blocking, so this is safe to call while panicking
+    pub fn recent_lines() -> Vec<String> {
+        let Some(logger) = LOGGER.get() else {
+            return Vec::new();
+        };
+
+        let Ok(recent_lines) = logger.recent_lines.try_lock() else {
+            return Vec::new();
+        };
+
+        recent_lines.iter().cloned().collect()
+    }
+
+    fn record_recent_line(&self, line: String) {
+        let Ok(mut recent_lines) = self.recent_lines.lock() else {
+            return;
+        };
+
+        if recent_lines.len() >= RECENT_LINE_LIMIT {
+            recent_lines.pop_front();
+        }
+
+        recent_lines.push_back(line);
+    }
+
+    fn write_to_file(&self, line: &str, level: Level) {
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+
+        if file
+            .as_ref()
+            .is_some_and(|file| file.len > LOG_FILE_SIZE_LIMIT)
+        {
+            *file = LogFile::open();
+        }
+
+        let Some(file) = file.as_mut() else {
+            return;
+        };
+
+        let _ = writeln!(file.writer, "{line}");
+        file.len += line.len() as u64 + 1;
+
+        // keep context on disk in case we crash
+        if level <= Level::Warn {
+            let _ = file.writer.flush();
+        }
+    }
+}
+
+impl Log for ClientLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let target = LogTarget::from_record_target(metadata.target());
+        let filters = self.filters.read().unwrap();
+
+        metadata.level() <= filters.level(target)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let target = LogTarget::from_record_target(record.target());
+        let target_name: &'static str = target.into();
+        let message = record.args().to_string();
+
+        let time = chrono::Local::now().format("%H:%M:%S%.3f");
+        let line = format!(
+            "{time} {:<5} [{target_name}] {}: {message}",
+            record.level(),
+            record.target()
+        );
+
+        println!("{line}");
+        self.write_to_file(&line, record.level());
+        self.record_recent_line(line);
+
+        let level = match record.level() {
+            Level::Error => LogLevel::Error,
+            Level::Warn => LogLevel::Warn,
+            Level::Info => LogLevel::Info,
+            Level::Debug => LogLevel::Debug,
+            Level::Trace => LogLevel::Trace,
+        };
+
+        (self.listener)(LogRecord {
+            level,
+            message,
+            target: record.target().to_string(),
+        });
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.writer.flush();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classifies_targets() {
+        assert_eq!(
+            LogTarget::from_record_target("hub_os::lua_api::battle_api"),
+            LogTarget::Lua
+        );
+        assert_eq!(LogTarget::from_record_target("netplay"), LogTarget::Netplay);
+        assert_eq!(
+            LogTarget::from_record_target("wgpu_core::device"),
+            LogTarget::Render
+        );
+        assert_eq!(
+            LogTarget::from_record_target("hub_os::scenes::main_menu_scene"),
+            LogTarget::General
+        );
+    }
+
+    #[test]
+    fn parses_filters() {
+        let filters = LogFilters::parse("warn, netplay=debug,render=trace").unwrap();
+
+        assert_eq!(filters.level(LogTarget::General), LevelFilter::Warn);
+        assert_eq!(filters.level(LogTarget::Netplay), LevelFilter::Debug);
+        assert_eq!(filters.level(LogTarget::Render), LevelFilter::Trace);
+        assert_eq!(filters.max_level(), LevelFilter::Trace);
+
+        assert_eq!(LogFilters::parse("").unwrap(), LogFilters::default());
+        assert!(LogFilters::parse("audio=info").is_err());
+        assert!(LogFilters::parse("netplay=loud").is_err());
+    }
+}
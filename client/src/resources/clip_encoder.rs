@@ -0,0 +1,74 @@
+use super::ResourcePaths;
+use framework::prelude::UVec2;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Gif delays are in hundredths of a second, spreads the rounding error across frames
+fn frame_delay(index: usize, frame_rate: usize) -> u16 {
+    let start = index * 100 / frame_rate;
+    let end = (index + 1) * 100 / frame_rate;
+
+    (end - start) as u16
+}
+
+fn encode_gif(
+    path: &str,
+    size: UVec2,
+    frames: Vec<Vec<u8>>,
+    frame_rate: usize,
+) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let writer = std::io::BufWriter::new(file);
+
+    let (width, height) = (size.x as u16, size.y as u16);
+    let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(|e| e.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|e| e.to_string())?;
+
+    for (i, mut rgba) in frames.into_iter().enumerate() {
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut rgba, 10);
+        frame.delay = frame_delay(i, frame_rate);
+
+        encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Encodes RGBA frames to a gif in the clips folder on a separate thread
+pub fn save_clip(size: UVec2, frames: Vec<Vec<u8>>, frame_rate: usize) {
+    let elapsed_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let file_name = format!("{}.gif", elapsed_time.as_millis());
+
+    log::info!("Starting background thread to save clip");
+
+    std::thread::spawn(move || {
+        let folder_path = ResourcePaths::game_folder().to_string() + ResourcePaths::CLIPS_FOLDER;
+
+        // create parent folder
+        let _ = std::fs::create_dir_all(&folder_path);
+
+        let path = folder_path + &file_name;
+
+        if let Err(e) = encode_gif(&path, size, frames, frame_rate) {
+            log::error!("Failed to save clip to {:?}: {}", path, e);
+            let _ = std::fs::remove_file(&path);
+            return;
+        }
+
+        log::info!("Saved clip to {path}");
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frame_delays_match_frame_rate() {
+        let total: u16 = (0..15).map(|i| frame_delay(i, 15)).sum();
+        assert_eq!(total, 100);
+
+        assert!((0..15).all(|i| matches!(frame_delay(i, 15), 6 | 7)));
+    }
+}
@@ -2,6 +2,10 @@ use crate::render::FrameTime;
 use framework::prelude::{Color, UVec2, Vec2};
 
 pub const DEFAULT_PACKAGE_REPO: &str = "https://hubos.dev";
+// MiB
+pub const DEFAULT_AUDIO_CACHE_LIMIT: u16 = 256;
+// MiB
+pub const DEFAULT_ASSET_MEMORY_LIMIT: u16 = 1024;
 
 // 1 MiB
 pub const BATTLE_VM_MEMORY: usize = 1024 * 1024;
@@ -15,9 +19,22 @@ pub const TRUE_RESOLUTION: UVec2 = UVec2::new(
     (RESOLUTION_F.y * DEFAULT_SCALE) as u32,
 );
 pub const MAX_VOLUME: u8 = 100;
+/// Percentages for the ui text scale setting
+pub const MIN_TEXT_SCALE: u8 = 100;
+pub const MAX_TEXT_SCALE: u8 = 200;
+pub const DEFAULT_TEXT_CHAR_DELAY: u8 = 2;
+/// How many dialogue messages are kept for the textbox backlog
+pub const DIALOGUE_BACKLOG_LIMIT: usize = 50;
+/// How many notifications are kept for review
+pub const NOTIFICATION_LIMIT: usize = 50;
+/// How many copies of previous saves are kept in the backup folder
+pub const SAVE_BACKUP_LIMIT: usize = 20;
 
 // battle
 pub const INPUT_DELAY: usize = 2;
+/// Lockstep can't correct late inputs, extra delay hides the wait for remote inputs
+pub const LOCKSTEP_INPUT_DELAY: usize = 6;
+pub const DEFAULT_NETPLAY_MAX_REDUNDANCY: u8 = 8;
 pub const BATTLE_UI_MARGIN: f32 = 2.0;
 pub const CARD_SELECT_CARD_COLS: usize = 5;
 pub const CARD_SELECT_COLS: usize = CARD_SELECT_CARD_COLS + 1;
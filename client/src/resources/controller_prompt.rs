@@ -0,0 +1,38 @@
+use framework::input::Button;
+
+/// Buttons checked when picking a gamepad by pressing a button on it
+pub const CONTROLLER_PROMPT_BUTTONS: [Button; 8] = [
+    Button::A,
+    Button::B,
+    Button::X,
+    Button::Y,
+    Button::Start,
+    Button::Select,
+    Button::DPadUp,
+    Button::DPadDown,
+];
+
+/// Shown over every scene until a gamepad is picked or a key is pressed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ControllerPrompt {
+    /// The active gamepad was disconnected
+    Reconnect,
+    /// Multiple gamepads are connected, the first to press a button becomes the active gamepad
+    Assign,
+}
+
+impl ControllerPrompt {
+    pub fn title_key(self) -> &'static str {
+        match self {
+            ControllerPrompt::Reconnect => "controller_prompt.reconnect_title",
+            ControllerPrompt::Assign => "controller_prompt.assign_title",
+        }
+    }
+
+    pub fn message_key(self) -> &'static str {
+        match self {
+            ControllerPrompt::Reconnect => "controller_prompt.reconnect_message",
+            ControllerPrompt::Assign => "controller_prompt.assign_message",
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use super::{ClientLogger, ResourcePaths};
+use crate::packages::{PackageId, PackageInfo, PackageNamespace};
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+const PENDING_FILE_NAME: &str = "pending";
+
+/// package id -> description, kept outside of Globals so the panic hook can read it
+static INSTALLED_PACKAGES: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+pub struct CrashReporter;
+
+impl CrashReporter {
+    fn folder() -> String {
+        ResourcePaths::game_folder().to_string() + ResourcePaths::CRASH_REPORT_FOLDER
+    }
+
+    /// Replaces the default panic hook with one that writes a report to the crash report folder
+    pub fn install_panic_hook() {
+        std::panic::set_hook(Box::new(|info| {
+            let backtrace = Backtrace::force_capture();
+            let panic_message = format!("{info}");
+
+            log::error!("{panic_message}\n{backtrace}");
+            log::logger().flush();
+
+            let report = Self::build_report(
+                &panic_message,
+                &backtrace.to_string(),
+                &Self::installed_packages(),
+                &ClientLogger::recent_lines(),
+            );
+
+            match Self::save_report(&report) {
+                Some(path) => eprintln!("Crash report saved at {path}"),
+                None => eprintln!("Failed to save crash report:\n{report}"),
+            }
+        }));
+    }
+
+    /// Only built-in and local packages are listed, server packages come and go with the server
+    pub fn record_package(package_info: &PackageInfo) {
+        if !Self::listed_namespace(package_info.namespace) {
+            return;
+        }
+
+        let description = format!(
+            "{:?} {:?} {}",
+            package_info.category, package_info.namespace, package_info.hash
+        );
+
+        if let Ok(mut packages) = INSTALLED_PACKAGES.lock() {
+            packages.insert(package_info.id.to_string(), description);
+        }
+    }
+
+    pub fn forget_package(namespace: PackageNamespace, id: &PackageId) {
+        if !Self::listed_namespace(namespace) {
+            return;
+        }
+
+        if let Ok(mut packages) = INSTALLED_PACKAGES.lock() {
+            packages.remove(id.as_str());
+        }
+    }
+
+    fn listed_namespace(namespace: PackageNamespace) -> bool {
+        matches!(
+            namespace,
+            PackageNamespace::BuiltIn | PackageNamespace::Local
+        )
+    }
+
+    fn installed_packages() -> Vec<String> {
+        // the panic may have happened while the list was locked
+        let Ok(packages) = INSTALLED_PACKAGES.try_lock() else {
+            return Vec::new();
+        };
+
+        (packages.iter())
+            .map(|(id, description)| format!("{id} {description}"))
+            .collect()
+    }
+
+    fn build_report(
+        panic_message: &str,
+        backtrace: &str,
+        packages: &[String],
+        log_lines: &[String],
+    ) -> String {
+        let mut report = String::new();
+
+        let _ = writeln!(report, "Version: {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(
+            report,
+            "Platform: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+
+        let _ = writeln!(report, "\n[Panic]\n{panic_message}");
+        let _ = writeln!(report, "\n[Backtrace]\n{backtrace}");
+
+        let _ = writeln!(report, "\n[Packages]");
+        for package in packages {
+            let _ = writeln!(report, "{package}");
+        }
+
+        let _ = writeln!(report, "\n[Log]");
+        for line in log_lines {
+            let _ = writeln!(report, "{line}");
+        }
+
+        report
+    }
+
+    /// Writes the report and marks it as pending, returns the path of the report
+    fn save_report(report: &str) -> Option<String> {
+        let folder = Self::folder();
+        std::fs::create_dir_all(&folder).ok()?;
+
+        let secs = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let path = format!("{folder}{secs}.txt");
+        std::fs::write(&path, report).ok()?;
+        std::fs::write(folder + PENDING_FILE_NAME, &path).ok()?;
+
+        Some(path)
+    }
+
+    /// Returns the path of a report written since the last call, the report is left in the folder
+    pub fn take_pending_report() -> Option<String> {
+        let pending_path = Self::folder() + PENDING_FILE_NAME;
+        let path = std::fs::read_to_string(&pending_path).ok()?;
+        let _ = std::fs::remove_file(&pending_path);
+
+        let path = path.trim().to_string();
+        std::path::Path::new(&path).exists().then_some(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn report_sections() {
+        let report = CrashReporter::build_report(
+            "panicked at src/lib.rs:1:1",
+            "0: main",
+            &[String::from("dev.example.card abc123")],
+            &[String::from("INFO loaded")],
+        );
+
+        let panic_index = report.find("[Panic]\npanicked at").unwrap();
+        let packages_index = report.find("[Packages]\ndev.example.card abc123").unwrap();
+        let log_index = report.find("[Log]\nINFO loaded").unwrap();
+
+        assert!(panic_index < packages_index);
+        assert!(packages_index < log_index);
+    }
+}
@@ -3,7 +3,7 @@ use crate::bindable::CardClass;
 use crate::packages::{AugmentPackage, CardPackage, PackageManager, PackageNamespace};
 use crate::saves::{Card, Deck};
 use framework::prelude::GameIO;
-use packets::structures::PackageId;
+use packets::structures::{DeckRules, PackageId};
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 
@@ -14,6 +14,8 @@ pub struct DeckRestrictions {
     pub mega_limit: usize,
     pub giga_limit: usize,
     pub dark_limit: usize,
+    #[serde(flatten)]
+    pub rules: DeckRules,
 }
 
 impl Default for DeckRestrictions {
@@ -23,6 +25,7 @@ impl Default for DeckRestrictions {
             mega_limit: 5,
             giga_limit: 1,
             dark_limit: 3,
+            rules: DeckRules::default(),
         }
     }
 }
@@ -105,6 +108,7 @@ struct DeckValidator<'a> {
     mega_count: usize,
     giga_count: usize,
     dark_count: usize,
+    total_points: usize,
     id_counts: HashMap<&'a PackageId, usize>,
     card_counts: HashMap<&'a Card, usize>,
 }
@@ -127,6 +131,7 @@ impl<'a> DeckValidator<'a> {
             mega_count: 0,
             giga_count: 0,
             dark_count: 0,
+            total_points: 0,
             id_counts: HashMap::new(),
             card_counts: HashMap::new(),
         }
@@ -153,6 +158,8 @@ impl<'a> DeckValidator<'a> {
             _ => {}
         }
 
+        self.total_points += self.deck_restrictions.rules.card_points(&card.package_id);
+
         // update package specific count
         (self.id_counts)
             .entry(&package.package_info.id)
@@ -172,6 +179,17 @@ impl<'a> DeckValidator<'a> {
             return true;
         };
 
+        // test server rules
+        let rules = &self.deck_restrictions.rules;
+
+        if rules.is_package_banned(&card.package_id) || !rules.is_code_allowed(&card.code) {
+            return false;
+        }
+
+        if rules.is_over_budget(self.total_points) && rules.card_points(&card.package_id) > 0 {
+            return false;
+        }
+
         // test class
         let class_valid = match package.card_properties.card_class {
             CardClass::Mega => self.mega_count <= self.deck_restrictions.mega_limit,
@@ -191,7 +209,7 @@ impl<'a> DeckValidator<'a> {
             .cloned()
             .unwrap_or_default();
 
-        if package_count > package.limit {
+        if package_count > rules.copy_limit(package.limit) {
             return false;
         }
 
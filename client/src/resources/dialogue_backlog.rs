@@ -0,0 +1,60 @@
+use super::DIALOGUE_BACKLOG_LIMIT;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Recent textbox messages from overworld and battle dialogue, oldest first
+#[derive(Default)]
+pub struct DialogueBacklog {
+    messages: RefCell<VecDeque<String>>,
+}
+
+impl DialogueBacklog {
+    /// Textbox effect tokens are removed, empty messages are skipped
+    pub fn record(&self, text: &str) {
+        let text: String = text
+            .chars()
+            .filter(|c| !matches!(c, '\x01'..='\x03'))
+            .collect();
+
+        let text = text.trim();
+
+        if text.is_empty() {
+            return;
+        }
+
+        let mut messages = self.messages.borrow_mut();
+
+        if messages.len() >= DIALOGUE_BACKLOG_LIMIT {
+            messages.pop_front();
+        }
+
+        messages.push_back(text.to_string());
+    }
+
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.borrow().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn records_recent_messages() {
+        let backlog = DialogueBacklog::default();
+
+        backlog.record("\x01...\x01 Hello!");
+        backlog.record(" \x02 ");
+
+        assert_eq!(backlog.messages(), ["... Hello!"]);
+
+        for i in 0..DIALOGUE_BACKLOG_LIMIT {
+            backlog.record(&i.to_string());
+        }
+
+        let messages = backlog.messages();
+        assert_eq!(messages.len(), DIALOGUE_BACKLOG_LIMIT);
+        assert_eq!(messages[0], "0");
+    }
+}
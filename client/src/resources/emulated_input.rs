@@ -1,3 +1,4 @@
+use super::Input;
 use framework::prelude::Button;
 
 // controlled by mobile_overlay.rs
@@ -6,6 +7,10 @@ pub struct EmulatedInput {
     previous_buttons: Vec<Button>,
     pressed_buttons: Vec<Button>,
     latest_button: Option<Button>,
+    /// Inputs pressed directly, skipping bindings
+    previous_inputs: Vec<Input>,
+    pressed_inputs: Vec<Input>,
+    latest_input: Option<Input>,
 }
 
 impl EmulatedInput {
@@ -13,6 +18,10 @@ impl EmulatedInput {
         std::mem::swap(&mut self.previous_buttons, &mut self.pressed_buttons);
         self.pressed_buttons.clear();
         self.latest_button = None;
+
+        std::mem::swap(&mut self.previous_inputs, &mut self.pressed_inputs);
+        self.pressed_inputs.clear();
+        self.latest_input = None;
     }
 
     pub fn emulate_button(&mut self, button: Button) {
@@ -23,19 +32,35 @@ impl EmulatedInput {
         }
     }
 
-    pub fn latest_button(&self) -> Option<Button> {
-        self.latest_button
+    pub fn emulate_input(&mut self, input: Input) {
+        self.pressed_inputs.push(input);
+
+        if !self.previous_inputs.contains(&input) {
+            self.latest_input = Some(input);
+        }
     }
 
-    pub fn is_button_down(&self, button: Button) -> bool {
-        self.pressed_buttons.contains(&button)
+    pub fn latest_input(&self) -> Option<Input> {
+        self.latest_input
+    }
+
+    pub fn was_input_down(&self, input: Input) -> bool {
+        self.previous_inputs.contains(&input)
     }
 
-    pub fn was_button_just_pressed(&self, button: Button) -> bool {
-        !self.previous_buttons.contains(&button) && self.pressed_buttons.contains(&button)
+    pub fn is_input_down(&self, input: Input) -> bool {
+        self.pressed_inputs.contains(&input)
     }
 
-    pub fn was_button_released(&self, button: Button) -> bool {
-        self.previous_buttons.contains(&button) && !self.pressed_buttons.contains(&button)
+    pub fn latest_button(&self) -> Option<Button> {
+        self.latest_button
+    }
+
+    pub fn was_button_down(&self, button: Button) -> bool {
+        self.previous_buttons.contains(&button)
+    }
+
+    pub fn is_button_down(&self, button: Button) -> bool {
+        self.pressed_buttons.contains(&button)
     }
 }
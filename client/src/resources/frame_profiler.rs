@@ -0,0 +1,139 @@
+use framework::prelude::{Duration, Instant};
+use std::collections::VecDeque;
+use strum::{EnumIter, IntoStaticStr};
+
+const HISTORY_LEN: usize = 240;
+
+#[derive(Clone, Copy, PartialEq, Eq, EnumIter, IntoStaticStr)]
+pub enum ProfileSegment {
+    Update,
+    Lua,
+    Render,
+    Netplay,
+}
+
+impl ProfileSegment {
+    pub const TOTAL: usize = 4;
+}
+
+pub type SegmentTimes = [Duration; ProfileSegment::TOTAL];
+
+/// Splits frame time into segments, time spent in a nested segment is excluded from the outer segment
+#[derive(Default)]
+pub struct FrameProfiler {
+    enabled: bool,
+    stack: Vec<(ProfileSegment, Instant)>,
+    current: SegmentTimes,
+    history: VecDeque<SegmentTimes>,
+}
+
+impl FrameProfiler {
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.stack.clear();
+        self.current = Default::default();
+        self.history.clear();
+    }
+
+    pub fn begin(&mut self, segment: ProfileSegment) {
+        if !self.enabled {
+            return;
+        }
+
+        let now = Instant::now();
+
+        // pause the outer segment
+        if let Some((outer_segment, start)) = self.stack.last_mut() {
+            self.current[*outer_segment as usize] += now - *start;
+            *start = now;
+        }
+
+        self.stack.push((segment, now));
+    }
+
+    pub fn end(&mut self) {
+        let Some((segment, start)) = self.stack.pop() else {
+            return;
+        };
+
+        let now = Instant::now();
+        self.current[segment as usize] += now - start;
+
+        // resume the outer segment
+        if let Some((_, start)) = self.stack.last_mut() {
+            *start = now;
+        }
+    }
+
+    /// Should be called outside of segments
+    pub fn end_frame(&mut self) {
+        if !self.enabled {
+            return;
+        }
+
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(std::mem::take(&mut self.current));
+    }
+
+    /// Oldest first
+    pub fn history(&self) -> impl Iterator<Item = &SegmentTimes> {
+        self.history.iter()
+    }
+
+    pub fn averages(&self) -> SegmentTimes {
+        let mut averages = SegmentTimes::default();
+
+        if self.history.is_empty() {
+            return averages;
+        }
+
+        for times in &self.history {
+            for (average, time) in averages.iter_mut().zip(times) {
+                *average += *time;
+            }
+        }
+
+        averages.map(|total| total / self.history.len() as u32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nested_segments_are_exclusive() {
+        let mut profiler = FrameProfiler::default();
+        profiler.set_enabled(true);
+
+        profiler.begin(ProfileSegment::Update);
+        profiler.begin(ProfileSegment::Lua);
+        std::thread::sleep(Duration::from_millis(5));
+        profiler.end();
+        profiler.end();
+        profiler.end_frame();
+
+        let averages = profiler.averages();
+        let update = averages[ProfileSegment::Update as usize];
+        let lua = averages[ProfileSegment::Lua as usize];
+
+        assert!(lua >= Duration::from_millis(5));
+        assert!(update < lua);
+        assert_eq!(profiler.history().count(), 1);
+
+        // ignored while disabled
+        profiler.set_enabled(false);
+        profiler.begin(ProfileSegment::Render);
+        profiler.end();
+        profiler.end_frame();
+
+        assert_eq!(profiler.history().count(), 0);
+    }
+}
@@ -14,9 +14,16 @@ pub struct GlobalMusic {
 }
 
 impl GlobalMusic {
-    pub fn load_with(sound_font_bytes: Vec<u8>, mut load: impl FnMut(&str) -> SoundBuffer) -> Self {
+    pub fn load_with(sound_font_bytes: Vec<u8>, load: impl FnMut(&str) -> SoundBuffer) -> Self {
         Self {
             sound_font: Self::load_sound_font(sound_font_bytes),
+            ..Self::load_tracks(load)
+        }
+    }
+
+    fn load_tracks(mut load: impl FnMut(&str) -> SoundBuffer) -> Self {
+        Self {
+            sound_font: None,
             main_menu: load(ResourcePaths::MAIN_MENU_MUSIC),
             customize: load(ResourcePaths::CUSTOMIZE_MUSIC),
             battle: load(ResourcePaths::BATTLE_MUSIC),
@@ -24,6 +31,18 @@ impl GlobalMusic {
         }
     }
 
+    /// Paths for every track, excluding the sound font
+    pub fn track_paths() -> Vec<String> {
+        let mut paths = Vec::new();
+
+        Self::load_tracks(|path| {
+            paths.push(path.to_string());
+            SoundBuffer::new_empty()
+        });
+
+        paths
+    }
+
     fn load_sound_font(sound_font_bytes: Vec<u8>) -> Option<Arc<SoundFont>> {
         let mut cursor = Cursor::new(sound_font_bytes);
 
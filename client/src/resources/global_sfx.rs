@@ -1,4 +1,4 @@
-use super::{ResourcePaths, SoundBuffer};
+use super::{AudioBus, ResourcePaths, SoundBuffer};
 use field_count::FieldCount;
 
 #[derive(Default, FieldCount)]
@@ -83,6 +83,31 @@ impl GlobalSfx {
         }
     }
 
+    /// Sounds that play outside of the sfx bus
+    pub fn bus_routes(&self) -> [(&SoundBuffer, AudioBus); 7] {
+        [
+            (&self.cursor_move, AudioBus::Ui),
+            (&self.cursor_select, AudioBus::Ui),
+            (&self.cursor_cancel, AudioBus::Ui),
+            (&self.cursor_error, AudioBus::Ui),
+            (&self.menu_close, AudioBus::Ui),
+            (&self.page_turn, AudioBus::Ui),
+            (&self.text_blip, AudioBus::Voice),
+        ]
+    }
+
+    /// Paths for every sound effect
+    pub fn paths() -> Vec<String> {
+        let mut paths = Vec::new();
+
+        Self::load_with(|path| {
+            paths.push(path.to_string());
+            SoundBuffer::new_empty()
+        });
+
+        paths
+    }
+
     pub fn total() -> usize {
         Self::field_count()
     }
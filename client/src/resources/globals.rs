@@ -1,17 +1,21 @@
+use crate::accessibility::ScreenReader;
 use crate::args::Args;
 use crate::battle::BattleProps;
+use crate::bindable::{Element, ElementDefinition};
 use crate::lua_api::BattleLuaApi;
 use crate::packages::*;
 use crate::render::ui::{GlyphAtlas, PackageListing};
 use crate::render::{
     BackgroundPipeline, MapPipeline, PostProcessAdjust, PostProcessAdjustConfig,
-    PostProcessColorBlindness, PostProcessGhosting, SpritePipelineCollection,
+    PostProcessColorBlindness, PostProcessFlashReduction, PostProcessGhosting,
+    SpritePipelineCollection,
 };
 use crate::resources::*;
 use crate::saves::{BlockGrid, Config, GlobalSave};
 use framework::prelude::*;
 use packets::address_parsing::uri_encode;
 use packets::structures::FileHash;
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -19,6 +23,11 @@ use super::restrictions::Restrictions;
 
 pub struct Globals {
     pub config: Config,
+    pub localization: Localization,
+    pub dialogue_backlog: DialogueBacklog,
+    pub notification_center: NotificationCenter,
+    /// Updated by scenes, saved with screenshots
+    pub screenshot_metadata: ScreenshotMetadata,
     pub post_process_adjust_config: PostProcessAdjustConfig,
     pub post_process_ghosting: f32,
     pub post_process_color_blindness: u8,
@@ -32,6 +41,7 @@ pub struct Globals {
     pub status_packages: PackageManager<StatusPackage>,
     pub tile_state_packages: PackageManager<TileStatePackage>,
     pub library_packages: PackageManager<LibraryPackage>,
+    pub emote_packages: PackageManager<EmotePackage>,
     pub resource_packages: PackageManager<ResourcePackage>,
     pub battle_api: BattleLuaApi,
 
@@ -39,6 +49,7 @@ pub struct Globals {
     pub audio: AudioManager,
     pub music: GlobalMusic,
     pub sfx: Box<GlobalSfx>,
+    pub screen_reader: ScreenReader,
 
     // assets
     pub assets: LocalAssetManager,
@@ -52,13 +63,20 @@ pub struct Globals {
 
     // input emulation
     pub emulated_input: EmulatedInput,
+    /// Set by battles to the input given to the simulation, read by the input display
+    pub displayed_input: RefCell<Option<Vec<Input>>>,
+    /// Set by the supporting service when gamepads are connected or disconnected
+    pub controller_prompt: Option<ControllerPrompt>,
 
     // networking
     pub network: Network,
     pub connected_to_server: bool,
+    pub connected_server_address: Option<String>,
 
     // debug
     pub debug_visible: bool,
+    /// Toggled with F3 + G, displayed by the profiler overlay
+    pub frame_profiler: RefCell<FrameProfiler>,
 }
 
 impl Globals {
@@ -74,7 +92,6 @@ impl Globals {
 
         let resources_mod_path = resource_packages.category().mod_path();
         resource_packages.load_packages_in_folder(
-            &assets,
             PackageNamespace::Local,
             resources_mod_path,
             |_, _| {},
@@ -83,12 +100,35 @@ impl Globals {
 
         // load config
         let config = Config::load(&assets);
-        let music_volume = config.music_volume();
-        let sfx_volume = config.sfx_volume();
+        assets.set_memory_budget(config.asset_memory_limit_bytes());
 
-        let audio = AudioManager::new(&config.audio_device)
-            .with_music_volume(music_volume)
-            .with_sfx_volume(sfx_volume);
+        // the --log flag takes priority over the config
+        if args.log.is_none() {
+            match LogFilters::parse(&config.log_filters) {
+                Ok(filters) => ClientLogger::set_filters(filters),
+                Err(e) => log::warn!("Invalid LogFilters in config.ini: {e}"),
+            }
+        }
+
+        // load client text, language packs are resource packages
+        let mut languages = Vec::new();
+        Localization::scan_languages(ResourcePaths::TRANSLATIONS_FOLDER, &mut languages);
+
+        for package in resource_packages.enabled_packages(&global_save) {
+            Localization::scan_languages(&package.translations_folder(), &mut languages);
+        }
+
+        languages.sort();
+
+        let localization = Localization::new(&assets, &config.language, languages);
+
+        let mut audio = AudioManager::new(&config.audio_backend, &config.audio_device);
+
+        for bus in AudioBus::ALL {
+            audio.set_bus_volume(bus, config.bus_volume(bus));
+        }
+
+        let screen_reader = ScreenReader::new(config.screen_reader);
 
         if config.fullscreen {
             game_io.window_mut().set_fullscreen(true);
@@ -110,9 +150,14 @@ impl Globals {
         game_io.set_post_process_enabled::<PostProcessAdjust>(enable_adjustment);
         game_io.set_post_process_enabled::<PostProcessGhosting>(enable_ghosting);
         game_io.set_post_process_enabled::<PostProcessColorBlindness>(enable_color_blindness);
+        game_io.set_post_process_enabled::<PostProcessFlashReduction>(config.flash_reduction);
 
         Self {
             config,
+            localization,
+            dialogue_backlog: DialogueBacklog::default(),
+            notification_center: NotificationCenter::default(),
+            screenshot_metadata: ScreenshotMetadata::default(),
             post_process_adjust_config,
             post_process_ghosting,
             post_process_color_blindness,
@@ -126,6 +171,7 @@ impl Globals {
             status_packages: PackageManager::new(PackageCategory::Status),
             tile_state_packages: PackageManager::new(PackageCategory::TileState),
             library_packages: PackageManager::new(PackageCategory::Library),
+            emote_packages: PackageManager::new(PackageCategory::Emote),
             resource_packages,
             battle_api: BattleLuaApi::new(),
 
@@ -133,6 +179,7 @@ impl Globals {
             audio,
             music: GlobalMusic::default(),
             sfx: Box::default(),
+            screen_reader,
 
             // assets
             glyph_atlas: Arc::new(GlyphAtlas::new_default(game_io, &assets)),
@@ -150,16 +197,54 @@ impl Globals {
 
             // input emulation
             emulated_input: EmulatedInput::default(),
+            displayed_input: RefCell::new(None),
+            controller_prompt: None,
 
             // networking
             network: Network::new(&args),
             connected_to_server: false,
+            connected_server_address: None,
 
             // debug
             debug_visible: false,
+            frame_profiler: RefCell::new(FrameProfiler::default()),
         }
     }
 
+    /// Languages with translations for client text, card packages, or player packages, sorted
+    pub fn package_languages(&self) -> Vec<String> {
+        let card_languages = (self.card_packages.namespaces())
+            .flat_map(|ns| self.card_packages.packages(ns))
+            .flat_map(|package| package.translations.languages());
+
+        let player_languages = (self.player_packages.namespaces())
+            .flat_map(|ns| self.player_packages.packages(ns))
+            .flat_map(|package| package.translations.languages());
+
+        let client_languages = self.localization.languages().iter().map(String::as_str);
+
+        let mut languages: Vec<_> = card_languages
+            .chain(player_languages)
+            .chain(client_languages)
+            .map(String::from)
+            .collect();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    /// Applies the configured language to client text and packages with translations
+    pub fn apply_language(&mut self) {
+        let language = &self.config.language;
+        self.localization.set_language(&self.assets, language);
+        self.card_packages.set_language(language);
+        self.player_packages.set_language(language);
+    }
+
+    pub fn translate(&self, key: &str) -> String {
+        self.localization.translate(key)
+    }
+
     pub fn packages(&self, namespace: PackageNamespace) -> impl Iterator<Item = &PackageInfo> {
         (self
             .augment_packages
@@ -200,6 +285,11 @@ impl Globals {
                 .packages(namespace)
                 .map(|package| &package.package_info),
         )
+        .chain(
+            self.emote_packages
+                .packages(namespace)
+                .map(|package| &package.package_info),
+        )
     }
 
     pub fn load_virtual_package(
@@ -245,6 +335,10 @@ impl Globals {
                 self.tile_state_packages
                     .load_virtual_package(&self.assets, namespace, hash)
             }
+            PackageCategory::Emote => {
+                self.emote_packages
+                    .load_virtual_package(&self.assets, namespace, hash)
+            }
         }?;
 
         // load child packages
@@ -255,7 +349,10 @@ impl Globals {
         self.character_packages
             .load_child_packages(namespace, child_packages);
 
-        self.package_info(category, namespace, &id)
+        let package_info = self.package_info(category, namespace, &id)?;
+        CrashReporter::record_package(package_info);
+
+        Some(package_info)
     }
 
     pub fn load_package(
@@ -300,6 +397,10 @@ impl Globals {
                 self.tile_state_packages
                     .load_package(&self.assets, namespace, path)
             }
+            PackageCategory::Emote => {
+                self.emote_packages
+                    .load_package(&self.assets, namespace, path)
+            }
         }?;
 
         // load child packages
@@ -310,7 +411,10 @@ impl Globals {
         self.character_packages
             .load_child_packages(namespace, child_packages);
 
-        self.package_info(category, namespace, &id)
+        let package_info = self.package_info(category, namespace, &id)?;
+        CrashReporter::record_package(package_info);
+
+        Some(package_info)
     }
 
     pub fn unload_package(
@@ -364,6 +468,10 @@ impl Globals {
                 self.tile_state_packages
                     .unload_package(&self.assets, namespace, id);
             }
+            PackageCategory::Emote => {
+                self.emote_packages
+                    .unload_package(&self.assets, namespace, id);
+            }
         }
 
         // unload child packages
@@ -373,6 +481,8 @@ impl Globals {
             self.character_packages
                 .unload_package(&self.assets, namespace, id);
         }
+
+        CrashReporter::forget_package(namespace, id);
     }
 
     // returns package info, and the namespace the package should be loaded with
@@ -542,6 +652,10 @@ impl Globals {
                 .tile_state_packages
                 .package(namespace, id)
                 .map(|package| package.package_info()),
+            PackageCategory::Emote => self
+                .emote_packages
+                .package(namespace, id)
+                .map(|package| package.package_info()),
         }
     }
 
@@ -588,6 +702,10 @@ impl Globals {
                 .tile_state_packages
                 .package_or_fallback(namespace, id)
                 .map(|package| package.package_info()),
+            PackageCategory::Emote => self
+                .emote_packages
+                .package_or_fallback(namespace, id)
+                .map(|package| package.package_info()),
         }
     }
 
@@ -635,6 +753,32 @@ impl Globals {
                 .tile_state_packages
                 .package(namespace, id)
                 .map(|package| package.create_package_listing()),
+            PackageCategory::Emote => self
+                .emote_packages
+                .package(namespace, id)
+                .map(|package| package.create_package_listing()),
+        }
+    }
+
+    /// Searches every loaded library for the element, battles use the registry in SharedBattleResources
+    pub fn element_definition(&self, element: Element) -> Option<&ElementDefinition> {
+        if !matches!(element, Element::Custom(_)) {
+            return None;
+        }
+
+        let mut namespaces = self.library_packages.namespaces();
+
+        namespaces.find_map(|ns| {
+            (self.library_packages.packages(ns))
+                .flat_map(|package| package.elements.iter())
+                .find(|definition| definition.element == element)
+        })
+    }
+
+    pub fn element_name(&self, element: Element) -> String {
+        match self.element_definition(element) {
+            Some(definition) => definition.name.clone(),
+            None => element.to_string(),
         }
     }
 
@@ -651,6 +795,7 @@ impl Globals {
             .chain(self.library_packages.namespaces())
             .chain(self.status_packages.namespaces())
             .chain(self.tile_state_packages.namespaces())
+            .chain(self.emote_packages.namespaces())
             .filter(move |ns| namespace_set.insert(*ns))
     }
 
@@ -680,6 +825,9 @@ impl Globals {
 
         self.tile_state_packages
             .remove_namespace(&self.assets, namespace);
+
+        self.emote_packages
+            .remove_namespace(&self.assets, namespace);
     }
 
     pub fn resolve_package_download_path(
@@ -708,6 +856,7 @@ impl Globals {
             .chain(self.resource_packages.package_ids(ns))
             .chain(self.status_packages.package_ids(ns))
             .chain(self.tile_state_packages.package_ids(ns))
+            .chain(self.emote_packages.package_ids(ns))
             .map(|id| uri_encode(id.as_str()))
             .collect();
 
@@ -14,24 +14,40 @@ impl<'a> InputUtil<'a> {
     pub fn new(game_io: &'a GameIO) -> Self {
         let globals = game_io.resource::<Globals>().unwrap();
 
+        Self::new_with_config(game_io, &globals.config)
+    }
+
+    /// Reads input using another set of bindings, such as for a second local player
+    pub fn new_with_config(game_io: &'a GameIO, config: &'a Config) -> Self {
+        let globals = game_io.resource::<Globals>().unwrap();
+
         Self {
             input_manager: game_io.input(),
             emulated: &globals.emulated_input,
-            config: &globals.config,
+            config,
         }
     }
 
+    /// Keyboard takes precedence, followed by the gamepad, touch buttons, then inputs pressed by touch
     pub fn latest_input(&self) -> Option<Input> {
+        let key_bindings = &self.config.key_bindings;
+        let controller_bindings = &self.config.controller_bindings;
+
+        // a source with an unbound press shouldn't hide presses from the others
         let latest_key = self.input_manager.latest_key();
         let latest_button = self.input_manager.latest_button();
-        let latest_button = latest_button.or(self.emulated.latest_button());
+        let latest_emulated_button = self.emulated.latest_button();
 
-        latest_key
-            .and_then(|key| Self::input_from_binding(&self.config.key_bindings, key))
+        (latest_key.and_then(|key| Self::input_from_binding(key_bindings, key)))
             .or_else(|| {
                 latest_button
-                    .and_then(|key| Self::input_from_binding(&self.config.controller_bindings, key))
+                    .and_then(|button| Self::input_from_binding(controller_bindings, button))
+            })
+            .or_else(|| {
+                latest_emulated_button
+                    .and_then(|button| Self::input_from_binding(controller_bindings, button))
             })
+            .or(self.emulated.latest_input())
     }
 
     fn input_from_binding<K: std::cmp::PartialEq>(
@@ -68,119 +84,68 @@ impl<'a> InputUtil<'a> {
     }
 
     pub fn is_down(&self, input: Input) -> bool {
-        let config = &self.config;
-        let input_manager = self.input_manager;
-
-        if let Some(keys) = config.key_bindings.get(&input) {
-            let is_down = keys.iter().any(|key| input_manager.is_key_down(*key));
-
-            if is_down {
-                return true;
-            }
-        }
-
-        if let Some(buttons) = config.controller_bindings.get(&input) {
-            let is_down = buttons.iter().any(|button| {
-                input_manager.is_button_down(config.controller_index, *button)
-                    || self.emulated.is_button_down(*button)
-            });
-
-            if is_down {
-                return true;
-            }
-        }
-
-        false
+        self.state(input).is_down
     }
 
     pub fn was_just_pressed(&self, input: Input) -> bool {
-        let already_down = self.any(
-            input,
-            |key| {
-                !self.input_manager.was_key_just_pressed(key) && self.input_manager.is_key_down(key)
-            },
-            |index, button| {
-                (!self.input_manager.was_button_just_pressed(index, button)
-                    && self.input_manager.is_button_down(index, button))
-                    || (!self.emulated.was_button_just_pressed(button)
-                        && self.emulated.is_button_down(button))
-            },
-        );
-
-        if already_down {
-            // handle multiple bindings to the same input
-            // prevent was_just_pressed from activating if other bindings are already pressed
-            return false;
-        }
-
-        self.any(
-            input,
-            |key| self.input_manager.was_key_just_pressed(key),
-            |index, button| {
-                self.input_manager.was_button_just_pressed(index, button)
-                    || self.emulated.was_button_just_pressed(button)
-            },
-        )
+        self.state(input).was_just_pressed()
     }
 
     pub fn was_released(&self, input: Input) -> bool {
-        // handle multiple bindings to the same input, nothing should still be down
-        if self.is_down(input) {
-            return false;
-        }
-
-        self.any(
-            input,
-            |key| self.input_manager.was_key_released(key),
-            |index, button| {
-                self.input_manager.was_button_released(index, button)
-                    || self.emulated.was_button_released(button)
-            },
-        )
+        self.state(input).was_released()
     }
 
+    /// Ignores the keyboard, touch buttons emulating the gamepad are included
     pub fn controller_just_pressed(&self, input: Input) -> bool {
-        let config = &self.config;
-        let input_manager = self.input_manager;
-
-        if let Some(buttons) = config.controller_bindings.get(&input) {
-            let just_pressed = buttons.iter().any(|button| {
-                input_manager.was_button_just_pressed(config.controller_index, *button)
-                    || self.emulated.was_button_just_pressed(*button)
-            });
-
-            if just_pressed {
-                return true;
-            }
-        }
-
-        false
+        self.button_state(input).was_just_pressed()
     }
 
-    fn any(
-        &self,
-        input: Input,
-        key_callback: impl Fn(Key) -> bool,
-        button_callback: impl Fn(usize, Button) -> bool,
-    ) -> bool {
-        let config = &self.config;
+    /// Every source merged, an input is held as long as any source holds it
+    fn state(&self, input: Input) -> InputState {
+        let emulated = InputState {
+            was_down: self.emulated.was_input_down(input),
+            is_down: self.emulated.is_input_down(input),
+            tapped: false,
+        };
 
-        if let Some(keys) = config.key_bindings.get(&input) {
-            if keys.iter().any(|key| key_callback(*key)) {
-                return true;
-            }
-        }
+        self.key_state(input)
+            .merge(self.button_state(input))
+            .merge(emulated)
+    }
 
-        if let Some(buttons) = config.controller_bindings.get(&input) {
-            if buttons
-                .iter()
-                .any(|button| button_callback(config.controller_index, *button))
-            {
-                return true;
-            }
-        }
+    fn key_state(&self, input: Input) -> InputState {
+        let input_manager = self.input_manager;
+        let keys = self.config.key_bindings.get(&input);
+
+        (keys.into_iter().flatten()).fold(InputState::default(), |state, &key| {
+            state.merge(InputState::from_edges(
+                input_manager.is_key_down(key),
+                input_manager.was_key_just_pressed(key),
+                input_manager.was_key_released(key),
+            ))
+        })
+    }
 
-        false
+    fn button_state(&self, input: Input) -> InputState {
+        let input_manager = self.input_manager;
+        let index = self.config.controller_index;
+        let buttons = self.config.controller_bindings.get(&input);
+
+        (buttons.into_iter().flatten()).fold(InputState::default(), |state, &button| {
+            let emulated = InputState {
+                was_down: self.emulated.was_button_down(button),
+                is_down: self.emulated.is_button_down(button),
+                tapped: false,
+            };
+
+            state
+                .merge(InputState::from_edges(
+                    input_manager.is_button_down(index, button),
+                    input_manager.was_button_just_pressed(index, button),
+                    input_manager.was_button_released(index, button),
+                ))
+                .merge(emulated)
+        })
     }
 
     pub fn as_axis(&self, negative: Input, positive: Input) -> f32 {
@@ -197,3 +162,75 @@ impl<'a> InputUtil<'a> {
         value
     }
 }
+
+/// An input's state for one frame, merged from one or more sources
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+struct InputState {
+    was_down: bool,
+    is_down: bool,
+    /// Pressed and released between frames
+    tapped: bool,
+}
+
+impl InputState {
+    fn from_edges(is_down: bool, just_pressed: bool, released: bool) -> Self {
+        Self {
+            was_down: if is_down {
+                !just_pressed
+            } else {
+                released && !just_pressed
+            },
+            is_down,
+            tapped: just_pressed && released && !is_down,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            was_down: self.was_down || other.was_down,
+            is_down: self.is_down || other.is_down,
+            tapped: self.tapped || other.tapped,
+        }
+    }
+
+    /// Handing an input from one source to another in the same frame doesn't press it again
+    fn was_just_pressed(&self) -> bool {
+        !self.was_down && (self.is_down || self.tapped)
+    }
+
+    fn was_released(&self) -> bool {
+        !self.is_down && (self.was_down || self.tapped)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InputState;
+
+    #[test]
+    fn merged_sources_share_edges() {
+        let held = InputState::from_edges(true, false, false);
+        let pressed = InputState::from_edges(true, true, false);
+        let released = InputState::from_edges(false, false, true);
+        let tapped = InputState::from_edges(false, true, true);
+        let idle = InputState::default();
+
+        // pressing on a second source while the first is held
+        assert!(!held.merge(pressed).was_just_pressed());
+        assert!(pressed.merge(idle).was_just_pressed());
+
+        // handing off from one source to another
+        let handoff = released.merge(pressed);
+        assert!(!handoff.was_just_pressed());
+        assert!(!handoff.was_released());
+
+        // releasing one of two held sources
+        assert!(!released.merge(held).was_released());
+        assert!(released.merge(idle).was_released());
+
+        // taps shorter than a frame still register both edges
+        assert!(tapped.was_just_pressed());
+        assert!(tapped.was_released());
+        assert!(!tapped.merge(held).was_just_pressed());
+    }
+}
@@ -0,0 +1,158 @@
+use super::ResourcePaths;
+use packets::structures::{FileHash, PackageCategory, PackageId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::UNIX_EPOCH;
+
+/// Reads `<hash> <path>` lines from the resource manifest, invalid lines are skipped
+pub fn parse_integrity_manifest(text: &str) -> Vec<(FileHash, &str)> {
+    text.lines()
+        .flat_map(|line| {
+            let (hash, path) = line.trim().split_once(' ')?;
+            Some((FileHash::from_hex(hash)?, path.trim()))
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct HashedFile {
+    len: u64,
+    /// Milliseconds since the unix epoch
+    modified: u64,
+    hash: FileHash,
+}
+
+/// Problems found while verifying files during boot
+#[derive(Default)]
+pub struct IntegrityReport {
+    /// Built-in resources that are missing or don't match the manifest
+    pub resources: Vec<String>,
+    /// Packages installed from the repo that changed since they were installed, paired with the installed hash
+    pub packages: Vec<(PackageCategory, PackageId, FileHash)>,
+}
+
+impl IntegrityReport {
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty() && self.packages.is_empty()
+    }
+}
+
+/// Stored hashes used to avoid rereading unchanged files on every boot
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntegrityCache {
+    #[serde(skip)]
+    modified: bool,
+    files: HashMap<String, HashedFile>,
+    /// Hashes of packages as they were after installing from the repo
+    installed_packages: HashMap<PackageId, FileHash>,
+}
+
+impl IntegrityCache {
+    pub fn load() -> Self {
+        let path = ResourcePaths::INTEGRITY_CACHE;
+
+        match std::fs::read(path) {
+            Ok(bytes) => rmp_serde::from_slice(&bytes).unwrap_or_else(|e| {
+                log::error!("Failed to load {path:?}: {e}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Writes to disk if anything changed since loading
+    pub fn save(&mut self) {
+        if !self.modified {
+            return;
+        }
+
+        let path = ResourcePaths::INTEGRITY_CACHE;
+
+        if let Some(parent_path) = ResourcePaths::parent(path) {
+            let _ = std::fs::create_dir_all(parent_path);
+        }
+
+        let bytes = match rmp_serde::to_vec_named(self) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::error!("Failed to serialize {path:?}: {e}");
+                return;
+            }
+        };
+
+        if let Err(e) = std::fs::write(path, bytes) {
+            log::error!("Failed to save {path:?}: {e}");
+            return;
+        }
+
+        self.modified = false;
+    }
+
+    /// Reuses the stored hash while the size and modified time match, otherwise reads and hashes the file
+    pub fn file_hash(&mut self, path: &str) -> Option<FileHash> {
+        let metadata = std::fs::metadata(path).ok()?;
+
+        let len = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        if let Some(file) = self.files.get(path) {
+            if file.len == len && file.modified == modified {
+                return Some(file.hash);
+            }
+        }
+
+        let bytes = std::fs::read(path).ok()?;
+        let hash = FileHash::hash(&bytes);
+
+        let file = HashedFile {
+            len,
+            modified,
+            hash,
+        };
+
+        self.files.insert(path.to_string(), file);
+        self.modified = true;
+
+        Some(hash)
+    }
+
+    pub fn forget_file(&mut self, path: &str) {
+        self.modified |= self.files.remove(path).is_some();
+    }
+
+    pub fn installed_hash(&self, id: &PackageId) -> Option<FileHash> {
+        self.installed_packages.get(id).copied()
+    }
+
+    pub fn record_package(&mut self, id: PackageId, hash: FileHash) {
+        self.installed_packages.insert(id, hash);
+        self.modified = true;
+    }
+
+    pub fn forget_package(&mut self, id: &PackageId) {
+        self.modified |= self.installed_packages.remove(id).is_some();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_manifest() {
+        let hash = FileHash::hash(b"test");
+        let text = format!("{hash} resources/a b.png\n\ninvalid resources/b.png\n{hash}\n");
+
+        let entries = parse_integrity_manifest(&text);
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].0 == hash);
+        assert_eq!(entries[0].1, "resources/a b.png");
+    }
+}
@@ -3,7 +3,7 @@ use crate::render::ui::GlyphAtlas;
 use super::*;
 use framework::prelude::*;
 use packets::structures::{AssetDataType, FileHash, TextureAnimPathPair};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fs;
 use std::sync::Arc;
@@ -25,9 +25,11 @@ struct VirtualZipTracking {
 pub struct LocalAssetManager {
     loaded_zips: RefCell<HashMap<FileHash, VirtualZipTracking>>,
     text_cache: RefCell<HashMap<Arc<str>, Arc<str>>>,
-    texture_cache: RefCell<HashMap<Arc<str>, Arc<Texture>>>,
-    sound_cache: RefCell<HashMap<Arc<str>, SoundBuffer>>,
+    texture_cache: RefCell<AssetCache<Arc<Texture>>>,
+    sound_cache: RefCell<AssetCache<SoundBuffer>>,
     glyph_atlases: RefCell<HashMap<TextureAnimPathPair<'static>, Arc<GlyphAtlas>>>,
+    // bytes shared by textures and sounds
+    memory_budget: Cell<usize>,
 }
 
 impl LocalAssetManager {
@@ -35,15 +37,16 @@ impl LocalAssetManager {
         let text: HashMap<Arc<str>, Arc<str>> =
             HashMap::from([(Arc::from(ResourcePaths::BLANK), Arc::from(""))]);
 
-        let textures: HashMap<Arc<str>, Arc<Texture>> = HashMap::from([(
+        let mut textures = AssetCache::default();
+        textures.insert_pinned(
             ResourcePaths::BLANK.into(),
             RenderTarget::new(game_io, UVec2::new(1, 1))
                 .texture()
                 .clone(),
-        )]);
+        );
 
-        let sounds: HashMap<Arc<str>, SoundBuffer> =
-            HashMap::from([(ResourcePaths::BLANK.into(), SoundBuffer::new_empty())]);
+        let mut sounds = AssetCache::default();
+        sounds.insert_pinned(ResourcePaths::BLANK.into(), SoundBuffer::new_empty());
 
         Self {
             loaded_zips: RefCell::new(HashMap::new()),
@@ -51,9 +54,62 @@ impl LocalAssetManager {
             texture_cache: RefCell::new(textures),
             sound_cache: RefCell::new(sounds),
             glyph_atlases: Default::default(),
+            memory_budget: Cell::new(usize::MAX),
         }
     }
 
+    pub fn set_memory_budget(&self, bytes: usize) {
+        self.memory_budget.set(bytes);
+        self.enforce_memory_budget();
+    }
+
+    /// Evicts the least recently used textures and sounds until the caches fit in the budget,
+    /// assets held outside of the cache are kept
+    fn enforce_memory_budget(&self) {
+        let budget = self.memory_budget.get();
+        let mut texture_cache = self.texture_cache.borrow_mut();
+        let mut sound_cache = self.sound_cache.borrow_mut();
+
+        let mut total_bytes = texture_cache.total_bytes() + sound_cache.total_bytes();
+
+        if total_bytes <= budget {
+            return;
+        }
+
+        let texture_candidates = texture_cache
+            .eviction_candidates()
+            .map(|(last_used, path)| (last_used, path, true));
+
+        let sound_candidates = sound_cache
+            .eviction_candidates()
+            .map(|(last_used, path)| (last_used, path, false));
+
+        let mut candidates: Vec<_> = texture_candidates.chain(sound_candidates).collect();
+        candidates.sort_by_key(|(last_used, ..)| *last_used);
+
+        let mut evicted = 0;
+
+        for (_, path, is_texture) in candidates {
+            if total_bytes <= budget {
+                break;
+            }
+
+            if is_texture {
+                texture_cache.remove(&path);
+            } else {
+                sound_cache.remove(&path);
+            }
+
+            total_bytes = texture_cache.total_bytes() + sound_cache.total_bytes();
+            evicted += 1;
+        }
+
+        log::debug!(
+            "Evicted {evicted} assets, {} MiB cached",
+            total_bytes / 1024 / 1024
+        );
+    }
+
     pub fn contains_virtual_zip(&self, hash: &FileHash) -> bool {
         self.loaded_zips.borrow().contains_key(hash)
     }
@@ -171,7 +227,7 @@ impl LocalAssetManager {
 
                     match Texture::load_from_memory(game_io, &bytes) {
                         Ok(texture) => {
-                            texture_cache.insert(virtual_path.clone(), texture);
+                            texture_cache.insert_pinned(virtual_path.clone(), texture);
                             virtual_files.push(virtual_path);
                         }
                         Err(err) => {
@@ -186,7 +242,7 @@ impl LocalAssetManager {
                     let read_result = file.read_to_end(&mut bytes);
 
                     let sound = SoundBuffer::decode(game_io, bytes);
-                    sound_cache.insert(virtual_path.clone(), sound);
+                    sound_cache.insert_pinned(virtual_path.clone(), sound);
                     virtual_files.push(virtual_path);
 
                     read_result
@@ -212,17 +268,65 @@ impl LocalAssetManager {
         meta
     }
 
+    /// Reads and decodes uncached audio across threads, later calls to non_midi_audio will use the cache
+    pub fn preload_non_midi_audio(&self, paths: &[String], mut progress: impl FnMut(usize, usize)) {
+        let mut sound_cache = self.sound_cache.borrow_mut();
+
+        let uncached_paths: Vec<_> = paths
+            .iter()
+            .filter(|path| !sound_cache.contains_key(path.as_str()))
+            .collect();
+
+        let cached_total = paths.len() - uncached_paths.len();
+
+        for completed in 1..=cached_total {
+            progress(completed, paths.len());
+        }
+
+        let sounds = parallel_map(
+            uncached_paths.clone(),
+            |path| SoundBuffer::load_non_midi(path),
+            |completed, total| progress(cached_total + completed, cached_total + total),
+        );
+
+        for (path, sound) in uncached_paths.into_iter().zip(sounds) {
+            sound_cache.insert(path.as_str().into(), sound);
+        }
+
+        drop(sound_cache);
+        self.enforce_memory_budget();
+    }
+
     pub fn non_midi_audio(&self, path: &str) -> SoundBuffer {
         let mut sound_cache = self.sound_cache.borrow_mut();
 
         if let Some(sound) = sound_cache.get(path) {
-            sound.clone()
-        } else {
-            let bytes = fs::read(path).unwrap_or_default();
-            let sound = SoundBuffer::decode_non_midi(bytes);
-            sound_cache.insert(path.into(), sound.clone());
-            sound
+            return sound;
         }
+
+        let sound = SoundBuffer::load_non_midi(path);
+        sound_cache.insert(path.into(), sound.clone());
+
+        drop(sound_cache);
+        self.enforce_memory_budget();
+
+        sound
+    }
+
+    /// Reads text from disk without touching the cache, usable from any thread
+    /// True for cached text, such as files from virtual zips and resource packages, or files on disk
+    pub fn text_exists(&self, path: &str) -> bool {
+        self.text_cache.borrow().contains_key(path) || std::path::Path::new(path).is_file()
+    }
+
+    pub fn read_text(path: &str) -> String {
+        let res = fs::read_to_string(path);
+
+        if let Err(err) = &res {
+            log::warn!("Failed to load {:?}: {}", ResourcePaths::shorten(path), err);
+        }
+
+        res.unwrap_or_default()
     }
 
     pub fn clear_local_mod_assets(&self) {
@@ -234,8 +338,8 @@ impl LocalAssetManager {
         let base_mod_folder = ResourcePaths::clean_folder(&base_mod_folder);
 
         text_cache.retain(|key, _| !key.starts_with(&base_mod_folder));
-        texture_cache.retain(|key, _| !key.starts_with(&base_mod_folder));
-        sound_cache.retain(|key, _| !key.starts_with(&base_mod_folder));
+        texture_cache.retain(|key| !key.starts_with(&base_mod_folder));
+        sound_cache.retain(|key| !key.starts_with(&base_mod_folder));
     }
 
     pub fn override_cache(&self, game_io: &GameIO, path: &str, file_path: &str) {
@@ -263,10 +367,10 @@ impl LocalAssetManager {
                 let mut texture_cache = self.texture_cache.borrow_mut();
 
                 if let Ok(texture) = Texture::load_from_memory(game_io, &bytes) {
-                    texture_cache.insert(path.into(), texture.clone());
+                    texture_cache.insert_pinned(path.into(), texture.clone());
 
                     let absolute_path = ResourcePaths::game_folder().to_owned() + path;
-                    texture_cache.insert(absolute_path.into(), texture);
+                    texture_cache.insert_pinned(absolute_path.into(), texture);
                 } else {
                     log::error!("Failed to load texture: {file_path}");
                 }
@@ -275,10 +379,10 @@ impl LocalAssetManager {
                 let mut sound_cache = self.sound_cache.borrow_mut();
 
                 let sound = SoundBuffer::decode(game_io, bytes);
-                sound_cache.insert(path.into(), sound.clone());
+                sound_cache.insert_pinned(path.into(), sound.clone());
 
                 let absolute_path = ResourcePaths::game_folder().to_owned() + path;
-                sound_cache.insert(absolute_path.into(), sound);
+                sound_cache.insert_pinned(absolute_path.into(), sound);
             }
             AssetDataType::Data => {
                 // used for zips, ignore
@@ -315,13 +419,7 @@ impl AssetManager for LocalAssetManager {
         if let Some(text) = text_cache.get(path) {
             text.to_string()
         } else {
-            let res = fs::read_to_string(path);
-
-            if let Err(err) = &res {
-                log::warn!("Failed to load {:?}: {}", ResourcePaths::shorten(path), err);
-            }
-
-            let text = res.unwrap_or_default();
+            let text = Self::read_text(path);
 
             text_cache.insert(path.into(), text.clone().into());
             text
@@ -332,33 +430,40 @@ impl AssetManager for LocalAssetManager {
         let mut texture_cache = self.texture_cache.borrow_mut();
 
         if let Some(texture) = texture_cache.get(path) {
-            texture.clone()
-        } else {
-            let bytes = fs::read(path).unwrap_or_default();
-            let texture = match Texture::load_from_memory(game_io, &bytes) {
-                Ok(texture) => texture,
-                Err(err) => {
-                    log::warn!("Failed to load {:?}: {}", ResourcePaths::shorten(path), err);
-                    texture_cache.get(ResourcePaths::BLANK).unwrap().clone()
-                }
-            };
-
-            texture_cache.insert(path.into(), texture.clone());
-            texture
+            return texture;
         }
+
+        let bytes = fs::read(path).unwrap_or_default();
+        let texture = match Texture::load_from_memory(game_io, &bytes) {
+            Ok(texture) => texture,
+            Err(err) => {
+                log::warn!("Failed to load {:?}: {}", ResourcePaths::shorten(path), err);
+                texture_cache.get(ResourcePaths::BLANK).unwrap()
+            }
+        };
+
+        texture_cache.insert(path.into(), texture.clone());
+
+        drop(texture_cache);
+        self.enforce_memory_budget();
+
+        texture
     }
 
     fn audio(&self, game_io: &GameIO, path: &str) -> SoundBuffer {
         let mut sound_cache = self.sound_cache.borrow_mut();
 
         if let Some(sound) = sound_cache.get(path) {
-            sound.clone()
-        } else {
-            let bytes = fs::read(path).unwrap_or_default();
-            let sound = SoundBuffer::decode(game_io, bytes);
-            sound_cache.insert(path.into(), sound.clone());
-            sound
+            return sound;
         }
+
+        let sound = SoundBuffer::load(game_io, path);
+        sound_cache.insert(path.into(), sound.clone());
+
+        drop(sound_cache);
+        self.enforce_memory_budget();
+
+        sound
     }
 
     fn glyph_atlas(
@@ -0,0 +1,265 @@
+use super::{LocalAssetManager, ResourcePaths};
+use crate::packages::normalize_language;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Used when a key is missing from the selected language
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Keyed text read from a translation file, nested tables are flattened into dotted keys
+#[derive(Default, Clone)]
+pub struct StringTable {
+    strings: HashMap<String, String>,
+}
+
+impl StringTable {
+    pub fn parse(source: &str) -> Result<Self, toml::de::Error> {
+        let table: toml::Table = source.parse()?;
+
+        let mut strings = HashMap::new();
+        Self::flatten("", table, &mut strings);
+
+        Ok(Self { strings })
+    }
+
+    fn flatten(prefix: &str, table: toml::Table, strings: &mut HashMap<String, String>) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() {
+                key
+            } else {
+                format!("{prefix}.{key}")
+            };
+
+            match value {
+                toml::Value::String(text) => {
+                    strings.insert(key, text);
+                }
+                toml::Value::Table(table) => Self::flatten(&key, table, strings),
+                _ => log::warn!("Expected text for translation key {key:?}"),
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+}
+
+/// Replaces `{name}` placeholders with matching args, unmatched placeholders are kept
+pub fn format_translation<K: AsRef<str>, V: AsRef<str>>(text: &str, args: &[(K, V)]) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut remaining = text;
+
+    while let Some(start) = remaining.find('{') {
+        output.push_str(&remaining[..start]);
+        remaining = &remaining[start..];
+
+        let Some(end) = remaining.find('}') else {
+            break;
+        };
+
+        let name = &remaining[1..end];
+
+        match args.iter().find(|(key, _)| key.as_ref() == name) {
+            Some((_, value)) => output.push_str(value.as_ref()),
+            None => output.push_str(&remaining[..=end]),
+        }
+
+        remaining = &remaining[end + 1..];
+    }
+
+    output.push_str(remaining);
+    output
+}
+
+/// Client text for the configured language, language packs are resource packages
+/// providing `resources/translations/<language>.toml`
+#[derive(Default)]
+pub struct Localization {
+    language: String,
+    languages: Vec<String>,
+    /// Searched in order, ending with the fallback language
+    tables: Vec<StringTable>,
+    /// Tables shipped by packages in `translations/`, keyed by the package folder
+    package_tables: RefCell<HashMap<String, Vec<StringTable>>>,
+}
+
+impl Localization {
+    /// `languages` lists every language with a translation file, including ones from language packs
+    pub fn new(assets: &LocalAssetManager, language: &str, languages: Vec<String>) -> Self {
+        let mut localization = Self {
+            languages,
+            ..Default::default()
+        };
+
+        localization.set_language(assets, language);
+        localization
+    }
+
+    /// Adds languages with a `<language>.toml` file in the folder
+    pub fn scan_languages(folder: &str, languages: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(folder) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "toml")
+            {
+                let Some(stem) = path.file_stem() else {
+                    continue;
+                };
+
+                let language = normalize_language(&stem.to_string_lossy());
+
+                if !languages.contains(&language) {
+                    languages.push(language);
+                }
+            }
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Languages available for client text
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    pub fn set_language(&mut self, assets: &LocalAssetManager, language: &str) {
+        self.language = normalize_language(language);
+        self.package_tables.borrow_mut().clear();
+
+        self.tables = Self::language_chain(&self.language)
+            .into_iter()
+            .filter(|language| self.languages.contains(language))
+            .flat_map(|language| {
+                let path = format!("{}{language}.toml", ResourcePaths::TRANSLATIONS_FOLDER);
+                Self::load_table(assets, &path)
+            })
+            .collect();
+    }
+
+    /// The exact language, the base language ("pt" for "pt-br"), then the fallback language
+    fn language_chain(language: &str) -> Vec<String> {
+        let base_language = language.split('-').next().unwrap_or_default();
+        let mut chain = Vec::new();
+
+        for language in [language, base_language, FALLBACK_LANGUAGE] {
+            if !language.is_empty() && !chain.iter().any(|l| l == language) {
+                chain.push(language.to_string());
+            }
+        }
+
+        chain
+    }
+
+    fn load_table(assets: &LocalAssetManager, path: &str) -> Option<StringTable> {
+        if !assets.text_exists(path) {
+            return None;
+        }
+
+        match StringTable::parse(&assets.text(path)) {
+            Ok(table) => Some(table),
+            Err(e) => {
+                log::error!("Failed to parse {:?}:\n{e}", ResourcePaths::shorten(path));
+                None
+            }
+        }
+    }
+
+    fn resolve<'a>(tables: &'a [StringTable], key: &str) -> Option<&'a str> {
+        tables.iter().find_map(|table| table.get(key))
+    }
+
+    /// Falls back to the key if no translation exists
+    pub fn translate(&self, key: &str) -> String {
+        Self::resolve(&self.tables, key).unwrap_or(key).to_string()
+    }
+
+    pub fn translate_with<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        key: &str,
+        args: &[(K, V)],
+    ) -> String {
+        format_translation(&self.translate(key), args)
+    }
+
+    /// Prefers text from the package's `translations/` folder, followed by client text
+    pub fn translate_for_package<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        assets: &LocalAssetManager,
+        folder_path: &str,
+        key: &str,
+        args: &[(K, V)],
+    ) -> String {
+        let mut package_tables = self.package_tables.borrow_mut();
+
+        let tables = package_tables
+            .entry(folder_path.to_string())
+            .or_insert_with(|| {
+                Self::language_chain(&self.language)
+                    .into_iter()
+                    .flat_map(|language| {
+                        let path = format!("{folder_path}translations/{language}.toml");
+                        Self::load_table(assets, &path)
+                    })
+                    .collect()
+            });
+
+        let text = Self::resolve(tables, key)
+            .or_else(|| Self::resolve(&self.tables, key))
+            .unwrap_or(key);
+
+        format_translation(text, args)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flattens_nested_tables() {
+        let table = StringTable::parse(
+            r#"
+            title = "Hub OS"
+
+            [config.category]
+            video = "Video"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(table.get("title"), Some("Hub OS"));
+        assert_eq!(table.get("config.category.video"), Some("Video"));
+        assert_eq!(table.get("config.category"), None);
+    }
+
+    #[test]
+    fn formats_named_args() {
+        let args = [("name", "Cannon"), ("1", "3")];
+
+        assert_eq!(
+            format_translation("{name} x{1} {missing}", &args),
+            "Cannon x3 {missing}"
+        );
+        assert_eq!(
+            format_translation("unclosed {name", &args),
+            "unclosed {name"
+        );
+        assert_eq!(format_translation("", &args), "");
+    }
+
+    #[test]
+    fn chains_languages() {
+        assert_eq!(Localization::language_chain("pt-br"), ["pt-br", "pt", "en"]);
+        assert_eq!(Localization::language_chain("en"), ["en"]);
+        assert_eq!(Localization::language_chain(""), ["en"]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+/// Tags are stored in the first few pages of a file
+pub const LOOP_POINTS_SEARCH_LEN: usize = 64 * 1024;
+
+const COMMENT_HEADERS: [&[u8]; 2] = [b"\x03vorbis", b"OpusTags"];
+
+/// Loop points read from LOOPSTART and LOOPLENGTH or LOOPEND tags, measured in sample frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopPoints {
+    pub start: usize,
+    /// Exclusive, loops at the end of the track if unset
+    pub end: Option<usize>,
+}
+
+impl LoopPoints {
+    /// Reads loop tags from vorbis comments, `bytes` only needs to contain the start of the file
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let mut start = None;
+        let mut length = None;
+        let mut end = None;
+
+        for comment in Self::read_comments(bytes)? {
+            let Some((key, value)) = comment.split_once('=') else {
+                continue;
+            };
+
+            let Ok(value) = value.trim().parse::<usize>() else {
+                continue;
+            };
+
+            match key.to_ascii_uppercase().as_str() {
+                "LOOPSTART" | "LOOP_START" => start = Some(value),
+                "LOOPLENGTH" | "LOOP_LENGTH" => length = Some(value),
+                "LOOPEND" | "LOOP_END" => end = Some(value),
+                _ => {}
+            }
+        }
+
+        let start = start?;
+        let end = length.map(|length| start + length).or(end);
+
+        if end.is_some_and(|end| end <= start) {
+            return None;
+        }
+
+        Some(Self { start, end })
+    }
+
+    fn read_comments(bytes: &[u8]) -> Option<Vec<String>> {
+        let (header_index, header) = COMMENT_HEADERS.iter().find_map(|header| {
+            let index = bytes
+                .windows(header.len())
+                .position(|window| window == *header)?;
+
+            Some((index, header))
+        })?;
+
+        let mut reader = &bytes[header_index + header.len()..];
+
+        let vendor_len = read_u32(&mut reader)? as usize;
+        reader = reader.get(vendor_len..)?;
+
+        let comment_count = read_u32(&mut reader)?;
+        let mut comments = Vec::new();
+
+        for _ in 0..comment_count {
+            let len = read_u32(&mut reader)? as usize;
+            let comment = reader.get(..len)?;
+            reader = &reader[len..];
+
+            comments.push(String::from_utf8_lossy(comment).into_owned());
+        }
+
+        Some(comments)
+    }
+
+    /// Range of interleaved samples, `sample_count` is used when the end is unset
+    pub fn sample_range(&self, channels: u16, sample_count: usize) -> Range<usize> {
+        let channels = channels as usize;
+        let start = self.start * channels;
+        let end = self
+            .end
+            .map(|end| end * channels)
+            .unwrap_or(sample_count)
+            .min(sample_count);
+
+        start.min(end)..end
+    }
+}
+
+fn read_u32(reader: &mut &[u8]) -> Option<u32> {
+    let bytes = reader.get(..4)?;
+    *reader = &reader[4..];
+
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_header(comments: &[&str]) -> Vec<u8> {
+        let vendor = b"test";
+
+        // garbage before the header, such as an ogg page header
+        let mut bytes = b"OggS\0\0\x03vorbis".to_vec();
+        bytes.extend((vendor.len() as u32).to_le_bytes());
+        bytes.extend(vendor);
+        bytes.extend((comments.len() as u32).to_le_bytes());
+
+        for comment in comments {
+            bytes.extend((comment.len() as u32).to_le_bytes());
+            bytes.extend(comment.as_bytes());
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn reads_loop_tags() {
+        let bytes = create_header(&["TITLE=Song", "LOOPSTART=100", "LOOPLENGTH=50"]);
+        let loop_points = LoopPoints::parse(&bytes);
+
+        assert_eq!(
+            loop_points,
+            Some(LoopPoints {
+                start: 100,
+                end: Some(150)
+            })
+        );
+
+        let bytes = create_header(&["loopend=300", "loopstart=100"]);
+        let loop_points = LoopPoints::parse(&bytes).unwrap();
+
+        assert_eq!(loop_points.end, Some(300));
+        assert_eq!(loop_points.sample_range(2, 1000), 200..600);
+        assert_eq!(loop_points.sample_range(2, 400), 200..400);
+    }
+
+    #[test]
+    fn rejects_missing_and_invalid_tags() {
+        assert_eq!(LoopPoints::parse(&create_header(&["TITLE=Song"])), None);
+        assert_eq!(LoopPoints::parse(&create_header(&["LOOPSTART=x"])), None);
+        assert_eq!(
+            LoopPoints::parse(&create_header(&["LOOPSTART=20", "LOOPEND=10"])),
+            None
+        );
+        assert_eq!(LoopPoints::parse(b"MThd"), None);
+
+        // truncated comment
+        let mut bytes = create_header(&["LOOPSTART=20"]);
+        bytes.pop();
+        assert_eq!(LoopPoints::parse(&bytes), None);
+
+        // start only
+        let loop_points = LoopPoints::parse(&create_header(&["LOOPSTART=20"])).unwrap();
+        assert_eq!(loop_points.sample_range(1, 100), 20..100);
+    }
+}
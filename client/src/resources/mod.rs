@@ -1,32 +1,68 @@
+mod asset_cache;
 mod asset_manager;
 mod audio_manager;
+mod audio_mixer;
+mod audio_placement;
 mod boot_thread;
+mod client_logger;
+mod clip_encoder;
 mod constants;
+mod controller_prompt;
+mod crash_reporter;
 mod deck_restrictions;
+mod dialogue_backlog;
 mod emulated_input;
+mod frame_profiler;
 mod global_music;
 mod global_sfx;
 mod globals;
 mod input_util;
+mod integrity;
 mod local_asset_manager;
+mod localization;
+mod loop_points;
 mod network;
+mod network_conditions;
+mod notification_center;
+mod png_encoder;
 mod resource_paths;
 mod restrictions;
+mod save_profiles;
+mod screenshots;
 mod sound_buffer;
+mod stun;
+mod thread_pool;
 
+pub use asset_cache::*;
 pub use asset_manager::*;
 pub use audio_manager::*;
+pub use audio_mixer::*;
+pub use audio_placement::*;
 pub use boot_thread::*;
+pub use client_logger::*;
+pub use clip_encoder::*;
 pub use constants::*;
+pub use controller_prompt::*;
+pub use crash_reporter::*;
 pub use deck_restrictions::*;
+pub use dialogue_backlog::*;
 pub use emulated_input::*;
+pub use frame_profiler::*;
 pub use global_music::*;
 pub use global_sfx::*;
 pub use globals::*;
 pub use input_util::*;
+pub use integrity::*;
 pub use local_asset_manager::*;
+pub use localization::*;
+pub use loop_points::*;
 pub use network::*;
+pub use notification_center::*;
 pub use packets::structures::Input;
+pub use png_encoder::*;
 pub use resource_paths::*;
 pub use restrictions::*;
+pub use save_profiles::*;
+pub use screenshots::*;
 pub use sound_buffer::*;
+pub use thread_pool::*;
@@ -1,3 +1,5 @@
+use super::network_conditions::{self, NetworkConditions};
+use super::stun::{self, StunTransactionId};
 use crate::args::Args;
 use crate::render::FrameTime;
 use crate::structures::{DenseSlotMap, GenerationalIndex};
@@ -9,11 +11,13 @@ use packets::{
 };
 use std::collections::HashMap;
 use std::future::Future;
-use std::net::{SocketAddr, UdpSocket};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
 use std::sync::Arc;
 use std::time::Duration;
 
 const DISCONNECT_AFTER: Duration = Duration::from_secs(5);
+const STUN_ATTEMPTS: usize = 3;
+const STUN_ATTEMPT_TIMEOUT: Duration = Duration::from_millis(500);
 pub type ClientPacketSender = Arc<dyn Fn(Reliability, ClientPacket) + Send + Sync>;
 pub type ServerPacketReceiver = flume::Receiver<ServerPacket>;
 pub type NetplayPacketSender = Arc<dyn Fn(NetplayPacket) + Send + Sync>;
@@ -34,6 +38,7 @@ enum Event {
     NetplaySubscription(SocketAddr, flume::Sender<NetplayPacketReceiver>),
     SendingClientPacket(SocketAddr, Reliability, ClientPacket),
     SendingNetplayPacket(SocketAddr, NetplayPacket),
+    StunRequest(SocketAddr, flume::Sender<SocketAddr>),
     ReceivedPacket(SocketAddr, Instant, Vec<u8>),
     Tick,
 }
@@ -41,6 +46,7 @@ enum Event {
 pub struct Network {
     socket: Arc<UdpSocket>,
     sender: flume::Sender<Event>,
+    stun_servers: Vec<String>,
     time: FrameTime,
 }
 
@@ -54,8 +60,9 @@ impl Network {
         std::thread::spawn({
             let sender = sender.clone();
             let socket = socket.clone();
+            let conditions = NetworkConditions::new(args);
 
-            move || socket_listener(socket, sender)
+            move || socket_listener(socket, sender, conditions)
         });
 
         std::thread::spawn({
@@ -67,6 +74,7 @@ impl Network {
         Self {
             socket,
             sender,
+            stun_servers: args.stun_server.clone(),
             time: 0,
         }
     }
@@ -96,6 +104,53 @@ impl Network {
         }
     }
 
+    /// Resolves addresses other players may be able to reach us at directly,
+    /// the address seen by the server is already known and isn't included
+    pub fn gather_netplay_candidates(
+        &self,
+        server_address: String,
+    ) -> impl Future<Output = Vec<SocketAddr>> {
+        let event_sender = self.sender.clone();
+        let stun_servers = self.stun_servers.clone();
+        let local_port = self.socket.local_addr().map(|addr| addr.port());
+
+        async move {
+            let mut candidates = Vec::new();
+
+            let Ok(local_port) = local_port else {
+                return candidates;
+            };
+
+            // LAN address, helps players behind the same NAT
+            let server_addr = packets::address_parsing::resolve_socket_addr(&server_address).await;
+
+            if let Some(ip) = server_addr.and_then(local_ip_for) {
+                candidates.push(SocketAddr::new(ip, local_port));
+            }
+
+            // public address as seen by STUN servers,
+            // differs from the address seen by the server when the server is on the same network
+            for stun_server in stun_servers {
+                let Some(addr) = packets::address_parsing::resolve_socket_addr(&stun_server).await
+                else {
+                    log::warn!("Failed to resolve STUN server {stun_server:?}");
+                    continue;
+                };
+
+                let Some(mapped_addr) = stun_binding(&event_sender, addr).await else {
+                    log::warn!("No response from STUN server {stun_server:?}");
+                    continue;
+                };
+
+                if !candidates.contains(&mapped_addr) {
+                    candidates.push(mapped_addr);
+                }
+            }
+
+            candidates
+        }
+    }
+
     pub fn subscribe_to_server(
         &self,
         address: String,
@@ -172,15 +227,78 @@ impl Network {
     }
 }
 
-fn socket_listener(socket: Arc<UdpSocket>, packet_sender: flume::Sender<Event>) {
+// connecting a udp socket sends nothing, but resolves the interface used to reach the address
+fn local_ip_for(addr: SocketAddr) -> Option<IpAddr> {
+    // the game socket is bound to an ipv4 address
+    if !addr.is_ipv4() {
+        return None;
+    }
+
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect(addr).ok()?;
+
+    let ip = socket.local_addr().ok()?.ip();
+
+    if ip.is_unspecified() {
+        None
+    } else {
+        Some(ip)
+    }
+}
+
+async fn stun_binding(event_sender: &flume::Sender<Event>, addr: SocketAddr) -> Option<SocketAddr> {
+    use futures::future::Either;
+
+    for _ in 0..STUN_ATTEMPTS {
+        let (sender, receiver) = flume::bounded(1);
+        event_sender.send(Event::StunRequest(addr, sender)).ok()?;
+
+        let response = Box::pin(receiver.into_recv_async());
+        let timeout = Box::pin(async_sleep(STUN_ATTEMPT_TIMEOUT));
+
+        if let Either::Left((Ok(mapped_addr), _)) = futures::future::select(response, timeout).await
+        {
+            return Some(mapped_addr);
+        }
+    }
+
+    None
+}
+
+fn socket_listener(
+    socket: Arc<UdpSocket>,
+    packet_sender: flume::Sender<Event>,
+    conditions: NetworkConditions,
+) {
     let mut buffer = vec![0; 100000];
 
+    let delay_sender = if conditions.is_ideal() {
+        None
+    } else {
+        log::warn!("Simulating network conditions for received packets");
+
+        let packet_sender = packet_sender.clone();
+
+        Some(network_conditions::spawn_delay_queue(
+            conditions,
+            move |(addr, bytes)| {
+                let event = Event::ReceivedPacket(addr, Instant::now(), bytes);
+                packet_sender.send(event).is_ok()
+            },
+        ))
+    };
+
     while !packet_sender.is_disconnected() {
         let (addr, bytes) = match socket.recv_from(&mut buffer) {
             Ok((len, addr)) => (addr, &buffer[..len]),
             _ => continue,
         };
 
+        if let Some(delay_sender) = &delay_sender {
+            let _ = delay_sender.send((addr, bytes.to_vec()));
+            continue;
+        }
+
         let _ = packet_sender.send(Event::ReceivedPacket(addr, Instant::now(), bytes.to_vec()));
     }
 }
@@ -220,6 +338,7 @@ impl Connection {
 
 struct EventListener {
     socket: Arc<UdpSocket>,
+    stun_requests: HashMap<SocketAddr, (StunTransactionId, flume::Sender<SocketAddr>)>,
     connection_map: HashMap<SocketAddr, GenerationalIndex>,
     connections: DenseSlotMap<Connection>,
     receiver: flume::Receiver<Event>,
@@ -230,6 +349,7 @@ impl EventListener {
     fn new(socket: Arc<UdpSocket>, receiver: flume::Receiver<Event>, resend_budget: usize) -> Self {
         Self {
             socket,
+            stun_requests: HashMap::new(),
             connection_map: HashMap::new(),
             connections: Default::default(),
             receiver,
@@ -254,11 +374,10 @@ impl EventListener {
                 Event::SendingClientPacket(addr, reliability, body) => {
                     self.send_client_packet(addr, reliability, packets::serialize(body))
                 }
-                Event::SendingNetplayPacket(addr, body) => self.send_netplay_packet(
-                    addr,
-                    Reliability::ReliableOrdered,
-                    packets::serialize(body),
-                ),
+                Event::SendingNetplayPacket(addr, body) => {
+                    self.send_netplay_packet(addr, body.reliability(), packets::serialize(body))
+                }
+                Event::StunRequest(addr, sender) => self.send_stun_request(addr, sender),
                 Event::ReceivedPacket(addr, time, body) => self.sort_packet(addr, time, body),
                 Event::Tick => self.tick(),
             }
@@ -335,7 +454,27 @@ impl EventListener {
         })
     }
 
+    fn send_stun_request(&mut self, addr: SocketAddr, sender: flume::Sender<SocketAddr>) {
+        use rand::RngCore;
+
+        let mut transaction_id = StunTransactionId::default();
+        rand::rngs::OsRng.fill_bytes(&mut transaction_id);
+
+        let _ = (self.socket).send_to(&stun::binding_request(&transaction_id), addr);
+
+        // replaces the previous attempt
+        self.stun_requests.insert(addr, (transaction_id, sender));
+    }
+
     fn sort_packet(&mut self, addr: SocketAddr, time: Instant, bytes: Vec<u8>) {
+        if let Some((transaction_id, _)) = self.stun_requests.get(&addr) {
+            if let Some(mapped_addr) = stun::parse_binding_response(&bytes, transaction_id) {
+                let (_, sender) = self.stun_requests.remove(&addr).unwrap();
+                let _ = sender.send(mapped_addr);
+                return;
+            }
+        }
+
         let Some(index) = self.connection_map.get_mut(&addr) else {
             return;
         };
@@ -0,0 +1,91 @@
+// Artificial latency, jitter, and packet loss for testing netplay without a bad connection
+
+use crate::args::Args;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy)]
+pub struct NetworkConditions {
+    latency: Duration,
+    jitter: Duration,
+    packet_loss: f32,
+}
+
+impl NetworkConditions {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            latency: Duration::from_millis(args.simulate_latency),
+            jitter: Duration::from_millis(args.simulate_jitter),
+            packet_loss: args.simulate_packet_loss.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn is_ideal(&self) -> bool {
+        self.latency.is_zero() && self.jitter.is_zero() && self.packet_loss == 0.0
+    }
+
+    /// Returns None if the packet should be dropped
+    fn delay(&self, rng: &mut impl Rng) -> Option<Duration> {
+        if self.packet_loss > 0.0 && rng.gen::<f32>() < self.packet_loss {
+            return None;
+        }
+
+        if self.jitter.is_zero() {
+            return Some(self.latency);
+        }
+
+        // packets can be reordered by jitter, just like a real connection
+        let jitter = self.jitter.as_secs_f32();
+        let offset = rng.gen_range(-jitter..=jitter);
+        let delay = (self.latency.as_secs_f32() + offset).max(0.0);
+
+        Some(Duration::from_secs_f32(delay))
+    }
+}
+
+/// Holds received packets until their simulated arrival,
+/// `deliver` is called from a separate thread
+pub fn spawn_delay_queue<T: Send + 'static>(
+    conditions: NetworkConditions,
+    deliver: impl Fn(T) -> bool + Send + 'static,
+) -> flume::Sender<T> {
+    let (sender, receiver) = flume::unbounded::<T>();
+
+    std::thread::spawn(move || {
+        let mut rng = rand::thread_rng();
+        // sorted by arrival time
+        let mut pending: Vec<(Instant, T)> = Vec::new();
+
+        loop {
+            let received = match pending.first() {
+                Some((time, _)) => receiver.recv_deadline(*time),
+                None => receiver
+                    .recv()
+                    .map_err(|_| flume::RecvTimeoutError::Disconnected),
+            };
+
+            match received {
+                Ok(item) => {
+                    if let Some(delay) = conditions.delay(&mut rng) {
+                        let time = Instant::now() + delay;
+                        let index = pending.partition_point(|(other_time, _)| *other_time <= time);
+                        pending.insert(index, (time, item));
+                    }
+                }
+                Err(flume::RecvTimeoutError::Timeout) => {}
+                Err(flume::RecvTimeoutError::Disconnected) => return,
+            }
+
+            let now = Instant::now();
+            let ready_count = pending.partition_point(|(time, _)| *time <= now);
+
+            for (_, item) in pending.drain(..ready_count) {
+                if !deliver(item) {
+                    return;
+                }
+            }
+        }
+    });
+
+    sender
+}
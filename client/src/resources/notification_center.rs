@@ -0,0 +1,141 @@
+use super::NOTIFICATION_LIMIT;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Older toasts are dropped when many notifications arrive at once, they're still kept for review
+const MAX_PENDING_TOASTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// A package finished downloading for the first time
+    Download,
+    /// An installed package was replaced with a newer version
+    PackageUpdate,
+    /// Sent by the connected server, such as friend logins or mail
+    Server,
+}
+
+impl NotificationKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationKind::Download => "Download",
+            NotificationKind::PackageUpdate => "Update",
+            NotificationKind::Server => "Server",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub text: String,
+    /// Seconds since the unix epoch
+    pub received_at: u64,
+}
+
+/// Messages from background work, each one is displayed as a toast and kept for review, oldest first
+#[derive(Default)]
+pub struct NotificationCenter {
+    notifications: RefCell<VecDeque<Notification>>,
+    toasts: RefCell<VecDeque<Notification>>,
+    unread: Cell<usize>,
+}
+
+impl NotificationCenter {
+    /// Empty messages are skipped
+    pub fn push(&self, kind: NotificationKind, text: impl Into<String>) {
+        let text: String = text.into();
+        let text = text.trim();
+
+        if text.is_empty() {
+            return;
+        }
+
+        let received_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let notification = Notification {
+            kind,
+            text: text.to_string(),
+            received_at,
+        };
+
+        let mut notifications = self.notifications.borrow_mut();
+
+        if notifications.len() >= NOTIFICATION_LIMIT {
+            notifications.pop_front();
+        }
+
+        notifications.push_back(notification.clone());
+
+        let mut toasts = self.toasts.borrow_mut();
+
+        if toasts.len() >= MAX_PENDING_TOASTS {
+            toasts.pop_front();
+        }
+
+        toasts.push_back(notification);
+        self.unread
+            .set((self.unread.get() + 1).min(NOTIFICATION_LIMIT));
+    }
+
+    /// The next notification to display as a toast
+    pub fn take_toast(&self) -> Option<Notification> {
+        self.toasts.borrow_mut().pop_front()
+    }
+
+    pub fn notifications(&self) -> Vec<Notification> {
+        self.notifications.borrow().iter().cloned().collect()
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.unread.get()
+    }
+
+    pub fn mark_read(&self) {
+        self.unread.set(0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_recent_notifications_and_toasts() {
+        let center = NotificationCenter::default();
+
+        center.push(NotificationKind::Server, " Lan logged in ");
+        center.push(NotificationKind::Server, " ");
+
+        assert_eq!(center.unread_count(), 1);
+        assert_eq!(center.take_toast().unwrap().text, "Lan logged in");
+        assert_eq!(center.take_toast(), None);
+
+        for i in 0..NOTIFICATION_LIMIT {
+            center.push(NotificationKind::Download, i.to_string());
+        }
+
+        let last_toast = (NOTIFICATION_LIMIT - 1).to_string();
+        assert_eq!(
+            center.take_toast().unwrap().text,
+            (NOTIFICATION_LIMIT - 3).to_string()
+        );
+        assert_eq!(
+            center.take_toast().unwrap().text,
+            (NOTIFICATION_LIMIT - 2).to_string()
+        );
+        assert_eq!(center.take_toast().unwrap().text, last_toast);
+
+        let notifications = center.notifications();
+        assert_eq!(notifications.len(), NOTIFICATION_LIMIT);
+        assert_eq!(notifications[0].text, "0");
+        assert_eq!(center.unread_count(), NOTIFICATION_LIMIT);
+
+        center.mark_read();
+        assert_eq!(center.unread_count(), 0);
+    }
+}
@@ -0,0 +1,58 @@
+/// Uncompressed RGBA pixels to PNG bytes, rows are stored top to bottom
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+
+    let row_len = width as usize * 4;
+
+    // every row starts with a filter type byte, 0 for no filter
+    let mut pixel_data = Vec::with_capacity((row_len + 1) * height as usize);
+
+    for row in rgba.chunks_exact(row_len.max(1)).take(height as usize) {
+        pixel_data.push(0);
+        pixel_data.extend(row);
+    }
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    let _ = encoder.write_all(&pixel_data);
+    let compressed_data = encoder.finish().unwrap_or_default();
+
+    let mut header = Vec::new();
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // bit depth, RGBA color type, compression, filter, interlace
+    header.extend([8, 6, 0, 0, 0]);
+
+    let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut bytes, b"IHDR", &header);
+    write_png_chunk(&mut bytes, b"IDAT", &compressed_data);
+    write_png_chunk(&mut bytes, b"IEND", &[]);
+
+    bytes
+}
+
+fn write_png_chunk(bytes: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+
+    let crc_start = bytes.len();
+    bytes.extend(chunk_type);
+    bytes.extend(data);
+
+    let crc = crc32(&bytes[crc_start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+
+    for byte in bytes {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB88320 & mask);
+        }
+    }
+
+    !crc
+}
@@ -2,16 +2,37 @@ use framework::prelude::WinitPlatformApp;
 use std::sync::OnceLock;
 
 static GAME_PATH: OnceLock<String> = OnceLock::new();
+static PROFILE_PATH: OnceLock<String> = OnceLock::new();
 
 pub struct ResourcePaths;
 
 impl ResourcePaths {
     pub const SERVER_CACHE_FOLDER: &'static str = "cache/servers/";
     pub const MOD_CACHE_FOLDER: &'static str = "cache/mods/";
+    pub const AUDIO_CACHE_FOLDER: &'static str = "cache/audio/";
+    pub const INTEGRITY_CACHE: &'static str = "cache/integrity";
     pub const IDENTITY_FOLDER: &'static str = "identity/";
+    pub const JOURNAL_FOLDER: &'static str = "journal/";
+    pub const BATTLE_LOG_FOLDER: &'static str = "battle_logs/";
+    pub const LOG_FOLDER: &'static str = "logs/";
+    pub const SCRIPT_PROFILE_FOLDER: &'static str = "script_profiles/";
+    pub const BATTLE_TEST_FOLDER: &'static str = "battle_tests/";
+    pub const SCREENSHOTS_FOLDER: &'static str = "screenshots/";
+    pub const CLIPS_FOLDER: &'static str = "clips/";
+    pub const SAVE_BACKUP_FOLDER: &'static str = "save_backups/";
+    pub const SAVE_EXPORT_FOLDER: &'static str = "save_exports/";
+    pub const FOLDER_CODE_FOLDER: &'static str = "folder_codes/";
+    pub const PROFILES_FOLDER: &'static str = "profiles/";
+    pub const CRASH_REPORT_FOLDER: &'static str = "crash_reports/";
     pub const VIRTUAL_PREFIX: &'static str = "/virtual/";
     pub const SEPARATOR: &'static str = "/";
 
+    /// Generated for releases
+    pub const INTEGRITY_MANIFEST: &'static str = "resources/integrity.txt";
+
+    // Translations
+    pub const TRANSLATIONS_FOLDER: &'static str = "resources/translations/";
+
     // Music
     pub const SOUND_FONT: &'static str = "resources/music/soundfont.sf2";
     pub const MAIN_MENU_MUSIC: &'static str = "resources/music/main_menu.ogg";
@@ -217,6 +238,7 @@ impl ResourcePaths {
         "resources/scenes/battle/tile_cracked.animation";
     pub const BATTLE_TILE_BROKEN_ANIMATION: &'static str =
         "resources/scenes/battle/tile_broken.animation";
+    pub const BATTLE_TEAM_INDICATORS: &'static str = "resources/scenes/battle/team_indicators.toml";
     pub const BATTLE_SHADOW_SMALL: &'static str = "resources/scenes/battle/shadow_small.png";
     pub const BATTLE_SHADOW_BIG: &'static str = "resources/scenes/battle/shadow_big.png";
     pub const BATTLE_CHARGE: &'static str = "resources/scenes/battle/charge.png";
@@ -286,6 +308,26 @@ impl ResourcePaths {
         GAME_PATH.get().unwrap()
     }
 
+    /// An empty name uses the default profile, stored directly in the game folder
+    pub fn init_profile(name: &str) {
+        let folder = if name.is_empty() {
+            String::new()
+        } else {
+            format!("{}{name}/", Self::PROFILES_FOLDER)
+        };
+
+        let _ = PROFILE_PATH.set(folder);
+    }
+
+    /// Relative folder for saves, config, and identities
+    pub fn profile_folder() -> &'static str {
+        PROFILE_PATH.get().map(String::as_str).unwrap_or_default()
+    }
+
+    pub fn profile_path(path: &str) -> String {
+        Self::profile_folder().to_string() + path
+    }
+
     pub fn is_absolute(path_str: &str) -> bool {
         use std::path::Path;
 
@@ -91,11 +91,7 @@ impl Restrictions {
             card_packages
                 .package_or_fallback(PackageNamespace::Local, &card.package_id)
                 .map(|package| {
-                    if package.default_codes.contains(&card.code) {
-                        package.limit
-                    } else {
-                        0
-                    }
+                    package_card_count(&package.default_codes, package.limit, &card.code)
                 })
                 .unwrap_or(0)
         } else {
@@ -235,3 +231,36 @@ impl Restrictions {
         })
     }
 }
+
+/// Packages only grant the codes they list, wildcard matching is only used for selection and aux props.
+/// Otherwise a wildcard card would count as owned for every package
+fn package_card_count(default_codes: &[String], limit: usize, code: &str) -> usize {
+    if default_codes
+        .iter()
+        .any(|default_code| default_code == code)
+    {
+        limit
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bindable::WILDCARD_CODE;
+
+    #[test]
+    fn wildcard_cards_need_a_listed_wildcard() {
+        let codes = vec![String::from("A"), String::from("B")];
+
+        assert_eq!(package_card_count(&codes, 5, "A"), 5);
+        assert_eq!(package_card_count(&codes, 5, "C"), 0);
+        assert_eq!(package_card_count(&codes, 5, WILDCARD_CODE), 0);
+
+        let codes = vec![String::from("A"), String::from(WILDCARD_CODE)];
+
+        assert_eq!(package_card_count(&codes, 5, WILDCARD_CODE), 5);
+        assert_eq!(package_card_count(&codes, 5, "B"), 0);
+    }
+}
@@ -0,0 +1,154 @@
+use super::ResourcePaths;
+
+const SELECTED_PROFILE_FILE: &str = "selected";
+
+/// Named sets of saves, config, and identities. The default profile uses the game folder directly
+pub struct SaveProfiles;
+
+impl SaveProfiles {
+    pub const DEFAULT_NAME: &'static str = "Default";
+    pub const NAME_LIMIT: usize = 16;
+
+    /// Resolves the profile to use for this launch, a profile passed as a flag is created if missing
+    pub fn resolve(flag: Option<&str>) -> String {
+        let Some(name) = flag else {
+            return Self::remembered().unwrap_or_default();
+        };
+
+        if name.is_empty() || name == Self::DEFAULT_NAME {
+            Self::remember("");
+            return String::new();
+        }
+
+        if !Self::exists(name) {
+            if let Err(e) = Self::create(name) {
+                log::error!("{e}");
+                return String::new();
+            }
+        }
+
+        Self::remember(name);
+        name.to_string()
+    }
+
+    /// The profile in use, set by ResourcePaths::init_profile
+    pub fn active() -> String {
+        ResourcePaths::profile_folder()
+            .strip_prefix(ResourcePaths::PROFILES_FOLDER)
+            .map(|name| name.trim_end_matches('/').to_string())
+            .unwrap_or_default()
+    }
+
+    /// The default profile is listed first as an empty string
+    pub fn list() -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(ResourcePaths::PROFILES_FOLDER)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| Self::is_valid_name(name))
+            .collect();
+
+        names.sort_by_key(|name| name.to_lowercase());
+        names.insert(0, String::new());
+        names
+    }
+
+    pub fn display_name(name: &str) -> &str {
+        if name.is_empty() {
+            Self::DEFAULT_NAME
+        } else {
+            name
+        }
+    }
+
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= Self::NAME_LIMIT
+            && name != Self::DEFAULT_NAME
+            && !name.starts_with(' ')
+            && !name.ends_with(' ')
+            && (name.chars()).all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    }
+
+    fn exists(name: &str) -> bool {
+        std::path::Path::new(&Self::folder(name)).is_dir()
+    }
+
+    fn folder(name: &str) -> String {
+        format!("{}{name}/", ResourcePaths::PROFILES_FOLDER)
+    }
+
+    /// New profiles start with a copy of the current profile's config
+    pub fn create(name: &str) -> Result<(), String> {
+        if !Self::is_valid_name(name) {
+            return Err(format!(
+                "Profile names must be up to {} letters, numbers, spaces, dashes, or underscores",
+                Self::NAME_LIMIT
+            ));
+        }
+
+        if Self::exists(name) {
+            return Err(format!("The {name} profile already exists"));
+        }
+
+        let folder = Self::folder(name);
+
+        std::fs::create_dir_all(&folder)
+            .map_err(|e| format!("Failed to create {folder:?}: {e}"))?;
+
+        let config_path = ResourcePaths::profile_path(crate::saves::Config::PATH);
+        let _ = std::fs::copy(config_path, folder + crate::saves::Config::PATH);
+
+        Ok(())
+    }
+
+    fn selected_profile_path() -> String {
+        ResourcePaths::PROFILES_FOLDER.to_string() + SELECTED_PROFILE_FILE
+    }
+
+    fn remembered() -> Option<String> {
+        let name = std::fs::read_to_string(Self::selected_profile_path()).ok()?;
+        let name = name.trim();
+
+        (Self::is_valid_name(name) && Self::exists(name)).then(|| name.to_string())
+    }
+
+    pub fn remember(name: &str) {
+        let _ = std::fs::create_dir_all(ResourcePaths::PROFILES_FOLDER);
+
+        if let Err(e) = std::fs::write(Self::selected_profile_path(), name) {
+            log::error!("Failed to save the selected profile: {e}");
+        }
+    }
+
+    /// Starts a new instance of the game using the profile, the caller should exit after this succeeds
+    pub fn relaunch(name: &str) -> Result<(), String> {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+
+        std::process::Command::new(exe)
+            .arg("--profile")
+            .arg(Self::display_name(name))
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn profile_names() {
+        assert!(SaveProfiles::is_valid_name("Player 2"));
+        assert!(SaveProfiles::is_valid_name("tester_alt-1"));
+        assert!(!SaveProfiles::is_valid_name(""));
+        assert!(!SaveProfiles::is_valid_name(SaveProfiles::DEFAULT_NAME));
+        assert!(!SaveProfiles::is_valid_name(" padded"));
+        assert!(!SaveProfiles::is_valid_name("../escape"));
+        assert!(!SaveProfiles::is_valid_name("a name that is too long"));
+    }
+}
@@ -0,0 +1,146 @@
+use super::{encode_png, ResourcePaths};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where a screenshot was taken, saved next to the image as `<name>.toml`
+#[derive(Default, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotMetadata {
+    /// Seconds since the unix epoch
+    pub taken_at: u64,
+    pub server_address: Option<String>,
+    /// Name of the overworld area
+    pub area: Option<String>,
+    /// Encounter package id
+    pub battle: Option<String>,
+}
+
+impl ScreenshotMetadata {
+    /// Lines for the gallery, empty if nothing about the location is known
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        if let Some(address) = &self.server_address {
+            lines.push(format!("Server: {address}"));
+        }
+
+        if let Some(area) = &self.area {
+            lines.push(format!("Area: {area}"));
+        }
+
+        if let Some(battle) = &self.battle {
+            lines.push(format!("Battle: {battle}"));
+        }
+
+        lines
+    }
+}
+
+pub struct Screenshot {
+    pub path: String,
+    pub metadata: ScreenshotMetadata,
+}
+
+impl Screenshot {
+    fn folder() -> String {
+        ResourcePaths::game_folder().to_string() + ResourcePaths::SCREENSHOTS_FOLDER
+    }
+
+    /// Encodes and writes the image and metadata on a separate thread
+    pub fn save(width: u32, height: u32, rgba: Vec<u8>, mut metadata: ScreenshotMetadata) {
+        let elapsed_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        metadata.taken_at = elapsed_time.as_secs();
+
+        let file_stem = elapsed_time.as_millis().to_string();
+
+        std::thread::spawn(move || {
+            let folder_path = Self::folder();
+
+            // create parent folder
+            let _ = std::fs::create_dir_all(&folder_path);
+
+            let path = format!("{folder_path}{file_stem}.png");
+
+            if let Err(e) = std::fs::write(&path, encode_png(width, height, &rgba)) {
+                log::error!("Failed to save screenshot to {:?}: {}", path, e);
+                return;
+            }
+
+            match toml::to_string(&metadata) {
+                Ok(text) => {
+                    let metadata_path = format!("{folder_path}{file_stem}.toml");
+
+                    if let Err(e) = std::fs::write(&metadata_path, text) {
+                        log::error!("Failed to save {:?}: {}", metadata_path, e);
+                    }
+                }
+                Err(e) => log::error!("Failed to serialize screenshot metadata: {e}"),
+            }
+
+            log::info!("Saved screenshot to {path}");
+        });
+    }
+
+    /// Saved screenshots, newest first
+    pub fn list() -> Vec<Screenshot> {
+        let Ok(entries) = std::fs::read_dir(Self::folder()) else {
+            return Vec::new();
+        };
+
+        let mut screenshots: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "png"))
+            .map(|path| {
+                let metadata = std::fs::read_to_string(path.with_extension("toml"))
+                    .ok()
+                    .and_then(|text| toml::from_str(&text).ok())
+                    .unwrap_or_default();
+
+                Screenshot {
+                    path: ResourcePaths::clean(&path.to_string_lossy()),
+                    metadata,
+                }
+            })
+            .collect();
+
+        screenshots.sort_by(|a, b| b.path.cmp(&a.path));
+        screenshots
+    }
+
+    /// Removes the image and metadata
+    pub fn delete(&self) -> std::io::Result<()> {
+        std::fs::remove_file(&self.path)?;
+
+        let metadata_path = std::path::Path::new(&self.path).with_extension("toml");
+        let _ = std::fs::remove_file(metadata_path);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metadata_round_trip() {
+        let metadata = ScreenshotMetadata {
+            taken_at: 5,
+            area: Some(String::from("Central Area")),
+            ..Default::default()
+        };
+
+        let text = toml::to_string(&metadata).unwrap();
+
+        assert_eq!(
+            toml::from_str::<ScreenshotMetadata>(&text).unwrap(),
+            metadata
+        );
+        assert_eq!(metadata.describe(), ["Area: Central Area"]);
+
+        // missing fields are allowed
+        let metadata: ScreenshotMetadata = toml::from_str("").unwrap();
+        assert!(metadata.describe().is_empty());
+    }
+}
@@ -1,22 +1,107 @@
-use crate::resources::Globals;
+use crate::resources::{Globals, LoopPoints, LOOP_POINTS_SEARCH_LEN};
 use framework::prelude::GameIO;
 use itertools::Itertools;
 use rodio::Source;
 use rustysynth::{MidiFile, MidiFileSequencer, Synthesizer, SynthesizerSettings};
-use std::io::Cursor;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Encoded audio at least this size is decoded while playing instead of upfront
+const STREAMING_THRESHOLD: usize = 1024 * 1024;
+
+trait StreamReader: Read + Seek + Send + Sync {}
+
+impl<T: Read + Seek + Send + Sync> StreamReader for T {}
+
+type StreamDecoder = rodio::Decoder<Box<dyn StreamReader>>;
+
+enum StreamSource {
+    File(PathBuf),
+    Memory(Arc<[u8]>),
+}
+
+impl StreamSource {
+    fn create_decoder(&self) -> Option<StreamDecoder> {
+        let reader: Box<dyn StreamReader> = match self {
+            StreamSource::File(path) => Box::new(BufReader::new(File::open(path).ok()?)),
+            StreamSource::Memory(bytes) => Box::new(Cursor::new(bytes.clone())),
+        };
+
+        rodio::Decoder::new(reader).ok()
+    }
+
+    fn read_loop_points(&self) -> Option<LoopPoints> {
+        match self {
+            StreamSource::File(path) => {
+                let mut header = Vec::new();
+                let file = File::open(path).ok()?;
+                file.take(LOOP_POINTS_SEARCH_LEN as u64)
+                    .read_to_end(&mut header)
+                    .ok()?;
+
+                LoopPoints::parse(&header)
+            }
+            StreamSource::Memory(bytes) => {
+                LoopPoints::parse(&bytes[..bytes.len().min(LOOP_POINTS_SEARCH_LEN)])
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum SoundData {
+    Decoded(Arc<[i16]>),
+    Streamed(Arc<StreamSource>),
+}
+
 #[derive(Clone)]
 pub struct SoundBuffer {
     channels: u16,
     sample_rate: u32,
     duration: Duration,
-    data: Arc<[i16]>,
+    loop_points: Option<LoopPoints>,
+    data: SoundData,
 }
 
 impl SoundBuffer {
+    /// Large files are streamed from disk while playing
+    pub fn load(game_io: &GameIO, path: &str) -> Self {
+        if Self::is_streamable_file(path) {
+            return Self::stream(StreamSource::File(path.into()));
+        }
+
+        Self::decode(game_io, std::fs::read(path).unwrap_or_default())
+    }
+
+    /// Large files are streamed from disk while playing
+    pub fn load_non_midi(path: &str) -> Self {
+        if Self::is_streamable_file(path) {
+            return Self::stream(StreamSource::File(path.into()));
+        }
+
+        Self::decode_non_midi(std::fs::read(path).unwrap_or_default())
+    }
+
+    fn is_streamable_file(path: &str) -> bool {
+        let Ok(mut file) = File::open(path) else {
+            return false;
+        };
+
+        let is_large = file
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() >= STREAMING_THRESHOLD as u64);
+
+        // midi is rendered by our synthesizer, rodio can't stream it
+        let mut header = [0; 4];
+        let is_midi = file.read_exact(&mut header).is_ok() && &header == b"MThd";
+
+        is_large && !is_midi
+    }
+
     pub fn decode(game_io: &GameIO, raw: Vec<u8>) -> Self {
         if raw.starts_with(b"MThd") {
             return Self::decode_midi(game_io, raw);
@@ -30,6 +115,12 @@ impl SoundBuffer {
     }
 
     pub fn decode_non_midi(raw: Vec<u8>) -> Self {
+        if raw.len() >= STREAMING_THRESHOLD {
+            return Self::stream(StreamSource::Memory(raw.into()));
+        }
+
+        let loop_points = LoopPoints::parse(&raw[..raw.len().min(LOOP_POINTS_SEARCH_LEN)]);
+
         let cursor = Cursor::new(raw);
         let Ok(decoder) = rodio::Decoder::new(cursor) else {
             return Self::new_empty();
@@ -44,7 +135,24 @@ impl SoundBuffer {
             channels,
             sample_rate,
             duration: Duration::from_secs_f32(duration),
-            data,
+            loop_points,
+            data: SoundData::Decoded(data),
+        }
+    }
+
+    fn stream(source: StreamSource) -> Self {
+        // validates the source, and reads the format
+        let Some(decoder) = source.create_decoder() else {
+            return Self::new_empty();
+        };
+
+        Self {
+            channels: decoder.channels(),
+            sample_rate: decoder.sample_rate(),
+            // unknown for most formats without decoding the whole stream
+            duration: decoder.total_duration().unwrap_or_default(),
+            loop_points: source.read_loop_points(),
+            data: SoundData::Streamed(Arc::new(source)),
         }
     }
 
@@ -94,7 +202,8 @@ impl SoundBuffer {
             channels: 2,
             sample_rate,
             duration: Duration::from_secs_f64(midi_file.get_length()),
-            data,
+            loop_points: None,
+            data: SoundData::Decoded(data),
         }
     }
 
@@ -103,42 +212,77 @@ impl SoundBuffer {
             channels: 1,
             sample_rate: 1,
             duration: Duration::ZERO,
-            data: Arc::new([]),
+            loop_points: None,
+            data: SoundData::Decoded(Arc::new([])),
         }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        match &self.data {
+            SoundData::Decoded(data) => data.is_empty(),
+            // empty or invalid streams are replaced with an empty buffer
+            SoundData::Streamed(_) => false,
+        }
     }
 
     pub fn create_sampler(&self) -> SoundBufferSampler {
-        SoundBufferSampler {
-            buffer: self.clone(),
-            index: 0,
-            loop_range: None,
-            stop_looping: Default::default(),
-        }
+        SoundBufferSampler::new(self.clone(), None)
     }
 
+    /// Uses the loop points from the file's tags when no range is provided
     pub fn create_looped_sampler(
         &self,
         range: Option<std::ops::Range<usize>>,
     ) -> SoundBufferSampler {
-        SoundBufferSampler {
-            buffer: self.clone(),
-            index: 0,
-            loop_range: Some(range.unwrap_or(0..self.data.len())),
-            stop_looping: Default::default(),
-        }
+        let range = range.unwrap_or_else(|| {
+            let sample_count = match &self.data {
+                SoundData::Decoded(data) => data.len(),
+                // loops when the stream ends
+                SoundData::Streamed(_) => usize::MAX,
+            };
+
+            match self.loop_points {
+                Some(loop_points) => {
+                    let range = loop_points.sample_range(self.channels, sample_count);
+
+                    // the sampler plays the sample at the end of its range
+                    range.start..range.end.saturating_sub(1).max(range.start)
+                }
+                None => 0..sample_count,
+            }
+        });
+
+        SoundBufferSampler::new(self.clone(), Some(range))
     }
 
     pub fn id(&self) -> usize {
-        self.data.as_ptr() as usize
+        match &self.data {
+            SoundData::Decoded(data) => data.as_ptr() as usize,
+            SoundData::Streamed(source) => Arc::as_ptr(source) as usize,
+        }
     }
 
     pub fn duration(&self) -> Duration {
         self.duration
     }
+
+    pub fn memory_usage(&self) -> usize {
+        match &self.data {
+            SoundData::Decoded(data) => std::mem::size_of_val(&**data),
+            SoundData::Streamed(source) => match &**source {
+                StreamSource::File(_) => 0,
+                StreamSource::Memory(bytes) => bytes.len(),
+            },
+        }
+    }
+
+    /// True if another buffer or a sampler shares the data
+    pub fn in_use(&self) -> bool {
+        match &self.data {
+            SoundData::Decoded(data) => Arc::strong_count(data) > 1,
+            SoundData::Streamed(source) => Arc::strong_count(source) > 1,
+        }
+    }
 }
 
 impl Default for SoundBuffer {
@@ -149,18 +293,59 @@ impl Default for SoundBuffer {
 
 impl PartialEq for SoundBuffer {
     fn eq(&self, other: &Self) -> bool {
-        Arc::ptr_eq(&self.data, &other.data)
+        match (&self.data, &other.data) {
+            (SoundData::Decoded(a), SoundData::Decoded(b)) => Arc::ptr_eq(a, b),
+            (SoundData::Streamed(a), SoundData::Streamed(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
     }
 }
 
 pub struct SoundBufferSampler {
     buffer: SoundBuffer,
+    // only used for streamed buffers
+    decoder: Option<StreamDecoder>,
     index: usize,
     loop_range: Option<std::ops::Range<usize>>,
     stop_looping: Arc<AtomicBool>,
 }
 
 impl SoundBufferSampler {
+    fn new(buffer: SoundBuffer, loop_range: Option<std::ops::Range<usize>>) -> Self {
+        let decoder = match &buffer.data {
+            SoundData::Decoded(_) => None,
+            SoundData::Streamed(source) => source.create_decoder(),
+        };
+
+        Self {
+            buffer,
+            decoder,
+            index: 0,
+            loop_range,
+            stop_looping: Default::default(),
+        }
+    }
+
+    fn read_sample(&mut self) -> Option<i16> {
+        match &self.buffer.data {
+            SoundData::Decoded(data) => data.get(self.index).cloned(),
+            SoundData::Streamed(_) => self.decoder.as_mut()?.next(),
+        }
+    }
+
+    fn seek(&mut self, index: usize) {
+        self.index = index;
+
+        if let SoundData::Streamed(source) = &self.buffer.data {
+            // decoders can't reliably seek backwards, restart and skip instead
+            self.decoder = source.create_decoder();
+
+            if let Some(decoder) = &mut self.decoder {
+                decoder.by_ref().take(index).for_each(drop);
+            }
+        }
+    }
+
     pub fn end_loop_callback(&self) -> impl Fn() {
         let stop_looping = self.stop_looping.clone();
 
@@ -196,15 +381,21 @@ impl Iterator for SoundBufferSampler {
     type Item = i16;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let sample = self.buffer.data.get(self.index).cloned();
+        let mut sample = self.read_sample();
         self.index += 1;
 
         if let Some(range) = self.loop_range.clone() {
-            if self.index > range.end {
+            if self.index > range.end || sample.is_none() {
                 if self.stop_looping.load(std::sync::atomic::Ordering::Relaxed) {
                     self.loop_range = None;
                 } else {
-                    self.index = range.start;
+                    self.seek(range.start);
+
+                    if sample.is_none() {
+                        // ending here would stop the sink, continue from the start of the loop
+                        sample = self.read_sample();
+                        self.index += 1;
+                    }
                 }
             }
         }
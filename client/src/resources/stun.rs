@@ -0,0 +1,178 @@
+// Minimal STUN (RFC 5389) client, only Binding requests are supported
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+const BINDING_REQUEST: u16 = 0x0001;
+const BINDING_SUCCESS: u16 = 0x0101;
+const MAGIC_COOKIE: u32 = 0x2112A442;
+const HEADER_LEN: usize = 20;
+
+const MAPPED_ADDRESS: u16 = 0x0001;
+const XOR_MAPPED_ADDRESS: u16 = 0x0020;
+
+const FAMILY_IPV4: u8 = 0x01;
+const FAMILY_IPV6: u8 = 0x02;
+
+pub type StunTransactionId = [u8; 12];
+
+pub fn binding_request(transaction_id: &StunTransactionId) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend(BINDING_REQUEST.to_be_bytes());
+    // no attributes
+    bytes.extend(0u16.to_be_bytes());
+    bytes.extend(MAGIC_COOKIE.to_be_bytes());
+    bytes.extend(transaction_id);
+    bytes
+}
+
+/// Returns the address the STUN server saw the request come from,
+/// None if the bytes aren't a successful response to the transaction
+pub fn parse_binding_response(
+    bytes: &[u8],
+    transaction_id: &StunTransactionId,
+) -> Option<SocketAddr> {
+    if bytes.len() < HEADER_LEN {
+        return None;
+    }
+
+    let message_type = u16::from_be_bytes([bytes[0], bytes[1]]);
+    let message_len = u16::from_be_bytes([bytes[2], bytes[3]]) as usize;
+    let cookie = u32::from_be_bytes(bytes[4..8].try_into().ok()?);
+
+    if message_type != BINDING_SUCCESS
+        || cookie != MAGIC_COOKIE
+        || bytes[8..HEADER_LEN] != transaction_id[..]
+    {
+        return None;
+    }
+
+    let mut attributes = bytes.get(HEADER_LEN..HEADER_LEN + message_len)?;
+    let mut mapped_address = None;
+
+    while attributes.len() >= 4 {
+        let attribute_type = u16::from_be_bytes([attributes[0], attributes[1]]);
+        let attribute_len = u16::from_be_bytes([attributes[2], attributes[3]]) as usize;
+        let value = attributes.get(4..4 + attribute_len)?;
+
+        match attribute_type {
+            XOR_MAPPED_ADDRESS => {
+                // preferred, some NATs rewrite addresses found in packets
+                return parse_address(value, Some(transaction_id));
+            }
+            MAPPED_ADDRESS => {
+                mapped_address = parse_address(value, None);
+            }
+            _ => {}
+        }
+
+        // attributes are padded to 4 bytes
+        let padded_len = (attribute_len + 3) & !3;
+        attributes = attributes.get(4 + padded_len..).unwrap_or_default();
+    }
+
+    mapped_address
+}
+
+fn parse_address(
+    value: &[u8],
+    xor_transaction_id: Option<&StunTransactionId>,
+) -> Option<SocketAddr> {
+    let family = *value.get(1)?;
+    let port = u16::from_be_bytes([*value.get(2)?, *value.get(3)?]);
+
+    // xor addresses are masked by the magic cookie followed by the transaction id
+    let (port_mask, mask) = match xor_transaction_id {
+        Some(transaction_id) => {
+            let mut mask = [0; 16];
+            mask[..4].copy_from_slice(&MAGIC_COOKIE.to_be_bytes());
+            mask[4..].copy_from_slice(transaction_id);
+
+            ((MAGIC_COOKIE >> 16) as u16, mask)
+        }
+        None => (0, [0; 16]),
+    };
+
+    let ip = match family {
+        FAMILY_IPV4 => {
+            let mut octets: [u8; 4] = value.get(4..8)?.try_into().ok()?;
+
+            for (octet, mask) in octets.iter_mut().zip(mask) {
+                *octet ^= mask;
+            }
+
+            IpAddr::V4(Ipv4Addr::from(octets))
+        }
+        FAMILY_IPV6 => {
+            let mut octets: [u8; 16] = value.get(4..20)?.try_into().ok()?;
+
+            for (octet, mask) in octets.iter_mut().zip(mask) {
+                *octet ^= mask;
+            }
+
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+
+    Some(SocketAddr::new(ip, port ^ port_mask))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TRANSACTION_ID: StunTransactionId = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+
+    fn response(attribute_type: u16, value: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(BINDING_SUCCESS.to_be_bytes());
+        bytes.extend((4 + value.len() as u16).to_be_bytes());
+        bytes.extend(MAGIC_COOKIE.to_be_bytes());
+        bytes.extend(TRANSACTION_ID);
+        bytes.extend(attribute_type.to_be_bytes());
+        bytes.extend((value.len() as u16).to_be_bytes());
+        bytes.extend(value);
+        bytes
+    }
+
+    #[test]
+    fn request_header() {
+        let bytes = binding_request(&TRANSACTION_ID);
+
+        assert_eq!(bytes.len(), HEADER_LEN);
+        assert_eq!(bytes[..4], [0, 1, 0, 0]);
+        assert_eq!(bytes[8..], TRANSACTION_ID);
+    }
+
+    #[test]
+    fn xor_mapped_ipv4() {
+        // 192.0.2.1:32853, example from RFC 5769
+        let value = [0x00, 0x01, 0xa1, 0x47, 0xe1, 0x12, 0xa6, 0x43];
+        let bytes = response(XOR_MAPPED_ADDRESS, &value);
+
+        assert_eq!(
+            parse_binding_response(&bytes, &TRANSACTION_ID),
+            Some("192.0.2.1:32853".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn mapped_ipv4() {
+        let value = [0x00, 0x01, 0x1f, 0x90, 10, 0, 0, 2];
+        let bytes = response(MAPPED_ADDRESS, &value);
+
+        assert_eq!(
+            parse_binding_response(&bytes, &TRANSACTION_ID),
+            Some("10.0.0.2:8080".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn wrong_transaction_ignored() {
+        let value = [0x00, 0x01, 0x1f, 0x90, 10, 0, 0, 2];
+        let bytes = response(MAPPED_ADDRESS, &value);
+
+        assert_eq!(parse_binding_response(&bytes, &[0; 12]), None);
+        assert_eq!(parse_binding_response(&bytes[..10], &TRANSACTION_ID), None);
+    }
+}
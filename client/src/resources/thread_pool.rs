@@ -0,0 +1,93 @@
+/// Runs `work` on every item using a thread for each available core.
+/// `progress` is called on the current thread as items complete, with the completed count and total.
+/// Results are returned in the same order as the items.
+pub fn parallel_map<T, R, W, P>(items: Vec<T>, work: W, mut progress: P) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    W: Fn(T) -> R + Sync,
+    P: FnMut(usize, usize),
+{
+    let total = items.len();
+
+    let thread_count = std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(total);
+
+    if thread_count <= 1 {
+        return items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let result = work(item);
+                progress(i + 1, total);
+                result
+            })
+            .collect();
+    }
+
+    let (work_sender, work_receiver) = flume::unbounded();
+
+    for pair in items.into_iter().enumerate() {
+        let _ = work_sender.send(pair);
+    }
+
+    drop(work_sender);
+
+    let (result_sender, result_receiver) = flume::unbounded();
+    let mut results: Vec<Option<R>> = (0..total).map(|_| None).collect();
+
+    std::thread::scope(|scope| {
+        for _ in 0..thread_count {
+            let work_receiver = work_receiver.clone();
+            let result_sender = result_sender.clone();
+            let work = &work;
+
+            scope.spawn(move || {
+                for (i, item) in work_receiver.iter() {
+                    if result_sender.send((i, work(item))).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        // the receiver disconnects once every worker finishes
+        drop(result_sender);
+
+        for (completed, (i, result)) in result_receiver.iter().enumerate() {
+            results[i] = Some(result);
+            progress(completed + 1, total);
+        }
+    });
+
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn results_keep_order() {
+        let items: Vec<usize> = (0..100).collect();
+        let mut last_progress = (0, 0);
+
+        let results = parallel_map(
+            items,
+            |i| i * 2,
+            |completed, total| last_progress = (completed, total),
+        );
+
+        assert_eq!(results, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(last_progress, (100, 100));
+    }
+
+    #[test]
+    fn empty_items() {
+        let results: Vec<()> = parallel_map(Vec::<()>::new(), |_| (), |_, _| panic!());
+
+        assert!(results.is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::packages::PackageId;
+use packets::structures::AchievementDefinition;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AchievementSource {
+    /// A library package listing the achievement
+    Package(PackageId),
+    /// The address of the server that defined the achievement
+    Server(String),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AchievementUnlock {
+    pub source: AchievementSource,
+    pub id: String,
+    /// Seconds since the unix epoch
+    pub unlocked_at: u64,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Achievements {
+    /// Definitions sent by servers paired with the server's address,
+    /// kept to list achievements after disconnecting.
+    /// Icon paths point to the local server cache
+    pub server_definitions: Vec<(String, AchievementDefinition)>,
+    pub unlocks: Vec<AchievementUnlock>,
+}
+
+impl Achievements {
+    /// Replaces any definition from the same server with a matching id
+    pub fn register_server_achievement(
+        &mut self,
+        address: &str,
+        definition: AchievementDefinition,
+    ) {
+        let address = packets::address_parsing::strip_data(address);
+
+        let existing = (self.server_definitions.iter_mut())
+            .find(|(a, d)| a == address && d.id == definition.id);
+
+        match existing {
+            Some((_, existing)) => *existing = definition,
+            None => (self.server_definitions).push((address.to_string(), definition)),
+        }
+    }
+
+    pub fn unlocked_at(&self, source: &AchievementSource, id: &str) -> Option<u64> {
+        (self.unlocks.iter())
+            .find(|unlock| unlock.source == *source && unlock.id == id)
+            .map(|unlock| unlock.unlocked_at)
+    }
+
+    /// Returns false if the achievement was already unlocked
+    pub fn unlock(&mut self, source: AchievementSource, id: String) -> bool {
+        if self.unlocked_at(&source, &id).is_some() {
+            return false;
+        }
+
+        let unlocked_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        self.unlocks.push(AchievementUnlock {
+            source,
+            id,
+            unlocked_at,
+        });
+
+        true
+    }
+
+    pub fn update_package_id(&mut self, old_id: &PackageId, new_id: &PackageId) {
+        for unlock in &mut self.unlocks {
+            if unlock.source == AchievementSource::Package(old_id.clone()) {
+                unlock.source = AchievementSource::Package(new_id.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unlocks_are_recorded_once_per_source() {
+        let mut achievements = Achievements::default();
+        let package_source = AchievementSource::Package(PackageId::from("library"));
+        let server_source = AchievementSource::Server(String::from("localhost:8765"));
+
+        assert!(achievements.unlock(package_source.clone(), String::from("a")));
+        assert!(!achievements.unlock(package_source.clone(), String::from("a")));
+        assert!(achievements.unlock(server_source.clone(), String::from("a")));
+
+        assert!(achievements.unlocked_at(&package_source, "a").is_some());
+        assert!(achievements.unlocked_at(&package_source, "b").is_none());
+
+        let new_id = PackageId::from("renamed");
+        achievements.update_package_id(&PackageId::from("library"), &new_id);
+
+        let renamed_source = AchievementSource::Package(new_id);
+        assert!(achievements.unlocked_at(&renamed_source, "a").is_some());
+    }
+
+    #[test]
+    fn server_definitions_are_replaced_by_id() {
+        let mut achievements = Achievements::default();
+
+        let definition = |name: &str| AchievementDefinition {
+            id: String::from("a"),
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        achievements.register_server_achievement("localhost:8765", definition("First"));
+        achievements.register_server_achievement("localhost:8765?data", definition("Second"));
+        achievements.register_server_achievement("localhost:9000", definition("Other"));
+
+        let names: Vec<_> = (achievements.server_definitions.iter())
+            .map(|(_, definition)| definition.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["Second", "Other"]);
+    }
+}
@@ -3,14 +3,14 @@ use crate::packages::PackageNamespace;
 use crate::resources::{AssetManager, Globals, ResourcePaths};
 use crate::{SupportingServiceComm, SupportingServiceEvent};
 use framework::prelude::*;
-use packets::structures::{FileHash, PackageCategory, PackageId};
+use packets::structures::{EncounterData, FileHash, PackageCategory, PackageId};
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct BattleRecording {
     pub encounter_package_pair: Option<(PackageNamespace, PackageId)>,
-    pub data: Option<String>,
+    pub data: Option<EncounterData>,
     pub seed: u64,
     pub player_setups: Vec<PlayerSetup>,
     pub package_zips: Vec<(PackageCategory, PackageNamespace, Vec<u8>)>,
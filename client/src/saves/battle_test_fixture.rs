@@ -0,0 +1,401 @@
+use crate::battle::{BattleProps, PlayerSetup};
+use crate::packages::PackageNamespace;
+use crate::render::Background;
+use crate::resources::{Globals, Input};
+use crate::saves::{BattleRecording, Card, Deck, PlayerInputBuffer};
+use framework::prelude::GameIO;
+use packets::structures::{
+    BattleStatistics, EncounterData, InstalledBlock, InstalledSwitchDrive, PackageId,
+};
+use packets::NetplayBufferItem;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_FRAMES: usize = 60 * 60 * 5;
+
+#[derive(Serialize, Deserialize)]
+pub struct BattleTestInput {
+    pub frame: usize,
+    #[serde(default = "default_input_duration")]
+    pub duration: usize,
+    pub pressed: Vec<Input>,
+}
+
+fn default_input_duration() -> usize {
+    1
+}
+
+/// Checked against the local player's statistics once the battle exits or runs out of frames
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BattleTestExpectations {
+    pub completed: Option<bool>,
+    pub health: Option<i32>,
+    pub turns: Option<u32>,
+    /// Names of enemies still alive, order doesn't matter
+    pub enemy_survivors: Option<Vec<String>>,
+}
+
+impl BattleTestExpectations {
+    pub fn failures(&self, completed: bool, statistics: &BattleStatistics) -> Vec<String> {
+        let mut failures = Vec::new();
+
+        if let Some(expected) = self.completed {
+            if expected != completed {
+                failures.push(format!(
+                    "expected completed = {expected}, found {completed}"
+                ));
+            }
+        }
+
+        if let Some(expected) = self.health {
+            if expected != statistics.health {
+                failures.push(format!(
+                    "expected health = {expected}, found {}",
+                    statistics.health
+                ));
+            }
+        }
+
+        if let Some(expected) = self.turns {
+            if expected != statistics.turns {
+                failures.push(format!(
+                    "expected turns = {expected}, found {}",
+                    statistics.turns
+                ));
+            }
+        }
+
+        if let Some(expected) = &self.enemy_survivors {
+            let mut expected = expected.clone();
+            expected.sort();
+
+            let mut found: Vec<_> = (statistics.enemy_survivors.iter())
+                .map(|survivor| survivor.name.clone())
+                .collect();
+            found.sort();
+
+            if expected != found {
+                failures.push(format!(
+                    "expected enemy_survivors = {expected:?}, found {found:?}"
+                ));
+            }
+        }
+
+        failures
+    }
+}
+
+/// A scripted battle for regression testing mods, see `--test` and `--replay` in args.rs
+#[derive(Serialize, Deserialize)]
+pub struct BattleTestFixture {
+    pub encounter: PackageId,
+    pub player: PackageId,
+    #[serde(default)]
+    pub seed: u64,
+    /// Passed to encounter_init, arrays become 1 indexed tables
+    pub data: Option<toml::Value>,
+    /// Overrides the player package's health
+    pub health: Option<i32>,
+    /// Defaults to health
+    pub base_health: Option<i32>,
+    #[serde(default)]
+    pub deck: Vec<Card>,
+    #[serde(default)]
+    pub blocks: Vec<InstalledBlock>,
+    #[serde(default)]
+    pub drives: Vec<InstalledSwitchDrive>,
+    pub max_frames: Option<usize>,
+    #[serde(default)]
+    pub inputs: Vec<BattleTestInput>,
+    #[serde(default)]
+    pub expect: BattleTestExpectations,
+}
+
+impl BattleTestFixture {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        toml::from_str(&text).map_err(|e| e.to_string())
+    }
+
+    /// Captures the local player's inputs from a solo battle against a local encounter
+    pub fn from_recording(recording: &BattleRecording) -> Result<Self, String> {
+        let Some((namespace, encounter)) = &recording.encounter_package_pair else {
+            return Err(String::from("battle has no encounter package"));
+        };
+
+        if namespace.strip_recording() != PackageNamespace::Local {
+            return Err(String::from("encounter package is not installed locally"));
+        }
+
+        let [setup] = recording.player_setups.as_slice() else {
+            return Err(String::from("only single player battles can be exported"));
+        };
+
+        let inputs = Self::inputs_from_items(setup.buffer.iter_from(0));
+
+        Ok(Self {
+            encounter: encounter.clone(),
+            player: setup.package_id.clone(),
+            seed: recording.seed,
+            data: recording.data.as_ref().map(encounter_data_to_toml),
+            health: Some(setup.health),
+            base_health: Some(setup.base_health),
+            deck: setup.deck.cards.clone(),
+            blocks: setup.blocks.clone(),
+            drives: setup.drives.clone(),
+            max_frames: Some(setup.buffer.len()),
+            inputs,
+            expect: BattleTestExpectations::default(),
+        })
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, text).map_err(|e| e.to_string())
+    }
+
+    /// Merges repeated frames into runs, frames without input are skipped
+    fn inputs_from_items<'a>(
+        items: impl IntoIterator<Item = &'a NetplayBufferItem>,
+    ) -> Vec<BattleTestInput> {
+        let mut inputs: Vec<BattleTestInput> = Vec::new();
+
+        for (frame, item) in items.into_iter().enumerate() {
+            if item.pressed.is_empty() {
+                continue;
+            }
+
+            if let Some(last) = inputs.last_mut() {
+                if last.frame + last.duration == frame && last.pressed == item.pressed {
+                    last.duration += 1;
+                    continue;
+                }
+            }
+
+            inputs.push(BattleTestInput {
+                frame,
+                duration: 1,
+                pressed: item.pressed.clone(),
+            });
+        }
+
+        inputs
+    }
+
+    pub fn battle_props(&self, game_io: &GameIO) -> Result<BattleProps, String> {
+        let Some(mut player_setup) = self.player_setup(game_io) else {
+            return Err(format!("player {:?} is not installed", self.player));
+        };
+
+        // playback stops when the buffer runs out, keep going after the last input
+        if let Some(max_frames) = self.max_frames {
+            while player_setup.buffer.len() < max_frames {
+                player_setup.buffer.push_last(NetplayBufferItem::default());
+            }
+        }
+
+        let props = BattleProps {
+            encounter_package_pair: Some((PackageNamespace::Local, self.encounter.clone())),
+            data: self.encounter_data(),
+            seed: self.seed,
+            background: Background::new_battle(game_io),
+            player_setups: vec![player_setup],
+            senders: Vec::new(),
+            receivers: Vec::new(),
+            statistics_callback: None,
+            recording_enabled: false,
+            playback: true,
+            lockstep: false,
+        };
+
+        if props.encounter_package(game_io).is_none() {
+            return Err(format!("encounter {:?} is not installed", self.encounter));
+        }
+
+        Ok(props)
+    }
+
+    pub fn encounter_data(&self) -> Option<EncounterData> {
+        fn convert(value: &toml::Value) -> EncounterData {
+            match value {
+                toml::Value::Boolean(b) => EncounterData::Bool(*b),
+                toml::Value::Integer(n) => EncounterData::Integer(*n),
+                toml::Value::Float(n) => EncounterData::Number(*n),
+                toml::Value::String(s) => EncounterData::String(s.clone()),
+                toml::Value::Datetime(datetime) => EncounterData::String(datetime.to_string()),
+                toml::Value::Array(values) => EncounterData::Table(
+                    (values.iter().enumerate())
+                        .map(|(i, value)| (EncounterData::Integer(i as i64 + 1), convert(value)))
+                        .collect(),
+                ),
+                toml::Value::Table(table) => EncounterData::Table(
+                    (table.iter())
+                        .map(|(key, value)| (EncounterData::String(key.clone()), convert(value)))
+                        .collect(),
+                ),
+            }
+        }
+
+        self.data.as_ref().map(convert)
+    }
+
+    pub fn max_frames(&self) -> usize {
+        self.max_frames.unwrap_or(DEFAULT_MAX_FRAMES)
+    }
+
+    pub fn input_buffer(&self) -> PlayerInputBuffer {
+        let total_frames = (self.inputs.iter())
+            .map(|input| input.frame + input.duration)
+            .max()
+            .unwrap_or_default();
+
+        let mut buffer = PlayerInputBuffer::default();
+
+        for frame in 0..total_frames {
+            let mut pressed = Vec::new();
+
+            for input in &self.inputs {
+                if (input.frame..input.frame + input.duration).contains(&frame) {
+                    pressed.extend(input.pressed.iter().cloned());
+                }
+            }
+
+            buffer.push_last(NetplayBufferItem {
+                pressed,
+                signals: Vec::new(),
+            });
+        }
+
+        buffer
+    }
+
+    pub fn player_setup(&self, game_io: &GameIO) -> Option<PlayerSetup> {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let player_package =
+            (globals.player_packages).package_or_fallback(PackageNamespace::Local, &self.player)?;
+
+        let mut setup = PlayerSetup::new_dummy(player_package, 0, true);
+        setup.health = self.health.unwrap_or(player_package.health);
+        setup.base_health = self.base_health.unwrap_or(setup.health);
+        setup.deck = Deck::new(String::new());
+        setup.deck.cards = self.deck.clone();
+        setup.blocks = self.blocks.clone();
+        setup.drives = self.drives.clone();
+        setup.buffer = self.input_buffer();
+
+        Some(setup)
+    }
+}
+
+/// Inverse of the conversion in `encounter_data`, tables with keys 1..n become arrays
+fn encounter_data_to_toml(data: &EncounterData) -> toml::Value {
+    match data {
+        EncounterData::Bool(b) => toml::Value::Boolean(*b),
+        EncounterData::Integer(n) => toml::Value::Integer(*n),
+        EncounterData::Number(n) => toml::Value::Float(*n),
+        EncounterData::String(s) => toml::Value::String(s.clone()),
+        EncounterData::Table(entries) => {
+            let is_array = (entries.iter().enumerate())
+                .all(|(i, (key, _))| *key == EncounterData::Integer(i as i64 + 1));
+
+            if is_array {
+                let values = entries
+                    .iter()
+                    .map(|(_, value)| encounter_data_to_toml(value));
+                return toml::Value::Array(values.collect());
+            }
+
+            let table = entries.iter().map(|(key, value)| {
+                let key = match key {
+                    EncounterData::String(s) => s.clone(),
+                    EncounterData::Integer(n) => n.to_string(),
+                    EncounterData::Number(n) => n.to_string(),
+                    EncounterData::Bool(b) => b.to_string(),
+                    EncounterData::Table(_) => String::from("table"),
+                };
+
+                (key, encounter_data_to_toml(value))
+            });
+
+            toml::Value::Table(table.collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overlapping_inputs_merge() {
+        let fixture: BattleTestFixture = toml::from_str(
+            r#"
+            encounter = "test.encounter"
+            player = "test.player"
+
+            [[inputs]]
+            frame = 1
+            duration = 3
+            pressed = ["Shoot"]
+
+            [[inputs]]
+            frame = 2
+            pressed = ["Left"]
+            "#,
+        )
+        .unwrap();
+
+        let buffer = fixture.input_buffer();
+
+        assert_eq!(buffer.len(), 4);
+        assert!(buffer.get(0).unwrap().pressed.is_empty());
+        assert_eq!(buffer.get(1).unwrap().pressed, [Input::Shoot]);
+        assert_eq!(buffer.get(2).unwrap().pressed, [Input::Shoot, Input::Left]);
+        assert_eq!(buffer.get(3).unwrap().pressed, [Input::Shoot]);
+    }
+
+    #[test]
+    fn exported_inputs_replay_identically() {
+        let items: Vec<_> = [
+            vec![],
+            vec![Input::Shoot],
+            vec![Input::Shoot],
+            vec![Input::Shoot, Input::Left],
+            vec![],
+            vec![Input::Left],
+        ]
+        .into_iter()
+        .map(|pressed| NetplayBufferItem {
+            pressed,
+            signals: Vec::new(),
+        })
+        .collect();
+
+        let inputs = BattleTestFixture::inputs_from_items(&items);
+
+        assert_eq!(inputs.len(), 3);
+        assert_eq!((inputs[0].frame, inputs[0].duration), (1, 2));
+
+        let fixture = BattleTestFixture {
+            encounter: PackageId::from("test.encounter"),
+            player: PackageId::from("test.player"),
+            seed: 0,
+            data: None,
+            health: None,
+            base_health: None,
+            deck: Vec::new(),
+            blocks: Vec::new(),
+            drives: Vec::new(),
+            max_frames: None,
+            inputs,
+            expect: BattleTestExpectations::default(),
+        };
+
+        let buffer = fixture.input_buffer();
+        assert_eq!(buffer.len(), items.len());
+
+        for (i, item) in items.iter().enumerate() {
+            assert_eq!(buffer.get(i), Some(item));
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use super::{BlockGrid, InstalledBlock};
+use crate::bindable::BlockColor;
+use crate::packages::AugmentPackage;
+
+const SIDE_LEN: usize = BlockGrid::SIDE_LEN;
+/// Placements tested before giving up, keeps unsolvable grids from stalling the scene
+const SEARCH_LIMIT: usize = 250_000;
+
+struct Placement {
+    rotation: u8,
+    position: (usize, usize),
+    cells: Vec<usize>,
+}
+
+struct ArrangedBlock {
+    /// Index in the original list
+    index: usize,
+    color: BlockColor,
+    placements: Vec<Placement>,
+    /// Identical to the previous block, used to skip swapped duplicates
+    repeats_previous: bool,
+}
+
+/// Searches for a placement of every block where each block applies without bugs:
+/// nothing on the edge, no same colored neighbors,
+/// flat blocks on the line, and other blocks off of it
+pub struct BlockArranger {
+    installed_blocks: Vec<InstalledBlock>,
+    blocks: Vec<ArrangedBlock>,
+    grid: [Option<BlockColor>; SIDE_LEN * SIDE_LEN],
+    chosen: Vec<usize>,
+    attempts: usize,
+}
+
+impl BlockArranger {
+    pub fn new(blocks: &[(&InstalledBlock, &AugmentPackage)]) -> Self {
+        let mut arranged_blocks: Vec<_> = (blocks.iter())
+            .enumerate()
+            .map(|(index, (block, package))| ArrangedBlock {
+                index,
+                color: block.color,
+                placements: resolve_placements(package),
+                repeats_previous: false,
+            })
+            .collect();
+
+        // place large blocks first, and keep identical blocks together
+        arranged_blocks.sort_by_cached_key(|arranged| {
+            let (block, _) = blocks[arranged.index];
+            let size = arranged.placements.first().map(|p| p.cells.len());
+
+            (
+                std::cmp::Reverse(size.unwrap_or_default()),
+                block.package_id.clone(),
+                block.color as u8,
+            )
+        });
+
+        for i in 1..arranged_blocks.len() {
+            let (previous, _) = blocks[arranged_blocks[i - 1].index];
+            let (block, _) = blocks[arranged_blocks[i].index];
+
+            arranged_blocks[i].repeats_previous =
+                previous.package_id == block.package_id && previous.color == block.color;
+        }
+
+        Self {
+            installed_blocks: blocks.iter().map(|(block, _)| (*block).clone()).collect(),
+            blocks: arranged_blocks,
+            grid: [None; SIDE_LEN * SIDE_LEN],
+            chosen: Vec::new(),
+            attempts: 0,
+        }
+    }
+
+    /// Returns the blocks in their original order with updated rotations and positions
+    pub fn solve(mut self) -> Option<Vec<InstalledBlock>> {
+        let required_cells: usize = (self.blocks.iter())
+            .map(|arranged| arranged.placements.first().map(|p| p.cells.len()))
+            .sum::<Option<usize>>()?;
+
+        if required_cells > (SIDE_LEN - 2) * (SIDE_LEN - 2) {
+            return None;
+        }
+
+        if !self.search(0) {
+            return None;
+        }
+
+        let mut solution = std::mem::take(&mut self.installed_blocks);
+
+        for (arranged, &placement_index) in self.blocks.iter().zip(&self.chosen) {
+            let placement = &arranged.placements[placement_index];
+            let block = &mut solution[arranged.index];
+            block.rotation = placement.rotation;
+            block.position = placement.position;
+        }
+
+        Some(solution)
+    }
+
+    fn search(&mut self, depth: usize) -> bool {
+        let Some(arranged) = self.blocks.get(depth) else {
+            return true;
+        };
+
+        // identical blocks only try placements after the previous copy's placement
+        let start = if arranged.repeats_previous {
+            self.chosen[depth - 1] + 1
+        } else {
+            0
+        };
+
+        for placement_index in start..arranged.placements.len() {
+            self.attempts += 1;
+
+            if self.attempts > SEARCH_LIMIT {
+                return false;
+            }
+
+            let arranged = &self.blocks[depth];
+            let color = arranged.color;
+            let cells = &arranged.placements[placement_index].cells;
+
+            if !self.fits(cells, color) {
+                continue;
+            }
+
+            for &cell in cells {
+                self.grid[cell] = Some(color);
+            }
+
+            self.chosen.push(placement_index);
+
+            if self.search(depth + 1) {
+                return true;
+            }
+
+            self.chosen.pop();
+
+            for &cell in &self.blocks[depth].placements[placement_index].cells {
+                self.grid[cell] = None;
+            }
+        }
+
+        false
+    }
+
+    fn fits(&self, cells: &[usize], color: BlockColor) -> bool {
+        cells.iter().all(|&cell| {
+            if self.grid[cell].is_some() {
+                return false;
+            }
+
+            // cells of the same block aren't placed yet, so any match is another block
+            let (x, y) = (cell % SIDE_LEN, cell / SIDE_LEN);
+            let neighbors = [(x, y - 1), (x - 1, y), (x + 1, y), (x, y + 1)];
+
+            neighbors
+                .into_iter()
+                .all(|(x, y)| self.grid[y * SIDE_LEN + x] != Some(color))
+        })
+    }
+}
+
+/// Placements inside of the main grid that apply without bugs, skipping repeated rotations
+fn resolve_placements(package: &AugmentPackage) -> Vec<Placement> {
+    let mut placements = Vec::new();
+    let mut shapes = Vec::<Vec<(usize, usize)>>::new();
+
+    for rotation in 0..4 {
+        let offsets: Vec<_> = (0..5)
+            .flat_map(|y| (0..5).map(move |x| (x, y)))
+            .filter(|&offset| package.exists_at(rotation, offset))
+            .collect();
+
+        if offsets.is_empty() {
+            return Vec::new();
+        }
+
+        // compare shapes with the top left of each shape aligned
+        let min_x = offsets.iter().map(|(x, _)| *x).min().unwrap_or_default();
+        let min_y = offsets.iter().map(|(_, y)| *y).min().unwrap_or_default();
+        let shape: Vec<_> = (offsets.iter())
+            .map(|(x, y)| (x - min_x, y - min_y))
+            .collect();
+
+        if shapes.contains(&shape) {
+            continue;
+        }
+
+        shapes.push(shape);
+
+        // block positions are offset by two, placing the shape's center on the grid cell
+        for position_y in 0..SIDE_LEN + 2 {
+            for position_x in 0..SIDE_LEN + 2 {
+                let cells: Option<Vec<_>> = (offsets.iter())
+                    .map(|&(x, y)| {
+                        let grid_x = (position_x + x).checked_sub(2)?;
+                        let grid_y = (position_y + y).checked_sub(2)?;
+
+                        if BlockGrid::is_edge((grid_x, grid_y)) {
+                            return None;
+                        }
+
+                        Some((grid_x, grid_y))
+                    })
+                    .collect();
+
+                let Some(cells) = cells else {
+                    continue;
+                };
+
+                let touches_line = cells.iter().any(|(_, y)| *y == BlockGrid::LINE_Y);
+
+                if touches_line != package.is_flat {
+                    continue;
+                }
+
+                placements.push(Placement {
+                    rotation,
+                    position: (position_x, position_y),
+                    cells: cells.iter().map(|(x, y)| y * SIDE_LEN + x).collect(),
+                });
+            }
+        }
+    }
+
+    placements
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::packages::PackageId;
+
+    fn package(is_flat: bool, shape: &[(usize, usize)]) -> AugmentPackage {
+        let mut package = AugmentPackage {
+            is_flat,
+            has_shape: true,
+            ..Default::default()
+        };
+
+        for (x, y) in shape {
+            package.shape[y * 5 + x] = true;
+        }
+
+        package
+    }
+
+    fn block(id: &str, color: BlockColor) -> InstalledBlock {
+        InstalledBlock {
+            package_id: PackageId::from(id),
+            color,
+            ..Default::default()
+        }
+    }
+
+    fn solve(blocks: &[(InstalledBlock, &AugmentPackage)]) -> Option<Vec<InstalledBlock>> {
+        let pairs: Vec<_> = blocks
+            .iter()
+            .map(|(block, package)| (block, *package))
+            .collect();
+
+        BlockArranger::new(&pairs).solve()
+    }
+
+    fn cells(block: &InstalledBlock, package: &AugmentPackage) -> Vec<(usize, usize)> {
+        BlockGrid::iterate_block_positions(block, package).collect()
+    }
+
+    #[test]
+    fn places_flat_blocks_on_the_line() {
+        let single = package(true, &[(2, 2)]);
+        let blocks = vec![
+            (block("single", BlockColor::Red), &single),
+            (block("single", BlockColor::Red), &single),
+            (block("single", BlockColor::Red), &single),
+        ];
+
+        let solution = solve(&blocks).unwrap();
+        let mut all_cells: Vec<_> = (solution.iter())
+            .flat_map(|block| cells(block, &single))
+            .collect();
+        all_cells.sort();
+
+        // three red blocks only fit on the line by leaving gaps between them
+        assert_eq!(all_cells, [(1, 3), (3, 3), (5, 3)]);
+
+        let mut blocks = blocks;
+        blocks.push((block("single", BlockColor::Red), &single));
+        assert!(solve(&blocks).is_none());
+    }
+
+    #[test]
+    fn keeps_other_blocks_off_the_line() {
+        let square = package(false, &[(2, 2), (3, 2), (2, 3), (3, 3)]);
+        let bar = package(true, &[(0, 2), (1, 2), (2, 2), (3, 2), (4, 2)]);
+
+        let blocks = vec![
+            (block("square", BlockColor::Red), &square),
+            (block("bar", BlockColor::Blue), &bar),
+            (block("square", BlockColor::Green), &square),
+        ];
+
+        let solution = solve(&blocks).unwrap();
+
+        for (block, (_, package)) in solution.iter().zip(&blocks) {
+            let cells = cells(block, package);
+            let touches_line = cells.iter().any(|(_, y)| *y == BlockGrid::LINE_Y);
+
+            assert_eq!(touches_line, package.is_flat);
+            assert!(cells.iter().all(|&cell| !BlockGrid::is_edge(cell)));
+        }
+    }
+}
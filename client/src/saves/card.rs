@@ -1,4 +1,4 @@
-use crate::bindable::Element;
+use crate::bindable::{CardClass, Element};
 use crate::packages::*;
 use crate::render::ui::*;
 use crate::render::*;
@@ -6,6 +6,7 @@ use crate::resources::Globals;
 use crate::resources::*;
 use framework::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct Card {
@@ -27,7 +28,7 @@ impl Card {
             .map(|package| package.card_properties.short_name.as_ref())
             .unwrap_or("?????");
 
-        let mut text_style = TextStyle::new_monospace(game_io, FontName::Thick);
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::Thick).with_monospace(true);
         text_style.letter_spacing = 2.0;
         text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
         text_style.bounds.set_position(position);
@@ -43,22 +44,33 @@ impl Card {
         sprite_queue: &mut SpriteColorQueue,
         position: Vec2,
         scale: f32,
+    ) {
+        let (preview_texture, _) = CardPackage::preview_texture(game_io, &self.package_id);
+
+        self.draw_preview_with_art(game_io, sprite_queue, position, scale, preview_texture);
+    }
+
+    /// Allows for drawing with placeholder art while the preview art is unloaded
+    pub fn draw_preview_with_art(
+        &self,
+        game_io: &GameIO,
+        sprite_queue: &mut SpriteColorQueue,
+        position: Vec2,
+        scale: f32,
+        art_texture: Arc<Texture>,
     ) {
         let globals = game_io.resource::<Globals>().unwrap();
-        let assets = &globals.assets;
         let package_manager = &globals.card_packages;
 
-        let (preview_texture_path, element, secondary_element, damage);
+        let (element, secondary_element, damage);
 
         if let Some(package) =
             package_manager.package_or_fallback(PackageNamespace::Local, &self.package_id)
         {
-            preview_texture_path = package.preview_texture_path.as_str();
             element = package.card_properties.element;
             secondary_element = package.card_properties.secondary_element;
             damage = package.card_properties.damage;
         } else {
-            preview_texture_path = "";
             element = Element::None;
             secondary_element = Element::None;
             damage = 0;
@@ -73,16 +85,12 @@ impl Card {
         let scale = Vec2::new(scale, 1.0);
 
         // preview
-        let mut preview_texture = assets.texture(game_io, preview_texture_path);
+        let fit_scale = art_scale(art_texture.size(), CARD_PREVIEW_SIZE);
 
-        if preview_texture.size() == UVec2::new(1, 1) {
-            preview_texture = assets.texture(game_io, ResourcePaths::CARD_PREVIEW_MISSING);
-        }
-
-        let mut sprite = Sprite::new(game_io, preview_texture);
-        sprite.set_origin(-PREVIEW_OFFSET + sprite.size() * 0.5);
+        let mut sprite = Sprite::new(game_io, art_texture);
+        sprite.set_origin(-PREVIEW_OFFSET / fit_scale + sprite.size() * 0.5);
         sprite.set_position(position);
-        sprite.set_scale(scale);
+        sprite.set_scale(scale * fit_scale);
         sprite_queue.draw_sprite(&sprite);
 
         // secondary_element
@@ -102,7 +110,7 @@ impl Card {
         sprite_queue.draw_sprite(&element_sprite);
 
         // code
-        let mut label = TextStyle::new(game_io, FontName::Thick);
+        let mut label = TextStyle::new_unscaled(game_io, FontName::Thick);
         label.letter_spacing = 2.0;
         label.scale = scale;
         label.bounds.set_position(CODE_OFFSET * scale + position);
@@ -132,26 +140,26 @@ impl Card {
         color: Color,
     ) {
         let globals = game_io.resource::<Globals>().unwrap();
-        let assets = &globals.assets;
         let package_manager = &globals.card_packages;
 
-        let (icon_texture_path, short_name, element, limit);
+        let (short_name, element, card_class, limit);
 
         if let Some(package) =
             package_manager.package_or_fallback(PackageNamespace::Local, &self.package_id)
         {
-            icon_texture_path = package.icon_texture_path.as_str();
             short_name = package.card_properties.short_name.as_ref();
             element = package.card_properties.element;
+            card_class = package.card_properties.card_class;
             limit = package.limit;
         } else {
-            icon_texture_path = ResourcePaths::CARD_ICON_MISSING;
             short_name = "?????";
             element = Element::None;
+            card_class = CardClass::Standard;
             limit = 0;
         };
 
         const ICON_OFFSET: Vec2 = Vec2::new(2.0, 1.0);
+        const CLASS_BADGE_OFFSET: Vec2 = Vec2::new(12.0, 10.0);
         const NAME_OFFSET: Vec2 = Vec2::new(18.0, 3.0);
         const ELEMENT_OFFSET: Vec2 = Vec2::new(75.0, 1.0);
         const CODE_OFFSET: Vec2 = Vec2::new(91.0, 3.0);
@@ -159,18 +167,28 @@ impl Card {
         const LIM_OFFSET: Vec2 = Vec2::new(101.0, 8.0);
 
         // icon
-        let mut icon_texture = assets.texture(game_io, icon_texture_path);
-
-        if icon_texture.size() != UVec2::new(14, 14) {
-            icon_texture = assets.texture(game_io, ResourcePaths::CARD_ICON_MISSING);
-        }
+        let (icon_texture, _) =
+            CardPackage::icon_texture(game_io, PackageNamespace::Local, &self.package_id);
+        let icon_scale = art_scale(icon_texture.size(), CARD_ICON_SIZE);
 
         let mut sprite = Sprite::new(game_io, icon_texture);
         sprite.set_position(ICON_OFFSET + position);
+        sprite.set_scale(icon_scale);
         sprite_queue.draw_sprite(&sprite);
 
+        // class badge
+        if let Some((badge_text, badge_color)) = class_badge(card_class) {
+            let mut badge_style = TextStyle::new_unscaled(game_io, FontName::Micro);
+            badge_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+            badge_style.color = badge_color;
+            badge_style
+                .bounds
+                .set_position(CLASS_BADGE_OFFSET + position);
+            badge_style.draw(game_io, sprite_queue, badge_text);
+        }
+
         // text style
-        let mut label = TextStyle::new_monospace(game_io, FontName::Thick);
+        let mut label = TextStyle::new_unscaled(game_io, FontName::Thick).with_monospace(true);
         label.shadow_color = TEXT_DARK_SHADOW_COLOR;
         label.color = color;
 
@@ -201,6 +219,16 @@ impl Card {
     }
 }
 
+/// Marks cards restricted by class limits, standard cards are left unmarked
+fn class_badge(card_class: CardClass) -> Option<(&'static str, Color)> {
+    match card_class {
+        CardClass::Standard => None,
+        CardClass::Mega => Some(("M", Color::from((99, 206, 247, 255)))),
+        CardClass::Giga => Some(("G", Color::from((247, 115, 181, 255)))),
+        CardClass::Dark => Some(("D", Color::from((189, 115, 247, 255)))),
+    }
+}
+
 impl<'lua> rollback_mlua::FromLua<'lua> for Card {
     fn from_lua(
         lua_value: rollback_mlua::Value<'lua>,
@@ -0,0 +1,32 @@
+use super::Card;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A card granted by a server, kept as a record of where the card came from
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CardReward {
+    pub card: Card,
+    pub pack_name: String,
+    /// The address of the server that granted the card
+    pub server_address: String,
+    /// Seconds since the unix epoch
+    pub received_at: u64,
+}
+
+impl CardReward {
+    pub fn new_pack(pack_name: &str, server_address: &str, cards: &[Card]) -> Vec<Self> {
+        let received_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        (cards.iter())
+            .map(|card| Self {
+                card: card.clone(),
+                pack_name: pack_name.to_string(),
+                server_address: server_address.to_string(),
+                received_at,
+            })
+            .collect()
+    }
+}
@@ -1,8 +1,15 @@
+use super::{InputProfile, SaveSyncConfig, TouchLayout};
+use crate::packages::normalize_language;
 use crate::render::PostProcessColorBlindness;
-use crate::resources::{AssetManager, Input, DEFAULT_PACKAGE_REPO, MAX_VOLUME};
+use crate::resources::{
+    AssetManager, AudioBus, Input, ResourcePaths, DEFAULT_ASSET_MEMORY_LIMIT,
+    DEFAULT_AUDIO_CACHE_LIMIT, DEFAULT_NETPLAY_MAX_REDUNDANCY, DEFAULT_PACKAGE_REPO,
+    DEFAULT_TEXT_CHAR_DELAY, MAX_TEXT_SCALE, MAX_VOLUME, MIN_TEXT_SCALE,
+};
 use framework::cfg_macros::{cfg_android, cfg_desktop_and_web};
 use framework::input::{Button, Key};
 use itertools::Itertools;
+use packets::structures::BattleTimers;
 use std::collections::HashMap;
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
@@ -12,8 +19,24 @@ pub enum KeyStyle {
     Emulator,
 }
 
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputDisplayPosition {
+    #[default]
+    Off,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Config {
+    /// Selects package translations, empty uses the text packages are written in
+    pub language: String,
+    /// Log levels per target, see `LogFilters`, empty uses the defaults
+    pub log_filters: String,
+    /// Crash reports can be sent here on the next launch after asking, empty keeps them local
+    pub crash_report_url: String,
     pub fullscreen: bool,
     pub vsync: bool,
     pub lock_aspect_ratio: bool,
@@ -21,16 +44,50 @@ pub struct Config {
     pub saturation: u8,
     pub ghosting: u8,
     pub color_blindness: u8,
+    /// Limits full screen flashes and camera shakes for photosensitive players
+    pub flash_reduction: bool,
+    /// Marks tiles and characters with team patterns in battle
+    pub team_patterns: bool,
+    /// Percentage applied to new text styles, see `text_scale()`
+    pub ui_text_scale: u8,
+    /// Frames between characters in textboxes, 0 displays each page instantly
+    pub text_char_delay: u8,
+    /// Moves on from completed textbox pages without waiting for input
+    pub auto_advance_text: bool,
+    pub asset_memory_limit: u16, // MiB
+    pub input_display: InputDisplayPosition,
+    /// Hides debug and touch overlays for capture software, the input display is kept
+    pub clean_feed: bool,
+    /// Keeps recent frames in memory to save as a clip
+    pub clip_recording: bool,
     pub music: u8,
     pub sfx: u8,
+    pub ui: u8,
+    pub voice: u8,
     pub mute_music: bool,
     pub mute_sfx: bool,
+    /// Announces focused menu items through the platform's screen reader
+    pub screen_reader: bool,
+    /// Empty for the system default
+    pub audio_backend: String,
     pub audio_device: String,
     pub key_style: KeyStyle,
     pub key_bindings: HashMap<Input, Vec<Key>>,
     pub controller_bindings: HashMap<Input, Vec<Button>>,
     pub controller_index: usize,
+    /// key_bindings and controller_bindings hold the active profile's bindings
+    pub input_profiles: Vec<InputProfile>,
+    pub input_profile: usize,
+    pub touch_layout: TouchLayout,
+    pub save_sync: SaveSyncConfig,
     pub package_repo: String,
+    pub audio_cache_limit: u16,  // MiB
+    pub netplay_turn_time: u16,  // seconds
+    pub netplay_match_time: u16, // seconds
+    /// Cap on how many previous inputs are resent with each netplay input
+    pub netplay_max_redundancy: u8,
+    /// Requests delay based netplay without rollback, used when every player opts in
+    pub netplay_lockstep: bool,
 }
 
 impl Config {
@@ -135,6 +192,37 @@ impl Config {
         ])
     }
 
+    /// Stores the live bindings in the active profile
+    pub fn sync_input_profile(&mut self) {
+        if let Some(profile) = self.input_profiles.get_mut(self.input_profile) {
+            profile.key_bindings = self.key_bindings.clone();
+            profile.controller_bindings = self.controller_bindings.clone();
+        }
+    }
+
+    pub fn switch_input_profile(&mut self, index: usize) {
+        if index == self.input_profile || index >= self.input_profiles.len() {
+            return;
+        }
+
+        self.sync_input_profile();
+        self.input_profile = index;
+
+        let profile = &self.input_profiles[index];
+        self.key_bindings = profile.key_bindings.clone();
+        self.controller_bindings = profile.controller_bindings.clone();
+    }
+
+    pub fn input_profile_for_device(&self, guid: &str) -> Option<usize> {
+        if guid.is_empty() {
+            return None;
+        }
+
+        self.input_profiles
+            .iter()
+            .position(|profile| profile.device_guid == guid)
+    }
+
     pub fn music_volume(&self) -> f32 {
         if self.mute_music {
             return 0.0;
@@ -143,16 +231,45 @@ impl Config {
         self.music as f32 / MAX_VOLUME as f32
     }
 
-    pub fn sfx_volume(&self) -> f32 {
-        if self.mute_sfx {
+    /// Muting sfx also mutes the ui and voice buses
+    pub fn bus_volume(&self, bus: AudioBus) -> f32 {
+        let (value, muted) = match bus {
+            AudioBus::Music => (self.music, self.mute_music),
+            AudioBus::Sfx => (self.sfx, self.mute_sfx),
+            AudioBus::Ui => (self.ui, self.mute_sfx),
+            AudioBus::Voice => (self.voice, self.mute_sfx),
+        };
+
+        if muted {
             return 0.0;
         }
 
-        self.sfx as f32 / MAX_VOLUME as f32
+        value as f32 / MAX_VOLUME as f32
+    }
+
+    pub fn text_scale(&self) -> f32 {
+        self.ui_text_scale.clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE) as f32 * 0.01
+    }
+
+    pub fn asset_memory_limit_bytes(&self) -> usize {
+        self.asset_memory_limit as usize * 1024 * 1024
     }
 
+    pub fn audio_cache_limit_bytes(&self) -> u64 {
+        self.audio_cache_limit as u64 * 1024 * 1024
+    }
+
+    pub fn battle_timers(&self) -> BattleTimers {
+        BattleTimers {
+            turn_time: self.netplay_turn_time,
+            match_time: self.netplay_match_time,
+        }
+    }
+
+    pub const PATH: &'static str = "config.ini";
+
     pub fn load(assets: &impl AssetManager) -> Self {
-        let config_text = assets.text("config.ini");
+        let config_text = assets.text(&ResourcePaths::profile_path(Self::PATH));
 
         if config_text.is_empty() {
             let default_config = Config::default();
@@ -172,7 +289,8 @@ impl Config {
     }
 
     pub fn save(&self) {
-        if let Err(err) = std::fs::write("config.ini", self.to_string()) {
+        if let Err(err) = std::fs::write(ResourcePaths::profile_path(Self::PATH), self.to_string())
+        {
             log::error!("Failed to save config: {err:?}");
         }
     }
@@ -204,6 +322,9 @@ impl Config {
 impl Default for Config {
     fn default() -> Config {
         Config {
+            language: String::new(),
+            log_filters: String::new(),
+            crash_report_url: String::new(),
             fullscreen: {
                 cfg_android! {true}
                 cfg_desktop_and_web! {false}
@@ -213,17 +334,39 @@ impl Default for Config {
             brightness: 100,
             saturation: 100,
             ghosting: 0,
+            flash_reduction: false,
+            team_patterns: false,
+            ui_text_scale: 100,
+            text_char_delay: DEFAULT_TEXT_CHAR_DELAY,
+            auto_advance_text: false,
             color_blindness: PostProcessColorBlindness::TOTAL_OPTIONS,
+            asset_memory_limit: DEFAULT_ASSET_MEMORY_LIMIT,
+            input_display: InputDisplayPosition::Off,
+            clean_feed: false,
+            clip_recording: false,
             music: MAX_VOLUME,
             sfx: MAX_VOLUME,
+            ui: MAX_VOLUME,
+            voice: MAX_VOLUME,
             mute_music: false,
             mute_sfx: false,
+            screen_reader: false,
+            audio_backend: String::new(),
             audio_device: String::new(),
             key_style: Default::default(),
             key_bindings: Self::default_key_bindings(Default::default()),
             controller_bindings: Self::default_controller_bindings(),
             controller_index: 0,
+            input_profiles: InputProfile::default_profiles(),
+            input_profile: 0,
+            touch_layout: TouchLayout::default(),
+            save_sync: SaveSyncConfig::default(),
             package_repo: String::from(DEFAULT_PACKAGE_REPO),
+            audio_cache_limit: DEFAULT_AUDIO_CACHE_LIMIT,
+            netplay_turn_time: 0,
+            netplay_match_time: 0,
+            netplay_max_redundancy: DEFAULT_NETPLAY_MAX_REDUNDANCY,
+            netplay_lockstep: false,
         }
     }
 }
@@ -235,23 +378,48 @@ impl From<&str> for Config {
         use strum::IntoEnumIterator;
 
         let mut config = Config {
+            language: String::new(),
+            log_filters: String::new(),
+            crash_report_url: String::new(),
             fullscreen: false,
             vsync: true,
             lock_aspect_ratio: true,
             brightness: 100,
             saturation: 100,
             ghosting: 0,
+            flash_reduction: false,
+            team_patterns: false,
+            ui_text_scale: 100,
+            text_char_delay: DEFAULT_TEXT_CHAR_DELAY,
+            auto_advance_text: false,
             color_blindness: PostProcessColorBlindness::TOTAL_OPTIONS,
+            asset_memory_limit: DEFAULT_ASSET_MEMORY_LIMIT,
+            input_display: InputDisplayPosition::Off,
+            clean_feed: false,
+            clip_recording: false,
             music: MAX_VOLUME,
             sfx: MAX_VOLUME,
+            ui: MAX_VOLUME,
+            voice: MAX_VOLUME,
             mute_music: false,
             mute_sfx: false,
+            screen_reader: false,
+            audio_backend: String::new(),
             audio_device: String::new(),
             key_style: Default::default(),
             key_bindings: HashMap::new(),
             controller_bindings: HashMap::new(),
             controller_index: 0,
+            input_profiles: InputProfile::default_profiles(),
+            input_profile: 0,
+            touch_layout: TouchLayout::default(),
+            save_sync: SaveSyncConfig::default(),
             package_repo: String::from(DEFAULT_PACKAGE_REPO),
+            audio_cache_limit: DEFAULT_AUDIO_CACHE_LIMIT,
+            netplay_turn_time: 0,
+            netplay_match_time: 0,
+            netplay_max_redundancy: DEFAULT_NETPLAY_MAX_REDUNDANCY,
+            netplay_lockstep: false,
         };
 
         use ini::Ini;
@@ -264,6 +432,15 @@ impl From<&str> for Config {
             }
         };
 
+        if let Some(properties) = ini.section(Some("General")) {
+            let language = properties.get("Language").unwrap_or_default();
+            config.language = normalize_language(language);
+            config.log_filters = properties.get("LogFilters").unwrap_or_default().to_string();
+            config.crash_report_url = (properties.get("CrashReportUrl").unwrap_or_default())
+                .trim()
+                .to_string();
+        }
+
         if let Some(properties) = ini.section(Some("Video")) {
             config.fullscreen = parse_or_default(properties.get("Fullscreen"));
             config.vsync = parse_or(properties.get("VSync"), true);
@@ -275,13 +452,41 @@ impl From<&str> for Config {
                 properties.get("ColorBlindness"),
                 PostProcessColorBlindness::TOTAL_OPTIONS,
             );
+            config.flash_reduction = parse_or_default(properties.get("FlashReduction"));
+            config.team_patterns = parse_or_default(properties.get("TeamPatterns"));
+            config.ui_text_scale =
+                parse_or(properties.get("TextScale"), 100u8).clamp(MIN_TEXT_SCALE, MAX_TEXT_SCALE);
+            config.text_char_delay =
+                parse_or(properties.get("TextCharDelay"), DEFAULT_TEXT_CHAR_DELAY);
+            config.auto_advance_text = parse_or_default(properties.get("AutoAdvanceText"));
+            config.asset_memory_limit = parse_or(
+                properties.get("AssetMemoryLimit"),
+                DEFAULT_ASSET_MEMORY_LIMIT,
+            );
+
+            let input_display_str = properties.get("InputDisplay").unwrap_or_default();
+
+            config.input_display = match input_display_str.to_lowercase().as_str() {
+                "topleft" => InputDisplayPosition::TopLeft,
+                "topright" => InputDisplayPosition::TopRight,
+                "bottomleft" => InputDisplayPosition::BottomLeft,
+                "bottomright" => InputDisplayPosition::BottomRight,
+                _ => InputDisplayPosition::Off,
+            };
+
+            config.clean_feed = parse_or_default(properties.get("CleanFeed"));
+            config.clip_recording = parse_or_default(properties.get("ClipRecording"));
         }
 
         if let Some(properties) = ini.section(Some("Audio")) {
             config.music = parse_or_default::<u8>(properties.get("Music")).min(MAX_VOLUME);
             config.sfx = parse_or_default::<u8>(properties.get("SFX")).min(MAX_VOLUME);
+            config.ui = parse_or(properties.get("UI"), MAX_VOLUME).min(MAX_VOLUME);
+            config.voice = parse_or(properties.get("Voice"), MAX_VOLUME).min(MAX_VOLUME);
             config.mute_music = parse_or_default(properties.get("MuteMusic"));
             config.mute_sfx = parse_or_default(properties.get("MuteSFX"));
+            config.screen_reader = parse_or_default(properties.get("ScreenReader"));
+            config.audio_backend = properties.get("Backend").unwrap_or_default().to_string();
             config.audio_device = properties
                 .get("OutputDevice")
                 .unwrap_or_default()
@@ -313,6 +518,7 @@ impl From<&str> for Config {
 
         if let Some(properties) = ini.section(Some("Controller")) {
             config.controller_index = parse_or_default(properties.get("ControllerIndex"));
+            config.input_profile = parse_or_default(properties.get("InputProfile"));
 
             for input in Input::iter() {
                 let input_string = format!("{input:?}");
@@ -328,6 +534,30 @@ impl From<&str> for Config {
             }
         }
 
+        config.input_profiles = (0..)
+            .map_while(|i| ini.section(Some(InputProfile::section_name(i))))
+            .map(InputProfile::parse)
+            .collect();
+
+        if config.input_profiles.is_empty() {
+            config.input_profiles = InputProfile::default_profiles();
+        }
+
+        if config.input_profile >= config.input_profiles.len() {
+            config.input_profile = 0;
+        }
+
+        // the Keyboard and Controller sections hold the latest bindings for the active profile
+        config.sync_input_profile();
+
+        if let Some(properties) = ini.section(Some(TouchLayout::SECTION_NAME)) {
+            config.touch_layout = TouchLayout::parse(properties);
+        }
+
+        if let Some(properties) = ini.section(Some(SaveSyncConfig::SECTION_NAME)) {
+            config.save_sync = SaveSyncConfig::parse(properties);
+        }
+
         if let Some(properties) = ini.section(Some("Online")) {
             config.package_repo = properties
                 .get("PackageRepo")
@@ -337,6 +567,16 @@ impl From<&str> for Config {
             if config.package_repo.is_empty() {
                 config.package_repo = String::from(DEFAULT_PACKAGE_REPO);
             }
+
+            config.audio_cache_limit =
+                parse_or(properties.get("AudioCacheLimit"), DEFAULT_AUDIO_CACHE_LIMIT);
+            config.netplay_turn_time = parse_or_default(properties.get("NetplayTurnTime"));
+            config.netplay_match_time = parse_or_default(properties.get("NetplayMatchTime"));
+            config.netplay_max_redundancy = parse_or(
+                properties.get("NetplayMaxRedundancy"),
+                DEFAULT_NETPLAY_MAX_REDUNDANCY,
+            );
+            config.netplay_lockstep = parse_or_default(properties.get("NetplayLockstep"));
         }
 
         config
@@ -351,6 +591,11 @@ impl ToString for Config {
         let attempt = || -> Result<String, std::fmt::Error> {
             let mut s = String::new();
 
+            writeln!(s, "[General]")?;
+            writeln!(s, "Language = {}", self.language)?;
+            writeln!(s, "LogFilters = {}", self.log_filters)?;
+            writeln!(s, "CrashReportUrl = {}", self.crash_report_url)?;
+
             writeln!(s, "[Video]")?;
             writeln!(s, "Fullscreen = {}", self.fullscreen)?;
             writeln!(s, "VSync = {}", self.vsync)?;
@@ -359,12 +604,33 @@ impl ToString for Config {
             writeln!(s, "Saturation = {}", self.saturation)?;
             writeln!(s, "Ghosting = {}", self.ghosting)?;
             writeln!(s, "ColorBlindness = {}", self.color_blindness)?;
+            writeln!(s, "FlashReduction = {}", self.flash_reduction)?;
+            writeln!(s, "TeamPatterns = {}", self.team_patterns)?;
+            writeln!(s, "TextScale = {}", self.ui_text_scale)?;
+            writeln!(s, "TextCharDelay = {}", self.text_char_delay)?;
+            writeln!(s, "AutoAdvanceText = {}", self.auto_advance_text)?;
+            writeln!(s, "AssetMemoryLimit = {}", self.asset_memory_limit)?;
+
+            match self.input_display {
+                InputDisplayPosition::Off => writeln!(s, "InputDisplay = Off")?,
+                InputDisplayPosition::TopLeft => writeln!(s, "InputDisplay = TopLeft")?,
+                InputDisplayPosition::TopRight => writeln!(s, "InputDisplay = TopRight")?,
+                InputDisplayPosition::BottomLeft => writeln!(s, "InputDisplay = BottomLeft")?,
+                InputDisplayPosition::BottomRight => writeln!(s, "InputDisplay = BottomRight")?,
+            }
+
+            writeln!(s, "CleanFeed = {}", self.clean_feed)?;
+            writeln!(s, "ClipRecording = {}", self.clip_recording)?;
 
             writeln!(s, "[Audio]")?;
             writeln!(s, "Music = {}", self.music)?;
             writeln!(s, "SFX = {}", self.sfx)?;
+            writeln!(s, "UI = {}", self.ui)?;
+            writeln!(s, "Voice = {}", self.voice)?;
             writeln!(s, "MuteMusic = {}", self.mute_music)?;
             writeln!(s, "MuteSFX = {}", self.mute_sfx)?;
+            writeln!(s, "ScreenReader = {}", self.screen_reader)?;
+            writeln!(s, "Backend = {}", self.audio_backend)?;
             writeln!(s, "OutputDevice = {}", self.audio_device)?;
 
             writeln!(s, "[Keyboard]")?;
@@ -391,6 +657,7 @@ impl ToString for Config {
 
             writeln!(s, "[Controller]")?;
             writeln!(s, "ControllerIndex = {}", self.controller_index)?;
+            writeln!(s, "InputProfile = {}", self.input_profile)?;
 
             for input in Input::iter() {
                 write!(s, "{input:?} = ")?;
@@ -407,6 +674,20 @@ impl ToString for Config {
                 }
             }
 
+            for (i, profile) in self.input_profiles.iter().enumerate() {
+                let mut profile = profile.clone();
+
+                if i == self.input_profile {
+                    profile.key_bindings = self.key_bindings.clone();
+                    profile.controller_bindings = self.controller_bindings.clone();
+                }
+
+                profile.write(&mut s, i)?;
+            }
+
+            self.touch_layout.write(&mut s)?;
+            self.save_sync.write(&mut s)?;
+
             writeln!(s, "[Online]")?;
 
             if self.package_repo != DEFAULT_PACKAGE_REPO {
@@ -415,6 +696,12 @@ impl ToString for Config {
                 writeln!(s, "PackageRepo = ",)?;
             }
 
+            writeln!(s, "AudioCacheLimit = {}", self.audio_cache_limit)?;
+            writeln!(s, "NetplayTurnTime = {}", self.netplay_turn_time)?;
+            writeln!(s, "NetplayMatchTime = {}", self.netplay_match_time)?;
+            writeln!(s, "NetplayMaxRedundancy = {}", self.netplay_max_redundancy)?;
+            writeln!(s, "NetplayLockstep = {}", self.netplay_lockstep)?;
+
             Ok(s)
         };
 
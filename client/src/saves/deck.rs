@@ -61,6 +61,23 @@ impl Deck {
         game_io: &GameIO,
         rng: &mut impl rand::Rng,
         namespace: PackageNamespace,
+    ) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        self.shuffle_with(rng, |card| {
+            let package_manager = &globals.card_packages;
+            let package = package_manager.package_or_fallback(namespace, &card.package_id)?;
+
+            Some(package.card_properties.card_class)
+        });
+    }
+
+    /// Shuffles without package lookups, `class_of` is used to keep gigas out of the opening cards
+    /// and should return None for cards with missing packages
+    pub fn shuffle_with(
+        &mut self,
+        rng: &mut impl rand::Rng,
+        class_of: impl Fn(&Card) -> Option<CardClass>,
     ) {
         use rand::seq::SliceRandom;
 
@@ -80,19 +97,13 @@ impl Deck {
             return;
         }
 
-        let globals = game_io.resource::<Globals>().unwrap();
-
         let mut non_giga_vec = Vec::new();
         // Cycle every card past the required 10 to count non-gigas
         for i in 10..self.cards.len() {
-            let non_giga_card = &self.cards[i];
-            let Some(package) = globals
-                .card_packages
-                .package_or_fallback(namespace, &non_giga_card.package_id)
-            else {
+            let Some(card_class) = class_of(&self.cards[i]) else {
                 continue;
             };
-            if package.card_properties.card_class == CardClass::Giga {
+            if card_class == CardClass::Giga {
                 continue;
             }
             non_giga_vec.push(i);
@@ -109,16 +120,12 @@ impl Deck {
         // Cycle the initial 10 starting at index 0
         for i in 0..=9 {
             // Get the card we're on, skip loop if it's blank somehow
-            let card = &self.cards[i];
-            let Some(package) = globals
-                .card_packages
-                .package_or_fallback(namespace, &card.package_id)
-            else {
+            let Some(card_class) = class_of(&self.cards[i]) else {
                 continue;
             };
 
             // If it's not giga, don't proceed.
-            if package.card_properties.card_class != CardClass::Giga {
+            if card_class != CardClass::Giga {
                 continue;
             }
 
@@ -0,0 +1,233 @@
+use super::{Card, Deck};
+use crate::packages::PackageNamespace;
+use crate::resources::{Globals, ResourcePaths};
+use framework::prelude::GameIO;
+use packets::structures::PackageId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Package ids are stored once and referenced by index from each card
+#[derive(Serialize, Deserialize)]
+struct DeckShareData {
+    version: u8,
+    name: String,
+    package_ids: Vec<PackageId>,
+    cards: Vec<(u16, String)>,
+    regular_index: Option<usize>,
+}
+
+/// Folders encoded as text for sharing in chat: compressed msgpack in base32
+pub struct DeckShareCode;
+
+impl DeckShareCode {
+    pub const PREFIX: &'static str = "FLDR-";
+    const VERSION: u8 = 1;
+    /// Share codes come from other players, limits how much a code can expand to
+    const MAX_DATA_LEN: u64 = 64 * 1024;
+
+    pub fn encode(deck: &Deck) -> Result<String, String> {
+        let mut package_ids = Vec::new();
+        let mut package_lookup = HashMap::new();
+
+        let cards = (deck.cards.iter())
+            .map(|card| {
+                let index = *package_lookup.entry(&card.package_id).or_insert_with(|| {
+                    package_ids.push(card.package_id.clone());
+                    package_ids.len() - 1
+                });
+
+                (index as u16, card.code.clone())
+            })
+            .collect();
+
+        let data = DeckShareData {
+            version: Self::VERSION,
+            name: deck.name.clone(),
+            package_ids,
+            cards,
+            regular_index: deck.regular_index,
+        };
+
+        let bytes = rmp_serde::to_vec(&data).map_err(|e| e.to_string())?;
+
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&bytes).map_err(|e| e.to_string())?;
+        let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+        Ok(String::from(Self::PREFIX) + &base32_encode(&compressed))
+    }
+
+    pub fn decode(code: &str) -> Result<Deck, String> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let code = code.to_uppercase();
+
+        let Some(code) = code.strip_prefix(Self::PREFIX) else {
+            return Err(String::from("Not a folder code"));
+        };
+
+        let compressed = base32_decode(code).ok_or_else(|| String::from("Not a folder code"))?;
+
+        let mut bytes = Vec::new();
+        flate2::read::DeflateDecoder::new(compressed.as_slice())
+            .take(Self::MAX_DATA_LEN)
+            .read_to_end(&mut bytes)
+            .map_err(|_| String::from("The folder code is damaged"))?;
+
+        let data: DeckShareData = rmp_serde::from_slice(&bytes)
+            .map_err(|_| String::from("The folder code is damaged"))?;
+
+        if data.version > Self::VERSION {
+            return Err(String::from("Shared from a newer version of the game"));
+        }
+
+        let cards = (data.cards.into_iter())
+            .map(|(index, code)| {
+                let package_id = data.package_ids.get(index as usize)?.clone();
+                Some(Card { package_id, code })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| String::from("The folder code is damaged"))?;
+
+        let regular_index = data.regular_index.filter(|index| *index < cards.len());
+
+        Ok(Deck {
+            name: data.name.chars().take(Deck::NAME_MAX_LEN).collect(),
+            cards,
+            regular_index,
+        })
+    }
+
+    /// Writes the code to the folder code folder, returns the path of the new file
+    pub fn write(deck: &Deck, code: &str) -> Result<String, String> {
+        let folder = ResourcePaths::game_folder().to_string() + ResourcePaths::FOLDER_CODE_FOLDER;
+        let _ = std::fs::create_dir_all(&folder);
+
+        let name: String = (deck.name.chars())
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        let path = format!("{folder}{name}.txt");
+        std::fs::write(&path, code).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+
+        Ok(path)
+    }
+
+    /// Card packages used by the folder that aren't installed
+    pub fn missing_packages(globals: &Globals, deck: &Deck) -> Vec<PackageId> {
+        let mut missing_packages: Vec<_> = (deck.cards.iter())
+            .map(|card| &card.package_id)
+            .filter(|id| {
+                (globals.card_packages)
+                    .package_or_fallback(PackageNamespace::Local, id)
+                    .is_none()
+            })
+            .cloned()
+            .collect();
+
+        missing_packages.sort();
+        missing_packages.dedup();
+        missing_packages
+    }
+
+    /// Copies used by the folder past what the player owns
+    pub fn unowned_card_count(game_io: &GameIO, deck: &Deck) -> usize {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let mut use_counts = HashMap::<&Card, usize>::new();
+
+        for card in &deck.cards {
+            *use_counts.entry(card).or_default() += 1;
+        }
+
+        use_counts
+            .into_iter()
+            .map(|(card, count)| {
+                let owned = globals.restrictions.card_count(game_io, card);
+                count.saturating_sub(owned)
+            })
+            .sum()
+    }
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+    }
+
+    output
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|b| *b == c)? as u32;
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base32_round_trip() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+
+        for len in 0..16 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            assert_eq!(base32_decode(&base32_encode(&bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn share_code_round_trip() {
+        let mut deck = Deck::new(String::from("Shared"));
+        deck.regular_index = Some(1);
+
+        for (id, code) in [("cannon", "A"), ("sword", "S"), ("cannon", "B")] {
+            deck.cards.push(Card {
+                package_id: PackageId::from(id),
+                code: code.to_string(),
+            });
+        }
+
+        let code = DeckShareCode::encode(&deck).unwrap();
+        assert!(code.starts_with(DeckShareCode::PREFIX));
+
+        // pasted codes may be lowercase or wrapped
+        let pasted = code.to_lowercase().replace('a', "a\n");
+        let decoded = DeckShareCode::decode(&pasted).unwrap();
+
+        assert!(decoded == deck);
+        assert!(DeckShareCode::decode("FLDR-11").is_err());
+    }
+}
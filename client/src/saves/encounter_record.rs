@@ -0,0 +1,27 @@
+use crate::battle::EncounterMedal;
+use crate::render::FrameTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncounterRecord {
+    pub wins: u32,
+    pub best_time: Option<FrameTime>,
+    pub medals: Vec<EncounterMedal>,
+}
+
+impl EncounterRecord {
+    pub fn record_win(&mut self, time: FrameTime, medals: &[EncounterMedal]) {
+        self.wins += 1;
+
+        if self.best_time.map_or(true, |best_time| time < best_time) {
+            self.best_time = Some(time);
+        }
+
+        for medal in medals {
+            if !self.medals.contains(medal) {
+                self.medals.push(*medal);
+            }
+        }
+    }
+}
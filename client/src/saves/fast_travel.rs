@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FastTravelPoint {
+    /// The path the server sent the map from, see `MapJournal`
+    pub map_path: String,
+    pub area_name: String,
+    pub object_id: u32,
+    pub name: String,
+}
+
+/// Warps visited on a single server
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FastTravelLog {
+    /// Sorted by first visit
+    pub points: Vec<FastTravelPoint>,
+}
+
+impl FastTravelLog {
+    /// Returns true if the point is new or its names changed
+    pub fn record(&mut self, point: FastTravelPoint) -> bool {
+        let existing = (self.points.iter_mut())
+            .find(|p| p.map_path == point.map_path && p.object_id == point.object_id);
+
+        match existing {
+            Some(existing) if *existing == point => false,
+            Some(existing) => {
+                *existing = point;
+                true
+            }
+            None => {
+                self.points.push(point);
+                true
+            }
+        }
+    }
+
+    /// Forgets points for an area, used when a server marks a previously recorded map as private
+    pub fn remove_area(&mut self, map_path: &str) -> bool {
+        let original_len = self.points.len();
+        self.points.retain(|point| point.map_path != map_path);
+        self.points.len() != original_len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn point(map_path: &str, object_id: u32, name: &str) -> FastTravelPoint {
+        FastTravelPoint {
+            map_path: map_path.to_string(),
+            area_name: String::from("Area"),
+            object_id,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn points_are_recorded_once() {
+        let mut log = FastTravelLog::default();
+
+        assert!(log.record(point("/server/maps/a.tmx", 1, "Square")));
+        assert!(!log.record(point("/server/maps/a.tmx", 1, "Square")));
+        assert!(log.record(point("/server/maps/b.tmx", 1, "Square")));
+        assert!(log.record(point("/server/maps/a.tmx", 1, "Plaza")));
+
+        let names: Vec<_> = log.points.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Plaza", "Square"]);
+
+        assert!(log.remove_area("/server/maps/a.tmx"));
+        assert!(!log.remove_area("/server/maps/a.tmx"));
+        assert_eq!(log.points.len(), 1);
+    }
+}
@@ -1,8 +1,11 @@
-use super::{BlockGrid, Deck, InstalledBlock, ServerInfo};
+use super::{
+    Achievements, BlockGrid, CardReward, Deck, EncounterRecord, FastTravelLog, InstalledBlock,
+    Loadout, QuestLog, SaveBackup, ServerInfo, ServerPreferences,
+};
 use crate::packages::*;
-use crate::resources::{AssetManager, Globals};
+use crate::resources::{AssetManager, Globals, ResourcePaths};
 use framework::prelude::GameIO;
-use packets::structures::InstalledSwitchDrive;
+use packets::structures::{InstalledSwitchDrive, PackageCategory};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -17,6 +20,13 @@ pub struct GlobalSave {
     pub installed_blocks: HashMap<PackageId, Vec<InstalledBlock>>,
     pub installed_drive_parts: HashMap<PackageId, Vec<InstalledSwitchDrive>>,
     pub resource_package_order: Vec<(PackageId, bool)>,
+    pub server_preferences: HashMap<String, ServerPreferences>, // address -> preferences
+    pub encounter_records: HashMap<PackageId, EncounterRecord>,
+    pub loadouts: Vec<Loadout>,
+    pub card_rewards: Vec<CardReward>,
+    pub quest_logs: HashMap<String, QuestLog>, // address -> quest log
+    pub fast_travel_logs: HashMap<String, FastTravelLog>, // address -> visited warps
+    pub achievements: Achievements,
 }
 
 impl GlobalSave {
@@ -28,7 +38,7 @@ impl GlobalSave {
     }
 
     pub fn load(assets: &impl AssetManager) -> Self {
-        let bytes = assets.binary(Self::PATH);
+        let bytes = assets.binary(&ResourcePaths::profile_path(Self::PATH));
 
         if bytes.is_empty() {
             // no save data
@@ -40,29 +50,77 @@ impl GlobalSave {
             Ok(save) => save,
             Err(e) => {
                 log::error!("Failed to load save data: {}", e);
-                log::info!("Backing up corrupted data to {:?}", Self::CORRUPTED_PATH);
+                let corrupted_path = ResourcePaths::profile_path(Self::CORRUPTED_PATH);
+                log::info!("Backing up corrupted data to {corrupted_path:?}");
+                log::info!("Older saves can be restored from Config > Profile > Restore Backup");
 
                 // crash if we can't back up the corrupted save
                 // we never want to accidentally reset a player's save, it should be recoverable
-                std::fs::write(Self::CORRUPTED_PATH, bytes).unwrap();
+                std::fs::write(corrupted_path, bytes).unwrap();
 
                 Self::default()
             }
         }
     }
 
+    /// Used to verify save data from other sources before replacing the current save
+    pub fn is_valid(bytes: &[u8]) -> bool {
+        rmp_serde::from_slice::<Self>(bytes).is_ok()
+    }
+
     pub fn save(&self) {
         use std::fs::File;
 
         log::info!("Saving...");
 
-        let mut file = File::create(Self::PATH).unwrap();
+        SaveBackup::back_up_current();
+
+        let path = ResourcePaths::profile_path(Self::PATH);
+        let mut file = File::create(&path).unwrap();
 
         if let Err(e) = rmp_serde::encode::write_named(&mut file, self) {
-            log::error!("Failed to save data to {:?}: {}", Self::PATH, e);
+            log::error!("Failed to save data to {:?}: {}", path, e);
         }
     }
 
+    pub fn server_preferences(&self, address: &str) -> ServerPreferences {
+        let address = packets::address_parsing::strip_data(address);
+
+        (self.server_preferences.get(address))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_server_preferences(&mut self, address: &str, preferences: ServerPreferences) {
+        let address = packets::address_parsing::strip_data(address).to_string();
+
+        if preferences == ServerPreferences::default() {
+            self.server_preferences.remove(&address);
+        } else {
+            self.server_preferences.insert(address, preferences);
+        }
+    }
+
+    pub fn quest_log(&self, address: &str) -> Option<&QuestLog> {
+        let address = packets::address_parsing::strip_data(address);
+        self.quest_logs.get(address)
+    }
+
+    pub fn quest_log_mut(&mut self, address: &str) -> &mut QuestLog {
+        let address = packets::address_parsing::strip_data(address).to_string();
+        self.quest_logs.entry(address).or_default()
+    }
+
+    pub fn fast_travel_log(&self, address: &str) -> Option<&FastTravelLog> {
+        let address = packets::address_parsing::strip_data(address);
+        self.fast_travel_logs.get(address)
+    }
+
+    pub fn fast_travel_log_mut(&mut self, address: &str) -> &mut FastTravelLog {
+        let address = packets::address_parsing::strip_data(address).to_string();
+        self.fast_travel_logs.entry(address).or_default()
+    }
+
     pub fn player_package<'a>(&self, game_io: &'a GameIO) -> Option<&'a PlayerPackage> {
         let player_id = &self.selected_character;
 
@@ -91,6 +149,21 @@ impl GlobalSave {
             .unwrap_or(&[])
     }
 
+    /// Renames a folder, keeping loadouts pointed at it
+    pub fn rename_deck(&mut self, index: usize, name: String) {
+        let Some(deck) = self.decks.get_mut(index) else {
+            return;
+        };
+
+        for loadout in &mut self.loadouts {
+            if loadout.deck_name.as_ref() == Some(&deck.name) {
+                loadout.deck_name = Some(name.clone());
+            }
+        }
+
+        deck.name = name;
+    }
+
     pub fn valid_augments<'a>(
         &self,
         game_io: &'a GameIO,
@@ -111,6 +184,51 @@ impl GlobalSave {
         block_grid.augments(game_io)
     }
 
+    /// Every package referenced by the save, without duplicates
+    pub fn required_packages(&self) -> Vec<(PackageCategory, PackageId)> {
+        let players = std::iter::once(&self.selected_character)
+            .chain(self.installed_blocks.keys())
+            .chain(self.installed_drive_parts.keys())
+            .chain(self.loadouts.iter().map(|loadout| &loadout.character))
+            .filter(|id| !id.as_str().is_empty())
+            .map(|id| (PackageCategory::Player, id.clone()));
+
+        let cards = (self.decks.iter())
+            .flat_map(|deck| deck.cards.iter())
+            .map(|card| (PackageCategory::Card, card.package_id.clone()));
+
+        let loadout_blocks = self.loadouts.iter().flat_map(|loadout| &loadout.blocks);
+        let blocks = (self.installed_blocks.values().flatten())
+            .chain(loadout_blocks)
+            .map(|block| (PackageCategory::Augment, block.package_id.clone()));
+
+        let loadout_drive_parts = self
+            .loadouts
+            .iter()
+            .flat_map(|loadout| &loadout.drive_parts);
+        let drive_parts = (self.installed_drive_parts.values().flatten())
+            .chain(loadout_drive_parts)
+            .map(|part| (PackageCategory::Augment, part.package_id.clone()));
+
+        let encounters =
+            (self.encounter_records.keys()).map(|id| (PackageCategory::Encounter, id.clone()));
+
+        let resources = (self.resource_package_order.iter())
+            .map(|(id, _)| (PackageCategory::Resource, id.clone()));
+
+        let mut packages: Vec<_> = players
+            .chain(cards)
+            .chain(blocks)
+            .chain(drive_parts)
+            .chain(encounters)
+            .chain(resources)
+            .collect();
+
+        packages.sort_by(|a, b| (a.0 as u8, &a.1).cmp(&(b.0 as u8, &b.1)));
+        packages.dedup();
+        packages
+    }
+
     pub fn update_package_id(&mut self, old_id: &PackageId, new_id: &PackageId) {
         if old_id == new_id {
             return;
@@ -156,6 +274,40 @@ impl GlobalSave {
             }
         }
 
+        // update loadouts
+        for loadout in &mut self.loadouts {
+            if loadout.character == *old_id {
+                loadout.character = new_id.clone();
+            }
+
+            for block in &mut loadout.blocks {
+                if block.package_id == *old_id {
+                    block.package_id = new_id.clone();
+                }
+            }
+
+            for part in &mut loadout.drive_parts {
+                if part.package_id == *old_id {
+                    part.package_id = new_id.clone();
+                }
+            }
+        }
+
+        // update card rewards
+        for reward in &mut self.card_rewards {
+            if reward.card.package_id == *old_id {
+                reward.card.package_id = new_id.clone();
+            }
+        }
+
+        // update achievements
+        self.achievements.update_package_id(old_id, new_id);
+
+        // update encounter records
+        if let Some(record) = self.encounter_records.remove(old_id) {
+            self.encounter_records.insert(new_id.clone(), record);
+        }
+
         // update resources
         for (id, _) in &mut self.resource_package_order {
             if id == old_id {
@@ -176,6 +328,13 @@ impl Default for GlobalSave {
             installed_blocks: HashMap::new(),
             installed_drive_parts: HashMap::new(),
             resource_package_order: Vec::new(),
+            server_preferences: HashMap::new(),
+            encounter_records: HashMap::new(),
+            loadouts: Vec::new(),
+            card_rewards: Vec::new(),
+            quest_logs: HashMap::new(),
+            fast_travel_logs: HashMap::new(),
+            achievements: Achievements::default(),
         }
     }
 }
@@ -0,0 +1,98 @@
+use super::{Config, KeyStyle};
+use crate::resources::Input;
+use framework::input::{Button, Key};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// Named set of bindings, such as one per gamepad
+#[derive(Clone, PartialEq, Eq)]
+pub struct InputProfile {
+    pub name: String,
+    /// Selects this profile when a gamepad with a matching GUID connects, empty to disable
+    pub device_guid: String,
+    pub key_bindings: HashMap<Input, Vec<Key>>,
+    pub controller_bindings: HashMap<Input, Vec<Button>>,
+}
+
+impl InputProfile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            device_guid: String::new(),
+            key_bindings: Config::default_key_bindings(KeyStyle::default()),
+            controller_bindings: Config::default_controller_bindings(),
+        }
+    }
+
+    pub fn default_profiles() -> Vec<Self> {
+        vec![
+            Self::new("Keyboard"),
+            Self::new("Pad 1"),
+            Self::new("Pad 2"),
+        ]
+    }
+
+    pub(super) fn section_name(index: usize) -> String {
+        format!("InputProfile{index}")
+    }
+
+    pub(super) fn parse(properties: &ini::Properties) -> Self {
+        let mut profile = Self {
+            name: properties.get("Name").unwrap_or_default().to_string(),
+            device_guid: properties.get("Device").unwrap_or_default().to_string(),
+            key_bindings: HashMap::new(),
+            controller_bindings: HashMap::new(),
+        };
+
+        for input in Input::iter() {
+            let keys = parse_list(properties.get(&format!("Key.{input:?}")));
+            profile.key_bindings.insert(input, keys);
+
+            let buttons = parse_list(properties.get(&format!("Button.{input:?}")));
+            profile.controller_bindings.insert(input, buttons);
+        }
+
+        profile
+    }
+
+    pub(super) fn write(&self, s: &mut String, index: usize) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        writeln!(s, "[{}]", Self::section_name(index))?;
+        writeln!(s, "Name = {}", self.name)?;
+        writeln!(s, "Device = {}", self.device_guid)?;
+
+        for input in Input::iter() {
+            let keys = self.key_bindings.get(&input).map(|keys| {
+                keys.iter()
+                    .map(|key| -> &'static str { key.into() })
+                    .join(",")
+            });
+
+            let buttons = self.controller_bindings.get(&input).map(|buttons| {
+                buttons
+                    .iter()
+                    .map(|button| -> &'static str { button.into() })
+                    .join(",")
+            });
+
+            let keys = keys.as_deref().unwrap_or("None");
+            let buttons = buttons.as_deref().unwrap_or("None");
+
+            writeln!(s, "Key.{input:?} = {keys}")?;
+            writeln!(s, "Button.{input:?} = {buttons}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_list<T: FromStr>(value: Option<&str>) -> Vec<T> {
+    value
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .flat_map(|value| T::from_str(value).ok())
+        .collect()
+}
@@ -0,0 +1,108 @@
+use super::{Deck, GlobalSave, InstalledBlock};
+use crate::packages::PackageId;
+use packets::structures::InstalledSwitchDrive;
+use serde::{Deserialize, Serialize};
+
+/// A named build: character, folder, blocks, and switch drives
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Loadout {
+    pub name: String,
+    pub character: PackageId,
+    /// Folders are referenced by name, indices shift as folders are moved and removed
+    pub deck_name: Option<String>,
+    pub blocks: Vec<InstalledBlock>,
+    pub drive_parts: Vec<InstalledSwitchDrive>,
+}
+
+impl Loadout {
+    pub const NAME_MAX_LEN: usize = 8;
+
+    /// The character, folder, blocks, and drives currently equipped
+    pub fn current(global_save: &GlobalSave) -> Self {
+        Self {
+            name: String::new(),
+            character: global_save.selected_character.clone(),
+            deck_name: global_save.active_deck().map(|deck| deck.name.clone()),
+            blocks: global_save.active_blocks().to_vec(),
+            drive_parts: global_save.active_drive_parts().to_vec(),
+        }
+    }
+
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// The selected folder if the name matches, otherwise the first folder with a matching name
+    pub fn deck_index(&self, global_save: &GlobalSave) -> Option<usize> {
+        let deck_name = self.deck_name.as_ref()?;
+
+        if (global_save.active_deck()).is_some_and(|deck| deck.name == *deck_name) {
+            return Some(global_save.selected_deck);
+        }
+
+        (global_save.decks.iter()).position(|deck| deck.name == *deck_name)
+    }
+
+    pub fn deck<'a>(&self, global_save: &'a GlobalSave) -> Option<&'a Deck> {
+        global_save.decks.get(self.deck_index(global_save)?)
+    }
+
+    /// Equips the loadout, returns false if the folder couldn't be found
+    pub fn apply(&self, global_save: &mut GlobalSave) -> bool {
+        global_save.selected_character = self.character.clone();
+
+        (global_save.installed_blocks).insert(self.character.clone(), self.blocks.clone());
+
+        (global_save.installed_drive_parts)
+            .insert(self.character.clone(), self.drive_parts.clone());
+
+        let Some(deck_index) = self.deck_index(global_save) else {
+            return self.deck_name.is_none();
+        };
+
+        global_save.selected_deck = deck_index;
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_restores_the_captured_build() {
+        let mut save = GlobalSave::new();
+        save.decks = vec![Deck::new(String::from("A")), Deck::new(String::from("B"))];
+        save.selected_deck = 1;
+        save.selected_character = PackageId::from("player");
+
+        let block = InstalledBlock {
+            package_id: PackageId::from("block"),
+            ..Default::default()
+        };
+        save.installed_blocks
+            .insert(save.selected_character.clone(), vec![block]);
+
+        let loadout = Loadout::current(&save).with_name(String::from("PvP"));
+        assert_eq!(loadout.deck_name.as_deref(), Some("B"));
+
+        // switch builds
+        save.selected_deck = 0;
+        save.selected_character = PackageId::from("other");
+
+        assert!(loadout.apply(&mut save));
+        assert_eq!(save.selected_deck, 1);
+        assert_eq!(save.selected_character, PackageId::from("player"));
+        assert_eq!(save.active_blocks(), loadout.blocks.as_slice());
+
+        // folders are matched by name after moving
+        save.decks.swap(0, 1);
+        assert!(loadout.apply(&mut save));
+        assert_eq!(save.selected_deck, 0);
+
+        save.decks.remove(0);
+        assert!(!loadout.apply(&mut save));
+    }
+}
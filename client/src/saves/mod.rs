@@ -1,20 +1,48 @@
+mod achievements;
 mod battle_recording;
+mod battle_test_fixture;
+mod block_arranger;
 mod block_grid;
 mod card;
+mod card_reward;
 mod config;
 mod deck;
+mod deck_share_code;
+mod encounter_record;
+mod fast_travel;
 mod global_save;
+mod input_profile;
+mod loadout;
 mod player_input_buffer;
+mod quest_log;
+mod save_backups;
+mod save_export;
+mod save_sync;
 mod server_info;
+mod touch_layout;
 
+pub use achievements::*;
 pub use battle_recording::*;
+pub use battle_test_fixture::*;
+pub use block_arranger::*;
 pub use block_grid::*;
 pub use card::*;
+pub use card_reward::*;
 pub use config::*;
 pub use deck::*;
+pub use deck_share_code::*;
+pub use encounter_record::*;
+pub use fast_travel::*;
 pub use global_save::*;
+pub use input_profile::*;
+pub use loadout::*;
 pub use player_input_buffer::*;
+pub use quest_log::*;
+pub use save_backups::*;
+pub use save_export::*;
+pub use save_sync::*;
 pub use server_info::*;
+pub use touch_layout::*;
 
 pub use packets::structures::InstalledBlock;
 pub use packets::structures::InstalledSwitchDrive;
@@ -81,8 +81,148 @@ impl PlayerInputBuffer {
             .map(|(item, _)| item)
     }
 
+    /// Iterates over inputs starting at `frame`, only seeks once
+    pub fn iter_from(&self, frame: usize) -> impl Iterator<Item = &NetplayBufferItem> {
+        let mut run_index = 0;
+        let mut offset = frame;
+
+        for (_, count) in &self.buffer {
+            if offset < *count {
+                break;
+            }
+
+            offset -= *count;
+            run_index += 1;
+        }
+
+        (self.buffer.iter().skip(run_index).enumerate()).flat_map(move |(i, (item, count))| {
+            // the first run may start before the frame
+            let repeat = if i == 0 { *count - offset } else { *count };
+
+            std::iter::repeat(item).take(repeat)
+        })
+    }
+
+    /// Removes inputs after `frame`, `frame` is kept
+    pub fn truncate_after(&mut self, frame: usize) {
+        let new_len = frame + 1;
+
+        while self.len > new_len {
+            let Some((_, count)) = self.buffer.back_mut() else {
+                break;
+            };
+
+            let excess = self.len - new_len;
+
+            if *count > excess {
+                *count -= excess;
+                self.len = new_len;
+            } else {
+                self.len -= *count;
+                self.buffer.pop_back();
+            }
+        }
+    }
+
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.len = 0;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packets::structures::Input;
+    use std::time::{Duration, Instant};
+
+    fn item(pressed: Vec<Input>) -> NetplayBufferItem {
+        NetplayBufferItem {
+            pressed,
+            signals: Vec::new(),
+        }
+    }
+
+    fn alternating_buffer(len: usize) -> PlayerInputBuffer {
+        let mut buffer = PlayerInputBuffer::default();
+
+        for i in 0..len {
+            // runs of 3 to keep the run length encoding busy
+            let pressed = if (i / 3) % 2 == 0 {
+                vec![Input::Shoot]
+            } else {
+                Vec::new()
+            };
+
+            buffer.push_last(item(pressed));
+        }
+
+        buffer
+    }
+
+    #[test]
+    fn iter_from_matches_get() {
+        let buffer = alternating_buffer(20);
+
+        for frame in 0..22 {
+            let expected: Vec<_> = (frame..buffer.len()).flat_map(|i| buffer.get(i)).collect();
+            let found: Vec<_> = buffer.iter_from(frame).collect();
+
+            assert_eq!(found, expected, "frame {frame}");
+        }
+    }
+
+    #[test]
+    fn truncate_after_matches_delete_last() {
+        for frame in 0..20 {
+            let mut buffer = alternating_buffer(20);
+            let mut expected = buffer.clone();
+
+            for _ in frame + 1..20 {
+                expected.delete_last();
+            }
+
+            buffer.truncate_after(frame);
+
+            assert_eq!(buffer.len(), frame + 1);
+            assert_eq!(
+                buffer.iter_from(0).collect::<Vec<_>>(),
+                expected.iter_from(0).collect::<Vec<_>>()
+            );
+        }
+
+        let mut buffer = alternating_buffer(5);
+        buffer.truncate_after(10);
+        assert_eq!(buffer.len(), 5);
+    }
+
+    #[test]
+    fn iter_from_beats_get_at_high_rollback_depth() {
+        const ROLLBACK_DEPTH: usize = 600;
+        const ROUNDS: usize = 20;
+
+        let buffer = alternating_buffer(ROLLBACK_DEPTH);
+
+        // the fastest round of each is compared to keep scheduler noise out
+        let mut get_duration = Duration::MAX;
+        let mut iter_duration = Duration::MAX;
+
+        for _ in 0..ROUNDS {
+            let start = Instant::now();
+            let from_get: Vec<_> = (0..ROLLBACK_DEPTH).flat_map(|i| buffer.get(i)).collect();
+            get_duration = get_duration.min(start.elapsed());
+
+            let start = Instant::now();
+            let from_iter: Vec<_> = buffer.iter_from(0).collect();
+            iter_duration = iter_duration.min(start.elapsed());
+
+            assert_eq!(from_iter, from_get);
+        }
+
+        // get rescans the runs for every frame, expected to be around 100x slower at this depth
+        assert!(
+            iter_duration * 4 < get_duration,
+            "get: {get_duration:?}, iter_from: {iter_duration:?}"
+        );
+    }
+}
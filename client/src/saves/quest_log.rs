@@ -0,0 +1,112 @@
+use packets::structures::Quest;
+use serde::{Deserialize, Serialize};
+
+/// Quests given by a single server
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuestLog {
+    /// Sorted by when the quest was first received
+    pub quests: Vec<Quest>,
+    /// The id of the quest displayed on the hud
+    pub tracked_quest: Option<String>,
+}
+
+impl QuestLog {
+    pub fn is_empty(&self) -> bool {
+        self.quests.is_empty()
+    }
+
+    pub fn quest(&self, id: &str) -> Option<&Quest> {
+        self.quests.iter().find(|quest| quest.id == id)
+    }
+
+    pub fn tracked(&self) -> Option<&Quest> {
+        self.quest(self.tracked_quest.as_ref()?)
+    }
+
+    /// Replaces the quest with a matching id, or adds the quest to the end of the log
+    pub fn update(&mut self, quest: Quest) {
+        match self.quests.iter_mut().find(|q| q.id == quest.id) {
+            Some(existing) => *existing = quest,
+            None => self.quests.push(quest),
+        }
+    }
+
+    /// Returns false if the quest or objective doesn't exist
+    pub fn set_progress(&mut self, id: &str, objective_index: usize, progress: u32) -> bool {
+        let Some(quest) = self.quests.iter_mut().find(|quest| quest.id == id) else {
+            return false;
+        };
+
+        let Some(objective) = quest.objectives.get_mut(objective_index) else {
+            return false;
+        };
+
+        objective.progress = progress;
+        true
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.quests.retain(|quest| quest.id != id);
+
+        if self.tracked_quest.as_deref() == Some(id) {
+            self.tracked_quest = None;
+        }
+    }
+
+    /// Tracks the quest, or stops tracking it if it's already tracked
+    pub fn toggle_tracked(&mut self, id: &str) {
+        if self.tracked_quest.as_deref() == Some(id) {
+            self.tracked_quest = None;
+        } else {
+            self.tracked_quest = Some(id.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packets::structures::QuestObjective;
+
+    fn quest(id: &str, name: &str) -> Quest {
+        Quest {
+            id: id.to_string(),
+            name: name.to_string(),
+            objectives: vec![QuestObjective {
+                goal: 2,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn quests_are_replaced_by_id() {
+        let mut log = QuestLog::default();
+        log.update(quest("a", "First"));
+        log.update(quest("b", "Second"));
+        log.update(quest("a", "Renamed"));
+
+        assert_eq!(log.quests.len(), 2);
+        assert_eq!(log.quests[0].name, "Renamed");
+
+        assert!(log.set_progress("b", 0, 2));
+        assert!(!log.set_progress("b", 1, 2));
+        assert!(!log.set_progress("c", 0, 2));
+        assert!(log.quest("b").unwrap().is_complete());
+    }
+
+    #[test]
+    fn removing_a_quest_stops_tracking() {
+        let mut log = QuestLog::default();
+        log.update(quest("a", "First"));
+
+        log.toggle_tracked("a");
+        assert_eq!(log.tracked().map(|quest| quest.id.as_str()), Some("a"));
+
+        log.remove("a");
+        assert!(log.tracked_quest.is_none());
+        assert!(log.is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+use super::GlobalSave;
+use crate::resources::{ResourcePaths, SAVE_BACKUP_LIMIT};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BACKUP_EXTENSION: &str = "dat";
+
+/// A copy of the save from before a write, named by the time it was replaced
+pub struct SaveBackup {
+    pub path: String,
+    /// Milliseconds since the unix epoch
+    pub created_at: u64,
+}
+
+impl SaveBackup {
+    fn folder() -> String {
+        ResourcePaths::profile_path(ResourcePaths::SAVE_BACKUP_FOLDER)
+    }
+
+    /// Copies the save on disk into the backup folder and removes the oldest backups past the limit
+    pub(super) fn back_up_current() {
+        let save_path = ResourcePaths::profile_path(GlobalSave::PATH);
+
+        let Ok(bytes) = std::fs::read(&save_path) else {
+            // nothing to back up yet
+            return;
+        };
+
+        if !GlobalSave::is_valid(&bytes) {
+            // keeping a damaged save would push out a working backup
+            return;
+        }
+
+        let folder = Self::folder();
+        let _ = std::fs::create_dir_all(&folder);
+
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        let path = format!("{folder}{created_at}.{BACKUP_EXTENSION}");
+
+        if let Err(e) = std::fs::write(&path, bytes) {
+            log::error!("Failed to back up the save to {path:?}: {e}");
+            return;
+        }
+
+        for backup in Self::list().into_iter().skip(SAVE_BACKUP_LIMIT) {
+            let _ = std::fs::remove_file(backup.path);
+        }
+    }
+
+    /// Newest first
+    pub fn list() -> Vec<Self> {
+        let mut backups: Vec<_> = std::fs::read_dir(Self::folder())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .flat_map(|entry| {
+                let path = entry.path();
+
+                if path.extension()? != BACKUP_EXTENSION {
+                    return None;
+                }
+
+                let created_at = path.file_stem()?.to_str()?.parse().ok()?;
+
+                Some(Self {
+                    path: path.to_string_lossy().to_string(),
+                    created_at,
+                })
+            })
+            .collect();
+
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        backups
+    }
+
+    pub fn load(&self) -> Result<GlobalSave, String> {
+        let bytes = std::fs::read(&self.path).map_err(|e| e.to_string())?;
+        rmp_serde::from_slice(&bytes).map_err(|e| format!("The backup is damaged: {e}"))
+    }
+}
@@ -0,0 +1,150 @@
+use super::GlobalSave;
+use crate::packages::PackageNamespace;
+use crate::resources::{Globals, ResourcePaths};
+use packets::structures::{FileHash, PackageCategory, PackageId};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A save in a single portable file, written to the save export folder
+#[derive(Serialize, Deserialize)]
+pub struct SaveExport {
+    /// Format version, exports from newer versions are rejected
+    pub version: u32,
+    pub game_version: String,
+    /// Seconds since the unix epoch
+    pub exported_at: u64,
+    checksum: FileHash,
+    save_bytes: Vec<u8>,
+}
+
+impl SaveExport {
+    pub const VERSION: u32 = 1;
+    pub const EXTENSION: &'static str = "hubsave";
+
+    pub fn new(save: &GlobalSave) -> Result<Self, String> {
+        let save_bytes = rmp_serde::to_vec_named(save).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            version: Self::VERSION,
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            checksum: FileHash::hash(&save_bytes),
+            save_bytes,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let export: Self =
+            rmp_serde::from_slice(bytes).map_err(|_| String::from("Not a save export"))?;
+
+        if export.version > Self::VERSION {
+            return Err(format!(
+                "Exported from a newer version of the game ({})",
+                export.game_version
+            ));
+        }
+
+        if FileHash::hash(&export.save_bytes) != export.checksum {
+            return Err(String::from("The export is damaged"));
+        }
+
+        Ok(export)
+    }
+
+    pub fn global_save(&self) -> Result<GlobalSave, String> {
+        rmp_serde::from_slice(&self.save_bytes).map_err(|e| format!("Invalid save data: {e}"))
+    }
+
+    fn folder() -> String {
+        ResourcePaths::game_folder().to_string() + ResourcePaths::SAVE_EXPORT_FOLDER
+    }
+
+    /// Returns the path of the new file
+    pub fn write(&self, nickname: &str) -> Result<String, String> {
+        let folder = Self::folder();
+        let _ = std::fs::create_dir_all(&folder);
+
+        let nickname: String = (nickname.chars())
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+
+        let path = format!(
+            "{folder}{nickname}_{}.{}",
+            self.exported_at,
+            Self::EXTENSION
+        );
+
+        let bytes = rmp_serde::to_vec_named(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, bytes).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+
+        Ok(path)
+    }
+
+    /// Paths of exports in the export folder, newest first
+    pub fn list() -> Vec<String> {
+        let mut entries: Vec<_> = std::fs::read_dir(Self::folder())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                let path = entry.path();
+                path.extension().is_some_and(|ext| ext == Self::EXTENSION)
+            })
+            .map(|entry| {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                (modified, entry.path().to_string_lossy().to_string())
+            })
+            .collect();
+
+        entries.sort_by(|a, b| b.0.cmp(&a.0));
+        entries.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// Packages used by the save that aren't installed
+    pub fn missing_packages(
+        globals: &Globals,
+        save: &GlobalSave,
+    ) -> Vec<(PackageCategory, PackageId)> {
+        save.required_packages()
+            .into_iter()
+            .filter(|(category, id)| {
+                globals
+                    .package_info(*category, PackageNamespace::Local, id)
+                    .is_none()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn export_round_trip() {
+        let mut save = GlobalSave::default();
+        save.nickname = String::from("Tester");
+
+        let export = SaveExport::new(&save).unwrap();
+        let bytes = rmp_serde::to_vec_named(&export).unwrap();
+
+        let imported = SaveExport::from_bytes(&bytes).unwrap();
+        assert_eq!(imported.global_save().unwrap().nickname, "Tester");
+    }
+
+    #[test]
+    fn rejects_damaged_exports() {
+        let mut export = SaveExport::new(&GlobalSave::default()).unwrap();
+        export.save_bytes.push(0);
+
+        let bytes = rmp_serde::to_vec_named(&export).unwrap();
+        assert!(SaveExport::from_bytes(&bytes).is_err());
+
+        export.version = SaveExport::VERSION + 1;
+        let bytes = rmp_serde::to_vec_named(&export).unwrap();
+        assert!(SaveExport::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,523 @@
+use super::GlobalSave;
+use crate::resources::ResourcePaths;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::SystemTime;
+use strum::{EnumString, IntoStaticStr};
+
+const MANIFEST_NAME: &str = "manifest.toml";
+const DEFAULT_S3_REGION: &str = "us-east-1";
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(ascii_case_insensitive)]
+pub enum SaveSyncProvider {
+    #[default]
+    None,
+    WebDav,
+    S3,
+}
+
+/// Where saves are synced, for S3 the username and password are the access key and secret key
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SaveSyncConfig {
+    pub provider: SaveSyncProvider,
+    /// A WebDAV folder, or an S3 bucket using path style urls: `https://endpoint/bucket/prefix`
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    pub region: String,
+}
+
+impl SaveSyncConfig {
+    pub(super) const SECTION_NAME: &'static str = "SaveSync";
+
+    pub fn enabled(&self) -> bool {
+        self.provider != SaveSyncProvider::None && !self.url.is_empty()
+    }
+
+    pub(super) fn parse(properties: &ini::Properties) -> Self {
+        let get = |key: &str| properties.get(key).unwrap_or_default().trim().to_string();
+
+        Self {
+            provider: SaveSyncProvider::from_str(&get("Provider")).unwrap_or_default(),
+            url: get("Url").trim_end_matches('/').to_string(),
+            username: get("Username"),
+            password: get("Password"),
+            region: get("Region"),
+        }
+    }
+
+    pub(super) fn write(&self, s: &mut String) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        let provider: &'static str = self.provider.into();
+
+        writeln!(s, "[{}]", Self::SECTION_NAME)?;
+        writeln!(s, "Provider = {provider}")?;
+        writeln!(s, "Url = {}", self.url)?;
+        writeln!(s, "Username = {}", self.username)?;
+        writeln!(s, "Password = {}", self.password)?;
+        writeln!(s, "Region = {}", self.region)?;
+
+        Ok(())
+    }
+}
+
+/// Lists the files in the last push
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SaveSyncManifest {
+    modified: u64,
+    files: Vec<String>,
+}
+
+/// Modification times from the last sync, used to tell which side changed since
+#[derive(Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveSyncRecord {
+    local_modified: u64,
+    remote_modified: u64,
+}
+
+impl SaveSyncRecord {
+    const PATH: &'static str = "save_sync.toml";
+
+    fn load() -> Option<Self> {
+        let text = std::fs::read_to_string(ResourcePaths::profile_path(Self::PATH)).ok()?;
+        toml::from_str(&text).ok()
+    }
+
+    fn save(&self) {
+        let Ok(text) = toml::to_string(self) else {
+            return;
+        };
+
+        let path = ResourcePaths::profile_path(Self::PATH);
+
+        if let Err(e) = std::fs::write(&path, text) {
+            log::error!("Failed to save {path:?}: {e}");
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSyncAction {
+    UpToDate,
+    Push,
+    Pull,
+    /// Both sides changed since the last sync, times are unix seconds
+    Conflict {
+        local_modified: u64,
+        remote_modified: u64,
+    },
+}
+
+impl SaveSyncAction {
+    /// A local_modified of zero means there are no local files
+    fn decide(
+        local_modified: u64,
+        remote_modified: Option<u64>,
+        record: Option<SaveSyncRecord>,
+    ) -> Self {
+        let Some(remote_modified) = remote_modified else {
+            return if local_modified > 0 {
+                Self::Push
+            } else {
+                Self::UpToDate
+            };
+        };
+
+        if local_modified == 0 {
+            return Self::Pull;
+        }
+
+        let (local_changed, remote_changed) = match record {
+            Some(record) => (
+                local_modified > record.local_modified,
+                remote_modified != record.remote_modified,
+            ),
+            // never synced from this device, we can't tell which save is newer
+            None => (true, true),
+        };
+
+        match (local_changed, remote_changed) {
+            (false, false) => Self::UpToDate,
+            (true, false) => Self::Push,
+            (false, true) => Self::Pull,
+            (true, true) => Self::Conflict {
+                local_modified,
+                remote_modified,
+            },
+        }
+    }
+}
+
+/// Pushes and pulls the save file and journal to a remote folder
+pub struct SaveSync {
+    config: SaveSyncConfig,
+}
+
+impl SaveSync {
+    pub fn new(config: SaveSyncConfig) -> Self {
+        Self { config }
+    }
+
+    /// Paths relative to the profile folder, matching the remote layout
+    fn local_files() -> Vec<String> {
+        let mut files = Vec::new();
+
+        if std::path::Path::new(&ResourcePaths::profile_path(GlobalSave::PATH)).is_file() {
+            files.push(GlobalSave::PATH.to_string());
+        }
+
+        let journal_folder = ResourcePaths::profile_path(ResourcePaths::JOURNAL_FOLDER);
+
+        if let Ok(entries) = std::fs::read_dir(journal_folder) {
+            for entry in entries.flatten() {
+                if entry.path().is_file() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    files.push(format!("{}{name}", ResourcePaths::JOURNAL_FOLDER));
+                }
+            }
+        }
+
+        files
+    }
+
+    fn local_modified(files: &[String]) -> u64 {
+        files
+            .iter()
+            .flat_map(|path| {
+                let metadata = std::fs::metadata(ResourcePaths::profile_path(path)).ok()?;
+                metadata.modified().ok()
+            })
+            .map(unix_secs)
+            .max()
+            .unwrap_or_default()
+    }
+
+    async fn remote_manifest(&self) -> Result<Option<SaveSyncManifest>, String> {
+        let Some(bytes) = self.get(MANIFEST_NAME).await? else {
+            return Ok(None);
+        };
+
+        let text = String::from_utf8_lossy(&bytes);
+        let manifest = toml::from_str(&text).map_err(|e| format!("Invalid manifest: {e}"))?;
+
+        Ok(Some(manifest))
+    }
+
+    pub async fn check(&self) -> Result<SaveSyncAction, String> {
+        let local_modified = Self::local_modified(&Self::local_files());
+        let remote_modified = self.remote_manifest().await?.map(|m| m.modified);
+
+        Ok(SaveSyncAction::decide(
+            local_modified,
+            remote_modified,
+            SaveSyncRecord::load(),
+        ))
+    }
+
+    pub async fn push(&self) -> Result<(), String> {
+        let files = Self::local_files();
+
+        if self.config.provider == SaveSyncProvider::WebDav {
+            // webdav requires folders to exist before files can be placed in them
+            self.create_folder(ResourcePaths::JOURNAL_FOLDER).await?;
+        }
+
+        for path in &files {
+            let local_path = ResourcePaths::profile_path(path);
+            let bytes = std::fs::read(&local_path)
+                .map_err(|e| format!("Failed to read {local_path:?}: {e}"))?;
+            self.put(path, bytes).await?;
+        }
+
+        let manifest = SaveSyncManifest {
+            modified: unix_secs(SystemTime::now()),
+            files: files.clone(),
+        };
+
+        let manifest_text = toml::to_string(&manifest).map_err(|e| e.to_string())?;
+        self.put(MANIFEST_NAME, manifest_text.into_bytes()).await?;
+
+        SaveSyncRecord {
+            local_modified: Self::local_modified(&files),
+            remote_modified: manifest.modified,
+        }
+        .save();
+
+        log::info!("Pushed {} save files", files.len());
+
+        Ok(())
+    }
+
+    /// Local files missing from the remote are kept
+    pub async fn pull(&self) -> Result<(), String> {
+        let Some(manifest) = self.remote_manifest().await? else {
+            return Err(String::from("No save found"));
+        };
+
+        let mut downloads = Vec::with_capacity(manifest.files.len());
+
+        for path in &manifest.files {
+            // avoid writing outside of the game folder
+            let is_save = path == GlobalSave::PATH;
+            let is_journal = path
+                .strip_prefix(ResourcePaths::JOURNAL_FOLDER)
+                .is_some_and(|name| !name.starts_with('.') && !name.contains(['/', '\\']));
+
+            if !is_save && !is_journal {
+                log::warn!("Skipping unexpected save file {path:?}");
+                continue;
+            }
+
+            let Some(bytes) = self.get(path).await? else {
+                return Err(format!("{path:?} is missing"));
+            };
+
+            if is_save && !GlobalSave::is_valid(&bytes) {
+                return Err(String::from("The remote save is damaged"));
+            }
+
+            downloads.push((path, bytes));
+        }
+
+        // write after every download succeeds to avoid mixing saves
+        let _ = std::fs::create_dir_all(ResourcePaths::profile_path(ResourcePaths::JOURNAL_FOLDER));
+
+        for (path, bytes) in downloads {
+            let local_path = ResourcePaths::profile_path(path);
+            std::fs::write(&local_path, bytes)
+                .map_err(|e| format!("Failed to write {local_path:?}: {e}"))?;
+        }
+
+        SaveSyncRecord {
+            local_modified: Self::local_modified(&Self::local_files()),
+            remote_modified: manifest.modified,
+        }
+        .save();
+
+        log::info!("Pulled {} save files", manifest.files.len());
+
+        Ok(())
+    }
+
+    fn request(
+        &self,
+        method: surf::http::Method,
+        path: &str,
+        body: Vec<u8>,
+    ) -> Result<surf::RequestBuilder, String> {
+        let uri = format!("{}/{path}", self.config.url);
+        let url =
+            surf::Url::parse(&uri).map_err(|e| format!("Invalid save sync url {uri:?}: {e}"))?;
+
+        let config = &self.config;
+        let mut request = surf::RequestBuilder::new(method, url.clone());
+
+        match config.provider {
+            SaveSyncProvider::None => {}
+            SaveSyncProvider::WebDav => {
+                if !config.username.is_empty() {
+                    let credentials = format!("{}:{}", config.username, config.password);
+                    let value = format!("Basic {}", base64_encode(credentials.as_bytes()));
+                    request = request.header("Authorization", value);
+                }
+            }
+            SaveSyncProvider::S3 => {
+                let headers = s3_signature_headers(config, method, &url, &body, chrono::Utc::now());
+
+                for (name, value) in headers {
+                    request = request.header(name, value);
+                }
+            }
+        }
+
+        if !body.is_empty() {
+            request = request.body_bytes(body);
+        }
+
+        Ok(request)
+    }
+
+    async fn get(&self, path: &str) -> Result<Option<Vec<u8>>, String> {
+        let request = self.request(surf::http::Method::Get, path, Vec::new())?;
+        let mut response = request.await.map_err(|e| e.to_string())?;
+
+        if response.status() == surf::StatusCode::NotFound {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Failed to download {path:?}: {}",
+                response.status()
+            ));
+        }
+
+        let bytes = response.body_bytes().await.map_err(|e| e.to_string())?;
+
+        Ok(Some(bytes))
+    }
+
+    async fn put(&self, path: &str, bytes: Vec<u8>) -> Result<(), String> {
+        let request = self.request(surf::http::Method::Put, path, bytes)?;
+        let response = request.await.map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to upload {path:?}: {}", response.status()));
+        }
+
+        Ok(())
+    }
+
+    async fn create_folder(&self, path: &str) -> Result<(), String> {
+        let request = self.request(surf::http::Method::MkCol, path, Vec::new())?;
+        let response = request.await.map_err(|e| e.to_string())?;
+
+        // 405 means the folder already exists
+        if !response.status().is_success()
+            && response.status() != surf::StatusCode::MethodNotAllowed
+        {
+            return Err(format!("Failed to create {path:?}: {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
+
+/// AWS signature version 4 headers, signing the host, date, and payload. The host header is left to the client
+fn s3_signature_headers(
+    config: &SaveSyncConfig,
+    method: surf::http::Method,
+    url: &surf::Url,
+    body: &[u8],
+    now: chrono::DateTime<chrono::Utc>,
+) -> [(&'static str, String); 3] {
+    use hmac_sha256::{Hash, HMAC};
+
+    let region = if config.region.is_empty() {
+        DEFAULT_S3_REGION
+    } else {
+        &config.region
+    };
+
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let scope = format!("{date}/{region}/s3/aws4_request");
+    let payload_hash = hex_encode(&Hash::hash(body));
+
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "{method}\n{}\n{}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}",
+        url.path(),
+        url.query().unwrap_or_default(),
+    );
+
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        hex_encode(&Hash::hash(canonical_request.as_bytes()))
+    );
+
+    let secret = format!("AWS4{}", config.password);
+    let key = HMAC::mac(date.as_bytes(), secret.as_bytes());
+    let key = HMAC::mac(region.as_bytes(), key);
+    let key = HMAC::mac(b"s3", key);
+    let key = HMAC::mac(b"aws4_request", key);
+    let signature = hex_encode(&HMAC::mac(string_to_sign.as_bytes(), key));
+
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        config.username
+    );
+
+    [
+        ("x-amz-content-sha256", payload_hash),
+        ("x-amz-date", amz_date),
+        ("Authorization", authorization),
+    ]
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(n >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sync_actions() {
+        let record = SaveSyncRecord {
+            local_modified: 100,
+            remote_modified: 200,
+        };
+
+        let decide = SaveSyncAction::decide;
+
+        assert_eq!(decide(100, None, None), SaveSyncAction::Push);
+        assert_eq!(decide(0, None, None), SaveSyncAction::UpToDate);
+        assert_eq!(decide(0, Some(200), None), SaveSyncAction::Pull);
+        assert_eq!(
+            decide(100, Some(200), Some(record)),
+            SaveSyncAction::UpToDate
+        );
+        assert_eq!(decide(150, Some(200), Some(record)), SaveSyncAction::Push);
+        assert_eq!(decide(100, Some(300), Some(record)), SaveSyncAction::Pull);
+        assert_eq!(
+            decide(150, Some(300), Some(record)),
+            SaveSyncAction::Conflict {
+                local_modified: 150,
+                remote_modified: 300
+            }
+        );
+        assert!(matches!(
+            decide(100, Some(200), None),
+            SaveSyncAction::Conflict { .. }
+        ));
+    }
+
+    #[test]
+    fn basic_auth_encoding() {
+        assert_eq!(base64_encode(b"user:pass"), "dXNlcjpwYXNz");
+        assert_eq!(base64_encode(b"ab"), "YWI=");
+        assert_eq!(base64_encode(b"a"), "YQ==");
+    }
+}
@@ -1,3 +1,6 @@
+use super::Config;
+use crate::resources::MAX_VOLUME;
+use packets::structures::{ActorId, ChatChannel};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -5,3 +8,83 @@ pub struct ServerInfo {
     pub name: String,
     pub address: String,
 }
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerPreferences {
+    pub music: Option<u8>, // overrides the music volume from the config
+    pub name_tags: bool,
+    pub chat_filter: ChatFilterLevel,
+}
+
+/// Hides chat from other players, messages we send are always shown
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChatFilterLevel {
+    #[default]
+    All,
+    /// Hides area chat
+    PartyAndWhispers,
+    /// Only messages from the server
+    SystemOnly,
+}
+
+impl ChatFilterLevel {
+    pub fn allows(self, channel: ChatChannel) -> bool {
+        match self {
+            ChatFilterLevel::All => true,
+            ChatFilterLevel::PartyAndWhispers => channel != ChatChannel::Area,
+            ChatFilterLevel::SystemOnly => channel == ChatChannel::System,
+        }
+    }
+}
+
+impl ServerPreferences {
+    pub fn music_volume(&self, config: &Config) -> f32 {
+        if config.mute_music {
+            return 0.0;
+        }
+
+        self.music.unwrap_or(config.music) as f32 / MAX_VOLUME as f32
+    }
+}
+
+impl Default for ServerPreferences {
+    fn default() -> Self {
+        Self {
+            music: None,
+            name_tags: true,
+            chat_filter: ChatFilterLevel::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chat_filter_levels() {
+        let channels = [
+            ChatChannel::Area,
+            ChatChannel::Whisper(ActorId::new(0, 0)),
+            ChatChannel::Party,
+            ChatChannel::System,
+        ];
+
+        let allowed = |level: ChatFilterLevel| -> Vec<_> {
+            (channels.iter())
+                .map(|channel| level.allows(*channel))
+                .collect()
+        };
+
+        assert_eq!(allowed(ChatFilterLevel::All), [true, true, true, true]);
+        assert_eq!(
+            allowed(ChatFilterLevel::PartyAndWhispers),
+            [false, true, true, true]
+        );
+        assert_eq!(
+            allowed(ChatFilterLevel::SystemOnly),
+            [false, false, false, true]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::resources::Input;
+use framework::input::Button;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// On screen buttons in the order they're laid out, each names a state in the input overlay animation
+pub const TOUCH_BUTTONS: [Button; 8] = [
+    Button::LeftTrigger,
+    Button::Select,
+    Button::RightTrigger,
+    Button::Start,
+    Button::A,
+    Button::B,
+    Button::X,
+    Button::Y,
+];
+
+pub const TOUCH_PAGE_COUNT: usize = 2;
+
+/// What an on screen button presses
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TouchBinding {
+    None,
+    /// Pressed as a gamepad button, resolved through the controller bindings
+    Button(Button),
+    Input(Input),
+}
+
+impl TouchBinding {
+    /// Every binding in the order cycled through in the config
+    pub fn options() -> Vec<Self> {
+        let mut options = vec![Self::None];
+        options.extend(TOUCH_BUTTONS.map(Self::Button));
+        options.extend(Input::iter().map(Self::Input));
+        options
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::None => String::from("None"),
+            Self::Button(button) => {
+                let name: &'static str = button.into();
+                format!("Pad {name}")
+            }
+            Self::Input(input) => format!("{input:?}"),
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        if let Some(name) = value.strip_prefix("Button.") {
+            return Button::from_str(name)
+                .map(Self::Button)
+                .unwrap_or(Self::None);
+        }
+
+        Input::iter()
+            .find(|input| format!("{input:?}") == value)
+            .map(Self::Input)
+            .unwrap_or(Self::None)
+    }
+
+    fn write(&self) -> String {
+        match self {
+            Self::None => String::from("None"),
+            Self::Button(button) => {
+                let name: &'static str = button.into();
+                format!("Button.{name}")
+            }
+            Self::Input(input) => format!("{input:?}"),
+        }
+    }
+}
+
+/// Bindings for the buttons in mobile_overlay.rs, tapping the corner of the screen switches pages
+#[derive(Clone, PartialEq, Eq)]
+pub struct TouchLayout {
+    pub pages: [[TouchBinding; TOUCH_BUTTONS.len()]; TOUCH_PAGE_COUNT],
+}
+
+impl Default for TouchLayout {
+    fn default() -> Self {
+        Self {
+            pages: [
+                TOUCH_BUTTONS.map(TouchBinding::Button),
+                [
+                    TouchBinding::Input(Input::FaceLeft),
+                    TouchBinding::Input(Input::Flee),
+                    TouchBinding::Input(Input::FaceRight),
+                    TouchBinding::Input(Input::EndTurn),
+                    TouchBinding::Input(Input::Special),
+                    TouchBinding::Input(Input::Sprint),
+                    TouchBinding::Input(Input::Info),
+                    TouchBinding::Input(Input::Map),
+                ],
+            ],
+        }
+    }
+}
+
+impl TouchLayout {
+    pub(super) const SECTION_NAME: &'static str = "Touch";
+
+    pub fn slot_name(page: usize, slot: usize) -> String {
+        let button: &'static str = TOUCH_BUTTONS[slot].into();
+        format!("Page{}.{button}", page + 1)
+    }
+
+    pub(super) fn parse(properties: &ini::Properties) -> Self {
+        let mut layout = Self::default();
+
+        for (page, bindings) in layout.pages.iter_mut().enumerate() {
+            for (slot, binding) in bindings.iter_mut().enumerate() {
+                if let Some(value) = properties.get(&Self::slot_name(page, slot)) {
+                    *binding = TouchBinding::parse(value);
+                }
+            }
+        }
+
+        layout
+    }
+
+    pub(super) fn write(&self, s: &mut String) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        writeln!(s, "[{}]", Self::SECTION_NAME)?;
+
+        for (page, bindings) in self.pages.iter().enumerate() {
+            for (slot, binding) in bindings.iter().enumerate() {
+                writeln!(s, "{} = {}", Self::slot_name(page, slot), binding.write())?;
+            }
+        }
+
+        Ok(())
+    }
+}
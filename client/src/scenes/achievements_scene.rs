@@ -0,0 +1,248 @@
+use crate::bindable::SpriteColorMode;
+use crate::format_util::format_timestamp;
+use crate::packages::PackageNamespace;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::AchievementSource;
+use framework::prelude::*;
+use packets::structures::AchievementDefinition;
+
+const LIST_WIDTH: f32 = 112.0;
+const ICON_SIZE: f32 = 32.0;
+
+struct AchievementEntry {
+    definition: AchievementDefinition,
+    /// The library or server name
+    source_name: String,
+    unlocked_at: Option<u64>,
+}
+
+/// Lists achievements from installed libraries and visited servers
+pub struct AchievementsScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    entries: Vec<AchievementEntry>,
+    unlocked_count: usize,
+    list: ListPanel,
+    /// The selected achievement's icon, loaded when the selection changes
+    icon: Option<(usize, Sprite)>,
+    ui_input_tracker: UiInputTracker,
+    next_scene: NextScene,
+}
+
+impl AchievementsScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let mut list = ListPanel::new(game_io, "UNLOCKS", LIST_WIDTH);
+
+        let entries = Self::collect_entries(game_io);
+        let unlocked_count = (entries.iter())
+            .filter(|entry| entry.unlocked_at.is_some())
+            .count();
+
+        list.set_total_items(entries.len());
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            entries,
+            unlocked_count,
+            list,
+            icon: None,
+            ui_input_tracker: UiInputTracker::new(),
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn collect_entries(game_io: &GameIO) -> Vec<AchievementEntry> {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let global_save = &globals.global_save;
+        let achievements = &global_save.achievements;
+        let mut entries = Vec::new();
+
+        // libraries
+        let mut libraries: Vec<_> = (globals.library_packages)
+            .packages(PackageNamespace::Local)
+            .filter(|package| !package.achievements.is_empty())
+            .collect();
+
+        libraries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for package in libraries {
+            let source = AchievementSource::Package(package.package_info.id.clone());
+
+            for definition in &package.achievements {
+                entries.push(AchievementEntry {
+                    definition: definition.clone(),
+                    source_name: package.name.clone(),
+                    unlocked_at: achievements.unlocked_at(&source, &definition.id),
+                });
+            }
+        }
+
+        // servers, named by the server list when possible
+        for (address, definition) in &achievements.server_definitions {
+            let source_name = (global_save.server_list.iter())
+                .find(|info| packets::address_parsing::strip_data(&info.address) == address)
+                .map(|info| info.name.clone())
+                .unwrap_or_else(|| address.clone());
+
+            let source = AchievementSource::Server(address.clone());
+
+            entries.push(AchievementEntry {
+                definition: definition.clone(),
+                source_name,
+                unlocked_at: achievements.unlocked_at(&source, &definition.id),
+            });
+        }
+
+        entries
+    }
+
+    fn update_icon(&mut self, game_io: &GameIO) {
+        let index = self.list.selected_index();
+
+        if self.icon.as_ref().is_some_and(|(i, _)| *i == index) {
+            return;
+        }
+
+        let Some(entry) = self.entries.get(index) else {
+            self.icon = None;
+            return;
+        };
+
+        let path = &entry.definition.icon_texture_path;
+
+        if path.is_empty() {
+            self.icon = None;
+            return;
+        }
+
+        let assets = &game_io.resource::<Globals>().unwrap().assets;
+        let mut sprite = assets.new_sprite(game_io, path);
+
+        // fit the icon into a square
+        let size = sprite.size();
+        let scale = (ICON_SIZE / size.x.max(size.y).max(1.0)).min(1.0);
+        sprite.set_scale(Vec2::splat(scale));
+        sprite.set_position(self.list.details_bounds().top_left());
+
+        // locked icons are darkened
+        if entry.unlocked_at.is_none() {
+            sprite.set_color(Color::WHITE.multiply_color(0.5));
+        }
+
+        self.icon = Some((index, sprite));
+    }
+
+    fn describe(entry: &AchievementEntry) -> String {
+        let status = match entry.unlocked_at {
+            Some(unlocked_at) => format!("Unlocked {}", format_timestamp(unlocked_at)),
+            None => String::from("Locked"),
+        };
+
+        [
+            entry.definition.name.as_str(),
+            entry.definition.description.as_str(),
+            "",
+            entry.source_name.as_str(),
+            status.as_str(),
+        ]
+        .join("\n")
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+    }
+}
+
+impl Scene for AchievementsScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+        self.update_icon(game_io);
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list, locked achievements are dimmed
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let entry = &self.entries[index];
+
+                text_style.color = if entry.unlocked_at.is_some() {
+                    Color::WHITE
+                } else {
+                    Color::WHITE.multiply_color(0.75)
+                };
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &entry.definition.name);
+            },
+        );
+
+        text_style.color = Color::WHITE;
+
+        // draw icon and details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        if let Some((_, sprite)) = &self.icon {
+            sprite_queue.draw_sprite(sprite);
+            text_style.bounds.y += ICON_SIZE + 4.0;
+        }
+
+        let details = match self.entries.get(self.list.selected_index()) {
+            Some(entry) => Self::describe(entry),
+            None => String::from("Achievements from libraries and servers appear here."),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw unlock progress
+        let progress_text = format!("{}/{}", self.unlocked_count, self.entries.len());
+        let progress_width = text_style.measure(&progress_text).size.x;
+        text_style.bounds.x = details_bounds.right() - progress_width;
+        text_style.bounds.y = RESOLUTION_F.y - self.list.line_height() - 8.0;
+        text_style.draw(game_io, &mut sprite_queue, &progress_text);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("ACHIEVEMENTS").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -2,14 +2,26 @@
 
 use framework::prelude::*;
 
-use crate::{battle::BattleProps, resources::Globals, transitions::BATTLE_HOLD_DURATION};
+use crate::battle::BattleProps;
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::EncounterPreviewCard;
+use crate::render::{Camera, SpriteColorQueue};
+use crate::resources::{Globals, Input, InputUtil};
+use crate::transitions::BATTLE_HOLD_DURATION;
 
 use super::BattleScene;
 
+/// How long previews that can't be declined are displayed
+const PREVIEW_DURATION: Duration = Duration::from_millis(1500);
+
 pub struct BattleInitScene {
     start_instant: Instant,
     camera: OrthoCamera,
+    ui_camera: Camera,
     model: FlatModel,
+    fade_color: Color,
+    preview: Option<EncounterPreviewCard>,
+    decline_callback: Option<Box<dyn FnOnce()>>,
     battle_scene: Option<BattleScene>,
     next_scene: NextScene,
 }
@@ -25,11 +37,65 @@ impl BattleInitScene {
         Self {
             start_instant: Instant::now(),
             camera,
+            ui_camera: Camera::new_ui(game_io),
             model,
+            fade_color: Color::WHITE,
+            preview: None,
+            decline_callback: None,
             battle_scene: Some(BattleScene::new(game_io, props)),
             next_scene: NextScene::None,
         }
     }
+
+    pub fn with_fade_color(mut self, color: Color) -> Self {
+        self.fade_color = color;
+        self.model.set_color(color);
+        self
+    }
+
+    /// The decline callback is only called if the preview is declinable and the player backs out
+    pub fn with_preview(
+        mut self,
+        preview: EncounterPreviewCard,
+        decline_callback: impl FnOnce() + 'static,
+    ) -> Self {
+        self.preview = Some(preview);
+        self.decline_callback = Some(Box::new(decline_callback));
+        self
+    }
+
+    fn preview_accepted(&mut self, game_io: &GameIO) -> bool {
+        let Some(preview) = &self.preview else {
+            return true;
+        };
+
+        if !preview.declinable() {
+            return self.start_instant.elapsed() >= BATTLE_HOLD_DURATION + PREVIEW_DURATION;
+        }
+
+        let input_util = InputUtil::new(game_io);
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if input_util.was_just_pressed(Input::Confirm) {
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+            return true;
+        }
+
+        if input_util.was_just_pressed(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            if let Some(callback) = self.decline_callback.take() {
+                callback();
+            }
+
+            self.battle_scene = None;
+
+            let transition = crate::transitions::new_colored_battle_pop(game_io, self.fade_color);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+        }
+
+        false
+    }
 }
 
 impl Scene for BattleInitScene {
@@ -51,8 +117,12 @@ impl Scene for BattleInitScene {
             return;
         }
 
+        if self.battle_scene.is_none() || !self.preview_accepted(game_io) {
+            return;
+        }
+
         if let Some(scene) = self.battle_scene.take() {
-            let transition = crate::transitions::new_battle(game_io);
+            let transition = crate::transitions::new_colored_battle(game_io, self.fade_color);
             self.next_scene = NextScene::new_swap(scene).with_transition(transition);
         }
     }
@@ -63,5 +133,13 @@ impl Scene for BattleInitScene {
 
         flat_queue.draw_model(&self.model);
         render_pass.consume_queue(flat_queue);
+
+        if let Some(preview) = &mut self.preview {
+            let mut sprite_queue =
+                SpriteColorQueue::new(game_io, &self.ui_camera, SpriteColorMode::Multiply);
+
+            preview.draw(game_io, &mut sprite_queue);
+            render_pass.consume_queue(sprite_queue);
+        }
     }
 }
@@ -2,18 +2,26 @@ use crate::battle::*;
 use crate::bindable::SpriteColorMode;
 use crate::lua_api::encounter_init;
 use crate::packages::{Package, PackageNamespace};
-use crate::render::ui::{Textbox, TextboxMessage, TextboxQuestion};
+use crate::render::ui::{
+    FontName, TextStyle, Textbox, TextboxMessage, TextboxQuestion, TEXT_DARK_SHADOW_COLOR,
+};
 use crate::render::*;
 use crate::resources::*;
-use crate::saves::{BattleRecording, PlayerInputBuffer};
+use crate::saves::{
+    AchievementSource, BattleRecording, BattleTestFixture, Config, PlayerInputBuffer,
+};
 use framework::prelude::*;
-use packets::structures::PackageId;
+use packets::structures::{BattleRole, PackageId};
 use packets::{NetplayBufferItem, NetplayPacket, NetplaySignal};
 use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const SLOW_COOLDOWN: FrameTime = INPUT_BUFFER_LIMIT as FrameTime;
 const BUFFER_TOLERANCE: usize = 3;
+const RELAY_INDICATOR_MARGIN: f32 = 2.0;
+const SCRIPT_PROFILE_POSITION: Vec2 = Vec2::new(2.0, 32.0);
+const SCRIPT_PROFILE_ENTRIES: usize = 8;
 
 pub enum BattleEvent {
     Description(Arc<str>),
@@ -28,6 +36,15 @@ pub enum BattleEvent {
 struct PlayerController {
     connected: bool,
     buffer: PlayerInputBuffer,
+    input_receiver: NetplayInputReceiver,
+    /// How many previous inputs this player wants included with our inputs
+    requested_redundancy: u8,
+    /// Frames of local input this player has yet to acknowledge
+    lag: usize,
+    /// Inputs queued for resimulation, avoids seeking the buffer for every frame
+    resimulation_inputs: VecDeque<NetplayBufferItem>,
+    /// Set for additional players on this machine
+    local_input: Option<Box<Config>>,
 }
 
 struct Backup {
@@ -47,8 +64,15 @@ pub struct BattleScene {
     simulation: BattleSimulation,
     state: Box<dyn State>,
     backups: VecDeque<Backup>,
+    /// Discarded backups, reused to avoid reallocating every snapshot
+    snapshot_pool: Vec<BattleSimulation>,
     player_controllers: Vec<PlayerController>,
     local_index: Option<usize>,
+    /// True when hole punching failed and packets are routed through the server
+    relayed: bool,
+    /// Recently sent local inputs, resent with new inputs to cover lost packets
+    sent_inputs: VecDeque<NetplayBufferItem>,
+    max_redundancy: u8,
     slow_cooldown: FrameTime,
     frame_by_frame_debug: bool,
     resimulating: bool,
@@ -61,7 +85,7 @@ pub struct BattleScene {
 
 impl BattleScene {
     pub fn new(game_io: &mut GameIO, mut props: BattleProps) -> Self {
-        let mut is_playing_back_recording = false;
+        let mut is_playing_back_recording = props.playback;
 
         if let Some(recording) = props.try_load_recording(game_io) {
             props = BattleProps::from_recording(game_io, &recording);
@@ -108,6 +132,8 @@ impl BattleScene {
 
         // load battle package
         if let Some(encounter_package) = props.encounter_package(game_io) {
+            simulation.config.medal_criteria = encounter_package.medals.clone();
+
             let vm_manager = &mut resources.vm_manager;
             let vm_index = vm_manager
                 .find_vm_from_info(encounter_package.package_info())
@@ -120,7 +146,7 @@ impl BattleScene {
                 simulation: &mut simulation,
             };
 
-            encounter_init(context, props.data.as_deref());
+            encounter_init(context, props.data.as_ref());
         }
 
         // load the players in the correct order
@@ -135,21 +161,40 @@ impl BattleScene {
                 .map(|setup| setup.index)
         };
 
+        let mut boss_id = None;
+
         for setup in player_setups {
             if let Some(remote_controller) = player_controllers.get_mut(setup.index) {
                 remote_controller.buffer = setup.buffer.clone();
+                remote_controller.input_receiver = setup.input_receiver.clone();
+                remote_controller.local_input = setup.local_input.clone();
                 remote_controller.connected = true;
             }
 
             let result = Player::load(game_io, &resources, &mut simulation, setup);
 
-            if let Err(e) = result {
-                log::error!("{e}");
+            match result {
+                Ok(id) if setup.role == BattleRole::Boss => boss_id = Some(id),
+                Ok(_) => {}
+                Err(e) => log::error!("{e}"),
             }
         }
 
         simulation.initialize_uninitialized();
 
+        // the encounter package turns the boss player into its boss, after it's placed on the field
+        let boss_callback = simulation.config.boss_callback.clone();
+
+        if let (Some(callback), Some(id)) = (boss_callback, boss_id) {
+            callback.call(game_io, &resources, &mut simulation, id);
+        }
+
+        // receivers without a player index are from the server
+        let relayed = props.receivers.iter().any(|(index, _)| index.is_none());
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let max_redundancy = globals.config.netplay_max_redundancy;
+
         Self {
             props,
             recording,
@@ -163,8 +208,12 @@ impl BattleScene {
             simulation,
             state: Box::new(IntroState::new()),
             backups: VecDeque::new(),
+            snapshot_pool: Vec::new(),
             player_controllers,
             local_index,
+            relayed,
+            sent_inputs: VecDeque::new(),
+            max_redundancy,
             slow_cooldown: 0,
             frame_by_frame_debug: false,
             resimulating: false,
@@ -176,10 +225,132 @@ impl BattleScene {
         }
     }
 
+    fn draw_relay_indicator(&self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        // the slowest remote decides how long we wait
+        let remote_lag = (self.player_controllers.iter())
+            .filter(|controller| controller.connected)
+            .map(|controller| controller.lag)
+            .max()
+            .unwrap_or_default();
+
+        let latency_ms = remote_lag * 1000 / 60;
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+        text_style.color = match latency_ms {
+            0..=99 => Color::GREEN,
+            100..=199 => Color::YELLOW,
+            _ => Color::RED,
+        };
+
+        let text = format!("RELAY {latency_ms}ms");
+        let size = text_style.measure(&text).size;
+
+        text_style
+            .bounds
+            .set_position(RESOLUTION_F - size - RELAY_INDICATOR_MARGIN);
+        text_style.draw(game_io, sprite_queue, &text);
+    }
+
+    fn draw_script_profile(&self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        let profiler = self.resources.script_profiler.borrow();
+
+        if !profiler.enabled() {
+            return;
+        }
+
+        let vms = self.resources.vm_manager.vms();
+        let mut text = String::from("LUA AVG/PEAK MS");
+
+        for summary in profiler.summaries().iter().take(SCRIPT_PROFILE_ENTRIES) {
+            text += &format!(
+                "\n{:.2}/{:.2} {} {}",
+                summary.average.as_secs_f64() * 1000.0,
+                summary.peak.as_secs_f64() * 1000.0,
+                vms[summary.vm_index].package_id,
+                summary.label
+            );
+        }
+
+        let mut text_style = TextStyle::new(game_io, FontName::ThinSmall);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+        text_style.bounds.set_position(SCRIPT_PROFILE_POSITION);
+        text_style.draw(game_io, sprite_queue, &text);
+    }
+
+    fn save_test_fixture(&self, recording: &BattleRecording) {
+        let fixture = match BattleTestFixture::from_recording(recording) {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                log::error!("Failed to export inputs: {e}");
+                return;
+            }
+        };
+
+        let elapsed_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let file_name = format!("{}.toml", elapsed_time.as_secs());
+
+        std::thread::spawn(move || {
+            let folder_path =
+                ResourcePaths::game_folder().to_string() + ResourcePaths::BATTLE_TEST_FOLDER;
+
+            // create parent folder
+            let _ = std::fs::create_dir_all(&folder_path);
+
+            let path = folder_path + &file_name;
+
+            if let Err(e) = fixture.save(&path) {
+                log::error!("Failed to save inputs to {:?}: {}", path, e);
+                return;
+            }
+
+            log::info!("Saved inputs to {path}, replay with --replay or run with --test");
+        });
+    }
+
+    fn save_script_profile(&self) {
+        let vms = self.resources.vm_manager.vms();
+        let csv = (self.resources.script_profiler.borrow())
+            .export_csv(|vm_index| vms[vm_index].package_id.to_string());
+
+        let elapsed_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let file_name = format!("{}.csv", elapsed_time.as_secs());
+
+        std::thread::spawn(move || {
+            let folder_path =
+                ResourcePaths::game_folder().to_string() + ResourcePaths::SCRIPT_PROFILE_FOLDER;
+
+            // create parent folder
+            let _ = std::fs::create_dir_all(&folder_path);
+
+            let path = folder_path + &file_name;
+
+            if let Err(e) = std::fs::write(&path, csv) {
+                log::error!("Failed to save script profile to {:?}: {}", path, e);
+                return;
+            }
+
+            log::info!("Saved script profile to {path}");
+        });
+    }
+
     fn is_solo(&self) -> bool {
         self.player_controllers.len() == 1
     }
 
+    /// Every player is on this machine, so the battle can be paused
+    fn is_offline(&self) -> bool {
+        (self.player_controllers.iter().enumerate())
+            .all(|(i, controller)| Some(i) == self.local_index || controller.local_input.is_some())
+    }
+
+    /// Offline battles wait for a gamepad to be picked
+    fn paused_for_controller(&self, game_io: &GameIO) -> bool {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        globals.controller_prompt.is_some() && !self.is_playing_back_recording && self.is_offline()
+    }
+
     fn update_textbox(&mut self, game_io: &mut GameIO) {
         while let Ok(event) = self.resources.event_receiver.try_recv() {
             match event {
@@ -302,6 +473,7 @@ impl BattleScene {
             .iter()
             .enumerate()
             .filter(|(i, controller)| controller.connected && Some(*i) != self.local_index)
+            .filter(|(_, controller)| controller.local_input.is_none())
             .count()
     }
 
@@ -375,15 +547,26 @@ impl BattleScene {
         match packet {
             NetplayPacket::Buffer {
                 index,
+                frame,
                 data,
+                redundant_data,
                 buffer_sizes,
+                redundancy_requests,
             } => {
                 let mut resimulation_time = self.simulation.time;
 
+                let local_buffer_len = (self.local_index)
+                    .and_then(|index| self.player_controllers.get(index))
+                    .map(|controller| controller.buffer.len());
+
                 if let Some(controller) = self.player_controllers.get_mut(index) {
-                    // check disconnect
-                    if data.signals.contains(&NetplaySignal::Disconnect) {
-                        controller.connected = false;
+                    let requested_redundancy = self
+                        .local_index
+                        .and_then(|index| redundancy_requests.get(index))
+                        .cloned();
+
+                    if let Some(requested_redundancy) = requested_redundancy {
+                        controller.requested_redundancy = requested_redundancy;
                     }
 
                     // figure out if we should slow down
@@ -401,21 +584,39 @@ impl BattleScene {
                         if self.slow_cooldown == 0 && has_substantial_diff {
                             self.slow_cooldown = SLOW_COOLDOWN;
                         }
-                    }
 
-                    if let Some(input) = self.simulation.inputs.get(index) {
-                        if !input.matches(&data) {
-                            // resolve the time of the input if it differs from our simulation
-                            resimulation_time =
-                                self.synced_time + controller.buffer.len() as FrameTime;
+                        // inputs we've sent that haven't reached the remote yet
+                        if let Some(local_buffer_len) = local_buffer_len {
+                            controller.lag = local_buffer_len.saturating_sub(remote_received_size);
                         }
                     }
 
-                    if !data.signals.is_empty() {
-                        log::debug!("Received {:?} from {index}", data.signals);
+                    let received = self.synced_time as usize + controller.buffer.len();
+                    let ready =
+                        (controller.input_receiver).receive(received, frame, data, redundant_data);
+
+                    for data in ready {
+                        // check disconnect
+                        if data.signals.contains(&NetplaySignal::Disconnect) {
+                            controller.connected = false;
+                        }
+
+                        if let Some(input) = self.simulation.inputs.get(index) {
+                            if !input.matches(&data) {
+                                // resolve the time of the input if it differs from our simulation
+                                let input_time =
+                                    self.synced_time + controller.buffer.len() as FrameTime;
+                                resimulation_time = resimulation_time.min(input_time);
+                            }
+                        }
+
+                        if !data.signals.is_empty() {
+                            log::debug!("Received {:?} from {index}", data.signals);
+                        }
+
+                        controller.buffer.push_last(data);
                     }
 
-                    controller.buffer.push_last(data);
                     self.resimulate(game_io, resimulation_time);
                 }
             }
@@ -446,6 +647,28 @@ impl BattleScene {
             return;
         };
 
+        let accepting_input =
+            !self.textbox_is_blocking_input && !game_io.input().is_key_down(Key::F3);
+
+        // players sharing this machine skip the network
+        for controller in &mut self.player_controllers {
+            let Some(config) = &controller.local_input else {
+                continue;
+            };
+
+            let input_util = InputUtil::new_with_config(game_io, config);
+            let mut pressed = Vec::new();
+
+            if accepting_input {
+                pressed.extend(Input::BATTLE.into_iter().filter(|i| input_util.is_down(*i)));
+            }
+
+            controller.buffer.push_last(NetplayBufferItem {
+                pressed,
+                signals: Vec::new(),
+            });
+        }
+
         let input_util = InputUtil::new(game_io);
 
         let Some(local_controller) = self.player_controllers.get_mut(local_index) else {
@@ -455,7 +678,7 @@ impl BattleScene {
         // gather input
         let mut pressed = Vec::new();
 
-        if !self.textbox_is_blocking_input && !game_io.input().is_key_down(Key::F3) {
+        if accepting_input {
             for input in Input::BATTLE {
                 if input_util.is_down(input) {
                     pressed.push(input);
@@ -463,6 +686,10 @@ impl BattleScene {
             }
         }
 
+        // share what the simulation sees with the input display
+        let globals = game_io.resource::<Globals>().unwrap();
+        *globals.displayed_input.borrow_mut() = Some(pressed.clone());
+
         // create buffer item
         let data = NetplayBufferItem {
             pressed,
@@ -472,6 +699,25 @@ impl BattleScene {
         // update local buffer
         local_controller.buffer.push_last(data.clone());
 
+        let frame = self.synced_time as usize + local_controller.buffer.len() - 1;
+
+        // resend as many previous inputs as the lossiest remote asks for
+        let redundancy = (self.player_controllers.iter().enumerate())
+            .filter(|(i, controller)| controller.connected && *i != local_index)
+            .map(|(_, controller)| controller.requested_redundancy)
+            .max()
+            .unwrap_or_default()
+            .min(self.max_redundancy) as usize;
+
+        let skipped = self.sent_inputs.len().saturating_sub(redundancy);
+        let redundant_data = self.sent_inputs.iter().skip(skipped).cloned().collect();
+
+        self.sent_inputs.push_back(data.clone());
+
+        while self.sent_inputs.len() > self.max_redundancy as usize {
+            self.sent_inputs.pop_front();
+        }
+
         // gather buffer sizes for remotes to know if they should slow down
         let buffer_sizes = self
             .player_controllers
@@ -479,10 +725,20 @@ impl BattleScene {
             .map(|controller| controller.buffer.len())
             .collect();
 
+        // let remotes know how many of their previous inputs we need
+        let redundancy_requests = self
+            .player_controllers
+            .iter()
+            .map(|controller| controller.input_receiver.requested_redundancy())
+            .collect();
+
         self.broadcast(NetplayPacket::Buffer {
             index: local_index,
+            frame: Some(frame),
             data,
+            redundant_data,
             buffer_sizes,
+            redundancy_requests,
         });
     }
 
@@ -490,7 +746,12 @@ impl BattleScene {
         for (index, player_input) in self.simulation.inputs.iter_mut().enumerate() {
             player_input.flush();
 
-            if let Some(controller) = self.player_controllers.get(index) {
+            if let Some(controller) = self.player_controllers.get_mut(index) {
+                if let Some(data) = controller.resimulation_inputs.pop_front() {
+                    player_input.load_data(data);
+                    continue;
+                }
+
                 let index = (self.simulation.time - self.synced_time) as usize;
 
                 if let Some(data) = controller.buffer.get(index) {
@@ -531,12 +792,25 @@ impl BattleScene {
         self.simulation.is_resimulation = true;
         self.resimulating = true;
 
+        // queue inputs for the whole range up front
+        let start_index = (self.simulation.time - self.synced_time) as usize;
+        let steps = (local_time - self.simulation.time) as usize;
+
+        for controller in &mut self.player_controllers {
+            let inputs = controller.buffer.iter_from(start_index).take(steps);
+            controller.resimulation_inputs = inputs.cloned().collect();
+        }
+
         // resimulate until we're caught up to our previous time
         while self.simulation.time < local_time {
             // avoiding snapshots for the first frame as it's still retained
             self.simulate(game_io);
         }
 
+        for controller in &mut self.player_controllers {
+            controller.resimulation_inputs.clear();
+        }
+
         self.simulation.is_resimulation = false;
         self.resimulating = false;
     }
@@ -564,15 +838,19 @@ impl BattleScene {
         // undo input committed to the recording
         if let Some(recording) = &mut self.recording {
             for setup in &mut recording.player_setups {
-                for _ in 0..steps - 1 {
-                    setup.buffer.delete_last();
+                let new_len = setup.buffer.len().saturating_sub(steps - 1);
+
+                match new_len.checked_sub(1) {
+                    Some(last_frame) => setup.buffer.truncate_after(last_frame),
+                    None => setup.buffer.clear(),
                 }
 
                 debug_assert_eq!(self.synced_time as usize, setup.buffer.len());
             }
 
             if let Some(index) = self.local_index {
-                debug_assert_eq!(self.player_controllers[index].buffer.len(), INPUT_DELAY);
+                let input_delay = self.props.input_delay();
+                debug_assert_eq!(self.player_controllers[index].buffer.len(), input_delay);
             }
         }
     }
@@ -581,20 +859,29 @@ impl BattleScene {
         self.resources.vm_manager.rollback(steps);
 
         for _ in 0..steps - 1 {
-            self.backups.pop_back();
+            if let Some(backup) = self.backups.pop_back() {
+                self.snapshot_pool.push(backup.simulation);
+            }
         }
 
         let backup = self.backups.back_mut().unwrap();
-        self.simulation = backup.simulation.clone(game_io);
+        (backup.simulation).clone_into(game_io, &mut self.simulation);
         self.state = backup.state.clone_box();
         self.already_snapped = true;
     }
 
     fn simulate(&mut self, game_io: &GameIO) {
-        if !self.already_snapped {
+        // lockstep never rolls back, skip the cost of snapshots
+        if !self.already_snapped && !self.props.lockstep {
             self.resources.vm_manager.snap();
 
-            let mut simulation_clone = self.simulation.clone(game_io);
+            let mut simulation_clone = match self.snapshot_pool.pop() {
+                Some(mut pooled_simulation) => {
+                    (self.simulation).clone_into(game_io, &mut pooled_simulation);
+                    pooled_simulation
+                }
+                None => self.simulation.clone(game_io),
+            };
 
             // use the clone as self.simulation
             // gives us a fresh Archetype order for hecs::World
@@ -629,7 +916,10 @@ impl BattleScene {
         simulation.post_update(game_io, resources);
 
         if self.backups.len() > INPUT_BUFFER_LIMIT {
-            self.backups.pop_front();
+            // the pool can't grow past the backup limit, as every new backup takes from it first
+            if let Some(backup) = self.backups.pop_front() {
+                self.snapshot_pool.push(backup.simulation);
+            }
         }
     }
 
@@ -652,18 +942,46 @@ impl BattleScene {
             }
         }
 
+        // save inputs as a battle test fixture
+        if game_io.input().was_key_just_pressed(Key::T) {
+            if let Some(recording) = &self.recording {
+                self.save_test_fixture(recording);
+            } else {
+                log::error!("Recording is disabled");
+            }
+        }
+
+        // save battle log
+        if game_io.input().was_key_just_pressed(Key::L) {
+            self.simulation.battle_log.save(None);
+        }
+
         if game_io.input().was_key_just_pressed(Key::I) {
             self.draw_player_indices = !self.draw_player_indices;
         }
+
+        // save script profile
+        if game_io.input().was_key_just_pressed(Key::P) {
+            if self.resources.script_profiler.borrow().enabled() {
+                self.save_script_profile();
+            } else {
+                log::error!("Script profiling requires the debug overlay");
+            }
+        }
     }
 
-    fn exit(&mut self, game_io: &GameIO, fleeing: bool) {
+    fn exit(&mut self, game_io: &mut GameIO, fleeing: bool) {
         self.exiting = true;
 
         if !self.is_playing_back_recording {
             self.pending_signals.push(NetplaySignal::Disconnect);
         }
 
+        // keep the latest log around for bug reports
+        if !self.simulation.battle_log.is_empty() {
+            self.simulation.battle_log.save(Some("latest.txt"));
+        }
+
         if let Some(statistics_callback) = self.props.statistics_callback.take() {
             self.simulation.wrap_up_statistics();
             let mut statistics = self.simulation.statistics.clone();
@@ -671,9 +989,67 @@ impl BattleScene {
             statistics_callback(Some(statistics));
         }
 
-        // clean up music stack
-        let globals = game_io.resource::<Globals>().unwrap();
+        if !fleeing && !self.is_playing_back_recording {
+            self.record_encounter_win(game_io);
+        }
+
+        if !self.is_playing_back_recording {
+            self.record_achievements(game_io);
+        }
+
+        // clean up music stack and script volumes
+        let globals = game_io.resource_mut::<Globals>().unwrap();
         globals.audio.pop_music_stack();
+        globals.audio.reset_script_bus_volumes();
+        globals.screenshot_metadata.battle = None;
+    }
+
+    fn record_encounter_win(&self, game_io: &mut GameIO) {
+        let Some(medals) = &self.simulation.earned_medals else {
+            return;
+        };
+
+        // only tracking encounters the player has installed
+        let Some((PackageNamespace::Local, package_id)) = &self.props.encounter_package_pair else {
+            return;
+        };
+
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+        let global_save = &mut globals.global_save;
+        let record = global_save.encounter_records.entry(package_id.clone());
+
+        (record.or_default()).record_win(self.simulation.statistics.time, medals);
+        global_save.save();
+    }
+
+    fn record_achievements(&self, game_io: &mut GameIO) {
+        if self.simulation.unlocked_achievements.is_empty() {
+            return;
+        }
+
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+        let library_packages = &globals.library_packages;
+        let achievements = &mut globals.global_save.achievements;
+        let mut unlocked = false;
+
+        for (package_id, id) in &self.simulation.unlocked_achievements {
+            // only achievements listed by installed libraries can be unlocked
+            let defined = library_packages
+                .package(PackageNamespace::Local, package_id)
+                .is_some_and(|package| package.achievements.iter().any(|a| a.id == *id));
+
+            if !defined {
+                log::warn!("No achievement {id:?} defined by a library with the id {package_id}");
+                continue;
+            }
+
+            let source = AchievementSource::Package(package_id.clone());
+            unlocked |= achievements.unlock(source, id.clone());
+        }
+
+        if unlocked {
+            globals.global_save.save();
+        }
     }
 
     fn core_update(&mut self, game_io: &GameIO) {
@@ -706,6 +1082,9 @@ impl BattleScene {
 
                 // simulate as long as we have input
                 self.simulation.time < total_frames
+            } else if self.props.lockstep {
+                // wait for every input, nothing is predicted
+                self.input_synced()
             } else {
                 // simulate as long as we can roll back to the synced time
                 self.simulation.time < self.synced_time + INPUT_BUFFER_LIMIT as FrameTime
@@ -728,7 +1107,7 @@ impl BattleScene {
         }
     }
 
-    fn handle_exit_requests(&mut self, game_io: &GameIO) {
+    fn handle_exit_requests(&mut self, game_io: &mut GameIO) {
         let requested_exit = if self.is_playing_back_recording {
             // pressing confirm or cancel, without pressing pause
             // as pause is used to exit frame_by_frame_debug
@@ -766,26 +1145,53 @@ impl Scene for BattleScene {
     }
 
     fn enter(&mut self, game_io: &mut GameIO) {
-        let globals = game_io.resource::<Globals>().unwrap();
+        let globals = game_io.resource_mut::<Globals>().unwrap();
         globals.audio.push_music_stack();
+
+        globals.screenshot_metadata.battle = (self.props.encounter_package_pair)
+            .as_ref()
+            .map(|(_, package_id)| package_id.to_string());
     }
 
     fn update(&mut self, game_io: &mut GameIO) {
         // Set to transparent at start of update loop
         self.resources.fade_sprite.set_color(Color::TRANSPARENT);
 
+        // profile scripts while the debug overlay is visible
+        let debug_visible = game_io.resource::<Globals>().unwrap().debug_visible;
+        (self.resources.script_profiler.borrow_mut()).set_enabled(debug_visible);
+
         self.update_textbox(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals
+            .frame_profiler
+            .borrow_mut()
+            .begin(ProfileSegment::Netplay);
         self.handle_packets(game_io);
-        self.core_update(game_io);
+        globals.frame_profiler.borrow_mut().end();
+
+        if !self.paused_for_controller(game_io) {
+            self.core_update(game_io);
+        }
+
+        self.resources.script_profiler.borrow_mut().end_frame();
         self.detect_debug_hotkeys(game_io);
         self.handle_exit_requests(game_io);
         self.simulation.camera.update(game_io);
     }
 
     fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        // pack textures seen last frame before anything records draws
+        self.resources.texture_atlases.pack_pending(game_io);
+
         // draw simulation
-        self.simulation
-            .draw(game_io, render_pass, self.draw_player_indices);
+        self.simulation.draw(
+            game_io,
+            &self.resources,
+            render_pass,
+            self.draw_player_indices,
+        );
 
         // draw ui
         let mut sprite_queue =
@@ -803,6 +1209,12 @@ impl Scene for BattleScene {
         fade_sprite.set_color(self.resources.fade_color.take());
         sprite_queue.draw_sprite(fade_sprite);
 
+        if self.relayed {
+            self.draw_relay_indicator(game_io, &mut sprite_queue);
+        }
+
+        self.draw_script_profile(game_io, &mut sprite_queue);
+
         // draw textbox over everything
         self.textbox.draw(game_io, &mut sprite_queue);
 
@@ -1,8 +1,9 @@
-use crate::battle::BattleProps;
+use crate::battle::{BattleProps, EncounterMedal};
 use crate::bindable::SpriteColorMode;
 use crate::packages::*;
 use crate::render::ui::{
-    GridScrollTracker, SceneTitle, SubSceneFrame, Textbox, TextboxMessage, UiInputTracker,
+    FontName, GridScrollTracker, SceneTitle, SubSceneFrame, TextStyle, Textbox, TextboxMessage,
+    UiInputTracker,
 };
 use crate::render::*;
 use crate::resources::*;
@@ -102,6 +103,36 @@ impl BattleSelectScene {
         }
     }
 
+    fn describe_medals(game_io: &GameIO, package: &EncounterPackage) -> String {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let record = globals
+            .global_save
+            .encounter_records
+            .get(&package.package_info.id);
+
+        let mut text = String::new();
+
+        if let Some(best_time) = record.and_then(|record| record.best_time) {
+            text += &format!("\nBest Time: {}", format_time(best_time));
+        }
+
+        for medal in package.medals.available() {
+            let earned = record.is_some_and(|record| record.medals.contains(&medal));
+            let status = if earned { "CLEAR" } else { "---" };
+
+            let label = match (medal, package.medals.par_time) {
+                (EncounterMedal::ParTime, Some(par_time)) => {
+                    format!("{} {}", medal.label(), format_time(par_time))
+                }
+                _ => medal.label().to_string(),
+            };
+
+            text += &format!("\n{label}: {status}");
+        }
+
+        text
+    }
+
     fn handle_music(&self, game_io: &GameIO) {
         let globals = game_io.resource::<Globals>().unwrap();
 
@@ -186,6 +217,27 @@ impl Scene for BattleSelectScene {
             return;
         }
 
+        if input_tracker.is_active(Input::Option) {
+            // local versus
+            let Some(props) = BattleProps::new_local_versus(game_io) else {
+                let globals = game_io.resource::<Globals>().unwrap();
+                globals.audio.play_sound(&globals.sfx.cursor_error);
+
+                let message = String::from("Connect a second gamepad to play local versus.");
+                let interface = TextboxMessage::new(message);
+
+                self.textbox.push_interface(interface);
+                self.textbox.open();
+                return;
+            };
+
+            let scene = BattleInitScene::new(game_io, props);
+
+            let transition = crate::transitions::new_battle(game_io);
+            self.next_scene = NextScene::new_push(scene).with_transition(transition);
+            return;
+        }
+
         if input_tracker.is_active(Input::Option2) && !self.package_ids.is_empty() {
             // description
             let globals = game_io.resource::<Globals>().unwrap();
@@ -197,7 +249,9 @@ impl Scene for BattleSelectScene {
                 .package(PackageNamespace::Local, package_id)
                 .unwrap();
 
-            let interface = TextboxMessage::new(package.description.clone());
+            let description =
+                package.description.clone() + &Self::describe_medals(game_io, package);
+            let interface = TextboxMessage::new(description);
 
             self.textbox.push_interface(interface);
             self.textbox.open();
@@ -224,6 +278,10 @@ impl Scene for BattleSelectScene {
         // draw previews
         let globals = game_io.resource::<Globals>().unwrap();
         let assets = &globals.assets;
+        let encounter_records = &globals.global_save.encounter_records;
+
+        let mut medal_style = TextStyle::new(game_io, FontName::ThinSmall);
+        medal_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
 
         for (i, position) in self.scroll_tracker.iter_visible() {
             // get package
@@ -249,6 +307,27 @@ impl Scene for BattleSelectScene {
             preview_sprite.set_origin(preview_sprite.size() * 0.5);
             preview_sprite.set_position(position);
             sprite_queue.draw_sprite(&preview_sprite);
+
+            // draw medal progress in the bottom right of the preview
+            let available_medals = package.medals.available();
+
+            if !available_medals.is_empty() {
+                let earned_count = encounter_records
+                    .get(package_id)
+                    .map(|record| {
+                        (available_medals.iter())
+                            .filter(|medal| record.medals.contains(medal))
+                            .count()
+                    })
+                    .unwrap_or_default();
+
+                let text = format!("{earned_count}/{}", available_medals.len());
+                let text_size = medal_style.measure(&text).size;
+                let text_position = position + preview_sprite.size() * 0.5 - text_size;
+
+                medal_style.bounds.set_position(text_position);
+                medal_style.draw(game_io, &mut sprite_queue, &text);
+            }
         }
 
         // draw cursor
@@ -269,3 +348,10 @@ impl Scene for BattleSelectScene {
         render_pass.consume_queue(sprite_queue);
     }
 }
+
+fn format_time(time: FrameTime) -> String {
+    let seconds = time / 60;
+    let hundredths = (time % 60) * 100 / 60;
+
+    format!("{}:{:02}.{hundredths:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,130 @@
+use crate::battle::*;
+use crate::lua_api::encounter_init;
+use crate::resources::Globals;
+use crate::saves::BattleTestFixture;
+use framework::prelude::*;
+
+/// Runs battle test fixtures as fast as possible without drawing, then exits the process.
+/// The exit code is the number of failed fixtures
+pub struct BattleTestScene {
+    fixture_paths: Vec<String>,
+    next_scene: NextScene,
+}
+
+impl BattleTestScene {
+    pub fn new(fixture_paths: Vec<String>) -> Box<Self> {
+        Box::new(Self {
+            fixture_paths,
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn run_fixture(game_io: &GameIO, fixture: &BattleTestFixture) -> Result<(), Vec<String>> {
+        let props = fixture.battle_props(game_io).map_err(|e| vec![e])?;
+        let encounter_package = props.encounter_package(game_io).unwrap();
+
+        // same setup as the BattleScene, without rollback or netplay
+        let globals = game_io.resource::<Globals>().unwrap();
+        let mut dependencies = globals.battle_dependencies(game_io, &props);
+        dependencies.sort_by_key(|(_, ns)| *ns);
+
+        let mut simulation = BattleSimulation::new(game_io, &props);
+        simulation.seed_random(props.seed);
+
+        let mut resources = SharedBattleResources::new(game_io, &mut simulation, &dependencies);
+
+        let vm_index = (resources.vm_manager)
+            .find_vm_from_info(encounter_package.package_info())
+            .unwrap();
+
+        let context = BattleScriptContext {
+            vm_index,
+            resources: &mut resources,
+            game_io,
+            simulation: &mut simulation,
+        };
+
+        encounter_init(context, props.data.as_ref());
+
+        let player_setup = &props.player_setups[0];
+
+        if let Err(e) = Player::load(game_io, &resources, &mut simulation, player_setup) {
+            return Err(vec![e.to_string()]);
+        }
+
+        simulation.initialize_uninitialized();
+
+        let mut state: Box<dyn State> = Box::new(IntroState::new());
+
+        while !simulation.exit && (simulation.time as usize) < fixture.max_frames() {
+            let input_index = simulation.time as usize;
+
+            for (index, player_input) in simulation.inputs.iter_mut().enumerate() {
+                player_input.flush();
+
+                if index != player_setup.index {
+                    continue;
+                }
+
+                if let Some(data) = player_setup.buffer.get(input_index) {
+                    player_input.load_data(data.clone());
+                }
+            }
+
+            if let Some(next_state) = state.next_state(game_io) {
+                state = next_state;
+            }
+
+            simulation.pre_update(game_io, &mut resources, &mut *state);
+            state.update(game_io, &mut resources, &mut simulation);
+            simulation.post_update(game_io, &mut resources);
+        }
+
+        let completed = simulation.exit;
+        simulation.wrap_up_statistics();
+
+        let failures = (fixture.expect).failures(completed, &simulation.statistics);
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+impl Scene for BattleTestScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        let mut failed = 0;
+
+        for path in &self.fixture_paths {
+            let result = BattleTestFixture::load(path)
+                .map_err(|e| vec![e])
+                .and_then(|fixture| Self::run_fixture(game_io, &fixture));
+
+            match result {
+                Ok(()) => log::info!("PASS {path}"),
+                Err(failures) => {
+                    failed += 1;
+
+                    for failure in failures {
+                        log::error!("FAIL {path}: {failure}");
+                    }
+                }
+            }
+        }
+
+        log::info!(
+            "{} passed, {failed} failed",
+            self.fixture_paths.len() - failed
+        );
+
+        std::process::exit(failed as i32);
+    }
+
+    fn draw(&mut self, _game_io: &mut GameIO, _render_pass: &mut RenderPass) {}
+}
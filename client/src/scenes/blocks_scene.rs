@@ -8,18 +8,21 @@ use crate::render::ui::{
 };
 use crate::render::{Animator, AnimatorLoopMode, Background, Camera, FrameTime, SpriteColorQueue};
 use crate::resources::*;
-use crate::saves::{BlockGrid, InstalledBlock};
+use crate::saves::{BlockArranger, BlockGrid, InstalledBlock};
 use framework::prelude::*;
 use itertools::Itertools;
 use packets::structures::PackageCategory;
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 const DIM_COLOR: Color = Color::new(0.75, 0.75, 0.75, 1.0);
+/// Frames between each block placed by auto-arrange
+const ARRANGE_INTERVAL: FrameTime = 8;
 
 enum Event {
     Leave,
     Applied,
+    AutoArrange,
 }
 
 #[derive(Clone, Copy)]
@@ -40,6 +43,7 @@ enum State {
     ListSelection,
     GridSelection { x: usize, y: usize },
     BlockContext { x: usize, y: usize },
+    Arranging,
     Applying,
 }
 
@@ -63,6 +67,8 @@ pub struct BlocksScene {
     block_preview: Option<BlockPreview>,
     block_context_menu: ContextMenu<BlockOption>,
     held_block: Option<InstalledBlock>,
+    /// Blocks waiting to be placed by auto-arrange
+    arranged_blocks: VecDeque<InstalledBlock>,
     cursor: GridCursor,
     block_returns_to_grid: bool,
     state: State,
@@ -200,6 +206,7 @@ impl BlocksScene {
                 &[("Move", BlockOption::Move), ("Remove", BlockOption::Remove)],
             ),
             held_block: None,
+            arranged_blocks: VecDeque::new(),
             cursor,
             state: State::ListSelection,
             block_returns_to_grid: false,
@@ -346,6 +353,10 @@ impl BlocksScene {
                     self.information_text.style.color = Color::WHITE;
                 }
             }
+            State::Arranging => {
+                self.information_text.text = String::from("ARRANGING...");
+                self.information_text.style.color = Color::WHITE;
+            }
             State::Applying => match self.arrow.status() {
                 GridArrowStatus::Block { position, progress } => {
                     let block_name = if let Some(block) = self.grid.get_block(position) {
@@ -379,6 +390,16 @@ impl BlocksScene {
         let prev_state = self.state;
         let prev_held = self.held_block.is_some();
 
+        let can_arrange = matches!(
+            self.state,
+            State::ListSelection | State::GridSelection { .. }
+        ) && !prev_held;
+
+        if can_arrange && self.input_tracker.is_active(Input::Option2) {
+            self.request_auto_arrange(game_io);
+            return;
+        }
+
         if let Some(block) = &mut self.held_block {
             let prev_rotation = block.rotation;
 
@@ -599,6 +620,20 @@ impl BlocksScene {
                     self.state = State::GridSelection { x, y };
                 }
             }
+            State::Arranging => {
+                if self.time % ARRANGE_INTERVAL == 0 {
+                    if let Some(block) = self.arranged_blocks.pop_front() {
+                        self.grid.install_block(game_io, block);
+                        self.update_colors();
+
+                        globals.audio.play_sound(&globals.sfx.customize_block);
+                    } else {
+                        self.state = State::ListSelection;
+
+                        globals.audio.play_sound(&globals.sfx.customize_complete);
+                    }
+                }
+            }
             State::Applying => {
                 let prev_status = self.arrow.status();
 
@@ -653,6 +688,67 @@ impl BlocksScene {
         }
     }
 
+    fn request_auto_arrange(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.grid.installed_blocks().next().is_none() {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            return;
+        }
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+
+        let event_sender = self.event_sender.clone();
+        let question = TextboxQuestion::new(
+            String::from("Automatically arrange the installed blocks?"),
+            move |yes| {
+                if yes {
+                    event_sender.send(Event::AutoArrange).unwrap();
+                }
+            },
+        );
+
+        self.textbox.push_interface(question);
+        self.textbox.open();
+    }
+
+    fn auto_arrange(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let packages = &globals.augment_packages;
+
+        let blocks: Vec<_> = (self.grid.installed_blocks())
+            .flat_map(|block| {
+                let package =
+                    packages.package_or_fallback(PackageNamespace::Local, &block.package_id)?;
+                Some((block, package))
+            })
+            .collect();
+
+        let solution = if blocks.len() == self.grid.installed_blocks().count() {
+            BlockArranger::new(&blocks).solve()
+        } else {
+            // blocks without packages can't be placed
+            None
+        };
+
+        let Some(solution) = solution else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+
+            let message = String::from("Couldn't find an arrangement without bugs.");
+            self.textbox.push_interface(TextboxMessage::new(message));
+            self.textbox.open();
+            return;
+        };
+
+        // clear the grid and place the blocks one at a time
+        self.grid = BlockGrid::new(PackageNamespace::Local);
+        self.arranged_blocks = solution.into();
+        self.state = State::Arranging;
+        self.update_colors();
+        self.update_cursor_sprite();
+        self.update_text(game_io);
+    }
+
     fn uninstall(&mut self, game_io: &GameIO, block: InstalledBlock) {
         let tracked_invalid_key = (Cow::Borrowed(&block.package_id), block.color);
 
@@ -705,7 +801,7 @@ impl BlocksScene {
                 }
             }
             State::ListSelection => self.cursor.use_textbox_cursor(),
-            State::Applying => {
+            State::Arranging | State::Applying => {
                 self.cursor.hide();
             }
             _ => {}
@@ -753,6 +849,9 @@ impl BlocksScene {
                     let transition = crate::transitions::new_sub_scene_pop(game_io);
                     self.next_scene = NextScene::new_pop().with_transition(transition);
                 }
+                Event::AutoArrange => {
+                    self.auto_arrange(game_io);
+                }
                 Event::Applied => {
                     self.arrow.reset();
                     self.state = State::ListSelection;
@@ -1,15 +1,26 @@
 use crate::bindable::SpriteColorMode;
 use crate::packages::*;
-use crate::render::ui::{FontName, LogBox, Text};
+use crate::render::ui::{FontName, LogBox, Text, Textbox, TextboxMessage, TextboxQuestion};
 use crate::render::*;
 use crate::resources::*;
 use framework::logging::{LogLevel, LogRecord};
 use framework::prelude::*;
 
-use super::{CategoryFilter, MainMenuScene, PackagesScene};
+use crate::saves::BattleTestFixture;
+
+use super::{
+    BattleInitScene, BattleTestScene, CategoryFilter, MainMenuScene, PackageUpdatesScene,
+    PackagesScene, SaveProfilesScene,
+};
 
 const LOG_MARGIN: f32 = 2.0;
 
+enum Event {
+    DownloadPackages,
+    KeepPackages,
+    SendCrashReport { path: String, uri: String },
+}
+
 pub struct BootScene {
     camera: Camera,
     background: Background,
@@ -17,10 +28,19 @@ pub struct BootScene {
     log_frame_sprite: Sprite,
     progress_bar_sprite: Sprite,
     progress_bar_bounds: Rect,
+    progress: f32,
     status_position: Vec2,
     log_box: LogBox,
     log_receiver: flume::Receiver<LogRecord>,
     event_receiver: flume::Receiver<BootEvent>,
+    battle_test_paths: Vec<String>,
+    battle_replay_path: Option<String>,
+    offer_profile_select: bool,
+    integrity_report: IntegrityReport,
+    crash_report_path: Option<String>,
+    textbox: Textbox,
+    scene_event_sender: flume::Sender<Event>,
+    scene_event_receiver: flume::Receiver<Event>,
     done: bool,
     next_scene: NextScene,
 }
@@ -65,6 +85,7 @@ impl BootScene {
 
         // work thread
         let receiver = BootThread::spawn(game_io);
+        let (scene_event_sender, scene_event_receiver) = flume::unbounded();
 
         BootScene {
             camera: Camera::new_ui(game_io),
@@ -73,15 +94,58 @@ impl BootScene {
             log_frame_sprite,
             progress_bar_sprite,
             progress_bar_bounds,
+            progress: 0.0,
             status_position,
             log_box,
             log_receiver,
             event_receiver: receiver,
+            battle_test_paths: Vec::new(),
+            battle_replay_path: None,
+            offer_profile_select: false,
+            integrity_report: IntegrityReport::default(),
+            crash_report_path: CrashReporter::take_pending_report(),
+            textbox: Textbox::new_navigation(game_io),
+            scene_event_sender,
+            scene_event_receiver,
             done: false,
             next_scene: NextScene::None,
         }
     }
 
+    /// Skips the menus and runs battle test fixtures once packages are loaded
+    pub fn with_battle_tests(mut self, paths: Vec<String>) -> Self {
+        self.battle_test_paths = paths;
+        self
+    }
+
+    /// Plays back a battle test fixture once packages are loaded, the menus are available after
+    pub fn with_battle_replay(mut self, path: Option<String>) -> Self {
+        self.battle_replay_path = path;
+        self
+    }
+
+    /// Lets the player pick a save profile before the main menu when there's more than one
+    pub fn with_profile_select(mut self, offer: bool) -> Self {
+        self.offer_profile_select = offer;
+        self
+    }
+
+    fn start_replay(&mut self, game_io: &mut GameIO, path: &str) {
+        let props = match BattleTestFixture::load(path) {
+            Ok(fixture) => fixture.battle_props(game_io),
+            Err(e) => Err(e),
+        };
+
+        match props {
+            Ok(props) => {
+                let scene = BattleInitScene::new(game_io, props);
+                let transition = crate::transitions::new_battle(game_io);
+                self.next_scene = NextScene::new_push(scene).with_transition(transition);
+            }
+            Err(e) => log::error!("Failed to replay {path:?}: {e}"),
+        }
+    }
+
     fn handle_thread_messages(&mut self, game_io: &mut GameIO) {
         while let Ok(record) = self.log_receiver.try_recv() {
             let high_priority = matches!(record.level, LogLevel::Warn | LogLevel::Error);
@@ -102,8 +166,10 @@ impl BootScene {
                     self.status_label.text = status_update.label.to_string();
 
                     // update progress bar
+                    // the total grows as packages are discovered, avoid moving backwards
                     let multiplier = status_update.progress as f32 / status_update.total as f32;
-                    self.update_progress_bar(multiplier);
+                    self.progress = self.progress.max(multiplier);
+                    self.update_progress_bar(self.progress);
                 }
                 BootEvent::Music(music) => {
                     let globals = game_io.resource_mut::<Globals>().unwrap();
@@ -111,14 +177,22 @@ impl BootScene {
                 }
                 BootEvent::Sfx(sfx) => {
                     let globals = game_io.resource_mut::<Globals>().unwrap();
+
+                    for (buffer, bus) in sfx.bus_routes() {
+                        globals.audio.set_sound_bus(buffer, bus);
+                    }
+
                     globals.sfx = sfx;
                 }
                 BootEvent::PlayerManager(player_packages) => {
                     let globals = game_io.resource_mut::<Globals>().unwrap();
                     globals.player_packages = player_packages;
+                    globals.apply_language();
                 }
                 BootEvent::CardManager(card_packages) => {
-                    game_io.resource_mut::<Globals>().unwrap().card_packages = card_packages;
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+                    globals.card_packages = card_packages;
+                    globals.apply_language();
                 }
                 BootEvent::EncounterManager(encounter_packages) => {
                     let globals = game_io.resource_mut::<Globals>().unwrap();
@@ -137,10 +211,20 @@ impl BootScene {
                 BootEvent::LibraryManager(library_packages) => {
                     game_io.resource_mut::<Globals>().unwrap().library_packages = library_packages;
                 }
+                BootEvent::EmoteManager(emote_packages) => {
+                    game_io.resource_mut::<Globals>().unwrap().emote_packages = emote_packages;
+                }
                 BootEvent::CharacterManager(character_packages) => {
                     let globals = game_io.resource_mut::<Globals>().unwrap();
                     globals.character_packages = character_packages;
                 }
+                BootEvent::IntegrityReport(integrity_report) => {
+                    self.integrity_report = integrity_report;
+                }
+                BootEvent::Done if !self.battle_test_paths.is_empty() => {
+                    let paths = std::mem::take(&mut self.battle_test_paths);
+                    self.next_scene = NextScene::new_swap(BattleTestScene::new(paths));
+                }
                 BootEvent::Done => {
                     let globals = game_io.resource::<Globals>().unwrap();
                     let mut available_players =
@@ -150,10 +234,25 @@ impl BootScene {
                         log::info!("Missing player mod");
                     }
 
+                    // packages from the boot thread skip Globals::load_package
+                    for namespace in [PackageNamespace::BuiltIn, PackageNamespace::Local] {
+                        globals
+                            .packages(namespace)
+                            .for_each(CrashReporter::record_package);
+                    }
+
+                    if let Some(path) = self.crash_report_path.take() {
+                        self.report_crash(game_io, path);
+                    }
+
                     let message = "Press Any Button";
                     self.status_label.text = String::from(message);
                     self.update_progress_bar(1.0);
                     self.done = true;
+
+                    if let Some(path) = self.battle_replay_path.take() {
+                        self.start_replay(game_io, &path);
+                    }
                 }
             }
         }
@@ -163,6 +262,121 @@ impl BootScene {
         self.status_label.style.bounds.set_position(status_position);
     }
 
+    /// Lets the player know about the last crash, asking to send the report if an endpoint is configured
+    fn report_crash(&mut self, game_io: &GameIO, path: String) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let uri = globals.config.crash_report_url.clone();
+
+        if uri.is_empty() {
+            let message = format!("The game crashed last time. A crash report was saved at {path}");
+
+            self.textbox.push_interface(TextboxMessage::new(message));
+        } else {
+            let message = format!(
+                "The game crashed last time. A crash report was saved at {path}. Send it to {uri}?"
+            );
+
+            let event_sender = self.scene_event_sender.clone();
+
+            let interface = TextboxQuestion::new(message, move |yes| {
+                if yes {
+                    let _ = event_sender.send(Event::SendCrashReport { path, uri });
+                }
+            });
+
+            self.textbox.push_interface(interface);
+        }
+
+        self.textbox.open();
+    }
+
+    fn send_crash_report(game_io: &GameIO, path: &str, uri: String) {
+        let Ok(report) = std::fs::read(path) else {
+            log::error!("Failed to read {path:?}");
+            return;
+        };
+
+        game_io
+            .spawn_local_task(async move {
+                if crate::http::upload(&uri, report).await {
+                    log::info!("Sent crash report to {uri}");
+                } else {
+                    log::error!("Failed to send crash report to {uri}");
+                }
+            })
+            .detach();
+    }
+
+    /// Reports damaged files before leaving, offering to download changed packages again
+    fn prompt_integrity_report(&mut self) {
+        let resources = std::mem::take(&mut self.integrity_report.resources);
+
+        if !resources.is_empty() {
+            let message = format!(
+                "{} built-in files are missing or damaged. Reinstalling the game is recommended.",
+                resources.len()
+            );
+
+            self.textbox.push_interface(TextboxMessage::new(message));
+        }
+
+        if !self.integrity_report.packages.is_empty() {
+            let message = format!(
+                "{} installed packages changed since they were downloaded. Download them again?",
+                self.integrity_report.packages.len()
+            );
+
+            let event_sender = self.scene_event_sender.clone();
+
+            let interface = TextboxQuestion::new(message, move |yes| {
+                let event = if yes {
+                    Event::DownloadPackages
+                } else {
+                    Event::KeepPackages
+                };
+
+                event_sender.send(event).unwrap();
+            });
+
+            self.textbox.push_interface(interface);
+        }
+
+        self.textbox.open();
+    }
+
+    fn handle_scene_events(&mut self, game_io: &mut GameIO) {
+        while let Ok(event) = self.scene_event_receiver.try_recv() {
+            match event {
+                Event::SendCrashReport { path, uri } => {
+                    Self::send_crash_report(game_io, &path, uri);
+                }
+                Event::DownloadPackages => {
+                    let packages = std::mem::take(&mut self.integrity_report.packages);
+                    let scene = PackageUpdatesScene::new(game_io, packages);
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::KeepPackages => {
+                    // accept the changes to avoid asking every boot
+                    let packages = std::mem::take(&mut self.integrity_report.packages);
+                    let globals = game_io.resource::<Globals>().unwrap();
+                    let mut integrity_cache = IntegrityCache::load();
+
+                    for (category, id, _) in packages {
+                        if let Some(package_info) =
+                            globals.package_info(category, PackageNamespace::Local, &id)
+                        {
+                            integrity_cache.record_package(id, package_info.hash);
+                        }
+                    }
+
+                    integrity_cache.save();
+                    self.transfer(game_io);
+                }
+            }
+        }
+    }
+
     fn update_progress_bar(&mut self, multiplier: f32) {
         let mut bounds = self.progress_bar_bounds;
 
@@ -179,7 +393,12 @@ impl BootScene {
             available_players.next().is_some()
         };
 
-        if has_playable_character {
+        if std::mem::take(&mut self.offer_profile_select) && SaveProfiles::list().len() > 1 {
+            // returns to enter(), which moves on to the main menu
+            let scene = SaveProfilesScene::new(game_io);
+            let transition = crate::transitions::new_sub_scene(game_io);
+            self.next_scene = NextScene::new_push(scene).with_transition(transition);
+        } else if has_playable_character {
             let mut scene = MainMenuScene::new(game_io);
             scene.set_background(self.background.clone());
             let transition = crate::transitions::new_boot(game_io);
@@ -208,11 +427,22 @@ impl Scene for BootScene {
     fn update(&mut self, game_io: &mut GameIO) {
         self.background.update();
         self.handle_thread_messages(game_io);
+        self.handle_scene_events(game_io);
 
         if game_io.is_in_transition() {
             return;
         }
 
+        if self.textbox.is_complete() {
+            self.textbox.close();
+        }
+
+        self.textbox.update(game_io);
+
+        if self.textbox.is_open() || !self.next_scene.is_none() {
+            return;
+        }
+
         let input_util = InputUtil::new(game_io);
 
         // transfer to the next scene
@@ -220,7 +450,11 @@ impl Scene for BootScene {
             let globals = game_io.resource::<Globals>().unwrap();
             globals.audio.play_sound(&globals.sfx.start_game);
 
-            self.transfer(game_io);
+            if self.integrity_report.is_empty() {
+                self.transfer(game_io);
+            } else {
+                self.prompt_integrity_report();
+            }
         }
     }
 
@@ -242,6 +476,8 @@ impl Scene for BootScene {
         // draw status
         self.status_label.draw(game_io, &mut sprite_queue);
 
+        self.textbox.draw(game_io, &mut sprite_queue);
+
         render_pass.consume_queue(sprite_queue);
     }
 }
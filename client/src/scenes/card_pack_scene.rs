@@ -0,0 +1,208 @@
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::Card;
+use framework::prelude::*;
+
+const CARD_POSITION: Vec2 = Vec2::new(8.0, 21.0);
+/// Cards slide in from below the screen
+const CARD_START_Y: f32 = RESOLUTION_F.y;
+const ENTER_DURATION: FrameTime = 12;
+/// Time the card's back is shown before it flips
+const FLIP_DELAY: FrameTime = 16;
+const LIST_POSITION: Vec2 = Vec2::new(96.0, 40.0);
+const LINE_HEIGHT: f32 = 16.0;
+const VISIBLE_ROWS: usize = 6;
+
+/// Opens a pack of cards granted by a server, revealing one card at a time
+pub struct CardPackScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    ui_input_tracker: UiInputTracker,
+    pack_name: String,
+    cards: Vec<Card>,
+    card_preview: FullCard,
+    current_index: usize,
+    /// Time since the current card started entering
+    time: FrameTime,
+    next_scene: NextScene,
+}
+
+impl CardPackScene {
+    pub fn new(game_io: &GameIO, pack_name: String, cards: Vec<Card>) -> Box<Self> {
+        let mut card_preview = FullCard::new(game_io, CARD_POSITION);
+        card_preview.set_card(cards.first().cloned());
+        card_preview.set_flipped(true);
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_top_bar(true),
+            ui_input_tracker: UiInputTracker::new(),
+            pack_name,
+            cards,
+            card_preview,
+            current_index: 0,
+            time: 0,
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn revealed(&self) -> bool {
+        self.time >= ENTER_DURATION + FLIP_DELAY
+    }
+
+    /// The number of cards flipped face up
+    fn revealed_count(&self) -> usize {
+        if self.revealed() {
+            self.current_index + 1
+        } else {
+            self.current_index
+        }
+    }
+
+    fn reveal(&mut self, game_io: &GameIO) {
+        self.time = ENTER_DURATION + FLIP_DELAY;
+        self.card_preview.set_flipped(false);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.play_sound(&globals.sfx.shine);
+    }
+
+    fn next_card(&mut self, game_io: &GameIO) {
+        self.current_index += 1;
+        self.time = 0;
+
+        let card = self.cards.get(self.current_index).cloned();
+        self.card_preview.set_card(card);
+        self.card_preview.set_flipped(true);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.play_sound(&globals.sfx.appear);
+    }
+
+    fn leave(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+        let transition = crate::transitions::new_scene_pop(game_io);
+        self.next_scene = NextScene::new_pop().with_transition(transition);
+    }
+
+    fn update_card_animation(&mut self, game_io: &GameIO) {
+        if self.cards.is_empty() || self.revealed() {
+            return;
+        }
+
+        self.time += 1;
+
+        let progress = (self.time as f32 / ENTER_DURATION as f32).min(1.0);
+        let mut position = CARD_POSITION;
+        position.y += (CARD_START_Y - CARD_POSITION.y) * (1.0 - progress).powi(2);
+        self.card_preview.set_position(position);
+
+        if self.revealed() {
+            self.reveal(game_io);
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let is_last_card = self.current_index + 1 >= self.cards.len();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            if is_last_card && (self.revealed() || self.cards.is_empty()) {
+                self.leave(game_io);
+                return;
+            }
+
+            // skip to the end of the pack
+            self.current_index = self.cards.len() - 1;
+            self.card_preview.set_card(self.cards.last().cloned());
+            self.card_preview.set_position(CARD_POSITION);
+            self.reveal(game_io);
+            return;
+        }
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        if !self.revealed() && !self.cards.is_empty() {
+            self.card_preview.set_position(CARD_POSITION);
+            self.reveal(game_io);
+        } else if is_last_card {
+            self.leave(game_io);
+        } else {
+            self.next_card(game_io);
+        }
+    }
+}
+
+impl Scene for CardPackScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        if !self.cards.is_empty() {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.appear);
+        }
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.update_card_animation(game_io);
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        // draw pack name and progress
+        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+        text_style
+            .bounds
+            .set_position(Vec2::new(LIST_POSITION.x, 22.0));
+        text_style.draw(game_io, &mut sprite_queue, &self.pack_name);
+
+        let revealed_count = self.revealed_count();
+        let progress_text = format!("{revealed_count}/{}", self.cards.len());
+        let progress_width = text_style.measure(&progress_text).size.x;
+        text_style.bounds.x = RESOLUTION_F.x - progress_width - 8.0;
+        text_style.draw(game_io, &mut sprite_queue, &progress_text);
+
+        // draw revealed cards, keeping the latest visible
+        let start = revealed_count.saturating_sub(VISIBLE_ROWS);
+        let mut position = LIST_POSITION;
+
+        for card in &self.cards[start..revealed_count] {
+            card.draw_list_item(game_io, &mut sprite_queue, position, Color::WHITE);
+            position.y += LINE_HEIGHT;
+        }
+
+        // draw card
+        if !self.cards.is_empty() {
+            self.card_preview.draw(game_io, &mut sprite_queue);
+        }
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("CARD PACK").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{BlocksScene, CharacterSelectScene, ManageSwitchDriveScene};
+use super::{BlocksScene, CharacterSelectScene, LoadoutScene, ManageSwitchDriveScene};
 use crate::battle::PlayerFallbackResources;
 use crate::bindable::SpriteColorMode;
 use crate::packages::PlayerPackage;
@@ -15,6 +15,7 @@ enum Event {
     BlockCustomization,
     CharacterSelect,
     EquipDrives,
+    Loadouts,
 }
 
 pub struct CharacterScene {
@@ -80,7 +81,8 @@ impl CharacterScene {
             let event = match i {
                 0 => Event::BlockCustomization,
                 1 => Event::EquipDrives,
-                _ => Event::CharacterSelect,
+                2 => Event::CharacterSelect,
+                _ => Event::Loadouts,
             };
 
             event_sender.send(event).unwrap();
@@ -149,6 +151,11 @@ impl Scene for CharacterScene {
                     self.next_scene = NextScene::new_push(ManageSwitchDriveScene::new(game_io))
                         .with_transition(transition)
                 }
+                Event::Loadouts => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    self.next_scene =
+                        NextScene::new_push(LoadoutScene::new(game_io)).with_transition(transition)
+                }
             }
         }
 
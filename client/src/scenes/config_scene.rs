@@ -1,10 +1,18 @@
-use super::{CategoryFilter, PackageUpdatesScene, PackagesScene, ResourceOrderScene};
+use super::{
+    AchievementsScene, CategoryFilter, NotificationsScene, PackageUpdatesScene, PackagesScene,
+    ResourceOrderScene, SaveBackupsScene, SaveImportScene, SaveProfilesScene,
+    ScreenshotGalleryScene, ServerCacheScene, TouchLayoutScene,
+};
 use crate::bindable::SpriteColorMode;
+use crate::format_util::format_timestamp;
 use crate::packages::PackageNamespace;
 use crate::render::ui::*;
 use crate::render::*;
 use crate::resources::*;
-use crate::saves::{Config, KeyStyle};
+use crate::saves::{
+    ChatFilterLevel, Config, GlobalSave, InputDisplayPosition, KeyStyle, SaveExport, SaveSync,
+    SaveSyncAction, ServerPreferences,
+};
 use framework::prelude::*;
 use packets::structures::{FileHash, PackageCategory, PackageId};
 use std::cell::RefCell;
@@ -17,14 +25,38 @@ enum Event {
     EnterCategory,
     OpenBindingContextMenu(flume::Sender<Option<BindingContextOption>>),
     RequestNicknameChange,
-    ChangeNickname { name: String },
+    ChangeNickname {
+        name: String,
+    },
+    RequestCrashReportUrlChange,
+    ChangeCrashReportUrl {
+        url: String,
+    },
+    ViewScreenshots,
+    ViewAchievements,
+    ViewNotifications,
+    ViewSaveProfiles,
+    ExportSave,
+    ImportSave,
+    RestoreBackup,
+    SyncSave,
+    SaveSyncChecked(Result<SaveSyncAction, String>),
+    RunSaveSync(SaveSyncAction),
+    SaveSyncFinished {
+        pulled: bool,
+        result: Result<(), String>,
+    },
     ViewPackages,
     UpdatePackages,
     ReceivedLatestHashes(Vec<(PackageCategory, PackageId, FileHash)>),
     ViewUpdates(Vec<(PackageCategory, PackageId, FileHash)>),
     ReorderResources,
+    ManageCache,
     ClearCache,
-    Leave { save: bool },
+    EditTouchLayout,
+    Leave {
+        save: bool,
+    },
 }
 
 #[derive(EnumIter, IntoStaticStr, Clone, Copy)]
@@ -34,6 +66,7 @@ enum ConfigCategory {
     Keyboard,
     Gamepad,
     Mods,
+    Server,
     Profile,
 }
 
@@ -53,6 +86,9 @@ pub struct ConfigScene {
     textbox: Textbox,
     doorstop_remover: Option<TextboxDoorstopRemover>,
     config: Rc<RefCell<Config>>,
+    /// Overlay settings are applied while browsing, these are restored when leaving without saving
+    initial_overlay_settings: (InputDisplayPosition, bool),
+    server_preferences: Option<Rc<RefCell<ServerPreferences>>>,
     next_scene: NextScene,
 }
 
@@ -74,8 +110,15 @@ impl ConfigScene {
 
         // config
         let config = globals.config.clone();
+        let initial_overlay_settings = (config.input_display, config.clean_feed);
         let config = Rc::new(RefCell::new(config));
 
+        // preferences for the server we're connected to
+        let server_preferences = (globals.connected_server_address.as_ref()).map(|address| {
+            let preferences = globals.global_save.server_preferences(address);
+            Rc::new(RefCell::new(preferences))
+        });
+
         // layout positioning
         let ui_animator =
             Animator::load_new(assets, ResourcePaths::CONFIG_UI_ANIMATION).with_state("DEFAULT");
@@ -120,6 +163,8 @@ impl ConfigScene {
             doorstop_remover: None,
             next_scene: NextScene::None,
             config,
+            initial_overlay_settings,
+            server_preferences,
         })
     }
 
@@ -128,7 +173,8 @@ impl ConfigScene {
         event_sender: flume::Sender<Event>,
         start: Vec2,
     ) -> UiLayout {
-        let assets = &game_io.resource::<Globals>().unwrap().assets;
+        let globals = game_io.resource::<Globals>().unwrap();
+        let assets = &globals.assets;
         let ui_texture = assets.texture(game_io, ResourcePaths::UI_NINE_PATCHES);
         let ui_animator = Animator::load_new(assets, ResourcePaths::UI_NINE_PATCHES_ANIMATION);
         let button_9patch = build_9patch!(game_io, ui_texture, &ui_animator, "BUTTON");
@@ -143,7 +189,10 @@ impl ConfigScene {
             Rect::new(start.x, start.y, f32::INFINITY, f32::INFINITY),
             ConfigCategory::iter()
                 .map(|option| {
-                    UiButton::new_text(game_io, FontName::Thick, option.into())
+                    let name: &'static str = option.into();
+                    let key = format!("config.category.{}", name.to_lowercase());
+
+                    UiButton::new_text(game_io, FontName::Thick, &globals.translate(&key))
                         .on_activate({
                             let event_sender = event_sender.clone();
 
@@ -172,6 +221,7 @@ impl ConfigScene {
     fn generate_submenu(
         game_io: &mut GameIO,
         config: &Rc<RefCell<Config>>,
+        server_preferences: &Option<Rc<RefCell<ServerPreferences>>>,
         category: ConfigCategory,
         event_sender: &flume::Sender<Event>,
     ) -> Vec<Box<dyn UiNode>> {
@@ -182,7 +232,10 @@ impl ConfigScene {
             ConfigCategory::Gamepad => {
                 Self::generate_controller_menu(game_io, config, event_sender)
             }
-            ConfigCategory::Mods => Self::generate_mods_menu(game_io, event_sender),
+            ConfigCategory::Mods => Self::generate_mods_menu(game_io, config, event_sender),
+            ConfigCategory::Server => {
+                Self::generate_server_menu(game_io, config, server_preferences)
+            }
             ConfigCategory::Profile => Self::generate_profile_menu(game_io, event_sender),
         }
     }
@@ -301,6 +354,132 @@ impl ConfigScene {
                     game_io.set_post_process_enabled::<PostProcessColorBlindness>(enable);
                 },
             )),
+            Box::new(UiConfigToggle::new(
+                "Flash Reduction",
+                config.borrow().flash_reduction,
+                config.clone(),
+                |game_io, mut config| {
+                    config.flash_reduction = !config.flash_reduction;
+
+                    let enable = config.flash_reduction;
+                    game_io.set_post_process_enabled::<PostProcessFlashReduction>(enable);
+
+                    config.flash_reduction
+                },
+            )),
+            Box::new(UiConfigToggle::new(
+                "Team Patterns",
+                config.borrow().team_patterns,
+                config.clone(),
+                |_, mut config| {
+                    config.team_patterns = !config.team_patterns;
+                    config.team_patterns
+                },
+            )),
+            // applied after saving, menus are laid out with the scale they were opened with
+            Box::new(
+                UiConfigPercentage::new(
+                    "Text Scale",
+                    config.borrow().ui_text_scale,
+                    config.clone(),
+                    |_, mut config, value| {
+                        config.ui_text_scale = value;
+                    },
+                )
+                .with_lower_bound(MIN_TEXT_SCALE)
+                .with_upper_bound(MAX_TEXT_SCALE),
+            ),
+            Box::new(UiConfigCycle::new(
+                "Text Speed",
+                config.borrow().text_char_delay,
+                config.clone(),
+                &[
+                    ("Slow", 3),
+                    ("Normal", DEFAULT_TEXT_CHAR_DELAY),
+                    ("Fast", 1),
+                    ("Instant", 0),
+                ],
+                |_, mut config, value| {
+                    config.text_char_delay = value;
+                },
+            )),
+            Box::new(UiConfigToggle::new(
+                "Auto Advance",
+                config.borrow().auto_advance_text,
+                config.clone(),
+                |_, mut config| {
+                    config.auto_advance_text = !config.auto_advance_text;
+                    config.auto_advance_text
+                },
+            )),
+            Box::new(UiConfigCycle::new(
+                "Asset Memory",
+                config.borrow().asset_memory_limit,
+                config.clone(),
+                &[
+                    ("256MiB", 256),
+                    ("512MiB", 512),
+                    ("1GiB", DEFAULT_ASSET_MEMORY_LIMIT),
+                    ("2GiB", 2048),
+                    ("4GiB", 4096),
+                ],
+                |game_io, mut config, value| {
+                    let globals = game_io.resource::<Globals>().unwrap();
+
+                    config.asset_memory_limit = value;
+                    globals
+                        .assets
+                        .set_memory_budget(config.asset_memory_limit_bytes());
+                },
+            )),
+            Box::new(UiConfigCycle::new(
+                "Input Display",
+                config.borrow().input_display,
+                config.clone(),
+                &[
+                    ("Off", InputDisplayPosition::Off),
+                    ("Top Left", InputDisplayPosition::TopLeft),
+                    ("Top Right", InputDisplayPosition::TopRight),
+                    ("Bottom Left", InputDisplayPosition::BottomLeft),
+                    ("Bottom Right", InputDisplayPosition::BottomRight),
+                ],
+                |game_io, mut config, value| {
+                    // displayed immediately for positioning
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+                    config.input_display = value;
+                    globals.config.input_display = value;
+                },
+            )),
+            Box::new(UiConfigToggle::new(
+                "Clean Feed",
+                config.borrow().clean_feed,
+                config.clone(),
+                |game_io, mut config| {
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+                    config.clean_feed = !config.clean_feed;
+                    globals.config.clean_feed = config.clean_feed;
+                    config.clean_feed
+                },
+            )),
+            Box::new(UiConfigToggle::new(
+                "Clip Recording",
+                config.borrow().clip_recording,
+                config.clone(),
+                |_, mut config| {
+                    config.clip_recording = !config.clip_recording;
+                    config.clip_recording
+                },
+            )),
+            // used only when every player in the battle opts in
+            Box::new(UiConfigToggle::new(
+                "Lockstep Netplay",
+                config.borrow().netplay_lockstep,
+                config.clone(),
+                |_, mut config| {
+                    config.netplay_lockstep = !config.netplay_lockstep;
+                    config.netplay_lockstep
+                },
+            )),
         ]
     }
 
@@ -341,6 +520,32 @@ impl ConfigScene {
                     }
                 },
             )),
+            Box::new(UiConfigPercentage::new(
+                "UI",
+                config.borrow().ui,
+                config.clone(),
+                |game_io, mut config, value| {
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+
+                    config.ui = value;
+                    globals
+                        .audio
+                        .set_bus_volume(AudioBus::Ui, config.bus_volume(AudioBus::Ui));
+                },
+            )),
+            Box::new(UiConfigPercentage::new(
+                "Voice",
+                config.borrow().voice,
+                config.clone(),
+                |game_io, mut config, value| {
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+
+                    config.voice = value;
+                    globals
+                        .audio
+                        .set_bus_volume(AudioBus::Voice, config.bus_volume(AudioBus::Voice));
+                },
+            )),
             Box::new(UiConfigToggle::new(
                 "Mute Music",
                 config.borrow().mute_music,
@@ -362,11 +567,75 @@ impl ConfigScene {
                     config.mute_sfx = !config.mute_sfx;
 
                     let audio = &mut game_io.resource_mut::<Globals>().unwrap().audio;
-                    audio.set_sfx_volume(config.sfx_volume());
+
+                    for bus in [AudioBus::Sfx, AudioBus::Ui, AudioBus::Voice] {
+                        audio.set_bus_volume(bus, config.bus_volume(bus));
+                    }
 
                     config.mute_sfx
                 },
             )),
+            Box::new(UiConfigToggle::new(
+                "Screen Reader",
+                config.borrow().screen_reader,
+                config.clone(),
+                |game_io, mut config| {
+                    config.screen_reader = !config.screen_reader;
+
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+                    globals.screen_reader.set_enabled(config.screen_reader);
+
+                    config.screen_reader
+                },
+            )),
+            Box::new(UiConfigCycle::new(
+                "Music Cache",
+                config.borrow().audio_cache_limit,
+                config.clone(),
+                &[
+                    ("64MiB", 64),
+                    ("128MiB", 128),
+                    ("256MiB", DEFAULT_AUDIO_CACHE_LIMIT),
+                    ("512MiB", 512),
+                    ("1GiB", 1024),
+                ],
+                |_, mut config, value| {
+                    config.audio_cache_limit = value;
+                },
+            )),
+            Box::new(UiConfigDynamicCycle::new(
+                game_io,
+                "Backend",
+                config.borrow().audio_backend.clone(),
+                config.clone(),
+                |_, value| {
+                    if value.is_empty() {
+                        String::from("Auto")
+                    } else {
+                        value.clone()
+                    }
+                },
+                |game_io, mut config, previous_value, cycle_right| {
+                    let names: Vec<_> = std::iter::once("")
+                        .chain(AudioManager::backend_names())
+                        .collect();
+
+                    let backend_name =
+                        UiConfigDynamicCycle::cycle_slice(&names, cycle_right, |name| {
+                            *name == previous_value.as_str()
+                        })
+                        .map(|name| name.to_string())
+                        .unwrap_or_default();
+
+                    // devices missing from the new backend fall back to the default device
+                    config.audio_backend = backend_name.clone();
+
+                    let audio = &mut game_io.resource_mut::<Globals>().unwrap().audio;
+                    audio.use_output(&config.audio_backend, &config.audio_device);
+
+                    backend_name
+                },
+            )),
             Box::new(UiConfigDynamicCycle::new(
                 game_io,
                 "Device",
@@ -381,7 +650,11 @@ impl ConfigScene {
                 },
                 |game_io, mut config, previous_value, cycle_right| {
                     let names: Vec<_> = std::iter::once(None)
-                        .chain(AudioManager::device_names().map(Some))
+                        .chain(
+                            AudioManager::device_names(&config.audio_backend)
+                                .into_iter()
+                                .map(Some),
+                        )
                         .collect();
 
                     let device_name =
@@ -397,7 +670,7 @@ impl ConfigScene {
                         .unwrap_or_default();
 
                     let audio = &mut game_io.resource_mut::<Globals>().unwrap().audio;
-                    audio.use_device(&device_name);
+                    audio.use_output(&config.audio_backend, &device_name);
 
                     config.audio_device = device_name.clone();
 
@@ -407,12 +680,47 @@ impl ConfigScene {
         ]
     }
 
+    fn generate_input_profile_cycle(
+        game_io: &mut GameIO,
+        config: &Rc<RefCell<Config>>,
+    ) -> Box<dyn UiNode> {
+        let text_config = config.clone();
+
+        Box::new(UiConfigDynamicCycle::new(
+            game_io,
+            "Profile",
+            config.borrow().input_profile,
+            config.clone(),
+            move |_, index| {
+                let config = text_config.borrow();
+                let profile = config.input_profiles.get(*index);
+
+                profile
+                    .map(|profile| profile.name.clone())
+                    .unwrap_or_default()
+            },
+            |_, mut config, previous_value, cycle_right| {
+                let len = config.input_profiles.len().max(1);
+
+                let index = if cycle_right {
+                    (previous_value + 1) % len
+                } else {
+                    (previous_value + len - 1) % len
+                };
+
+                config.switch_input_profile(index);
+                config.input_profile
+            },
+        ))
+    }
+
     fn generate_keyboard_menu(
-        game_io: &GameIO,
+        game_io: &mut GameIO,
         config: &Rc<RefCell<Config>>,
         event_sender: &flume::Sender<Event>,
     ) -> Vec<Box<dyn UiNode>> {
         let mut children: Vec<Box<dyn UiNode>> = vec![
+            Self::generate_input_profile_cycle(game_io, config),
             Box::new(UiConfigCycle::new(
                 "Style",
                 config.borrow().key_style,
@@ -458,6 +766,7 @@ impl ConfigScene {
         event_sender: &flume::Sender<Event>,
     ) -> Vec<Box<dyn UiNode>> {
         let mut children: Vec<Box<dyn UiNode>> = vec![
+            Self::generate_input_profile_cycle(game_io, config),
             Box::new(UiConfigDynamicCycle::new(
                 game_io,
                 "Active Gamepad",
@@ -479,6 +788,43 @@ impl ConfigScene {
                     id
                 },
             )),
+            Box::new(UiConfigToggle::new(
+                "Bind Profile",
+                Self::input_profile_device(&config.borrow()).is_some(),
+                config.clone(),
+                |game_io, mut config| {
+                    let profile_index = config.input_profile;
+                    let bound = Self::input_profile_device(&config).is_some();
+
+                    // bind to the active gamepad, another press unbinds
+                    let guid = if bound {
+                        String::new()
+                    } else {
+                        let controllers = game_io.input().controllers();
+
+                        controllers
+                            .iter()
+                            .find(|controller| controller.id() == config.controller_index)
+                            .map(|controller| controller.guid().to_string())
+                            .unwrap_or_default()
+                    };
+
+                    if let Some(profile) = config.input_profiles.get_mut(profile_index) {
+                        profile.device_guid = guid;
+                    }
+
+                    Self::input_profile_device(&config).is_some()
+                },
+            )),
+            Box::new(
+                UiButton::new_text(game_io, FontName::Thick, "Touch Buttons").on_activate({
+                    let event_sender = event_sender.clone();
+
+                    move || {
+                        let _ = event_sender.send(Event::EditTouchLayout);
+                    }
+                }),
+            ),
             Box::new(
                 UiButton::new_text(game_io, FontName::Thick, "Reset Binds").on_activate({
                     let config = config.clone();
@@ -509,10 +855,48 @@ impl ConfigScene {
         children
     }
 
+    fn input_profile_device(config: &Config) -> Option<&str> {
+        let profile = config.input_profiles.get(config.input_profile)?;
+        Some(profile.device_guid.as_str()).filter(|guid| !guid.is_empty())
+    }
+
     fn generate_mods_menu(
-        game_io: &GameIO,
+        game_io: &mut GameIO,
+        config: &Rc<RefCell<Config>>,
         event_sender: &flume::Sender<Event>,
     ) -> Vec<Box<dyn UiNode>> {
+        let language_cycle = UiConfigDynamicCycle::new(
+            game_io,
+            "Language",
+            config.borrow().language.clone(),
+            config.clone(),
+            |_, value| {
+                if value.is_empty() {
+                    String::from("Default")
+                } else {
+                    value.to_uppercase()
+                }
+            },
+            |game_io, mut config, previous_value, cycle_right| {
+                let globals = game_io.resource::<Globals>().unwrap();
+
+                // the empty language selects the default text
+                let mut languages = globals.package_languages();
+                languages.insert(0, String::new());
+
+                let language =
+                    UiConfigDynamicCycle::cycle_slice(&languages, cycle_right, |language| {
+                        language == previous_value
+                    })
+                    .cloned()
+                    .unwrap_or_default();
+
+                config.language = language.clone();
+
+                language
+            },
+        );
+
         let create_button = |name: &str, event: Event| -> Box<dyn UiNode> {
             let event_sender = event_sender.clone();
 
@@ -524,13 +908,89 @@ impl ConfigScene {
         };
 
         vec![
+            Box::new(language_cycle),
             create_button("Manage Mods", Event::ViewPackages),
             create_button("Update Mods", Event::UpdatePackages),
             create_button("Resource Mods", Event::ReorderResources),
+            create_button("Manage Cache", Event::ManageCache),
             create_button("Clear Cache", Event::ClearCache),
         ]
     }
 
+    fn generate_server_menu(
+        game_io: &GameIO,
+        config: &Rc<RefCell<Config>>,
+        server_preferences: &Option<Rc<RefCell<ServerPreferences>>>,
+    ) -> Vec<Box<dyn UiNode>> {
+        let Some(server_preferences) = server_preferences else {
+            return vec![Box::new(
+                Text::new(game_io, FontName::Thick)
+                    .with_str("Not connected")
+                    .with_shadow_color(TEXT_DARK_SHADOW_COLOR),
+            )];
+        };
+
+        let preferences = server_preferences.borrow();
+
+        vec![
+            Box::new(UiConfigCycle::new(
+                "Music",
+                preferences.music,
+                config.clone(),
+                &[
+                    ("Default", None),
+                    ("0%", Some(0)),
+                    ("25%", Some(25)),
+                    ("50%", Some(50)),
+                    ("75%", Some(75)),
+                    ("100%", Some(100)),
+                ],
+                {
+                    let server_preferences = server_preferences.clone();
+
+                    move |game_io, config, value| {
+                        let mut preferences = server_preferences.borrow_mut();
+                        preferences.music = value;
+
+                        let audio = &mut game_io.resource_mut::<Globals>().unwrap().audio;
+                        audio.set_music_volume(preferences.music_volume(&config));
+                    }
+                },
+            )),
+            Box::new(UiConfigToggle::new(
+                "Name Tags",
+                preferences.name_tags,
+                config.clone(),
+                {
+                    let server_preferences = server_preferences.clone();
+
+                    move |_, _| {
+                        let mut preferences = server_preferences.borrow_mut();
+                        preferences.name_tags = !preferences.name_tags;
+                        preferences.name_tags
+                    }
+                },
+            )),
+            Box::new(UiConfigCycle::new(
+                "Chat",
+                preferences.chat_filter,
+                config.clone(),
+                &[
+                    ("All", ChatFilterLevel::All),
+                    ("Party", ChatFilterLevel::PartyAndWhispers),
+                    ("System", ChatFilterLevel::SystemOnly),
+                ],
+                {
+                    let server_preferences = server_preferences.clone();
+
+                    move |_, _, value| {
+                        server_preferences.borrow_mut().chat_filter = value;
+                    }
+                },
+            )),
+        ]
+    }
+
     fn generate_profile_menu(
         game_io: &GameIO,
         event_sender: &flume::Sender<Event>,
@@ -545,10 +1005,18 @@ impl ConfigScene {
             )
         };
 
-        vec![create_button(
-            "Change Nickname",
-            Event::RequestNicknameChange,
-        )]
+        vec![
+            create_button("Change Nickname", Event::RequestNicknameChange),
+            create_button("Screenshots", Event::ViewScreenshots),
+            create_button("Achievements", Event::ViewAchievements),
+            create_button("Notifications", Event::ViewNotifications),
+            create_button("Sync Save", Event::SyncSave),
+            create_button("Save Profiles", Event::ViewSaveProfiles),
+            create_button("Export Save", Event::ExportSave),
+            create_button("Import Save", Event::ImportSave),
+            create_button("Restore Backup", Event::RestoreBackup),
+            create_button("Crash Reports", Event::RequestCrashReportUrlChange),
+        ]
     }
 }
 
@@ -597,8 +1065,13 @@ impl ConfigScene {
         while let Ok(event) = self.event_receiver.try_recv() {
             match event {
                 Event::CategoryChange(category) => {
-                    let children =
-                        Self::generate_submenu(game_io, &self.config, category, &self.event_sender);
+                    let children = Self::generate_submenu(
+                        game_io,
+                        &self.config,
+                        &self.server_preferences,
+                        category,
+                        &self.event_sender,
+                    );
 
                     let label: &'static str = category.into();
 
@@ -635,6 +1108,25 @@ impl ConfigScene {
                     global_save.nickname = name;
                     global_save.save();
                 }
+                Event::RequestCrashReportUrlChange => {
+                    let event_sender = self.event_sender.clone();
+                    let interface = TextboxPrompt::new(move |url| {
+                        let url = url.trim().to_string();
+                        let _ = event_sender.send(Event::ChangeCrashReportUrl { url });
+                    })
+                    .with_str(&self.config.borrow().crash_report_url)
+                    .with_filter(|grapheme| !grapheme.chars().any(char::is_whitespace));
+
+                    let message =
+                        "Enter a url to offer crash reports to on the next launch, leave empty to keep them local.";
+                    self.textbox
+                        .push_interface(TextboxMessage::new(String::from(message)));
+                    self.textbox.push_interface(interface);
+                    self.textbox.open();
+                }
+                Event::ChangeCrashReportUrl { url } => {
+                    self.config.borrow_mut().crash_report_url = url;
+                }
                 Event::ViewPackages => {
                     let scene = PackagesScene::new(game_io, CategoryFilter::default());
                     let transition = crate::transitions::new_sub_scene(game_io);
@@ -703,10 +1195,204 @@ impl ConfigScene {
                     let scene = ResourceOrderScene::new(game_io);
                     self.next_scene = NextScene::new_push(scene).with_transition(transition);
                 }
+                Event::ViewScreenshots => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = ScreenshotGalleryScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::ViewAchievements => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = AchievementsScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::ViewNotifications => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = NotificationsScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::ViewSaveProfiles => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = SaveProfilesScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::ExportSave => {
+                    let globals = game_io.resource::<Globals>().unwrap();
+                    let global_save = &globals.global_save;
+
+                    let result = SaveExport::new(global_save)
+                        .and_then(|export| export.write(&global_save.nickname));
+
+                    let message = match result {
+                        Ok(path) => {
+                            format!("Exported the save to {}", ResourcePaths::shorten(&path))
+                        }
+                        Err(e) => {
+                            log::error!("{e}");
+                            format!("Failed to export the save: {e}")
+                        }
+                    };
+
+                    self.textbox.push_interface(TextboxMessage::new(message));
+                    self.textbox.open();
+                }
+                Event::ImportSave => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = SaveImportScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::RestoreBackup => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = SaveBackupsScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::SyncSave => {
+                    let globals = game_io.resource::<Globals>().unwrap();
+                    let config = self.config.borrow().save_sync.clone();
+
+                    if globals.connected_to_server {
+                        let message = String::from("You should jack out before syncing your save.");
+                        self.textbox.push_interface(TextboxMessage::new(message));
+                        self.textbox.open();
+                        continue;
+                    }
+
+                    if !config.enabled() {
+                        let message = String::from(
+                            "Save sync isn't set up. Add a SaveSync section to config.ini to enable it.",
+                        );
+                        self.textbox.push_interface(TextboxMessage::new(message));
+                        self.textbox.open();
+                        continue;
+                    }
+
+                    let event_sender = self.event_sender.clone();
+                    let (doorstop, doorstop_remover) = TextboxDoorstop::new();
+                    self.doorstop_remover = Some(doorstop_remover);
+
+                    self.textbox
+                        .push_interface(doorstop.with_str("Checking the cloud save..."));
+                    self.textbox.open();
+
+                    game_io
+                        .spawn_local_task(async move {
+                            let result = SaveSync::new(config).check().await;
+                            let _ = event_sender.send(Event::SaveSyncChecked(result));
+                        })
+                        .detach();
+                }
+                Event::SaveSyncChecked(result) => {
+                    if let Some(remove_doorstop) = self.doorstop_remover.take() {
+                        remove_doorstop();
+                    }
+
+                    match result {
+                        Ok(SaveSyncAction::UpToDate) => {
+                            let message = String::from("Your save is up to date.");
+                            self.textbox.push_interface(TextboxMessage::new(message));
+                        }
+                        Ok(SaveSyncAction::Conflict {
+                            local_modified,
+                            remote_modified,
+                        }) => {
+                            let message = format!(
+                                "Both saves changed since the last sync.\nCloud: {}\nThis device: {}",
+                                format_timestamp(remote_modified),
+                                format_timestamp(local_modified)
+                            );
+                            self.textbox.push_interface(TextboxMessage::new(message));
+
+                            let event_sender = self.event_sender.clone();
+                            let options = &["Use Cloud", "Use This", "Cancel"];
+                            let interface = TextboxQuiz::new(options, move |selection| {
+                                let action = match selection {
+                                    0 => SaveSyncAction::Pull,
+                                    1 => SaveSyncAction::Push,
+                                    _ => return,
+                                };
+
+                                let _ = event_sender.send(Event::RunSaveSync(action));
+                            });
+                            self.textbox.push_interface(interface);
+                        }
+                        Ok(action) => {
+                            let _ = self.event_sender.send(Event::RunSaveSync(action));
+                        }
+                        Err(e) => {
+                            log::error!("{e}");
+                            let message = format!("Save sync failed: {e}");
+                            self.textbox.push_interface(TextboxMessage::new(message));
+                        }
+                    }
+                }
+                Event::RunSaveSync(action) => {
+                    let config = self.config.borrow().save_sync.clone();
+                    let pulled = action == SaveSyncAction::Pull;
+
+                    let message = if pulled {
+                        "Downloading the cloud save..."
+                    } else {
+                        "Uploading your save..."
+                    };
+
+                    let event_sender = self.event_sender.clone();
+                    let (doorstop, doorstop_remover) = TextboxDoorstop::new();
+                    self.doorstop_remover = Some(doorstop_remover);
+
+                    self.textbox.push_interface(doorstop.with_str(message));
+                    self.textbox.open();
+
+                    game_io
+                        .spawn_local_task(async move {
+                            let save_sync = SaveSync::new(config);
+
+                            let result = if pulled {
+                                save_sync.pull().await
+                            } else {
+                                save_sync.push().await
+                            };
+
+                            let _ = event_sender.send(Event::SaveSyncFinished { pulled, result });
+                        })
+                        .detach();
+                }
+                Event::SaveSyncFinished { pulled, result } => {
+                    if let Some(remove_doorstop) = self.doorstop_remover.take() {
+                        remove_doorstop();
+                    }
+
+                    let message = match result {
+                        Ok(()) if pulled => {
+                            let globals = game_io.resource_mut::<Globals>().unwrap();
+                            globals.global_save = GlobalSave::load(&globals.assets);
+
+                            String::from("Downloaded the cloud save.")
+                        }
+                        Ok(()) => String::from("Uploaded your save."),
+                        Err(e) => {
+                            log::error!("{e}");
+                            format!("Save sync failed: {e}")
+                        }
+                    };
+
+                    self.textbox.push_interface(TextboxMessage::new(message));
+                    self.textbox.open();
+                }
+                Event::ManageCache => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = ServerCacheScene::new(game_io);
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
+                Event::EditTouchLayout => {
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let scene = TouchLayoutScene::new(game_io, self.config.clone());
+                    self.next_scene = NextScene::new_push(scene).with_transition(transition);
+                }
                 Event::ClearCache => {
                     let globals = &mut game_io.resource::<Globals>().unwrap();
 
                     let message = if !globals.connected_to_server {
+                        let _ = std::fs::remove_dir_all(ResourcePaths::AUDIO_CACHE_FOLDER);
+
                         match std::fs::remove_dir_all(ResourcePaths::SERVER_CACHE_FOLDER) {
                             Ok(()) => String::from("Successfully cleared cache."),
                             Err(e) => {
@@ -733,17 +1419,43 @@ impl ConfigScene {
 
                     if save {
                         // save new config
+                        let previous_language = std::mem::take(&mut globals.config.language);
                         globals.config = self.config.borrow().clone();
                         globals.config.save();
+
+                        if globals.config.language != previous_language {
+                            globals.apply_language();
+                        }
+
+                        // save server preferences
+                        let address = globals.connected_server_address.as_ref();
+
+                        if let Some((address, preferences)) =
+                            address.zip(self.server_preferences.as_ref())
+                        {
+                            let global_save = &mut globals.global_save;
+                            global_save.set_server_preferences(address, preferences.take());
+                            global_save.save();
+                        }
                     } else {
                         // reapply previous config
+                        let (input_display, clean_feed) = self.initial_overlay_settings;
+                        globals.config.input_display = input_display;
+                        globals.config.clean_feed = clean_feed;
+
                         let config = &globals.config;
 
                         // audio
                         let audio = &mut globals.audio;
 
-                        audio.set_music_volume(config.music_volume());
-                        audio.set_sfx_volume(config.sfx_volume());
+                        for bus in AudioBus::ALL {
+                            audio.set_bus_volume(bus, config.bus_volume(bus));
+                        }
+
+                        globals.screen_reader.set_enabled(config.screen_reader);
+
+                        // assets
+                        (globals.assets).set_memory_budget(config.asset_memory_limit_bytes());
 
                         // post processing
                         globals.post_process_adjust_config =
@@ -754,6 +1466,7 @@ impl ConfigScene {
                         let enable_ghosting = config.ghosting > 0;
                         let enable_color_blindness =
                             config.color_blindness < PostProcessColorBlindness::TOTAL_OPTIONS;
+                        let enable_flash_reduction = config.flash_reduction;
 
                         // window
                         let fullscreen = config.fullscreen;
@@ -774,6 +1487,9 @@ impl ConfigScene {
                         game_io.set_post_process_enabled::<PostProcessColorBlindness>(
                             enable_color_blindness,
                         );
+                        game_io.set_post_process_enabled::<PostProcessFlashReduction>(
+                            enable_flash_reduction,
+                        );
                     }
 
                     let transition = crate::transitions::new_scene_pop(game_io);
@@ -856,7 +1572,9 @@ impl ConfigScene {
         let globals = game_io.resource::<Globals>().unwrap();
         let config = self.config.borrow();
 
-        if *config == globals.config {
+        let overlay_settings = (config.input_display, config.clean_feed);
+
+        if *config == globals.config && overlay_settings == self.initial_overlay_settings {
             // no changes, no need to ask if we should save
             let _ = self.event_sender.send(Event::Leave { save: false });
 
@@ -868,18 +1586,15 @@ impl ConfigScene {
 
         // get permission to save
         let interface = if config.validate() {
-            TextboxQuestion::new(String::from("Save changes?"), move |save| {
+            TextboxQuestion::new(globals.translate("config.save_changes"), move |save| {
                 let _ = event_sender.send(Event::Leave { save });
             })
         } else {
-            TextboxQuestion::new(
-                String::from("Config is invalid, use old config?"),
-                move |leave| {
-                    if leave {
-                        let _ = event_sender.send(Event::Leave { save: false });
-                    }
-                },
-            )
+            TextboxQuestion::new(globals.translate("config.invalid"), move |leave| {
+                if leave {
+                    let _ = event_sender.send(Event::Leave { save: false });
+                }
+            })
         };
 
         self.textbox.push_interface(interface);
@@ -0,0 +1,386 @@
+use crate::bindable::SpriteColorMode;
+use crate::packages::{PackageId, PackageNamespace};
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::Deck;
+use framework::prelude::*;
+use std::collections::BTreeMap;
+
+const LEFT_OFFSET: f32 = 70.0;
+const RIGHT_OFFSET: f32 = 148.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum DeckDiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl DeckDiffKind {
+    fn color(self) -> Color {
+        match self {
+            DeckDiffKind::Added => Color::GREEN,
+            DeckDiffKind::Removed => Color::ORANGE,
+            DeckDiffKind::Changed => Color::YELLOW,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DeckDiffRow {
+    package_id: PackageId,
+    kind: DeckDiffKind,
+    /// Sorted codes for this card in the left folder
+    left_codes: Vec<String>,
+    /// Sorted codes for this card in the right folder
+    right_codes: Vec<String>,
+}
+
+struct DeckDiffLine {
+    name: String,
+    kind: DeckDiffKind,
+    left: String,
+    right: String,
+}
+
+pub struct DeckCompareScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    left_index: usize,
+    right_index: usize,
+    lines: Vec<DeckDiffLine>,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    next_scene: NextScene,
+}
+
+impl DeckCompareScene {
+    /// Compares the folder at `deck_index` with the next folder,
+    /// left and right pick another folder to compare against
+    pub fn new(game_io: &GameIO, deck_index: usize) -> Box<Self> {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let total_decks = globals.global_save.decks.len();
+
+        // the first line is reserved for folder names
+        let list = ListPanel::new(game_io, "COMPARE", RESOLUTION_F.x - 16.0)
+            .with_header_lines(1)
+            .with_cursor(false);
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            left_index: deck_index,
+            right_index: (deck_index + 1) % total_decks.max(1),
+            lines: Vec::new(),
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            next_scene: NextScene::None,
+        });
+
+        scene.compare(game_io);
+        scene
+    }
+
+    fn compare(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let package_manager = &globals.card_packages;
+        let decks = &globals.global_save.decks;
+
+        let (Some(left), Some(right)) = (decks.get(self.left_index), decks.get(self.right_index))
+        else {
+            self.lines = Vec::new();
+            self.list.set_total_items(0);
+            return;
+        };
+
+        self.lines = compare_decks(left, right)
+            .into_iter()
+            .map(|row| {
+                let name = package_manager
+                    .package_or_fallback(PackageNamespace::Local, &row.package_id)
+                    .map(|package| package.card_properties.short_name.to_string());
+
+                DeckDiffLine {
+                    name: name.unwrap_or_else(|| String::from("?????")),
+                    kind: row.kind,
+                    left: format_codes(&row.left_codes),
+                    right: format_codes(&row.right_codes),
+                }
+            })
+            .collect();
+
+        self.list.set_total_items(self.lines.len());
+        self.list.set_selected_index(0);
+    }
+
+    fn cycle_right_deck(&mut self, game_io: &GameIO, forward: bool) -> bool {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let total_decks = globals.global_save.decks.len();
+
+        if total_decks < 3 {
+            // there's no other folder to compare against
+            return false;
+        }
+
+        loop {
+            self.right_index = if forward {
+                (self.right_index + 1) % total_decks
+            } else {
+                (self.right_index + total_decks - 1) % total_decks
+            };
+
+            if self.right_index != self.left_index {
+                break;
+            }
+        }
+
+        self.compare(game_io);
+        true
+    }
+}
+
+impl Scene for DeckCompareScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.ui_input_tracker.update(game_io);
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        // swap the compared folder
+        let left = self.ui_input_tracker.is_active(Input::Left);
+        let right = self.ui_input_tracker.is_active(Input::Right);
+
+        if (left || right) && self.cycle_right_deck(game_io, right) {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.page_turn);
+        }
+
+        // scrolling
+        let prev_index = self.list.selected_index();
+        let scroll_tracker = self.list.scroll_tracker_mut();
+
+        if self.ui_input_tracker.is_active(Input::Up) {
+            scroll_tracker.move_view_up();
+        }
+
+        if self.ui_input_tracker.is_active(Input::Down) {
+            scroll_tracker.move_view_down();
+        }
+
+        if self.ui_input_tracker.is_active(Input::ShoulderL) {
+            scroll_tracker.page_up();
+        }
+
+        if self.ui_input_tracker.is_active(Input::ShoulderR) {
+            scroll_tracker.page_down();
+        }
+
+        if prev_index != self.list.selected_index() {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new_monospace(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let line = &self.lines[index];
+
+                text_style.color = line.kind.color();
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &line.name);
+
+                text_style.bounds.x = position.x + LEFT_OFFSET;
+                text_style.draw(game_io, sprite_queue, &line.left);
+
+                text_style.bounds.x = position.x + RIGHT_OFFSET;
+                text_style.draw(game_io, sprite_queue, &line.right);
+            },
+        );
+
+        // draw header
+        let globals = game_io.resource::<Globals>().unwrap();
+        let decks = &globals.global_save.decks;
+        let deck_name = |index: usize| decks.get(index).map(|deck| deck.name.as_str());
+
+        let mut position = self.list.header_start();
+
+        text_style.color = Color::YELLOW;
+        text_style
+            .bounds
+            .set_position(position + Vec2::new(LEFT_OFFSET, 0.0));
+        text_style.draw(
+            game_io,
+            &mut sprite_queue,
+            deck_name(self.left_index).unwrap_or_default(),
+        );
+
+        text_style.bounds.x = position.x + RIGHT_OFFSET;
+        text_style.draw(
+            game_io,
+            &mut sprite_queue,
+            deck_name(self.right_index).unwrap_or_default(),
+        );
+
+        if self.lines.is_empty() {
+            position.y += self.list.line_height();
+
+            text_style.color = Color::WHITE;
+            text_style.bounds.set_position(position);
+            text_style.draw(game_io, &mut sprite_queue, "No differences");
+        }
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("FOLDER COMPARE").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
+
+/// Lists cards that were added, removed, or had copies or codes changed between two folders,
+/// cards are in the order they first appear in the left folder, then the right folder
+fn compare_decks(left: &Deck, right: &Deck) -> Vec<DeckDiffRow> {
+    let mut order = Vec::<&PackageId>::new();
+    let mut codes = BTreeMap::<&PackageId, (Vec<String>, Vec<String>)>::new();
+
+    for (side, deck) in [left, right].into_iter().enumerate() {
+        for card in &deck.cards {
+            let entry = codes.entry(&card.package_id).or_insert_with(|| {
+                order.push(&card.package_id);
+                Default::default()
+            });
+
+            let list = if side == 0 {
+                &mut entry.0
+            } else {
+                &mut entry.1
+            };
+            list.push(card.code.clone());
+        }
+    }
+
+    order
+        .into_iter()
+        .flat_map(|package_id| {
+            let (mut left_codes, mut right_codes) = codes.remove(package_id)?;
+            left_codes.sort();
+            right_codes.sort();
+
+            let kind = if left_codes.is_empty() {
+                DeckDiffKind::Added
+            } else if right_codes.is_empty() {
+                DeckDiffKind::Removed
+            } else if left_codes != right_codes {
+                DeckDiffKind::Changed
+            } else {
+                return None;
+            };
+
+            Some(DeckDiffRow {
+                package_id: package_id.clone(),
+                kind,
+                left_codes,
+                right_codes,
+            })
+        })
+        .collect()
+}
+
+fn format_codes(codes: &[String]) -> String {
+    if codes.is_empty() {
+        return String::from("-");
+    }
+
+    codes.join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::saves::Card;
+
+    fn deck(cards: &[(&str, &str)]) -> Deck {
+        let mut deck = Deck::new(String::from("Test"));
+
+        deck.cards = (cards.iter())
+            .map(|(id, code)| Card {
+                package_id: PackageId::from(*id),
+                code: code.to_string(),
+            })
+            .collect();
+
+        deck
+    }
+
+    #[test]
+    fn compare_decks_lists_differences() {
+        let left = deck(&[
+            ("cannon", "A"),
+            ("cannon", "B"),
+            ("sword", "S"),
+            ("bomb", "*"),
+        ]);
+        let right = deck(&[
+            ("sword", "S"),
+            ("cannon", "A"),
+            ("cannon", "A"),
+            ("wave", "W"),
+        ]);
+
+        let rows = compare_decks(&left, &right);
+        let summary: Vec<_> = (rows.iter())
+            .map(|row| (row.package_id.as_str(), row.kind))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("cannon", DeckDiffKind::Changed),
+                ("bomb", DeckDiffKind::Removed),
+                ("wave", DeckDiffKind::Added),
+            ]
+        );
+
+        assert_eq!(rows[0].left_codes, ["A", "B"]);
+        assert_eq!(rows[0].right_codes, ["A", "A"]);
+    }
+
+    #[test]
+    fn compare_decks_ignores_card_order() {
+        let left = deck(&[("cannon", "A"), ("sword", "S")]);
+        let right = deck(&[("sword", "S"), ("cannon", "A")]);
+
+        assert!(compare_decks(&left, &right).is_empty());
+    }
+}
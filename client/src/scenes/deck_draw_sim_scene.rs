@@ -0,0 +1,333 @@
+use crate::bindable::{CardClass, SpriteColorMode};
+use crate::packages::{PackageId, PackageNamespace};
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::{Card, Deck};
+use framework::prelude::*;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::collections::HashMap;
+
+const TRIALS: usize = 5000;
+const TURNS: usize = 3;
+// matches the base hand size in battle, hand size boosts from blocks are not accounted for
+const HAND_SIZE: usize = 5;
+const COPIES_OFFSET: f32 = 74.0;
+const TURN_OFFSET: f32 = 98.0;
+const TURN_SPACING: f32 = 38.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DrawSimView {
+    Cards,
+    Codes,
+}
+
+struct DrawSimRow {
+    label: String,
+    copies: usize,
+    turn_chances: [f32; TURNS],
+}
+
+pub struct DeckDrawSimScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    view: DrawSimView,
+    card_rows: Vec<DrawSimRow>,
+    code_rows: Vec<DrawSimRow>,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    next_scene: NextScene,
+}
+
+impl DeckDrawSimScene {
+    pub fn new(game_io: &GameIO, deck_index: usize) -> Box<Self> {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let package_manager = &globals.card_packages;
+        let deck = &globals.global_save.decks[deck_index];
+
+        let card_classes: HashMap<_, _> = deck
+            .cards
+            .iter()
+            .flat_map(|card| {
+                let package = package_manager
+                    .package_or_fallback(PackageNamespace::Local, &card.package_id)?;
+
+                Some((card.package_id.clone(), package.card_properties.card_class))
+            })
+            .collect();
+
+        let mut rng = Xoshiro256PlusPlus::from_entropy();
+        let (mut card_rows, code_rows) = simulate_draws(deck, &mut rng, |card| {
+            card_classes.get(&card.package_id).cloned()
+        });
+
+        // resolve card names
+        for row in &mut card_rows {
+            let package_id = PackageId::from(row.label.as_str());
+            let name = package_manager
+                .package_or_fallback(PackageNamespace::Local, &package_id)
+                .map(|package| package.card_properties.short_name.to_string());
+
+            row.label = name.unwrap_or_else(|| String::from("?????"));
+        }
+
+        // the first line is reserved for turn numbers
+        let mut list = ListPanel::new(game_io, "CARDS", RESOLUTION_F.x - 16.0)
+            .with_header_lines(1)
+            .with_cursor(false);
+        list.set_total_items(card_rows.len());
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            view: DrawSimView::Cards,
+            card_rows,
+            code_rows,
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn rows(&self) -> &[DrawSimRow] {
+        match self.view {
+            DrawSimView::Cards => &self.card_rows,
+            DrawSimView::Codes => &self.code_rows,
+        }
+    }
+
+    fn set_view(&mut self, view: DrawSimView) {
+        self.view = view;
+
+        let label = match view {
+            DrawSimView::Cards => "CARDS",
+            DrawSimView::Codes => "CODES",
+        };
+
+        self.list.set_label(label.to_string());
+
+        let total_rows = self.rows().len();
+        self.list.set_total_items(total_rows);
+        self.list.set_selected_index(0);
+    }
+}
+
+impl Scene for DeckDrawSimScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.ui_input_tracker.update(game_io);
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        // swap views
+        let left = self.ui_input_tracker.is_active(Input::Left);
+        let right = self.ui_input_tracker.is_active(Input::Right);
+
+        if left || right {
+            let view = match self.view {
+                DrawSimView::Cards => DrawSimView::Codes,
+                DrawSimView::Codes => DrawSimView::Cards,
+            };
+
+            self.set_view(view);
+
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.page_turn);
+        }
+
+        // scrolling
+        let prev_index = self.list.selected_index();
+        let scroll_tracker = self.list.scroll_tracker_mut();
+
+        if self.ui_input_tracker.is_active(Input::Up) {
+            scroll_tracker.move_view_up();
+        }
+
+        if self.ui_input_tracker.is_active(Input::Down) {
+            scroll_tracker.move_view_down();
+        }
+
+        if self.ui_input_tracker.is_active(Input::ShoulderL) {
+            scroll_tracker.page_up();
+        }
+
+        if self.ui_input_tracker.is_active(Input::ShoulderR) {
+            scroll_tracker.page_down();
+        }
+
+        if prev_index != self.list.selected_index() {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new_monospace(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        let rows = match self.view {
+            DrawSimView::Cards => &self.card_rows,
+            DrawSimView::Codes => &self.code_rows,
+        };
+
+        text_style.color = Color::WHITE;
+
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let row = &rows[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &row.label);
+
+                text_style.bounds.x = position.x + COPIES_OFFSET;
+                text_style.draw(game_io, sprite_queue, &format!("x{}", row.copies));
+
+                for (turn, chance) in row.turn_chances.iter().enumerate() {
+                    text_style.bounds.x = position.x + TURN_OFFSET + turn as f32 * TURN_SPACING;
+                    text_style.draw(game_io, sprite_queue, &format!("{:>3.0}%", chance * 100.0));
+                }
+            },
+        );
+
+        // draw header
+        let position = self.list.header_start();
+        text_style.color = Color::YELLOW;
+
+        for turn in 0..TURNS {
+            let mut turn_position = position;
+            turn_position.x += TURN_OFFSET + turn as f32 * TURN_SPACING;
+
+            text_style.bounds.set_position(turn_position);
+            text_style.draw(game_io, &mut sprite_queue, &format!("T{}", turn + 1));
+        }
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("DRAW SIM").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
+
+/// Runs simulated battles and returns the chance of seeing a card or code by each turn,
+/// assumes every card in hand is used each turn.
+///
+/// Card rows are labelled using package ids
+fn simulate_draws(
+    deck: &Deck,
+    rng: &mut impl rand::Rng,
+    class_of: impl Fn(&Card) -> Option<CardClass>,
+) -> (Vec<DrawSimRow>, Vec<DrawSimRow>) {
+    let mut card_rows = Vec::<DrawSimRow>::new();
+    let mut code_rows = Vec::<DrawSimRow>::new();
+    let mut card_lookup = HashMap::new();
+    let mut code_lookup = HashMap::new();
+
+    for card in &deck.cards {
+        let package_id = card.package_id.as_str();
+        let index = *card_lookup.entry(package_id).or_insert_with(|| {
+            card_rows.push(DrawSimRow {
+                label: package_id.to_string(),
+                copies: 0,
+                turn_chances: [0.0; TURNS],
+            });
+
+            card_rows.len() - 1
+        });
+        card_rows[index].copies += 1;
+
+        let code = card.code.as_str();
+        let index = *code_lookup.entry(code).or_insert_with(|| {
+            code_rows.push(DrawSimRow {
+                label: format!("Code {code}"),
+                copies: 0,
+                turn_chances: [0.0; TURNS],
+            });
+
+            code_rows.len() - 1
+        });
+        code_rows[index].copies += 1;
+    }
+
+    if deck.cards.is_empty() {
+        return (card_rows, code_rows);
+    }
+
+    let mut card_hits = vec![[0usize; TURNS]; card_rows.len()];
+    let mut code_hits = vec![[0usize; TURNS]; code_rows.len()];
+    let mut card_first_turn = vec![None; card_rows.len()];
+    let mut code_first_turn = vec![None; code_rows.len()];
+
+    for _ in 0..TRIALS {
+        let mut deck = deck.clone();
+        deck.shuffle_with(rng, &class_of);
+
+        card_first_turn.fill(None);
+        code_first_turn.fill(None);
+
+        for (i, card) in deck.cards.iter().take(HAND_SIZE * TURNS).enumerate() {
+            let turn = i / HAND_SIZE;
+
+            let card_index = card_lookup[card.package_id.as_str()];
+            card_first_turn[card_index].get_or_insert(turn);
+
+            let code_index = code_lookup[card.code.as_str()];
+            code_first_turn[code_index].get_or_insert(turn);
+        }
+
+        let tally = |hits: &mut [[usize; TURNS]], first_turns: &[Option<usize>]| {
+            for (hits, first_turn) in hits.iter_mut().zip(first_turns) {
+                let Some(first_turn) = *first_turn else {
+                    continue;
+                };
+
+                for hit in &mut hits[first_turn..] {
+                    *hit += 1;
+                }
+            }
+        };
+
+        tally(&mut card_hits, &card_first_turn);
+        tally(&mut code_hits, &code_first_turn);
+    }
+
+    let resolve = |rows: &mut [DrawSimRow], hits: &[[usize; TURNS]]| {
+        for (row, hits) in rows.iter_mut().zip(hits) {
+            for (chance, hit_count) in row.turn_chances.iter_mut().zip(hits) {
+                *chance = *hit_count as f32 / TRIALS as f32;
+            }
+        }
+    };
+
+    resolve(&mut card_rows, &card_hits);
+    resolve(&mut code_rows, &code_hits);
+
+    (card_rows, code_rows)
+}
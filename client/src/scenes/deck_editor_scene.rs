@@ -1,4 +1,5 @@
 use crate::bindable::CardClass;
+use crate::bindable::Element;
 use crate::bindable::SpriteColorMode;
 use crate::packages::*;
 use crate::render::ui::*;
@@ -9,6 +10,10 @@ use framework::prelude::*;
 use std::collections::HashMap;
 
 const NAMESPACE: PackageNamespace = PackageNamespace::Local;
+const SEARCH_LIMIT: usize = 8;
+/// Inclusive attack ranges cycled through by the filter menu
+const DAMAGE_RANGES: [(i32, i32); 5] = [(0, 0), (1, 50), (51, 100), (101, 200), (201, i32::MAX)];
+const MIN_COUNT_LIMIT: isize = 5;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum EditorMode {
@@ -19,6 +24,7 @@ enum EditorMode {
 enum Event {
     Leave(bool),
     SwitchMode(EditorMode),
+    FilterSearch(String),
 }
 
 #[repr(u8)]
@@ -33,6 +39,17 @@ enum Sorting {
     Class,
 }
 
+#[derive(Clone, Copy)]
+enum FilterOption {
+    Search,
+    Element,
+    Damage,
+    Code,
+    Source,
+    Count,
+    Clear,
+}
+
 pub struct DeckEditorScene {
     deck_index: usize,
     deck_restrictions: DeckRestrictions,
@@ -44,6 +61,10 @@ pub struct DeckEditorScene {
     page_tracker: PageTracker,
     context_menu: ContextMenu<Sorting>,
     last_sort: Option<Sorting>,
+    filter_menu: ContextMenu<FilterOption>,
+    pack_filter: CardFilter,
+    /// Pack cards hidden by the filter
+    hidden_pack_items: Vec<CardListItem>,
     mode: EditorMode,
     deck_dock: Dock,
     pack_dock: Dock,
@@ -112,7 +133,7 @@ impl DeckEditorScene {
 
         let (event_sender, event_receiver) = flume::unbounded();
 
-        Self {
+        let mut scene = Self {
             deck_index,
             deck_restrictions,
             camera,
@@ -135,6 +156,9 @@ impl DeckEditorScene {
                 ],
             ),
             last_sort: None,
+            filter_menu: ContextMenu::new(game_io, "FILTER", Vec2::ZERO),
+            pack_filter: CardFilter::default(),
+            hidden_pack_items: Vec::new(),
             mode: EditorMode::Default,
             deck_dock,
             pack_dock,
@@ -144,6 +168,90 @@ impl DeckEditorScene {
             event_sender,
             event_receiver,
             next_scene: NextScene::None,
+        };
+
+        scene.update_filter_menu(game_io);
+        scene
+    }
+
+    fn pack_items(&self) -> impl Iterator<Item = &CardListItem> {
+        self.pack_dock.card_items().chain(&self.hidden_pack_items)
+    }
+
+    fn apply_pack_filter(&mut self, game_io: &GameIO) {
+        let card_manager = &game_io.resource::<Globals>().unwrap().card_packages;
+        let pack_dock = &mut self.pack_dock;
+
+        let items =
+            (pack_dock.card_slots.drain(..).flatten()).chain(self.hidden_pack_items.drain(..));
+
+        let (visible_items, hidden_items): (Vec<_>, Vec<_>) = items.partition(|item| {
+            let package = card_manager.package_or_fallback(NAMESPACE, &item.card.package_id);
+            self.pack_filter.matches(package, item)
+        });
+
+        pack_dock.card_slots = visible_items.into_iter().map(Some).collect();
+        self.hidden_pack_items = hidden_items;
+
+        pack_dock.scroll_tracker.forget_index();
+        (pack_dock.scroll_tracker).set_total_items(pack_dock.card_slots.len());
+        pack_dock.update_preview();
+    }
+
+    fn update_filter_menu(&mut self, game_io: &GameIO) {
+        let filter = &self.pack_filter;
+        let any = String::from("Any");
+
+        let search = if filter.search.is_empty() {
+            any.clone()
+        } else {
+            filter.search.clone()
+        };
+
+        let element = match filter.element {
+            Some(element) => element.to_string(),
+            None => any.clone(),
+        };
+
+        let damage = match filter.damage_range.map(|i| DAMAGE_RANGES[i]) {
+            Some((min, max)) if min == max => min.to_string(),
+            Some((min, i32::MAX)) => format!("{min}+"),
+            Some((min, max)) => format!("{min}-{max}"),
+            None => any.clone(),
+        };
+
+        let code = filter.code.clone().unwrap_or_else(|| any.clone());
+
+        let source = match filter.source {
+            Some(PackageNamespace::BuiltIn) => String::from("Game"),
+            Some(_) => String::from("Mods"),
+            None => any.clone(),
+        };
+
+        let count = match filter.min_count {
+            Some(count) => format!("{count}+"),
+            None => any,
+        };
+
+        let labels = [
+            (format!("Name: {search}"), FilterOption::Search),
+            (format!("Elem: {element}"), FilterOption::Element),
+            (format!("Atk: {damage}"), FilterOption::Damage),
+            (format!("Code: {code}"), FilterOption::Code),
+            (format!("Src: {source}"), FilterOption::Source),
+            (format!("No.: {count}"), FilterOption::Count),
+            (String::from("Clear"), FilterOption::Clear),
+        ];
+
+        let options: Vec<_> = (labels.iter())
+            .map(|(label, option)| (label.as_str(), *option))
+            .collect();
+
+        let focused_option = self.filter_menu.focused_option();
+        self.filter_menu.set_options(game_io, &options);
+
+        if let Some(option) = focused_option {
+            self.filter_menu.focus_option(option);
         }
     }
 
@@ -246,7 +354,9 @@ impl Scene for DeckEditorScene {
 
             dock.draw(game_io, &mut sprite_queue, self.mode, offset);
 
-            if !self.textbox.is_open() && !self.context_menu.is_open() {
+            let menu_open = self.context_menu.is_open() || self.filter_menu.is_open();
+
+            if !self.textbox.is_open() && !menu_open {
                 dock.draw_cursor(&mut sprite_queue, offset);
             }
         }
@@ -269,6 +379,14 @@ impl Scene for DeckEditorScene {
             self.context_menu.draw(game_io, &mut sprite_queue);
         }
 
+        // draw filter menu
+        if self.filter_menu.is_open() {
+            self.filter_menu
+                .set_position(self.pack_dock.context_menu_position);
+
+            self.filter_menu.draw(game_io, &mut sprite_queue);
+        }
+
         // draw deck total frame
         let offset = Vec2::new(self.page_tracker.page_offset(0), 0.0);
         let original_total_position = self.deck_total_sprite.position();
@@ -329,6 +447,11 @@ fn handle_events(scene: &mut DeckEditorScene, game_io: &mut GameIO) {
 
             scene.mode = mode;
         }
+        Event::FilterSearch(search) => {
+            scene.pack_filter.search = search;
+            scene.apply_pack_filter(game_io);
+            scene.update_filter_menu(game_io);
+        }
     }
 }
 
@@ -340,6 +463,11 @@ fn handle_input(scene: &mut DeckEditorScene, game_io: &mut GameIO) {
         return;
     }
 
+    if scene.filter_menu.is_open() {
+        handle_filter_menu_input(scene, game_io);
+        return;
+    }
+
     // dock scrolling
     let active_dock = if scene.page_tracker.active_page() == 0 {
         &mut scene.deck_dock
@@ -354,6 +482,7 @@ fn handle_input(scene: &mut DeckEditorScene, game_io: &mut GameIO) {
 
     if original_index != scroll_tracker.selected_index() {
         active_dock.update_preview();
+        active_dock.announce_selection(game_io);
 
         let globals = game_io.resource::<Globals>().unwrap();
         globals.audio.play_sound(&globals.sfx.cursor_move);
@@ -430,6 +559,14 @@ fn handle_input(scene: &mut DeckEditorScene, game_io: &mut GameIO) {
         scene.context_menu.open();
     }
 
+    // filter menu
+    if scene.page_tracker.active_page() == 1 && input_util.was_just_pressed(Input::Option2) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+
+        scene.filter_menu.open();
+    }
+
     // flip card previews
     if input_util.was_just_pressed(Input::Special) {
         let globals = game_io.resource::<Globals>().unwrap();
@@ -506,7 +643,7 @@ fn handle_context_menu_input(scene: &mut DeckEditorScene, game_io: &mut GameIO)
                 .package(NAMESPACE, &item.card.package_id)
                 .unwrap();
 
-            package.card_properties.element as u8
+            package.card_properties.element.id()
         }),
         Sorting::Number => sort_card_items(card_slots, |item: &CardListItem| -item.count),
         Sorting::Class => sort_card_items(card_slots, |item: &CardListItem| {
@@ -530,6 +667,110 @@ fn handle_context_menu_input(scene: &mut DeckEditorScene, game_io: &mut GameIO)
     dock.update_preview();
 }
 
+fn handle_filter_menu_input(scene: &mut DeckEditorScene, game_io: &mut GameIO) {
+    let input_util = InputUtil::new(game_io);
+
+    // closing menu
+    if input_util.was_just_pressed(Input::Option2) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+        scene.filter_menu.close();
+
+        return;
+    }
+
+    let Some(selected_option) = scene.filter_menu.update(game_io, &scene.ui_input_tracker) else {
+        return;
+    };
+
+    let card_manager = &game_io.resource::<Globals>().unwrap().card_packages;
+    let pack_package =
+        |item: &CardListItem| card_manager.package_or_fallback(NAMESPACE, &item.card.package_id);
+
+    match selected_option {
+        FilterOption::Search => {
+            let event_sender = scene.event_sender.clone();
+            let edit_sender = event_sender.clone();
+
+            let interface = TextboxPrompt::new(move |search| {
+                event_sender.send(Event::FilterSearch(search)).unwrap();
+            })
+            .with_str(&scene.pack_filter.search)
+            .with_character_limit(SEARCH_LIMIT)
+            .with_filter(|grapheme| grapheme != "\n")
+            .on_edit(move |search| {
+                edit_sender
+                    .send(Event::FilterSearch(search.to_string()))
+                    .unwrap();
+            });
+
+            scene.textbox.push_interface(interface);
+            scene.textbox.open();
+            return;
+        }
+        FilterOption::Element => {
+            let mut elements: Vec<_> = (scene.pack_items())
+                .flat_map(pack_package)
+                .map(|package| package.card_properties.element)
+                .collect();
+
+            elements.sort_by_key(|element| element.id());
+            elements.dedup();
+
+            let filter = &mut scene.pack_filter;
+            filter.element = cycle_filter(&filter.element, &elements);
+        }
+        FilterOption::Damage => {
+            let ranges: Vec<_> = (0..DAMAGE_RANGES.len()).collect();
+
+            let filter = &mut scene.pack_filter;
+            filter.damage_range = cycle_filter(&filter.damage_range, &ranges);
+        }
+        FilterOption::Code => {
+            let mut codes: Vec<_> = (scene.pack_items())
+                .map(|item| item.card.code.clone())
+                .collect();
+
+            codes.sort();
+            codes.dedup();
+
+            let filter = &mut scene.pack_filter;
+            filter.code = cycle_filter(&filter.code, &codes);
+        }
+        FilterOption::Source => {
+            let sources = [PackageNamespace::BuiltIn, PackageNamespace::Local];
+
+            let filter = &mut scene.pack_filter;
+            filter.source = cycle_filter(&filter.source, &sources);
+        }
+        FilterOption::Count => {
+            let counts: Vec<_> = (1..=MIN_COUNT_LIMIT).collect();
+
+            let filter = &mut scene.pack_filter;
+            filter.min_count = cycle_filter(&filter.min_count, &counts);
+        }
+        FilterOption::Clear => {
+            scene.pack_filter = CardFilter::default();
+        }
+    }
+
+    scene.apply_pack_filter(game_io);
+    scene.update_filter_menu(game_io);
+}
+
+/// Moves to the next option, or back to no filter after the last option
+fn cycle_filter<T: PartialEq + Clone>(current: &Option<T>, options: &[T]) -> Option<T> {
+    let next_index = match current {
+        Some(value) => (options.iter())
+            .position(|option| option == value)
+            .map_or(0, |i| i + 1),
+        None => 0,
+    };
+
+    options.get(next_index).cloned()
+}
+
 fn sort_card_items<F, K>(card_slots: &mut [Option<CardListItem>], key_function: F)
 where
     F: FnMut(&CardListItem) -> K + Copy,
@@ -732,6 +973,26 @@ fn transfer_to_deck(
     let card_manager = &globals.card_packages;
     let package = card_manager.package(NAMESPACE, &card_item.card.package_id)?;
     let deck_dock = &mut scene.deck_dock;
+    let rules = &scene.deck_restrictions.rules;
+
+    // maintain server rules
+    if rules.is_package_banned(&card_item.card.package_id)
+        || !rules.is_code_allowed(&card_item.card.code)
+    {
+        return None;
+    }
+
+    let card_points = rules.card_points(&card_item.card.package_id);
+
+    if card_points > 0 {
+        let total_points: usize = (deck_dock.card_items())
+            .map(|item| rules.card_points(&item.card.package_id))
+            .sum();
+
+        if rules.is_over_budget(total_points + card_points) {
+            return None;
+        }
+    }
 
     // maintain duplicate limit requirement
     let package_count = deck_dock
@@ -739,7 +1000,7 @@ fn transfer_to_deck(
         .filter(|item| item.card.package_id == card_item.card.package_id)
         .count();
 
-    if package_count >= package.limit {
+    if package_count >= rules.copy_limit(package.limit) {
         return None;
     }
 
@@ -826,13 +1087,26 @@ fn transfer_to_pack(
         .position(|item| item.as_ref().unwrap().card == card);
 
     let Some(pack_index) = pack_index else {
-        pack_slots.push(Some(CardListItem {
-            card,
-            valid: true,
-            count: 1,
-            show_count: true,
-            is_regular: false,
-        }));
+        // cards hidden by the filter are shown again when returned to the pack
+        let hidden_items = &mut scene.hidden_pack_items;
+        let hidden_index = hidden_items.iter().position(|item| item.card == card);
+
+        let item = match hidden_index {
+            Some(index) => {
+                let mut item = hidden_items.remove(index);
+                item.count += 1;
+                item
+            }
+            None => CardListItem {
+                card,
+                valid: true,
+                count: 1,
+                show_count: true,
+                is_regular: false,
+            },
+        };
+
+        pack_slots.push(Some(item));
 
         scene
             .pack_dock
@@ -870,6 +1144,82 @@ fn transfer_to_pack_cleanup(scene: &mut DeckEditorScene, pack_index: Option<usiz
     true
 }
 
+#[derive(Default)]
+struct CardFilter {
+    /// Matched against card names and package ids, ignoring case
+    search: String,
+    element: Option<Element>,
+    /// Index into DAMAGE_RANGES
+    damage_range: Option<usize>,
+    code: Option<String>,
+    source: Option<PackageNamespace>,
+    min_count: Option<isize>,
+}
+
+impl CardFilter {
+    fn matches(&self, package: Option<&CardPackage>, item: &CardListItem) -> bool {
+        if self
+            .code
+            .as_ref()
+            .is_some_and(|code| item.card.code != *code)
+        {
+            return false;
+        }
+
+        if self.min_count.is_some_and(|count| item.count < count) {
+            return false;
+        }
+
+        let search = self.search.trim().to_lowercase();
+
+        let uses_package = !search.is_empty()
+            || self.element.is_some()
+            || self.damage_range.is_some()
+            || self.source.is_some();
+
+        if !uses_package {
+            return true;
+        }
+
+        let Some(package) = package else {
+            return false;
+        };
+
+        let properties = &package.card_properties;
+
+        if !search.is_empty() {
+            let name = properties.short_name.to_lowercase();
+            let id = package.package_info.id.as_str().to_lowercase();
+
+            if !name.contains(&search) && !id.contains(&search) {
+                return false;
+            }
+        }
+
+        if let Some(element) = self.element {
+            if properties.element != element && properties.secondary_element != element {
+                return false;
+            }
+        }
+
+        if let Some(index) = self.damage_range {
+            let (min, max) = DAMAGE_RANGES[index];
+
+            if !(min..=max).contains(&properties.damage) {
+                return false;
+            }
+        }
+
+        if let Some(source) = self.source {
+            if package.package_info.namespace != source {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 struct Dock {
     card_slots: Vec<Option<CardListItem>>,
     card_count: usize,
@@ -990,6 +1340,44 @@ impl Dock {
         None
     }
 
+    fn announce_selection(&self, game_io: &GameIO) {
+        let selected_index = self.scroll_tracker.selected_index();
+
+        let Some(card_list_item) = self.card_slots.get(selected_index) else {
+            return;
+        };
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        let Some(item) = card_list_item else {
+            let text = globals.translate("deck_editor.empty_slot");
+            globals.screen_reader.announce(&text);
+            return;
+        };
+
+        let name = globals
+            .card_packages
+            .package_or_fallback(NAMESPACE, &item.card.package_id)
+            .map(|package| package.card_properties.short_name.as_ref())
+            .unwrap_or("?????");
+
+        let count = item.count.to_string();
+        let args = [
+            ("name", name),
+            ("code", item.card.code.as_str()),
+            ("count", count.as_str()),
+        ];
+
+        let key = match (item.show_count, item.valid) {
+            (true, _) => "deck_editor.card_count",
+            (false, true) => "deck_editor.card",
+            (false, false) => "deck_editor.invalid_card",
+        };
+
+        let text = globals.localization.translate_with(key, &args);
+        globals.screen_reader.announce(&text);
+    }
+
     fn draw(
         &mut self,
         game_io: &GameIO,
@@ -1222,3 +1610,60 @@ impl CardListItem {
         label.draw(game_io, sprite_queue);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn card_item(code: &str, count: isize) -> CardListItem {
+        CardListItem {
+            card: Card {
+                package_id: PackageId::from("dev.test.cannon"),
+                code: code.to_string(),
+            },
+            valid: true,
+            count,
+            show_count: true,
+            is_regular: false,
+        }
+    }
+
+    #[test]
+    fn card_filter_matches() {
+        let mut package = CardPackage::default();
+        package.package_info.id = PackageId::from("dev.test.cannon");
+        package.card_properties.short_name = "Cannon".into();
+        package.card_properties.damage = 40;
+
+        let item = card_item("A", 2);
+
+        let mut filter = CardFilter {
+            search: String::from("cann"),
+            damage_range: Some(1),
+            code: Some(String::from("A")),
+            min_count: Some(2),
+            ..Default::default()
+        };
+
+        assert!(filter.matches(Some(&package), &item));
+
+        // missing packages only match filters that don't need a package
+        assert!(!filter.matches(None, &item));
+
+        filter.min_count = Some(3);
+        assert!(!filter.matches(Some(&package), &item));
+
+        filter.min_count = None;
+        filter.damage_range = Some(2);
+        assert!(!filter.matches(Some(&package), &item));
+    }
+
+    #[test]
+    fn cycle_filter_wraps_to_none() {
+        let options = [1, 2];
+
+        assert_eq!(cycle_filter(&None, &options), Some(1));
+        assert_eq!(cycle_filter(&Some(1), &options), Some(2));
+        assert_eq!(cycle_filter(&Some(2), &options), None);
+    }
+}
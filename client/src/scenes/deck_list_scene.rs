@@ -1,15 +1,23 @@
-use super::DeckEditorScene;
+use super::{DeckCompareScene, DeckDrawSimScene, DeckEditorScene};
 use crate::bindable::SpriteColorMode;
 use crate::packages::PackageNamespace;
 use crate::render::ui::*;
 use crate::render::*;
 use crate::resources::*;
-use crate::saves::Deck;
+use crate::saves::{Deck, DeckShareCode};
 use framework::prelude::*;
 
+/// Missing packages listed before summarizing the rest
+const MISSING_PACKAGE_LINES: usize = 3;
+const SHARE_CODE_LIMIT: usize = 4096;
+
 enum Event {
     Rename(String),
     Delete,
+    ExportCode,
+    RequestCode,
+    ImportCode(String),
+    ConfirmImport,
     CloseTextbox,
 }
 
@@ -18,6 +26,9 @@ enum DeckOption {
     Edit,
     Equip,
     ChangeName,
+    DrawSim,
+    Compare,
+    Share,
     New,
     Delete,
 }
@@ -42,6 +53,7 @@ pub struct DeckListScene {
     card_scroll_tracker: ScrollTracker,
     card_list_position: Vec2,
     context_menu: ContextMenu<DeckOption>,
+    pending_import: Option<Deck>,
     event_sender: flume::Sender<Event>,
     event_receiver: flume::Receiver<Event>,
     textbox: Textbox,
@@ -150,6 +162,7 @@ impl DeckListScene {
             card_list_position,
             context_menu: ContextMenu::new(game_io, "SELECT", Vec2::new(3.0, 50.0))
                 .with_arrow(true),
+            pending_import: None,
             event_sender,
             event_receiver,
             textbox: Textbox::new_navigation(game_io),
@@ -211,6 +224,10 @@ impl Scene for DeckListScene {
             return;
         }
 
+        if self.textbox.is_complete() {
+            self.textbox.close();
+        }
+
         self.textbox.update(game_io);
 
         handle_events(self, game_io);
@@ -325,7 +342,7 @@ fn handle_events(scene: &mut DeckListScene, game_io: &mut GameIO) {
     match scene.event_receiver.try_recv() {
         Ok(Event::Rename(name)) => {
             let deck_index = scene.deck_scroll_tracker.selected_index();
-            global_save.decks[deck_index].name = name;
+            global_save.rename_deck(deck_index, name);
             global_save.save();
 
             scene.textbox.close();
@@ -359,6 +376,120 @@ fn handle_events(scene: &mut DeckListScene, game_io: &mut GameIO) {
                 create_new_deck(scene, game_io);
             }
         }
+        Ok(Event::ExportCode) => {
+            let deck_index = scene.deck_scroll_tracker.selected_index();
+            let deck = &global_save.decks[deck_index];
+
+            let result =
+                DeckShareCode::encode(deck).and_then(|code| DeckShareCode::write(deck, &code));
+
+            let message = match result {
+                Ok(path) => format!("Saved the code for {} to {path}", deck.name),
+                Err(e) => {
+                    log::error!("{e}");
+                    format!("Failed to create a code: {e}")
+                }
+            };
+
+            scene.textbox.push_interface(TextboxMessage::new(message));
+            scene.textbox.open();
+        }
+        Ok(Event::RequestCode) => {
+            let event_sender = scene.event_sender.clone();
+            let callback = move |code: String| {
+                let event = if !code.trim().is_empty() {
+                    Event::ImportCode(code)
+                } else {
+                    Event::CloseTextbox
+                };
+
+                event_sender.send(event).unwrap();
+            };
+
+            let textbox_interface =
+                TextboxPrompt::new(callback).with_character_limit(SHARE_CODE_LIMIT);
+
+            scene.textbox.push_interface(textbox_interface);
+            scene.textbox.open();
+        }
+        Ok(Event::ImportCode(code)) => {
+            let deck = match DeckShareCode::decode(&code) {
+                Ok(deck) => deck,
+                Err(e) => {
+                    scene.textbox.push_interface(TextboxMessage::new(e));
+                    scene.textbox.open();
+                    return;
+                }
+            };
+
+            let globals = game_io.resource::<Globals>().unwrap();
+            let missing_packages = DeckShareCode::missing_packages(globals, &deck);
+            let unowned_count = DeckShareCode::unowned_card_count(game_io, &deck);
+
+            let mut lines = vec![format!("{}: {} cards", deck.name, deck.cards.len())];
+
+            if !missing_packages.is_empty() {
+                lines.push(format!("Missing Packages: {}", missing_packages.len()));
+
+                for id in missing_packages.iter().take(MISSING_PACKAGE_LINES) {
+                    lines.push(format!(" {id}"));
+                }
+
+                if missing_packages.len() > MISSING_PACKAGE_LINES {
+                    let remaining = missing_packages.len() - MISSING_PACKAGE_LINES;
+                    lines.push(format!(" and {remaining} more"));
+                }
+            }
+
+            if unowned_count > 0 {
+                lines.push(format!("{unowned_count} cards aren't owned."));
+            }
+
+            let event_sender = scene.event_sender.clone();
+            let callback = move |response| {
+                let event = if response {
+                    Event::ConfirmImport
+                } else {
+                    Event::CloseTextbox
+                };
+
+                event_sender.send(event).unwrap();
+            };
+
+            let question = format!("Import {}?", deck.name);
+            scene.pending_import = Some(deck);
+
+            scene
+                .textbox
+                .push_interface(TextboxMessage::new(lines.join("\n")));
+            scene
+                .textbox
+                .push_interface(TextboxQuestion::new(question, callback));
+            scene.textbox.open();
+        }
+        Ok(Event::ConfirmImport) => {
+            let Some(deck) = scene.pending_import.take() else {
+                return;
+            };
+
+            let deck_validity =
+                (scene.deck_restrictions).validate_deck(game_io, PackageNamespace::Local, &deck);
+
+            let global_save = &mut game_io.resource_mut::<Globals>().unwrap().global_save;
+            let card_count = deck.cards.len();
+            global_save.decks.push(deck);
+            global_save.save();
+
+            scene.deck_validities.push(deck_validity);
+
+            let total_decks = global_save.decks.len();
+            let deck_scroll_tracker = &mut scene.deck_scroll_tracker;
+            deck_scroll_tracker.set_total_items(total_decks);
+            deck_scroll_tracker.set_selected_index(total_decks - 1);
+
+            scene.card_scroll_tracker.set_total_items(card_count);
+            scene.textbox.close();
+        }
         Ok(Event::CloseTextbox) => {
             scene.textbox.close();
         }
@@ -444,17 +575,34 @@ fn handle_input(scene: &mut DeckListScene, game_io: &mut GameIO) {
 
         let options: &[(&str, DeckOption)] = if total_decks == 0 {
             &[("NEW", DeckOption::New)]
+        } else if total_decks == 1 {
+            &[
+                ("EDIT", DeckOption::Edit),
+                ("EQUIP", DeckOption::Equip),
+                ("CHG NAME", DeckOption::ChangeName),
+                ("DRAW SIM", DeckOption::DrawSim),
+                ("SHARE", DeckOption::Share),
+                ("NEW", DeckOption::New),
+                ("DELETE", DeckOption::Delete),
+            ]
         } else {
             &[
                 ("EDIT", DeckOption::Edit),
                 ("EQUIP", DeckOption::Equip),
                 ("CHG NAME", DeckOption::ChangeName),
+                ("DRAW SIM", DeckOption::DrawSim),
+                ("COMPARE", DeckOption::Compare),
+                ("SHARE", DeckOption::Share),
                 ("NEW", DeckOption::New),
                 ("DELETE", DeckOption::Delete),
             ]
         };
 
+        // taller menus start higher to stay on screen
+        let menu_y = if options.len() > 6 { 18.0 } else { 50.0 };
+
         scene.context_menu.set_options(game_io, options);
+        scene.context_menu.set_position(Vec2::new(3.0, menu_y));
 
         scene.context_menu.open();
     }
@@ -500,6 +648,35 @@ fn handle_context_menu_input(scene: &mut DeckListScene, game_io: &mut GameIO) {
             scene.textbox.push_interface(textbox_interface);
             scene.textbox.open();
         }
+        DeckOption::DrawSim => {
+            let deck_index = scene.deck_scroll_tracker.selected_index();
+
+            scene.next_scene = NextScene::new_push(DeckDrawSimScene::new(game_io, deck_index))
+                .with_transition(crate::transitions::new_sub_scene(game_io));
+        }
+        DeckOption::Compare => {
+            let deck_index = scene.deck_scroll_tracker.selected_index();
+
+            scene.next_scene = NextScene::new_push(DeckCompareScene::new(game_io, deck_index))
+                .with_transition(crate::transitions::new_sub_scene(game_io));
+        }
+        DeckOption::Share => {
+            let event_sender = scene.event_sender.clone();
+            let options = &["Share Code", "Enter Code", "Cancel"];
+
+            let textbox_interface = TextboxQuiz::new(options, move |selection| {
+                let event = match selection {
+                    0 => Event::ExportCode,
+                    1 => Event::RequestCode,
+                    _ => Event::CloseTextbox,
+                };
+
+                event_sender.send(event).unwrap();
+            });
+
+            scene.textbox.push_interface(textbox_interface);
+            scene.textbox.open();
+        }
         DeckOption::New => create_new_deck(scene, game_io),
         DeckOption::Delete => {
             let event_sender = scene.event_sender.clone();
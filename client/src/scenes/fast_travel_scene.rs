@@ -0,0 +1,166 @@
+use crate::bindable::SpriteColorMode;
+use crate::overworld::OverworldEvent;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::FastTravelPoint;
+use framework::prelude::*;
+
+const LIST_WIDTH: f32 = 112.0;
+
+/// Lists warps visited on the connected server, confirm asks the server to move the player
+pub struct FastTravelScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    points: Vec<FastTravelPoint>,
+    current_map_path: Option<String>,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    event_sender: flume::Sender<OverworldEvent>,
+    next_scene: NextScene,
+}
+
+impl FastTravelScene {
+    pub fn new(
+        game_io: &GameIO,
+        server_address: &str,
+        current_map_path: Option<String>,
+        event_sender: flume::Sender<OverworldEvent>,
+    ) -> Box<Self> {
+        let mut list = ListPanel::new(game_io, "WARPS", LIST_WIDTH);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let points = (globals.global_save.fast_travel_log(server_address))
+            .map(|log| log.points.clone())
+            .unwrap_or_default();
+
+        list.set_total_items(points.len());
+
+        // start on a warp in the current area
+        let current_index = (current_map_path.as_ref())
+            .and_then(|map_path| points.iter().position(|p| p.map_path == *map_path));
+
+        if let Some(index) = current_index {
+            list.set_selected_index(index);
+        }
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            points,
+            current_map_path,
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            event_sender,
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn describe(&self, point: &FastTravelPoint) -> String {
+        let mut lines = vec![point.name.clone(), point.area_name.clone()];
+
+        if self.current_map_path.as_ref() == Some(&point.map_path) {
+            lines.push(String::new());
+            lines.push(String::from("Current area"));
+        }
+
+        lines.join("\n")
+    }
+
+    fn leave(&mut self, game_io: &GameIO) {
+        let transition = crate::transitions::new_scene_pop(game_io);
+        self.next_scene = NextScene::new_pop().with_transition(transition);
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+            self.leave(game_io);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        let Some(point) = self.points.get(self.list.selected_index()) else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            return;
+        };
+
+        // the server decides if the player is moved
+        let event = OverworldEvent::FastTravel {
+            map_path: point.map_path.clone(),
+            object_id: point.object_id,
+        };
+
+        let _ = self.event_sender.send(event);
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+        self.leave(game_io);
+    }
+}
+
+impl Scene for FastTravelScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let point = &self.points[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &point.name);
+            },
+        );
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match self.points.get(self.list.selected_index()) {
+            Some(point) => self.describe(point),
+            None => String::from("Visited warps appear here."),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("FAST TRAVEL").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -113,6 +113,10 @@ impl Scene for InitialConnectScene {
                     | ServerPacket::InitiateNetplay { .. }
                     | ServerPacket::Restrictions { .. }
                     | ServerPacket::AddCard { .. }
+                    | ServerPacket::CardPack { .. }
+                    | ServerPacket::QuestUpdate { .. }
+                    | ServerPacket::QuestProgress { .. }
+                    | ServerPacket::QuestRemove { .. }
                     | ServerPacket::AddBlock { .. }
                     | ServerPacket::EnablePlayableCharacter { .. } => {
                         self.deferred_packets.push(packet);
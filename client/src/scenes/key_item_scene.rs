@@ -73,7 +73,7 @@ impl KeyItemsScene {
             key_items.len()
         });
 
-        v_scroll_tracker.define_cursor(cursor_left_start, LINE_HEIGHT);
+        v_scroll_tracker.define_text_cursor(game_io, cursor_left_start, LINE_HEIGHT);
         v_scroll_tracker.define_scrollbar(
             scrollable_frame.scroll_start(),
             scrollable_frame.scroll_end(),
@@ -95,10 +95,10 @@ impl KeyItemsScene {
 
         let (mut interface, _) = TextboxDoorstop::new();
 
-        interface = interface.with_str(if let Some(item) = key_items.first() {
-            &item.description
+        interface = interface.with_string(if let Some(item) = key_items.first() {
+            item.description_with_metadata()
         } else {
-            "You have no Key Items."
+            String::from("You have no Key Items.")
         });
 
         textbox.push_interface(interface);
@@ -182,7 +182,7 @@ impl Scene for KeyItemsScene {
             // update textbox
             let item = &self.key_items[v_index * 2 + h_index];
             let (mut interface, _) = TextboxDoorstop::new();
-            interface = interface.with_str(&item.description);
+            interface = interface.with_string(item.description_with_metadata());
 
             self.textbox.push_interface(interface);
             self.textbox.advance_interface(game_io);
@@ -195,8 +195,9 @@ impl Scene for KeyItemsScene {
             self.cursor_right_start
         };
 
+        let line_height = self.v_scroll_tracker.cursor_multiplier();
         self.v_scroll_tracker
-            .define_cursor(cursor_start, LINE_HEIGHT);
+            .define_cursor(cursor_start, line_height);
     }
 
     fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
@@ -234,7 +235,7 @@ impl Scene for KeyItemsScene {
                 text_style.draw(game_io, &mut sprite_queue, &item.name);
 
                 if i % 2 == 1 {
-                    y_offset += LINE_HEIGHT;
+                    y_offset += self.v_scroll_tracker.cursor_multiplier();
                 }
             }
         } else {
@@ -0,0 +1,342 @@
+use crate::bindable::SpriteColorMode;
+use crate::packages::PackageNamespace;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::Loadout;
+use framework::prelude::*;
+
+const LIST_WIDTH: f32 = 112.0;
+
+enum Event {
+    Create(String),
+    Rename(usize, String),
+    Delete(usize),
+}
+
+#[derive(Clone, Copy)]
+enum LoadoutOption {
+    Equip,
+    Overwrite,
+    Rename,
+    Delete,
+}
+
+pub struct LoadoutScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    list: ListPanel,
+    context_menu: ContextMenu<LoadoutOption>,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl LoadoutScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let list = ListPanel::new(game_io, "LOADOUTS", LIST_WIDTH);
+
+        let context_menu_position = Vec2::new(list.details_bounds().x, 80.0);
+        let context_menu = ContextMenu::new(game_io, "LOADOUT", context_menu_position)
+            .with_options(
+                game_io,
+                &[
+                    ("Equip", LoadoutOption::Equip),
+                    ("Overwrite", LoadoutOption::Overwrite),
+                    ("Rename", LoadoutOption::Rename),
+                    ("Delete", LoadoutOption::Delete),
+                ],
+            );
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            list,
+            context_menu,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        });
+
+        scene.refresh_list(game_io);
+        scene
+    }
+
+    fn refresh_list(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        // the last row creates a new loadout
+        let total_loadouts = globals.global_save.loadouts.len();
+        self.list.set_total_items(total_loadouts + 1);
+    }
+
+    fn describe(game_io: &GameIO, loadout: &Loadout) -> String {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let global_save = &globals.global_save;
+
+        let character_name = (globals.player_packages)
+            .package(PackageNamespace::Local, &loadout.character)
+            .map(|package| package.name.as_str())
+            .unwrap_or("Missing");
+
+        let deck_name = match (&loadout.deck_name, loadout.deck(global_save)) {
+            (Some(name), Some(_)) => name.clone(),
+            (Some(name), None) => format!("{name} (Missing)"),
+            (None, _) => String::from("None"),
+        };
+
+        let mut lines = vec![
+            format!("Character: {character_name}"),
+            format!("Folder: {deck_name}"),
+            format!("Blocks: {}", loadout.blocks.len()),
+            format!("Drives: {}", loadout.drive_parts.len()),
+        ];
+
+        if Loadout::current(global_save).with_name(loadout.name.clone()) == *loadout {
+            lines.push(String::from("Equipped"));
+        }
+
+        lines.join("\n")
+    }
+
+    fn request_name(&mut self, create: impl FnOnce(String) -> Event + 'static, name: &str) {
+        let event_sender = self.event_sender.clone();
+        let callback = move |name: String| {
+            if !name.is_empty() {
+                let _ = event_sender.send(create(name));
+            }
+        };
+
+        let interface = TextboxPrompt::new(callback)
+            .with_str(name)
+            .with_character_limit(Loadout::NAME_MAX_LEN);
+
+        self.textbox.push_interface(interface);
+        self.textbox.open();
+    }
+
+    fn handle_events(&mut self, game_io: &mut GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            let globals = game_io.resource_mut::<Globals>().unwrap();
+            let global_save = &mut globals.global_save;
+
+            match event {
+                Event::Create(name) => {
+                    let loadout = Loadout::current(global_save).with_name(name);
+                    global_save.loadouts.push(loadout);
+                    global_save.save();
+
+                    let index = global_save.loadouts.len() - 1;
+                    self.refresh_list(game_io);
+                    self.list.set_selected_index(index);
+                }
+                Event::Rename(index, name) => {
+                    if let Some(loadout) = global_save.loadouts.get_mut(index) {
+                        loadout.name = name;
+                        global_save.save();
+                    }
+                }
+                Event::Delete(index) => {
+                    if index < global_save.loadouts.len() {
+                        global_save.loadouts.remove(index);
+                        global_save.save();
+                    }
+
+                    self.refresh_list(game_io);
+                }
+            }
+        }
+    }
+
+    fn handle_context_menu_input(&mut self, game_io: &mut GameIO) {
+        let Some(option) = self.context_menu.update(game_io, &self.ui_input_tracker) else {
+            return;
+        };
+
+        self.context_menu.close();
+
+        let index = self.list.selected_index();
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+        let global_save = &mut globals.global_save;
+
+        let Some(loadout) = global_save.loadouts.get(index).cloned() else {
+            return;
+        };
+
+        match option {
+            LoadoutOption::Equip => {
+                let character_installed = (globals.player_packages)
+                    .package(PackageNamespace::Local, &loadout.character)
+                    .is_some();
+
+                if !character_installed {
+                    globals.audio.play_sound(&globals.sfx.cursor_error);
+
+                    let message = String::from("The character used by this loadout is missing.");
+                    self.textbox.push_interface(TextboxMessage::new(message));
+                    self.textbox.open();
+                    return;
+                }
+
+                let global_save = &mut globals.global_save;
+                let folder_found = loadout.apply(global_save);
+                global_save.save();
+
+                let message = if folder_found {
+                    format!("Equipped {}.", loadout.name)
+                } else {
+                    format!(
+                        "Equipped {}, but its folder is missing. The selected folder was kept.",
+                        loadout.name
+                    )
+                };
+
+                self.textbox.push_interface(TextboxMessage::new(message));
+                self.textbox.open();
+            }
+            LoadoutOption::Overwrite => {
+                let current = Loadout::current(global_save).with_name(loadout.name.clone());
+                global_save.loadouts[index] = current;
+                global_save.save();
+
+                let message = format!("Saved the current build to {}.", loadout.name);
+                self.textbox.push_interface(TextboxMessage::new(message));
+                self.textbox.open();
+            }
+            LoadoutOption::Rename => {
+                self.request_name(move |name| Event::Rename(index, name), &loadout.name);
+            }
+            LoadoutOption::Delete => {
+                let event_sender = self.event_sender.clone();
+                let question = format!("Delete {}?", loadout.name);
+                let interface = TextboxQuestion::new(question, move |yes| {
+                    if yes {
+                        let _ = event_sender.send(Event::Delete(index));
+                    }
+                });
+
+                self.textbox.push_interface(interface);
+                self.textbox.open();
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &mut GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        if self.context_menu.is_open() {
+            self.handle_context_menu_input(game_io);
+            return;
+        }
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+
+        let index = self.list.selected_index();
+
+        if index < globals.global_save.loadouts.len() {
+            self.context_menu.open();
+        } else {
+            let name = format!("Build{}", index + 1);
+            self.request_name(Event::Create, &name);
+        }
+    }
+}
+
+impl Scene for LoadoutScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if self.textbox.is_complete() {
+            self.textbox.close();
+        }
+
+        self.textbox.update(game_io);
+        self.handle_events(game_io);
+
+        if game_io.is_in_transition() || self.textbox.is_open() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list, the last row creates a loadout
+        let globals = game_io.resource::<Globals>().unwrap();
+        let loadouts = &globals.global_save.loadouts;
+
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let label = loadouts.get(index).map(|loadout| loadout.name.as_str());
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, label.unwrap_or("NEW"));
+            },
+        );
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match loadouts.get(self.list.selected_index()) {
+            Some(loadout) => Self::describe(game_io, loadout),
+            None => String::from("Save the current character, folder, blocks, and drives."),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw context menu
+        self.context_menu.draw(game_io, &mut sprite_queue);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("LOADOUTS").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -139,6 +139,13 @@ impl Scene for MainMenuScene {
         // can't be on a server if the player is viewing the main menu
         let globals = game_io.resource_mut::<Globals>().unwrap();
         globals.connected_to_server = false;
+        globals.connected_server_address = None;
+        globals.screenshot_metadata.area = None;
+
+        // drop server preferences
+        globals
+            .audio
+            .set_music_volume(globals.config.music_volume());
 
         // update the background if it's necessary
         if self.update_bg_on_enter {
@@ -1,47 +1,81 @@
+mod achievements_scene;
 mod battle_init_scene;
 mod battle_scene;
 mod battle_select_scene;
+mod battle_test_scene;
 mod blocks_scene;
 mod boot_scene;
+mod card_pack_scene;
 mod character_scene;
 mod character_select_scene;
 mod config_scene;
+mod deck_compare_scene;
+mod deck_draw_sim_scene;
 mod deck_editor_scene;
 mod deck_list_scene;
+mod fast_travel_scene;
 mod initial_connect_scene;
 mod key_item_scene;
 mod library_scene;
+mod loadout_scene;
 mod main_menu_scene;
 mod manage_switch_drive_scene;
 mod netplay_init_scene;
+mod notifications_scene;
 mod overworld_online_scene;
 mod package_scene;
 mod package_updates_scene;
 mod packages_scene;
+mod quest_journal_scene;
+mod ranked_season_scene;
 mod resource_order_scene;
+mod save_backups_scene;
+mod save_import_scene;
+mod save_profiles_scene;
+mod screenshot_gallery_scene;
+mod server_cache_scene;
 mod server_edit_scene;
 mod server_list_scene;
+mod touch_layout_scene;
+mod trade_scene;
 
+pub use achievements_scene::*;
 pub use battle_init_scene::*;
 pub use battle_scene::*;
 pub use battle_select_scene::*;
+pub use battle_test_scene::*;
 pub use blocks_scene::*;
 pub use boot_scene::*;
+pub use card_pack_scene::*;
 pub use character_scene::*;
 pub use character_select_scene::*;
 pub use config_scene::*;
+pub use deck_compare_scene::*;
+pub use deck_draw_sim_scene::*;
 pub use deck_editor_scene::*;
 pub use deck_list_scene::*;
+pub use fast_travel_scene::*;
 pub use initial_connect_scene::*;
 pub use key_item_scene::*;
 pub use library_scene::*;
+pub use loadout_scene::*;
 pub use main_menu_scene::*;
 pub use manage_switch_drive_scene::*;
 pub use netplay_init_scene::*;
+pub use notifications_scene::*;
 pub use overworld_online_scene::*;
 pub use package_scene::*;
 pub use package_updates_scene::*;
 pub use packages_scene::*;
+pub use quest_journal_scene::*;
+pub use ranked_season_scene::*;
 pub use resource_order_scene::*;
+pub use save_backups_scene::*;
+pub use save_import_scene::*;
+pub use save_profiles_scene::*;
+pub use screenshot_gallery_scene::*;
+pub use server_cache_scene::*;
 pub use server_edit_scene::*;
 pub use server_list_scene::*;
+pub use touch_layout_scene::*;
+pub use trade_scene::*;
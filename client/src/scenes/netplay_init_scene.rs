@@ -1,5 +1,5 @@
 use super::BattleScene;
-use crate::battle::{BattleProps, BattleStatisticsCallback, PlayerSetup};
+use crate::battle::{BattleProps, BattleStatisticsCallback, NetplayInputReceiver, PlayerSetup};
 use crate::bindable::SpriteColorMode;
 use crate::packages::{PackageId, PackageNamespace};
 use crate::render::*;
@@ -9,12 +9,14 @@ use crate::transitions::HoldColorScene;
 use framework::prelude::*;
 use futures::Future;
 use packets::structures::{
-    Emotion, FileHash, InstalledBlock, InstalledSwitchDrive, PackageCategory, RemotePlayerInfo,
+    BattleRole, BattleTimers, Emotion, EncounterData, FileHash, InstalledBlock,
+    InstalledSwitchDrive, PackageCategory, RemotePlayerInfo,
 };
-use packets::{NetplayBufferItem, NetplayPacket, NetplaySignal, SERVER_TICK_RATE};
+use packets::{NetplayPacket, NetplaySignal, MAX_NETPLAY_PLAYERS, SERVER_TICK_RATE};
 use rand::rngs::OsRng;
 use rand::RngCore;
 use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
 use std::pin::Pin;
 
 const MAX_FALLBACK_SILENCE: Duration = Duration::from_secs(3);
@@ -22,11 +24,12 @@ const MAX_FALLBACK_SILENCE: Duration = Duration::from_secs(3);
 pub struct NetplayProps {
     pub background: Option<Background>,
     pub encounter_package: Option<(PackageNamespace, PackageId)>,
-    pub data: Option<String>,
+    pub data: Option<EncounterData>,
     pub health: i32,
     pub base_health: i32,
     pub emotion: Emotion,
     pub remote_players: Vec<RemotePlayerInfo>,
+    pub role: BattleRole,
     pub fallback_address: String,
     pub statistics_callback: Option<BattleStatisticsCallback>,
 }
@@ -51,6 +54,9 @@ struct RemotePlayerConnection {
     deck: Deck,
     blocks: Vec<InstalledBlock>,
     drives: Vec<InstalledSwitchDrive>,
+    timers: BattleTimers,
+    lockstep: bool,
+    role: BattleRole,
     load_map: HashMap<FileHash, PackageCategory>,
     requested_packages: Option<Vec<FileHash>>,
     ready_for_packages: bool,
@@ -59,6 +65,7 @@ struct RemotePlayerConnection {
     send: Option<NetplayPacketSender>,
     receiver: Option<NetplayPacketReceiver>,
     buffer: PlayerInputBuffer,
+    input_receiver: NetplayInputReceiver,
 }
 
 pub struct NetplayInitScene {
@@ -66,8 +73,9 @@ pub struct NetplayInitScene {
     local_health: i32,
     local_base_health: i32,
     local_emotion: Emotion,
+    local_role: BattleRole,
     encounter_package: Option<(PackageNamespace, PackageId)>,
-    data: Option<String>,
+    data: Option<EncounterData>,
     background: Option<Background>,
     statistics_callback: Option<BattleStatisticsCallback>,
     last_heartbeat: Instant,
@@ -95,6 +103,7 @@ impl NetplayInitScene {
             base_health,
             emotion,
             remote_players,
+            role,
             fallback_address,
             statistics_callback,
         } = props;
@@ -103,28 +112,51 @@ impl NetplayInitScene {
 
         log::debug!("Assigned player index {}", local_index);
 
+        let too_many_players = remote_players.len() >= MAX_NETPLAY_PLAYERS;
+
+        if too_many_players {
+            log::error!(
+                "Received {} remote players, netplay supports up to {MAX_NETPLAY_PLAYERS} players",
+                remote_players.len()
+            );
+        }
+
         let globals = game_io.resource::<Globals>().unwrap();
         let network = &globals.network;
 
         let remote_index_map: Vec<_> = remote_players.iter().map(|info| info.index).collect();
-        let total_remote = remote_players.len();
 
         let remote_futures: Vec<_> = remote_players
             .iter()
-            .map(|remote_player| network.subscribe_to_netplay(remote_player.address.to_string()))
-            .chain(std::iter::once(
-                network.subscribe_to_netplay(fallback_address),
-            ))
+            .map(|remote_player| {
+                let candidate_futures = candidate_addresses(remote_player)
+                    .into_iter()
+                    .map(|address| network.subscribe_to_netplay(address.to_string()));
+
+                futures::future::join_all(candidate_futures)
+            })
             .collect();
 
+        let fallback_future = network.subscribe_to_netplay(fallback_address);
+
         let (event_sender, event_receiver) = flume::unbounded();
 
         let communication_future = async move {
             let results = futures::future::join_all(remote_futures).await;
-            let mut senders_and_receivers: Vec<_> = results.into_iter().flatten().collect();
-            let fallback_sender_receiver = senders_and_receivers.pop().unwrap();
+            let candidate_channels: Vec<Vec<_>> = results
+                .into_iter()
+                .map(|candidates| candidates.into_iter().flatten().collect())
+                .collect();
+
+            let Some(fallback_sender_receiver) = fallback_future.await else {
+                let _ = event_sender.send(Event::AddressesFailed);
+                return;
+            };
 
-            if senders_and_receivers.len() < total_remote {
+            if candidate_channels
+                .iter()
+                .any(|channels| channels.is_empty())
+            {
                 log::error!("Server sent an invalid address for a remote player, using fallback");
 
                 let _ = event_sender.send(Event::Fallback {
@@ -133,19 +165,24 @@ impl NetplayInitScene {
                 return;
             }
 
-            let success = punch_holes(
+            let selected_candidates = punch_holes(
                 local_index,
                 &remote_index_map,
-                &senders_and_receivers,
+                &candidate_channels,
                 &fallback_sender_receiver,
             )
             .await;
 
-            let event = if success {
+            let event = if let Some(selected_candidates) = selected_candidates {
                 log::debug!("Hole punching successful");
-                Event::ResolvedAddresses {
-                    players: senders_and_receivers,
-                }
+
+                let players = candidate_channels
+                    .into_iter()
+                    .zip(selected_candidates)
+                    .map(|(mut channels, index)| channels.swap_remove(index))
+                    .collect();
+
+                Event::ResolvedAddresses { players }
             } else {
                 log::debug!("Hole punching failed");
                 Event::Fallback {
@@ -168,6 +205,9 @@ impl NetplayInitScene {
                 deck: Deck::default(),
                 blocks: Vec::new(),
                 drives: Vec::new(),
+                timers: BattleTimers::default(),
+                lockstep: false,
+                role: BattleRole::Player,
                 load_map: HashMap::new(),
                 requested_packages: None,
                 ready_for_packages: false,
@@ -176,6 +216,7 @@ impl NetplayInitScene {
                 send: None,
                 receiver: None,
                 buffer: PlayerInputBuffer::new_with_delay(INPUT_DELAY),
+                input_receiver: NetplayInputReceiver::default(),
             })
             .collect();
 
@@ -184,12 +225,13 @@ impl NetplayInitScene {
             local_health: health,
             local_base_health: base_health,
             local_emotion: emotion,
+            local_role: role,
             encounter_package,
             data,
             background,
             statistics_callback,
             last_heartbeat: game_io.frame_start_instant(),
-            failed: false,
+            failed: too_many_players,
             seed: 0,
             missing_packages: HashSet::new(),
             player_connections: remote_player_connections,
@@ -309,6 +351,9 @@ impl NetplayInitScene {
                 regular_card,
                 blocks,
                 drives,
+                timers,
+                lockstep,
+                role,
                 ..
             } => {
                 connection.player_package = player_package;
@@ -320,6 +365,9 @@ impl NetplayInitScene {
                     .collect();
                 connection.blocks = blocks;
                 connection.drives = drives;
+                connection.timers = timers;
+                connection.lockstep = lockstep;
+                connection.role = role;
             }
             NetplayPacket::PackageList { index, packages } => {
                 connection.received_package_list = true;
@@ -417,12 +465,23 @@ impl NetplayInitScene {
                 self.seed = self.seed.max(seed);
                 connection.ready = true;
             }
-            NetplayPacket::Buffer { data, .. } => {
-                if data.signals.contains(&NetplaySignal::Disconnect) {
-                    self.failed = true;
-                }
+            NetplayPacket::Buffer {
+                frame,
+                data,
+                redundant_data,
+                ..
+            } => {
+                let received = connection.buffer.len();
+                let ready =
+                    (connection.input_receiver).receive(received, frame, data, redundant_data);
 
-                connection.buffer.push_last(data);
+                for data in ready {
+                    if data.signals.contains(&NetplaySignal::Disconnect) {
+                        self.failed = true;
+                    }
+
+                    connection.buffer.push_last(data);
+                }
             }
         }
     }
@@ -513,6 +572,7 @@ impl NetplayInitScene {
             .collect();
         let blocks = player_setup.blocks.clone();
         let drives = player_setup.drives.clone();
+        let lockstep = globals.config.netplay_lockstep;
 
         self.broadcast(NetplayPacket::PlayerSetup {
             index: self.local_index,
@@ -522,6 +582,9 @@ impl NetplayInitScene {
             regular_card: player_setup.deck.regular_index,
             blocks,
             drives,
+            timers: player_setup.timers,
+            lockstep,
+            role: self.local_role,
         })
     }
 
@@ -592,17 +655,25 @@ impl NetplayInitScene {
         }
     }
 
+    /// Boss battles need an encounter package to define the boss, and only one boss
+    fn agreed_on_roles(&self) -> bool {
+        let remote_roles = self.player_connections.iter().map(|c| c.role);
+        let boss_count = std::iter::once(self.local_role)
+            .chain(remote_roles)
+            .filter(|role| *role == BattleRole::Boss)
+            .count();
+
+        match boss_count {
+            0 => true,
+            1 => self.encounter_package.is_some(),
+            _ => false,
+        }
+    }
+
     fn handle_transition(&mut self, game_io: &mut GameIO) {
         if self.failed {
             // let other player's know we're giving up on them
-            self.broadcast(NetplayPacket::Buffer {
-                index: self.local_index,
-                data: NetplayBufferItem {
-                    pressed: Vec::new(),
-                    signals: vec![NetplaySignal::Disconnect],
-                },
-                buffer_sizes: Vec::new(),
-            });
+            self.broadcast(NetplayPacket::new_disconnect_signal(self.local_index));
 
             // make sure the statistics callback gets called
             if let Some(callback) = self.statistics_callback.take() {
@@ -616,6 +687,12 @@ impl NetplayInitScene {
         }
 
         if self.all_ready() {
+            if !self.agreed_on_roles() {
+                log::error!("Players failed to agree on a single boss");
+                self.failed = true;
+                return;
+            }
+
             let globals = game_io.resource::<Globals>().unwrap();
 
             // clean up zips
@@ -640,9 +717,27 @@ impl NetplayInitScene {
             local_setup.health = self.local_health;
             local_setup.base_health = self.local_base_health;
             local_setup.emotion = self.local_emotion.clone();
+            local_setup.role = self.local_role;
+
+            props.lockstep = agree_on_lockstep(
+                globals.config.netplay_lockstep,
+                self.player_connections.iter().map(|c| c.lockstep),
+            );
+
+            let input_delay = props.input_delay();
+            let local_setup = &mut props.player_setups[0];
+
+            if props.lockstep {
+                local_setup.buffer = PlayerInputBuffer::new_with_delay(input_delay);
+            }
 
             // setup other players
             for mut connection in std::mem::take(&mut self.player_connections) {
+                if props.lockstep {
+                    // inputs sent with the larger delay are held by the receiver until now
+                    connection.buffer = PlayerInputBuffer::new_with_delay(input_delay);
+                }
+
                 let namespace = PackageNamespace::Netplay(connection.index as u8);
                 let package = globals
                     .player_packages
@@ -669,6 +764,10 @@ impl NetplayInitScene {
                     index: connection.index,
                     local: false,
                     buffer: connection.buffer,
+                    timers: connection.timers,
+                    role: connection.role,
+                    input_receiver: connection.input_receiver,
+                    local_input: None,
                 });
 
                 if let Some(send) = connection.send.take() {
@@ -771,35 +870,66 @@ impl Scene for NetplayInitScene {
     }
 }
 
+/// Addresses to attempt connecting to in order of preference,
+/// addresses reported by the player are usually more direct than the address seen by the server
+fn candidate_addresses(remote_player: &RemotePlayerInfo) -> Vec<SocketAddr> {
+    let mut addresses = Vec::new();
+
+    let address_iter = (remote_player.candidates.iter())
+        .chain(std::iter::once(&remote_player.address))
+        .cloned();
+
+    for address in address_iter {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    addresses
+}
+
+/// Returns the selected candidate for each remote player, or None if we should use the fallback
 async fn punch_holes(
     local_index: usize,
     remote_index_map: &[usize],
-    senders_and_receivers: &[(NetplayPacketSender, NetplayPacketReceiver)],
+    candidate_channels: &[Vec<(NetplayPacketSender, NetplayPacketReceiver)>],
     fallback_sender_receiver: &(NetplayPacketSender, NetplayPacketReceiver),
-) -> bool {
-    use futures::future::FutureExt;
+) -> Option<Vec<usize>> {
+    use futures::future::{Fuse, FusedFuture, FutureExt};
     use futures::StreamExt;
 
-    for (send, _) in senders_and_receivers {
+    for (send, _) in candidate_channels.iter().flatten() {
         send(NetplayPacket::Hello { index: local_index });
     }
 
     // expecting the first message from everyone to be Hello and the second is a HelloAck
-    let hello_streams = senders_and_receivers.iter().map(|(_, receiver)| {
-        Box::pin(
-            receiver
-                .stream()
-                .skip_while(|packet| {
-                    // skip non hello packets as those are leftovers from a previous match
-                    let out = !matches!(packet, NetplayPacket::Hello { .. });
-                    async move { out }
-                })
-                .take(2),
-        )
-    });
+    let hello_streams = candidate_channels
+        .iter()
+        .enumerate()
+        .flat_map(|(remote, channels)| {
+            channels
+                .iter()
+                .enumerate()
+                .map(move |(candidate, (_, receiver))| (remote, candidate, receiver))
+        })
+        .map(|(remote, candidate, receiver)| {
+            Box::pin(
+                receiver
+                    .stream()
+                    .skip_while(|packet| {
+                        // skip non hello packets as those are leftovers from a previous match
+                        let out = !matches!(packet, NetplayPacket::Hello { .. });
+                        async move { out }
+                    })
+                    .take(2)
+                    .map(move |packet| (remote, candidate, packet)),
+            )
+        });
 
     let mut hello_stream = futures::stream::select_all(hello_streams);
-    let mut total_responses = 0;
+
+    // the most preferred candidate that responded for each remote player
+    let mut selected_candidates: Vec<Option<usize>> = vec![None; candidate_channels.len()];
 
     // if we receive anything from the fallback future we'll switch to it
     let fallback_stream = fallback_sender_receiver.1.stream().skip_while(|packet| {
@@ -812,28 +942,42 @@ async fn punch_holes(
     let timer = async_sleep(Duration::from_secs(2)).fuse();
     futures::pin_mut!(fallback_stream, timer);
 
+    // once everyone has responded, give preferred candidates a moment to catch up
+    let mut grace_timer = Fuse::<Pin<Box<dyn Future<Output = ()>>>>::terminated();
+
     loop {
         futures::select! {
-            result = hello_stream.select_next_some() => {
+            (remote, candidate, result) = hello_stream.select_next_some() => {
                 match result {
                     NetplayPacket::Hello { index } => {
                         log::debug!("Received Hello");
 
-                        if let Some(slice_index) = remote_index_map.iter().position(|i| *i == index) {
+                        if remote_index_map.get(remote) == Some(&index) {
                             log::debug!("Sending HelloAck");
 
-                            let send = &senders_and_receivers[slice_index].0;
+                            let send = &candidate_channels[remote][candidate].0;
                             send(NetplayPacket::HelloAck { index: local_index });
                         }
                     }
                     NetplayPacket::HelloAck {..} => {
                         log::debug!("Received HelloAck");
 
-                        total_responses += 1;
+                        let selected = &mut selected_candidates[remote];
 
-                        if total_responses == senders_and_receivers.len() {
+                        if selected.map_or(true, |selected| candidate < selected) {
+                            *selected = Some(candidate);
+                        }
+
+                        if selected_candidates.iter().all(|selected| *selected == Some(0)) {
+                            // everyone responded through their most preferred candidate
+                            return selected_candidates.into_iter().collect();
+                        }
+
+                        if grace_timer.is_terminated() && selected_candidates.iter().all(Option::is_some) {
                             // received a response from everyone, looks like we all support hole punching
-                            return true;
+                            let sleep: Pin<Box<dyn Future<Output = ()>>> =
+                                Box::pin(async_sleep(Duration::from_millis(250)));
+                            grace_timer = sleep.fuse();
                         }
                     }
                     packet => {
@@ -844,13 +988,21 @@ async fn punch_holes(
                     }
                 }
             }
+            _ = grace_timer => {
+                return selected_candidates.into_iter().collect();
+            }
             result = fallback_stream.select_next_some() => {
                 if let NetplayPacket::Hello { .. } = result {
                     log::debug!("Received Hello through fallback channel");
-                    return false;
+                    return None;
                 }
             }
             _ = timer => {
+                if selected_candidates.iter().all(Option::is_some) {
+                    // preferred candidates never responded, settle for what we have
+                    return selected_candidates.into_iter().collect();
+                }
+
                 log::debug!("Hole punch timer exhausted");
 
                 // out of time, we'll assume hole punching failed
@@ -860,8 +1012,26 @@ async fn punch_holes(
                     index: local_index,
                 });
 
-                return false;
+                return None;
             }
         }
     }
 }
+
+/// Lockstep is only used if everyone opted in
+fn agree_on_lockstep(local: bool, remotes: impl IntoIterator<Item = bool>) -> bool {
+    local && remotes.into_iter().all(|lockstep| lockstep)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lockstep_requires_everyone() {
+        assert!(agree_on_lockstep(true, [true, true]));
+        assert!(!agree_on_lockstep(false, [true, true]));
+        assert!(!agree_on_lockstep(true, [true, false]));
+        assert!(agree_on_lockstep(true, []));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::bindable::SpriteColorMode;
+use crate::format_util::format_timestamp;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+
+const LIST_WIDTH: f32 = 112.0;
+
+/// Lists recent notifications, newest first
+pub struct NotificationsScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    notifications: Vec<Notification>,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    next_scene: NextScene,
+}
+
+impl NotificationsScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let mut list = ListPanel::new(game_io, "RECENT", LIST_WIDTH);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let notification_center = &globals.notification_center;
+        let mut notifications = notification_center.notifications();
+        notifications.reverse();
+        notification_center.mark_read();
+
+        list.set_total_items(notifications.len());
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            notifications,
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+    }
+}
+
+impl Scene for NotificationsScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let notification = &self.notifications[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, notification.kind.label());
+            },
+        );
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match self.notifications.get(self.list.selected_index()) {
+            Some(notification) => {
+                format!(
+                    "{}\n\n{}",
+                    notification.text,
+                    format_timestamp(notification.received_at)
+                )
+            }
+            None => String::from("Downloads, updates, and server messages appear here."),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("NOTIFICATIONS").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
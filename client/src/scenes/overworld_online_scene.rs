@@ -1,30 +1,46 @@
 use super::{
-    InitialConnectScene, NetplayInitScene, NetplayProps, PackageScene, ServerEditProp,
-    ServerEditScene,
+    CardPackScene, FastTravelScene, InitialConnectScene, NetplayInitScene, NetplayProps,
+    PackageScene, QuestJournalScene, RankedSeasonScene, ServerEditProp, ServerEditScene,
+    TradeAction, TradeScene, TradeStatus,
 };
-use crate::battle::BattleProps;
+use crate::battle::{BattleProps, PlayerSetup};
 use crate::bindable::SpriteColorMode;
 use crate::overworld::components::*;
 use crate::overworld::*;
 use crate::packages::{PackageId, PackageNamespace};
 use crate::render::ui::{
-    PackageListing, TextStyle, TextboxDoorstop, TextboxDoorstopRemover, TextboxInterface,
-    TextboxMessage, TextboxPrompt, TextboxQuestion, TextboxQuiz,
+    EncounterPreviewCard, FontName, PackageListing, TextStyle, TextboxDoorstop,
+    TextboxDoorstopRemover, TextboxInterface, TextboxMessage, TextboxPrompt, TextboxQuestion,
+    TextboxQuiz,
 };
 use crate::render::{AnimatorLoopMode, SpriteColorQueue};
 use crate::resources::*;
-use crate::saves::{BlockGrid, Card};
+use crate::saves::{AchievementSource, BlockGrid, Card, CardReward, Deck, FastTravelPoint};
 use crate::scenes::BattleInitScene;
 use bimap::BiMap;
 use framework::prelude::*;
 use packets::address_parsing::uri_encode;
 use packets::structures::{
-    ActorId, ActorProperty, BattleStatistics, FileHash, SpriteId, SpriteParent, TextboxOptions,
+    ActorId, ActorProperty, BattleStatistics, ChatChannel, FileHash, SpriteId, SpriteParent,
+    TextboxOptions,
 };
 use packets::{
     address_parsing, ClientAssetType, ClientPacket, Reliability, ServerPacket, SERVER_TICK_RATE,
 };
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+/// How long to keep trying to resume the session after losing the connection
+const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Delay between attempts to resolve the server when resolving fails
+const RECONNECT_RETRY_RATE: Duration = Duration::from_secs(1);
+
+struct Reconnection {
+    start: Instant,
+    last_attempt: Instant,
+    pending: Option<flume::Receiver<(ClientPacketSender, ServerPacketReceiver)>>,
+}
 
 pub struct OverworldOnlineScene {
     area: OverworldArea,
@@ -40,6 +56,9 @@ pub struct OverworldOnlineScene {
     packet_receiver: ServerPacketReceiver,
     synchronizing_packets: bool,
     stored_packets: Vec<ServerPacket>,
+    resume_token: Option<Vec<u8>>,
+    reconnection: Option<Reconnection>,
+    avatar_upload: Option<AvatarUpload>,
     previous_boost_packet: Option<ClientPacket>,
     last_position_send: Instant,
     assets: ServerAssetManager,
@@ -50,6 +69,14 @@ pub struct OverworldOnlineScene {
     doorstop_remover: Option<TextboxDoorstopRemover>,
     encounter_packages: HashMap<String, PackageId>, // server_path -> package_id
     loaded_zips: HashMap<String, FileHash>,         // server_path -> hash
+    music_override: Option<(String, bool)>,         // server_path, loops
+    ambience_path: String,
+    ranked_queued: Rc<Cell<bool>>,
+    trade_status: Option<Rc<RefCell<TradeStatus>>>,
+    /// Signs the results of the active netplay battle
+    netplay_result_key: Option<Vec<u8>>,
+    /// Unset while on a private map
+    journal_map_path: Option<String>,
 }
 
 impl OverworldOnlineScene {
@@ -60,6 +87,7 @@ impl OverworldOnlineScene {
         packet_receiver: flume::Receiver<ServerPacket>,
     ) -> Self {
         let mut area = OverworldArea::new(game_io);
+        area.journal = MapJournal::for_address(&address);
 
         let player_entity = area.player_data.entity;
         let entities = &mut area.entities;
@@ -83,6 +111,16 @@ impl OverworldOnlineScene {
         let emote_menu_index = menu_manager.register_menu(Box::new(emote_menu));
         menu_manager.bind_menu(Input::Option2, emote_menu_index);
 
+        // emote wheel
+        let emote_wheel = EmoteWheel::new(game_io);
+        let emote_wheel_index = menu_manager.register_menu(Box::new(emote_wheel));
+        menu_manager.bind_menu(Input::Info, emote_wheel_index);
+
+        // chat box
+        let chat_box = ChatBox::new(game_io);
+        let chat_box_index = menu_manager.register_menu(Box::new(chat_box));
+        menu_manager.bind_menu(Input::Special, chat_box_index);
+
         // hud
         let hud = OverworldHud::new(game_io, area.player_data.health);
 
@@ -100,6 +138,9 @@ impl OverworldOnlineScene {
             packet_receiver,
             synchronizing_packets: false,
             stored_packets: Vec::new(),
+            resume_token: None,
+            reconnection: None,
+            avatar_upload: None,
             previous_boost_packet: None,
             last_position_send: game_io.frame_start_instant(),
             assets,
@@ -110,6 +151,12 @@ impl OverworldOnlineScene {
             doorstop_remover: None,
             encounter_packages: HashMap::new(),
             loaded_zips: HashMap::new(),
+            music_override: None,
+            ambience_path: String::new(),
+            ranked_queued: Rc::new(Cell::new(false)),
+            trade_status: None,
+            netplay_result_key: None,
+            journal_map_path: None,
         }
     }
 
@@ -133,6 +180,26 @@ impl OverworldOnlineScene {
         );
 
         // send asset found signals
+        self.send_asset_found_signals();
+
+        // send boosts
+        self.send_boosts(game_io);
+
+        // send avatar data, the server expects it before the join request
+        self.send_avatar_data(game_io);
+        self.flush_avatar_upload();
+
+        // nothing else to send, request join
+        let send_packet = &self.send_packet;
+        send_packet(Reliability::ReliableOrdered, ClientPacket::RequestJoin);
+
+        // candidates take a moment to gather, sent separately to avoid delaying the join
+        self.send_netplay_candidates(game_io);
+    }
+
+    fn send_asset_found_signals(&self) {
+        let send_packet = &self.send_packet;
+
         for asset in self.assets.stored_assets() {
             send_packet(
                 Reliability::ReliableOrdered,
@@ -142,16 +209,37 @@ impl OverworldOnlineScene {
                 },
             );
         }
+    }
 
-        // send boosts
-        self.send_boosts(game_io);
+    fn send_netplay_candidates(&self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let server_address = address_parsing::strip_data(&self.server_address).to_string();
+        let candidates_future = globals.network.gather_netplay_candidates(server_address);
+        let send_packet = self.send_packet.clone();
 
-        // send avatar data
-        self.send_avatar_data(game_io);
+        game_io
+            .spawn_local_task(async move {
+                let addresses = candidates_future.await;
 
-        // nothing else to send, request join
-        let send_packet = &self.send_packet;
-        send_packet(Reliability::ReliableOrdered, ClientPacket::RequestJoin);
+                if !addresses.is_empty() {
+                    send_packet(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::NetplayCandidates { addresses },
+                    );
+                }
+            })
+            .detach();
+    }
+
+    fn send_battle_deck(&self, deck: &Deck) {
+        let cards = (deck.cards.iter())
+            .map(|card| (card.package_id.clone(), card.code.clone()))
+            .collect();
+
+        (self.send_packet)(
+            Reliability::ReliableOrdered,
+            ClientPacket::BattleDeck { cards },
+        );
     }
 
     pub fn send_boosts(&mut self, game_io: &GameIO) {
@@ -185,38 +273,75 @@ impl OverworldOnlineScene {
         let player_package = global_save.player_package(game_io).unwrap();
 
         let assets = &globals.assets;
+
+        let avatar_assets = vec![
+            (
+                ClientAssetType::Texture,
+                assets.binary(&player_package.overworld_paths.texture),
+            ),
+            (
+                ClientAssetType::Animation,
+                assets.binary(&player_package.overworld_paths.animation),
+            ),
+            (
+                ClientAssetType::MugshotTexture,
+                assets.binary(&player_package.mugshot_paths.texture),
+            ),
+            (
+                ClientAssetType::MugshotAnimation,
+                assets.binary(&player_package.mugshot_paths.animation),
+            ),
+        ];
+
+        // sent after the assets
+        let avatar_change = ClientPacket::AvatarChange {
+            name: player_package.name.clone(),
+            element: globals.element_name(player_package.element),
+            base_health: player_package.health,
+        };
+
+        if self.avatar_upload.is_some() {
+            // replacing an unfinished upload
+            let send_packet = &self.send_packet;
+            send_packet(
+                Reliability::ReliableOrdered,
+                ClientPacket::AssetStreamCancel,
+            );
+        }
+
+        self.avatar_upload = Some(AvatarUpload::new(avatar_assets, avatar_change));
+    }
+
+    fn update_avatar_upload(&mut self) {
+        if self.reconnection.is_some() {
+            return;
+        }
+
+        let Some(upload) = &mut self.avatar_upload else {
+            return;
+        };
+
         let send_packet = &self.send_packet;
 
-        vec![
-            ClientPacket::Asset {
-                asset_type: ClientAssetType::Texture,
-                data: assets.binary(&player_package.overworld_paths.texture),
-            },
-            ClientPacket::Asset {
-                asset_type: ClientAssetType::Animation,
-                data: assets.binary(&player_package.overworld_paths.animation),
-            },
-            ClientPacket::Asset {
-                asset_type: ClientAssetType::MugshotTexture,
-                data: assets.binary(&player_package.mugshot_paths.texture),
-            },
-            ClientPacket::Asset {
-                asset_type: ClientAssetType::MugshotAnimation,
-                data: assets.binary(&player_package.mugshot_paths.animation),
-            },
-        ]
-        .into_iter()
-        .for_each(|packet| send_packet(Reliability::ReliableOrdered, packet));
+        for packet in upload.next_packets() {
+            send_packet(Reliability::ReliableOrdered, packet);
+        }
 
-        // send avatar change signal
-        send_packet(
-            Reliability::ReliableOrdered,
-            ClientPacket::AvatarChange {
-                name: player_package.name.clone(),
-                element: player_package.element.to_string(),
-                base_health: player_package.health,
-            },
-        );
+        if upload.is_complete() {
+            self.avatar_upload = None;
+        }
+    }
+
+    fn flush_avatar_upload(&mut self) {
+        let Some(mut upload) = self.avatar_upload.take() else {
+            return;
+        };
+
+        let send_packet = &self.send_packet;
+
+        for packet in upload.remaining_packets() {
+            send_packet(Reliability::ReliableOrdered, packet);
+        }
     }
 
     pub fn packet_receiver(&self) -> &ServerPacketReceiver {
@@ -238,20 +363,120 @@ impl OverworldOnlineScene {
             return;
         }
 
+        self.poll_reconnection();
+
         while let Ok(packet) = self.packet_receiver.try_recv() {
+            // hearing from the server again
+            self.reconnection = None;
             self.handle_packet(game_io, packet);
         }
 
-        if self.packet_receiver.is_disconnected() {
-            self.area
-                .event_sender
-                .send(OverworldEvent::Disconnected {
-                    message: String::from(
-                        "Everything is still.\x01..\x01\nLooks like we've been disconnected.",
-                    ),
-                })
-                .unwrap();
+        let reconnect_pending = self
+            .reconnection
+            .as_ref()
+            .is_some_and(|reconnection| reconnection.pending.is_some());
+
+        if self.packet_receiver.is_disconnected() && !reconnect_pending {
+            self.handle_disconnect(game_io);
+        }
+    }
+
+    fn handle_disconnect(&mut self, game_io: &GameIO) {
+        let now = game_io.frame_start_instant();
+
+        if self.resume_token.is_some() {
+            let reconnection = self.reconnection.get_or_insert(Reconnection {
+                start: now,
+                last_attempt: now - RECONNECT_RETRY_RATE,
+                pending: None,
+            });
+
+            if now - reconnection.start < RECONNECT_TIMEOUT {
+                if now - reconnection.last_attempt < RECONNECT_RETRY_RATE {
+                    return;
+                }
+
+                let globals = game_io.resource::<Globals>().unwrap();
+                let subscription = globals
+                    .network
+                    .subscribe_to_server(self.server_address.clone());
+                let (sender, receiver) = flume::bounded(1);
+
+                game_io
+                    .spawn_local_task(async move {
+                        if let Some(connection) = subscription.await {
+                            let _ = sender.send(connection);
+                        }
+                    })
+                    .detach();
+
+                reconnection.last_attempt = now;
+                reconnection.pending = Some(receiver);
+                return;
+            }
         }
+
+        self.reconnection = None;
+        self.area
+            .event_sender
+            .send(OverworldEvent::Disconnected {
+                message: String::from(
+                    "Everything is still.\x01..\x01\nLooks like we've been disconnected.",
+                ),
+            })
+            .unwrap();
+    }
+
+    fn poll_reconnection(&mut self) {
+        let Some(reconnection) = &mut self.reconnection else {
+            return;
+        };
+
+        let Some(pending) = &reconnection.pending else {
+            return;
+        };
+
+        let (send_packet, packet_receiver) = match pending.try_recv() {
+            Ok(connection) => connection,
+            Err(flume::TryRecvError::Empty) => return,
+            Err(flume::TryRecvError::Disconnected) => {
+                // failed to resolve, try again after a delay
+                reconnection.pending = None;
+                return;
+            }
+        };
+
+        reconnection.pending = None;
+
+        self.send_packet = send_packet;
+        self.packet_receiver = packet_receiver;
+
+        // the server resends the area, drop anything from the old connection
+        self.synchronizing_packets = false;
+        self.stored_packets.clear();
+
+        let Some(token) = self.resume_token.clone() else {
+            return;
+        };
+
+        let send_packet = &self.send_packet;
+        send_packet(Reliability::ReliableOrdered, ClientPacket::Resume { token });
+
+        // caches were cleared by the server when the connection dropped
+        self.send_asset_found_signals();
+
+        if let Some(upload) = &mut self.avatar_upload {
+            // the server may have only received part of the upload
+            let send_packet = &self.send_packet;
+            send_packet(
+                Reliability::ReliableOrdered,
+                ClientPacket::AssetStreamCancel,
+            );
+            upload.restart();
+        }
+
+        let send_packet = &self.send_packet;
+        send_packet(Reliability::ReliableOrdered, ClientPacket::RequestJoin);
     }
 
     pub fn handle_packet(&mut self, game_io: &mut GameIO, packet: ServerPacket) {
@@ -345,6 +570,7 @@ impl OverworldOnlineScene {
             ServerPacket::TransferStart => {
                 self.excluded_actors.clear();
                 self.excluded_objects.clear();
+                self.hud.minimap_mut().clear_object_markers();
                 self.transferring = true;
 
                 // despawn all other actors
@@ -403,6 +629,9 @@ impl OverworldOnlineScene {
                 });
             }
             ServerPacket::Kick { reason } => {
+                // the server won't accept the session again
+                self.resume_token = None;
+
                 self.area
                     .event_sender
                     .send(OverworldEvent::Disconnected {
@@ -410,6 +639,17 @@ impl OverworldOnlineScene {
                     })
                     .unwrap();
             }
+            ServerPacket::ResumeToken { token } => {
+                self.resume_token = Some(token);
+            }
+            ServerPacket::AvatarRejected { reason } => {
+                self.area
+                    .event_sender
+                    .send(OverworldEvent::SystemMessage {
+                        message: format!("Avatar rejected: \"{reason}\""),
+                    })
+                    .unwrap();
+            }
             ServerPacket::RemoveAsset { path } => self.assets.delete_asset(&path),
             ServerPacket::AssetStreamStart {
                 name,
@@ -430,6 +670,24 @@ impl OverworldOnlineScene {
             ServerPacket::AssetStream { data } => {
                 self.assets.receive_download_data(game_io, data);
             }
+            ServerPacket::CachedAudio {
+                name,
+                last_modified,
+                hash,
+            } => {
+                let loaded =
+                    self.assets
+                        .load_cached_audio(game_io, name.clone(), last_modified, hash);
+
+                if !loaded {
+                    // evicted since the cache was last trimmed, or never downloaded
+                    let send_packet = &self.send_packet;
+                    send_packet(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::CachedAudioMissing { path: name },
+                    );
+                }
+            }
             ServerPacket::Preload {
                 asset_path,
                 data_type,
@@ -464,6 +722,25 @@ impl OverworldOnlineScene {
                 let data = self.assets.text(&map_path);
 
                 if let Some(mut map) = load_map(game_io, &self.assets, &data) {
+                    let journal = &mut self.area.journal;
+
+                    if map.private() {
+                        journal.remove_area(&map_path);
+
+                        let globals = game_io.resource_mut::<Globals>().unwrap();
+                        let global_save = &mut globals.global_save;
+                        let fast_travel_log = global_save.fast_travel_log_mut(&self.server_address);
+
+                        if fast_travel_log.remove_area(&map_path) {
+                            global_save.save();
+                        }
+
+                        self.journal_map_path = None;
+                    } else {
+                        journal.record_map(&map_path, map.name(), &data);
+                        self.journal_map_path = Some(map_path);
+                    }
+
                     for id in &self.excluded_objects {
                         let Some(entity) = map.get_object_entity(*id) else {
                             continue;
@@ -472,11 +749,21 @@ impl OverworldOnlineScene {
                         Excluded::increment(map.object_entities_mut(), entity);
                     }
 
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+                    globals.screenshot_metadata.area =
+                        (!map.private()).then(|| map.name().to_string());
+
                     self.area.set_map(game_io, &self.assets, map);
                 } else {
                     log::warn!("Failed to load map provided by server");
                 }
             }
+            ServerPacket::AnimateTile { x, y, z, gid } => {
+                let world_time = self.area.world_time;
+                let position = IVec3::new(x, y, z);
+
+                self.area.map.play_tile_animation(position, gid, world_time);
+            }
             ServerPacket::Health { health } => {
                 let player_data = &mut self.area.player_data;
                 player_data.health = health;
@@ -517,6 +804,30 @@ impl OverworldOnlineScene {
                 let globals = game_io.resource_mut::<Globals>().unwrap();
                 globals.restrictions.add_card(card, count);
             }
+            ServerPacket::CardPack { name, cards } => {
+                let cards: Vec<_> = (cards.into_iter())
+                    .map(|(package_id, code)| Card { package_id, code })
+                    .collect();
+
+                // record where the cards came from
+                let server_address = address_parsing::strip_data(&self.server_address);
+                let rewards = CardReward::new_pack(&name, server_address, &cards);
+
+                let globals = game_io.resource_mut::<Globals>().unwrap();
+
+                for card in &cards {
+                    globals.restrictions.add_card(card.clone(), 1);
+                }
+
+                let global_save = &mut globals.global_save;
+                global_save.card_rewards.extend(rewards);
+                global_save.save();
+
+                let scene = CardPackScene::new(game_io, name, cards);
+                let transition = crate::transitions::new_sub_scene(game_io);
+                let next_scene = NextScene::new_push(scene).with_transition(transition);
+                self.next_scene_queue.push_back(next_scene);
+            }
             ServerPacket::AddBlock {
                 package_id,
                 color,
@@ -540,6 +851,37 @@ impl OverworldOnlineScene {
                     globals.audio.play_sound(&sound);
                 }
             }
+            ServerPacket::PlaySoundAt { path, x, y, z } => {
+                if self.area.visible {
+                    let sound = self.assets.audio(game_io, &path);
+                    let globals = game_io.resource::<Globals>().unwrap();
+
+                    let camera = &self.area.world_camera;
+                    let screen_position = self.area.map.world_3d_to_screen(Vec3::new(x, y, z));
+                    let offset = (screen_position - camera.position()) * camera.scale();
+                    let placement = AudioPlacement::from_offset(offset, RESOLUTION_F * 0.5);
+
+                    globals.audio.play_sound_at(&sound, Some(placement));
+                }
+            }
+            ServerPacket::PlayStinger { path } => {
+                if self.area.visible {
+                    let sound = self.assets.audio(game_io, &path);
+                    let globals = game_io.resource::<Globals>().unwrap();
+                    globals.audio.play_stinger(&sound);
+                }
+            }
+            ServerPacket::PlayMusic { path, loops } => {
+                if path.is_empty() {
+                    // fall back to the map's music
+                    self.music_override = None;
+                } else {
+                    self.music_override = Some((path, loops));
+                }
+            }
+            ServerPacket::PlayAmbience { path } => {
+                self.ambience_path = path;
+            }
             ServerPacket::ExcludeObject { id } => {
                 if !self.excluded_objects.contains(&id) {
                     let map = &mut self.area.map;
@@ -586,6 +928,20 @@ impl OverworldOnlineScene {
                     self.excluded_actors.remove(index);
                 }
             }
+            ServerPacket::ObjectMinimapMarker { id, visible } => {
+                self.hud.minimap_mut().set_object_marker(id, visible);
+            }
+            ServerPacket::ActorMinimapMarker { actor_id, visible } => {
+                if let Some(entity) = self.actor_id_map.get_by_left(&actor_id) {
+                    let entities = &mut self.area.entities;
+
+                    if visible {
+                        let _ = entities.insert_one(*entity, MinimapMarker);
+                    } else {
+                        let _ = entities.remove_one::<MinimapMarker>(*entity);
+                    }
+                }
+            }
             ServerPacket::MoveCamera {
                 x,
                 y,
@@ -891,6 +1247,188 @@ impl OverworldOnlineScene {
                     shop.remove_item(&id);
                 }
             }
+            ServerPacket::RankedSeason { info } => {
+                let send_packet = self.send_packet.clone();
+                let request_queue = move |queued| {
+                    send_packet(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::RankedQueue { queued },
+                    );
+                };
+
+                let scene = RankedSeasonScene::new(
+                    game_io,
+                    &self.assets,
+                    info,
+                    self.ranked_queued.clone(),
+                    request_queue,
+                );
+
+                let transition = crate::transitions::new_sub_scene(game_io);
+                let next_scene = NextScene::new_push(scene).with_transition(transition);
+                self.next_scene_queue.push_back(next_scene);
+            }
+            ServerPacket::RankedQueueStatus { queued } => {
+                self.ranked_queued.set(queued);
+            }
+            ServerPacket::TradeRequest { partner_name } => {
+                let send_packet = self.send_packet.clone();
+                let message = format!("{partner_name} wants to trade. Accept?");
+                let interface = TextboxQuestion::new(message, move |accepted| {
+                    send_packet(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::TradeResponse { accepted },
+                    );
+                });
+
+                self.menu_manager.push_textbox_interface(interface);
+            }
+            ServerPacket::PartyInvite { inviter_name } => {
+                let send_packet = self.send_packet.clone();
+                let message = format!("{inviter_name} invited you to a party. Join?");
+                let interface = TextboxQuestion::new(message, move |accepted| {
+                    send_packet(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::PartyResponse { accepted },
+                    );
+                });
+
+                self.menu_manager.push_textbox_interface(interface);
+            }
+            ServerPacket::PartyUpdate { mut members } => {
+                let local_id = (self.actor_id_map)
+                    .get_by_right(&self.area.player_data.entity)
+                    .cloned();
+
+                members.retain(|member| Some(member.actor_id) != local_id);
+                self.hud.set_party_members(members);
+            }
+            ServerPacket::TradeStart { partner_name } => {
+                let status = Rc::new(RefCell::new(TradeStatus::default()));
+                self.trade_status = Some(status.clone());
+
+                let send_packet = self.send_packet.clone();
+                let send_action = move |action| {
+                    let packet = match action {
+                        TradeAction::Offer(offer) => ClientPacket::TradeOffer { offer },
+                        TradeAction::Confirm(revision) => ClientPacket::TradeConfirm { revision },
+                        TradeAction::Cancel => ClientPacket::TradeCancel,
+                    };
+
+                    send_packet(Reliability::ReliableOrdered, packet);
+                };
+
+                let player_data = &self.area.player_data;
+                let items = (player_data.inventory.items()).cloned();
+
+                let scene = TradeScene::new(
+                    game_io,
+                    partner_name,
+                    player_data.money,
+                    items,
+                    &self.area.item_registry,
+                    status,
+                    send_action,
+                );
+
+                let transition = crate::transitions::new_sub_scene(game_io);
+                let next_scene = NextScene::new_push(scene).with_transition(transition);
+                self.next_scene_queue.push_back(next_scene);
+            }
+            ServerPacket::TradeUpdate {
+                offer,
+                partner_offer,
+                revision,
+                confirmed,
+                partner_confirmed,
+            } => {
+                if let Some(status) = &self.trade_status {
+                    let mut status = status.borrow_mut();
+                    status.offer = offer;
+                    status.partner_offer = partner_offer;
+                    status.revision = revision;
+                    status.confirmed = confirmed;
+                    status.partner_confirmed = partner_confirmed;
+                }
+            }
+            ServerPacket::TradeEnd { completed, reason } => {
+                if let Some(status) = self.trade_status.take() {
+                    status.borrow_mut().ended = Some((completed, reason));
+                }
+            }
+            ServerPacket::Notification { message } => {
+                let globals = game_io.resource::<Globals>().unwrap();
+                let notification_center = &globals.notification_center;
+                notification_center.push(NotificationKind::Server, message);
+            }
+            ServerPacket::QuestUpdate { quest } => {
+                let globals = game_io.resource_mut::<Globals>().unwrap();
+                let global_save = &mut globals.global_save;
+                global_save
+                    .quest_log_mut(&self.server_address)
+                    .update(quest);
+                global_save.save();
+
+                self.refresh_quest_tracker(game_io);
+            }
+            ServerPacket::QuestProgress {
+                id,
+                objective_index,
+                progress,
+            } => {
+                let globals = game_io.resource_mut::<Globals>().unwrap();
+                let global_save = &mut globals.global_save;
+                let quest_log = global_save.quest_log_mut(&self.server_address);
+
+                if quest_log.set_progress(&id, objective_index, progress) {
+                    global_save.save();
+                    self.refresh_quest_tracker(game_io);
+                }
+            }
+            ServerPacket::QuestRemove { id } => {
+                let globals = game_io.resource_mut::<Globals>().unwrap();
+                let global_save = &mut globals.global_save;
+                global_save.quest_log_mut(&self.server_address).remove(&id);
+                global_save.save();
+
+                self.refresh_quest_tracker(game_io);
+            }
+            ServerPacket::RegisterAchievement { mut definition } => {
+                // resolve the icon to the cached file to display it after disconnecting
+                if !definition.icon_texture_path.is_empty() {
+                    definition.icon_texture_path =
+                        self.assets.local_path(&definition.icon_texture_path);
+                }
+
+                let globals = game_io.resource_mut::<Globals>().unwrap();
+                let global_save = &mut globals.global_save;
+                let achievements = &mut global_save.achievements;
+                achievements.register_server_achievement(&self.server_address, definition);
+                global_save.save();
+            }
+            ServerPacket::UnlockAchievement { id } => {
+                let address = address_parsing::strip_data(&self.server_address);
+                let source = AchievementSource::Server(address.to_string());
+
+                let globals = game_io.resource_mut::<Globals>().unwrap();
+                let global_save = &mut globals.global_save;
+                let achievements = &mut global_save.achievements;
+
+                let name = (achievements.server_definitions.iter())
+                    .find(|(a, definition)| a == address && definition.id == id)
+                    .map(|(_, definition)| definition.name.clone())
+                    .unwrap_or_else(|| id.clone());
+
+                if achievements.unlock(source, id) {
+                    global_save.save();
+
+                    globals.audio.play_sound(&globals.sfx.shine);
+
+                    let message = format!("Achievement unlocked: {name}");
+                    let event = OverworldEvent::SystemMessage { message };
+                    self.area.event_sender.send(event).unwrap();
+                }
+            }
             ServerPacket::ReferServer { name, address } => {
                 let globals = game_io.resource::<Globals>().unwrap();
                 let index = globals.global_save.server_list.len() + 1;
@@ -972,7 +1510,11 @@ impl OverworldOnlineScene {
 
                 restrictions.load_restrictions_toml(restrictions_text);
             }
-            ServerPacket::InitiateEncounter { package_path, data } => {
+            ServerPacket::InitiateEncounter {
+                package_path,
+                data,
+                preview,
+            } => {
                 let globals = game_io.resource::<Globals>().unwrap();
 
                 if let Some(package_id) = self.encounter_packages.get(&package_path) {
@@ -986,6 +1528,8 @@ impl OverworldOnlineScene {
                     player_setup.base_health = player_data.base_health;
                     player_setup.emotion = player_data.emotion.clone();
 
+                    self.send_battle_deck(&player_setup.deck);
+
                     // callback
                     let event_sender = self.area.event_sender.clone();
                     props.statistics_callback = Some(Box::new(move |statistics| {
@@ -1000,9 +1544,33 @@ impl OverworldOnlineScene {
                         .generate_background(game_io, &self.assets);
 
                     // create scene
-                    let scene = BattleInitScene::new(game_io, props);
+                    let mut scene = BattleInitScene::new(game_io, props);
+                    let mut fade_color = Color::WHITE;
+
+                    if let Some(preview) = preview {
+                        if let Some(color) = preview.fade_color {
+                            fade_color = Color::from(color);
+                            scene = scene.with_fade_color(fade_color);
+                        }
+
+                        let card = EncounterPreviewCard::new(game_io, &self.assets, &preview);
+
+                        // report the decline as the battle's results
+                        let event_sender = self.area.event_sender.clone();
+                        let statistics = BattleStatistics {
+                            health: player_data.health,
+                            emotion: player_data.emotion.clone(),
+                            declined: true,
+                            ..Default::default()
+                        };
+
+                        scene = scene.with_preview(card, move || {
+                            let _ = event_sender
+                                .send(OverworldEvent::BattleStatistics(Some(statistics)));
+                        });
+                    }
 
-                    let transition = crate::transitions::new_battle(game_io);
+                    let transition = crate::transitions::new_colored_battle(game_io, fade_color);
                     let next_scene = NextScene::new_push(scene).with_transition(transition);
                     self.next_scene_queue.push_back(next_scene);
                 }
@@ -1011,8 +1579,13 @@ impl OverworldOnlineScene {
                 package_path,
                 data,
                 remote_players,
+                result_key,
+                role,
             } => {
                 (self.send_packet)(Reliability::ReliableOrdered, ClientPacket::EncounterStart);
+                self.send_battle_deck(&PlayerSetup::from_globals(game_io).deck);
+
+                self.netplay_result_key = Some(result_key);
 
                 // copy background
                 let background = self
@@ -1042,6 +1615,7 @@ impl OverworldOnlineScene {
                     base_health: player_data.base_health,
                     emotion: player_data.emotion.clone(),
                     remote_players,
+                    role,
                     fallback_address: self.server_address.clone(),
                     statistics_callback: Some(statistics_callback),
                 };
@@ -1238,6 +1812,70 @@ impl OverworldOnlineScene {
                     Emote::animate_actor(&mut self.area.entities, entity, &emote_id, false);
                 }
             }
+            ServerPacket::ActorPackageEmote {
+                actor_id,
+                package_id,
+                emote_id,
+            } => {
+                let Some(&entity) = self.actor_id_map.get_by_left(&actor_id) else {
+                    return;
+                };
+
+                // players without the package won't see the emote
+                let globals = game_io.resource::<Globals>().unwrap();
+                let Some(package) = globals
+                    .emote_packages
+                    .package(PackageNamespace::Local, &package_id)
+                else {
+                    return;
+                };
+
+                let sprite = globals.assets.new_sprite(game_io, &package.texture_path);
+                let animator = Animator::load_new(&globals.assets, &package.animation_path);
+
+                Emote::spawn_or_recycle_from(&mut self.area, entity, sprite, animator, &emote_id);
+            }
+            ServerPacket::ChatMessage {
+                channel,
+                sender_id,
+                sender_name,
+                message,
+            } => {
+                let local_id = (self.actor_id_map)
+                    .get_by_right(&self.area.player_data.entity)
+                    .cloned();
+                let sent_by_us = sender_id.is_some() && sender_id == local_id;
+
+                if !sent_by_us && !self.area.chat_filter.allows(channel) {
+                    return;
+                }
+
+                let recipient_name = match channel {
+                    ChatChannel::Whisper(recipient_id) if sent_by_us => {
+                        self.actor_name(recipient_id)
+                    }
+                    _ => None,
+                };
+
+                if let (ChatChannel::Whisper(_), Some(sender_id), false) =
+                    (channel, sender_id, sent_by_us)
+                {
+                    self.area.chat_log.reply_target = Some(sender_id);
+                }
+
+                let text = format_chat_line(
+                    channel,
+                    &sender_name,
+                    recipient_name.as_deref(),
+                    sent_by_us,
+                    &message,
+                );
+
+                self.push_chat_line(channel, text);
+            }
+            ServerPacket::ChatCommands { commands } => {
+                self.area.chat_log.server_commands = commands;
+            }
             ServerPacket::ActorAnimate {
                 actor_id,
                 state,
@@ -1395,6 +2033,111 @@ impl OverworldOnlineScene {
         }
     }
 
+    fn actor_name(&self, actor_id: ActorId) -> Option<String> {
+        let entity = *self.actor_id_map.get_by_left(&actor_id)?;
+        let name_label = self.area.entities.get::<&NameLabel>(entity).ok()?;
+
+        Some(name_label.0.clone())
+    }
+
+    /// Names of every actor except the local player
+    fn other_actor_names(&self) -> Vec<(ActorId, String)> {
+        let entities = &self.area.entities;
+
+        (self.actor_id_map.iter())
+            .filter(|(_, entity)| **entity != self.area.player_data.entity)
+            .filter_map(|(actor_id, entity)| {
+                let name_label = entities.get::<&NameLabel>(*entity).ok()?;
+                Some((*actor_id, name_label.0.clone()))
+            })
+            .collect()
+    }
+
+    fn push_chat_line(&mut self, channel: ChatChannel, text: String) {
+        self.area.chat_log.push(ChatLine {
+            channel,
+            text,
+            received_time: self.area.world_time,
+        });
+    }
+
+    fn submit_chat(&mut self, text: &str) {
+        let (channel, message) = match ChatCommand::parse(text) {
+            ChatCommand::Say(message) => (ChatChannel::Area, message),
+            ChatCommand::Party(message) => (ChatChannel::Party, message),
+            ChatCommand::Reply(message) => match self.area.chat_log.reply_target {
+                Some(actor_id) => (ChatChannel::Whisper(actor_id), message),
+                None => {
+                    let notice = String::from("No whisper to reply to.");
+                    self.push_chat_line(ChatChannel::System, notice);
+                    return;
+                }
+            },
+            ChatCommand::Invite(name) => {
+                let names = self.other_actor_names();
+                let names_iter = names.iter().map(|(id, name)| (*id, name.as_str()));
+
+                match find_name(name, names_iter) {
+                    Some(actor_id) => (self.send_packet)(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::PartyInvite { actor_id },
+                    ),
+                    None => {
+                        let notice = String::from("Couldn't find a player with that name.");
+                        self.push_chat_line(ChatChannel::System, notice);
+                    }
+                }
+
+                return;
+            }
+            ChatCommand::Leave => {
+                (self.send_packet)(Reliability::ReliableOrdered, ClientPacket::PartyLeave);
+                return;
+            }
+            ChatCommand::Custom { name, args } => {
+                if self.area.chat_log.server_command(name).is_some() {
+                    (self.send_packet)(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::ChatCommand {
+                            name: name.to_string(),
+                            args: args.to_string(),
+                        },
+                    );
+                } else {
+                    let notice = String::from("Unknown command, press tab to complete commands.");
+                    self.push_chat_line(ChatChannel::System, notice);
+                }
+
+                return;
+            }
+            ChatCommand::Whisper(text) => {
+                let names = self.other_actor_names();
+                let names_iter = names.iter().map(|(id, name)| (*id, name.as_str()));
+
+                match split_whisper(text, names_iter) {
+                    Some((actor_id, message)) => (ChatChannel::Whisper(actor_id), message),
+                    None => {
+                        let notice = String::from("Couldn't find a player with that name.");
+                        self.push_chat_line(ChatChannel::System, notice);
+                        return;
+                    }
+                }
+            }
+        };
+
+        if message.is_empty() {
+            return;
+        }
+
+        (self.send_packet)(
+            Reliability::ReliableOrdered,
+            ClientPacket::ChatMessage {
+                channel,
+                message: message.to_string(),
+            },
+        );
+    }
+
     fn push_textbox_interface_with_options(
         &mut self,
         game_io: &GameIO,
@@ -1427,6 +2170,81 @@ impl OverworldOnlineScene {
         self.menu_manager.push_textbox_interface(doorstop_interface);
     }
 
+    fn update_journal(&mut self) {
+        let Some(map_path) = &self.journal_map_path else {
+            return;
+        };
+
+        let entities = &mut self.area.entities;
+        let player_entity = self.area.player_data.entity;
+
+        let Ok(&position) = entities.query_one_mut::<&Vec3>(player_entity) else {
+            return;
+        };
+
+        let tile_position = self.area.map.world_3d_to_tile_space(position);
+        let tile = tile_position.truncate().floor().as_ivec2();
+
+        self.area.journal.explore(map_path, tile);
+    }
+
+    /// Records warps the player walks up to, private maps are skipped like the journal
+    fn update_fast_travel_log(&mut self, game_io: &mut GameIO) {
+        const VISIT_RADIUS: f32 = 1.5;
+
+        let Some(map_path) = &self.journal_map_path else {
+            return;
+        };
+
+        let entities = &mut self.area.entities;
+        let player_entity = self.area.player_data.entity;
+
+        let Ok(&position) = entities.query_one_mut::<&Vec3>(player_entity) else {
+            return;
+        };
+
+        let map = &self.area.map;
+        let mut points = Vec::new();
+
+        map.inspect_objects_near(position, VISIT_RADIUS, |object| {
+            let data = object.data;
+
+            if !data.object_type.is_warp() {
+                return;
+            }
+
+            let name = if data.name.is_empty() {
+                format!("Warp {}", data.id)
+            } else {
+                data.name.clone()
+            };
+
+            points.push(FastTravelPoint {
+                map_path: map_path.clone(),
+                area_name: map.name().to_string(),
+                object_id: data.id,
+                name,
+            });
+        });
+
+        if points.is_empty() {
+            return;
+        }
+
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+        let global_save = &mut globals.global_save;
+        let fast_travel_log = global_save.fast_travel_log_mut(&self.server_address);
+        let mut modified = false;
+
+        for point in points {
+            modified |= fast_travel_log.record(point);
+        }
+
+        if modified {
+            global_save.save();
+        }
+    }
+
     fn handle_events(&mut self, game_io: &mut GameIO) {
         while let Ok(event) = self.area.event_receiver.try_recv() {
             match event {
@@ -1445,6 +2263,21 @@ impl OverworldOnlineScene {
                         ClientPacket::Emote { emote_id },
                     );
                 }
+                OverworldEvent::ChatSubmitted(text) => {
+                    self.submit_chat(&text);
+                }
+                OverworldEvent::PackageEmoteSelected {
+                    package_id,
+                    emote_id,
+                } => {
+                    (self.send_packet)(
+                        Reliability::ReliableOrdered,
+                        ClientPacket::PackageEmote {
+                            package_id,
+                            emote_id,
+                        },
+                    );
+                }
                 OverworldEvent::ItemUse(item_id) => {
                     (self.send_packet)(
                         Reliability::ReliableOrdered,
@@ -1476,9 +2309,15 @@ impl OverworldOnlineScene {
                         },
                     };
 
+                    let signature =
+                        (self.netplay_result_key.take()).map(|key| battle_stats.sign_results(&key));
+
                     (self.send_packet)(
                         Reliability::ReliableOrdered,
-                        ClientPacket::BattleResults { battle_stats },
+                        ClientPacket::BattleResults {
+                            battle_stats,
+                            signature,
+                        },
                     );
                 }
                 OverworldEvent::WarpIn {
@@ -1518,7 +2357,17 @@ impl OverworldOnlineScene {
                         }
                     }
                 }
+                OverworldEvent::ViewJournalArea(index) => {
+                    let journal_area = self.area.journal.areas().get(index);
+                    let data = journal_area.map(|area| area.map_data.as_str());
+
+                    self.area.journal_view = data
+                        .and_then(|data| load_map(game_io, &self.assets, data))
+                        .map(|map| (index, map));
+                }
                 OverworldEvent::TransferServer { address, data } => {
+                    self.area.journal.save();
+
                     let transition = crate::transitions::new_connect(game_io);
                     let scene = InitialConnectScene::new(game_io, address, data, false);
                     let next_scene = NextScene::new_swap(scene).with_transition(transition);
@@ -1552,7 +2401,33 @@ impl OverworldOnlineScene {
                 OverworldEvent::NextScene(next_scene) => {
                     self.next_scene_queue.push_back(next_scene);
                 }
+                OverworldEvent::OpenFastTravel => {
+                    let scene = FastTravelScene::new(
+                        game_io,
+                        &self.server_address,
+                        self.journal_map_path.clone(),
+                        self.area.event_sender.clone(),
+                    );
+                    let transition = crate::transitions::new_sub_scene(game_io);
+                    let next_scene = NextScene::new_push(scene).with_transition(transition);
+
+                    self.next_scene_queue.push_back(next_scene);
+                }
+                OverworldEvent::FastTravel {
+                    map_path,
+                    object_id,
+                } => {
+                    (self.send_packet)(
+                        Reliability::Reliable,
+                        ClientPacket::FastTravelRequest {
+                            map_path,
+                            object_id,
+                        },
+                    );
+                }
                 OverworldEvent::Leave => {
+                    self.area.journal.save();
+
                     let transition = crate::transitions::new_connect(game_io);
                     self.next_scene_queue
                         .push_back(NextScene::new_pop().with_transition(transition));
@@ -1561,9 +2436,27 @@ impl OverworldOnlineScene {
         }
     }
 
+    fn refresh_quest_tracker(&mut self, game_io: &GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+        let quest_log = globals.global_save.quest_log(&self.server_address);
+        let tracked_quest = quest_log.and_then(|log| log.tracked()).cloned();
+
+        self.hud.set_tracked_quest(tracked_quest);
+    }
+
     fn handle_input(&mut self, game_io: &GameIO) {
         let input_util = InputUtil::new(game_io);
 
+        if input_util.was_just_pressed(Input::Option) {
+            let globals = game_io.resource::<Globals>().unwrap();
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+
+            let scene = QuestJournalScene::new(game_io, self.server_address.clone());
+            let transition = crate::transitions::new_sub_scene(game_io);
+            let next_scene = NextScene::new_push(scene).with_transition(transition);
+            self.next_scene_queue.push_back(next_scene);
+        }
+
         if input_util.was_just_pressed(Input::ShoulderR) {
             self.menu_manager.use_player_avatar(game_io);
             let event_sender = self.area.event_sender.clone();
@@ -1688,12 +2581,15 @@ impl OverworldOnlineScene {
 
         let globals = game_io.resource::<Globals>().unwrap();
 
-        if !globals.audio.is_music_playing() {
+        let (music_path, loops) = match &self.music_override {
+            Some((path, loops)) => (path.as_str(), *loops),
+            None => (self.area.map.music_path(), true),
+        };
+
+        if loops && !globals.audio.is_music_playing() {
             globals.audio.restart_music();
         }
 
-        let music_path = self.area.map.music_path();
-
         let sound_buffer = if music_path.is_empty() {
             globals.music.overworld.clone()
         } else {
@@ -1701,7 +2597,22 @@ impl OverworldOnlineScene {
         };
 
         if globals.audio.current_music().as_ref() != Some(&sound_buffer) {
-            globals.audio.play_music(&sound_buffer, true);
+            let fade = self.area.map.music_fade();
+            globals.audio.crossfade_music(&sound_buffer, loops, fade);
+        }
+
+        // ambience
+        let ambience = if self.ambience_path.is_empty() {
+            None
+        } else {
+            Some(self.assets.audio(game_io, &self.ambience_path))
+        };
+
+        if globals.audio.current_ambience() != ambience {
+            match ambience {
+                Some(sound_buffer) => globals.audio.play_ambience(&sound_buffer),
+                None => globals.audio.stop_ambience(),
+            }
         }
     }
 }
@@ -1714,6 +2625,22 @@ impl Scene for OverworldOnlineScene {
     fn enter(&mut self, game_io: &mut GameIO) {
         self.area.visible = true;
 
+        // apply preferences for this server, these may have changed in the config scene
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+        let preferences = globals.global_save.server_preferences(&self.server_address);
+
+        globals.connected_server_address = Some(self.server_address.clone());
+        globals.screenshot_metadata.area =
+            (!self.area.map.private()).then(|| self.area.map.name().to_string());
+        globals
+            .audio
+            .set_music_volume(preferences.music_volume(&globals.config));
+        self.area.name_tags_visible = preferences.name_tags;
+        self.area.chat_filter = preferences.chat_filter;
+
+        // the tracked quest may have changed in the quest journal
+        self.refresh_quest_tracker(game_io);
+
         // handle events triggered from other scenes
         // should be called before handling packets, but it's not necessary to do this every frame
         self.handle_events(game_io);
@@ -1739,8 +2666,13 @@ impl Scene for OverworldOnlineScene {
         }
     }
 
-    fn exit(&mut self, _game_io: &mut GameIO) {
+    fn exit(&mut self, game_io: &mut GameIO) {
         self.area.visible = false;
+        self.area.journal.save();
+
+        // ambience resumes in update_music when we return
+        let globals = game_io.resource::<Globals>().unwrap();
+        globals.audio.stop_ambience();
 
         // disable audio on the player's ActorPropertyAnimator
         let entity = self.area.player_data.entity;
@@ -1787,6 +2719,9 @@ impl Scene for OverworldOnlineScene {
         Emote::system(area);
         self.area.update(game_io);
         self.send_position(game_io);
+        self.update_avatar_upload();
+        self.update_journal();
+        self.update_fast_travel_log(game_io);
 
         if !self.area.is_input_locked(game_io) {
             self.handle_input(game_io);
@@ -1818,12 +2753,20 @@ impl Scene for OverworldOnlineScene {
         sprite_queue.update_camera(&self.area.ui_camera);
 
         if !self.menu_manager.is_blocking_hud() {
-            // hide the map name while the textbox is visible
+            // hide the map name and minimap while the textbox is visible
             let texbox_is_open = self.menu_manager.is_textbox_open();
             self.hud.set_map_name_visible(!texbox_is_open);
+            self.hud.minimap_mut().set_visible(!texbox_is_open);
+
+            // the chat box shows its own history
+            self.hud.set_chat_feed_visible(!self.menu_manager.is_open());
 
             // draw the hud
-            self.hud.draw(game_io, &mut sprite_queue, &self.area.map);
+            let journal_area = (self.journal_map_path.as_ref())
+                .and_then(|map_path| self.area.journal.area(map_path));
+
+            self.hud
+                .draw(game_io, &mut sprite_queue, &self.area, journal_area);
 
             // draw hud attachments
             self.area
@@ -1834,6 +2777,23 @@ impl Scene for OverworldOnlineScene {
         self.menu_manager
             .draw(game_io, render_pass, &mut sprite_queue, &self.area);
 
+        let status_text = if self.reconnection.is_some() {
+            Some(String::from("RECONNECTING..."))
+        } else {
+            (self.avatar_upload.as_ref())
+                .map(|upload| format!("UPLOADING AVATAR {}%", (upload.progress() * 100.0) as u32))
+        };
+
+        if let Some(text) = status_text {
+            let mut text_style = TextStyle::new(game_io, FontName::Thick);
+            text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+            let size = text_style.measure(&text).size;
+            let position = Vec2::new(RESOLUTION_F.x - size.x - 4.0, 4.0);
+            text_style.bounds.set_position(position);
+            text_style.draw(game_io, &mut sprite_queue, &text);
+        }
+
         render_pass.consume_queue(sprite_queue);
     }
 }
@@ -2,7 +2,7 @@ use crate::bindable::SpriteColorMode;
 use crate::packages::{PackageNamespace, RepoPackageUpdater, UpdateStatus};
 use crate::render::ui::*;
 use crate::render::{Animator, AnimatorLoopMode, Background, Camera, SpriteColorQueue};
-use crate::resources::{AssetManager, Globals, Input, InputUtil, ResourcePaths};
+use crate::resources::{AssetManager, Globals, Input, InputUtil, IntegrityCache, ResourcePaths};
 use framework::prelude::*;
 use packets::address_parsing::uri_encode;
 use taffy::style::{AlignItems, Dimension, FlexDirection};
@@ -422,6 +422,10 @@ impl PackageScene {
 
         globals.unload_package(category, PackageNamespace::Local, id);
 
+        let mut integrity_cache = IntegrityCache::load();
+        integrity_cache.forget_package(id);
+        integrity_cache.save();
+
         log::info!("Deleting {path:?}");
         let _ = std::fs::remove_dir_all(path);
     }
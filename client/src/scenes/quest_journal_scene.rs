@@ -0,0 +1,185 @@
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+use packets::structures::Quest;
+
+const LIST_WIDTH: f32 = 112.0;
+
+/// Lists quests given by the connected server, confirm toggles the hud tracker
+pub struct QuestJournalScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    server_address: String,
+    next_scene: NextScene,
+}
+
+impl QuestJournalScene {
+    pub fn new(game_io: &GameIO, server_address: String) -> Box<Self> {
+        let mut list = ListPanel::new(game_io, "QUESTS", LIST_WIDTH);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let global_save = &globals.global_save;
+        let quest_log = global_save.quest_log(&server_address);
+        list.set_total_items(quest_log.map(|log| log.quests.len()).unwrap_or_default());
+
+        // start on the tracked quest
+        if let Some(log) = quest_log {
+            let tracked_index = (log.tracked_quest.as_ref())
+                .and_then(|id| log.quests.iter().position(|quest| quest.id == *id));
+
+            if let Some(index) = tracked_index {
+                list.set_selected_index(index);
+            }
+        }
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            server_address,
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn describe(quest: &Quest, tracked: bool) -> String {
+        let mut lines = Vec::new();
+
+        if !quest.description.is_empty() {
+            lines.push(quest.description.clone());
+            lines.push(String::new());
+        }
+
+        for objective in &quest.objectives {
+            let marker = if objective.is_complete() { "*" } else { "-" };
+
+            match objective.progress_text() {
+                Some(progress) => {
+                    lines.push(format!("{marker} {} {progress}", objective.description))
+                }
+                None => lines.push(format!("{marker} {}", objective.description)),
+            }
+        }
+
+        if quest.is_complete() {
+            lines.push(String::from("Complete!"));
+        }
+
+        if tracked {
+            lines.push(String::from("Tracking"));
+        }
+
+        lines.join("\n")
+    }
+
+    fn handle_input(&mut self, game_io: &mut GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        let global_save = &mut globals.global_save;
+        let quest_log = global_save.quest_log_mut(&self.server_address);
+
+        let Some(quest) = quest_log.quests.get(self.list.selected_index()) else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            return;
+        };
+
+        let id = quest.id.clone();
+        quest_log.toggle_tracked(&id);
+        global_save.save();
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+    }
+}
+
+impl Scene for QuestJournalScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let quest_log = globals.global_save.quest_log(&self.server_address);
+        let quests = quest_log
+            .map(|log| log.quests.as_slice())
+            .unwrap_or_default();
+        let tracked_quest = quest_log.and_then(|log| log.tracked_quest.as_deref());
+
+        // draw list, completed quests are dimmed
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let quest = &quests[index];
+
+                text_style.color = if quest.is_complete() {
+                    Color::WHITE.multiply_color(0.75)
+                } else {
+                    Color::WHITE
+                };
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &quest.name);
+            },
+        );
+
+        text_style.color = Color::WHITE;
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match quests.get(self.list.selected_index()) {
+            Some(quest) => Self::describe(quest, tracked_quest == Some(quest.id.as_str())),
+            None => String::from("No quests."),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("QUESTS").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+use packets::structures::RankedSeasonInfo;
+use std::cell::Cell;
+use std::rc::Rc;
+
+const LINE_HEIGHT: f32 = 16.0;
+const TIER_ICON_SIZE: f32 = 16.0;
+const HEADER_POSITION: Vec2 = Vec2::new(12.0, 22.0);
+const TIERS_TOP: f32 = 62.0;
+const TIERS_HEIGHT: f32 = 28.0;
+const SIDE_MARGIN: f32 = 8.0;
+const RESULT_OFFSET: f32 = 128.0;
+const DELTA_OFFSET: f32 = 176.0;
+const INACTIVE_TIER_COLOR: Color = Color::new(0.4, 0.4, 0.4, 1.0);
+
+struct Matchmaking {
+    /// Updated by the overworld scene as the server responds
+    queued: Rc<Cell<bool>>,
+    was_queued: bool,
+    search_time: FrameTime,
+    request_queue: Box<dyn Fn(bool)>,
+}
+
+pub struct RankedSeasonScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    info: RankedSeasonInfo,
+    tier_sprites: Vec<Option<Sprite>>,
+    matchmaking: Option<Matchmaking>,
+    matches_frame: ScrollableFrame,
+    scroll_tracker: ScrollTracker,
+    line_height: f32,
+    ui_input_tracker: UiInputTracker,
+    next_scene: NextScene,
+}
+
+impl RankedSeasonScene {
+    pub fn new(
+        game_io: &GameIO,
+        assets: &impl AssetManager,
+        info: RankedSeasonInfo,
+        queued: Rc<Cell<bool>>,
+        request_queue: impl Fn(bool) + 'static,
+    ) -> Box<Self> {
+        // tier icons, fit to the same size to avoid large icons breaking the layout
+        let tier_sprites = (info.tiers.iter())
+            .map(|tier| {
+                if tier.icon_texture_path.is_empty() {
+                    return None;
+                }
+
+                let mut sprite = assets.new_sprite(game_io, &tier.icon_texture_path);
+                let size = sprite.size();
+
+                if size.x > 0.0 && size.y > 0.0 {
+                    let scale = (TIER_ICON_SIZE / size.x).min(TIER_ICON_SIZE / size.y);
+                    sprite.set_scale(Vec2::new(scale, scale));
+                }
+
+                Some(sprite)
+            })
+            .collect();
+
+        let matchmaking = info.matchmaking.then(|| Matchmaking {
+            was_queued: queued.get(),
+            queued,
+            search_time: 0,
+            request_queue: Box::new(request_queue),
+        });
+
+        // layout
+        let list_top = TIERS_TOP + TIERS_HEIGHT + 4.0;
+        let list_bounds = Rect::new(
+            SIDE_MARGIN,
+            list_top,
+            RESOLUTION_F.x - SIDE_MARGIN * 2.0,
+            RESOLUTION_F.y - list_top - SIDE_MARGIN,
+        );
+        let matches_frame = ScrollableFrame::new(game_io, list_bounds).with_label_str("RECENT");
+        let body_bounds = matches_frame.body_bounds();
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let line_height = LINE_HEIGHT * globals.config.text_scale();
+
+        let view_size = (body_bounds.height / line_height) as usize;
+        let mut scroll_tracker = ScrollTracker::new(game_io, view_size);
+        scroll_tracker.define_scrollbar(matches_frame.scroll_start(), matches_frame.scroll_end());
+        scroll_tracker.set_total_items(info.recent_matches.len());
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            info,
+            tier_sprites,
+            matchmaking,
+            matches_frame,
+            scroll_tracker,
+            line_height,
+            ui_input_tracker: UiInputTracker::new(),
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            // leaving the scene stops the search
+            if let Some(matchmaking) = &mut self.matchmaking {
+                matchmaking.cancel_search();
+            }
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        if let Some(matchmaking) = &mut self.matchmaking {
+            if self.ui_input_tracker.is_active(Input::Confirm) {
+                if matchmaking.queued.get() {
+                    globals.audio.play_sound(&globals.sfx.cursor_cancel);
+                    matchmaking.cancel_search();
+                } else {
+                    globals.audio.play_sound(&globals.sfx.cursor_select);
+                    (matchmaking.request_queue)(true);
+                }
+            }
+        }
+
+        // scrolling
+        let prev_index = self.scroll_tracker.top_index();
+
+        if self.ui_input_tracker.is_active(Input::Up) {
+            self.scroll_tracker.move_view_up();
+        }
+
+        if self.ui_input_tracker.is_active(Input::Down) {
+            self.scroll_tracker.move_view_down();
+        }
+
+        if prev_index != self.scroll_tracker.top_index() {
+            globals.audio.play_sound(&globals.sfx.cursor_move);
+        }
+    }
+
+    fn draw_header(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        let tier_index = self.info.tier_index();
+
+        let mut text_position = HEADER_POSITION;
+
+        if let Some(sprite) = tier_index.and_then(|i| self.tier_sprites[i].as_mut()) {
+            sprite.set_position(HEADER_POSITION);
+            sprite.set_color(Color::WHITE);
+            sprite_queue.draw_sprite(sprite);
+
+            text_position.x += TIER_ICON_SIZE + 4.0;
+        }
+
+        // the header is fit between the title and tiers
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+        text_style.bounds.set_position(text_position);
+        text_style.draw(game_io, sprite_queue, &self.info.season_name);
+
+        let standing_text = match tier_index {
+            Some(index) => format!("{} {}", self.info.tiers[index].name, self.info.rating),
+            None if self.info.in_placement() => format!(
+                "Placement {}/{}",
+                self.info.placement_matches_played, self.info.placement_matches_required
+            ),
+            None => format!("Unranked {}", self.info.rating),
+        };
+
+        text_style.font = FontName::Thin;
+        text_style.bounds.y += LINE_HEIGHT - 4.0;
+        text_style.draw(game_io, sprite_queue, &standing_text);
+
+        text_style.bounds.y += LINE_HEIGHT - 4.0;
+        text_style.draw(game_io, sprite_queue, &self.info.season_end_text);
+
+        // matchmaking status
+        let Some(matchmaking) = &self.matchmaking else {
+            return;
+        };
+
+        let status_text = if matchmaking.queued.get() {
+            let seconds = matchmaking.search_time / 60;
+            format!("Searching {}:{:02}", seconds / 60, seconds % 60)
+        } else {
+            String::from("Confirm: Find Match")
+        };
+
+        let status_width = text_style.measure(&status_text).size.x;
+        text_style.bounds.x = RESOLUTION_F.x - SIDE_MARGIN - status_width;
+        text_style.draw(game_io, sprite_queue, &status_text);
+    }
+
+    fn draw_tiers(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        if self.info.tiers.is_empty() {
+            return;
+        }
+
+        let tier_index = self.info.tier_index();
+        let spacing = (RESOLUTION_F.x - SIDE_MARGIN * 2.0) / self.info.tiers.len() as f32;
+
+        let mut text_style = TextStyle::new_unscaled(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        for (i, tier) in self.info.tiers.iter().enumerate() {
+            let center_x = SIDE_MARGIN + spacing * (i as f32 + 0.5);
+
+            let color = if tier_index == Some(i) {
+                Color::WHITE
+            } else {
+                INACTIVE_TIER_COLOR
+            };
+
+            if let Some(sprite) = &mut self.tier_sprites[i] {
+                sprite.set_position(Vec2::new(center_x - TIER_ICON_SIZE * 0.5, TIERS_TOP));
+                sprite.set_color(color);
+                sprite_queue.draw_sprite(sprite);
+            }
+
+            let rating_text = tier.min_rating.to_string();
+            let text_size = text_style.measure(&rating_text).size;
+
+            text_style.color = color;
+            text_style.bounds.set_position(Vec2::new(
+                center_x - text_size.x * 0.5,
+                TIERS_TOP + TIER_ICON_SIZE + 2.0,
+            ));
+            text_style.draw(game_io, sprite_queue, &rating_text);
+        }
+    }
+
+    fn draw_matches(&mut self, game_io: &GameIO, sprite_queue: &mut SpriteColorQueue) {
+        self.matches_frame.draw(game_io, sprite_queue);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        let mut position = self.matches_frame.body_bounds().top_left() + Vec2::new(3.0, 3.0);
+
+        if self.info.recent_matches.is_empty() {
+            text_style.bounds.set_position(position);
+            text_style.draw(game_io, sprite_queue, "No matches yet.");
+            return;
+        }
+
+        for result in &self.info.recent_matches[self.scroll_tracker.view_range()] {
+            text_style.color = Color::WHITE;
+            text_style.bounds.set_position(position);
+            text_style.draw(game_io, sprite_queue, &result.opponent_name);
+
+            let result_text = if result.won { "WIN" } else { "LOSE" };
+            text_style.bounds.x = position.x + RESULT_OFFSET;
+            text_style.draw(game_io, sprite_queue, result_text);
+
+            let (delta_text, delta_color) = match result.rating_delta {
+                delta if delta > 0 => (format!("+{delta}"), Color::GREEN),
+                delta if delta < 0 => (delta.to_string(), Color::RED),
+                _ => (String::from("0"), Color::WHITE),
+            };
+
+            text_style.color = delta_color;
+            text_style.bounds.x = position.x + DELTA_OFFSET;
+            text_style.draw(game_io, sprite_queue, &delta_text);
+
+            position.y += self.line_height;
+        }
+
+        self.scroll_tracker.draw_scrollbar(sprite_queue);
+    }
+}
+
+impl Matchmaking {
+    /// Returns true when the server ends the search
+    fn update(&mut self) -> bool {
+        let queued = self.queued.get();
+        let search_ended = self.was_queued && !queued;
+        self.was_queued = queued;
+
+        if queued {
+            self.search_time += 1;
+        } else {
+            self.search_time = 0;
+        }
+
+        search_ended
+    }
+
+    fn cancel_search(&mut self) {
+        if !self.queued.get() {
+            return;
+        }
+
+        (self.request_queue)(false);
+
+        // update immediately to avoid treating our own request as a match starting
+        self.queued.set(false);
+        self.was_queued = false;
+    }
+}
+
+impl Scene for RankedSeasonScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        if let Some(matchmaking) = &mut self.matchmaking {
+            if matchmaking.update() {
+                // the server removed us from the queue to start a match
+                let transition = crate::transitions::new_scene_pop(game_io);
+                self.next_scene = NextScene::new_pop().with_transition(transition);
+                return;
+            }
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        self.draw_header(game_io, &mut sprite_queue);
+        self.draw_tiers(game_io, &mut sprite_queue);
+        self.draw_matches(game_io, &mut sprite_queue);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("RANKED").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -64,7 +64,7 @@ impl ResourceOrderScene {
         let cursor_start = scrollable_frame.body_bounds().top_left() + Vec2::new(-8.0, 2.0);
 
         let mut scroll_tracker = ScrollTracker::new(game_io, 7);
-        scroll_tracker.define_cursor(cursor_start, 16.0);
+        scroll_tracker.define_text_cursor(game_io, cursor_start, 16.0);
         scroll_tracker.define_scrollbar(
             scrollable_frame.scroll_start(),
             scrollable_frame.scroll_end(),
@@ -0,0 +1,225 @@
+use crate::bindable::SpriteColorMode;
+use crate::format_util::format_timestamp_millis;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::{GlobalSave, SaveBackup};
+use framework::prelude::*;
+
+const LIST_WIDTH: f32 = 112.0;
+
+enum Event {
+    Restore(usize),
+}
+
+pub struct SaveBackupsScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    backups: Vec<SaveBackup>,
+    labels: Vec<String>,
+    list: ListPanel,
+    /// The selected backup, loaded when the selection changes
+    selected: Option<(usize, Result<GlobalSave, String>)>,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl SaveBackupsScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let list = ListPanel::new(game_io, "BACKUPS", LIST_WIDTH);
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            backups: Vec::new(),
+            labels: Vec::new(),
+            list,
+            selected: None,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        });
+
+        scene.refresh_backups();
+        scene
+    }
+
+    fn refresh_backups(&mut self) {
+        self.backups = SaveBackup::list();
+        self.labels = (self.backups.iter())
+            .map(|backup| format_timestamp_millis(backup.created_at))
+            .collect();
+
+        self.selected = None;
+        self.list.set_total_items(self.backups.len());
+    }
+
+    fn update_selection(&mut self) {
+        let index = self.list.selected_index();
+
+        if self.selected.as_ref().is_some_and(|(i, _)| *i == index) {
+            return;
+        }
+
+        self.selected = (self.backups.get(index)).map(|backup| (index, backup.load()));
+    }
+
+    fn handle_events(&mut self, game_io: &mut GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                Event::Restore(index) => {
+                    let Some((selected_index, Ok(save))) = self.selected.take() else {
+                        continue;
+                    };
+
+                    if selected_index != index {
+                        continue;
+                    }
+
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+
+                    // saving backs up the save being replaced, so restoring can be undone
+                    globals.global_save = save;
+                    globals.global_save.save();
+                    globals.audio.play_sound(&globals.sfx.cursor_select);
+
+                    let message = format!("Restored the save from {}.", self.labels[index]);
+                    self.textbox.push_interface(TextboxMessage::new(message));
+                    self.textbox.open();
+
+                    self.refresh_backups();
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        let Some((index, Ok(_))) = &self.selected else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            return;
+        };
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+
+        let index = *index;
+        let question = format!(
+            "Restore the save from {}? The current save will be backed up.",
+            self.labels[index]
+        );
+
+        let event_sender = self.event_sender.clone();
+        let interface = TextboxQuestion::new(question, move |yes| {
+            if yes {
+                let _ = event_sender.send(Event::Restore(index));
+            }
+        });
+
+        self.textbox.push_interface(interface);
+        self.textbox.open();
+    }
+}
+
+impl Scene for SaveBackupsScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if self.textbox.is_complete() {
+            self.textbox.close();
+        }
+
+        self.textbox.update(game_io);
+        self.handle_events(game_io);
+        self.update_selection();
+
+        if game_io.is_in_transition() || self.textbox.is_open() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let label = &self.labels[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, label);
+            },
+        );
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match &self.selected {
+            Some((_, Ok(save))) => format!(
+                "Nickname: {}\nFolders: {}\nServers: {}",
+                save.nickname,
+                save.decks.len(),
+                save.server_list.len()
+            ),
+            Some((_, Err(e))) => e.clone(),
+            None => {
+                format!("No backups yet. The last {SAVE_BACKUP_LIMIT} saves are kept as backups.")
+            }
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("RESTORE BACKUP").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::bindable::SpriteColorMode;
+use crate::format_util::format_timestamp;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::{GlobalSave, SaveExport};
+use framework::prelude::*;
+use packets::structures::{PackageCategory, PackageId};
+
+const LIST_WIDTH: f32 = 112.0;
+/// Missing packages listed in the details before summarizing the rest
+const MISSING_PACKAGE_LINES: usize = 4;
+
+enum Event {
+    Import(usize),
+}
+
+struct ValidatedExport {
+    save: GlobalSave,
+    description: String,
+    missing_packages: Vec<(PackageCategory, PackageId)>,
+}
+
+pub struct SaveImportScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    paths: Vec<String>,
+    labels: Vec<String>,
+    list: ListPanel,
+    /// The selected export, validated when the selection changes
+    selected: Option<(usize, Result<ValidatedExport, String>)>,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl SaveImportScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let mut list = ListPanel::new(game_io, "EXPORTS", LIST_WIDTH);
+
+        let paths = SaveExport::list();
+        let labels = (paths.iter())
+            .map(|path| {
+                let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+                let stem = file_name.trim_end_matches(SaveExport::EXTENSION);
+                stem.trim_end_matches('.').to_string()
+            })
+            .collect();
+
+        list.set_total_items(paths.len());
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            paths,
+            labels,
+            list,
+            selected: None,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn validate(game_io: &GameIO, path: &str) -> Result<ValidatedExport, String> {
+        let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+        let export = SaveExport::from_bytes(&bytes)?;
+        let save = export.global_save()?;
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let missing_packages = SaveExport::missing_packages(globals, &save);
+
+        let mut lines = vec![
+            format!("Exported: {}", format_timestamp(export.exported_at)),
+            format!("Game Version: {}", export.game_version),
+            format!("Nickname: {}", save.nickname),
+            format!("Folders: {}", save.decks.len()),
+        ];
+
+        if missing_packages.is_empty() {
+            lines.push(String::from("All packages are installed."));
+        } else {
+            lines.push(format!("Missing Packages: {}", missing_packages.len()));
+
+            for (_, id) in missing_packages.iter().take(MISSING_PACKAGE_LINES) {
+                lines.push(format!(" {id}"));
+            }
+
+            if missing_packages.len() > MISSING_PACKAGE_LINES {
+                let remaining = missing_packages.len() - MISSING_PACKAGE_LINES;
+                lines.push(format!(" and {remaining} more"));
+            }
+        }
+
+        Ok(ValidatedExport {
+            save,
+            description: lines.join("\n"),
+            missing_packages,
+        })
+    }
+
+    fn update_selection(&mut self, game_io: &GameIO) {
+        let index = self.list.selected_index();
+
+        if self.selected.as_ref().is_some_and(|(i, _)| *i == index) {
+            return;
+        }
+
+        self.selected = (self.paths.get(index)).map(|path| (index, Self::validate(game_io, path)));
+    }
+
+    fn handle_events(&mut self, game_io: &mut GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                Event::Import(index) => {
+                    let Some((selected_index, Ok(export))) = self.selected.take() else {
+                        continue;
+                    };
+
+                    if selected_index != index {
+                        continue;
+                    }
+
+                    let globals = game_io.resource_mut::<Globals>().unwrap();
+
+                    let resources_changed = globals.global_save.resource_package_order
+                        != export.save.resource_package_order;
+
+                    globals.global_save = export.save;
+                    globals.global_save.save();
+                    globals.audio.play_sound(&globals.sfx.cursor_select);
+
+                    // saving backs up the previous save
+                    let mut message = String::from(
+                        "Imported the save. The previous save can be restored from Restore Backup.",
+                    );
+
+                    if resources_changed {
+                        message += " Resource changes apply after a restart.";
+                    }
+
+                    self.textbox.push_interface(TextboxMessage::new(message));
+                    self.textbox.open();
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        let Some((index, Ok(export))) = &self.selected else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            return;
+        };
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+
+        let question = if export.missing_packages.is_empty() {
+            String::from("Replace the current save with this export?")
+        } else {
+            format!(
+                "{} packages used by this save aren't installed. Replace the current save anyway?",
+                export.missing_packages.len()
+            )
+        };
+
+        let index = *index;
+        let event_sender = self.event_sender.clone();
+        let interface = TextboxQuestion::new(question, move |yes| {
+            if yes {
+                let _ = event_sender.send(Event::Import(index));
+            }
+        });
+
+        self.textbox.push_interface(interface);
+        self.textbox.open();
+    }
+}
+
+impl Scene for SaveImportScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if self.textbox.is_complete() {
+            self.textbox.close();
+        }
+
+        self.textbox.update(game_io);
+        self.handle_events(game_io);
+        self.update_selection(game_io);
+
+        if game_io.is_in_transition() || self.textbox.is_open() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let label = &self.labels[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, label);
+            },
+        );
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match &self.selected {
+            Some((_, Ok(export))) => export.description.clone(),
+            Some((_, Err(e))) => format!("Can't import: {e}"),
+            None => format!(
+                "Place .{} files in {} to import them.",
+                SaveExport::EXTENSION,
+                ResourcePaths::SAVE_EXPORT_FOLDER
+            ),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("IMPORT SAVE").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
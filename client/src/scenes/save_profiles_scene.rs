@@ -0,0 +1,244 @@
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+
+const LIST_WIDTH: f32 = 120.0;
+const NEW_PROFILE_LABEL: &str = "New Profile";
+
+enum Event {
+    Create(String),
+    Switch(String),
+}
+
+pub struct SaveProfilesScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    /// Profile names, empty for the default profile
+    profiles: Vec<String>,
+    active_profile: String,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl SaveProfilesScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let list = ListPanel::new(game_io, "PROFILES", LIST_WIDTH);
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            profiles: Vec::new(),
+            active_profile: SaveProfiles::active(),
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        });
+
+        scene.refresh_profiles();
+        scene
+    }
+
+    fn refresh_profiles(&mut self) {
+        self.profiles = SaveProfiles::list();
+
+        // includes the new profile row
+        self.list.set_total_items(self.profiles.len() + 1);
+    }
+
+    fn leave(&mut self, game_io: &GameIO) {
+        let transition = crate::transitions::new_scene_pop(game_io);
+        self.next_scene = NextScene::new_pop().with_transition(transition);
+    }
+
+    fn handle_events(&mut self, game_io: &GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            let globals = game_io.resource::<Globals>().unwrap();
+
+            match event {
+                Event::Create(name) => {
+                    if let Err(e) = SaveProfiles::create(&name) {
+                        globals.audio.play_sound(&globals.sfx.cursor_error);
+                        self.textbox.push_interface(TextboxMessage::new(e));
+                        self.textbox.open();
+                        continue;
+                    }
+
+                    globals.audio.play_sound(&globals.sfx.cursor_select);
+                    self.refresh_profiles();
+
+                    if let Some(index) = self.profiles.iter().position(|p| *p == name) {
+                        self.list.set_selected_index(index);
+                    }
+                }
+                Event::Switch(name) => {
+                    SaveProfiles::remember(&name);
+
+                    match SaveProfiles::relaunch(&name) {
+                        Ok(()) => {
+                            log::logger().flush();
+                            std::process::exit(0);
+                        }
+                        Err(e) => {
+                            log::error!("Failed to restart: {e}");
+
+                            let message = format!(
+                                "Restart the game to use the {} profile.",
+                                SaveProfiles::display_name(&name)
+                            );
+                            self.textbox.push_interface(TextboxMessage::new(message));
+                            self.textbox.open();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+            self.leave(game_io);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if !self.ui_input_tracker.is_active(Input::Confirm) {
+            return;
+        }
+
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+
+        let index = self.list.selected_index();
+        let event_sender = self.event_sender.clone();
+
+        let Some(name) = self.profiles.get(index) else {
+            // new profile row
+            let interface = TextboxPrompt::new(move |name| {
+                let name = name.trim().to_string();
+
+                if !name.is_empty() {
+                    let _ = event_sender.send(Event::Create(name));
+                }
+            })
+            .with_filter(|grapheme| grapheme != "\n" && grapheme != "\t")
+            .with_character_limit(SaveProfiles::NAME_LIMIT);
+
+            self.textbox.push_interface(interface);
+            self.textbox.open();
+            return;
+        };
+
+        if *name == self.active_profile {
+            self.leave(game_io);
+            return;
+        }
+
+        let name = name.clone();
+        let question = format!(
+            "Switch to the {} profile? The game will restart.",
+            SaveProfiles::display_name(&name)
+        );
+
+        let interface = TextboxQuestion::new(question, move |yes| {
+            if yes {
+                let _ = event_sender.send(Event::Switch(name));
+            }
+        });
+
+        self.textbox.push_interface(interface);
+        self.textbox.open();
+    }
+}
+
+impl Scene for SaveProfilesScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if self.textbox.is_complete() {
+            self.textbox.close();
+        }
+
+        self.textbox.update(game_io);
+        self.handle_events(game_io);
+
+        if game_io.is_in_transition() || self.textbox.is_open() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list, the last row creates a profile
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let label = match self.profiles.get(index) {
+                    Some(name) => SaveProfiles::display_name(name),
+                    None => NEW_PROFILE_LABEL,
+                };
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, label);
+            },
+        );
+
+        // draw details
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let details = match self.profiles.get(self.list.selected_index()) {
+            Some(name) if *name == self.active_profile => {
+                String::from("In use.\n\nProfiles keep their own saves, config, and identities.")
+            }
+            Some(_) => String::from("Switching restarts the game."),
+            None => String::from("Creates a profile with a copy of the current config."),
+        };
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("PROFILES").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
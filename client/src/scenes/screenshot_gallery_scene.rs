@@ -0,0 +1,223 @@
+use crate::bindable::SpriteColorMode;
+use crate::format_util::format_timestamp;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+
+const LIST_WIDTH: f32 = 104.0;
+const PREVIEW_SCALE: f32 = 0.45;
+
+enum Event {
+    Delete(usize),
+}
+
+pub struct ScreenshotGalleryScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    screenshots: Vec<Screenshot>,
+    labels: Vec<String>,
+    list: ListPanel,
+    /// The selected screenshot, loaded when the selection changes
+    preview: Option<(usize, Sprite)>,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl ScreenshotGalleryScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let list = ListPanel::new(game_io, "PHOTOS", LIST_WIDTH);
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            screenshots: Vec::new(),
+            labels: Vec::new(),
+            list,
+            preview: None,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        });
+
+        scene.refresh_screenshots();
+        scene
+    }
+
+    fn refresh_screenshots(&mut self) {
+        self.screenshots = Screenshot::list();
+        self.labels = (self.screenshots.iter())
+            .map(|screenshot| format_timestamp(screenshot.metadata.taken_at))
+            .collect();
+
+        self.preview = None;
+        self.list.set_total_items(self.screenshots.len());
+    }
+
+    fn update_preview(&mut self, game_io: &GameIO) {
+        let index = self.list.selected_index();
+
+        if self.preview.as_ref().is_some_and(|(i, _)| *i == index) {
+            return;
+        }
+
+        let Some(screenshot) = self.screenshots.get(index) else {
+            self.preview = None;
+            return;
+        };
+
+        let bytes = std::fs::read(&screenshot.path).unwrap_or_default();
+
+        let mut sprite = match Texture::load_from_memory(game_io, &bytes) {
+            Ok(texture) => Sprite::new(game_io, texture),
+            Err(e) => {
+                log::warn!("Failed to load {:?}: {e}", screenshot.path);
+
+                let assets = &game_io.resource::<Globals>().unwrap().assets;
+                assets.new_sprite(game_io, ResourcePaths::BLANK)
+            }
+        };
+
+        sprite.set_size(RESOLUTION_F * PREVIEW_SCALE);
+        sprite.set_position(self.list.details_bounds().top_left());
+
+        self.preview = Some((index, sprite));
+    }
+
+    fn handle_events(&mut self, game_io: &GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                Event::Delete(index) => {
+                    let globals = game_io.resource::<Globals>().unwrap();
+
+                    if let Err(e) = self.screenshots[index].delete() {
+                        log::error!("{e}");
+                        globals.audio.play_sound(&globals.sfx.cursor_error);
+
+                        let message = String::from("Failed to delete screenshot.");
+                        self.textbox.push_interface(TextboxMessage::new(message));
+                        continue;
+                    }
+
+                    globals.audio.play_sound(&globals.sfx.cursor_select);
+                    self.refresh_screenshots();
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if self.ui_input_tracker.is_active(Input::Confirm) && !self.screenshots.is_empty() {
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+
+            let index = self.list.selected_index();
+            let event_sender = self.event_sender.clone();
+            let question = format!("Delete screenshot from {}?", self.labels[index]);
+            let interface = TextboxQuestion::new(question, move |yes| {
+                if yes {
+                    event_sender.send(Event::Delete(index)).unwrap();
+                }
+            });
+
+            self.textbox.push_interface(interface);
+            self.textbox.open();
+        }
+    }
+}
+
+impl Scene for ScreenshotGalleryScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+        self.textbox.update(game_io);
+        self.handle_events(game_io);
+        self.update_preview(game_io);
+
+        if game_io.is_in_transition() || self.textbox.is_open() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let label = &self.labels[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, label);
+            },
+        );
+
+        // draw preview and metadata
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        if let Some((index, sprite)) = &self.preview {
+            sprite_queue.draw_sprite(sprite);
+
+            text_style.bounds.y = sprite.position().y + sprite.size().y + 4.0;
+
+            let screenshot = &self.screenshots[*index];
+            let text = screenshot.metadata.describe().join("\n");
+            text_style.draw(game_io, &mut sprite_queue, &text);
+        } else {
+            text_style.draw(
+                game_io,
+                &mut sprite_queue,
+                "Press F12 to take a screenshot.",
+            );
+        }
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("SCREENSHOTS").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
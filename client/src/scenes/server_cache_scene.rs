@@ -0,0 +1,250 @@
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use framework::prelude::*;
+use packets::address_parsing::uri_decode;
+use std::fs;
+
+const SIZE_OFFSET: f32 = 160.0;
+
+enum Event {
+    Clear(usize),
+}
+
+struct CacheRow {
+    label: String,
+    path: String,
+    size: u64,
+}
+
+pub struct ServerCacheScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    rows: Vec<CacheRow>,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl ServerCacheScene {
+    pub fn new(game_io: &GameIO) -> Box<Self> {
+        let list = ListPanel::new(game_io, "CACHE", RESOLUTION_F.x - 16.0);
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            rows: Vec::new(),
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        });
+
+        scene.refresh_rows();
+        scene
+    }
+
+    fn refresh_rows(&mut self) {
+        let mut rows = vec![CacheRow {
+            label: String::from("Shared Audio"),
+            path: ResourcePaths::AUDIO_CACHE_FOLDER.to_string(),
+            size: folder_size(ResourcePaths::AUDIO_CACHE_FOLDER),
+        }];
+
+        if let Ok(entries) = fs::read_dir(ResourcePaths::SERVER_CACHE_FOLDER) {
+            for entry in entries.flatten() {
+                if !entry.metadata().is_ok_and(|metadata| metadata.is_dir()) {
+                    continue;
+                }
+
+                let Some(folder_name) = entry.file_name().to_str().map(String::from) else {
+                    continue;
+                };
+
+                // reverse the prefix created by the ServerAssetManager
+                let label = uri_decode(&folder_name)
+                    .unwrap_or_else(|| folder_name.clone())
+                    .replace("_p", ":");
+
+                let path = ResourcePaths::clean_folder(&format!(
+                    "{}{}",
+                    ResourcePaths::SERVER_CACHE_FOLDER,
+                    folder_name
+                ));
+
+                rows.push(CacheRow {
+                    label,
+                    size: folder_size(&path),
+                    path,
+                });
+            }
+        }
+
+        rows[1..].sort_by(|a, b| a.label.cmp(&b.label));
+
+        self.rows = rows;
+        self.list.set_total_items(self.rows.len());
+    }
+
+    fn handle_events(&mut self, game_io: &GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                Event::Clear(index) => {
+                    let row = &self.rows[index];
+
+                    let message = match fs::remove_dir_all(&row.path) {
+                        Ok(()) => format!("Cleared {}.", row.label),
+                        Err(e) => {
+                            log::error!("{e}");
+
+                            if matches!(e.kind(), std::io::ErrorKind::NotFound) {
+                                String::from("Cache is already empty.")
+                            } else {
+                                String::from("Failed to clear cache.")
+                            }
+                        }
+                    };
+
+                    self.refresh_rows();
+
+                    let globals = game_io.resource::<Globals>().unwrap();
+                    globals.audio.play_sound(&globals.sfx.cursor_select);
+
+                    self.textbox.push_interface(TextboxMessage::new(message));
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        if self.ui_input_tracker.is_active(Input::Confirm) {
+            let index = self.list.selected_index();
+            let row = &self.rows[index];
+
+            if globals.connected_to_server {
+                globals.audio.play_sound(&globals.sfx.cursor_error);
+
+                let message = String::from("You should jack out before clearing cache.");
+                self.textbox.push_interface(TextboxMessage::new(message));
+                self.textbox.open();
+                return;
+            }
+
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+
+            let event_sender = self.event_sender.clone();
+            let question = format!("Clear cache for {}?", row.label);
+            let interface = TextboxQuestion::new(question, move |yes| {
+                if yes {
+                    event_sender.send(Event::Clear(index)).unwrap();
+                }
+            });
+
+            self.textbox.push_interface(interface);
+            self.textbox.open();
+        }
+    }
+}
+
+impl Scene for ServerCacheScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+        self.textbox.update(game_io);
+        self.handle_events(game_io);
+
+        if game_io.is_in_transition() || self.textbox.is_open() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thick);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let row = &self.rows[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &row.label);
+
+                text_style.bounds.x = position.x + SIZE_OFFSET;
+                text_style.draw(game_io, sprite_queue, &format_size(row.size));
+            },
+        );
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("CACHE").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
+
+fn folder_size(path: &str) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .flat_map(|entry| entry.metadata())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn format_size(size: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+
+    if size >= MIB {
+        format!("{:.1} MiB", size as f32 / MIB as f32)
+    } else {
+        format!("{} KiB", size.div_ceil(KIB))
+    }
+}
@@ -58,7 +58,7 @@ impl ServerListScene {
         let cursor_start = scrollable_frame.body_bounds().top_left() + Vec2::new(0.0, 5.0);
 
         let mut scroll_tracker = ScrollTracker::new(game_io, 7);
-        scroll_tracker.define_cursor(cursor_start, 16.0);
+        scroll_tracker.define_text_cursor(game_io, cursor_start, 16.0);
         scroll_tracker.define_scrollbar(
             scrollable_frame.scroll_start(),
             scrollable_frame.scroll_end(),
@@ -447,7 +447,6 @@ impl Scene for ServerListScene {
         let mut text_style = TextStyle::new_monospace(game_io, FontName::Thin)
             .with_shadow_color(TEXT_DARK_SHADOW_COLOR);
 
-        const HEIGHT: f32 = 16.0;
         const INDICATOR_LEFT_MARGIN: f32 = 210.0;
         const TEXT_OFFSET: Vec2 = Vec2::new(10.0, 3.0);
 
@@ -474,7 +473,7 @@ impl Scene for ServerListScene {
                 .set_position(Vec2::new(INDICATOR_LEFT_MARGIN, y));
             sprite_queue.draw_sprite(&self.status_sprite);
 
-            y += HEIGHT;
+            y += self.scroll_tracker.cursor_multiplier();
         }
 
         self.scroll_tracker.draw_scrollbar(&mut sprite_queue);
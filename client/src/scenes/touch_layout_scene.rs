@@ -0,0 +1,174 @@
+use crate::bindable::SpriteColorMode;
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::{Config, TouchBinding, TouchLayout, TOUCH_BUTTONS, TOUCH_PAGE_COUNT};
+use framework::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+enum Event {
+    ResetPage,
+}
+
+/// Edits the bindings for the mobile overlay, changes are saved with the rest of the config
+pub struct TouchLayoutScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    ui_input_tracker: UiInputTracker,
+    list: ScrollableList,
+    page: usize,
+    config: Rc<RefCell<Config>>,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl TouchLayoutScene {
+    pub fn new(game_io: &mut GameIO, config: Rc<RefCell<Config>>) -> Box<Self> {
+        let list_bounds = Rect::new(8.0, 20.0, RESOLUTION_F.x - 16.0, RESOLUTION_F.y - 28.0);
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        let mut scene = Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            ui_input_tracker: UiInputTracker::new(),
+            list: ScrollableList::new(game_io, list_bounds, 16.0),
+            page: 0,
+            config,
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        });
+
+        scene.generate_page(game_io);
+        scene
+    }
+
+    fn generate_page(&mut self, game_io: &mut GameIO) {
+        let page = self.page;
+        let bindings = self.config.borrow().touch_layout.pages[page];
+
+        let mut children: Vec<Box<dyn UiNode>> = vec![Box::new(
+            UiButton::new_text(game_io, FontName::Thick, "Reset Page").on_activate({
+                let event_sender = self.event_sender.clone();
+
+                move || {
+                    let _ = event_sender.send(Event::ResetPage);
+                }
+            }),
+        )];
+
+        for (slot, button) in TOUCH_BUTTONS.into_iter().enumerate() {
+            let cycle = UiConfigDynamicCycle::new(
+                game_io,
+                button.into(),
+                bindings[slot],
+                self.config.clone(),
+                |_, binding| binding.label(),
+                move |_, mut config, previous_binding, cycle_right| {
+                    let options = TouchBinding::options();
+                    let binding = UiConfigDynamicCycle::cycle_slice(&options, cycle_right, |b| {
+                        b == previous_binding
+                    })
+                    .cloned()
+                    .unwrap_or(TouchBinding::None);
+
+                    config.touch_layout.pages[page][slot] = binding;
+
+                    binding
+                },
+            );
+
+            children.push(Box::new(cycle));
+        }
+
+        self.list
+            .set_label(format!("PAGE {}/{TOUCH_PAGE_COUNT}", page + 1));
+        self.list.set_children(children);
+    }
+
+    fn handle_events(&mut self, game_io: &mut GameIO) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                Event::ResetPage => {
+                    let default_layout = TouchLayout::default();
+
+                    let mut config = self.config.borrow_mut();
+                    config.touch_layout.pages[self.page] = default_layout.pages[self.page];
+                    drop(config);
+
+                    self.generate_page(game_io);
+                }
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &mut GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let focus_was_locked = self.list.is_focus_locked();
+        self.list.update(game_io, &self.ui_input_tracker);
+
+        if focus_was_locked || self.list.is_focus_locked() {
+            return;
+        }
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let transition = crate::transitions::new_scene_pop(game_io);
+            self.next_scene = NextScene::new_pop().with_transition(transition);
+            return;
+        }
+
+        // switch pages with the shoulder buttons
+        let page_offset = if self.ui_input_tracker.is_active(Input::ShoulderL) {
+            TOUCH_PAGE_COUNT - 1
+        } else if self.ui_input_tracker.is_active(Input::ShoulderR) {
+            1
+        } else {
+            return;
+        };
+
+        globals.audio.play_sound(&globals.sfx.cursor_move);
+
+        self.page = (self.page + page_offset) % TOUCH_PAGE_COUNT;
+        self.generate_page(game_io);
+    }
+}
+
+impl Scene for TouchLayoutScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+        self.handle_events(game_io);
+
+        if game_io.is_in_transition() {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        self.list.draw(game_io, &mut sprite_queue);
+
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("TOUCH BUTTONS").draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
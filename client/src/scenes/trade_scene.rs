@@ -0,0 +1,426 @@
+use crate::bindable::SpriteColorMode;
+use crate::packages::{PackageId, PackageNamespace};
+use crate::render::ui::*;
+use crate::render::*;
+use crate::resources::*;
+use crate::saves::Card;
+use framework::prelude::*;
+use packets::structures::{ItemDefinition, TradeOffer};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+const LIST_WIDTH: f32 = 112.0;
+const MONEY_STEP: usize = 100;
+
+/// Updated by the overworld scene as the server responds
+#[derive(Default)]
+pub struct TradeStatus {
+    pub offer: TradeOffer,
+    pub partner_offer: TradeOffer,
+    pub revision: u32,
+    pub confirmed: bool,
+    pub partner_confirmed: bool,
+    /// Set once the server ends the trade: (completed, reason)
+    pub ended: Option<(bool, String)>,
+}
+
+pub enum TradeAction {
+    Offer(TradeOffer),
+    Confirm(u32),
+    Cancel,
+}
+
+enum TradeEntryKind {
+    Money,
+    Card(Card),
+    Item(String),
+}
+
+struct TradeEntry {
+    kind: TradeEntryKind,
+    label: String,
+    owned: usize,
+    offered: usize,
+}
+
+impl TradeEntry {
+    fn step(&self) -> usize {
+        match self.kind {
+            TradeEntryKind::Money => MONEY_STEP,
+            _ => 1,
+        }
+    }
+}
+
+enum Event {
+    Confirm(u32),
+    Cancel,
+}
+
+pub struct TradeScene {
+    camera: Camera,
+    background: Background,
+    frame: SubSceneFrame,
+    list: ListPanel,
+    ui_input_tracker: UiInputTracker,
+    textbox: Textbox,
+    partner_name: String,
+    entries: Vec<TradeEntry>,
+    item_names: HashMap<String, String>,
+    status: Rc<RefCell<TradeStatus>>,
+    send_action: Box<dyn Fn(TradeAction)>,
+    ended: bool,
+    event_sender: flume::Sender<Event>,
+    event_receiver: flume::Receiver<Event>,
+    next_scene: NextScene,
+}
+
+impl TradeScene {
+    pub fn new(
+        game_io: &GameIO,
+        partner_name: String,
+        money: u32,
+        items: impl Iterator<Item = (String, usize)>,
+        item_registry: &HashMap<String, ItemDefinition>,
+        status: Rc<RefCell<TradeStatus>>,
+        send_action: impl Fn(TradeAction) + 'static,
+    ) -> Box<Self> {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        // tradable entries, money first
+        let mut entries = vec![TradeEntry {
+            kind: TradeEntryKind::Money,
+            label: String::from("Money"),
+            owned: money as usize,
+            offered: 0,
+        }];
+
+        let mut cards: Vec<_> = (globals.restrictions.card_iter())
+            .filter(|(_, count)| *count > 0)
+            .map(|(card, count)| (card.clone(), count))
+            .collect();
+        cards.sort_by(|(a, _), (b, _)| (&a.package_id, &a.code).cmp(&(&b.package_id, &b.code)));
+
+        entries.extend(cards.into_iter().map(|(card, owned)| TradeEntry {
+            label: card_label(game_io, &card.package_id, &card.code),
+            kind: TradeEntryKind::Card(card),
+            owned,
+            offered: 0,
+        }));
+
+        let item_names: HashMap<_, _> = (item_registry.iter())
+            .map(|(id, definition)| (id.clone(), definition.name.clone()))
+            .collect();
+
+        entries.extend(
+            items
+                .filter(|(_, count)| *count > 0)
+                .map(|(id, owned)| TradeEntry {
+                    label: item_names.get(&id).cloned().unwrap_or_else(|| id.clone()),
+                    kind: TradeEntryKind::Item(id),
+                    owned,
+                    offered: 0,
+                }),
+        );
+
+        let mut list = ListPanel::new(game_io, "OFFER", LIST_WIDTH);
+        list.set_total_items(entries.len());
+
+        let (event_sender, event_receiver) = flume::unbounded();
+
+        Box::new(Self {
+            camera: Camera::new_ui(game_io),
+            background: Background::new_sub_scene(game_io),
+            frame: SubSceneFrame::new(game_io).with_everything(true),
+            list,
+            ui_input_tracker: UiInputTracker::new(),
+            textbox: Textbox::new_navigation(game_io),
+            partner_name,
+            entries,
+            item_names,
+            status,
+            send_action: Box::new(send_action),
+            ended: false,
+            event_sender,
+            event_receiver,
+            next_scene: NextScene::None,
+        })
+    }
+
+    fn build_offer(&self) -> TradeOffer {
+        let mut offer = TradeOffer::default();
+
+        for entry in &self.entries {
+            if entry.offered == 0 {
+                continue;
+            }
+
+            match &entry.kind {
+                TradeEntryKind::Money => offer.money = entry.offered as u32,
+                TradeEntryKind::Card(card) => {
+                    offer
+                        .cards
+                        .push((card.package_id.clone(), card.code.clone(), entry.offered))
+                }
+                TradeEntryKind::Item(id) => offer.items.push((id.clone(), entry.offered)),
+            }
+        }
+
+        offer
+    }
+
+    fn describe_offer(&self, game_io: &GameIO, offer: &TradeOffer) -> String {
+        if offer.is_empty() {
+            return String::from("  Nothing");
+        }
+
+        let mut lines = Vec::new();
+
+        if offer.money > 0 {
+            lines.push(format!("  {}$", offer.money));
+        }
+
+        for (package_id, code, count) in &offer.cards {
+            let label = card_label(game_io, package_id, code);
+            lines.push(format!("  {label} x{count}"));
+        }
+
+        for (id, count) in &offer.items {
+            let name = self.item_names.get(id).unwrap_or(id);
+            lines.push(format!("  {name} x{count}"));
+        }
+
+        lines.join("\n")
+    }
+
+    fn handle_status(&mut self, game_io: &GameIO) {
+        // let the player finish answering open questions first
+        if self.ended || !self.textbox.is_complete() {
+            return;
+        }
+
+        let status = self.status.borrow();
+
+        let Some((completed, reason)) = &status.ended else {
+            return;
+        };
+
+        self.ended = true;
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        let message = if *completed {
+            globals.audio.play_sound(&globals.sfx.customize_complete);
+            String::from("Trade complete!")
+        } else {
+            globals.audio.play_sound(&globals.sfx.cursor_error);
+            reason.clone()
+        };
+
+        drop(status);
+
+        self.textbox.push_interface(TextboxMessage::new(message));
+        self.textbox.open();
+    }
+
+    fn handle_events(&mut self) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                Event::Confirm(revision) => (self.send_action)(TradeAction::Confirm(revision)),
+                Event::Cancel => (self.send_action)(TradeAction::Cancel),
+            }
+        }
+    }
+
+    fn handle_input(&mut self, game_io: &GameIO) {
+        self.ui_input_tracker.update(game_io);
+
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if self.ui_input_tracker.is_active(Input::Cancel) {
+            globals.audio.play_sound(&globals.sfx.cursor_cancel);
+
+            let event_sender = self.event_sender.clone();
+            let question = String::from("Cancel the trade?");
+            let interface = TextboxQuestion::new(question, move |yes| {
+                if yes {
+                    let _ = event_sender.send(Event::Cancel);
+                }
+            });
+
+            self.textbox.push_interface(interface);
+            self.textbox.open();
+            return;
+        }
+
+        // selection
+        self.list.handle_input(game_io, &self.ui_input_tracker);
+
+        // adjusting the offered amount
+        let delta = if self.ui_input_tracker.is_active(Input::Right) {
+            1
+        } else if self.ui_input_tracker.is_active(Input::Left) {
+            -1
+        } else {
+            0
+        };
+
+        if delta != 0 {
+            let index = self.list.selected_index();
+
+            if let Some(entry) = self.entries.get_mut(index) {
+                let step = entry.step();
+
+                let offered = if delta > 0 {
+                    (entry.offered + step).min(entry.owned)
+                } else {
+                    entry.offered.saturating_sub(step)
+                };
+
+                if offered != entry.offered {
+                    entry.offered = offered;
+                    globals.audio.play_sound(&globals.sfx.cursor_move);
+                    (self.send_action)(TradeAction::Offer(self.build_offer()));
+                } else {
+                    globals.audio.play_sound(&globals.sfx.cursor_error);
+                }
+            }
+        }
+
+        // confirming the trade
+        if self.ui_input_tracker.is_active(Input::Confirm) {
+            let status = self.status.borrow();
+
+            if status.confirmed {
+                globals.audio.play_sound(&globals.sfx.cursor_error);
+                return;
+            }
+
+            globals.audio.play_sound(&globals.sfx.cursor_select);
+
+            // confirm what the server holds, not what we last sent
+            let question = format!(
+                "Give:\n{}\nReceive:\n{}\nTrade?",
+                self.describe_offer(game_io, &status.offer),
+                self.describe_offer(game_io, &status.partner_offer)
+            );
+
+            let revision = status.revision;
+            let event_sender = self.event_sender.clone();
+            let interface = TextboxQuestion::new(question, move |yes| {
+                if yes {
+                    let _ = event_sender.send(Event::Confirm(revision));
+                }
+            });
+
+            drop(status);
+
+            self.textbox.push_interface(interface);
+            self.textbox.open();
+        }
+    }
+}
+
+impl Scene for TradeScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn enter(&mut self, game_io: &mut GameIO) {
+        self.textbox.use_player_avatar(game_io);
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.background.update();
+
+        if self.textbox.is_complete() {
+            self.textbox.close();
+
+            // leave after the result is read
+            if self.ended && self.next_scene.is_none() {
+                let transition = crate::transitions::new_scene_pop(game_io);
+                self.next_scene = NextScene::new_pop().with_transition(transition);
+            }
+        }
+
+        self.textbox.update(game_io);
+        self.handle_events();
+        self.handle_status(game_io);
+
+        if game_io.is_in_transition() || self.textbox.is_open() || self.ended {
+            return;
+        }
+
+        self.handle_input(game_io);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.background.draw(game_io, render_pass);
+
+        let mut sprite_queue =
+            SpriteColorQueue::new(game_io, &self.camera, SpriteColorMode::Multiply);
+
+        let mut text_style = TextStyle::new(game_io, FontName::Thin);
+        text_style.shadow_color = TEXT_DARK_SHADOW_COLOR;
+
+        // draw list, counts are right aligned
+        let body_bounds = self.list.body_bounds();
+
+        self.list.draw(
+            game_io,
+            &mut sprite_queue,
+            |sprite_queue, index, position| {
+                let entry = &self.entries[index];
+
+                text_style.bounds.set_position(position);
+                text_style.draw(game_io, sprite_queue, &entry.label);
+
+                let count_text = format!("{}/{}", entry.offered, entry.owned);
+                let count_width = text_style.measure(&count_text).size.x;
+                text_style.bounds.x = body_bounds.right() - count_width - 3.0;
+                text_style.draw(game_io, sprite_queue, &count_text);
+            },
+        );
+
+        // draw the partner's offer
+        let details_bounds = self.list.details_bounds();
+        text_style.bounds.set_position(details_bounds.top_left());
+        text_style.bounds.width = details_bounds.width;
+
+        let status = self.status.borrow();
+        let ready_text = |confirmed| if confirmed { "Ready" } else { "Deciding" };
+
+        let details = format!(
+            "{}:\n{}\n\n{}: {}\nYou: {}",
+            self.partner_name,
+            self.describe_offer(game_io, &status.partner_offer),
+            self.partner_name,
+            ready_text(status.partner_confirmed),
+            ready_text(status.confirmed),
+        );
+
+        drop(status);
+
+        text_style.draw(game_io, &mut sprite_queue, &details);
+
+        // draw frame
+        self.frame.draw(&mut sprite_queue);
+        SceneTitle::new("TRADE").draw(game_io, &mut sprite_queue);
+
+        self.textbox.draw(game_io, &mut sprite_queue);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
+
+fn card_label(game_io: &GameIO, package_id: &PackageId, code: &str) -> String {
+    let globals = game_io.resource::<Globals>().unwrap();
+
+    let name = (globals.card_packages)
+        .package_or_fallback(PackageNamespace::Local, package_id)
+        .map(|package| package.card_properties.short_name.as_ref())
+        .unwrap_or(package_id.as_str());
+
+    format!("{name} {code}")
+}
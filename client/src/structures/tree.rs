@@ -42,6 +42,13 @@ impl<T: Clone> Clone for TreeNode<T> {
             value: self.value.clone(),
         }
     }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.index = source.index;
+        self.parent = source.parent;
+        self.children.clone_from(&source.children);
+        self.value.clone_from(&source.value);
+    }
 }
 
 pub struct Tree<T> {
@@ -311,6 +318,23 @@ impl<T: Clone> Clone for Tree<T> {
             nodes: self.nodes.clone(),
         }
     }
+
+    /// Reuses existing node allocations
+    fn clone_from(&mut self, source: &Self) {
+        self.len = source.len;
+        self.nodes.truncate(source.nodes.len());
+
+        let reused_len = self.nodes.len();
+
+        for ((generation, node), (source_generation, source_node)) in
+            self.nodes.iter_mut().zip(&source.nodes)
+        {
+            *generation = *source_generation;
+            node.clone_from(source_node);
+        }
+
+        (self.nodes).extend_from_slice(&source.nodes[reused_len..]);
+    }
 }
 
 // maybe this should be returning nodes and not values?
@@ -428,4 +452,26 @@ mod tests {
         assert_eq!(index.index, index2.index);
         assert_eq!(tree.get(index), None);
     }
+
+    #[test]
+    fn clone_from() {
+        let mut source: Tree<i32> = Tree::new(0);
+        let child = source.insert_root_child(1);
+        source.insert_child(child, 2);
+
+        let mut larger: Tree<i32> = Tree::new(5);
+        for i in 0..5 {
+            larger.insert_root_child(i);
+        }
+
+        let mut smaller: Tree<i32> = Tree::new(5);
+
+        for tree in [&mut larger, &mut smaller] {
+            tree.clone_from(&source);
+
+            assert_eq!(tree.len(), source.len());
+            assert_eq!(tree.values().collect::<Vec<_>>(), [&0, &1, &2]);
+            assert_eq!(tree.get(child), Some(&1));
+        }
+    }
 }
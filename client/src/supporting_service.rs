@@ -1,5 +1,5 @@
 use crate::packages::PackageNamespace;
-use crate::resources::Globals;
+use crate::resources::{ControllerPrompt, Globals, ProfileSegment, CONTROLLER_PROMPT_BUTTONS};
 use framework::prelude::{GameIO, GameService};
 use packets::structures::PackageCategory;
 
@@ -24,6 +24,7 @@ impl SupportingServiceComm {
 
 pub struct SupportingService {
     suspended_music: bool,
+    known_controllers: Vec<usize>,
     receiver: flume::Receiver<SupportingServiceEvent>,
 }
 
@@ -35,13 +36,100 @@ impl SupportingService {
 
         Self {
             suspended_music: false,
+            known_controllers: Vec::new(),
             receiver,
         }
     }
+
+    /// Switches to the input profile bound to a newly connected gamepad,
+    /// prompts for a gamepad when the active one disconnects or another joins without a bound profile
+    fn detect_controllers(&mut self, game_io: &mut GameIO) {
+        let controllers = game_io.input().controllers();
+
+        let new_controllers: Vec<_> = controllers
+            .iter()
+            .filter(|controller| !self.known_controllers.contains(&controller.id()))
+            .map(|controller| (controller.id(), controller.guid().to_string()))
+            .collect();
+
+        let previous_controllers = std::mem::take(&mut self.known_controllers);
+        self.known_controllers = controllers.iter().map(|c| c.id()).collect();
+
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+        let config = &mut globals.config;
+
+        let active_disconnected = previous_controllers.contains(&config.controller_index)
+            && !self.known_controllers.contains(&config.controller_index);
+
+        if active_disconnected {
+            log::info!("Gamepad {} disconnected", config.controller_index);
+            globals.controller_prompt = Some(ControllerPrompt::Reconnect);
+        }
+
+        if new_controllers.is_empty() {
+            return;
+        }
+
+        let mut bound_profile = false;
+
+        for (id, guid) in new_controllers {
+            if let Some(index) = config.input_profile_for_device(&guid) {
+                config.switch_input_profile(index);
+                config.controller_index = id;
+                bound_profile = true;
+            }
+        }
+
+        if bound_profile {
+            globals.controller_prompt = None;
+        } else if self.known_controllers.len() > 1 && globals.controller_prompt.is_none() {
+            globals.controller_prompt = Some(ControllerPrompt::Assign);
+        }
+    }
+
+    /// The first gamepad to press a button becomes the active gamepad, pressing a key dismisses the prompt
+    fn resolve_controller_prompt(&mut self, game_io: &mut GameIO) {
+        let globals = game_io.resource::<Globals>().unwrap();
+
+        if globals.controller_prompt.is_none() {
+            return;
+        }
+
+        let input = game_io.input();
+        let pressed_controller = (input.controllers().iter())
+            .map(|controller| controller.id())
+            .find(|&id| {
+                CONTROLLER_PROMPT_BUTTONS
+                    .iter()
+                    .any(|&button| input.was_button_just_pressed(id, button))
+            });
+        let pressed_key = input.latest_key().is_some();
+
+        let globals = game_io.resource_mut::<Globals>().unwrap();
+
+        if let Some(id) = pressed_controller {
+            globals.config.controller_index = id;
+        } else if !pressed_key {
+            return;
+        }
+
+        globals.controller_prompt = None;
+        globals.audio.play_sound(&globals.sfx.cursor_select);
+    }
 }
 
 impl GameService for SupportingService {
     fn pre_update(&mut self, game_io: &mut GameIO) {
+        // the render segment for the previous frame ends in the profiler overlay
+        let globals = game_io.resource::<Globals>().unwrap();
+        let mut frame_profiler = globals.frame_profiler.borrow_mut();
+        frame_profiler.end_frame();
+        frame_profiler.begin(ProfileSegment::Update);
+        drop(frame_profiler);
+
+        self.detect_controllers(game_io);
+        self.resolve_controller_prompt(game_io);
+
         let suspended = game_io.suspended();
         let globals = game_io.resource_mut::<Globals>().unwrap();
         globals.network.tick();
@@ -67,6 +155,8 @@ impl GameService for SupportingService {
         }
 
         globals.audio.drop_empty_sinks();
+        globals.audio.update_ducking();
+        globals.audio.update_music_transitions();
 
         while let Ok(event) = self.receiver.try_recv() {
             match event {
@@ -79,5 +169,10 @@ impl GameService for SupportingService {
                 }
             }
         }
+
+        let globals = game_io.resource::<Globals>().unwrap();
+        let mut frame_profiler = globals.frame_profiler.borrow_mut();
+        frame_profiler.end();
+        frame_profiler.begin(ProfileSegment::Render);
     }
 }
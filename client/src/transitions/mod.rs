@@ -41,11 +41,19 @@ pub fn new_sub_scene_pop(game_io: &GameIO) -> ColorFadeTransition {
 }
 
 pub fn new_battle(game_io: &GameIO) -> ColorFadeTransition {
-    ColorFadeTransition::new(game_io, Color::WHITE, BATTLE_FADE_DURATION)
+    new_colored_battle(game_io, Color::WHITE)
+}
+
+pub fn new_colored_battle(game_io: &GameIO, color: Color) -> ColorFadeTransition {
+    ColorFadeTransition::new(game_io, color, BATTLE_FADE_DURATION)
 }
 
 pub fn new_battle_pop(game_io: &GameIO) -> ColorFadeTransition {
-    ColorFadeTransition::new(game_io, Color::WHITE, DEFAULT_FADE_DURATION)
+    new_colored_battle_pop(game_io, Color::WHITE)
+}
+
+pub fn new_colored_battle_pop(game_io: &GameIO, color: Color) -> ColorFadeTransition {
+    ColorFadeTransition::new(game_io, color, DEFAULT_FADE_DURATION)
 }
 
 pub fn new_connect(game_io: &GameIO) -> ColorFadeTransition {
@@ -1,8 +1,9 @@
 // Increment VERSION_ITERATION packets/src/lib.rs if packets are added or modified
 
 use super::structures::{BattleStatistics, Direction};
-use crate::structures::{ActorId, PackageId};
+use crate::structures::{ActorId, ChatChannel, FileHash, PackageId, TradeOffer};
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use strum::IntoStaticStr;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -26,15 +27,30 @@ pub enum ClientPacket {
         path: String,
         last_modified: u64,
     },
-    Asset {
+    /// Answers CachedAudio when the track is no longer in the shared audio cache
+    CachedAudioMissing {
+        path: String,
+    },
+    /// Replaces the stored data for the asset type, followed by AssetStream packets
+    AssetStreamStart {
+        asset_type: ClientAssetType,
+        size: u64,
+    },
+    AssetStream {
         asset_type: ClientAssetType,
         data: Vec<u8>,
     },
+    /// Drops partially uploaded avatar assets
+    AssetStreamCancel,
     Login {
         username: String,
         identity: Vec<u8>,
         data: String,
     },
+    /// Sent instead of Login to reclaim a session after a dropped connection
+    Resume {
+        token: Vec<u8>,
+    },
     Logout,
     RequestJoin,
     Ready {
@@ -60,9 +76,34 @@ pub enum ClientPacket {
     Emote {
         emote_id: String,
     },
+    /// An emote from an installed emote package, only visible to players with the package
+    PackageEmote {
+        package_id: PackageId,
+        emote_id: String,
+    },
     CustomWarp {
         tile_object_id: u32,
     },
+    ChatMessage {
+        channel: ChatChannel,
+        message: String,
+    },
+    /// A slash command the server registered, sent without the leading slash
+    ChatCommand {
+        name: String,
+        args: String,
+    },
+    FastTravelRequest {
+        map_path: String,
+        object_id: u32,
+    },
+    PartyInvite {
+        actor_id: ActorId,
+    },
+    PartyResponse {
+        accepted: bool,
+    },
+    PartyLeave,
     ObjectInteraction {
         tile_object_id: u32,
         button: u8,
@@ -101,8 +142,34 @@ pub enum ClientPacket {
     ItemUse {
         item_id: String,
     },
+    RankedQueue {
+        queued: bool,
+    },
+    /// Extra addresses other players can try reaching this client at during netplay,
+    /// such as LAN addresses or addresses reported by STUN servers
+    NetplayCandidates {
+        addresses: Vec<SocketAddr>,
+    },
+    TradeResponse {
+        accepted: bool,
+    },
+    /// Replaces the player's side of the trade
+    TradeOffer {
+        offer: TradeOffer,
+    },
+    /// Accepts both offers as of the revision from the latest TradeUpdate
+    TradeConfirm {
+        revision: u32,
+    },
+    TradeCancel,
     EncounterStart,
+    /// The folder used for the battle that just started, for servers to re-validate
+    BattleDeck {
+        cards: Vec<(PackageId, String)>,
+    },
     BattleResults {
         battle_stats: BattleStatistics,
+        /// Signed using the result key from InitiateNetplay, None for other battles
+        signature: Option<[u8; 32]>,
     },
 }
@@ -1,8 +1,10 @@
 // Increment VERSION_ITERATION lib.rs if packets are added or modified
 
 use crate::structures::{
-    FileHash, Input, InstalledBlock, InstalledSwitchDrive, PackageCategory, PackageId,
+    BattleRole, BattleTimers, FileHash, Input, InstalledBlock, InstalledSwitchDrive,
+    PackageCategory, PackageId,
 };
+use network_channels::Reliability;
 use serde::{Deserialize, Serialize};
 use strum::IntoStaticStr;
 
@@ -39,6 +41,11 @@ pub enum NetplayPacket {
         regular_card: Option<usize>,
         blocks: Vec<InstalledBlock>,
         drives: Vec<InstalledSwitchDrive>,
+        timers: BattleTimers,
+        /// Opts into delay based netplay without rollback, used if every player opts in
+        lockstep: bool,
+        /// The role assigned by the server, every player must agree on a single boss
+        role: BattleRole,
     },
     PackageList {
         index: usize,
@@ -63,8 +70,14 @@ pub enum NetplayPacket {
     },
     Buffer {
         index: usize,
+        /// Position of `data` in the sender's inputs, None for signals injected by the server
+        frame: Option<usize>,
         data: NetplayBufferItem,
+        /// Copies of the inputs preceding `data`, oldest first
+        redundant_data: Vec<NetplayBufferItem>,
         buffer_sizes: Vec<usize>,
+        /// How many previous inputs the sender wants each player to include, indexed by player
+        redundancy_requests: Vec<u8>,
     },
 }
 
@@ -72,11 +85,25 @@ impl NetplayPacket {
     pub fn new_disconnect_signal(index: usize) -> NetplayPacket {
         NetplayPacket::Buffer {
             index,
+            frame: None,
             data: NetplayBufferItem {
                 pressed: Vec::new(),
                 signals: vec![NetplaySignal::Disconnect],
             },
+            redundant_data: Vec::new(),
             buffer_sizes: Vec::new(),
+            redundancy_requests: Vec::new(),
+        }
+    }
+
+    pub fn reliability(&self) -> Reliability {
+        match self {
+            // framed buffers are reordered by the receiver,
+            // avoids stalling every input behind a single lost packet
+            NetplayPacket::Buffer { frame: Some(_), .. } => Reliability::Reliable,
+            // signals without a frame rely on arriving in the order they were sent
+            NetplayPacket::Buffer { frame: None, .. } => Reliability::ReliableOrdered,
+            _ => Reliability::ReliableOrdered,
         }
     }
 
@@ -95,3 +122,24 @@ impl NetplayPacket {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unframed_buffers_stay_ordered() {
+        let signal = NetplayPacket::new_disconnect_signal(0);
+        assert_eq!(signal.reliability(), Reliability::ReliableOrdered);
+
+        let buffer = NetplayPacket::Buffer {
+            index: 0,
+            frame: Some(10),
+            data: NetplayBufferItem::default(),
+            redundant_data: Vec::new(),
+            buffer_sizes: Vec::new(),
+            redundancy_requests: Vec::new(),
+        };
+        assert_eq!(buffer.reliability(), Reliability::Reliable);
+    }
+}
@@ -7,5 +7,17 @@ use strum::IntoStaticStr;
 pub enum ServerCommPacket {
     Poll,
     Alive,
-    Message { data: Vec<u8> },
+    Message {
+        data: Vec<u8>,
+    },
+    ClusterStatus {
+        /// Address clients should use to connect to the sender
+        public_address: String,
+        areas: Vec<String>,
+        player_count: usize,
+    },
+    ClusterEvent {
+        name: String,
+        data: Vec<u8>,
+    },
 }
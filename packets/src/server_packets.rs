@@ -39,6 +39,13 @@ pub enum ServerPacket {
     Kick {
         reason: String,
     },
+    ResumeToken {
+        token: Vec<u8>,
+    },
+    /// The uploaded avatar failed validation, the previous avatar is kept
+    AvatarRejected {
+        reason: String,
+    },
     RemoveAsset {
         path: String,
     },
@@ -52,6 +59,12 @@ pub enum ServerPacket {
     AssetStream {
         data: Vec<u8>,
     },
+    /// Asks the client to load audio from its shared cache, answered by CachedAudioMissing
+    CachedAudio {
+        name: String,
+        last_modified: u64,
+        hash: FileHash,
+    },
     Preload {
         asset_path: String,
         data_type: AssetDataType,
@@ -63,6 +76,12 @@ pub enum ServerPacket {
     MapUpdate {
         map_path: String,
     },
+    AnimateTile {
+        x: i32,
+        y: i32,
+        z: i32,
+        gid: u32,
+    },
     Health {
         health: i32,
     },
@@ -88,6 +107,10 @@ pub enum ServerPacket {
         code: String,
         count: isize,
     },
+    CardPack {
+        name: String,
+        cards: Vec<(PackageId, String)>,
+    },
     AddBlock {
         package_id: PackageId,
         color: BlockColor,
@@ -100,6 +123,22 @@ pub enum ServerPacket {
     PlaySound {
         path: String,
     },
+    PlaySoundAt {
+        path: String,
+        x: f32,
+        y: f32,
+        z: f32,
+    },
+    PlayMusic {
+        path: String,
+        loops: bool,
+    },
+    PlayStinger {
+        path: String,
+    },
+    PlayAmbience {
+        path: String,
+    },
     ExcludeObject {
         id: u32,
     },
@@ -112,6 +151,36 @@ pub enum ServerPacket {
     IncludeActor {
         actor_id: ActorId,
     },
+    ChatMessage {
+        channel: ChatChannel,
+        /// None for messages sent by the server
+        sender_id: Option<ActorId>,
+        sender_name: String,
+        message: String,
+    },
+    /// Replaces the list of slash commands registered by server scripts
+    ChatCommands {
+        commands: Vec<ChatCommandInfo>,
+    },
+    /// Displayed as a toast and kept in the player's notification list
+    Notification {
+        message: String,
+    },
+    ObjectMinimapMarker {
+        id: u32,
+        visible: bool,
+    },
+    ActorMinimapMarker {
+        actor_id: ActorId,
+        visible: bool,
+    },
+    PartyInvite {
+        inviter_name: String,
+    },
+    /// Sent when members join, leave, or change, the leader is listed first. Empty when not in a party
+    PartyUpdate {
+        members: Vec<PartyMember>,
+    },
     MoveCamera {
         x: f32,
         y: f32,
@@ -204,6 +273,49 @@ pub enum ServerPacket {
     RemoveShopItem {
         id: String,
     },
+    RankedSeason {
+        info: RankedSeasonInfo,
+    },
+    RankedQueueStatus {
+        queued: bool,
+    },
+    TradeRequest {
+        partner_name: String,
+    },
+    TradeStart {
+        partner_name: String,
+    },
+    /// Sent whenever either offer changes or is confirmed,
+    /// offers change the revision and clear confirmations
+    TradeUpdate {
+        offer: TradeOffer,
+        partner_offer: TradeOffer,
+        revision: u32,
+        confirmed: bool,
+        partner_confirmed: bool,
+    },
+    TradeEnd {
+        completed: bool,
+        reason: String,
+    },
+    /// Adds or replaces the quest with a matching id
+    QuestUpdate {
+        quest: Quest,
+    },
+    QuestProgress {
+        id: String,
+        objective_index: usize,
+        progress: u32,
+    },
+    QuestRemove {
+        id: String,
+    },
+    RegisterAchievement {
+        definition: AchievementDefinition,
+    },
+    UnlockAchievement {
+        id: String,
+    },
     ReferServer {
         name: String,
         address: String,
@@ -226,12 +338,17 @@ pub enum ServerPacket {
     },
     InitiateEncounter {
         package_path: String,
-        data: Option<String>,
+        data: Option<EncounterData>,
+        preview: Option<EncounterPreview>,
     },
     InitiateNetplay {
         package_path: Option<String>,
-        data: Option<String>,
+        data: Option<EncounterData>,
         remote_players: Vec<RemotePlayerInfo>,
+        /// Used to sign the battle results, unique to each player
+        result_key: Vec<u8>,
+        /// The recipient's role
+        role: BattleRole,
     },
     ActorConnected {
         actor_id: ActorId,
@@ -274,6 +391,11 @@ pub enum ServerPacket {
         actor_id: ActorId,
         emote_id: String,
     },
+    ActorPackageEmote {
+        actor_id: ActorId,
+        package_id: PackageId,
+        emote_id: String,
+    },
     ActorAnimate {
         actor_id: ActorId,
         state: String,
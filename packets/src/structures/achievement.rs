@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// An achievement defined by a server or library package
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    /// Describes how to unlock the achievement
+    pub description: String,
+    pub icon_texture_path: String,
+}
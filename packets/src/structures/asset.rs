@@ -58,6 +58,25 @@ impl AssetData {
         AssetData::Text(text)
     }
 
+    /// Text from text assets, decompressing if necessary
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Self::Text(text) => Some(text.clone()),
+            Self::CompressedText(bytes) => {
+                use flate2::read::ZlibDecoder;
+                use std::io::prelude::*;
+
+                let mut text = String::new();
+                ZlibDecoder::new(bytes.as_slice())
+                    .read_to_string(&mut text)
+                    .ok()?;
+
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
     pub fn data_type(&self) -> AssetDataType {
         match self {
             Self::Text(_) => AssetDataType::Text,
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BattleRole {
+    #[default]
+    Player,
+    /// Controls a boss defined by the encounter package, against every other player
+    Boss,
+}
@@ -12,6 +12,8 @@ pub struct BattleStatistics {
     pub health: i32,
     pub emotion: Emotion, // todo: track
     pub ran: bool,
+    /// The player backed out from the encounter preview
+    pub declined: bool,
     pub turns: u32,
     pub score: i32,
     pub ally_survivors: Vec<BattleSurvivor>,
@@ -25,6 +27,9 @@ pub struct BattleStatistics {
     pub movements: usize,
     pub max_kill_chain: usize, // todo: track
     pub counters: usize,       // todo: track
+
+    /// Hash of the simulation when the battle ended, matches between netplay clients unless they desynced
+    pub checksum: u64,
 }
 
 impl BattleStatistics {
@@ -32,6 +37,19 @@ impl BattleStatistics {
         Self::default()
     }
 
+    /// Signs the fields servers compare between netplay clients,
+    /// `key` is provided by the server when netplay is initiated
+    pub fn sign_results(&self, key: &[u8]) -> [u8; 32] {
+        let mut bytes = Vec::new();
+        bytes.extend(self.health.to_le_bytes());
+        bytes.push(self.ran as u8);
+        bytes.push(self.declined as u8);
+        bytes.extend(self.time.to_le_bytes());
+        bytes.extend(self.checksum.to_le_bytes());
+
+        hmac_sha256::HMAC::mac(bytes, key)
+    }
+
     pub fn calculate_score(&mut self) {
         let mut score = 0;
 
@@ -82,3 +100,34 @@ impl BattleStatistics {
         self.score = score;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signatures_cover_results() {
+        let key = b"key";
+        let statistics = BattleStatistics {
+            health: 100,
+            time: 600,
+            checksum: 5,
+            ..Default::default()
+        };
+
+        let signature = statistics.sign_results(key);
+        assert_ne!(signature, statistics.sign_results(b"other key"));
+
+        let tampered = BattleStatistics {
+            health: 0,
+            ..statistics.clone()
+        };
+        assert_ne!(signature, tampered.sign_results(key));
+
+        let tampered = BattleStatistics {
+            checksum: 6,
+            ..statistics
+        };
+        assert_ne!(signature, tampered.sign_results(key));
+    }
+}
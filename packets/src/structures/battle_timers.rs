@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Stall prevention for netplay, times are in seconds and 0 disables the timer
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BattleTimers {
+    /// Time a player has to open card select after the turn gauge fills
+    pub turn_time: u16,
+    /// Time before the match is decided by judgment
+    pub match_time: u16,
+}
+
+impl BattleTimers {
+    /// Resolves the settings every player will use, the strictest enabled timer wins.
+    /// Order independent so each client resolves the same result
+    pub fn negotiate<'a>(timers: impl IntoIterator<Item = &'a BattleTimers>) -> BattleTimers {
+        let strictest = |a: u16, b: u16| match (a, b) {
+            (0, b) => b,
+            (a, 0) => a,
+            (a, b) => a.min(b),
+        };
+
+        timers
+            .into_iter()
+            .fold(BattleTimers::default(), |acc, timers| BattleTimers {
+                turn_time: strictest(acc.turn_time, timers.turn_time),
+                match_time: strictest(acc.match_time, timers.match_time),
+            })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.turn_time > 0 || self.match_time > 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn strictest_enabled_timer_wins() {
+        let timers = [
+            BattleTimers {
+                turn_time: 0,
+                match_time: 300,
+            },
+            BattleTimers {
+                turn_time: 20,
+                match_time: 180,
+            },
+            BattleTimers::default(),
+        ];
+
+        let expected = BattleTimers {
+            turn_time: 20,
+            match_time: 180,
+        };
+
+        assert_eq!(BattleTimers::negotiate(&timers), expected);
+        assert_eq!(BattleTimers::negotiate(timers.iter().rev()), expected);
+        assert!(!BattleTimers::negotiate(&[]).is_enabled());
+    }
+}
@@ -0,0 +1,25 @@
+use super::ActorId;
+use serde::{Deserialize, Serialize};
+
+/// Longer messages are rejected by the server
+pub const MAX_CHAT_MESSAGE_LENGTH: usize = 200;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ChatChannel {
+    /// Players in the same area
+    Area,
+    /// A single player, paired with the player receiving the message
+    Whisper(ActorId),
+    /// Players in the same party, parties are assigned by the server
+    Party,
+    /// Messages sent by the server
+    System,
+}
+
+/// A slash command registered by a server script, sent to clients for autocompletion
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ChatCommandInfo {
+    /// Without the leading slash
+    pub name: String,
+    pub description: String,
+}
@@ -0,0 +1,130 @@
+use super::PackageId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Folder rules set by a server, read from the [deck] table of the restrictions file
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DeckRules {
+    /// Cards from these packages can't be placed in a folder
+    pub banned_packages: Vec<PackageId>,
+    /// Lowers the copy limit set by each card package
+    pub max_copies: Option<usize>,
+    /// Codes cards are limited to, every code is allowed when empty
+    pub allowed_codes: Vec<String>,
+    /// The total points a folder can spend
+    pub point_budget: Option<usize>,
+    /// Point costs by package id, unlisted cards cost nothing
+    pub points: HashMap<String, usize>,
+}
+
+impl DeckRules {
+    pub fn is_package_banned(&self, package_id: &PackageId) -> bool {
+        self.banned_packages.contains(package_id)
+    }
+
+    pub fn is_code_allowed(&self, code: &str) -> bool {
+        self.allowed_codes.is_empty() || self.allowed_codes.iter().any(|c| c == code)
+    }
+
+    pub fn copy_limit(&self, package_limit: usize) -> usize {
+        match self.max_copies {
+            Some(max_copies) => package_limit.min(max_copies),
+            None => package_limit,
+        }
+    }
+
+    pub fn card_points(&self, package_id: &PackageId) -> usize {
+        self.points
+            .get(package_id.as_str())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn is_over_budget(&self, total_points: usize) -> bool {
+        self.point_budget
+            .is_some_and(|budget| total_points > budget)
+    }
+
+    /// Checks the rules that don't depend on package info,
+    /// blank package ids are treated as empty slots
+    pub fn validate<'a>(
+        &self,
+        cards: impl IntoIterator<Item = (&'a PackageId, &'a str)>,
+    ) -> Result<(), String> {
+        let mut copies = HashMap::<&PackageId, usize>::new();
+        let mut total_points = 0;
+
+        for (package_id, code) in cards {
+            if package_id.is_blank() {
+                continue;
+            }
+
+            if self.is_package_banned(package_id) {
+                return Err(format!("{package_id} is banned"));
+            }
+
+            if !self.is_code_allowed(code) {
+                return Err(format!("{package_id} {code} uses a restricted code"));
+            }
+
+            let count = copies.entry(package_id).or_default();
+            *count += 1;
+
+            if self
+                .max_copies
+                .is_some_and(|max_copies| *count > max_copies)
+            {
+                return Err(format!("Too many copies of {package_id}"));
+            }
+
+            total_points += self.card_points(package_id);
+        }
+
+        if self.is_over_budget(total_points) {
+            return Err(format!("Spent {total_points} points, over the budget"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        let cannon = PackageId::from("cannon");
+        let sword = PackageId::from("sword");
+        let blank = PackageId::new_blank();
+
+        let rules = DeckRules {
+            banned_packages: vec![PackageId::from("bomb")],
+            max_copies: Some(2),
+            allowed_codes: vec![String::from("A"), String::from("*")],
+            point_budget: Some(5),
+            points: HashMap::from([(String::from("sword"), 2)]),
+        };
+
+        let valid = [(&cannon, "A"), (&cannon, "*"), (&sword, "A"), (&blank, "!")];
+        assert_eq!(rules.validate(valid), Ok(()));
+
+        let bomb = PackageId::from("bomb");
+        assert!(rules.validate([(&bomb, "A")]).is_err());
+        assert!(rules.validate([(&cannon, "B")]).is_err());
+        assert!(rules.validate([(&cannon, "A"); 3]).is_err());
+
+        let expensive = [(&sword, "A"), (&sword, "A"), (&cannon, "A")];
+        assert_eq!(rules.validate(expensive), Ok(()));
+
+        let rules = DeckRules {
+            point_budget: Some(3),
+            ..rules
+        };
+        assert!(rules.validate(expensive).is_err());
+
+        assert_eq!(DeckRules::default().copy_limit(4), 4);
+        assert_eq!(rules.copy_limit(4), 2);
+    }
+}
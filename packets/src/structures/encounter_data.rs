@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+/// Structured data passed from servers to encounter packages, received by encounter_init as a Lua value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EncounterData {
+    Bool(bool),
+    Integer(i64),
+    Number(f64),
+    String(String),
+    Table(Vec<(EncounterData, EncounterData)>),
+}
+
+impl EncounterData {
+    /// Prevents self referencing tables from recursing forever
+    pub const MAX_DEPTH: usize = 32;
+
+    /// Finds the value stored under a string key, None for other types
+    pub fn get(&self, key: &str) -> Option<&EncounterData> {
+        let EncounterData::Table(pairs) = self else {
+            return None;
+        };
+
+        pairs
+            .iter()
+            .find(|(k, _)| matches!(k, EncounterData::String(s) if s == key))
+            .map(|(_, value)| value)
+    }
+
+    /// Numbers without a fractional part are accepted, as Lua numbers may not be integers
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            EncounterData::Integer(n) => Some(*n),
+            EncounterData::Number(n) if n.fract() == 0.0 => Some(*n as i64),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "mlua")]
+impl<'lua> mlua::FromLua<'lua> for EncounterData {
+    fn from_lua(lua_value: mlua::Value<'lua>, _: &'lua mlua::Lua) -> mlua::Result<Self> {
+        fn convert(value: mlua::Value, depth: usize) -> mlua::Result<EncounterData> {
+            let data = match value {
+                mlua::Value::Boolean(b) => EncounterData::Bool(b),
+                mlua::Value::Integer(n) => EncounterData::Integer(n),
+                mlua::Value::Number(n) => EncounterData::Number(n),
+                mlua::Value::String(s) => EncounterData::String(s.to_str()?.to_string()),
+                mlua::Value::Table(table) if depth < EncounterData::MAX_DEPTH => {
+                    let mut pairs = Vec::new();
+
+                    for pair in table.pairs::<mlua::Value, mlua::Value>() {
+                        let (key, value) = pair?;
+                        pairs.push((convert(key, depth + 1)?, convert(value, depth + 1)?));
+                    }
+
+                    EncounterData::Table(pairs)
+                }
+                _ => {
+                    return Err(mlua::Error::FromLuaConversionError {
+                        from: value.type_name(),
+                        to: "EncounterData",
+                        message: Some(String::from(
+                            "expected a boolean, number, string, or table of these",
+                        )),
+                    })
+                }
+            };
+
+            Ok(data)
+        }
+
+        convert(lua_value, 0)
+    }
+}
+
+#[cfg(feature = "rollback_mlua")]
+impl<'lua> rollback_mlua::IntoLua<'lua> for &EncounterData {
+    fn into_lua(
+        self,
+        lua: &'lua rollback_mlua::Lua,
+    ) -> rollback_mlua::Result<rollback_mlua::Value<'lua>> {
+        let value = match self {
+            EncounterData::Bool(b) => rollback_mlua::Value::Boolean(*b),
+            EncounterData::Integer(n) => rollback_mlua::Value::Integer(*n),
+            EncounterData::Number(n) => rollback_mlua::Value::Number(*n),
+            EncounterData::String(s) => rollback_mlua::Value::String(lua.create_string(s)?),
+            EncounterData::Table(pairs) => {
+                let table = lua.create_table()?;
+
+                for (key, value) in pairs {
+                    table.raw_set(key, value)?;
+                }
+
+                rollback_mlua::Value::Table(table)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let data = EncounterData::Table(vec![
+            (
+                EncounterData::String(String::from("level")),
+                EncounterData::Integer(3),
+            ),
+            (
+                EncounterData::String(String::from("rate")),
+                EncounterData::Number(0.5),
+            ),
+            (
+                EncounterData::Integer(1),
+                EncounterData::Table(vec![(
+                    EncounterData::String(String::from("boss")),
+                    EncounterData::Bool(true),
+                )]),
+            ),
+        ]);
+
+        let bytes = crate::serialize(&data);
+        let decoded: EncounterData = crate::deserialize(&bytes).unwrap();
+
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn get_by_key() {
+        let data = EncounterData::Table(vec![
+            (
+                EncounterData::String(String::from("time")),
+                EncounterData::Number(600.0),
+            ),
+            (EncounterData::Integer(1), EncounterData::Bool(true)),
+        ]);
+
+        let time = data.get("time").and_then(EncounterData::as_integer);
+        assert_eq!(time, Some(600));
+        assert_eq!(data.get("1"), None);
+        assert_eq!(EncounterData::Bool(true).get("time"), None);
+        assert_eq!(EncounterData::Number(0.5).as_integer(), None);
+    }
+}
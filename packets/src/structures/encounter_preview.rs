@@ -0,0 +1,29 @@
+use super::TextureAnimPathPair;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EncounterPreviewEnemy {
+    pub name: String,
+    /// Displayed using the IDLE state
+    pub sprite: Option<TextureAnimPathPair<'static>>,
+}
+
+/// Shown to the player while transitioning into an encounter
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EncounterPreview {
+    pub enemies: Vec<EncounterPreviewEnemy>,
+    /// Describes what's at risk or to be gained
+    pub stakes: String,
+    /// Allows the player to back out before the battle starts
+    pub declinable: bool,
+    /// Replaces the white fade into battle
+    pub fade_color: Option<(u8, u8, u8)>,
+}
+
+impl EncounterPreview {
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.enemies
+            .iter()
+            .flat_map(|enemy| enemy.sprite.iter().flat_map(|v| v.dependencies()))
+    }
+}
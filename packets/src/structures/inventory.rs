@@ -1,10 +1,33 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct ItemDefinition {
     pub name: String,
     pub description: String,
     pub consumable: bool,
+    /// Values defined by the server, such as prices or categories
+    pub metadata: HashMap<String, String>,
+}
+
+impl ItemDefinition {
+    /// The description followed by a line for each metadata value, sorted by key
+    pub fn description_with_metadata(&self) -> String {
+        let mut entries: Vec<_> = self.metadata.iter().collect();
+        entries.sort();
+
+        let mut text = self.description.clone();
+
+        for (key, value) in entries {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+
+            text.push_str(&format!("{key}: {value}"));
+        }
+
+        text
+    }
 }
 
 #[derive(Default)]
@@ -43,3 +66,30 @@ impl Inventory {
         self.items.iter().any(|(id, _)| id == item_id)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metadata_is_listed_after_the_description() {
+        let mut item = ItemDefinition {
+            name: String::from("Potion"),
+            description: String::from("Heals 50HP"),
+            consumable: true,
+            metadata: HashMap::new(),
+        };
+
+        assert_eq!(item.description_with_metadata(), "Heals 50HP");
+
+        item.metadata
+            .insert(String::from("Price"), String::from("200z"));
+        item.metadata
+            .insert(String::from("Category"), String::from("Healing"));
+
+        assert_eq!(
+            item.description_with_metadata(),
+            "Heals 50HP\nCategory: Healing\nPrice: 200z"
+        );
+    }
+}
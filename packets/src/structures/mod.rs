@@ -1,10 +1,17 @@
+mod achievement;
 mod actor_property;
 mod asset;
+mod battle_role;
 mod battle_statistics;
+mod battle_timers;
 mod bbs_post;
 mod block_color;
+mod chat;
+mod deck_rules;
 mod direction;
 mod emotion;
+mod encounter_data;
+mod encounter_preview;
 mod file_hash;
 mod generational_index;
 mod input;
@@ -13,19 +20,30 @@ mod installed_switch_drive;
 mod inventory;
 mod package_category;
 mod package_id;
+mod party;
+mod quest;
+mod ranked_season;
 mod remote_player_info;
 mod shop_item;
 mod sprite;
 mod switch_drive_slot;
 mod textbox_options;
+mod trade_offer;
 
+pub use achievement::*;
 pub use actor_property::*;
 pub use asset::*;
+pub use battle_role::*;
 pub use battle_statistics::*;
+pub use battle_timers::*;
 pub use bbs_post::*;
 pub use block_color::*;
+pub use chat::*;
+pub use deck_rules::*;
 pub use direction::*;
 pub use emotion::*;
+pub use encounter_data::*;
+pub use encounter_preview::*;
 pub use file_hash::*;
 pub use generational_index::*;
 pub use input::*;
@@ -34,8 +52,12 @@ pub use installed_switch_drive::*;
 pub use inventory::*;
 pub use package_category::*;
 pub use package_id::*;
+pub use party::*;
+pub use quest::*;
+pub use ranked_season::*;
 pub use remote_player_info::*;
 pub use shop_item::*;
 pub use sprite::*;
 pub use switch_drive_slot::*;
 pub use textbox_options::*;
+pub use trade_offer::*;
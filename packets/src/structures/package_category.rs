@@ -13,6 +13,7 @@ pub enum PackageCategory {
     Resource,
     Status,
     TileState,
+    Emote,
 }
 
 impl PackageCategory {
@@ -26,6 +27,7 @@ impl PackageCategory {
             PackageCategory::Resource => "mods/resources/",
             PackageCategory::Status => "mods/statuses/",
             PackageCategory::TileState => "mods/tile_states/",
+            PackageCategory::Emote => "mods/emotes/",
         }
     }
 
@@ -39,11 +41,12 @@ impl PackageCategory {
             PackageCategory::Resource => "resources/packages/resources/",
             PackageCategory::Status => "resources/packages/statuses/",
             PackageCategory::TileState => "resources/packages/tile_states/",
+            PackageCategory::Emote => "resources/packages/emotes/",
         }
     }
 
     pub fn requires_vm(self) -> bool {
-        self != PackageCategory::Library
+        !matches!(self, PackageCategory::Library | PackageCategory::Emote)
     }
 
     pub fn local_only(self) -> bool {
@@ -62,6 +65,7 @@ impl From<&str> for PackageCategory {
             "resource" => Self::Resource,
             "status" => Self::Status,
             "tile_state" => Self::TileState,
+            "emote" => Self::Emote,
             _ => Self::Library,
         }
     }
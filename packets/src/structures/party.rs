@@ -0,0 +1,12 @@
+use super::ActorId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PartyMember {
+    pub actor_id: ActorId,
+    pub name: String,
+    pub health: i32,
+    pub max_health: i32,
+    /// The name of the member's current map
+    pub area_name: String,
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct QuestObjective {
+    pub description: String,
+    pub progress: u32,
+    /// Objectives with a goal of 0 or 1 are displayed without a counter
+    pub goal: u32,
+}
+
+impl QuestObjective {
+    pub fn is_complete(&self) -> bool {
+        self.progress >= self.goal.max(1)
+    }
+
+    /// "progress/goal", or None for objectives without a counter
+    pub fn progress_text(&self) -> Option<String> {
+        if self.goal <= 1 {
+            return None;
+        }
+
+        Some(format!("{}/{}", self.progress.min(self.goal), self.goal))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Quest {
+    /// Chosen by the server, quests are replaced when a quest with the same id is sent
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub objectives: Vec<QuestObjective>,
+    /// Set by the server to complete quests regardless of objectives
+    pub completed: bool,
+}
+
+impl Quest {
+    pub fn is_complete(&self) -> bool {
+        self.completed
+            || (!self.objectives.is_empty() && self.objectives.iter().all(|o| o.is_complete()))
+    }
+
+    /// The first objective left to complete
+    pub fn current_objective(&self) -> Option<&QuestObjective> {
+        self.objectives
+            .iter()
+            .find(|objective| !objective.is_complete())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn completion() {
+        let mut quest = Quest::default();
+        assert!(!quest.is_complete());
+
+        quest.objectives = vec![
+            QuestObjective {
+                goal: 3,
+                ..Default::default()
+            },
+            QuestObjective::default(),
+        ];
+
+        assert_eq!(quest.current_objective(), quest.objectives.first());
+        assert_eq!(quest.objectives[0].progress_text().as_deref(), Some("0/3"));
+        assert_eq!(quest.objectives[1].progress_text(), None);
+
+        quest.objectives[0].progress = 3;
+        assert_eq!(quest.current_objective(), quest.objectives.get(1));
+
+        quest.objectives[1].progress = 1;
+        assert!(quest.is_complete());
+        assert_eq!(quest.current_objective(), None);
+
+        quest.objectives[0].progress = 0;
+        quest.completed = true;
+        assert!(quest.is_complete());
+    }
+}
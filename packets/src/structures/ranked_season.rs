@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RankedTier {
+    pub name: String,
+    pub icon_texture_path: String,
+    /// The lowest rating that places a player in this tier
+    pub min_rating: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RankedMatchResult {
+    pub opponent_name: String,
+    pub won: bool,
+    pub rating_delta: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RankedSeasonInfo {
+    pub season_name: String,
+    /// Displayed as is, servers decide how to format the remaining time
+    pub season_end_text: String,
+    pub rating: i32,
+    pub placement_matches_played: u16,
+    pub placement_matches_required: u16,
+    /// Expected to be sorted by min_rating, lowest first
+    pub tiers: Vec<RankedTier>,
+    /// Most recent first
+    pub recent_matches: Vec<RankedMatchResult>,
+    /// Allows the player to queue for a match from the season scene
+    pub matchmaking: bool,
+}
+
+impl RankedSeasonInfo {
+    pub fn in_placement(&self) -> bool {
+        self.placement_matches_played < self.placement_matches_required
+    }
+
+    /// The highest tier the player's rating qualifies for, None while in placement
+    pub fn tier_index(&self) -> Option<usize> {
+        if self.in_placement() {
+            return None;
+        }
+
+        self.tiers
+            .iter()
+            .rposition(|tier| tier.min_rating <= self.rating)
+    }
+
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.tiers
+            .iter()
+            .map(|tier| tier.icon_texture_path.as_str())
+            .filter(|path| !path.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tier(min_rating: i32) -> RankedTier {
+        RankedTier {
+            min_rating,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tier_resolution() {
+        let mut info = RankedSeasonInfo {
+            rating: 1250,
+            placement_matches_played: 2,
+            placement_matches_required: 5,
+            tiers: vec![tier(0), tier(1000), tier(1500)],
+            ..Default::default()
+        };
+
+        assert_eq!(info.tier_index(), None);
+
+        info.placement_matches_played = 5;
+        assert_eq!(info.tier_index(), Some(1));
+
+        info.rating = 1500;
+        assert_eq!(info.tier_index(), Some(2));
+
+        info.rating = -10;
+        assert_eq!(info.tier_index(), None);
+    }
+}
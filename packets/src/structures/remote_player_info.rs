@@ -4,7 +4,10 @@ use std::net::SocketAddr;
 
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct RemotePlayerInfo {
+    /// The address seen by the server
     pub address: SocketAddr,
+    /// Addresses reported by the player, tried alongside `address`
+    pub candidates: Vec<SocketAddr>,
     pub index: usize,
     pub health: i32,
     pub base_health: i32,
@@ -0,0 +1,115 @@
+use super::PackageId;
+use serde::{Deserialize, Serialize};
+
+/// What one side of a trade is giving up
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TradeOffer {
+    pub money: u32,
+    /// (package_id, code, count)
+    pub cards: Vec<(PackageId, String, usize)>,
+    /// (item_id, count)
+    pub items: Vec<(String, usize)>,
+}
+
+impl TradeOffer {
+    pub fn is_empty(&self) -> bool {
+        self.money == 0 && self.cards.is_empty() && self.items.is_empty()
+    }
+
+    /// Merges duplicate entries and drops empty ones,
+    /// so ownership can be verified against each total
+    ///
+    /// None if a merged count overflows, the offer can't be owned
+    pub fn normalized(self) -> Option<Self> {
+        let mut cards = Vec::<(PackageId, String, usize)>::new();
+
+        for (package_id, code, count) in self.cards {
+            if count == 0 || package_id.is_blank() {
+                continue;
+            }
+
+            let existing = (cards.iter_mut())
+                .find(|(other_id, other_code, _)| *other_id == package_id && *other_code == code);
+
+            match existing {
+                Some((_, _, total)) => *total = total.checked_add(count)?,
+                None => cards.push((package_id, code, count)),
+            }
+        }
+
+        let mut items = Vec::<(String, usize)>::new();
+
+        for (item_id, count) in self.items {
+            if count == 0 {
+                continue;
+            }
+
+            match items.iter_mut().find(|(other_id, _)| *other_id == item_id) {
+                Some((_, total)) => *total = total.checked_add(count)?,
+                None => items.push((item_id, count)),
+            }
+        }
+
+        Some(Self {
+            money: self.money,
+            cards,
+            items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalization_merges_duplicates() {
+        let cannon = PackageId::from("cannon");
+
+        let offer = TradeOffer {
+            money: 0,
+            cards: vec![
+                (cannon.clone(), String::from("A"), 1),
+                (cannon.clone(), String::from("B"), 0),
+                (cannon.clone(), String::from("A"), 2),
+                (PackageId::new_blank(), String::from("A"), 1),
+            ],
+            items: vec![
+                (String::from("key"), 1),
+                (String::from("key"), 1),
+                (String::from("potion"), 0),
+            ],
+        };
+
+        let offer = offer.normalized().unwrap();
+        assert_eq!(offer.cards, [(cannon, String::from("A"), 3)]);
+        assert_eq!(offer.items, [(String::from("key"), 2)]);
+
+        assert!(TradeOffer::default().normalized().unwrap().is_empty());
+    }
+
+    #[test]
+    fn normalization_rejects_overflow() {
+        let cannon = PackageId::from("cannon");
+
+        let offer = TradeOffer {
+            money: 0,
+            cards: vec![
+                (cannon.clone(), String::from("A"), usize::MAX),
+                (cannon, String::from("A"), 1),
+            ],
+            items: Vec::new(),
+        };
+
+        assert_eq!(offer.normalized(), None);
+
+        let offer = TradeOffer {
+            money: 0,
+            cards: Vec::new(),
+            items: vec![(String::from("key"), usize::MAX), (String::from("key"), 2)],
+        };
+
+        assert_eq!(offer.normalized(), None);
+    }
+}
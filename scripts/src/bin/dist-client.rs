@@ -1,4 +1,6 @@
+use std::fmt::Write;
 use std::fs;
+use std::path::Path;
 use std::process::{Command, ExitCode};
 
 const BIN_NAME: &str = "hub_os";
@@ -44,5 +46,44 @@ fn main() -> ExitCode {
     )
     .unwrap();
 
+    write_integrity_manifest("dist/client");
+
     ExitCode::SUCCESS
 }
+
+/// Lists the hash of every resource, the client verifies these on startup
+fn write_integrity_manifest(client_folder: &str) {
+    let mut paths = Vec::new();
+    collect_files(Path::new(&format!("{client_folder}/resources")), &mut paths);
+    paths.sort();
+
+    let mut manifest = String::new();
+
+    for path in paths {
+        let bytes = fs::read(&path).unwrap();
+        let hash = hmac_sha256::Hash::hash(&bytes);
+
+        let relative_path = path.strip_prefix(client_folder).unwrap();
+        let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+
+        for byte in hash {
+            write!(manifest, "{byte:02x}").unwrap();
+        }
+
+        writeln!(manifest, " {relative_path}").unwrap();
+    }
+
+    fs::write(format!("{client_folder}/resources/integrity.txt"), manifest).unwrap();
+}
+
+fn collect_files(folder: &Path, paths: &mut Vec<std::path::PathBuf>) {
+    for entry in fs::read_dir(folder).unwrap().flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files(&path, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
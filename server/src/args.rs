@@ -61,6 +61,19 @@ pub struct Args {
     #[arg(long, value_name = "ASSET_PATH", 
     value_parser = clap::builder::ValueParser::new(optional_asset_path_parser))]
     pub emotes_texture_path: Option<String>,
+
+    /// Seconds a player can take to reconnect after losing connection before being removed, 0 disables resuming
+    #[arg(long, value_name = "SECONDS", default_value = "30.0")]
+    pub resume_window: f32,
+
+    /// Address of another server in the cluster, repeat for each server sharing areas or events.
+    /// Cluster packets from unlisted addresses are ignored
+    #[arg(long = "cluster-peer", value_name = "ADDRESS")]
+    pub cluster_peers: Vec<String>,
+
+    /// Routes connecting players to the least populated cluster peer hosting their requested area
+    #[arg(long)]
+    pub gateway: bool,
 }
 
 fn percentage_parser(value: &str) -> Result<f32, String> {
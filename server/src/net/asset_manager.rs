@@ -1,9 +1,11 @@
 use super::{Asset, AssetId, PackageInfo};
+use packets::structures::{AssetData, FileHash};
 use std::collections::HashMap;
 
 pub struct AssetManager {
     assets: HashMap<String, Asset>,
     package_paths: HashMap<String, String>,
+    audio_hashes: HashMap<String, FileHash>,
 }
 
 impl AssetManager {
@@ -11,6 +13,7 @@ impl AssetManager {
         AssetManager {
             assets: HashMap::new(),
             package_paths: HashMap::new(),
+            audio_hashes: HashMap::new(),
         }
     }
 
@@ -76,6 +79,11 @@ impl AssetManager {
         self.assets.get(path)
     }
 
+    /// Hashes are only tracked for audio, allowing clients to share cached music between servers
+    pub fn get_audio_hash(&self, path: &str) -> Option<FileHash> {
+        self.audio_hashes.get(path).cloned()
+    }
+
     pub fn set_asset(&mut self, path: String, asset: Asset) {
         for alternate_name in &asset.alternate_names {
             #[allow(clippy::single_match)]
@@ -91,6 +99,12 @@ impl AssetManager {
             }
         }
 
+        if let AssetData::Audio(data) = &asset.data {
+            self.audio_hashes.insert(path.clone(), FileHash::hash(data));
+        } else {
+            self.audio_hashes.remove(&path);
+        }
+
         self.assets.insert(path, asset);
     }
 
@@ -99,6 +113,8 @@ impl AssetManager {
             return;
         };
 
+        self.audio_hashes.remove(path);
+
         let try_remove = |paths: &mut HashMap<String, String>, name| {
             let optional_path_str = paths.get(&name).map(|path| path.as_str());
 
@@ -1,4 +1,5 @@
-use packets::structures::ActorId;
+use packets::structures::{ActorId, BattleStatistics, DeckRules, PackageId};
+use packets::ClientAssetType;
 
 use super::{Actor, Direction, PlayerData, WidgetTracker};
 use std::collections::HashSet;
@@ -10,10 +11,47 @@ pub(super) struct BattleTrackingInfo {
     pub plugin_index: usize,
     pub player_index: usize,
     pub remote_addresses: Vec<SocketAddr>,
+    /// Shared with the client to sign netplay results, empty for other battles
+    pub result_key: Vec<u8>,
+    /// Why the folder used for this battle broke the server's deck rules,
+    /// set until a valid folder is received when the player has deck rules
+    pub deck_rejection: Option<String>,
+}
+
+const MISSING_DECK_REJECTION: &str = "No folder was received";
+
+impl BattleTrackingInfo {
+    pub fn create_result_key() -> Vec<u8> {
+        let mut key = vec![0; 32];
+        getrandom::getrandom(&mut key).unwrap();
+        key
+    }
+
+    /// Battles with deck rules are treated as rejected until a folder arrives
+    pub fn initial_deck_rejection(deck_rules: Option<&DeckRules>) -> Option<String> {
+        deck_rules.map(|_| String::from(MISSING_DECK_REJECTION))
+    }
+
+    /// Stores the result of checking the folder used for this battle
+    pub fn track_deck<'a>(
+        &mut self,
+        rules: &DeckRules,
+        cards: impl IntoIterator<Item = (&'a PackageId, &'a str)>,
+    ) -> Result<(), String> {
+        let result = rules.validate(cards);
+        self.deck_rejection = result.clone().err();
+        result
+    }
+
+    /// True if the results were signed with this battle's key
+    pub fn verify_results(&self, stats: &BattleStatistics, signature: Option<[u8; 32]>) -> bool {
+        !self.result_key.is_empty() && signature == Some(stats.sign_results(&self.result_key))
+    }
 }
 
 pub(super) struct Client {
     pub socket_address: SocketAddr,
+    pub netplay_candidates: Vec<SocketAddr>,
     pub actor: Actor,
     pub warp_in: bool,
     pub warp_area: String,
@@ -29,10 +67,20 @@ pub(super) struct Client {
     pub animation_buffer: Vec<u8>,
     pub mugshot_texture_buffer: Vec<u8>,
     pub mugshot_animation_buffer: Vec<u8>,
+    /// First validation error for the avatar being uploaded, reported on AvatarChange
+    pub avatar_rejection: Option<String>,
     pub widget_tracker: WidgetTracker<usize>,
     pub battle_tracker: VecDeque<BattleTrackingInfo>,
+    /// Read from the [deck] table of the player's restrictions, re-validated at battle start
+    pub deck_rules: Option<DeckRules>,
     pub player_data: PlayerData,
     pub input_locks: usize,
+    pub resume_token: Vec<u8>,
+    /// Set while the connection is lost, the player stays in the area until the resume window ends
+    pub suspended_since: Option<std::time::Instant>,
+    /// Reclaimed a suspended session, waiting to resync
+    pub resuming: bool,
+    pub chat_muted: bool,
 }
 
 impl Client {
@@ -53,6 +101,7 @@ impl Client {
 
         Client {
             socket_address,
+            netplay_candidates: Vec::new(),
             actor: Actor {
                 id,
                 name,
@@ -92,13 +141,35 @@ impl Client {
             animation_buffer: Vec::new(),
             mugshot_texture_buffer: Vec::new(),
             mugshot_animation_buffer: Vec::new(),
+            avatar_rejection: None,
             widget_tracker: WidgetTracker::new(),
             battle_tracker: VecDeque::new(),
+            deck_rules: None,
             player_data: PlayerData::new(identity),
             input_locks: 0,
+            resume_token: create_resume_token(),
+            suspended_since: None,
+            resuming: false,
+            chat_muted: false,
+        }
+    }
+
+    pub fn avatar_buffer_mut(&mut self, asset_type: ClientAssetType) -> &mut Vec<u8> {
+        match asset_type {
+            ClientAssetType::Texture => &mut self.texture_buffer,
+            ClientAssetType::Animation => &mut self.animation_buffer,
+            ClientAssetType::MugshotTexture => &mut self.mugshot_texture_buffer,
+            ClientAssetType::MugshotAnimation => &mut self.mugshot_animation_buffer,
         }
     }
 
+    pub fn clear_avatar_buffers(&mut self) {
+        self.texture_buffer.clear();
+        self.animation_buffer.clear();
+        self.mugshot_texture_buffer.clear();
+        self.mugshot_animation_buffer.clear();
+    }
+
     pub fn is_in_widget(&self) -> bool {
         !self.widget_tracker.is_empty()
     }
@@ -161,3 +232,37 @@ where
 
     value_slice.parse().ok()
 }
+
+fn create_resume_token() -> Vec<u8> {
+    let mut token = vec![0; 16];
+    getrandom::getrandom(&mut token).unwrap();
+    token
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn missing_decks_are_rejected_with_deck_rules() {
+        assert_eq!(BattleTrackingInfo::initial_deck_rejection(None), None);
+
+        let rules = DeckRules {
+            max_copies: Some(1),
+            ..Default::default()
+        };
+
+        let mut info = BattleTrackingInfo {
+            deck_rejection: BattleTrackingInfo::initial_deck_rejection(Some(&rules)),
+            ..Default::default()
+        };
+        assert!(info.deck_rejection.is_some());
+
+        let cannon = PackageId::from("cannon");
+        assert!(info.track_deck(&rules, [(&cannon, "A"); 2]).is_err());
+        assert!(info.deck_rejection.is_some());
+
+        assert_eq!(info.track_deck(&rules, [(&cannon, "A")]), Ok(()));
+        assert_eq!(info.deck_rejection, None);
+    }
+}
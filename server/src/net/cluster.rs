@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// Rate servers share their areas and player counts with peers
+pub const CLUSTER_STATUS_RATE: Duration = Duration::from_secs(2);
+/// Peers that haven't shared a status in this long are excluded from routing
+const PEER_TIMEOUT: Duration = Duration::from_secs(10);
+/// Prefix for transfer data requesting a specific area in the cluster
+const AREA_REQUEST_PREFIX: &str = "cluster_area:";
+
+pub fn cluster_area_request(data: &str) -> Option<&str> {
+    data.strip_prefix(AREA_REQUEST_PREFIX)
+}
+
+pub fn create_cluster_area_request(area_id: &str) -> String {
+    format!("{AREA_REQUEST_PREFIX}{area_id}")
+}
+
+struct PeerStatus {
+    public_address: String,
+    areas: Vec<String>,
+    player_count: usize,
+    last_status: Instant,
+}
+
+#[derive(Default)]
+pub struct Cluster {
+    // None until the peer shares its status
+    peers: HashMap<SocketAddr, Option<PeerStatus>>,
+    last_status_broadcast: Option<Instant>,
+}
+
+impl Cluster {
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    pub fn add_peer(&mut self, socket_address: SocketAddr) {
+        self.peers.entry(socket_address).or_default();
+    }
+
+    pub fn peer_addresses(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.peers.keys().copied()
+    }
+
+    /// Only addresses resolved from --cluster-peer are peers
+    pub fn is_peer(&self, socket_address: SocketAddr) -> bool {
+        self.peers.contains_key(&socket_address)
+    }
+
+    /// Returns true and resets the timer if it's time to share our status
+    pub fn poll_status_broadcast(&mut self, now: Instant) -> bool {
+        let ready = self
+            .last_status_broadcast
+            .map(|last| now - last >= CLUSTER_STATUS_RATE)
+            .unwrap_or(true);
+
+        if ready {
+            self.last_status_broadcast = Some(now);
+        }
+
+        ready
+    }
+
+    /// Statuses from addresses that weren't added as peers are ignored
+    pub fn update_peer(
+        &mut self,
+        socket_address: SocketAddr,
+        public_address: String,
+        areas: Vec<String>,
+        player_count: usize,
+        now: Instant,
+    ) {
+        let Some(status) = self.peers.get_mut(&socket_address) else {
+            return;
+        };
+
+        *status = Some(PeerStatus {
+            public_address,
+            areas,
+            player_count,
+            last_status: now,
+        });
+    }
+
+    fn live_hosts<'a>(
+        &'a mut self,
+        area_id: &'a str,
+        now: Instant,
+    ) -> impl Iterator<Item = &'a mut PeerStatus> + 'a {
+        self.peers
+            .values_mut()
+            .flatten()
+            .filter(move |status| now - status.last_status < PEER_TIMEOUT)
+            .filter(move |status| status.areas.iter().any(|id| id == area_id))
+    }
+
+    /// Public addresses of peers hosting the area
+    pub fn area_hosts(&mut self, area_id: &str, now: Instant) -> Vec<String> {
+        self.live_hosts(area_id, now)
+            .map(|status| status.public_address.clone())
+            .collect()
+    }
+
+    /// Picks the least populated peer hosting the area, returning its public address
+    pub fn route_player(&mut self, area_id: &str, now: Instant) -> Option<String> {
+        let status = self
+            .live_hosts(area_id, now)
+            .min_by_key(|status| status.player_count)?;
+
+        // count the player until the peer shares an updated status
+        // avoids sending every player to the same peer between updates
+        status.player_count += 1;
+
+        Some(status.public_address.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn peer_address(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn areas(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn routes_to_least_populated_host() {
+        let now = Instant::now();
+        let mut cluster = Cluster::default();
+
+        for port in 1..=3 {
+            cluster.add_peer(peer_address(port));
+        }
+
+        cluster.update_peer(peer_address(1), "a".into(), areas(&["event"]), 3, now);
+        cluster.update_peer(peer_address(2), "b".into(), areas(&["event"]), 1, now);
+        cluster.update_peer(peer_address(3), "c".into(), areas(&["default"]), 0, now);
+
+        assert_eq!(cluster.route_player("event", now).as_deref(), Some("b"));
+        assert_eq!(cluster.route_player("event", now).as_deref(), Some("b"));
+        // b has caught up to a
+        let second_pick = cluster.route_player("event", now);
+        assert!(matches!(second_pick.as_deref(), Some("a" | "b")));
+
+        assert_eq!(cluster.route_player("missing", now), None);
+    }
+
+    #[test]
+    fn silent_peers_are_not_routed_to() {
+        let now = Instant::now();
+        let mut cluster = Cluster::default();
+        cluster.add_peer(peer_address(1));
+        cluster.add_peer(peer_address(2));
+        cluster.update_peer(peer_address(2), "b".into(), areas(&["default"]), 0, now);
+
+        assert_eq!(cluster.area_hosts("default", now), ["b"]);
+
+        let later = now + PEER_TIMEOUT;
+        assert!(cluster.area_hosts("default", later).is_empty());
+        assert_eq!(cluster.peer_addresses().count(), 2);
+    }
+
+    #[test]
+    fn unknown_senders_are_ignored() {
+        let now = Instant::now();
+        let mut cluster = Cluster::default();
+        cluster.add_peer(peer_address(1));
+        cluster.update_peer(peer_address(2), "b".into(), areas(&["default"]), 0, now);
+
+        assert!(!cluster.is_peer(peer_address(2)));
+        assert!(cluster.area_hosts("default", now).is_empty());
+        assert_eq!(cluster.route_player("default", now), None);
+        assert_eq!(cluster.peer_addresses().count(), 1);
+    }
+
+    #[test]
+    fn area_requests() {
+        let data = create_cluster_area_request("event");
+        assert_eq!(cluster_area_request(&data), Some("event"));
+        assert_eq!(cluster_area_request("event"), None);
+    }
+}
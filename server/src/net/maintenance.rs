@@ -0,0 +1,90 @@
+/// Seconds remaining when players are warned, along with the start of maintenance
+const ANNOUNCEMENT_TIMES: [f32; 6] = [300.0, 120.0, 60.0, 30.0, 10.0, 5.0];
+
+pub struct Maintenance {
+    remaining: f32,
+    safe_area: Option<String>,
+    announced_start: bool,
+}
+
+impl Maintenance {
+    pub fn new(duration: f32, safe_area: Option<String>) -> Self {
+        Self {
+            remaining: duration.max(0.0),
+            safe_area,
+            announced_start: false,
+        }
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.announced_start
+    }
+
+    pub fn safe_area(&self) -> Option<&str> {
+        self.safe_area.as_deref()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    /// Returns the seconds remaining if players should be warned
+    pub fn update(&mut self, delta: f32) -> Option<f32> {
+        if !self.announced_start {
+            self.announced_start = true;
+            return Some(self.remaining.ceil());
+        }
+
+        let previous = self.remaining;
+        self.remaining = (self.remaining - delta).max(0.0);
+
+        let crossed_announcement = ANNOUNCEMENT_TIMES
+            .into_iter()
+            .any(|time| previous > time && self.remaining <= time);
+
+        crossed_announcement.then_some(self.remaining)
+    }
+}
+
+pub fn format_countdown(seconds: f32) -> String {
+    let seconds = seconds.ceil() as u32;
+
+    match (seconds / 60, seconds % 60) {
+        (0, 1) => String::from("1 second"),
+        (0, seconds) => format!("{seconds} seconds"),
+        (1, 0) => String::from("1 minute"),
+        (minutes, 0) => format!("{minutes} minutes"),
+        (minutes, seconds) => format!("{minutes}m {seconds}s"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn announces_start_and_thresholds() {
+        let mut maintenance = Maintenance::new(65.0, None);
+
+        assert_eq!(maintenance.update(1.0), Some(65.0));
+        assert_eq!(maintenance.update(4.0), None);
+        assert_eq!(maintenance.update(1.0), Some(60.0));
+        assert_eq!(maintenance.update(1.0), None);
+
+        // a large step only announces once
+        assert_eq!(maintenance.update(50.0), Some(9.0));
+        assert!(!maintenance.is_complete());
+
+        maintenance.update(10.0);
+        assert!(maintenance.is_complete());
+    }
+
+    #[test]
+    fn countdown_text() {
+        assert_eq!(format_countdown(0.5), "1 second");
+        assert_eq!(format_countdown(30.0), "30 seconds");
+        assert_eq!(format_countdown(60.0), "1 minute");
+        assert_eq!(format_countdown(300.0), "5 minutes");
+        assert_eq!(format_countdown(90.0), "1m 30s");
+    }
+}
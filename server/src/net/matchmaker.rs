@@ -0,0 +1,642 @@
+use packets::structures::{ActorId, BattleStatistics, RankedMatchResult};
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+
+/// Ratings are kept between restarts in the server folder
+pub(super) const RATINGS_PATH: &str = "ratings.toml";
+const DEFAULT_RATING: i32 = 1000;
+const PLACEMENT_MATCHES: u32 = 10;
+const PLACEMENT_K_FACTOR: f32 = 40.0;
+const K_FACTOR: f32 = 20.0;
+const MAX_RECENT_MATCHES: usize = 10;
+const MAX_FLAGGED_MATCHES: usize = 50;
+/// Largest rating difference accepted as soon as a player queues
+const BASE_RATING_WINDOW: i32 = 100;
+/// How quickly the accepted rating difference widens while waiting
+const RATING_WINDOW_GROWTH: f32 = 10.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerRating {
+    pub rating: i32,
+    pub matches_played: u32,
+    /// Most recent first
+    pub recent_matches: VecDeque<RankedMatchResult>,
+}
+
+impl Default for PlayerRating {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_RATING,
+            matches_played: 0,
+            recent_matches: VecDeque::new(),
+        }
+    }
+}
+
+impl PlayerRating {
+    fn k_factor(&self) -> f32 {
+        // ratings move faster until we have a better idea of the player's skill
+        if self.matches_played < PLACEMENT_MATCHES {
+            PLACEMENT_K_FACTOR
+        } else {
+            K_FACTOR
+        }
+    }
+
+    fn record(&mut self, opponent_name: String, won: bool, rating_delta: i32) {
+        self.rating += rating_delta;
+        self.matches_played += 1;
+
+        self.recent_matches.push_front(RankedMatchResult {
+            opponent_name,
+            won,
+            rating_delta,
+        });
+
+        self.recent_matches.truncate(MAX_RECENT_MATCHES);
+    }
+}
+
+/// The probability of a player with `rating` beating a player with `opponent_rating`
+pub fn expected_score(rating: i32, opponent_rating: i32) -> f32 {
+    1.0 / (1.0 + 10.0_f32.powf((opponent_rating - rating) as f32 / 400.0))
+}
+
+struct QueueEntry {
+    player_id: ActorId,
+    identity: Vec<u8>,
+    name: String,
+    rating: i32,
+    queued_at: Instant,
+}
+
+impl QueueEntry {
+    fn rating_window(&self, now: Instant) -> i32 {
+        let waited = now.saturating_duration_since(self.queued_at).as_secs_f32();
+
+        BASE_RATING_WINDOW + (waited * RATING_WINDOW_GROWTH) as i32
+    }
+}
+
+/// A player's account of how a ranked match ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct MatchReport {
+    pub won: bool,
+    /// Fled, the final state is skipped as the battle ended early for this player
+    pub forfeited: bool,
+    pub time: i64,
+    pub checksum: u64,
+    /// Signed with the key given to this player
+    pub verified: bool,
+}
+
+impl MatchReport {
+    pub fn from_statistics(stats: &BattleStatistics, verified: bool) -> Self {
+        Self {
+            won: !stats.ran && !stats.declined && stats.health > 0,
+            forfeited: stats.ran,
+            time: stats.time,
+            checksum: stats.checksum,
+            verified,
+        }
+    }
+}
+
+/// A ranked match discarded for reports that didn't agree, kept for server operators to review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlaggedMatch {
+    pub player_names: [String; 2],
+    pub reason: &'static str,
+}
+
+struct MatchParticipant {
+    player_id: ActorId,
+    identity: Vec<u8>,
+    name: String,
+    report: Option<MatchReport>,
+}
+
+impl From<QueueEntry> for MatchParticipant {
+    fn from(entry: QueueEntry) -> Self {
+        Self {
+            player_id: entry.player_id,
+            identity: entry.identity,
+            name: entry.name,
+            report: None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Matchmaker {
+    ratings: HashMap<Vec<u8>, PlayerRating>,
+    queue: Vec<QueueEntry>,
+    active_matches: Vec<[MatchParticipant; 2]>,
+    flagged_matches: VecDeque<FlaggedMatch>,
+    ratings_changed: bool,
+}
+
+impl Matchmaker {
+    /// Reads ratings written by `ratings_to_string`, entries that can't be read are skipped
+    pub fn load_ratings(&mut self, text: &str) -> Result<(), toml::de::Error> {
+        let root_table: toml::Table = text.parse()?;
+
+        for (key, value) in root_table {
+            let Some(identity) = decode_hex(&key) else {
+                log::warn!("Skipping rating with invalid identity {key:?}");
+                continue;
+            };
+
+            let get_integer = |key: &str| value.get(key).and_then(toml::Value::as_integer);
+
+            let mut player_rating = PlayerRating::default();
+
+            if let Some(rating) = get_integer("rating") {
+                player_rating.rating = rating as i32;
+            }
+
+            if let Some(matches_played) = get_integer("matches_played") {
+                player_rating.matches_played = matches_played.max(0) as u32;
+            }
+
+            let recent_matches = value.get("recent_matches").and_then(toml::Value::as_array);
+
+            for result in recent_matches.into_iter().flatten() {
+                let opponent_name = result.get("opponent_name").and_then(toml::Value::as_str);
+                let won = result.get("won").and_then(toml::Value::as_bool);
+                let rating_delta = result.get("rating_delta").and_then(toml::Value::as_integer);
+
+                let (Some(opponent_name), Some(won), Some(rating_delta)) =
+                    (opponent_name, won, rating_delta)
+                else {
+                    continue;
+                };
+
+                player_rating.recent_matches.push_back(RankedMatchResult {
+                    opponent_name: opponent_name.to_string(),
+                    won,
+                    rating_delta: rating_delta as i32,
+                });
+            }
+
+            player_rating.recent_matches.truncate(MAX_RECENT_MATCHES);
+            self.ratings.insert(identity, player_rating);
+        }
+
+        Ok(())
+    }
+
+    pub fn ratings_to_string(&self) -> String {
+        let mut root_table = toml::Table::new();
+
+        for (identity, player_rating) in &self.ratings {
+            let recent_matches = (player_rating.recent_matches.iter())
+                .map(|result| {
+                    let mut table = toml::Table::new();
+                    table.insert(
+                        String::from("opponent_name"),
+                        result.opponent_name.clone().into(),
+                    );
+                    table.insert(String::from("won"), result.won.into());
+                    table.insert(
+                        String::from("rating_delta"),
+                        i64::from(result.rating_delta).into(),
+                    );
+                    toml::Value::Table(table)
+                })
+                .collect::<Vec<_>>();
+
+            let mut table = toml::Table::new();
+            table.insert(
+                String::from("rating"),
+                i64::from(player_rating.rating).into(),
+            );
+            table.insert(
+                String::from("matches_played"),
+                i64::from(player_rating.matches_played).into(),
+            );
+            table.insert(String::from("recent_matches"), recent_matches.into());
+
+            root_table.insert(encode_hex(identity), table.into());
+        }
+
+        root_table.to_string()
+    }
+
+    /// True if ratings changed since the last call, used to save ratings only when necessary
+    pub fn take_ratings_changed(&mut self) -> bool {
+        std::mem::take(&mut self.ratings_changed)
+    }
+
+    pub fn rating(&self, identity: &[u8]) -> Option<&PlayerRating> {
+        self.ratings.get(identity)
+    }
+
+    pub fn set_rating(&mut self, identity: Vec<u8>, rating: i32, matches_played: u32) {
+        let player_rating = self.ratings.entry(identity).or_default();
+        player_rating.rating = rating;
+        player_rating.matches_played = matches_played;
+        self.ratings_changed = true;
+    }
+
+    /// Most recent first
+    pub fn flagged_matches(&self) -> &VecDeque<FlaggedMatch> {
+        &self.flagged_matches
+    }
+
+    pub fn is_queued(&self, player_id: ActorId) -> bool {
+        self.queue.iter().any(|entry| entry.player_id == player_id)
+    }
+
+    pub fn is_in_match(&self, player_id: ActorId) -> bool {
+        self.active_matches
+            .iter()
+            .flatten()
+            .any(|participant| participant.player_id == player_id)
+    }
+
+    /// Returns false if the player is already queued or still in a ranked match
+    pub fn enqueue(
+        &mut self,
+        player_id: ActorId,
+        identity: Vec<u8>,
+        name: String,
+        now: Instant,
+    ) -> bool {
+        if self.is_queued(player_id) || self.is_in_match(player_id) {
+            return false;
+        }
+
+        let rating = (self.ratings.get(&identity))
+            .map(|player_rating| player_rating.rating)
+            .unwrap_or(DEFAULT_RATING);
+
+        self.queue.push(QueueEntry {
+            player_id,
+            identity,
+            name,
+            rating,
+            queued_at: now,
+        });
+
+        true
+    }
+
+    pub fn dequeue(&mut self, player_id: ActorId) -> bool {
+        let len = self.queue.len();
+        self.queue.retain(|entry| entry.player_id != player_id);
+
+        len != self.queue.len()
+    }
+
+    /// Pairs queued players with the closest rating both players will accept,
+    /// players who have waited the longest are paired first
+    pub fn pair_players(&mut self, now: Instant) -> Vec<[ActorId; 2]> {
+        let mut pairs = Vec::new();
+        let mut i = 0;
+
+        while i < self.queue.len() {
+            let entry = &self.queue[i];
+            let window = entry.rating_window(now);
+
+            let best_opponent = (i + 1..self.queue.len())
+                .filter(|&j| {
+                    let opponent = &self.queue[j];
+                    let difference = (entry.rating - opponent.rating).abs();
+
+                    difference <= window.min(opponent.rating_window(now))
+                })
+                .min_by_key(|&j| (entry.rating - self.queue[j].rating).abs());
+
+            let Some(j) = best_opponent else {
+                i += 1;
+                continue;
+            };
+
+            // j is always after i, remove it first to keep i valid
+            let opponent = self.queue.remove(j);
+            let entry = self.queue.remove(i);
+
+            pairs.push([entry.player_id, opponent.player_id]);
+            self.active_matches.push([entry.into(), opponent.into()]);
+        }
+
+        pairs
+    }
+
+    /// Ratings update once both players report, matches with reports that disagree are discarded and flagged
+    pub fn report_result(&mut self, player_id: ActorId, report: MatchReport) {
+        let Some(index) = self.find_match(player_id) else {
+            return;
+        };
+
+        let participants = &mut self.active_matches[index];
+
+        for participant in participants.iter_mut() {
+            if participant.player_id == player_id {
+                participant.report = Some(report);
+            }
+        }
+
+        let (Some(a_report), Some(b_report)) = (participants[0].report, participants[1].report)
+        else {
+            return;
+        };
+
+        let [a, b] = self.active_matches.remove(index);
+
+        match Self::resolve_winner(a_report, b_report) {
+            Ok(true) => self.apply_result(a, b),
+            Ok(false) => self.apply_result(b, a),
+            Err(reason) => {
+                log::warn!(
+                    "Discarding ranked match between {:?} and {:?}, {reason}",
+                    a.name,
+                    b.name
+                );
+
+                self.flagged_matches.push_front(FlaggedMatch {
+                    player_names: [a.name, b.name],
+                    reason,
+                });
+
+                self.flagged_matches.truncate(MAX_FLAGGED_MATCHES);
+            }
+        }
+    }
+
+    /// Returns true if the first player won, or why the reports can't be trusted
+    fn resolve_winner(a: MatchReport, b: MatchReport) -> Result<bool, &'static str> {
+        if !a.verified || !b.verified {
+            return Err("results weren't signed");
+        }
+
+        let a_won = match (a.won, b.won) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => return Err("results conflict"),
+        };
+
+        // both players saw the battle through, they should've ended on the same state
+        if !a.forfeited && !b.forfeited && (a.time != b.time || a.checksum != b.checksum) {
+            return Err("final states differ");
+        }
+
+        Ok(a_won)
+    }
+
+    /// Leaving during a ranked match counts as a loss
+    pub fn remove_player(&mut self, player_id: ActorId) {
+        self.dequeue(player_id);
+
+        let Some(index) = self.find_match(player_id) else {
+            return;
+        };
+
+        let [a, b] = self.active_matches.remove(index);
+
+        if a.player_id == player_id {
+            self.apply_result(b, a);
+        } else {
+            self.apply_result(a, b);
+        }
+    }
+
+    fn find_match(&self, player_id: ActorId) -> Option<usize> {
+        self.active_matches.iter().position(|participants| {
+            participants
+                .iter()
+                .any(|participant| participant.player_id == player_id)
+        })
+    }
+
+    fn apply_result(&mut self, winner: MatchParticipant, loser: MatchParticipant) {
+        let default_rating = PlayerRating::default();
+        let winner_rating = self.ratings.get(&winner.identity);
+        let winner_rating = winner_rating.unwrap_or(&default_rating);
+        let loser_rating = self.ratings.get(&loser.identity);
+        let loser_rating = loser_rating.unwrap_or(&default_rating);
+
+        let expected = expected_score(winner_rating.rating, loser_rating.rating);
+        let winner_delta = (winner_rating.k_factor() * (1.0 - expected)).round() as i32;
+        let loser_delta = -(loser_rating.k_factor() * (1.0 - expected)).round() as i32;
+
+        let winner_entry = self.ratings.entry(winner.identity).or_default();
+        winner_entry.record(loser.name.clone(), true, winner_delta);
+
+        let loser_entry = self.ratings.entry(loser.identity).or_default();
+        loser_entry.record(winner.name, false, loser_delta);
+
+        self.ratings_changed = true;
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn queue_player(matchmaker: &mut Matchmaker, index: u32, rating: i32, now: Instant) -> ActorId {
+        let player_id = ActorId::new(index, 0);
+        let identity = vec![index as u8];
+
+        matchmaker.set_rating(identity.clone(), rating, PLACEMENT_MATCHES);
+        matchmaker.enqueue(player_id, identity, index.to_string(), now);
+
+        player_id
+    }
+
+    fn create_report(won: bool) -> MatchReport {
+        MatchReport {
+            won,
+            forfeited: false,
+            time: 600,
+            checksum: 1,
+            verified: true,
+        }
+    }
+
+    #[test]
+    fn rating_window_widens() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let b = queue_player(&mut matchmaker, 2, 1300, now);
+
+        assert!(matchmaker.pair_players(now).is_empty());
+
+        let later = now + Duration::from_secs(30);
+        assert_eq!(matchmaker.pair_players(later), [[a, b]]);
+        assert!(!matchmaker.is_queued(a));
+        assert!(matchmaker.is_in_match(b));
+    }
+
+    #[test]
+    fn closest_rating_paired() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let _ = queue_player(&mut matchmaker, 2, 1090, now);
+        let c = queue_player(&mut matchmaker, 3, 1020, now);
+
+        assert_eq!(matchmaker.pair_players(now), [[a, c]]);
+    }
+
+    #[test]
+    fn results_update_ratings() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let b = queue_player(&mut matchmaker, 2, 1000, now);
+        matchmaker.pair_players(now);
+
+        matchmaker.report_result(a, create_report(true));
+        assert_eq!(matchmaker.rating(&[1]).unwrap().rating, 1000);
+
+        matchmaker.report_result(b, create_report(false));
+        assert_eq!(matchmaker.rating(&[1]).unwrap().rating, 1010);
+        assert_eq!(matchmaker.rating(&[2]).unwrap().rating, 990);
+        assert!(!matchmaker.is_in_match(a));
+
+        let recent = &matchmaker.rating(&[2]).unwrap().recent_matches;
+        assert_eq!(recent[0].opponent_name, "1");
+        assert!(!recent[0].won);
+    }
+
+    #[test]
+    fn conflicting_results_discarded() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let b = queue_player(&mut matchmaker, 2, 1000, now);
+        matchmaker.pair_players(now);
+
+        matchmaker.report_result(a, create_report(true));
+        matchmaker.report_result(b, create_report(true));
+
+        assert_eq!(matchmaker.rating(&[1]).unwrap().matches_played, 10);
+        assert_eq!(matchmaker.rating(&[2]).unwrap().matches_played, 10);
+        assert_eq!(matchmaker.flagged_matches()[0].reason, "results conflict");
+    }
+
+    #[test]
+    fn mismatched_reports_flagged() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        // different final states
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let b = queue_player(&mut matchmaker, 2, 1000, now);
+        matchmaker.pair_players(now);
+
+        matchmaker.report_result(a, create_report(true));
+        matchmaker.report_result(
+            b,
+            MatchReport {
+                checksum: 2,
+                ..create_report(false)
+            },
+        );
+
+        // unsigned
+        let c = queue_player(&mut matchmaker, 3, 1000, now);
+        let d = queue_player(&mut matchmaker, 4, 1000, now);
+        matchmaker.pair_players(now);
+
+        matchmaker.report_result(c, create_report(true));
+        matchmaker.report_result(
+            d,
+            MatchReport {
+                verified: false,
+                ..create_report(false)
+            },
+        );
+
+        let flagged = matchmaker.flagged_matches();
+        assert_eq!(flagged[0].reason, "results weren't signed");
+        assert_eq!(flagged[1].reason, "final states differ");
+        assert_eq!(
+            flagged[1].player_names,
+            [String::from("1"), String::from("2")]
+        );
+        assert_eq!(matchmaker.rating(&[1]).unwrap().rating, 1000);
+
+        // fleeing ends the battle early, the final state isn't compared
+        let e = queue_player(&mut matchmaker, 5, 1000, now);
+        let f = queue_player(&mut matchmaker, 6, 1000, now);
+        matchmaker.pair_players(now);
+
+        let fled_report = MatchReport {
+            forfeited: true,
+            checksum: 0,
+            ..create_report(false)
+        };
+
+        matchmaker.report_result(e, fled_report);
+        matchmaker.report_result(f, create_report(true));
+
+        assert_eq!(matchmaker.rating(&[6]).unwrap().rating, 1010);
+        assert_eq!(matchmaker.flagged_matches().len(), 2);
+    }
+
+    #[test]
+    fn leaving_forfeits() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let _ = queue_player(&mut matchmaker, 2, 1000, now);
+        matchmaker.pair_players(now);
+
+        matchmaker.remove_player(a);
+
+        assert_eq!(matchmaker.rating(&[1]).unwrap().rating, 990);
+        assert_eq!(matchmaker.rating(&[2]).unwrap().rating, 1010);
+    }
+
+    #[test]
+    fn ratings_survive_saving() {
+        let mut matchmaker = Matchmaker::default();
+        let now = Instant::now();
+
+        let a = queue_player(&mut matchmaker, 1, 1000, now);
+        let b = queue_player(&mut matchmaker, 200, 1000, now);
+        matchmaker.pair_players(now);
+        matchmaker.report_result(a, create_report(true));
+        matchmaker.report_result(b, create_report(false));
+
+        assert!(matchmaker.take_ratings_changed());
+        assert!(!matchmaker.take_ratings_changed());
+
+        let mut loaded = Matchmaker::default();
+        loaded
+            .load_ratings(&matchmaker.ratings_to_string())
+            .unwrap();
+
+        assert_eq!(loaded.ratings, matchmaker.ratings);
+        assert_eq!(
+            decode_hex(&encode_hex(&[0, 15, 200])),
+            Some(vec![0, 15, 200])
+        );
+        assert_eq!(decode_hex("abc"), None);
+    }
+}
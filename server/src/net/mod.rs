@@ -7,15 +7,20 @@ pub mod asset;
 mod asset_manager;
 mod boot;
 mod client;
+mod cluster;
+mod maintenance;
 pub mod map;
+mod matchmaker;
 mod packet_orchestrator;
 mod packet_scope;
+mod party_manager;
 mod player_data;
 mod plugin_wrapper;
 mod server;
 mod server_builder;
 mod server_config;
 mod sprite;
+mod trade_broker;
 mod widget_tracker;
 
 pub(super) use packet_orchestrator::*;
@@ -23,6 +28,7 @@ pub(super) use packet_orchestrator::*;
 pub use actor::Actor;
 pub use area::Area;
 pub use asset::{Asset, AssetId, PackageInfo};
+pub use matchmaker::{FlaggedMatch, PlayerRating};
 pub use net::Net;
 pub use packet_scope::*;
 pub use packets::structures::*;
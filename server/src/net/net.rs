@@ -1,16 +1,23 @@
 use super::asset_manager::AssetManager;
 use super::boot::Boot;
 use super::client::{BattleTrackingInfo, Client};
+use super::cluster::{cluster_area_request, create_cluster_area_request, Cluster};
+use super::maintenance::{format_countdown, Maintenance};
 use super::map::Map;
+use super::matchmaker::{FlaggedMatch, MatchReport, Matchmaker, RATINGS_PATH};
+use super::party_manager::PartyManager;
+use super::trade_broker::{Trade, TradeBroker, TradeConfirmation};
 use super::*;
 use crate::jobs::JobPromise;
 use crate::threads::ThreadMessage;
 use flume::Sender;
-use packets::{Reliability, ServerPacket, MAX_IDLE_DURATION};
+use packets::{
+    Reliability, ServerCommPacket, ServerPacket, MAX_IDLE_DURATION, MAX_NETPLAY_PLAYERS,
+};
 use slotmap::HopSlotMap;
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::rc::Rc;
 
 pub struct Net {
@@ -26,7 +33,19 @@ pub struct Net {
     asset_manager: AssetManager,
     active_plugin: usize,
     kick_list: Vec<Boot>,
+    // suspended players kicked before they could resume
+    kicked_sessions: Vec<ActorId>,
     item_registry: HashMap<String, ItemDefinition>,
+    chat_commands: Vec<ChatCommandInfo>,
+    achievement_registry: HashMap<String, AchievementDefinition>,
+    matchmaker: Matchmaker,
+    trade_broker: TradeBroker,
+    party_manager: PartyManager,
+    /// Parties with members that changed health, area, or name since the last tick
+    dirty_parties: HashSet<String>,
+    ranked_encounter_path: Option<String>,
+    cluster: Cluster,
+    maintenance: Option<Maintenance>,
 }
 
 impl Net {
@@ -75,6 +94,14 @@ impl Net {
             panic!("No default (default.tmx) area data found");
         }
 
+        let mut matchmaker = Matchmaker::default();
+
+        if let Ok(ratings_text) = read_to_string(RATINGS_PATH) {
+            if let Err(e) = matchmaker.load_ratings(&ratings_text) {
+                log::error!("Failed to read {RATINGS_PATH}: {e}");
+            }
+        }
+
         Net {
             packet_orchestrator,
             config,
@@ -88,7 +115,17 @@ impl Net {
             asset_manager,
             active_plugin: 0,
             kick_list: Vec::new(),
+            kicked_sessions: Vec::new(),
             item_registry: HashMap::new(),
+            chat_commands: Vec::new(),
+            achievement_registry: HashMap::new(),
+            matchmaker,
+            trade_broker: TradeBroker::default(),
+            party_manager: PartyManager::default(),
+            dirty_parties: HashSet::new(),
+            ranked_encounter_path: None,
+            cluster: Cluster::default(),
+            maintenance: None,
         }
     }
 
@@ -124,6 +161,20 @@ impl Net {
         self.areas.get(area_id)
     }
 
+    /// Resolves the area for a fast travel request, only warps can be traveled to
+    pub fn fast_travel_area(&self, map_path: &str, object_id: u32) -> Option<&str> {
+        use super::asset::get_map_path;
+
+        let area = (self.areas.values()).find(|area| get_map_path(area.id()) == map_path)?;
+        let object = area.map().get_object_by_id(object_id)?;
+
+        object
+            .class
+            .to_lowercase()
+            .ends_with("warp")
+            .then(|| area.id())
+    }
+
     pub fn get_area_mut(&mut self, area_id: &str) -> Option<&mut Area> {
         self.areas.get_mut(area_id)
     }
@@ -220,7 +271,68 @@ impl Net {
         }
     }
 
+    /// Pauses the area's music to play the stinger, music resumes after
+    pub fn play_stinger(&mut self, area_id: &str, path: &str) {
+        if let Some(area) = self.areas.get(area_id) {
+            ensure_asset(
+                &mut self.packet_orchestrator.borrow_mut(),
+                self.config.args.max_payload_size,
+                &self.asset_manager,
+                &mut self.clients,
+                area.connected_players(),
+                path,
+            );
+
+            broadcast_to_area(
+                &mut self.packet_orchestrator.borrow_mut(),
+                area,
+                Reliability::ReliableOrdered,
+                ServerPacket::PlayStinger {
+                    path: path.to_string(),
+                },
+            )
+        }
+    }
+
+    pub fn play_sound_at(&mut self, area_id: &str, path: &str, x: f32, y: f32, z: f32) {
+        if let Some(area) = self.areas.get(area_id) {
+            ensure_asset(
+                &mut self.packet_orchestrator.borrow_mut(),
+                self.config.args.max_payload_size,
+                &self.asset_manager,
+                &mut self.clients,
+                area.connected_players(),
+                path,
+            );
+
+            broadcast_to_area(
+                &mut self.packet_orchestrator.borrow_mut(),
+                area,
+                Reliability::Reliable,
+                ServerPacket::PlaySoundAt {
+                    path: path.to_string(),
+                    x,
+                    y,
+                    z,
+                },
+            )
+        }
+    }
+
+    pub fn animate_tile(&mut self, area_id: &str, x: i32, y: i32, z: i32, gid: u32) {
+        if let Some(area) = self.areas.get(area_id) {
+            broadcast_to_area(
+                &mut self.packet_orchestrator.borrow_mut(),
+                area,
+                Reliability::ReliableOrdered,
+                ServerPacket::AnimateTile { x, y, z, gid },
+            )
+        }
+    }
+
     pub fn set_player_name(&mut self, id: ActorId, name: &str) {
+        self.mark_party_dirty(id);
+
         let Some(client) = self.clients.get_mut(&id) else {
             return;
         };
@@ -313,6 +425,35 @@ impl Net {
         );
     }
 
+    pub fn set_player_package_emote(
+        &mut self,
+        id: ActorId,
+        package_id: PackageId,
+        emote_id: String,
+    ) {
+        let Some(client) = self.clients.get(&id) else {
+            return;
+        };
+
+        let Some(area) = self.areas.get(&client.actor.area_id) else {
+            // area deleted, should be getting kicked
+            return;
+        };
+
+        let packet = ServerPacket::ActorPackageEmote {
+            actor_id: id,
+            package_id,
+            emote_id,
+        };
+
+        broadcast_to_area(
+            &mut self.packet_orchestrator.borrow_mut(),
+            area,
+            Reliability::Reliable,
+            packet,
+        );
+    }
+
     pub fn exclusive_player_emote(
         &mut self,
         target_id: ActorId,
@@ -465,6 +606,27 @@ impl Net {
         );
     }
 
+    /// Streams audio offered through CachedAudio that the client no longer has cached
+    pub(super) fn stream_missing_audio(&mut self, id: ActorId, path: &str) {
+        // only audio the server offered by hash can be requested
+        if self.asset_manager.get_audio_hash(path).is_none() {
+            return;
+        }
+
+        let Some(asset) = self.asset_manager.get_asset(path) else {
+            return;
+        };
+
+        let byte_vecs: Vec<_> =
+            ServerPacket::create_asset_stream(self.config.args.max_payload_size, path, asset)
+                .map(packets::serialize)
+                .collect();
+
+        self.packet_orchestrator
+            .borrow_mut()
+            .send_byte_packets_by_id(id, Reliability::ReliableOrdered, &byte_vecs);
+    }
+
     pub fn play_sound_for_player(&mut self, id: ActorId, path: &str) {
         ensure_asset(
             &mut self.packet_orchestrator.borrow_mut(),
@@ -484,6 +646,45 @@ impl Net {
         );
     }
 
+    pub fn play_music_for_player(&mut self, id: ActorId, path: &str, loops: bool) {
+        ensure_asset(
+            &mut self.packet_orchestrator.borrow_mut(),
+            self.config.args.max_payload_size,
+            &self.asset_manager,
+            &mut self.clients,
+            &[id],
+            path,
+        );
+
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            id,
+            Reliability::ReliableOrdered,
+            ServerPacket::PlayMusic {
+                path: path.to_string(),
+                loops,
+            },
+        );
+    }
+
+    pub fn play_ambience_for_player(&mut self, id: ActorId, path: &str) {
+        ensure_asset(
+            &mut self.packet_orchestrator.borrow_mut(),
+            self.config.args.max_payload_size,
+            &self.asset_manager,
+            &mut self.clients,
+            &[id],
+            path,
+        );
+
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            id,
+            Reliability::ReliableOrdered,
+            ServerPacket::PlayAmbience {
+                path: path.to_string(),
+            },
+        );
+    }
+
     pub fn exclude_object_for_player(&mut self, id: ActorId, object_id: u32) {
         self.packet_orchestrator.borrow_mut().send_by_id(
             id,
@@ -516,6 +717,35 @@ impl Net {
         );
     }
 
+    pub fn set_object_minimap_marker_for_player(
+        &mut self,
+        id: ActorId,
+        object_id: u32,
+        visible: bool,
+    ) {
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            id,
+            Reliability::ReliableOrdered,
+            ServerPacket::ObjectMinimapMarker {
+                id: object_id,
+                visible,
+            },
+        );
+    }
+
+    pub fn set_actor_minimap_marker_for_player(
+        &mut self,
+        id: ActorId,
+        actor_id: ActorId,
+        visible: bool,
+    ) {
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            id,
+            Reliability::ReliableOrdered,
+            ServerPacket::ActorMinimapMarker { actor_id, visible },
+        );
+    }
+
     pub fn move_player_camera(&mut self, id: ActorId, x: f32, y: f32, z: f32, hold_time: f32) {
         self.packet_orchestrator.borrow_mut().send_by_id(
             id,
@@ -972,6 +1202,125 @@ impl Net {
         );
     }
 
+    pub fn open_ranked_season(&mut self, player_id: ActorId, info: RankedSeasonInfo) {
+        ensure_assets(
+            &mut self.packet_orchestrator.borrow_mut(),
+            self.config.args.max_payload_size,
+            &self.asset_manager,
+            &mut self.clients,
+            &[player_id],
+            info.dependencies(),
+        );
+
+        let Some(client) = self.clients.get_mut(&player_id) else {
+            return;
+        };
+
+        self.packet_orchestrator.borrow_mut().send(
+            client.socket_address,
+            Reliability::ReliableOrdered,
+            ServerPacket::RankedSeason { info },
+        );
+    }
+
+    pub fn get_player_rating(&self, player_id: ActorId) -> Option<PlayerRating> {
+        let client = self.clients.get(&player_id)?;
+        let identity = &client.player_data.identity;
+
+        Some(
+            self.matchmaker
+                .rating(identity)
+                .cloned()
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn set_player_rating(&mut self, player_id: ActorId, rating: i32, matches_played: u32) {
+        let Some(client) = self.clients.get(&player_id) else {
+            return;
+        };
+
+        let identity = client.player_data.identity.clone();
+        self.matchmaker.set_rating(identity, rating, matches_played);
+    }
+
+    /// The encounter used for matches created by matchmaking, None for the default PvP encounter
+    pub fn set_ranked_encounter(&mut self, package_path: Option<String>) {
+        self.ranked_encounter_path = package_path;
+    }
+
+    pub fn is_player_in_ranked_queue(&self, player_id: ActorId) -> bool {
+        self.matchmaker.is_queued(player_id)
+    }
+
+    pub(super) fn set_player_ranked_queued(&mut self, player_id: ActorId, queued: bool) {
+        use std::time::Instant;
+
+        let Some(client) = self.clients.get(&player_id) else {
+            return;
+        };
+
+        if !queued {
+            self.matchmaker.dequeue(player_id);
+        } else if !client.is_battling() {
+            self.matchmaker.enqueue(
+                player_id,
+                client.player_data.identity.clone(),
+                client.actor.name.clone(),
+                Instant::now(),
+            );
+        }
+
+        self.packet_orchestrator.borrow_mut().send(
+            client.socket_address,
+            Reliability::ReliableOrdered,
+            ServerPacket::RankedQueueStatus {
+                queued: self.matchmaker.is_queued(player_id),
+            },
+        );
+    }
+
+    pub(super) fn set_netplay_candidates(
+        &mut self,
+        player_id: ActorId,
+        addresses: Vec<std::net::SocketAddr>,
+    ) {
+        // limited to avoid having other clients send hello packets to an arbitrary list of addresses
+        const MAX_NETPLAY_CANDIDATES: usize = 4;
+
+        let Some(client) = self.clients.get_mut(&player_id) else {
+            return;
+        };
+
+        client.netplay_candidates = addresses;
+        client.netplay_candidates.truncate(MAX_NETPLAY_CANDIDATES);
+    }
+
+    /// Ranked matches with results that couldn't be verified, most recent first
+    pub fn flagged_ranked_matches(&self) -> &VecDeque<FlaggedMatch> {
+        self.matchmaker.flagged_matches()
+    }
+
+    /// Should be called before the battle is popped from the player's battle tracker
+    pub(super) fn report_ranked_result(
+        &mut self,
+        player_id: ActorId,
+        stats: &BattleStatistics,
+        signature: Option<[u8; 32]>,
+    ) {
+        let Some(client) = self.clients.get(&player_id) else {
+            return;
+        };
+
+        // folders breaking the deck rules are treated like unsigned results
+        let verified = (client.battle_tracker.front()).is_some_and(|info| {
+            info.deck_rejection.is_none() && info.verify_results(stats, signature)
+        });
+
+        let report = MatchReport::from_statistics(stats, verified);
+        self.matchmaker.report_result(player_id, report);
+    }
+
     pub fn is_player_battling(&self, id: ActorId) -> bool {
         if let Some(client) = self.clients.get(&id) {
             return client.is_battling();
@@ -980,16 +1329,32 @@ impl Net {
         false
     }
 
+    /// `boss_id` picks a player to control the encounter's boss, ignored without an encounter package
     pub fn initiate_netplay(
         &mut self,
         ids: &[ActorId],
         package_path: Option<String>,
-        data: Option<String>,
+        data: Option<EncounterData>,
+        boss_id: Option<ActorId>,
     ) {
+        // player indexes must be contiguous, skip missing and repeated players
+        let mut connected_ids: Vec<ActorId> = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            if self.clients.contains_key(id) && !connected_ids.contains(id) {
+                connected_ids.push(*id);
+            }
+        }
+
+        connected_ids.truncate(MAX_NETPLAY_PLAYERS);
+        let ids = &connected_ids[..];
+
         if let Some(package_path) = package_path.as_ref() {
             self.preload_package(ids, package_path);
         }
 
+        let boss_id = boss_id.filter(|_| package_path.is_some());
+
         // todo: put these clients in slow mode
 
         let remote_players: Vec<_> = ids
@@ -999,6 +1364,7 @@ impl Net {
                 self.clients.get(id).map(|client| RemotePlayerInfo {
                     index: i,
                     address: client.socket_address,
+                    candidates: client.netplay_candidates.clone(),
                     health: client.player_data.health,
                     base_health: client.player_data.base_health,
                     emotion: client.player_data.emotion.clone(),
@@ -1016,10 +1382,16 @@ impl Net {
                     .map(|info| info.address)
                     .collect();
 
+                let result_key = BattleTrackingInfo::create_result_key();
+
                 let tracking_info = BattleTrackingInfo {
                     plugin_index: self.active_plugin,
                     player_index,
                     remote_addresses,
+                    result_key: result_key.clone(),
+                    deck_rejection: BattleTrackingInfo::initial_deck_rejection(
+                        client.deck_rules.as_ref(),
+                    ),
                 };
 
                 client.battle_tracker.push_back(tracking_info);
@@ -1030,6 +1402,12 @@ impl Net {
                     .cloned()
                     .collect();
 
+                let role = if boss_id == Some(*id) {
+                    BattleRole::Boss
+                } else {
+                    BattleRole::Player
+                };
+
                 orchestrator.send(
                     client.socket_address,
                     Reliability::ReliableOrdered,
@@ -1037,6 +1415,8 @@ impl Net {
                         package_path: package_path.clone(),
                         data: data.clone(),
                         remote_players,
+                        result_key,
+                        role,
                     },
                 );
             }
@@ -1044,6 +1424,15 @@ impl Net {
     }
 
     pub fn set_player_restrictions(&mut self, player_id: ActorId, restrictions_path: Option<&str>) {
+        let deck_rules = restrictions_path
+            .and_then(|path| self.asset_manager.get_asset(path))
+            .and_then(|asset| asset.data.text())
+            .and_then(|text| parse_deck_rules(&text));
+
+        if let Some(client) = self.clients.get_mut(&player_id) {
+            client.deck_rules = deck_rules;
+        }
+
         if let Some(restrictions_path) = restrictions_path {
             ensure_asset(
                 &mut self.packet_orchestrator.borrow_mut(),
@@ -1064,6 +1453,25 @@ impl Net {
         );
     }
 
+    /// Checks the folder sent at the start of a battle against the player's deck rules,
+    /// returns the reason the folder was rejected
+    pub(super) fn validate_battle_deck(
+        &mut self,
+        player_id: ActorId,
+        cards: &[(PackageId, String)],
+    ) -> Option<String> {
+        let client = self.clients.get_mut(&player_id)?;
+        let rules = client.deck_rules.as_ref()?;
+
+        let cards = cards.iter().map(|(id, code)| (id, code.as_str()));
+
+        // the folder is sent as the battle is initiated
+        match client.battle_tracker.back_mut() {
+            Some(info) => info.track_deck(rules, cards).err(),
+            None => rules.validate(cards).err(),
+        }
+    }
+
     pub fn refer_server(&mut self, player_id: ActorId, name: String, address: String) {
         let Some(client) = self.clients.get_mut(&player_id) else {
             return;
@@ -1188,10 +1596,22 @@ impl Net {
         &mut self,
         player_id: ActorId,
         package_path: &str,
-        data: Option<String>,
+        data: Option<EncounterData>,
+        preview: Option<EncounterPreview>,
     ) {
         self.preload_package(&[player_id], package_path);
 
+        if let Some(preview) = &preview {
+            ensure_assets(
+                &mut self.packet_orchestrator.borrow_mut(),
+                self.config.args.max_payload_size,
+                &self.asset_manager,
+                &mut self.clients,
+                &[player_id],
+                preview.dependencies(),
+            );
+        }
+
         let Some(client) = self.clients.get_mut(&player_id) else {
             return;
         };
@@ -1210,6 +1630,7 @@ impl Net {
             ServerPacket::InitiateEncounter {
                 package_path: package_path.to_string(),
                 data,
+                preview,
             },
         );
     }
@@ -1235,6 +1656,8 @@ impl Net {
         element: String,
         base_health: i32,
     ) {
+        self.mark_party_dirty(player_id);
+
         let client = self.clients.get_mut(&player_id).unwrap();
         let player_data = &mut client.player_data;
 
@@ -1245,6 +1668,8 @@ impl Net {
     }
 
     pub fn set_player_health(&mut self, player_id: ActorId, health: i32) {
+        self.mark_party_dirty(player_id);
+
         if let Some(client) = self.clients.get_mut(&player_id) {
             client.player_data.health = health;
 
@@ -1257,6 +1682,8 @@ impl Net {
     }
 
     pub fn set_player_base_health(&mut self, player_id: ActorId, base_health: i32) {
+        self.mark_party_dirty(player_id);
+
         if let Some(client) = self.clients.get_mut(&player_id) {
             client.player_data.base_health = base_health;
 
@@ -1335,28 +1762,694 @@ impl Net {
         );
     }
 
-    pub fn give_player_card(
+    /// Routes a player's message to its channel, whispers are also sent back to the sender
+    pub fn send_player_chat_message(
+        &mut self,
+        player_id: ActorId,
+        channel: ChatChannel,
+        message: String,
+    ) {
+        let Some(client) = self.clients.get(&player_id) else {
+            return;
+        };
+
+        let packet = ServerPacket::ChatMessage {
+            channel,
+            sender_id: Some(player_id),
+            sender_name: client.actor.name.clone(),
+            message,
+        };
+
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        match channel {
+            ChatChannel::Area => {
+                let Some(area) = self.areas.get(&client.actor.area_id) else {
+                    // area deleted, should be getting kicked
+                    return;
+                };
+
+                broadcast_to_area(
+                    &mut packet_orchestrator,
+                    area,
+                    Reliability::ReliableOrdered,
+                    packet,
+                );
+            }
+            ChatChannel::Whisper(recipient_id) => {
+                if !self.clients.contains_key(&recipient_id) {
+                    return;
+                }
+
+                if recipient_id != player_id {
+                    let reliability = Reliability::ReliableOrdered;
+                    packet_orchestrator.send_by_id(player_id, reliability, packet.clone());
+                }
+
+                packet_orchestrator.send_by_id(recipient_id, Reliability::ReliableOrdered, packet);
+            }
+            ChatChannel::Party => {
+                let Some(party) = self.party_manager.party(player_id) else {
+                    return;
+                };
+
+                for id in &party.members {
+                    packet_orchestrator.send_by_id(
+                        *id,
+                        Reliability::ReliableOrdered,
+                        packet.clone(),
+                    );
+                }
+            }
+            ChatChannel::System => {
+                // reserved for the server
+            }
+        }
+    }
+
+    /// An empty sender name displays the message without a name
+    pub fn send_chat_message(&mut self, player_id: ActorId, sender_name: String, message: String) {
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            player_id,
+            Reliability::ReliableOrdered,
+            ServerPacket::ChatMessage {
+                channel: ChatChannel::System,
+                sender_id: None,
+                sender_name,
+                message,
+            },
+        );
+    }
+
+    pub fn broadcast_chat_message(&mut self, area_id: &str, sender_name: String, message: String) {
+        let Some(area) = self.areas.get(area_id) else {
+            return;
+        };
+
+        let packet = ServerPacket::ChatMessage {
+            channel: ChatChannel::System,
+            sender_id: None,
+            sender_name,
+            message,
+        };
+
+        broadcast_to_area(
+            &mut self.packet_orchestrator.borrow_mut(),
+            area,
+            Reliability::ReliableOrdered,
+            packet,
+        );
+    }
+
+    /// Displayed as a toast and kept in the player's notification list, for events such as friend logins or mail
+    pub fn send_notification(&mut self, player_id: ActorId, message: String) {
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            player_id,
+            Reliability::ReliableOrdered,
+            ServerPacket::Notification { message },
+        );
+    }
+
+    pub fn is_player_chat_muted(&self, player_id: ActorId) -> bool {
+        (self.clients.get(&player_id)).is_some_and(|client| client.chat_muted)
+    }
+
+    pub fn set_player_chat_muted(&mut self, player_id: ActorId, muted: bool) {
+        if let Some(client) = self.clients.get_mut(&player_id) {
+            client.chat_muted = muted;
+        }
+    }
+
+    pub fn get_player_party(&self, player_id: ActorId) -> Option<&str> {
+        let party = self.party_manager.party(player_id)?;
+        Some(&party.id)
+    }
+
+    /// The leader is listed first
+    pub fn get_party_members(&self, party_id: &str) -> Option<&[ActorId]> {
+        let party = self.party_manager.party_by_id(party_id)?;
+        Some(&party.members)
+    }
+
+    /// Moves the player into a party, parties are created when a new id is used.
+    /// Returns the id of the party the player left
+    pub fn set_player_party(
+        &mut self,
+        player_id: ActorId,
+        party_id: Option<String>,
+    ) -> Option<String> {
+        if !self.clients.contains_key(&player_id) {
+            return None;
+        }
+
+        let left_party = match party_id {
+            Some(party_id) => {
+                let left_party = self.party_manager.join(player_id, party_id.clone());
+                self.send_party_update(&party_id);
+                left_party
+            }
+            None => self.party_manager.leave(player_id),
+        };
+
+        let left_party = left_party?;
+
+        if self.party_manager.party(player_id).is_none() {
+            self.send_party_members(player_id, &[]);
+        }
+
+        self.send_party_update(&left_party.id);
+
+        Some(left_party.id)
+    }
+
+    /// Asks a player to join the inviter's party, returns false if either player is missing or the invite isn't allowed
+    pub fn invite_to_party(&mut self, inviter_id: ActorId, invitee_id: ActorId) -> bool {
+        let Some(inviter) = self.clients.get(&inviter_id) else {
+            return false;
+        };
+
+        if !self.clients.contains_key(&invitee_id) {
+            return false;
+        }
+
+        if !self.party_manager.invite(inviter_id, invitee_id) {
+            return false;
+        }
+
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            invitee_id,
+            Reliability::ReliableOrdered,
+            ServerPacket::PartyInvite {
+                inviter_name: inviter.actor.name.clone(),
+            },
+        );
+
+        true
+    }
+
+    /// Returns the id of the joined party
+    pub(super) fn respond_to_party_invite(
+        &mut self,
+        player_id: ActorId,
+        accepted: bool,
+    ) -> Option<String> {
+        if !accepted {
+            let inviter_id = self.party_manager.take_invite(player_id)?;
+            let name = self.clients.get(&player_id)?.actor.name.clone();
+            let notice = format!("{name} declined your party invite.");
+            self.send_chat_message(inviter_id, String::new(), notice);
+            return None;
+        }
+
+        let party_id = self.party_manager.accept(player_id)?;
+        self.send_party_update(&party_id);
+
+        Some(party_id)
+    }
+
+    /// Returns the id of the party the player left
+    pub fn leave_party(&mut self, player_id: ActorId) -> Option<String> {
+        self.set_player_party(player_id, None)
+    }
+
+    /// Resends the party hud for the player's party on the next tick
+    pub(super) fn mark_party_dirty(&mut self, player_id: ActorId) {
+        if let Some(party) = self.party_manager.party(player_id) {
+            self.dirty_parties.insert(party.id.clone());
+        }
+    }
+
+    fn send_party_update(&self, party_id: &str) {
+        let Some(party) = self.party_manager.party_by_id(party_id) else {
+            return;
+        };
+
+        let members: Vec<_> = (party.members.iter())
+            .flat_map(|id| self.clients.get(id))
+            .map(|client| PartyMember {
+                actor_id: client.actor.id,
+                name: client.actor.name.clone(),
+                health: client.player_data.health,
+                max_health: client.player_data.max_health(),
+                area_name: (self.areas.get(&client.actor.area_id))
+                    .map(|area| area.map().name().to_string())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        for id in &party.members {
+            self.send_party_members(*id, &members);
+        }
+    }
+
+    fn send_party_members(&self, player_id: ActorId, members: &[PartyMember]) {
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            player_id,
+            Reliability::ReliableOrdered,
+            ServerPacket::PartyUpdate {
+                members: members.to_vec(),
+            },
+        );
+    }
+
+    fn broadcast_party_updates(&mut self) {
+        let party_ids: Vec<_> = self.dirty_parties.drain().collect();
+
+        for party_id in party_ids {
+            self.send_party_update(&party_id);
+        }
+    }
+
+    pub fn chat_commands(&self) -> &[ChatCommandInfo] {
+        &self.chat_commands
+    }
+
+    /// Case insensitive, without the leading slash
+    pub fn get_chat_command(&self, name: &str) -> Option<&ChatCommandInfo> {
+        (self.chat_commands.iter()).find(|command| command.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Replaces any command with the same name
+    pub fn register_chat_command(&mut self, name: String, description: String) {
+        self.chat_commands
+            .retain(|command| !command.name.eq_ignore_ascii_case(&name));
+        self.chat_commands
+            .push(ChatCommandInfo { name, description });
+        self.chat_commands.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.broadcast_chat_commands();
+    }
+
+    pub fn unregister_chat_command(&mut self, name: &str) {
+        let len = self.chat_commands.len();
+
+        self.chat_commands
+            .retain(|command| !command.name.eq_ignore_ascii_case(name));
+
+        if self.chat_commands.len() != len {
+            self.broadcast_chat_commands();
+        }
+    }
+
+    fn broadcast_chat_commands(&mut self) {
+        self.packet_orchestrator.borrow_mut().broadcast_to_clients(
+            Reliability::ReliableOrdered,
+            ServerPacket::ChatCommands {
+                commands: self.chat_commands.clone(),
+            },
+        );
+    }
+
+    /// Removes items only if the player has enough of them, returns false otherwise
+    pub fn take_player_item(&mut self, player_id: ActorId, item_id: String, count: usize) -> bool {
+        let Some(client) = self.clients.get(&player_id) else {
+            return false;
+        };
+
+        if client.player_data.inventory.count_item(&item_id) < count {
+            return false;
+        }
+
+        self.give_player_item(player_id, item_id, -(count as isize));
+        true
+    }
+
+    pub fn item_registry(&self) -> &HashMap<String, ItemDefinition> {
+        &self.item_registry
+    }
+
+    /// Registered achievements are listed on each client as they connect
+    pub fn register_achievement(&mut self, definition: AchievementDefinition) {
+        (self.achievement_registry).insert(definition.id.clone(), definition);
+    }
+
+    pub fn achievement_registry(&self) -> &HashMap<String, AchievementDefinition> {
+        &self.achievement_registry
+    }
+
+    /// Clients ignore achievements that were already unlocked
+    pub fn unlock_player_achievement(&mut self, player_id: ActorId, id: String) {
+        let Some(definition) = self.achievement_registry.get(&id).cloned() else {
+            log::warn!("No achievement found with id {id:?}");
+            return;
+        };
+
+        // resend in case the definition changed since the player connected
+        self.send_achievement_definitions(player_id, [definition]);
+
+        self.packet_orchestrator.borrow_mut().send_by_id(
+            player_id,
+            Reliability::ReliableOrdered,
+            ServerPacket::UnlockAchievement { id },
+        );
+    }
+
+    fn send_achievement_definitions(
+        &mut self,
+        player_id: ActorId,
+        definitions: impl IntoIterator<Item = AchievementDefinition>,
+    ) {
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        for definition in definitions {
+            ensure_assets(
+                &mut packet_orchestrator,
+                self.config.args.max_payload_size,
+                &self.asset_manager,
+                &mut self.clients,
+                &[player_id],
+                [definition.icon_texture_path.as_str()],
+            );
+
+            packet_orchestrator.send_by_id(
+                player_id,
+                Reliability::ReliableOrdered,
+                ServerPacket::RegisterAchievement { definition },
+            );
+        }
+    }
+
+    pub fn give_player_card(
+        &mut self,
+        player_id: ActorId,
+        package_id: PackageId,
+        code: String,
+        count: isize,
+    ) {
+        if let Some(client) = self.clients.get_mut(&player_id) {
+            client.player_data.add_card(&package_id, &code, count);
+
+            self.packet_orchestrator.borrow_mut().send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::AddCard {
+                    package_id,
+                    code,
+                    count,
+                },
+            );
+        }
+    }
+
+    /// Gives each card once and lets the client present them as an opened pack
+    pub fn give_player_card_pack(
+        &mut self,
+        player_id: ActorId,
+        name: String,
+        cards: Vec<(PackageId, String)>,
+    ) {
+        if let Some(client) = self.clients.get_mut(&player_id) {
+            for (package_id, code) in &cards {
+                client.player_data.add_card(package_id, code, 1);
+            }
+
+            self.packet_orchestrator.borrow_mut().send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::CardPack { name, cards },
+            );
+        }
+    }
+
+    /// Asks the target to trade with the requester, returns false if either player is missing or busy
+    pub fn request_trade(&mut self, requester_id: ActorId, target_id: ActorId) -> bool {
+        let Some(requester) = self.clients.get(&requester_id) else {
+            return false;
+        };
+
+        let Some(target) = self.clients.get(&target_id) else {
+            return false;
+        };
+
+        if !self.trade_broker.request(requester_id, target_id) {
+            return false;
+        }
+
+        self.packet_orchestrator.borrow_mut().send(
+            target.socket_address,
+            Reliability::ReliableOrdered,
+            ServerPacket::TradeRequest {
+                partner_name: requester.actor.name.clone(),
+            },
+        );
+
+        true
+    }
+
+    pub fn is_player_trading(&self, player_id: ActorId) -> bool {
+        self.trade_broker.is_trading(player_id)
+    }
+
+    pub fn cancel_trade(&mut self, player_id: ActorId, reason: &str) {
+        if let Some(trade) = self.trade_broker.remove_player(player_id) {
+            self.end_trade(&trade, false, reason);
+        }
+    }
+
+    pub(super) fn respond_to_trade(&mut self, player_id: ActorId, accepted: bool) {
+        if !accepted {
+            self.cancel_trade(player_id, "The trade was declined.");
+            return;
+        }
+
+        let Some(trade) = self.trade_broker.accept(player_id).cloned() else {
+            return;
+        };
+
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        for (side, id) in trade.players.iter().enumerate() {
+            let partner_id = trade.players[1 - side];
+
+            let (Some(client), Some(partner)) =
+                (self.clients.get(id), self.clients.get(&partner_id))
+            else {
+                continue;
+            };
+
+            packet_orchestrator.send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::TradeStart {
+                    partner_name: partner.actor.name.clone(),
+                },
+            );
+        }
+
+        drop(packet_orchestrator);
+
+        self.send_trade_update(&trade);
+    }
+
+    pub(super) fn set_trade_offer(&mut self, player_id: ActorId, offer: TradeOffer) {
+        let offer = offer
+            .normalized()
+            .filter(|offer| self.player_owns_offer(player_id, offer));
+
+        let Some(offer) = offer else {
+            // resync the client with the offer we still hold
+            if let Some(trade) = self.trade_broker.trade(player_id).cloned() {
+                self.send_trade_update(&trade);
+            }
+
+            return;
+        };
+
+        if let Some(trade) = self.trade_broker.set_offer(player_id, offer).cloned() {
+            self.send_trade_update(&trade);
+        }
+    }
+
+    /// Returns the trade after both players confirm and the offers are swapped
+    pub(super) fn confirm_trade(&mut self, player_id: ActorId, revision: u32) -> Option<Trade> {
+        let trade = match self.trade_broker.confirm(player_id, revision) {
+            TradeConfirmation::Ignored => return None,
+            TradeConfirmation::Waiting(trade) => {
+                let trade = trade.clone();
+                self.send_trade_update(&trade);
+                return None;
+            }
+            TradeConfirmation::Complete(trade) => trade,
+        };
+
+        // everything is verified before anything moves, the trade completes fully or not at all
+        let offers_available = (trade.players.iter())
+            .zip(&trade.offers)
+            .all(|(player_id, offer)| self.player_owns_offer(*player_id, offer));
+
+        if !offers_available {
+            self.end_trade(&trade, false, "Part of an offer is no longer available.");
+            return None;
+        }
+
+        for (side, offer) in trade.offers.iter().enumerate() {
+            let giver_id = trade.players[side];
+            let receiver_id = trade.players[1 - side];
+
+            if offer.money > 0 {
+                self.adjust_player_money(giver_id, -(offer.money as i64));
+                self.adjust_player_money(receiver_id, offer.money as i64);
+            }
+
+            for (package_id, code, count) in &offer.cards {
+                let count = *count as isize;
+                self.give_player_card(giver_id, package_id.clone(), code.clone(), -count);
+                self.give_player_card(receiver_id, package_id.clone(), code.clone(), count);
+            }
+
+            for (item_id, count) in &offer.items {
+                let count = *count as isize;
+                self.give_player_item(giver_id, item_id.clone(), -count);
+                self.give_player_item(receiver_id, item_id.clone(), count);
+            }
+        }
+
+        self.end_trade(&trade, true, "");
+
+        Some(trade)
+    }
+
+    fn adjust_player_money(&mut self, player_id: ActorId, delta: i64) {
+        let Some(client) = self.clients.get(&player_id) else {
+            return;
+        };
+
+        let money = (client.player_data.money as i64 + delta).clamp(0, u32::MAX as i64);
+        self.set_player_money(player_id, money as u32);
+    }
+
+    fn player_owns_offer(&self, player_id: ActorId, offer: &TradeOffer) -> bool {
+        let Some(client) = self.clients.get(&player_id) else {
+            return false;
+        };
+
+        let player_data = &client.player_data;
+
+        if player_data.money < offer.money {
+            return false;
+        }
+
+        let owns_cards = offer.cards.iter().all(|(package_id, code, count)| {
+            let key = (
+                Cow::Borrowed(package_id.as_str()),
+                Cow::Borrowed(code.as_str()),
+            );
+            let owned_count = player_data.owned_cards.get(&key);
+
+            owned_count.is_some_and(|owned_count| owned_count >= count)
+        });
+
+        let owns_items = offer.items.iter().all(|(item_id, count)| {
+            self.item_registry.contains_key(item_id)
+                && player_data.inventory.count_item(item_id) >= *count
+        });
+
+        owns_cards && owns_items
+    }
+
+    fn send_trade_update(&mut self, trade: &Trade) {
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        for (side, player_id) in trade.players.iter().enumerate() {
+            let Some(client) = self.clients.get(player_id) else {
+                continue;
+            };
+
+            let partner_side = 1 - side;
+            let partner_offer = &trade.offers[partner_side];
+
+            // the partner's items need to be registered to display their names
+            for (item_id, _) in &partner_offer.items {
+                if client.player_data.inventory.item_registered(item_id) {
+                    continue;
+                }
+
+                if let Some(item_definition) = self.item_registry.get(item_id) {
+                    packet_orchestrator.send(
+                        client.socket_address,
+                        Reliability::ReliableOrdered,
+                        ServerPacket::RegisterItem {
+                            id: item_id.clone(),
+                            item_definition: item_definition.clone(),
+                        },
+                    );
+                }
+            }
+
+            packet_orchestrator.send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::TradeUpdate {
+                    offer: trade.offers[side].clone(),
+                    partner_offer: partner_offer.clone(),
+                    revision: trade.revision,
+                    confirmed: trade.confirmed[side],
+                    partner_confirmed: trade.confirmed[partner_side],
+                },
+            );
+        }
+    }
+
+    fn end_trade(&mut self, trade: &Trade, completed: bool, reason: &str) {
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        for player_id in &trade.players {
+            let Some(client) = self.clients.get(player_id) else {
+                continue;
+            };
+
+            packet_orchestrator.send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::TradeEnd {
+                    completed,
+                    reason: reason.to_string(),
+                },
+            );
+        }
+    }
+
+    /// Adds the quest to the player's quest log, replacing any quest with the same id
+    pub fn update_player_quest(&mut self, player_id: ActorId, quest: Quest) {
+        if let Some(client) = self.clients.get(&player_id) {
+            self.packet_orchestrator.borrow_mut().send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::QuestUpdate { quest },
+            );
+        }
+    }
+
+    pub fn set_player_quest_progress(
         &mut self,
         player_id: ActorId,
-        package_id: PackageId,
-        code: String,
-        count: isize,
+        id: String,
+        objective_index: usize,
+        progress: u32,
     ) {
-        if let Some(client) = self.clients.get_mut(&player_id) {
-            client.player_data.add_card(&package_id, &code, count);
-
+        if let Some(client) = self.clients.get(&player_id) {
             self.packet_orchestrator.borrow_mut().send(
                 client.socket_address,
                 Reliability::ReliableOrdered,
-                ServerPacket::AddCard {
-                    package_id,
-                    code,
-                    count,
+                ServerPacket::QuestProgress {
+                    id,
+                    objective_index,
+                    progress,
                 },
             );
         }
     }
 
+    pub fn remove_player_quest(&mut self, player_id: ActorId, id: String) {
+        if let Some(client) = self.clients.get(&player_id) {
+            self.packet_orchestrator.borrow_mut().send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::QuestRemove { id },
+            );
+        }
+    }
+
     pub fn give_player_block(
         &mut self,
         player_id: ActorId,
@@ -1510,6 +2603,10 @@ impl Net {
         client.transferring = true;
         client.ready = false;
 
+        if let Some(party) = self.party_manager.party(player_id) {
+            self.dirty_parties.insert(party.id.clone());
+        }
+
         self.packet_orchestrator.borrow_mut().send(
             client.socket_address,
             Reliability::ReliableOrdered,
@@ -1566,6 +2663,14 @@ impl Net {
 
     pub fn kick_player(&mut self, id: ActorId, reason: &str, warp_out: bool) {
         if let Some(client) = self.clients.get(&id) {
+            if client.suspended_since.is_some() {
+                // no connection to kick
+                if !self.kicked_sessions.contains(&id) {
+                    self.kicked_sessions.push(id);
+                }
+                return;
+            }
+
             self.kick_list.push(Boot {
                 socket_address: client.socket_address,
                 reason: reason.to_string(),
@@ -1619,6 +2724,26 @@ impl Net {
         id
     }
 
+    fn reject_avatar(&mut self, player_id: ActorId, reason: &str) {
+        let texture_path = asset::get_player_texture_path(player_id);
+
+        if self.get_asset(&texture_path).is_none() {
+            // no previous avatar to fall back to
+            self.kick_player(player_id, reason, true);
+            return;
+        }
+
+        if let Some(client) = self.clients.get(&player_id) {
+            self.packet_orchestrator.borrow_mut().send(
+                client.socket_address,
+                Reliability::ReliableOrdered,
+                ServerPacket::AvatarRejected {
+                    reason: reason.to_string(),
+                },
+            );
+        }
+    }
+
     pub(super) fn store_player_assets(&mut self, player_id: ActorId) -> Option<(String, String)> {
         use super::client::find_longest_frame_length;
 
@@ -1631,21 +2756,21 @@ impl Net {
             String::from_utf8_lossy(&client.mugshot_animation_buffer).into_owned();
 
         // reset buffers to store new data later
-        client.texture_buffer.clear();
-        client.animation_buffer.clear();
-        client.mugshot_texture_buffer.clear();
-        client.mugshot_animation_buffer.clear();
+        client.clear_avatar_buffers();
 
         let avatar_dimensions_limit = self.config.args.avatar_dimensions_limit;
 
-        if find_longest_frame_length(&animation_data) > avatar_dimensions_limit {
-            let reason = format!(
-                "Avatar has frames larger than limit {}x{}",
-                avatar_dimensions_limit, avatar_dimensions_limit
-            );
-
-            self.kick_player(player_id, &reason, true);
+        let rejection = client.avatar_rejection.take().or_else(|| {
+            (find_longest_frame_length(&animation_data) > avatar_dimensions_limit).then(|| {
+                format!(
+                    "Avatar has frames larger than limit {}x{}",
+                    avatar_dimensions_limit, avatar_dimensions_limit
+                )
+            })
+        });
 
+        if let Some(reason) = rejection {
+            self.reject_avatar(player_id, &reason);
             return None;
         }
 
@@ -1735,13 +2860,143 @@ impl Net {
     }
 
     pub(super) fn connect_client(&mut self, player_id: ActorId) {
-        self.packet_orchestrator.borrow_mut().send_by_id(
+        let definitions: Vec<_> = self.achievement_registry.values().cloned().collect();
+        self.send_achievement_definitions(player_id, definitions);
+
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        if let Some(client) = self.clients.get(&player_id) {
+            if self.config.args.resume_window > 0.0 {
+                packet_orchestrator.send(
+                    client.socket_address,
+                    Reliability::ReliableOrdered,
+                    ServerPacket::ResumeToken {
+                        token: client.resume_token.clone(),
+                    },
+                );
+            }
+        }
+
+        packet_orchestrator.send_by_id(
+            player_id,
+            Reliability::ReliableOrdered,
+            ServerPacket::ChatCommands {
+                commands: self.chat_commands.clone(),
+            },
+        );
+
+        packet_orchestrator.send_by_id(
             player_id,
             Reliability::ReliableOrdered,
             ServerPacket::CompleteConnection,
         );
     }
 
+    /// Keeps a player that lost connection in the area, returns false if the player can't be resumed
+    pub(super) fn suspend_client(&mut self, player_id: ActorId) -> bool {
+        use std::time::Instant;
+
+        if self.config.args.resume_window <= 0.0 {
+            return false;
+        }
+
+        let Some(client) = self.clients.get_mut(&player_id) else {
+            return false;
+        };
+
+        if !client.ready {
+            // still joining, there's nothing to resume
+            return false;
+        }
+
+        client.suspended_since = Some(Instant::now());
+        true
+    }
+
+    pub(super) fn find_suspended_session(&self, token: &[u8]) -> Option<ActorId> {
+        self.clients
+            .iter()
+            .find(|(_, client)| client.suspended_since.is_some() && client.resume_token == token)
+            .map(|(id, _)| *id)
+    }
+
+    /// Suspended players that were kicked or haven't resumed within the resume window
+    pub(super) fn expired_sessions(&mut self) -> Vec<ActorId> {
+        let resume_window = std::time::Duration::from_secs_f32(self.config.args.resume_window);
+
+        let mut expired: Vec<ActorId> = self
+            .clients
+            .iter()
+            .filter(|(_, client)| {
+                client
+                    .suspended_since
+                    .is_some_and(|time| time.elapsed() >= resume_window)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in std::mem::take(&mut self.kicked_sessions) {
+            let still_suspended = self
+                .clients
+                .get(&id)
+                .is_some_and(|client| client.suspended_since.is_some());
+
+            // kicked sessions may have resumed since
+            if still_suspended && !expired.contains(&id) {
+                expired.push(id);
+            }
+        }
+
+        expired
+    }
+
+    /// Binds a suspended player to a new connection, the player is resynced after RequestJoin
+    pub(super) fn resume_client(
+        &mut self,
+        player_id: ActorId,
+        socket_address: std::net::SocketAddr,
+    ) {
+        let Some(client) = self.clients.get_mut(&player_id) else {
+            return;
+        };
+
+        client.socket_address = socket_address;
+        client.suspended_since = None;
+        client.resuming = true;
+
+        // streams in flight were lost with the connection, the client resends what it has
+        client.cached_assets.clear();
+
+        self.packet_orchestrator
+            .borrow_mut()
+            .register_client(socket_address, player_id);
+    }
+
+    /// Resends the player's area, as the client may have missed updates while disconnected
+    pub(super) fn resync_client(&mut self, player_id: ActorId) {
+        let Some(client) = self.clients.get(&player_id) else {
+            return;
+        };
+
+        let area_id = client.actor.area_id.clone();
+
+        let in_area = self
+            .areas
+            .get(&area_id)
+            .is_some_and(|area| area.connected_players().contains(&player_id));
+
+        if !in_area {
+            // lost connection mid transfer, finish the transfer instead
+            self.complete_transfer(player_id);
+            return;
+        }
+
+        let actor = &client.actor;
+        let (x, y, z, direction) = (actor.x, actor.y, actor.z, actor.direction);
+
+        self.transfer_player(player_id, &area_id, false, x, y, z, direction);
+    }
+
     fn send_area(&mut self, player_id: ActorId, area_id: &str) {
         use super::asset::get_map_path;
 
@@ -1882,6 +3137,12 @@ impl Net {
         };
 
         self.free_actor_id(id);
+        self.matchmaker.remove_player(id);
+        self.cancel_trade(id, "Your trade partner left.");
+
+        if let Some(party) = self.party_manager.remove_player(id) {
+            self.dirty_parties.insert(party.id);
+        }
 
         // remove assets
         let remove_list = [
@@ -1909,7 +3170,11 @@ impl Net {
         // remove from packet_orchestrator
         let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
 
-        packet_orchestrator.unregister_client(client.socket_address);
+        if client.suspended_since.is_none() {
+            // suspended clients were unregistered when their connection dropped
+            // another client may be using the address now
+            packet_orchestrator.unregister_client(client.socket_address);
+        }
 
         // remove player from the area
         let Some(area) = self.areas.get_mut(&client.actor.area_id) else {
@@ -2525,6 +3790,217 @@ impl Net {
         });
     }
 
+    pub(super) fn add_cluster_peer(&mut self, socket_address: std::net::SocketAddr) {
+        self.cluster.add_peer(socket_address);
+    }
+
+    pub(super) fn is_cluster_peer(&self, socket_address: std::net::SocketAddr) -> bool {
+        self.cluster.is_peer(socket_address)
+    }
+
+    pub(super) fn update_cluster_peer(
+        &mut self,
+        socket_address: std::net::SocketAddr,
+        public_address: String,
+        areas: Vec<String>,
+        player_count: usize,
+    ) {
+        use std::time::Instant;
+
+        self.cluster.update_peer(
+            socket_address,
+            public_address,
+            areas,
+            player_count,
+            Instant::now(),
+        );
+    }
+
+    /// Public addresses of other servers in the cluster hosting the area
+    pub fn get_cluster_area_hosts(&mut self, area_id: &str) -> Vec<String> {
+        use std::time::Instant;
+
+        self.cluster.area_hosts(area_id, Instant::now())
+    }
+
+    /// Returns the address of the least populated server in the cluster hosting the requested area.
+    /// Players without a request are routed to the default area.
+    pub(super) fn route_cluster_player(&mut self, data: &str) -> Option<String> {
+        use std::time::Instant;
+
+        let area_id = cluster_area_request(data).unwrap_or("default");
+
+        self.cluster.route_player(area_id, Instant::now())
+    }
+
+    /// Moves a newly connected player into the area requested by a cluster transfer
+    pub(super) fn accept_cluster_area_request(&mut self, player_id: ActorId, data: &str) {
+        let Some(area_id) = cluster_area_request(data) else {
+            return;
+        };
+
+        let Some(area) = self.areas.get(area_id) else {
+            return;
+        };
+
+        let map = area.map();
+        let (x, y, z) = map.spawn_position();
+        let direction = map.spawn_direction();
+
+        self.transfer_player(player_id, area_id, true, x, y, z, direction);
+    }
+
+    /// Transfers the player to the area on this server, or to the least populated server in the cluster hosting it.
+    /// Returns false if no server hosts the area.
+    pub fn transfer_cluster_area(&mut self, id: ActorId, area_id: &str, warp: bool) -> bool {
+        use std::time::Instant;
+
+        if let Some(area) = self.areas.get(area_id) {
+            let map = area.map();
+            let (x, y, z) = map.spawn_position();
+            let direction = map.spawn_direction();
+
+            self.transfer_player(id, area_id, warp, x, y, z, direction);
+            return true;
+        }
+
+        let Some(address) = self.cluster.route_player(area_id, Instant::now()) else {
+            return false;
+        };
+
+        let data = create_cluster_area_request(area_id);
+        self.transfer_server(id, &address, &data, warp);
+
+        true
+    }
+
+    /// Emits the event on every server in the cluster, excluding this one
+    pub fn broadcast_cluster_event(&mut self, name: &str, data: &[u8]) {
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        for socket_address in self.cluster.peer_addresses() {
+            packet_orchestrator.create_connection(socket_address);
+            packet_orchestrator.send_server_comm(
+                socket_address,
+                Reliability::ReliableOrdered,
+                ServerCommPacket::ClusterEvent {
+                    name: name.to_string(),
+                    data: data.to_vec(),
+                },
+            );
+        }
+    }
+
+    fn broadcast_cluster_status(&mut self) {
+        use std::time::Instant;
+
+        if self.cluster.is_empty() || !self.cluster.poll_status_broadcast(Instant::now()) {
+            return;
+        }
+
+        let public_address = format!("{}:{}", self.config.public_ip, self.config.args.port);
+        let areas: Vec<String> = self.areas.keys().cloned().collect();
+        let player_count = self.clients.len();
+
+        let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
+
+        for socket_address in self.cluster.peer_addresses() {
+            // recreates connections dropped for silence
+            packet_orchestrator.create_connection(socket_address);
+            packet_orchestrator.send_server_comm(
+                socket_address,
+                Reliability::Reliable,
+                ServerCommPacket::ClusterStatus {
+                    public_address: public_address.clone(),
+                    areas: areas.clone(),
+                    player_count,
+                },
+            );
+        }
+    }
+
+    pub fn is_under_maintenance(&self) -> bool {
+        self.maintenance.is_some()
+    }
+
+    /// Refuses new connections, and kicks everyone once the countdown ends.
+    /// Players are moved to the safe area, if it exists.
+    pub fn start_maintenance(&mut self, duration: f32, safe_area: Option<String>) {
+        log::info!("Starting maintenance in {}", format_countdown(duration));
+
+        self.maintenance = Some(Maintenance::new(duration, safe_area));
+    }
+
+    pub fn cancel_maintenance(&mut self) {
+        if self.maintenance.take().is_none() {
+            return;
+        }
+
+        log::info!("Maintenance cancelled");
+
+        let player_ids: Vec<ActorId> = self.clients.keys().copied().collect();
+
+        for id in player_ids {
+            self.message_player(id, "Maintenance cancelled.", TextboxOptions::default());
+        }
+    }
+
+    /// True once maintenance has ended and every player has been kicked
+    pub(super) fn is_ready_for_shutdown(&self) -> bool {
+        let Some(maintenance) = &self.maintenance else {
+            return false;
+        };
+
+        maintenance.is_complete() && self.clients.is_empty()
+    }
+
+    pub(super) fn tick_maintenance(&mut self, delta: f32) {
+        let Some(maintenance) = &mut self.maintenance else {
+            return;
+        };
+
+        if maintenance.is_complete() {
+            return;
+        }
+
+        let is_starting = !maintenance.has_started();
+        let announcement = maintenance.update(delta);
+        let safe_area = maintenance.safe_area().map(String::from);
+        let is_complete = maintenance.is_complete();
+
+        let player_ids: Vec<ActorId> = self.clients.keys().copied().collect();
+
+        if let Some(remaining) = announcement {
+            let message = format!(
+                "The server is shutting down for maintenance in {}.",
+                format_countdown(remaining)
+            );
+
+            for &id in &player_ids {
+                self.message_player(id, &message, TextboxOptions::default());
+            }
+        }
+
+        if let Some(area_id) = safe_area.filter(|_| is_starting) {
+            for &id in &player_ids {
+                if self
+                    .get_player(id)
+                    .is_some_and(|player| player.area_id != area_id)
+                {
+                    self.transfer_cluster_area(id, &area_id, true);
+                }
+            }
+        }
+
+        if is_complete {
+            log::info!("Maintenance started, disconnecting players");
+
+            for id in player_ids {
+                self.kick_player(id, "Server shut down for maintenance", true);
+            }
+        }
+    }
+
     // ugly opengl like context storing
     // needed to correctly track message owners send without adding extra parameters
     // luckily not visible to plugin authors
@@ -2535,6 +4011,33 @@ impl Net {
     pub(super) fn tick(&mut self) {
         self.broadcast_bot_positions();
         self.broadcast_map_changes();
+        self.start_ranked_matches();
+        self.save_ratings();
+        self.broadcast_cluster_status();
+        self.broadcast_party_updates();
+    }
+
+    fn start_ranked_matches(&mut self) {
+        use std::time::Instant;
+
+        for ids in self.matchmaker.pair_players(Instant::now()) {
+            for id in ids {
+                self.packet_orchestrator.borrow_mut().send_by_id(
+                    id,
+                    Reliability::ReliableOrdered,
+                    ServerPacket::RankedQueueStatus { queued: false },
+                );
+            }
+
+            self.initiate_netplay(&ids, self.ranked_encounter_path.clone(), None, None);
+        }
+    }
+
+    fn save_ratings(&mut self) {
+        if self.matchmaker.take_ratings_changed() {
+            let ratings_text = self.matchmaker.ratings_to_string();
+            crate::jobs::write_file::write_file(RATINGS_PATH.to_string(), ratings_text.as_bytes());
+        }
     }
 
     fn broadcast_bot_positions(&mut self) {
@@ -2604,6 +4107,24 @@ impl Net {
     }
 }
 
+fn parse_deck_rules(restrictions_text: &str) -> Option<DeckRules> {
+    let mut root_table: toml::Table = match restrictions_text.parse() {
+        Ok(table) => table,
+        Err(err) => {
+            log::error!("Failed to parse restrictions: {err}");
+            return None;
+        }
+    };
+
+    match root_table.remove("deck")?.try_into() {
+        Ok(rules) => Some(rules),
+        Err(err) => {
+            log::error!("Failed to parse deck restrictions: {err}");
+            None
+        }
+    }
+}
+
 fn broadcast_actor_keyframes(
     packet_orchestrator: &mut PacketOrchestrator,
     area: &Area,
@@ -2741,6 +4262,7 @@ fn ensure_asset<PI, P>(
         };
 
         let mut byte_vecs = Vec::new();
+        let audio_hash = asset_manager.get_audio_hash(asset_path);
 
         for player_id in player_ids.clone() {
             let client = clients.get_mut(player_id.deref()).unwrap();
@@ -2749,6 +4271,27 @@ fn ensure_asset<PI, P>(
                 continue;
             }
 
+            let cached_audio_hash = audio_hash.filter(|_| asset.cache_to_disk);
+
+            if let Some(hash) = cached_audio_hash {
+                // the client may have this audio from a previous session, possibly from another server,
+                // the asset is streamed if the client answers with CachedAudioMissing
+                if asset.cachable {
+                    client.cached_assets.insert(asset_path.to_string());
+                }
+
+                packet_orchestrator.send(
+                    client.socket_address,
+                    Reliability::ReliableOrdered,
+                    ServerPacket::CachedAudio {
+                        name: asset_path.to_string(),
+                        last_modified: asset.last_modified,
+                        hash,
+                    },
+                );
+                continue;
+            }
+
             // lazily create stream
             if byte_vecs.is_empty() {
                 use packets::serialize;
@@ -227,6 +227,7 @@ impl PacketOrchestrator {
             }
         }
 
+        let reliability = packet.reliability();
         let data = Arc::new(serialize(packet));
 
         if let Some(addresses) = self.netplay_route_map.get(&socket_address) {
@@ -234,7 +235,7 @@ impl PacketOrchestrator {
                 if let Some(index) = self.connection_map.get(address) {
                     self.connections[*index]
                         .netplay_channel
-                        .send_shared_bytes(Reliability::ReliableOrdered, data.clone());
+                        .send_shared_bytes(reliability, data.clone());
                 }
             }
         }
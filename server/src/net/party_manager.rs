@@ -0,0 +1,180 @@
+use packets::structures::ActorId;
+
+pub(super) const MAX_PARTY_SIZE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Party {
+    pub id: String,
+    /// The leader followed by the other members
+    pub members: Vec<ActorId>,
+}
+
+/// Tracks parties and pending invites, a player can only be in one party at a time
+#[derive(Default)]
+pub(super) struct PartyManager {
+    parties: Vec<Party>,
+    /// Pending invites as (inviter, invitee)
+    invites: Vec<(ActorId, ActorId)>,
+    next_id: u32,
+}
+
+impl PartyManager {
+    pub fn party(&self, player_id: ActorId) -> Option<&Party> {
+        (self.parties.iter()).find(|party| party.members.contains(&player_id))
+    }
+
+    pub fn party_by_id(&self, party_id: &str) -> Option<&Party> {
+        (self.parties.iter()).find(|party| party.id == party_id)
+    }
+
+    /// Returns false if the invitee is in a party, already invited, or the inviter's party is full
+    pub fn invite(&mut self, inviter_id: ActorId, invitee_id: ActorId) -> bool {
+        if inviter_id == invitee_id
+            || self.party(invitee_id).is_some()
+            || self.invites.iter().any(|(_, id)| *id == invitee_id)
+        {
+            return false;
+        }
+
+        if self
+            .party(inviter_id)
+            .is_some_and(|party| party.members.len() >= MAX_PARTY_SIZE)
+        {
+            return false;
+        }
+
+        self.invites.push((inviter_id, invitee_id));
+        true
+    }
+
+    /// Joins the inviter's party, creating one if the inviter isn't in a party.
+    /// Returns the party's id, None without an invite or if the party filled up
+    pub fn accept(&mut self, invitee_id: ActorId) -> Option<String> {
+        let inviter_id = self.take_invite(invitee_id)?;
+
+        if let Some(party) = self.party(inviter_id) {
+            if party.members.len() >= MAX_PARTY_SIZE {
+                return None;
+            }
+
+            let party_id = party.id.clone();
+            self.join(invitee_id, party_id.clone());
+            return Some(party_id);
+        }
+
+        self.next_id += 1;
+        let party_id = format!("party-{}", self.next_id);
+
+        self.parties.push(Party {
+            id: party_id.clone(),
+            members: vec![inviter_id, invitee_id],
+        });
+
+        Some(party_id)
+    }
+
+    /// Returns the inviter
+    pub fn take_invite(&mut self, invitee_id: ActorId) -> Option<ActorId> {
+        let index = (self.invites.iter()).position(|(_, id)| *id == invitee_id)?;
+        Some(self.invites.remove(index).0)
+    }
+
+    /// Moves the player into the party, creating it if it doesn't exist.
+    /// Returns the party the player left
+    pub fn join(&mut self, player_id: ActorId, party_id: String) -> Option<Party> {
+        if self
+            .party(player_id)
+            .is_some_and(|party| party.id == party_id)
+        {
+            return None;
+        }
+
+        let left_party = self.leave(player_id);
+
+        match self.parties.iter_mut().find(|party| party.id == party_id) {
+            Some(party) => party.members.push(player_id),
+            None => self.parties.push(Party {
+                id: party_id,
+                members: vec![player_id],
+            }),
+        }
+
+        left_party
+    }
+
+    /// Returns the party as it is after the player left, empty parties are removed
+    pub fn leave(&mut self, player_id: ActorId) -> Option<Party> {
+        let index = (self.parties.iter()).position(|party| party.members.contains(&player_id))?;
+        let party = &mut self.parties[index];
+
+        party.members.retain(|id| *id != player_id);
+
+        if party.members.is_empty() {
+            Some(self.parties.remove(index))
+        } else {
+            Some(party.clone())
+        }
+    }
+
+    /// Used for disconnects, also drops invites involving the player
+    pub fn remove_player(&mut self, player_id: ActorId) -> Option<Party> {
+        (self.invites).retain(|(inviter, invitee)| *inviter != player_id && *invitee != player_id);
+        self.leave(player_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn id(index: u32) -> ActorId {
+        ActorId::new(index, 0)
+    }
+
+    #[test]
+    fn invites_create_and_fill_parties() {
+        let mut manager = PartyManager::default();
+
+        assert!(!manager.invite(id(1), id(1)));
+        assert!(manager.invite(id(1), id(2)));
+        assert!(!manager.invite(id(3), id(2)));
+
+        // accepting creates a party led by the inviter
+        let party_id = manager.accept(id(2)).unwrap();
+        assert_eq!(manager.party(id(1)).unwrap().members, [id(1), id(2)]);
+        assert!(manager.accept(id(2)).is_none());
+
+        // members of a party can't be invited
+        assert!(!manager.invite(id(3), id(1)));
+
+        for i in 3..=4 {
+            assert!(manager.invite(id(2), id(i)));
+            assert_eq!(manager.accept(id(i)), Some(party_id.clone()));
+        }
+
+        // full
+        assert!(!manager.invite(id(1), id(5)));
+    }
+
+    #[test]
+    fn leaving_passes_leadership_and_removes_empty_parties() {
+        let mut manager = PartyManager::default();
+        manager.invite(id(1), id(2));
+        let party_id = manager.accept(id(2)).unwrap();
+
+        let party = manager.leave(id(1)).unwrap();
+        assert_eq!(party.members, [id(2)]);
+
+        let party = manager.remove_player(id(2)).unwrap();
+        assert!(party.members.is_empty());
+        assert!(manager.party_by_id(&party_id).is_none());
+
+        // joining by id creates parties for scripts
+        assert!(manager.join(id(3), String::from("raid")).is_none());
+        assert_eq!(
+            manager.join(id(3), String::from("other")).unwrap().id,
+            "raid"
+        );
+        assert!(manager.party_by_id("raid").is_none());
+    }
+}
@@ -1,6 +1,6 @@
 use super::{BattleStatistics, Net};
 use crate::plugins::PluginInterface;
-use packets::structures::{ActorId, PackageId};
+use packets::structures::{ActorId, ChatChannel, PackageId, TradeOffer};
 
 pub(super) struct PluginWrapper {
     plugin_interfaces: Vec<Box<dyn PluginInterface>>,
@@ -120,11 +120,18 @@ impl PluginInterface for PluginWrapper {
         prevent_default
     }
 
-    fn handle_player_emote(&mut self, net: &mut Net, player_id: ActorId, emote_id: &str) -> bool {
+    fn handle_player_emote(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        emote_id: &str,
+        package_id: Option<&PackageId>,
+    ) -> bool {
         let mut prevent_default = false;
 
         self.wrap_calls(net, |plugin_interface, net| {
-            prevent_default |= plugin_interface.handle_player_emote(net, player_id, emote_id)
+            prevent_default |=
+                plugin_interface.handle_player_emote(net, player_id, emote_id, package_id)
         });
 
         prevent_default
@@ -136,6 +143,76 @@ impl PluginInterface for PluginWrapper {
         });
     }
 
+    fn handle_chat_message(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        channel: ChatChannel,
+        message: String,
+    ) -> Option<String> {
+        let mut message = Some(message);
+
+        // each plugin sees the message left by the previous plugin
+        self.wrap_calls(net, |plugin_interface, net| {
+            if let Some(text) = message.take() {
+                message = plugin_interface.handle_chat_message(net, player_id, channel, text);
+            }
+        });
+
+        message
+    }
+
+    fn handle_party_invite(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        target_id: ActorId,
+    ) -> bool {
+        let mut prevent_default = false;
+
+        self.wrap_calls(net, |plugin_interface, net| {
+            prevent_default |= plugin_interface.handle_party_invite(net, player_id, target_id)
+        });
+
+        prevent_default
+    }
+
+    fn handle_party_join(&mut self, net: &mut Net, player_id: ActorId, party_id: &str) {
+        self.wrap_calls(net, |plugin_interface, net| {
+            plugin_interface.handle_party_join(net, player_id, party_id)
+        });
+    }
+
+    fn handle_party_leave(&mut self, net: &mut Net, player_id: ActorId, party_id: &str) {
+        self.wrap_calls(net, |plugin_interface, net| {
+            plugin_interface.handle_party_leave(net, player_id, party_id)
+        });
+    }
+
+    fn handle_chat_command(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        command: &str,
+        args: &str,
+    ) {
+        self.wrap_calls(net, |plugin_interface, net| {
+            plugin_interface.handle_chat_command(net, player_id, command, args)
+        });
+    }
+
+    fn handle_fast_travel_request(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        area_id: &str,
+        object_id: u32,
+    ) {
+        self.wrap_calls(net, |plugin_interface, net| {
+            plugin_interface.handle_fast_travel_request(net, player_id, area_id, object_id)
+        });
+    }
+
     fn handle_object_interaction(
         &mut self,
         net: &mut Net,
@@ -301,6 +378,29 @@ impl PluginInterface for PluginWrapper {
         });
     }
 
+    fn handle_battle_deck_rejected(&mut self, net: &mut Net, player_id: ActorId, reason: &str) {
+        let plugin_index = (net.get_client(player_id))
+            .and_then(|client| client.battle_tracker.back())
+            .map(|info| info.plugin_index);
+
+        if let Some(i) = plugin_index {
+            self.wrap_call(i, net, |plugin_interface, net| {
+                plugin_interface.handle_battle_deck_rejected(net, player_id, reason)
+            });
+        }
+    }
+
+    fn handle_trade_completed(
+        &mut self,
+        net: &mut Net,
+        player_ids: [ActorId; 2],
+        offers: &[TradeOffer; 2],
+    ) {
+        self.wrap_calls(net, |plugin_interface, net| {
+            plugin_interface.handle_trade_completed(net, player_ids, offers)
+        });
+    }
+
     fn handle_battle_results(
         &mut self,
         net: &mut Net,
@@ -328,4 +428,16 @@ impl PluginInterface for PluginWrapper {
             plugin_interface.handle_server_message(net, socket_address, data)
         });
     }
+
+    fn handle_cluster_event(
+        &mut self,
+        net: &mut Net,
+        socket_address: std::net::SocketAddr,
+        name: &str,
+        data: &[u8],
+    ) {
+        self.wrap_calls(net, |plugin_interface, net| {
+            plugin_interface.handle_cluster_event(net, socket_address, name, data)
+        });
+    }
 }
@@ -2,12 +2,12 @@ use super::plugin_wrapper::PluginWrapper;
 use super::{Net, PacketOrchestrator, ServerConfig};
 use crate::jobs::{JobPromise, PromiseValue};
 use crate::plugins::PluginInterface;
-use crate::threads::{create_listening_thread, ListenerMessage, ThreadMessage};
+use crate::threads::{
+    create_console_thread, create_listening_thread, ListenerMessage, ThreadMessage,
+};
 use flume::{Receiver, Sender};
 use packets::structures::ActorId;
-use packets::{
-    ClientAssetType, ClientPacket, Reliability, ServerCommPacket, ServerPacket, SERVER_TICK_RATE,
-};
+use packets::{ClientPacket, Reliability, ServerCommPacket, ServerPacket, SERVER_TICK_RATE};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::net::{SocketAddr, UdpSocket};
@@ -70,6 +70,8 @@ impl Server {
         use futures::FutureExt;
         use futures::StreamExt;
 
+        self.resolve_cluster_peers().await;
+
         log::info!("Server started");
 
         let (listener_sender, listener_receiver) = flume::unbounded();
@@ -80,6 +82,8 @@ impl Server {
             (*self.config).clone(),
         );
 
+        create_console_thread(self.message_sender.clone());
+
         let sleep_future = async_std::task::sleep(SERVER_TICK_RATE).fuse();
         let mut message_stream = message_receiver.stream();
 
@@ -89,6 +93,12 @@ impl Server {
             futures::select_biased! {
                 _ = sleep_future => {
                     self.tick(listener_sender.clone()).await;
+
+                    if self.net.is_ready_for_shutdown() {
+                        log::info!("Server shut down for maintenance");
+                        return Ok(());
+                    }
+
                     sleep_future.set(async_std::task::sleep(SERVER_TICK_RATE).fuse());
                 }
                 message = message_stream.select_next_some() => {
@@ -125,6 +135,12 @@ impl Server {
                                 ServerCommPacket::Message { data }
                             );
                         }
+                        ThreadMessage::StartMaintenance { duration, safe_area } => {
+                            self.net.start_maintenance(duration, safe_area);
+                        }
+                        ThreadMessage::CancelMaintenance => {
+                            self.net.cancel_maintenance();
+                        }
                         ThreadMessage::PollServer { socket_address, promise } => {
                             let mut packet_orchestrator = self.packet_orchestrator.borrow_mut();
                             packet_orchestrator.create_connection(socket_address);
@@ -153,6 +169,8 @@ impl Server {
         self.plugin_wrapper
             .tick(&mut self.net, elapsed_time.as_secs_f32());
 
+        self.net.tick_maintenance(elapsed_time.as_secs_f32());
+
         // kick silent clients
         let mut kick_list = self.packet_orchestrator.borrow_mut().tick();
 
@@ -165,6 +183,27 @@ impl Server {
             })
             .unwrap();
 
+        // silent players stay for a moment, giving them a chance to resume
+        kick_list.retain(|boot| !self.suspend_client(boot.socket_address));
+
+        for player_id in self.net.expired_sessions() {
+            self.plugin_wrapper
+                .handle_player_disconnect(&mut self.net, player_id);
+
+            if let Some(party_id) = self.net.get_player_party(player_id) {
+                let party_id = party_id.to_string();
+
+                self.plugin_wrapper
+                    .handle_party_leave(&mut self.net, player_id, &party_id);
+            }
+
+            self.net.remove_player(player_id, true);
+
+            if self.config.args.log_connections {
+                log::debug!("{:?} disconnected for failing to resume", player_id);
+            }
+        }
+
         // add clients kicked by plugins to the kick list
         kick_list.extend(self.net.take_kick_list());
 
@@ -231,6 +270,37 @@ impl Server {
                 self.plugin_wrapper
                     .handle_server_message(&mut self.net, socket_address, &data);
             }
+            ServerCommPacket::ClusterStatus { .. } | ServerCommPacket::ClusterEvent { .. }
+                if !self.net.is_cluster_peer(socket_address) =>
+            {
+                // only servers listed with --cluster-peer can share cluster state
+                log::debug!("Ignoring cluster packet from unknown address {socket_address}");
+            }
+            ServerCommPacket::ClusterStatus {
+                public_address,
+                areas,
+                player_count,
+            } => {
+                self.net
+                    .update_cluster_peer(socket_address, public_address, areas, player_count);
+            }
+            ServerCommPacket::ClusterEvent { name, data } => {
+                self.plugin_wrapper.handle_cluster_event(
+                    &mut self.net,
+                    socket_address,
+                    &name,
+                    &data,
+                );
+            }
+        }
+    }
+
+    async fn resolve_cluster_peers(&mut self) {
+        for address in &self.config.args.cluster_peers {
+            match packets::address_parsing::resolve_socket_addr(address).await {
+                Some(socket_address) => self.net.add_cluster_peer(socket_address),
+                None => log::warn!("Failed to resolve cluster peer {address:?}"),
+            }
         }
     }
 
@@ -275,34 +345,53 @@ impl Server {
                         }
                     }
                 }
-                ClientPacket::Asset { asset_type, data } => {
-                    if data.len() > self.config.args.player_asset_limit {
-                        let reason = format!(
-                            "Avatar asset larger than {}KiB",
-                            self.config.args.player_asset_limit / 1024
-                        );
+                ClientPacket::CachedAudioMissing { path } => {
+                    net.stream_missing_audio(player_id, &path);
+                }
+                ClientPacket::AssetStreamStart { asset_type, size } => {
+                    let asset_limit = self.config.args.player_asset_limit;
 
-                        net.kick_player(player_id, &reason, true);
+                    if let Some(client) = net.get_client_mut(player_id) {
+                        let buffer = client.avatar_buffer_mut(asset_type);
+                        buffer.clear();
+
+                        if size as usize > asset_limit {
+                            let reason =
+                                format!("Avatar asset larger than {}KiB", asset_limit / 1024);
+                            client.avatar_rejection.get_or_insert(reason);
+                        } else {
+                            buffer.reserve(size as usize);
+                        }
                     }
+                }
+                ClientPacket::AssetStream { asset_type, data } => {
+                    let asset_limit = self.config.args.player_asset_limit;
 
                     if let Some(client) = net.get_client_mut(player_id) {
-                        match asset_type {
-                            ClientAssetType::Texture => {
-                                client.texture_buffer = data;
-                            }
-                            ClientAssetType::Animation => {
-                                client.animation_buffer = data;
-                            }
-                            ClientAssetType::MugshotTexture => {
-                                client.mugshot_texture_buffer = data;
-                            }
-                            ClientAssetType::MugshotAnimation => {
-                                client.mugshot_animation_buffer = data;
+                        if client.avatar_rejection.is_none() {
+                            let buffer = client.avatar_buffer_mut(asset_type);
+
+                            if buffer.len() + data.len() > asset_limit {
+                                buffer.clear();
+
+                                let reason =
+                                    format!("Avatar asset larger than {}KiB", asset_limit / 1024);
+                                client.avatar_rejection = Some(reason);
+                            } else {
+                                buffer.extend(data);
                             }
-                        };
+                        }
                     }
                 }
-                ClientPacket::Authorize { .. } | ClientPacket::Login { .. } => {
+                ClientPacket::AssetStreamCancel => {
+                    if let Some(client) = net.get_client_mut(player_id) {
+                        client.clear_avatar_buffers();
+                        client.avatar_rejection = None;
+                    }
+                }
+                ClientPacket::Authorize { .. }
+                | ClientPacket::Login { .. }
+                | ClientPacket::Resume { .. } => {
                     if self.config.args.log_packets {
                         log::debug!(
                             "Previous packet shouldn't be sent if the client is already connected"
@@ -310,6 +399,16 @@ impl Server {
                     }
                 }
                 ClientPacket::RequestJoin => {
+                    let resuming = net
+                        .get_client(player_id)
+                        .is_some_and(|client| client.resuming);
+
+                    if resuming {
+                        // the player never left, avoid running join events again
+                        net.resync_client(player_id);
+                        return;
+                    }
+
                     net.spawn_client(player_id);
 
                     self.plugin_wrapper.handle_player_connect(net, player_id);
@@ -353,7 +452,9 @@ impl Server {
                         client.actor.y = client.warp_y;
                         client.actor.z = client.warp_z;
 
-                        if client.transferring {
+                        if client.resuming {
+                            client.resuming = false;
+                        } else if client.transferring {
                             self.plugin_wrapper.handle_player_transfer(net, player_id);
                         } else {
                             self.plugin_wrapper.handle_player_join(net, player_id);
@@ -369,6 +470,95 @@ impl Server {
                     self.plugin_wrapper
                         .handle_custom_warp(net, player_id, tile_object_id);
                 }
+                ClientPacket::ChatMessage { channel, message } => {
+                    use packets::structures::{ChatChannel, MAX_CHAT_MESSAGE_LENGTH};
+
+                    let message = message.trim();
+
+                    if message.is_empty()
+                        || message.chars().count() > MAX_CHAT_MESSAGE_LENGTH
+                        || channel == ChatChannel::System
+                    {
+                        return;
+                    }
+
+                    if net.is_player_chat_muted(player_id) {
+                        let notice = String::from("You can't chat right now.");
+                        net.send_chat_message(player_id, String::new(), notice);
+                        return;
+                    }
+
+                    if channel == ChatChannel::Party && net.get_player_party(player_id).is_none() {
+                        let notice = String::from("You aren't in a party.");
+                        net.send_chat_message(player_id, String::new(), notice);
+                        return;
+                    }
+
+                    // scripts can block or replace the message
+                    let message = self.plugin_wrapper.handle_chat_message(
+                        net,
+                        player_id,
+                        channel,
+                        message.to_string(),
+                    );
+
+                    if let Some(message) = message {
+                        net.send_player_chat_message(player_id, channel, message);
+                    }
+                }
+                ClientPacket::ChatCommand { name, args } => {
+                    use packets::structures::MAX_CHAT_MESSAGE_LENGTH;
+
+                    if args.chars().count() > MAX_CHAT_MESSAGE_LENGTH {
+                        return;
+                    }
+
+                    // use the registered spelling, commands are case insensitive
+                    let Some(command) = net.get_chat_command(&name) else {
+                        let notice = format!("Unknown command /{name}");
+                        net.send_chat_message(player_id, String::new(), notice);
+                        return;
+                    };
+
+                    let command = command.name.clone();
+
+                    self.plugin_wrapper
+                        .handle_chat_command(net, player_id, &command, args.trim());
+                }
+                ClientPacket::FastTravelRequest {
+                    map_path,
+                    object_id,
+                } => {
+                    // scripts decide whether to move the player
+                    if let Some(area_id) = net.fast_travel_area(&map_path, object_id) {
+                        let area_id = area_id.to_string();
+
+                        self.plugin_wrapper
+                            .handle_fast_travel_request(net, player_id, &area_id, object_id);
+                    }
+                }
+                ClientPacket::PartyInvite { actor_id } => {
+                    let prevent_default = self
+                        .plugin_wrapper
+                        .handle_party_invite(net, player_id, actor_id);
+
+                    if !prevent_default && !net.invite_to_party(player_id, actor_id) {
+                        let notice = String::from("That player can't be invited.");
+                        net.send_chat_message(player_id, String::new(), notice);
+                    }
+                }
+                ClientPacket::PartyResponse { accepted } => {
+                    if let Some(party_id) = net.respond_to_party_invite(player_id, accepted) {
+                        self.plugin_wrapper
+                            .handle_party_join(net, player_id, &party_id);
+                    }
+                }
+                ClientPacket::PartyLeave => {
+                    if let Some(party_id) = net.leave_party(player_id) {
+                        self.plugin_wrapper
+                            .handle_party_leave(net, player_id, &party_id);
+                    }
+                }
                 ClientPacket::Boost {
                     health_boost,
                     augments,
@@ -388,6 +578,8 @@ impl Server {
                             },
                         );
 
+                        net.mark_party_dirty(player_id);
+
                         self.plugin_wrapper
                             .handle_player_augment(net, player_id, &augments);
                     }
@@ -428,12 +620,27 @@ impl Server {
                 ClientPacket::Emote { emote_id } => {
                     let prevent_default = self
                         .plugin_wrapper
-                        .handle_player_emote(net, player_id, &emote_id);
+                        .handle_player_emote(net, player_id, &emote_id, None);
 
                     if !prevent_default {
                         net.set_player_emote(player_id, emote_id);
                     }
                 }
+                ClientPacket::PackageEmote {
+                    package_id,
+                    emote_id,
+                } => {
+                    let prevent_default = self.plugin_wrapper.handle_player_emote(
+                        net,
+                        player_id,
+                        &emote_id,
+                        Some(&package_id),
+                    );
+
+                    if !prevent_default {
+                        net.set_player_package_emote(player_id, package_id, emote_id);
+                    }
+                }
                 ClientPacket::ObjectInteraction {
                     tile_object_id,
                     button,
@@ -556,7 +763,42 @@ impl Server {
                         }
                     }
                 }
-                ClientPacket::BattleResults { battle_stats } => {
+                ClientPacket::BattleDeck { cards } => {
+                    if let Some(reason) = net.validate_battle_deck(player_id, &cards) {
+                        self.plugin_wrapper
+                            .handle_battle_deck_rejected(net, player_id, &reason);
+                    }
+                }
+                ClientPacket::TradeResponse { accepted } => {
+                    net.respond_to_trade(player_id, accepted);
+                }
+                ClientPacket::TradeOffer { offer } => {
+                    net.set_trade_offer(player_id, offer);
+                }
+                ClientPacket::TradeConfirm { revision } => {
+                    if let Some(trade) = net.confirm_trade(player_id, revision) {
+                        self.plugin_wrapper.handle_trade_completed(
+                            net,
+                            trade.players,
+                            &trade.offers,
+                        );
+                    }
+                }
+                ClientPacket::TradeCancel => {
+                    net.cancel_trade(player_id, "The trade was cancelled.");
+                }
+                ClientPacket::RankedQueue { queued } => {
+                    net.set_player_ranked_queued(player_id, queued);
+                }
+                ClientPacket::NetplayCandidates { addresses } => {
+                    net.set_netplay_candidates(player_id, addresses);
+                }
+                ClientPacket::BattleResults {
+                    battle_stats,
+                    signature,
+                } => {
+                    net.report_ranked_result(player_id, &battle_stats, signature);
+
                     self.plugin_wrapper
                         .handle_battle_results(net, player_id, &battle_stats);
                 }
@@ -582,15 +824,66 @@ impl Server {
                         &data,
                     );
                 }
+                ClientPacket::Resume { token } => {
+                    let Some(player_id) = net.find_suspended_session(&token) else {
+                        self.packet_orchestrator.borrow_mut().send(
+                            socket_address,
+                            Reliability::ReliableOrdered,
+                            ServerPacket::Kick {
+                                reason: String::from("Session expired"),
+                            },
+                        );
+                        return;
+                    };
+
+                    net.resume_client(player_id, socket_address);
+                    self.player_id_map.insert(socket_address, player_id);
+
+                    if self.config.args.log_connections {
+                        log::debug!("{:?} resumed from {}", player_id, socket_address);
+                    }
+                }
                 ClientPacket::Login {
                     username,
                     identity,
                     data,
                 } => {
+                    if net.is_under_maintenance() {
+                        self.packet_orchestrator.borrow_mut().send(
+                            socket_address,
+                            Reliability::ReliableOrdered,
+                            ServerPacket::Kick {
+                                reason: String::from("Server is under maintenance"),
+                            },
+                        );
+                        return;
+                    }
+
+                    if self.config.args.gateway {
+                        if let Some(address) = net.route_cluster_player(&data) {
+                            if self.config.args.log_connections {
+                                log::debug!("Routing {socket_address} to {address}");
+                            }
+
+                            self.packet_orchestrator.borrow_mut().send(
+                                socket_address,
+                                Reliability::ReliableOrdered,
+                                ServerPacket::TransferServer {
+                                    address,
+                                    data,
+                                    warp_out: false,
+                                },
+                            );
+                            return;
+                        }
+                    }
+
                     let player_id = net.add_client(socket_address, username, identity);
 
                     self.player_id_map.insert(socket_address, player_id);
 
+                    net.accept_cluster_area_request(player_id, &data);
+
                     self.plugin_wrapper
                         .handle_player_request(net, player_id, &data);
                 }
@@ -605,11 +898,37 @@ impl Server {
         }
     }
 
+    /// Returns true if the player at this address can resume later
+    fn suspend_client(&mut self, socket_address: SocketAddr) -> bool {
+        let Some(&player_id) = self.player_id_map.get(&socket_address) else {
+            return false;
+        };
+
+        if !self.net.suspend_client(player_id) {
+            return false;
+        }
+
+        self.player_id_map.remove(&socket_address);
+
+        if self.config.args.log_connections {
+            log::debug!("{:?} lost connection, waiting for resume", player_id);
+        }
+
+        true
+    }
+
     fn disconnect_client(&mut self, socket_address: SocketAddr, reason: &str, warp_out: bool) {
         if let Some(player_id) = self.player_id_map.remove(&socket_address) {
             self.plugin_wrapper
                 .handle_player_disconnect(&mut self.net, player_id);
 
+            if let Some(party_id) = self.net.get_player_party(player_id) {
+                let party_id = party_id.to_string();
+
+                self.plugin_wrapper
+                    .handle_party_leave(&mut self.net, player_id, &party_id);
+            }
+
             self.net.remove_player(player_id, warp_out);
 
             if self.config.args.log_connections {
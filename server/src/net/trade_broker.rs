@@ -0,0 +1,198 @@
+use packets::structures::{ActorId, TradeOffer};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Trade {
+    /// The requester followed by the player asked to trade
+    pub players: [ActorId; 2],
+    pub offers: [TradeOffer; 2],
+    pub confirmed: [bool; 2],
+    pub accepted: bool,
+    /// Increased each time an offer changes, confirmations only count for the latest revision
+    pub revision: u32,
+}
+
+impl Trade {
+    fn new(requester_id: ActorId, target_id: ActorId) -> Self {
+        Self {
+            players: [requester_id, target_id],
+            offers: Default::default(),
+            confirmed: [false; 2],
+            accepted: false,
+            revision: 0,
+        }
+    }
+
+    pub fn side(&self, player_id: ActorId) -> Option<usize> {
+        self.players.iter().position(|id| *id == player_id)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum TradeConfirmation<'a> {
+    /// Stale revisions, unaccepted trades, and players outside of trades are ignored
+    Ignored,
+    Waiting(&'a Trade),
+    /// Both players confirmed, the trade is removed from the broker
+    Complete(Trade),
+}
+
+/// Tracks trades between players, a player can only be in one trade at a time
+#[derive(Default)]
+pub(super) struct TradeBroker {
+    trades: Vec<Trade>,
+}
+
+impl TradeBroker {
+    pub fn is_trading(&self, player_id: ActorId) -> bool {
+        self.trade_index(player_id).is_some()
+    }
+
+    pub fn trade(&self, player_id: ActorId) -> Option<&Trade> {
+        let index = self.trade_index(player_id)?;
+        Some(&self.trades[index])
+    }
+
+    fn trade_index(&self, player_id: ActorId) -> Option<usize> {
+        (self.trades.iter()).position(|trade| trade.side(player_id).is_some())
+    }
+
+    /// Returns false if either player is busy
+    pub fn request(&mut self, requester_id: ActorId, target_id: ActorId) -> bool {
+        if requester_id == target_id || self.is_trading(requester_id) || self.is_trading(target_id)
+        {
+            return false;
+        }
+
+        self.trades.push(Trade::new(requester_id, target_id));
+        true
+    }
+
+    /// Only the player asked to trade can accept
+    pub fn accept(&mut self, player_id: ActorId) -> Option<&Trade> {
+        let index = self.trade_index(player_id)?;
+        let trade = &mut self.trades[index];
+
+        if trade.accepted || trade.players[1] != player_id {
+            return None;
+        }
+
+        trade.accepted = true;
+        Some(trade)
+    }
+
+    /// Removes the player's trade, used for declines, cancels, and disconnects
+    pub fn remove_player(&mut self, player_id: ActorId) -> Option<Trade> {
+        let index = self.trade_index(player_id)?;
+        Some(self.trades.remove(index))
+    }
+
+    /// Replaces the player's offer, clearing confirmations for both players
+    pub fn set_offer(&mut self, player_id: ActorId, offer: TradeOffer) -> Option<&Trade> {
+        let index = self.trade_index(player_id)?;
+        let trade = &mut self.trades[index];
+        let side = trade.side(player_id)?;
+
+        if !trade.accepted {
+            return None;
+        }
+
+        trade.offers[side] = offer;
+        trade.confirmed = [false; 2];
+        trade.revision += 1;
+        Some(trade)
+    }
+
+    pub fn confirm(&mut self, player_id: ActorId, revision: u32) -> TradeConfirmation<'_> {
+        let Some(index) = self.trade_index(player_id) else {
+            return TradeConfirmation::Ignored;
+        };
+
+        let trade = &mut self.trades[index];
+
+        // confirming an old revision would accept offers the player never saw
+        if !trade.accepted || trade.revision != revision {
+            return TradeConfirmation::Ignored;
+        }
+
+        if let Some(side) = trade.side(player_id) {
+            trade.confirmed[side] = true;
+        }
+
+        if trade.confirmed.iter().all(|confirmed| *confirmed) {
+            TradeConfirmation::Complete(self.trades.remove(index))
+        } else {
+            TradeConfirmation::Waiting(&self.trades[index])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn id(index: u32) -> ActorId {
+        ActorId::new(index, 0)
+    }
+
+    fn offer(money: u32) -> TradeOffer {
+        TradeOffer {
+            money,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn players_trade_with_one_player_at_a_time() {
+        let mut broker = TradeBroker::default();
+
+        assert!(!broker.request(id(1), id(1)));
+        assert!(broker.request(id(1), id(2)));
+        assert!(!broker.request(id(3), id(2)));
+        assert!(!broker.request(id(1), id(3)));
+
+        // only the target can accept
+        assert!(broker.accept(id(1)).is_none());
+        assert!(broker.accept(id(2)).is_some());
+        assert!(broker.accept(id(2)).is_none());
+
+        assert!(broker.remove_player(id(2)).is_some());
+        assert!(!broker.is_trading(id(1)));
+        assert!(broker.request(id(3), id(1)));
+    }
+
+    #[test]
+    fn offers_require_matching_confirmations() {
+        let mut broker = TradeBroker::default();
+        broker.request(id(1), id(2));
+
+        // offers can't be made before the trade is accepted
+        assert!(broker.set_offer(id(1), offer(10)).is_none());
+        assert_eq!(broker.confirm(id(1), 0), TradeConfirmation::Ignored);
+
+        broker.accept(id(2));
+        broker.set_offer(id(1), offer(10));
+        broker.set_offer(id(2), offer(5));
+
+        let revision = broker.trade(id(1)).unwrap().revision;
+        assert!(matches!(
+            broker.confirm(id(1), revision),
+            TradeConfirmation::Waiting(_)
+        ));
+
+        // changing an offer clears confirmations
+        broker.set_offer(id(1), offer(1));
+        assert_eq!(broker.trade(id(1)).unwrap().confirmed, [false, false]);
+        assert_eq!(broker.confirm(id(2), revision), TradeConfirmation::Ignored);
+
+        let revision = broker.trade(id(1)).unwrap().revision;
+        broker.confirm(id(2), revision);
+
+        let TradeConfirmation::Complete(trade) = broker.confirm(id(1), revision) else {
+            panic!("expected the trade to complete");
+        };
+
+        assert_eq!(trade.offers, [offer(1), offer(5)]);
+        assert!(!broker.is_trading(id(1)));
+        assert!(!broker.is_trading(id(2)));
+    }
+}
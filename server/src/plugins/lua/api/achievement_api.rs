@@ -0,0 +1,46 @@
+use super::LuaApi;
+use packets::structures::{AchievementDefinition, ActorId};
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "register_achievement", |api_ctx, lua, params| {
+        let (id, table): (String, mlua::Table) = lua.unpack_multi(params)?;
+
+        let definition = AchievementDefinition {
+            id,
+            name: table.get("name")?,
+            description: table
+                .get::<_, Option<String>>("description")?
+                .unwrap_or_default(),
+            icon_texture_path: (table.get::<_, Option<String>>("icon_texture_path")?)
+                .unwrap_or_default(),
+        };
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.register_achievement(definition);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "get_achievement_ids", |api_ctx, lua, _| {
+        let net = api_ctx.net_ref.borrow();
+
+        let id_iter = (net.achievement_registry().keys())
+            .enumerate()
+            .map(|(i, id)| (i + 1, id.as_str()));
+
+        lua.pack_multi(lua.create_table_from(id_iter)?)
+    });
+
+    lua_api.add_dynamic_function(
+        "Net",
+        "unlock_player_achievement",
+        |api_ctx, lua, params| {
+            let (player_id, id): (ActorId, String) = lua.unpack_multi(params)?;
+
+            let mut net = api_ctx.net_ref.borrow_mut();
+            net.unlock_player_achievement(player_id, id);
+
+            lua.pack_multi(())
+        },
+    );
+}
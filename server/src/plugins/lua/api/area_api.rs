@@ -641,6 +641,22 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         }
     });
 
+    lua_api.add_dynamic_function("Net", "animate_tile", |api_ctx, lua, params| {
+        let (area_id, x, y, z, gid): (mlua::String, i32, i32, i32, u32) =
+            lua.unpack_multi(params)?;
+        let area_id_str = area_id.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        if net.get_area(area_id_str).is_none() {
+            return Err(create_area_error(area_id_str));
+        }
+
+        net.animate_tile(area_id_str, x, y, z, gid);
+
+        lua.pack_multi(())
+    });
+
     lua_api.add_dynamic_function("Net", "provide_asset", |api_ctx, lua, params| {
         let (area_id, asset_path): (mlua::String, mlua::String) = lua.unpack_multi(params)?;
 
@@ -660,4 +676,25 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
 
         lua.pack_multi(())
     });
+
+    lua_api.add_dynamic_function("Net", "play_stinger", |api_ctx, lua, params| {
+        let (area_id, asset_path): (mlua::String, mlua::String) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        net.play_stinger(area_id.to_str()?, asset_path.to_str()?);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "play_sound_at", |api_ctx, lua, params| {
+        let (area_id, asset_path, x, y, z): (mlua::String, mlua::String, f32, f32, f32) =
+            lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        net.play_sound_at(area_id.to_str()?, asset_path.to_str()?, x, y, z);
+
+        lua.pack_multi(())
+    });
 }
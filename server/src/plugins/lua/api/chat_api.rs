@@ -0,0 +1,89 @@
+use super::lua_errors::create_player_error;
+use super::LuaApi;
+use packets::structures::ActorId;
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "send_chat_message", |api_ctx, lua, params| {
+        let (player_id, message, sender_name): (ActorId, String, Option<String>) =
+            lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.send_chat_message(player_id, sender_name.unwrap_or_default(), message);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "broadcast_chat_message", |api_ctx, lua, params| {
+        let (area_id, message, sender_name): (mlua::String, String, Option<String>) =
+            lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.broadcast_chat_message(area_id.to_str()?, sender_name.unwrap_or_default(), message);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "is_player_chat_muted", |api_ctx, lua, params| {
+        let player_id: ActorId = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        if net.get_player(player_id).is_none() {
+            return Err(create_player_error(player_id));
+        }
+
+        lua.pack_multi(net.is_player_chat_muted(player_id))
+    });
+
+    lua_api.add_dynamic_function("Net", "set_player_chat_muted", |api_ctx, lua, params| {
+        let (player_id, muted): (ActorId, bool) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.set_player_chat_muted(player_id, muted);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "register_chat_command", |api_ctx, lua, params| {
+        let (name, description): (String, Option<String>) = lua.unpack_multi(params)?;
+
+        // accept "/name" for convenience
+        let name = name.strip_prefix('/').unwrap_or(&name).to_string();
+
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Invalid chat command name {name:?}"
+            )));
+        }
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.register_chat_command(name, description.unwrap_or_default());
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "unregister_chat_command", |api_ctx, lua, params| {
+        let name: mlua::String = lua.unpack_multi(params)?;
+        let name = name.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.unregister_chat_command(name.strip_prefix('/').unwrap_or(name));
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "list_chat_commands", |api_ctx, lua, _| {
+        let net = api_ctx.net_ref.borrow();
+        let table = lua.create_table()?;
+
+        for (i, command) in net.chat_commands().iter().enumerate() {
+            let command_table = lua.create_table()?;
+            command_table.set("name", command.name.as_str())?;
+            command_table.set("description", command.description.as_str())?;
+
+            table.set(i + 1, command_table)?;
+        }
+
+        lua.pack_multi(table)
+    });
+}
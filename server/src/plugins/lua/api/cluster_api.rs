@@ -0,0 +1,44 @@
+use super::LuaApi;
+use packets::structures::ActorId;
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "get_cluster_area_hosts", |api_ctx, lua, params| {
+        let area_id: mlua::String = lua.unpack_multi(params)?;
+        let area_id_str = area_id.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        let hosts = net.get_cluster_area_hosts(area_id_str);
+
+        lua.pack_multi(hosts)
+    });
+
+    lua_api.add_dynamic_function("Net", "transfer_cluster_area", |api_ctx, lua, params| {
+        let (player_id, area_id, warp_option): (ActorId, mlua::String, Option<bool>) =
+            lua.unpack_multi(params)?;
+        let area_id_str = area_id.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        let warp = warp_option.unwrap_or_default();
+        let transferred = net.transfer_cluster_area(player_id, area_id_str, warp);
+
+        lua.pack_multi(transferred)
+    });
+
+    lua_api.add_dynamic_function("Net", "broadcast_cluster_event", |api_ctx, lua, params| {
+        let (name, data_option): (mlua::String, Option<mlua::String>) = lua.unpack_multi(params)?;
+        let name_str = name.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        let data = data_option
+            .as_ref()
+            .map(|data| data.as_bytes())
+            .unwrap_or_default();
+
+        net.broadcast_cluster_event(name_str, data);
+
+        lua.pack_multi(())
+    });
+}
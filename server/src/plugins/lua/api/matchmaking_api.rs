@@ -0,0 +1,87 @@
+use super::lua_errors::create_player_error;
+use super::LuaApi;
+use packets::structures::ActorId;
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "get_player_rating", |api_ctx, lua, params| {
+        let player_id: ActorId = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        let Some(player_rating) = net.get_player_rating(player_id) else {
+            return Err(create_player_error(player_id));
+        };
+
+        // same shape as the fields accepted by Net.open_ranked_season()
+        let recent_matches = lua.create_table()?;
+
+        for (i, result) in player_rating.recent_matches.iter().enumerate() {
+            let result_table = lua.create_table()?;
+            result_table.set("opponent_name", result.opponent_name.as_str())?;
+            result_table.set("won", result.won)?;
+            result_table.set("rating_delta", result.rating_delta)?;
+
+            recent_matches.raw_set(i + 1, result_table)?;
+        }
+
+        let table = lua.create_table()?;
+        table.set("rating", player_rating.rating)?;
+        table.set("matches_played", player_rating.matches_played)?;
+        table.set("recent_matches", recent_matches)?;
+
+        lua.pack_multi(table)
+    });
+
+    lua_api.add_dynamic_function("Net", "set_player_rating", |api_ctx, lua, params| {
+        let (player_id, rating, matches_played): (ActorId, i32, Option<u32>) =
+            lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        let Some(player_rating) = net.get_player_rating(player_id) else {
+            return Err(create_player_error(player_id));
+        };
+
+        let matches_played = matches_played.unwrap_or(player_rating.matches_played);
+        net.set_player_rating(player_id, rating, matches_played);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(
+        "Net",
+        "is_player_in_ranked_queue",
+        |api_ctx, lua, params| {
+            let player_id: ActorId = lua.unpack_multi(params)?;
+
+            let net = api_ctx.net_ref.borrow();
+
+            lua.pack_multi(net.is_player_in_ranked_queue(player_id))
+        },
+    );
+
+    lua_api.add_dynamic_function("Net", "get_flagged_ranked_matches", |api_ctx, lua, _| {
+        let net = api_ctx.net_ref.borrow();
+
+        let flagged_tables = lua.create_table()?;
+
+        for (i, flagged) in net.flagged_ranked_matches().iter().enumerate() {
+            let flagged_table = lua.create_table()?;
+            flagged_table.set("player_names", flagged.player_names.clone())?;
+            flagged_table.set("reason", flagged.reason)?;
+
+            flagged_tables.raw_set(i + 1, flagged_table)?;
+        }
+
+        lua.pack_multi(flagged_tables)
+    });
+
+    lua_api.add_dynamic_function("Net", "set_ranked_encounter", |api_ctx, lua, params| {
+        let package_path: Option<String> = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.set_ranked_encounter(package_path);
+
+        lua.pack_multi(())
+    });
+}
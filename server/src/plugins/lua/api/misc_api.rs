@@ -1,5 +1,6 @@
 use packets::address_parsing::{uri_decode_raw, uri_encode_raw};
 
+use super::lua_helpers::*;
 use super::LuaApi;
 
 pub fn inject_static(lua_api: &mut LuaApi) {
@@ -41,3 +42,30 @@ pub fn inject_static(lua_api: &mut LuaApi) {
         Ok(())
     });
 }
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "is_under_maintenance", |api_ctx, lua, _| {
+        let net = api_ctx.net_ref.borrow();
+
+        lua.pack_multi(net.is_under_maintenance())
+    });
+
+    lua_api.add_dynamic_function("Net", "start_maintenance", |api_ctx, lua, params| {
+        let (duration, safe_area_option): (f32, Option<mlua::String>) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        let safe_area = optional_lua_string_to_optional_str(&safe_area_option)?;
+        net.start_maintenance(duration, safe_area.map(String::from));
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "cancel_maintenance", |api_ctx, lua, _| {
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        net.cancel_maintenance();
+
+        lua.pack_multi(())
+    });
+}
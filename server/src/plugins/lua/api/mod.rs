@@ -1,17 +1,24 @@
+mod achievement_api;
 mod actor_property_animation;
 mod area_api;
 mod asset_api;
 mod async_api;
 mod bot_api;
+mod chat_api;
+mod cluster_api;
 mod logging_api;
 mod lua_errors;
 mod lua_helpers;
+mod matchmaking_api;
 mod misc_api;
 mod object_api;
+mod party_api;
 mod player_api;
 mod player_data_api;
+mod quest_api;
 mod sprite_api;
 mod synchronization_api;
+mod trade_api;
 mod widget_api;
 
 use crate::jobs::JobPromiseManager;
@@ -61,13 +68,20 @@ impl LuaApi {
 
         logging_api::inject_static(&mut lua_api);
 
+        achievement_api::inject_dynamic(&mut lua_api);
         area_api::inject_dynamic(&mut lua_api);
         asset_api::inject_dynamic(&mut lua_api);
+        chat_api::inject_dynamic(&mut lua_api);
         object_api::inject_dynamic(&mut lua_api);
+        party_api::inject_dynamic(&mut lua_api);
         player_api::inject_dynamic(&mut lua_api);
         player_data_api::inject_dynamic(&mut lua_api);
+        matchmaking_api::inject_dynamic(&mut lua_api);
+        quest_api::inject_dynamic(&mut lua_api);
+        trade_api::inject_dynamic(&mut lua_api);
         widget_api::inject_dynamic(&mut lua_api);
         bot_api::inject_dynamic(&mut lua_api);
+        cluster_api::inject_dynamic(&mut lua_api);
         sprite_api::inject_dynamic(&mut lua_api);
         synchronization_api::inject_dynamic(&mut lua_api);
 
@@ -75,6 +89,7 @@ impl LuaApi {
         async_api::inject_dynamic(&mut lua_api);
 
         misc_api::inject_static(&mut lua_api);
+        misc_api::inject_dynamic(&mut lua_api);
 
         lua_api
     }
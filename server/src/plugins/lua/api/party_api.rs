@@ -0,0 +1,61 @@
+use super::lua_errors::create_player_error;
+use super::LuaApi;
+use packets::structures::ActorId;
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "get_player_party", |api_ctx, lua, params| {
+        let player_id: ActorId = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        if net.get_player(player_id).is_none() {
+            return Err(create_player_error(player_id));
+        }
+
+        lua.pack_multi(net.get_player_party(player_id))
+    });
+
+    lua_api.add_dynamic_function("Net", "set_player_party", |api_ctx, lua, params| {
+        let (player_id, party_id): (ActorId, Option<String>) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.set_player_party(player_id, party_id);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "get_party_members", |api_ctx, lua, params| {
+        let party_id: mlua::String = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        let Some(members) = net.get_party_members(party_id.to_str()?) else {
+            return lua.pack_multi(mlua::Nil);
+        };
+
+        let id_iter = members.iter().enumerate().map(|(i, id)| (i + 1, *id));
+
+        lua.pack_multi(lua.create_table_from(id_iter)?)
+    });
+
+    lua_api.add_dynamic_function("Net", "invite_to_party", |api_ctx, lua, params| {
+        let (inviter_id, invitee_id): (ActorId, ActorId) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        if net.get_player(inviter_id).is_none() {
+            return Err(create_player_error(inviter_id));
+        }
+
+        lua.pack_multi(net.invite_to_party(inviter_id, invitee_id))
+    });
+
+    lua_api.add_dynamic_function("Net", "leave_party", |api_ctx, lua, params| {
+        let player_id: ActorId = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.leave_party(player_id);
+
+        lua.pack_multi(())
+    });
+}
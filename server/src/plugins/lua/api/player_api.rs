@@ -1,7 +1,9 @@
-use packets::structures::ActorId;
+use packets::structures::{ActorId, EncounterData, EncounterPreview, EncounterPreviewEnemy};
+use packets::MAX_NETPLAY_PLAYERS;
 
 use super::lua_errors::{create_area_error, create_player_error};
 use super::lua_helpers::*;
+use super::widget_api::{parse_rgb_table, parse_texture_animation_pair};
 use super::LuaApi;
 use crate::net::Direction;
 
@@ -277,6 +279,29 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function("Net", "play_music_for_player", |api_ctx, lua, params| {
+        let (player_id, asset_path, loops): (ActorId, mlua::String, Option<bool>) =
+            lua.unpack_multi(params)?;
+        let asset_path_str = asset_path.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        net.play_music_for_player(player_id, asset_path_str, loops.unwrap_or(true));
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function("Net", "play_ambience_for_player", |api_ctx, lua, params| {
+        let (player_id, asset_path): (ActorId, mlua::String) = lua.unpack_multi(params)?;
+        let asset_path_str = asset_path.to_str()?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        net.play_ambience_for_player(player_id, asset_path_str);
+
+        lua.pack_multi(())
+    });
+
     lua_api.add_dynamic_function(
         "Net",
         "exclude_object_for_player",
@@ -325,6 +350,35 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function(
+        "Net",
+        "set_object_minimap_marker_for_player",
+        |api_ctx, lua, params| {
+            let (player_id, object_id, visible): (ActorId, u32, bool) = lua.unpack_multi(params)?;
+
+            let mut net = api_ctx.net_ref.borrow_mut();
+
+            net.set_object_minimap_marker_for_player(player_id, object_id, visible);
+
+            lua.pack_multi(())
+        },
+    );
+
+    lua_api.add_dynamic_function(
+        "Net",
+        "set_actor_minimap_marker_for_player",
+        |api_ctx, lua, params| {
+            let (player_id, actor_id, visible): (ActorId, ActorId, bool) =
+                lua.unpack_multi(params)?;
+
+            let mut net = api_ctx.net_ref.borrow_mut();
+
+            net.set_actor_minimap_marker_for_player(player_id, actor_id, visible);
+
+            lua.pack_multi(())
+        },
+    );
+
     lua_api.add_dynamic_function("Net", "move_player_camera", |api_ctx, lua, params| {
         let (player_id, x, y, z, duration): (ActorId, f32, f32, f32, Option<f32>) =
             lua.unpack_multi(params)?;
@@ -463,7 +517,7 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
             ActorId,
             ActorId,
             Option<String>,
-            Option<mlua::Value>,
+            Option<EncounterData>,
         ) = lua.unpack_multi(params)?;
 
         let mut net = api_ctx.net_ref.borrow_mut();
@@ -477,16 +531,37 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
             }
         }
 
-        let data_string = data.map(|v| lua_value_to_string(v, "", 0));
-
-        net.initiate_netplay(&player_ids, package_path, data_string);
+        net.initiate_netplay(&player_ids, package_path, data, None);
 
         lua.pack_multi(())
     });
 
     lua_api.add_dynamic_function("Net", "_initiate_netplay", |api_ctx, lua, params| {
-        let (player_ids, package_path, data): (Vec<ActorId>, Option<String>, Option<mlua::Value>) =
-            lua.unpack_multi(params)?;
+        let (player_ids, package_path, data, boss_id): (
+            Vec<ActorId>,
+            Option<String>,
+            Option<EncounterData>,
+            Option<ActorId>,
+        ) = lua.unpack_multi(params)?;
+
+        if player_ids.len() > MAX_NETPLAY_PLAYERS {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Netplay supports up to {MAX_NETPLAY_PLAYERS} players, received {}.",
+                player_ids.len()
+            )));
+        }
+
+        if let Some(boss_id) = boss_id {
+            if package_path.is_none() {
+                return Err(mlua::Error::RuntimeError(String::from(
+                    "Boss battles require an encounter package.",
+                )));
+            }
+
+            if !player_ids.contains(&boss_id) {
+                return Err(create_player_error(boss_id));
+            }
+        }
 
         let mut net = api_ctx.net_ref.borrow_mut();
         let mut battle_tracker = api_ctx.battle_tracker_ref.borrow_mut();
@@ -497,9 +572,7 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
             }
         }
 
-        let data_string = data.map(|v| lua_value_to_string(v, "", 0));
-
-        net.initiate_netplay(&player_ids, package_path, data_string);
+        net.initiate_netplay(&player_ids, package_path, data, boss_id);
 
         lua.pack_multi(())
     });
@@ -520,18 +593,22 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
     });
 
     lua_api.add_dynamic_function("Net", "_initiate_encounter", |api_ctx, lua, params| {
-        let (player_id, package_id, data): (ActorId, mlua::String, Option<mlua::Value>) =
-            lua.unpack_multi(params)?;
+        let (player_id, package_id, data, preview_table): (
+            ActorId,
+            mlua::String,
+            Option<EncounterData>,
+            Option<mlua::Table>,
+        ) = lua.unpack_multi(params)?;
 
         let package_id_str = package_id.to_str()?;
+        let preview = preview_table.map(parse_encounter_preview).transpose()?;
 
         let mut net = api_ctx.net_ref.borrow_mut();
 
         if let Some(tracker) = api_ctx.battle_tracker_ref.borrow_mut().get_mut(&player_id) {
             tracker.push_back(api_ctx.script_index);
 
-            let data_string = data.map(|v| lua_value_to_string(v, "", 0));
-            net.initiate_encounter(player_id, package_id_str, data_string);
+            net.initiate_encounter(player_id, package_id_str, data, preview);
         }
 
         lua.pack_multi(())
@@ -640,3 +717,32 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 }
+
+fn parse_encounter_preview(table: mlua::Table) -> mlua::Result<EncounterPreview> {
+    let enemy_tables: Option<Vec<mlua::Table>> = table.get("enemies")?;
+    let mut enemies = Vec::new();
+
+    for enemy_table in enemy_tables.unwrap_or_default() {
+        enemies.push(EncounterPreviewEnemy {
+            name: enemy_table.get("name")?,
+            sprite: enemy_table
+                .get::<_, Option<mlua::Table>>("sprite")?
+                .map(parse_texture_animation_pair)
+                .transpose()?,
+        });
+    }
+
+    Ok(EncounterPreview {
+        enemies,
+        stakes: table
+            .get::<_, Option<String>>("stakes")?
+            .unwrap_or_default(),
+        declinable: table
+            .get::<_, Option<bool>>("declinable")?
+            .unwrap_or_default(),
+        fade_color: table
+            .get::<_, Option<mlua::Table>>("fade_color")?
+            .map(parse_rgb_table)
+            .transpose()?,
+    })
+}
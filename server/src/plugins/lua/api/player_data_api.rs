@@ -164,6 +164,32 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         }
     });
 
+    lua_api.add_dynamic_function("Net", "get_player_inventory", |api_ctx, lua, params| {
+        let player_id: ActorId = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        let Some(player_data) = &net.get_player_data(player_id) else {
+            return Err(create_player_error(player_id));
+        };
+
+        let inventory_table = lua.create_table()?;
+
+        let item_iter = (player_data.inventory.items())
+            .filter(|(_, count)| *count > 0)
+            .enumerate();
+
+        for (i, (id, count)) in item_iter {
+            let item_table = lua.create_table()?;
+            item_table.set("id", id.as_str())?;
+            item_table.set("count", *count)?;
+
+            inventory_table.raw_set(i + 1, item_table)?;
+        }
+
+        lua.pack_multi(inventory_table)
+    });
+
     lua_api.add_dynamic_function("Net", "give_player_item", |api_ctx, lua, params| {
         let (player_id, item_id, count): (ActorId, String, Option<isize>) =
             lua.unpack_multi(params)?;
@@ -175,6 +201,19 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function("Net", "take_player_item", |api_ctx, lua, params| {
+        let (player_id, item_id, count): (ActorId, String, Option<usize>) =
+            lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        if net.get_player_data(player_id).is_none() {
+            return Err(create_player_error(player_id));
+        }
+
+        lua.pack_multi(net.take_player_item(player_id, item_id, count.unwrap_or(1)))
+    });
+
     lua_api.add_dynamic_function("Net", "get_player_item_count", |api_ctx, lua, params| {
         let (player_id, item_id): (ActorId, mlua::String) = lua.unpack_multi(params)?;
         let item_id_str = item_id.to_str()?;
@@ -210,6 +249,7 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
             name: item_table.get("name")?,
             description: item_table.get("description")?,
             consumable: item_table.get("consumable").unwrap_or_default(),
+            metadata: item_table.get("metadata").unwrap_or_default(),
         };
 
         net.set_item(item_id, item);
@@ -217,6 +257,35 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function("Net", "get_item", |api_ctx, lua, params| {
+        let item_id: mlua::String = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        let Some(item) = net.item_registry().get(item_id.to_str()?) else {
+            return lua.pack_multi(mlua::Nil);
+        };
+
+        // same shape as the table accepted by Net.register_item()
+        let item_table = lua.create_table()?;
+        item_table.set("name", item.name.as_str())?;
+        item_table.set("description", item.description.as_str())?;
+        item_table.set("consumable", item.consumable)?;
+        item_table.set("metadata", item.metadata.clone())?;
+
+        lua.pack_multi(item_table)
+    });
+
+    lua_api.add_dynamic_function("Net", "get_item_ids", |api_ctx, lua, _| {
+        let net = api_ctx.net_ref.borrow();
+
+        let id_iter = (net.item_registry().keys())
+            .enumerate()
+            .map(|(i, id)| (i + 1, id.as_str()));
+
+        lua.pack_multi(lua.create_table_from(id_iter)?)
+    });
+
     lua_api.add_dynamic_function("Net", "get_item_name", |api_ctx, lua, params| {
         let item_id: mlua::String = lua.unpack_multi(params)?;
 
@@ -279,6 +348,28 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function("Net", "give_player_card_pack", |api_ctx, lua, params| {
+        let (player_id, name, card_tables): (ActorId, mlua::String, Vec<mlua::Table>) =
+            lua.unpack_multi(params)?;
+
+        let mut cards = Vec::with_capacity(card_tables.len());
+
+        for table in card_tables {
+            let package_id: mlua::String = table.get("package_id")?;
+            let code: mlua::String = table.get("code")?;
+
+            cards.push((
+                PackageId::from(package_id.to_str()?),
+                code.to_str()?.to_string(),
+            ));
+        }
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.give_player_card_pack(player_id, name.to_str()?.to_string(), cards);
+
+        lua.pack_multi(())
+    });
+
     lua_api.add_dynamic_function("Net", "get_player_block_count", |api_ctx, lua, params| {
         let (player_id, package_id, color_string): (ActorId, mlua::String, mlua::String) =
             lua.unpack_multi(params)?;
@@ -0,0 +1,68 @@
+use super::LuaApi;
+use packets::structures::{ActorId, Quest, QuestObjective};
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "update_player_quest", |api_ctx, lua, params| {
+        let (player_id, quest_table): (ActorId, mlua::Table) = lua.unpack_multi(params)?;
+
+        let objective_tables: Option<Vec<mlua::Table>> = quest_table.get("objectives")?;
+        let mut objectives = Vec::new();
+
+        for table in objective_tables.unwrap_or_default() {
+            objectives.push(QuestObjective {
+                description: table
+                    .get::<_, Option<String>>("description")?
+                    .unwrap_or_default(),
+                progress: table.get::<_, Option<u32>>("progress")?.unwrap_or_default(),
+                goal: table.get::<_, Option<u32>>("goal")?.unwrap_or(1),
+            });
+        }
+
+        let quest = Quest {
+            id: quest_table.get("id")?,
+            name: quest_table
+                .get::<_, Option<String>>("name")?
+                .unwrap_or_default(),
+            description: quest_table
+                .get::<_, Option<String>>("description")?
+                .unwrap_or_default(),
+            objectives,
+            completed: quest_table
+                .get::<_, Option<bool>>("completed")?
+                .unwrap_or_default(),
+        };
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.update_player_quest(player_id, quest);
+
+        lua.pack_multi(())
+    });
+
+    lua_api.add_dynamic_function(
+        "Net",
+        "set_player_quest_progress",
+        |api_ctx, lua, params| {
+            let (player_id, id, objective_index, progress): (ActorId, String, usize, u32) =
+                lua.unpack_multi(params)?;
+
+            // lua indices start at 1
+            let Some(objective_index) = objective_index.checked_sub(1) else {
+                return lua.pack_multi(());
+            };
+
+            let mut net = api_ctx.net_ref.borrow_mut();
+            net.set_player_quest_progress(player_id, id, objective_index, progress);
+
+            lua.pack_multi(())
+        },
+    );
+
+    lua_api.add_dynamic_function("Net", "remove_player_quest", |api_ctx, lua, params| {
+        let (player_id, id): (ActorId, String) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.remove_player_quest(player_id, id);
+
+        lua.pack_multi(())
+    });
+}
@@ -0,0 +1,34 @@
+use super::LuaApi;
+use packets::structures::ActorId;
+
+pub fn inject_dynamic(lua_api: &mut LuaApi) {
+    lua_api.add_dynamic_function("Net", "request_trade", |api_ctx, lua, params| {
+        let (player_id, target_id): (ActorId, ActorId) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        lua.pack_multi(net.request_trade(player_id, target_id))
+    });
+
+    lua_api.add_dynamic_function("Net", "is_player_trading", |api_ctx, lua, params| {
+        let player_id: ActorId = lua.unpack_multi(params)?;
+
+        let net = api_ctx.net_ref.borrow();
+
+        lua.pack_multi(net.is_player_trading(player_id))
+    });
+
+    lua_api.add_dynamic_function("Net", "cancel_trade", |api_ctx, lua, params| {
+        let (player_id, reason): (ActorId, Option<mlua::String>) = lua.unpack_multi(params)?;
+
+        let reason = match &reason {
+            Some(reason) => reason.to_str()?,
+            None => "The trade was cancelled.",
+        };
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.cancel_trade(player_id, reason);
+
+        lua.pack_multi(())
+    });
+}
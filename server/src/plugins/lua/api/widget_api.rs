@@ -2,7 +2,10 @@ use super::lua_helpers::*;
 use super::LuaApi;
 use crate::net::ShopItem;
 use packets::structures::ActorId;
-use packets::structures::{PackageId, TextStyleBlueprint, TextboxOptions, TextureAnimPathPair};
+use packets::structures::{
+    PackageId, RankedMatchResult, RankedSeasonInfo, RankedTier, TextStyleBlueprint, TextboxOptions,
+    TextureAnimPathPair,
+};
 
 #[allow(clippy::type_complexity)]
 pub fn inject_dynamic(lua_api: &mut LuaApi) {
@@ -304,6 +307,15 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function("Net", "open_ranked_season", |api_ctx, lua, params| {
+        let (player_id, table): (ActorId, mlua::Table) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+        net.open_ranked_season(player_id, table_to_ranked_season_info(table)?);
+
+        lua.pack_multi(())
+    });
+
     lua_api.add_dynamic_function("Net", "refer_server", |api_ctx, lua, params| {
         let (player_id, name, address): (ActorId, String, String) = lua.unpack_multi(params)?;
 
@@ -314,6 +326,16 @@ pub fn inject_dynamic(lua_api: &mut LuaApi) {
         lua.pack_multi(())
     });
 
+    lua_api.add_dynamic_function("Net", "notify_player", |api_ctx, lua, params| {
+        let (player_id, message): (ActorId, String) = lua.unpack_multi(params)?;
+
+        let mut net = api_ctx.net_ref.borrow_mut();
+
+        net.send_notification(player_id, message);
+
+        lua.pack_multi(())
+    });
+
     lua_api.add_dynamic_function("Net", "refer_package", |api_ctx, lua, params| {
         let (player_id, package_id_string): (ActorId, mlua::String) = lua.unpack_multi(params)?;
 
@@ -381,7 +403,9 @@ fn parse_textbox_options<'lua>(
     Ok(textbox_options)
 }
 
-fn parse_texture_animation_pair(table: mlua::Table) -> mlua::Result<TextureAnimPathPair<'static>> {
+pub(super) fn parse_texture_animation_pair(
+    table: mlua::Table,
+) -> mlua::Result<TextureAnimPathPair<'static>> {
     Ok(TextureAnimPathPair {
         texture: table.get::<_, String>("texture_path")?.into(),
         animation: table.get::<_, String>("animation_path")?.into(),
@@ -416,7 +440,7 @@ fn parse_text_style(table: mlua::Table) -> mlua::Result<TextStyleBlueprint> {
     })
 }
 
-fn parse_rgb_table(table: mlua::Table) -> mlua::Result<(u8, u8, u8)> {
+pub(super) fn parse_rgb_table(table: mlua::Table) -> mlua::Result<(u8, u8, u8)> {
     Ok((table.get("r")?, table.get("g")?, table.get("b")?))
 }
 
@@ -447,3 +471,52 @@ fn table_to_shop_item(item_table: mlua::Table) -> mlua::Result<ShopItem> {
         price_text,
     })
 }
+
+fn table_to_ranked_season_info(table: mlua::Table) -> mlua::Result<RankedSeasonInfo> {
+    let tier_tables: Option<Vec<mlua::Table>> = table.get("tiers")?;
+    let match_tables: Option<Vec<mlua::Table>> = table.get("recent_matches")?;
+
+    let mut tiers = Vec::new();
+
+    for tier_table in tier_tables.unwrap_or_default() {
+        tiers.push(RankedTier {
+            name: tier_table.get("name")?,
+            icon_texture_path: tier_table
+                .get::<_, Option<String>>("icon_texture_path")?
+                .unwrap_or_default(),
+            min_rating: tier_table.get("min_rating")?,
+        });
+    }
+
+    // the client expects the lowest tier first
+    tiers.sort_by_key(|tier| tier.min_rating);
+
+    let mut recent_matches = Vec::new();
+
+    for match_table in match_tables.unwrap_or_default() {
+        recent_matches.push(RankedMatchResult {
+            opponent_name: match_table.get("opponent_name")?,
+            won: match_table.get("won")?,
+            rating_delta: match_table.get("rating_delta")?,
+        });
+    }
+
+    Ok(RankedSeasonInfo {
+        season_name: table.get("season_name")?,
+        season_end_text: table
+            .get::<_, Option<String>>("season_end_text")?
+            .unwrap_or_default(),
+        rating: table.get("rating")?,
+        placement_matches_played: table
+            .get::<_, Option<u16>>("placement_matches_played")?
+            .unwrap_or_default(),
+        placement_matches_required: table
+            .get::<_, Option<u16>>("placement_matches_required")?
+            .unwrap_or_default(),
+        tiers,
+        recent_matches,
+        matchmaking: table
+            .get::<_, Option<bool>>("matchmaking")?
+            .unwrap_or_default(),
+    })
+}
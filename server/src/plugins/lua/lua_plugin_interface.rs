@@ -3,7 +3,7 @@ use crate::jobs::JobPromiseManager;
 use crate::net::{BattleStatistics, Net, WidgetTracker};
 use crate::plugins::PluginInterface;
 use mlua::Lua;
-use packets::structures::{ActorId, PackageId};
+use packets::structures::{ActorId, ChatChannel, PackageId, TradeOffer};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::VecDeque;
@@ -349,7 +349,13 @@ impl PluginInterface for LuaPluginInterface {
         prevent_default.get()
     }
 
-    fn handle_player_emote(&mut self, net: &mut Net, player_id: ActorId, emote_id: &str) -> bool {
+    fn handle_player_emote(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        emote_id: &str,
+        package_id: Option<&PackageId>,
+    ) -> bool {
         use std::cell::Cell;
         use std::rc::Rc;
 
@@ -369,6 +375,7 @@ impl PluginInterface for LuaPluginInterface {
                 let event = lua.create_table()?;
                 event.set("player_id", player_id)?;
                 event.set("emote", emote_id)?;
+                event.set("package_id", package_id.map(|id| id.as_str()))?;
                 event.set(
                     "prevent_default",
                     lua.create_function(move |_, _: ()| {
@@ -403,6 +410,200 @@ impl PluginInterface for LuaPluginInterface {
         );
     }
 
+    fn handle_chat_message(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        channel: ChatChannel,
+        message: String,
+    ) -> Option<String> {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let prevent_default = Rc::new(Cell::new(false));
+        let message = Rc::new(RefCell::new(message));
+
+        let (channel_name, recipient_id) = match channel {
+            ChatChannel::Area => ("area", None),
+            ChatChannel::Whisper(recipient_id) => ("whisper", Some(recipient_id)),
+            ChatChannel::Party => ("party", None),
+            ChatChannel::System => ("system", None),
+        };
+
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let prevent_default_reference = prevent_default.clone();
+                let message_reference = message.clone();
+
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("channel", channel_name)?;
+                event.set("recipient_id", recipient_id)?;
+                event.set("message", message.borrow().as_str())?;
+                event.set(
+                    "prevent_default",
+                    lua.create_function(move |_, _: ()| {
+                        prevent_default_reference.set(true);
+                        Ok(())
+                    })?,
+                )?;
+                event.set(
+                    "set_message",
+                    lua.create_function(move |_, text: String| {
+                        *message_reference.borrow_mut() = text;
+                        Ok(())
+                    })?,
+                )?;
+
+                callback.call(("chat_message", event))
+            },
+        );
+
+        if prevent_default.get() {
+            return None;
+        }
+
+        let message = message.take();
+        Some(message)
+    }
+
+    fn handle_party_invite(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        target_id: ActorId,
+    ) -> bool {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let prevent_default = Rc::new(Cell::new(false));
+
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let prevent_default_reference = prevent_default.clone();
+
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("target_id", target_id)?;
+                event.set(
+                    "prevent_default",
+                    lua.create_function(move |_, _: ()| {
+                        prevent_default_reference.set(true);
+                        Ok(())
+                    })?,
+                )?;
+
+                callback.call(("party_invite", event))
+            },
+        );
+
+        prevent_default.get()
+    }
+
+    fn handle_party_join(&mut self, net: &mut Net, player_id: ActorId, party_id: &str) {
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("party_id", party_id)?;
+
+                callback.call(("party_join", event))
+            },
+        );
+    }
+
+    fn handle_party_leave(&mut self, net: &mut Net, player_id: ActorId, party_id: &str) {
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("party_id", party_id)?;
+
+                callback.call(("party_leave", event))
+            },
+        );
+    }
+
+    fn handle_chat_command(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        command: &str,
+        args: &str,
+    ) {
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("command", command)?;
+                event.set("args", args)?;
+
+                callback.call(("chat_command", event))
+            },
+        );
+    }
+
+    fn handle_fast_travel_request(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        area_id: &str,
+        object_id: u32,
+    ) {
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("area_id", area_id)?;
+                event.set("object_id", object_id)?;
+
+                callback.call(("fast_travel_request", event))
+            },
+        );
+    }
+
     fn handle_object_interaction(
         &mut self,
         net: &mut Net,
@@ -772,6 +973,65 @@ impl PluginInterface for LuaPluginInterface {
         );
     }
 
+    fn handle_battle_deck_rejected(&mut self, net: &mut Net, player_id: ActorId, reason: &str) {
+        let tracker = self.battle_trackers.get(&player_id);
+
+        let Some(script_index) = tracker.and_then(|tracker| tracker.back().cloned()) else {
+            return;
+        };
+
+        handle_event(
+            &mut self.scripts,
+            &[script_index],
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let event = lua.create_table()?;
+                event.set("player_id", player_id)?;
+                event.set("reason", reason)?;
+
+                callback.call(("battle_deck_rejected", event))
+            },
+        );
+    }
+
+    fn handle_trade_completed(
+        &mut self,
+        net: &mut Net,
+        player_ids: [ActorId; 2],
+        offers: &[TradeOffer; 2],
+    ) {
+        // one event per player, describing what they gave and received
+        for side in 0..2 {
+            let partner_side = 1 - side;
+
+            handle_event(
+                &mut self.scripts,
+                &self.all_scripts,
+                &mut self.widget_trackers,
+                &mut self.battle_trackers,
+                &mut self.promise_manager,
+                &mut self.lua_api,
+                net,
+                |lua, callback| {
+                    let event = lua.create_table()?;
+                    event.set("player_id", player_ids[side])?;
+                    event.set("partner_id", player_ids[partner_side])?;
+                    event.set("given", create_trade_offer_table(lua, &offers[side])?)?;
+                    event.set(
+                        "received",
+                        create_trade_offer_table(lua, &offers[partner_side])?,
+                    )?;
+
+                    callback.call(("trade_completed", event))
+                },
+            );
+        }
+    }
+
     fn handle_battle_results(
         &mut self,
         net: &mut Net,
@@ -800,6 +1060,7 @@ impl PluginInterface for LuaPluginInterface {
                 event.set("score", battle_stats.score)?;
                 event.set("time", battle_stats.time)?;
                 event.set("ran", battle_stats.ran)?;
+                event.set("declined", battle_stats.declined)?;
                 event.set("emotion", battle_stats.emotion.as_str())?;
                 event.set("turns", battle_stats.turns)?;
 
@@ -869,9 +1130,70 @@ impl PluginInterface for LuaPluginInterface {
             },
         );
     }
+
+    fn handle_cluster_event(
+        &mut self,
+        net: &mut Net,
+        socket_address: std::net::SocketAddr,
+        name: &str,
+        data: &[u8],
+    ) {
+        let address_string = socket_address.to_string();
+
+        handle_event(
+            &mut self.scripts,
+            &self.all_scripts,
+            &mut self.widget_trackers,
+            &mut self.battle_trackers,
+            &mut self.promise_manager,
+            &mut self.lua_api,
+            net,
+            |lua, callback| {
+                let event = lua.create_table()?;
+                event.set("address", address_string.as_str())?;
+                event.set("name", name)?;
+                event.set("data", lua.create_string(data)?)?;
+
+                callback.call(("cluster_event", event))
+            },
+        );
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
+fn create_trade_offer_table<'lua>(
+    lua: &'lua Lua,
+    offer: &TradeOffer,
+) -> mlua::Result<mlua::Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("money", offer.money)?;
+
+    let mut card_tables = Vec::with_capacity(offer.cards.len());
+
+    for (package_id, code, count) in &offer.cards {
+        let card_table = lua.create_table()?;
+        card_table.set("package_id", package_id.as_str())?;
+        card_table.set("code", code.as_str())?;
+        card_table.set("count", *count)?;
+        card_tables.push(card_table);
+    }
+
+    table.set("cards", card_tables)?;
+
+    let mut item_tables = Vec::with_capacity(offer.items.len());
+
+    for (item_id, count) in &offer.items {
+        let item_table = lua.create_table()?;
+        item_table.set("id", item_id.as_str())?;
+        item_table.set("count", *count)?;
+        item_tables.push(item_table);
+    }
+
+    table.set("items", item_tables)?;
+
+    Ok(table)
+}
+
 fn handle_event<F>(
     scripts: &mut [Lua],
     event_listeners: &[usize],
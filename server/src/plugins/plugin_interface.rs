@@ -1,5 +1,5 @@
 use crate::net::{BattleStatistics, Net};
-use packets::structures::{ActorId, PackageId};
+use packets::structures::{ActorId, ChatChannel, PackageId, TradeOffer};
 
 pub trait PluginInterface {
     fn init(&mut self, net: &mut Net);
@@ -19,8 +19,40 @@ pub trait PluginInterface {
         texture_path: &str,
         animation_path: &str,
     ) -> bool;
-    fn handle_player_emote(&mut self, net: &mut Net, player_id: ActorId, emote_id: &str) -> bool;
+    fn handle_player_emote(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        emote_id: &str,
+        package_id: Option<&PackageId>,
+    ) -> bool;
     fn handle_custom_warp(&mut self, net: &mut Net, player_id: ActorId, tile_object_id: u32);
+    /// Returns the message to send, None if the message was blocked
+    fn handle_chat_message(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        channel: ChatChannel,
+        message: String,
+    ) -> Option<String>;
+    /// Returns true to prevent the invite
+    fn handle_party_invite(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        target_id: ActorId,
+    ) -> bool;
+    fn handle_party_join(&mut self, net: &mut Net, player_id: ActorId, party_id: &str);
+    fn handle_party_leave(&mut self, net: &mut Net, player_id: ActorId, party_id: &str);
+    /// Only called for commands registered through Net.register_chat_command
+    fn handle_chat_command(&mut self, net: &mut Net, player_id: ActorId, command: &str, args: &str);
+    fn handle_fast_travel_request(
+        &mut self,
+        net: &mut Net,
+        player_id: ActorId,
+        area_id: &str,
+        object_id: u32,
+    );
     fn handle_object_interaction(
         &mut self,
         net: &mut Net,
@@ -56,6 +88,13 @@ pub trait PluginInterface {
     fn handle_shop_purchase(&mut self, net: &mut Net, player_id: ActorId, item_id: &str);
     fn handle_shop_description_request(&mut self, net: &mut Net, player_id: ActorId, item_id: &str);
     fn handle_item_use(&mut self, net: &mut Net, player_id: ActorId, item_id: &str);
+    fn handle_battle_deck_rejected(&mut self, net: &mut Net, player_id: ActorId, reason: &str);
+    fn handle_trade_completed(
+        &mut self,
+        net: &mut Net,
+        player_ids: [ActorId; 2],
+        offers: &[TradeOffer; 2],
+    );
     fn handle_battle_results(
         &mut self,
         net: &mut Net,
@@ -68,4 +107,11 @@ pub trait PluginInterface {
         socket_address: std::net::SocketAddr,
         data: &[u8],
     );
+    fn handle_cluster_event(
+        &mut self,
+        net: &mut Net,
+        socket_address: std::net::SocketAddr,
+        name: &str,
+        data: &[u8],
+    );
 }
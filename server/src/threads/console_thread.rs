@@ -0,0 +1,96 @@
+use crate::threads::ThreadMessage;
+use flume::Sender;
+
+const HELP: &str = "
+  maintenance <SECONDS> [SAFE_AREA]  Shut down after a countdown, moving players to SAFE_AREA
+  maintenance cancel                 Cancel a pending shutdown";
+
+/// Reads commands from stdin
+pub fn create_console_thread(sender: Sender<ThreadMessage>) {
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+
+            match line.trim() {
+                "" => continue,
+                "help" => {
+                    log::info!("Commands:{HELP}");
+                    continue;
+                }
+                _ => {}
+            }
+
+            match parse_command(&line) {
+                Ok(message) => {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => log::warn!("{err}, use \"help\" to list commands"),
+            }
+        }
+    });
+}
+
+fn parse_command(line: &str) -> Result<ThreadMessage, String> {
+    let mut words = line.split_whitespace();
+
+    match words.next() {
+        Some("maintenance") => {}
+        Some(command) => return Err(format!("Unknown command {command:?}")),
+        None => return Err(String::from("Missing command")),
+    }
+
+    let message = match (words.next(), words.next()) {
+        (Some("cancel"), None) => ThreadMessage::CancelMaintenance,
+        (Some(duration), safe_area) => {
+            let duration = duration
+                .parse::<f32>()
+                .ok()
+                .filter(|duration| duration.is_finite() && *duration >= 0.0)
+                .ok_or_else(|| format!("Invalid duration {duration:?}"))?;
+
+            ThreadMessage::StartMaintenance {
+                duration,
+                safe_area: safe_area.map(String::from),
+            }
+        }
+        (None, _) => return Err(String::from("Missing duration")),
+    };
+
+    if words.next().is_some() {
+        return Err(String::from("Too many arguments"));
+    }
+
+    Ok(message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn maintenance_commands() {
+        assert!(matches!(
+            parse_command("maintenance 30"),
+            Ok(ThreadMessage::StartMaintenance { duration, safe_area: None }) if duration == 30.0
+        ));
+
+        assert!(matches!(
+            parse_command(" maintenance 5 lobby "),
+            Ok(ThreadMessage::StartMaintenance { safe_area: Some(area), .. }) if area == "lobby"
+        ));
+
+        assert!(matches!(
+            parse_command("maintenance cancel"),
+            Ok(ThreadMessage::CancelMaintenance)
+        ));
+
+        assert!(parse_command("maintenance").is_err());
+        assert!(parse_command("maintenance -1").is_err());
+        assert!(parse_command("maintenance cancel now").is_err());
+        assert!(parse_command("restart").is_err());
+    }
+}
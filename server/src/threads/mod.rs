@@ -1,5 +1,8 @@
 mod thread_message;
 pub use thread_message::*;
 
+mod console_thread;
+pub use console_thread::create_console_thread;
+
 mod listening_thread;
 pub use listening_thread::create_listening_thread;
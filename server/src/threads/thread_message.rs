@@ -26,6 +26,11 @@ pub enum ThreadMessage {
         socket_address: SocketAddr,
         promise: JobPromise,
     },
+    StartMaintenance {
+        duration: f32,
+        safe_area: Option<String>,
+    },
+    CancelMaintenance,
 }
 
 pub enum ListenerMessage {